@@ -0,0 +1,96 @@
+//! Frames of a row of overlapping spheres under a directional light and 8,
+//! then 16, colored point lights, drawn with the forward and the deferred
+//! pipeline. Forward lights every fragment that passes the depth test, so
+//! with spheres drawn back to front deferred should win as lights are
+//! added.
+//!
+//! Run with `cargo bench --bench deferred`; compare against a saved
+//! criterion baseline to see the effect of a change.
+
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+use russsty::prelude::*;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+/// Bands and slices of each sphere; it has `2 * SEGMENTS * (RINGS - 1)`
+/// faces.
+const RINGS: u32 = 32;
+const SEGMENTS: u32 = 64;
+/// Spheres in the row, each partly behind the one in front.
+const SPHERES: usize = 8;
+
+/// `count` colored lights circling the row of spheres.
+fn lights(count: usize) -> Vec<PointLight> {
+    (0..count)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            let position = Vec3::new(angle.cos() * 2.5, angle.sin() * 1.5, 1.0);
+            let hue = (i * 0x2F) as u32 & 0xFF;
+            PointLight {
+                color: 0xFF000000 | (hue << 16) | ((0xFF - hue) << 8) | 0x80,
+                intensity: 0.5,
+                ..PointLight::new(position, 6.0)
+            }
+        })
+        .collect()
+}
+
+/// Update and render `engine` with `pipeline` and `count` point lights.
+fn frame(engine: &mut Engine, pipeline: Pipeline, count: usize) -> impl FnMut(&mut Bencher) + '_ {
+    engine.set_pipeline(pipeline);
+    engine.set_point_lights(lights(count));
+    move |b| {
+        b.iter(|| {
+            engine.update();
+            engine.render();
+        })
+    }
+}
+
+fn bench_deferred(c: &mut Criterion) {
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    let mut model = Model::new("sphere");
+    model.add_mesh(Mesh::uv_sphere("sphere", 1.0, SEGMENTS, RINGS));
+    engine.set_model(model);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_camera_position(Vec3::new(0.0, 0.0, -4.0));
+
+    // Back to front, so every sphere's fragments pass the depth test
+    let handle = engine.mesh_handle("sphere", "sphere").unwrap();
+    let transforms: Vec<Transform> = (1..SPHERES)
+        .rev()
+        .map(|i| {
+            let mut transform = Transform::new();
+            transform.set_position_xyz(0.15 * i as f32, 0.0, 0.6 * i as f32);
+            transform
+        })
+        .collect();
+    engine.add_instances(handle, &transforms);
+
+    for pipeline in [Pipeline::Forward, Pipeline::Deferred] {
+        engine.set_pipeline(pipeline);
+        engine.set_point_lights(lights(8));
+        engine.update();
+        engine.render();
+        println!("{pipeline}: {} pixels lit", engine.stats().pixels_lit);
+    }
+
+    c.bench_function("forward_8_lights", frame(&mut engine, Pipeline::Forward, 8));
+    c.bench_function(
+        "deferred_8_lights",
+        frame(&mut engine, Pipeline::Deferred, 8),
+    );
+    c.bench_function(
+        "forward_16_lights",
+        frame(&mut engine, Pipeline::Forward, 16),
+    );
+    c.bench_function(
+        "deferred_16_lights",
+        frame(&mut engine, Pipeline::Deferred, 16),
+    );
+}
+
+criterion_group!(benches, bench_deferred);
+criterion_main!(benches);
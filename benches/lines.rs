@@ -0,0 +1,80 @@
+//! Wireframe line drawing with endpoints far off screen.
+//!
+//! Projected vertices of triangles that straddle the near plane can land tens
+//! of thousands of pixels outside the framebuffer. Without clipping, every
+//! one of those pixels is walked and rejected by the bounds check.
+//!
+//! Run with `cargo bench --bench lines`; compare against a saved criterion
+//! baseline to see the effect of a change.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use russsty::bench::Renderer;
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+const LINE_COUNT: usize = 1000;
+
+/// Deterministic xorshift so runs are comparable without a rand dependency.
+fn next(state: &mut u32) -> u32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 17;
+    *state ^= *state << 5;
+    *state
+}
+
+fn range(state: &mut u32, min: i32, max: i32) -> i32 {
+    min + (next(state) % (max - min) as u32) as i32
+}
+
+/// Lines with one endpoint on screen and one up to 50k pixels away.
+fn offscreen_lines() -> Vec<(i32, i32, i32, i32)> {
+    let mut state = 0x9E37_79B9;
+    (0..LINE_COUNT)
+        .map(|_| {
+            let x0 = range(&mut state, 0, WIDTH as i32);
+            let y0 = range(&mut state, 0, HEIGHT as i32);
+            let x1 = range(&mut state, -50_000, 50_000);
+            let y1 = range(&mut state, -50_000, 50_000);
+            (x0, y0, x1, y1)
+        })
+        .collect()
+}
+
+/// Lines fully on screen, for reference.
+fn onscreen_lines() -> Vec<(i32, i32, i32, i32)> {
+    let mut state = 0x85EB_CA6B;
+    (0..LINE_COUNT)
+        .map(|_| {
+            (
+                range(&mut state, 0, WIDTH as i32),
+                range(&mut state, 0, HEIGHT as i32),
+                range(&mut state, 0, WIDTH as i32),
+                range(&mut state, 0, HEIGHT as i32),
+            )
+        })
+        .collect()
+}
+
+fn bench_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("draw_line_bresenham");
+
+    for (name, lines) in [
+        ("offscreen_1000", offscreen_lines()),
+        ("onscreen_1000", onscreen_lines()),
+    ] {
+        let mut renderer = Renderer::new(WIDTH, HEIGHT);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                renderer.clear_depth();
+                for &(x0, y0, x1, y1) in &lines {
+                    renderer.draw_line_bresenham(x0, y0, 1.0, x1, y1, 2.0, black_box(0xFFFFFFFF));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lines);
+criterion_main!(benches);
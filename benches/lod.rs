@@ -0,0 +1,60 @@
+//! Frames of a 300k-face sphere close to and far from the camera, drawn in
+//! full and then with a 30k-face level of detail attached. Only the far
+//! frame should get cheaper.
+//!
+//! Run with `cargo bench --bench lod`; compare against a saved criterion
+//! baseline to see the effect of a change.
+
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+use russsty::lod::LodGroup;
+use russsty::prelude::*;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+/// Bands and slices of the sphere; it has `2 * SEGMENTS * (RINGS - 1)`
+/// faces.
+const RINGS: u32 = 275;
+const SEGMENTS: u32 = 548;
+/// Camera distances either side of the level's threshold.
+const LOD_DISTANCE: f32 = 10.0;
+const NEAR: f32 = 4.0;
+const FAR: f32 = 16.0;
+
+/// Update and render `engine` from `distance` in front of the sphere.
+fn frame(engine: &mut Engine, distance: f32) -> impl FnMut(&mut Bencher) + '_ {
+    engine.set_camera_position(Vec3::new(0.0, 0.0, -distance));
+    move |b| {
+        b.iter(|| {
+            engine.update();
+            engine.render();
+        })
+    }
+}
+
+fn bench_lod(c: &mut Criterion) {
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    let mut model = Model::new("sphere");
+    model.add_mesh(Mesh::uv_sphere("sphere", 1.0, SEGMENTS, RINGS));
+    engine.set_model(model);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+
+    let handle = engine.mesh_handle("sphere", "sphere").unwrap();
+    let mesh = &engine.model("sphere").unwrap().meshes()[0];
+    let group = LodGroup::generate(mesh, &[(LOD_DISTANCE, 0.1)]);
+    println!(
+        "{} faces, {} in the level of detail",
+        mesh.face_count(),
+        group.levels()[0].mesh.face_count()
+    );
+
+    c.bench_function("frame_near_full", frame(&mut engine, NEAR));
+    c.bench_function("frame_far_full", frame(&mut engine, FAR));
+    engine.set_lod_group(handle, group);
+    c.bench_function("frame_far_lod", frame(&mut engine, FAR));
+    c.bench_function("frame_near_lod", frame(&mut engine, NEAR));
+}
+
+criterion_group!(benches, bench_lod);
+criterion_main!(benches);
@@ -0,0 +1,94 @@
+//! Per-mesh matrix math in `Engine::update`: the world matrix and the
+//! normal matrix, built naively from separate constructors and general
+//! 4x4 products versus the analytic `Mat4::from_trs` and
+//! `Mat4::inverse_transpose_rs`; and transforming points, one at a time
+//! versus `Mat4::transform_points_batch`, and matrix products.
+//!
+//! Run with `cargo bench --bench matrices`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use russsty::prelude::{Mat4, Vec3, Vec4};
+
+const TRANSLATION: Vec3 = Vec3::new(1.0, -2.0, 7.5);
+const ROTATION: Vec3 = Vec3::new(0.4, -1.2, 2.5);
+const SCALE: Vec3 = Vec3::new(2.0, 0.5, 3.0);
+const POINTS: usize = 100_000;
+
+fn naive_rs(rotation: Vec3, scale: Vec3) -> Mat4 {
+    Mat4::rotation_x(rotation.x)
+        * Mat4::rotation_y(rotation.y)
+        * Mat4::rotation_z(rotation.z)
+        * Mat4::scaling(scale.x, scale.y, scale.z)
+}
+
+fn bench_matrices(c: &mut Criterion) {
+    let mut group = c.benchmark_group("world_and_normal_matrix");
+
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            let (t, r, s) = black_box((TRANSLATION, ROTATION, SCALE));
+            let world = Mat4::translation(t.x, t.y, t.z) * naive_rs(r, s);
+            let normal = naive_rs(r, s)
+                .inverse()
+                .unwrap_or(Mat4::identity())
+                .transpose();
+            (world, normal)
+        })
+    });
+
+    group.bench_function("analytic", |b| {
+        b.iter(|| {
+            let (t, r, s) = black_box((TRANSLATION, ROTATION, SCALE));
+            let world = Mat4::from_trs(t, r, s);
+            let normal = Mat4::inverse_transpose_rs(r, s);
+            (world, normal)
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_transforms(c: &mut Criterion) {
+    let world = Mat4::from_trs(TRANSLATION, ROTATION, SCALE);
+    let view = Mat4::look_at_lh(Vec3::new(0.0, 2.0, -10.0), Vec3::ZERO, Vec3::UP);
+    let points: Vec<Vec3> = (0..POINTS)
+        .map(|i| {
+            let t = i as f32 * 0.001;
+            Vec3::new(t.sin(), t.cos(), t)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("transform");
+
+    group.bench_function("single_point", |b| {
+        b.iter(|| black_box(world) * black_box(points[0]))
+    });
+
+    group.bench_function("points_one_at_a_time", |b| {
+        let mut out: Vec<Vec4> = Vec::with_capacity(POINTS);
+        b.iter(|| {
+            out.clear();
+            out.extend(
+                black_box(&points)
+                    .iter()
+                    .map(|&p| world * Vec4::from_vec3(p, 1.0)),
+            );
+            black_box(&out);
+        })
+    });
+
+    group.bench_function("points_batch", |b| {
+        let mut out = Vec::with_capacity(POINTS);
+        b.iter(|| {
+            world.transform_points_batch(black_box(&points), &mut out);
+            black_box(&out);
+        })
+    });
+
+    group.bench_function("mat_mul", |b| b.iter(|| black_box(view) * black_box(world)));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_matrices, bench_transforms);
+criterion_main!(benches);
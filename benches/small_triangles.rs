@@ -0,0 +1,53 @@
+//! Frames of a 160k-face sphere far enough away to cover only a few
+//! hundred pixels, drawn with every triangle and then with
+//! `Engine::set_min_triangle_area` dropping the specks that cover no pixel
+//! center. The picture is the same; the second should be cheaper.
+//!
+//! Run with `cargo bench --bench small_triangles`; compare against a saved
+//! criterion baseline to see the effect of a change.
+
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+use russsty::prelude::*;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+/// Bands and slices of the sphere; it has `2 * SEGMENTS * (RINGS - 1)`
+/// faces.
+const RINGS: u32 = 201;
+const SEGMENTS: u32 = 400;
+/// About 20 pixels of radius on screen.
+const DISTANCE: f32 = 40.0;
+
+/// Update and render `engine` with the minimum triangle area at `area`.
+fn frame(engine: &mut Engine, area: f32) -> impl FnMut(&mut Bencher) + '_ {
+    engine.set_min_triangle_area(area);
+    engine.update();
+    let stats = engine.stats();
+    println!(
+        "min area {area}: {} triangles drawn, {} too small",
+        stats.triangles_drawn, stats.triangles_too_small
+    );
+    move |b| {
+        b.iter(|| {
+            engine.update();
+            engine.render();
+        })
+    }
+}
+
+fn bench_small_triangles(c: &mut Criterion) {
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    let mut model = Model::new("sphere");
+    model.add_mesh(Mesh::uv_sphere("sphere", 1.0, SEGMENTS, RINGS));
+    engine.set_model(model);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_camera_position(Vec3::new(0.0, 0.0, -DISTANCE));
+
+    c.bench_function("frame_distant_sphere_all", frame(&mut engine, 0.0));
+    c.bench_function("frame_distant_sphere_min_area", frame(&mut engine, 1.0));
+}
+
+criterion_group!(benches, bench_small_triangles);
+criterion_main!(benches);
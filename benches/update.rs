@@ -0,0 +1,66 @@
+//! `Engine::update` on a dense mesh that is half outside the view.
+//!
+//! The mesh's bounds overlap the frustum, so no face is culled up front and
+//! every face reaches the clipper. Faces wholly inside or wholly outside
+//! the view should be trivially accepted or rejected without building a
+//! clip polygon; only the column of faces across the screen edge is
+//! clipped.
+//!
+//! Run with `cargo bench --bench update`; compare against a saved criterion
+//! baseline to see the effect of a change.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use russsty::prelude::*;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 720;
+/// Grid cells per side; the mesh has `2 * GRID * GRID` faces.
+const GRID: usize = 96;
+
+/// A `GRID` x `GRID` plane at z = 0, spanning x in [-2, 8] so its right
+/// side hangs off screen for the default camera, and y in [-2, 2].
+fn grid_obj() -> String {
+    let mut obj = String::new();
+    for j in 0..=GRID {
+        for i in 0..=GRID {
+            let x = -2.0 + 10.0 * i as f32 / GRID as f32;
+            let y = -2.0 + 4.0 * j as f32 / GRID as f32;
+            obj += &format!("v {x} {y} 0.0\n");
+        }
+    }
+    let index = |i: usize, j: usize| j * (GRID + 1) + i + 1;
+    for j in 0..GRID {
+        for i in 0..GRID {
+            let (a, b) = (index(i, j), index(i, j + 1));
+            let (c, d) = (index(i + 1, j + 1), index(i + 1, j));
+            obj += &format!("f {a} {b} {c}\nf {a} {c} {d}\n");
+        }
+    }
+    obj
+}
+
+fn bench_update(c: &mut Criterion) {
+    let path = std::env::temp_dir().join("russsty_bench_update_grid.obj");
+    std::fs::write(&path, grid_obj()).expect("write temp obj");
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.backface_culling = false;
+    engine
+        .add_model("grid", path.to_str().unwrap())
+        .expect("load grid");
+    std::fs::remove_file(&path).ok();
+
+    engine.update();
+    let stats = engine.stats();
+    println!(
+        "{} faces: {} accepted, {} rejected, {} clipped",
+        stats.faces_total,
+        stats.faces_trivially_accepted,
+        stats.faces_trivially_rejected,
+        stats.faces_clipped
+    );
+
+    c.bench_function("update_half_offscreen_grid", |b| b.iter(|| engine.update()));
+}
+
+criterion_group!(benches, bench_update);
+criterion_main!(benches);
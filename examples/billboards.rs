@@ -0,0 +1,114 @@
+//! Swirl 200 particle sprites around a cube without a window, timing each
+//! frame, and write a few frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --release --example billboards --no-default-features
+//! ```
+//!
+//! Frames land in `target/billboards/frame_00000.png` and onward.
+
+use std::f32::consts::TAU;
+use std::time::Instant;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 120;
+const SAVED_FRAMES: u32 = 4;
+const PARTICLES: usize = 200;
+
+const CUBE_OBJ: &str = "\
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// A soft white dot: opaque in the middle, fading to clear at the rim.
+fn dot_texture(size: u32) -> Result<Texture, russsty::Error> {
+    let half = size as f32 / 2.0;
+    Texture::from_fn(size, size, |x, y| {
+        let dx = (x as f32 + 0.5 - half) / half;
+        let dy = (y as f32 + 0.5 - half) / half;
+        let alpha = (1.0 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+        ((alpha * 255.0) as u32) << 24 | 0x00FFFFFF
+    })
+}
+
+/// Where particle `i` of `PARTICLES` is at time `t`: on a tilted ring that
+/// passes in front of and behind the cube.
+fn particle_position(i: usize, t: f32) -> Vec3 {
+    let phase = i as f32 / PARTICLES as f32;
+    let angle = phase * TAU + t;
+    let radius = 2.0 + 0.5 * (phase * 7.0 * TAU).sin();
+    Vec3::new(
+        radius * angle.cos(),
+        1.0 + 0.6 * (angle * 2.0).sin(),
+        radius * angle.sin(),
+    )
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_billboards_cube.obj");
+    std::fs::write(&obj_path, CUBE_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.add_model("cube", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+    engine
+        .model_mut("cube")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(0.0, 1.0, 0.0));
+    engine.set_camera(FpsCamera::looking_at(
+        Vec3::new(0.0, 3.0, -7.0),
+        Vec3::new(0.0, 1.0, 0.0),
+    ));
+    engine.set_billboard_texture(dot_texture(16)?);
+
+    let mut presenter = PngPresenter::new("target/billboards")?;
+    let started = Instant::now();
+    for frame in 0..FRAMES {
+        let t = frame as f32 / 30.0;
+        for i in 0..PARTICLES {
+            // Warm colors cycling around the ring
+            let color = if i % 3 == 0 { 0xFFFFC040 } else { 0xFFFF6020 };
+            engine.draw_billboard(particle_position(i, t), 0.25, None, color);
+        }
+        engine.update();
+        engine.render();
+        if frame % (FRAMES / SAVED_FRAMES) == 0 {
+            let (width, height) = engine.frame_size();
+            presenter.present(engine.frame_buffer(), width, height)?;
+        }
+    }
+    let per_frame = started.elapsed().as_secs_f32() / FRAMES as f32;
+
+    println!(
+        "{PARTICLES} sprites: {:.2} ms/frame ({:.0} FPS); wrote {} frames to {}",
+        per_frame * 1000.0,
+        1.0 / per_frame,
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
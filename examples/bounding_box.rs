@@ -0,0 +1,103 @@
+//! Plug a user-supplied rasterizer into the engine.
+//!
+//! `BoundingBoxRasterizer` doesn't fill triangles at all; it outlines each
+//! one's screen-space bounding box, which makes overdraw and clipping
+//! visible at a glance. Needs no SDL2:
+//!
+//! ```text
+//! cargo run --example bounding_box --no-default-features
+//! ```
+//!
+//! The frame lands in `target/bounding_box/frame_00000.png`.
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+
+const CUBE_OBJ: &str = "\
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// Outlines each triangle's bounding box at the depth of its nearest vertex.
+struct BoundingBoxRasterizer;
+
+impl Rasterizer for BoundingBoxRasterizer {
+    fn fill_triangle(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        color: u32,
+        _texture: Option<&Texture>,
+    ) {
+        let [a, b, c] = triangle.points;
+        let min_x = a.position.x.min(b.position.x).min(c.position.x) as i32;
+        let max_x = a.position.x.max(b.position.x).max(c.position.x) as i32;
+        let min_y = a.position.y.min(b.position.y).min(c.position.y) as i32;
+        let max_y = a.position.y.max(b.position.y).max(c.position.y) as i32;
+        let inv_w = (1.0 / a.w).max(1.0 / b.w).max(1.0 / c.w);
+
+        for x in min_x..=max_x {
+            buffer.set_pixel_with_depth(x, min_y, inv_w, color);
+            buffer.set_pixel_with_depth(x, max_y, inv_w, color);
+        }
+        for y in min_y..=max_y {
+            buffer.set_pixel_with_depth(min_x, y, inv_w, color);
+            buffer.set_pixel_with_depth(max_x, y, inv_w, color);
+        }
+    }
+
+    fn name(&self) -> &str {
+        "BoundingBox"
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_bounding_box_cube.obj");
+    std::fs::write(&obj_path, CUBE_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.add_model("cube", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+
+    engine
+        .model_mut("cube")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.4, 0.6, 0.0));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_custom_rasterizer(Box::new(BoundingBoxRasterizer));
+    engine.draw_debug_hud(true);
+
+    let mut presenter = PngPresenter::new("target/bounding_box")?;
+    engine.update();
+    engine.render();
+    let (width, height) = engine.frame_size();
+    presenter.present(engine.frame_buffer(), width, height)?;
+
+    println!(
+        "Rendered with {} to {}",
+        engine.rasterizer_name(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
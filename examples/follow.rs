@@ -0,0 +1,98 @@
+//! Chase a box driving in a circle with a `FollowCamera`, shake the view
+//! partway round as if it hit something, and write the frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --release --example follow --no-default-features
+//! ```
+//!
+//! Frames land in `target/follow/frame_00000.png` and onward. The camera
+//! lags behind the box and swings round after it; from the hit on, a
+//! `CameraShake` wobbles the view and dies down. In the demo, F2 shakes.
+
+use std::f32::consts::TAU;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 60;
+const DT: f32 = 1.0 / 20.0;
+/// Frame at which the box "hits" something.
+const HIT_FRAME: u32 = 30;
+
+/// Radius of the box's circle, and how long one lap takes in seconds.
+const CIRCLE_RADIUS: f32 = 6.0;
+const LAP_SECONDS: f32 = 6.0;
+
+// A box from -1 to 1, two triangles per side
+const BOX_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    let obj_path = std::env::temp_dir().join("russsty_follow_box.obj");
+    std::fs::write(&obj_path, BOX_OBJ)?;
+    engine.add_model("box", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+    engine.set_render_mode(RenderMode::FilledWireframe);
+
+    let mut follow = FollowCamera::new(Vec3::new(0.0, 3.0, -8.0), 3.0);
+    engine.set_camera_shake(CameraShake::new(0.06, 10.0, 2.5).with_seed(42));
+
+    let mut presenter = PngPresenter::new("target/follow")?;
+    for frame in 0..FRAMES {
+        // Counter-clockwise seen from above, facing along the circle and
+        // resting on the grid
+        let angle = frame as f32 * DT / LAP_SECONDS * TAU;
+        let mut target = Transform::new();
+        target
+            .set_position(Vec3::new(
+                angle.cos() * CIRCLE_RADIUS,
+                1.0,
+                angle.sin() * CIRCLE_RADIUS,
+            ))
+            .set_rotation_xyz(0.0, -angle, 0.0);
+        *engine.model_mut("box").unwrap().transform_mut() = target;
+
+        if frame == HIT_FRAME {
+            engine.camera_shake_mut().unwrap().trigger(1.0);
+        }
+        if let Some(camera) = engine.camera_mut().as_fps_mut() {
+            follow.update(camera, &target, DT);
+        }
+
+        engine.update_with_dt(DT);
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
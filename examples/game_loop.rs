@@ -0,0 +1,169 @@
+//! Bounce a spinning cube with a fixed-timestep simulation, drawn at two
+//! very different frame rates, and show both runs land in the same place
+//! at the same tick. A few frames of the slow run are written to PNG,
+//! smoothed by interpolating between ticks.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --example game_loop --no-default-features
+//! ```
+//!
+//! Frames land in `target/game_loop/frame_00000.png` and onward.
+
+use std::f32::consts::FRAC_PI_2;
+use std::time::Duration;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const TICK_RATE: f64 = 50.0;
+/// Seconds of simulation per run.
+const SECONDS: u32 = 3;
+/// Ticks between printed samples.
+const SAMPLE_EVERY: u64 = 25;
+const GRAVITY: f32 = -9.8;
+/// Share of its speed the cube keeps on each bounce.
+const RESTITUTION: f32 = 0.8;
+
+const CUBE_OBJ: &str = "\
+v -0.5 -0.5 -0.5
+v -0.5 0.5 -0.5
+v 0.5 0.5 -0.5
+v 0.5 -0.5 -0.5
+v -0.5 -0.5 0.5
+v -0.5 0.5 0.5
+v 0.5 0.5 0.5
+v 0.5 -0.5 0.5
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// Everything the update and render closures share.
+struct Demo {
+    engine: Engine,
+    /// Height of the cube's center at the last two ticks
+    height: f32,
+    previous_height: f32,
+    velocity: f32,
+    /// `(tick, height)` every `SAMPLE_EVERY` ticks
+    samples: Vec<(u64, f32)>,
+    ticks: u64,
+}
+
+impl Demo {
+    fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let obj_path = std::env::temp_dir().join("russsty_game_loop_cube.obj");
+        std::fs::write(&obj_path, CUBE_OBJ)?;
+        let mut engine = Engine::new(WIDTH, HEIGHT);
+        engine.add_model("cube", obj_path.to_str().unwrap())?;
+        std::fs::remove_file(&obj_path).ok();
+        engine
+            .model_mut("cube")
+            .unwrap()
+            .set_animator(Animator::new().spin(Vec3::UP, FRAC_PI_2));
+        engine.camera_mut().set_position(Vec3::new(0.0, 2.0, -7.0));
+        Ok(Self {
+            engine,
+            height: 3.0,
+            previous_height: 3.0,
+            velocity: 0.0,
+            samples: Vec::new(),
+            ticks: 0,
+        })
+    }
+
+    /// One fixed step: fall, bounce off the ground, spin.
+    fn tick(&mut self, dt: f32) {
+        self.previous_height = self.height;
+        self.velocity += GRAVITY * dt;
+        self.height += self.velocity * dt;
+        if self.height < 0.5 {
+            self.height = 0.5;
+            self.velocity = -self.velocity * RESTITUTION;
+        }
+        self.engine.animate(dt);
+
+        self.ticks += 1;
+        if self.ticks.is_multiple_of(SAMPLE_EVERY) {
+            self.samples.push((self.ticks, self.height));
+        }
+    }
+
+    /// Draw the cube `alpha` of the way from the last tick to the current.
+    fn draw(&mut self, alpha: f32) {
+        let height = self.previous_height + (self.height - self.previous_height) * alpha;
+        self.engine
+            .model_mut("cube")
+            .unwrap()
+            .transform_mut()
+            .set_position(Vec3::new(0.0, height, 0.0));
+        self.engine.update();
+        self.engine.render();
+    }
+}
+
+/// Simulate `SECONDS` seconds drawn at `fps`, timed by a stand-in clock
+/// that advances exactly one frame per frame. With `save`, every `n`th
+/// frame goes to the presenter.
+fn run(
+    fps: u32,
+    mut save: Option<(&mut PngPresenter, u32)>,
+) -> Result<Demo, Box<dyn std::error::Error>> {
+    let mut demo = Demo::new()?;
+    let mut game_loop = GameLoop::new(TICK_RATE);
+    let frame_time = Duration::from_secs(1) / fps;
+    for frame in 0..SECONDS * fps {
+        game_loop.advance(frame_time, &mut demo, Demo::tick, Demo::draw);
+        if let Some((presenter, every)) = save.as_mut() {
+            if frame.is_multiple_of(*every) {
+                let (width, height) = demo.engine.frame_size();
+                presenter.present(demo.engine.frame_buffer(), width, height)?;
+            }
+        }
+    }
+    println!(
+        "{fps:>3} FPS: {} frames, {} ticks, last frame {:.2} ms updating, {:.2} ms rendering",
+        SECONDS * fps,
+        game_loop.ticks(),
+        game_loop.update_time().as_secs_f64() * 1000.0,
+        game_loop.render_time().as_secs_f64() * 1000.0
+    );
+    Ok(demo)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut presenter = PngPresenter::new("target/game_loop")?;
+    let slow = run(20, Some((&mut presenter, 10)))?;
+    let fast = run(200, None)?;
+
+    println!("tick   height @ 20 FPS   height @ 200 FPS");
+    for ((tick, a), (_, b)) in slow.samples.iter().zip(&fast.samples) {
+        println!("{tick:>4}   {a:>15.4}   {b:>16.4}");
+    }
+    let same = slow.samples == fast.samples
+        && slow.engine.model("cube").unwrap().transform().rotation()
+            == fast.engine.model("cube").unwrap().transform().rotation();
+    println!(
+        "{}; wrote {} frames to {}",
+        if same {
+            "Both runs match tick for tick"
+        } else {
+            "The runs DIFFER"
+        },
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Draw the light's direction as an arrow from the origin, plus the world
+//! axes, as depth-tested 3D lines around a model, and write an orbit
+//! around it to PNG.
+//!
+//! Needs no SDL2. With no arguments a generated box is drawn; pass an OBJ
+//! (and optionally its texture) to show that instead:
+//!
+//! ```text
+//! cargo run --release --example gizmos --no-default-features
+//! cargo run --release --example gizmos --no-default-features -- assets/crab.obj assets/crab.png
+//! ```
+//!
+//! Frames land in `target/gizmos/frame_00000.png` and onward. The lines
+//! start inside the model, which hides them until they come out of it.
+
+use std::f32::consts::TAU;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 8;
+
+const ARROW_COLOR: u32 = 0xFFFFD700;
+const AXIS_COLORS: [u32; 3] = [0xFFFF4040, 0xFF40FF40, 0xFF4080FF];
+
+// A box from -1 to 1, two triangles per side
+const BOX_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+
+    match args.next() {
+        Some(obj_path) => {
+            engine.add_model("model", &obj_path)?;
+            if let Some(texture_path) = args.next() {
+                let texture = Texture::from_file(&texture_path)?;
+                engine.model_mut("model").unwrap().set_texture(texture);
+                engine.set_texture_mode(TextureMode::Modulate);
+            }
+        }
+        None => {
+            let obj_path = std::env::temp_dir().join("russsty_gizmos_box.obj");
+            std::fs::write(&obj_path, BOX_OBJ)?;
+            engine.add_model("model", obj_path.to_str().unwrap())?;
+            std::fs::remove_file(&obj_path).ok();
+        }
+    }
+    let (min, max) = engine
+        .model("model")
+        .unwrap()
+        .bounding_box()
+        .ok_or("model has no meshes")?;
+    let radius = (max - min).magnitude() * 0.5;
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_light_direction(Vec3::new(0.6, -0.5, 0.8));
+    let light = engine.light_direction().normalize();
+
+    let mut presenter = PngPresenter::new("target/gizmos")?;
+    for frame in 0..FRAMES {
+        let angle = frame as f32 / FRAMES as f32 * TAU;
+        let eye = Vec3::new(angle.sin(), 0.6, -angle.cos()) * (radius * 3.0);
+        engine.set_camera(FpsCamera::looking_at(eye, Vec3::ZERO));
+
+        // Queued afresh every frame, like billboards
+        let reach = radius * 1.5;
+        engine.draw_arrow_3d(Vec3::ZERO, light * reach, ARROW_COLOR);
+        engine.draw_lines_3d(&[
+            (Vec3::ZERO, Vec3::RIGHT * reach, AXIS_COLORS[0]),
+            (Vec3::ZERO, Vec3::UP * reach, AXIS_COLORS[1]),
+            (Vec3::ZERO, Vec3::FORWARD * reach, AXIS_COLORS[2]),
+        ]);
+
+        engine.update();
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Render a spinning cube without a window and write the frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --example headless --no-default-features
+//! ```
+//!
+//! Frames land in `target/headless/frame_00000.png` and onward.
+
+use std::f32::consts::FRAC_PI_2;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const FRAMES: u32 = 8;
+
+const CUBE_OBJ: &str = "\
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_headless_cube.obj");
+    std::fs::write(&obj_path, CUBE_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.add_model("cube", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+
+    // Sit the cube on the ground grid and spin it a quarter turn per second,
+    // sampled at 4 frames per second
+    let cube = engine.model_mut("cube").unwrap();
+    cube.transform_mut().set_position(Vec3::new(0.0, 1.0, 0.0));
+    cube.set_animator(Animator::new().spin(Vec3::UP, FRAC_PI_2));
+    engine.camera_mut().set_position(Vec3::new(0.0, 2.0, -7.0));
+
+    let mut presenter = PngPresenter::new("target/headless")?;
+    for _ in 0..FRAMES {
+        engine.update_with_dt(0.25);
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
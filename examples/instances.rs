@@ -0,0 +1,121 @@
+//! Tumble a field of 500 asteroids, all instances of one mesh, without a
+//! window, and write a few frames to PNG with the stats overlay.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --release --example instances --no-default-features
+//! ```
+//!
+//! Frames land in `target/instances/frame_00000.png` and onward.
+
+use std::f32::consts::TAU;
+use std::time::Instant;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 120;
+const SAVED_FRAMES: u32 = 4;
+const ASTEROIDS: usize = 500;
+
+// An octahedron: a passable rock once scaled unevenly
+const ROCK_OBJ: &str = "\
+o rock
+v 1.0 0.0 0.0
+v -1.0 0.0 0.0
+v 0.0 1.0 0.0
+v 0.0 -1.0 0.0
+v 0.0 0.0 1.0
+v 0.0 0.0 -1.0
+f 1 3 5
+f 3 2 5
+f 2 4 5
+f 4 1 5
+f 3 1 6
+f 2 3 6
+f 4 2 6
+f 1 4 6
+";
+
+/// Cheap deterministic noise in [0, 1) for scattering the field.
+fn hash(i: usize, salt: u32) -> f32 {
+    let mut x = (i as u32).wrapping_mul(0x9E37_79B9) ^ salt.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x2C1B_3C6D);
+    x ^= x >> 12;
+    (x & 0xFFFF) as f32 / 65536.0
+}
+
+/// Asteroid `i`'s resting place: a thick ring around the origin.
+fn scatter(i: usize) -> Transform {
+    let angle = hash(i, 1) * TAU;
+    let radius = 6.0 + hash(i, 2) * 8.0;
+    let mut transform = Transform::new();
+    transform
+        .set_position(Vec3::new(
+            radius * angle.cos(),
+            (hash(i, 3) - 0.5) * 3.0,
+            radius * angle.sin(),
+        ))
+        .set_rotation(Vec3::new(hash(i, 4), hash(i, 5), hash(i, 6)) * TAU)
+        .set_scale(Vec3::new(
+            0.2 + hash(i, 7) * 0.3,
+            0.2 + hash(i, 8) * 0.2,
+            0.2 + hash(i, 9) * 0.3,
+        ));
+    transform
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_instances_rock.obj");
+    std::fs::write(&obj_path, ROCK_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.add_model("field", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+    let rocks = engine
+        .mesh_handle("field", "rock")
+        .ok_or("rock mesh missing")?;
+    let transforms: Vec<Transform> = (0..ASTEROIDS).map(scatter).collect();
+    engine.add_instances(rocks, &transforms);
+
+    engine.set_camera(FpsCamera::looking_at(
+        Vec3::new(0.0, 6.0, -18.0),
+        Vec3::ZERO,
+    ));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.draw_debug_hud(true);
+
+    let mut presenter = PngPresenter::new("target/instances")?;
+    let started = Instant::now();
+    let dt = 1.0 / 30.0;
+    for frame in 0..FRAMES {
+        // Every rock tumbles at its own rate
+        for (i, rock) in engine.instances_mut(rocks).unwrap().iter_mut().enumerate() {
+            let spin = Vec3::new(hash(i, 10), hash(i, 11), hash(i, 12)) * (2.0 * dt);
+            rock.rotate(spin);
+        }
+        engine.update();
+        engine.render();
+        if frame % (FRAMES / SAVED_FRAMES) == 0 {
+            let (width, height) = engine.frame_size();
+            presenter.present(engine.frame_buffer(), width, height)?;
+        }
+    }
+    let per_frame = started.elapsed().as_secs_f32() / FRAMES as f32;
+
+    let stats = engine.stats();
+    println!(
+        "{} mesh, {} instances ({} culled): {:.2} ms/frame ({:.0} FPS); wrote {} frames to {}",
+        stats.meshes_total,
+        stats.instances_total,
+        stats.instances_culled,
+        per_frame * 1000.0,
+        1.0 / per_frame,
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Render a flat brick wall lit through a normal map while the light
+//! circles it, and write the frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --example normal_map --no-default-features
+//! ```
+//!
+//! Frames land in `target/normal_map/frame_00000.png` and onward. The quad
+//! is two triangles; the mortar grooves and beveled brick edges are all in
+//! the normal map, and their shading swings round with the light.
+
+use std::f32::consts::TAU;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const FRAMES: u32 = 12;
+
+// A wall facing the default camera, with the brick pattern repeated twice
+// across it
+const WALL_OBJ: &str = "\
+v -2.0 -1.5 0.0
+v -2.0 1.5 0.0
+v 2.0 1.5 0.0
+v 2.0 -1.5 0.0
+vt 0.0 0.0
+vt 0.0 1.5
+vt 2.0 1.5
+vt 2.0 0.0
+vn 0.0 0.0 -1.0
+f 1/1/1 2/2/1 3/3/1
+f 1/1/1 3/3/1 4/4/1
+";
+
+const MAP_SIZE: u32 = 128;
+const BRICK_WIDTH: u32 = 64;
+const BRICK_HEIGHT: u32 = 32;
+const MORTAR: f32 = 3.0;
+const BEVEL: f32 = 4.0;
+
+/// Height of the brick pattern at texel (x, y): 0 in the mortar, rising
+/// over the bevel to 1 on the brick faces. Every other row is offset by
+/// half a brick.
+fn brick_height(x: u32, y: u32) -> f32 {
+    let (x, y) = (x % MAP_SIZE, y % MAP_SIZE);
+    let row = y / BRICK_HEIGHT;
+    let x = (x + row % 2 * BRICK_WIDTH / 2) % BRICK_WIDTH;
+    let y = y % BRICK_HEIGHT;
+    // Distance into the brick from its nearest edge
+    let edge = (x as f32 + 0.5)
+        .min(BRICK_WIDTH as f32 - x as f32 - 0.5)
+        .min(y as f32 + 0.5)
+        .min(BRICK_HEIGHT as f32 - y as f32 - 0.5);
+    ((edge - MORTAR) / BEVEL).clamp(0.0, 1.0)
+}
+
+/// Tangent-space normal map of [`brick_height`], by central differences.
+///
+/// Texture rows run down while `v` runs up, so the row slope enters the
+/// green (bitangent) channel with its sign flipped back.
+fn brick_normal_map() -> Result<Texture, russsty::Error> {
+    const STRENGTH: f32 = 2.0;
+    let wrap = |c: i64| c.rem_euclid(MAP_SIZE as i64) as u32;
+    Texture::from_fn(MAP_SIZE, MAP_SIZE, |x, y| {
+        let h = |dx: i64, dy: i64| brick_height(wrap(x as i64 + dx), wrap(y as i64 + dy));
+        let du = (h(1, 0) - h(-1, 0)) / 2.0;
+        let d_row = (h(0, 1) - h(0, -1)) / 2.0;
+        let n = Vec3::new(-du * STRENGTH, d_row * STRENGTH, 1.0).normalize();
+        let channel = |c: f32| ((c * 0.5 + 0.5) * 255.0).round() as u32;
+        0xFF000000 | (channel(n.x) << 16) | (channel(n.y) << 8) | channel(n.z)
+    })
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_normal_map_wall.obj");
+    std::fs::write(&obj_path, WALL_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    engine.add_model("wall", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_texture_mode(TextureMode::NormalMapped);
+    engine.set_normal_map(brick_normal_map()?);
+
+    let mut presenter = PngPresenter::new("target/normal_map")?;
+    for frame in 0..FRAMES {
+        // Raking light, circling the wall once over the frames
+        let angle = frame as f32 / FRAMES as f32 * TAU;
+        engine.set_light_direction(Vec3::new(angle.cos(), angle.sin(), 0.6));
+        engine.render_offscreen();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//! Stream a plasma effect into a cube's texture every frame, and write the
+//! frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --example plasma --no-default-features
+//! ```
+//!
+//! Frames land in `target/plasma/frame_00000.png` and onward. The pattern
+//! is computed into one reused buffer and copied into the bound texture
+//! in place, so the loop allocates nothing per frame.
+
+use std::f32::consts::TAU;
+
+use russsty::colors::from_hsv;
+use russsty::prelude::*;
+
+const WIDTH: u32 = 320;
+const HEIGHT: u32 = 240;
+const FRAMES: u32 = 16;
+const FRAME_DT: f32 = 1.0 / 8.0;
+
+const TEXTURE_SIZE: u32 = 64;
+
+// A cube with the whole texture on every face
+const CUBE_OBJ: &str = "\
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+vt 0.0 0.0
+vt 0.0 1.0
+vt 1.0 1.0
+vt 1.0 0.0
+f 1/1 2/2 3/3 4/4
+f 8/1 7/2 6/3 5/4
+f 4/1 3/2 7/3 8/4
+f 5/1 6/2 2/3 1/4
+f 2/1 6/2 7/3 3/4
+f 5/1 1/2 4/3 8/4
+";
+
+/// Classic sum-of-sines plasma at `time` seconds, written over `pixels`.
+fn plasma(pixels: &mut [u32], time: f32) {
+    let size = TEXTURE_SIZE as f32;
+    for (i, pixel) in pixels.iter_mut().enumerate() {
+        let x = (i as u32 % TEXTURE_SIZE) as f32 / size;
+        let y = (i as u32 / TEXTURE_SIZE) as f32 / size;
+        let (cx, cy) = (x - 0.5 + 0.3 * (time * 0.7).sin(), y - 0.5);
+        let value = (x * 10.0 + time).sin()
+            + (y * 8.0 - time * 1.3).sin()
+            + ((x + y) * 6.0 + time * 0.5).sin()
+            + ((cx * cx + cy * cy).sqrt() * 12.0 - time * 2.0).sin();
+        // value is in [-4, 4]; map one swing onto the hue circle
+        *pixel = from_hsv((value / 8.0 + 0.5 + time * 0.1).fract() * 360.0, 0.8, 1.0);
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_plasma_cube.obj");
+    std::fs::write(&obj_path, CUBE_OBJ)?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.add_model("cube", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+    engine
+        .model_mut("cube")
+        .unwrap()
+        .set_animator(Animator::new().spin(Vec3::new(0.3, 1.0, 0.0), TAU / 8.0));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine.set_texture_mode(TextureMode::Modulate);
+    engine.set_texture(Texture::new(TEXTURE_SIZE, TEXTURE_SIZE)?);
+
+    let mut pixels = vec![0; (TEXTURE_SIZE * TEXTURE_SIZE) as usize];
+    let mut presenter = PngPresenter::new("target/plasma")?;
+    for frame in 0..FRAMES {
+        plasma(&mut pixels, frame as f32 * FRAME_DT);
+        engine.texture_mut().unwrap().update_from_argb(&pixels)?;
+
+        engine.update_with_dt(FRAME_DT);
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Draw a model filled with its collision box over it in wireframe, using
+//! per-mesh render modes, and write a turntable to PNG.
+//!
+//! Needs no SDL2. With no arguments a generated rock is drawn; pass an OBJ
+//! (and optionally its texture) to box that instead:
+//!
+//! ```text
+//! cargo run --release --example proxy --no-default-features
+//! cargo run --release --example proxy --no-default-features -- assets/crab.obj assets/crab.png
+//! ```
+//!
+//! Frames land in `target/proxy/frame_00000.png` and onward. The last one
+//! is drawn with `Engine::set_render_mode_override`, as the demo's number
+//! keys do, which puts every mesh in the same mode.
+
+use std::f32::consts::TAU;
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 8;
+
+// An octahedron: a passable rock once scaled unevenly
+const ROCK_OBJ: &str = "\
+v 1.0 0.0 0.0
+v -1.0 0.0 0.0
+v 0.0 1.0 0.0
+v 0.0 -1.0 0.0
+v 0.0 0.0 1.0
+v 0.0 0.0 -1.0
+f 1 3 5
+f 3 2 5
+f 2 4 5
+f 4 1 5
+f 3 1 6
+f 2 3 6
+f 4 2 6
+f 1 4 6
+";
+
+// The box from -1 to 1, two triangles per side
+const CUBE_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// Load the OBJ text `obj` as model `name` through a temp file.
+fn generated(name: &str, obj: &str) -> Result<Model, Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join(format!("russsty_proxy_{name}.obj"));
+    std::fs::write(&obj_path, obj)?;
+    let model = Model::from_obj(name, obj_path.to_str().unwrap());
+    std::fs::remove_file(&obj_path).ok();
+    Ok(model?)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut model = match args.next() {
+        Some(obj_path) => {
+            let mut model = Model::from_obj("model", &obj_path)?;
+            if let Some(texture_path) = args.next() {
+                model.set_texture(Texture::from_file(&texture_path)?);
+            }
+            model
+        }
+        None => {
+            let mut model = generated("model", ROCK_OBJ)?;
+            model.transform_mut().set_scale(Vec3::new(1.5, 0.8, 1.0));
+            model
+        }
+    };
+
+    // A box mesh stretched over the model's bounds, drawn as lines only
+    let (min, max) = model.bounding_box().ok_or("model has no meshes")?;
+    let mut proxy = generated("proxy", CUBE_OBJ)?
+        .mesh_by_index(0)
+        .ok_or("cube has no mesh")?
+        .clone();
+    proxy
+        .transform_mut()
+        .set_position((min + max) * 0.5)
+        .set_scale((max - min) * 0.5);
+    proxy.set_render_mode(RenderMode::Wireframe);
+    model.add_mesh(proxy);
+    model.set_animator(Animator::new().spin(Vec3::UP, TAU / FRAMES as f32));
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    let textured = model.texture().is_some();
+    engine.set_model(model);
+    if textured {
+        engine.set_texture_mode(TextureMode::Modulate);
+    }
+
+    // The rock's stretch makes it about 1.5x its bounds
+    let radius = (max - min).magnitude() * 0.75;
+    let center = (min + max) * 0.5;
+    engine.set_camera(FpsCamera::looking_at(
+        center + Vec3::new(0.0, radius * 0.8, -radius * 2.5),
+        center,
+    ));
+    // The default for meshes without their own mode
+    engine.set_render_mode(RenderMode::Filled);
+
+    let mut presenter = PngPresenter::new("target/proxy")?;
+    for frame in 0..FRAMES {
+        if frame == FRAMES - 1 {
+            engine.set_render_mode_override(Some(RenderMode::FilledWireframe));
+        }
+        engine.update_with_dt(1.0);
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Cel-shade a model with a three-band toon shader written outside the
+//! crate, through `Engine::set_custom_shader`, ink its silhouette, and write
+//! a turntable to PNG.
+//!
+//! Needs no SDL2. With no arguments a generated torus is drawn; pass an OBJ
+//! (and optionally its texture) to shade that instead:
+//!
+//! ```text
+//! cargo run --release --example toon --no-default-features
+//! cargo run --release --example toon --no-default-features -- assets/crab.obj assets/crab.png
+//! ```
+//!
+//! Frames land in `target/toon/frame_00000.png` and onward.
+
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::fmt::Write as _;
+
+use russsty::colors::{from_rgb_f32, unpack_color, with_alpha_of};
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 8;
+
+/// Quantizes diffuse light into three flat bands, like a cartoon.
+struct ToonShader {
+    /// Unit vector pointing toward the light.
+    to_light: Vec3,
+    /// Light level of each band, brightest first.
+    bands: [f32; 3],
+    /// `N·L` above which each of the first two bands applies.
+    thresholds: [f32; 2],
+}
+
+impl ToonShader {
+    fn new(light_direction: Vec3) -> Self {
+        Self {
+            to_light: -light_direction.normalize(),
+            bands: [1.0, 0.6, 0.3],
+            thresholds: [0.6, 0.2],
+        }
+    }
+
+    fn band(&self, n_dot_l: f32) -> f32 {
+        if n_dot_l > self.thresholds[0] {
+            self.bands[0]
+        } else if n_dot_l > self.thresholds[1] {
+            self.bands[1]
+        } else {
+            self.bands[2]
+        }
+    }
+}
+
+impl PixelShader for ToonShader {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let level = self.band(fragment.normal().dot(self.to_light));
+        // The mesh's texture if it has one, else the unlit fill color
+        match fragment.sample_texture() {
+            Some(texel) => {
+                let (r, g, b) = unpack_color(texel);
+                with_alpha_of(from_rgb_f32([r * level, g * level, b * level]), texel)
+            }
+            None => from_rgb_f32(fragment.color().map(|c| c * level)),
+        }
+    }
+}
+
+/// A torus around the y axis as OBJ text, with smooth normals.
+fn torus_obj(major: f32, minor: f32, rings: usize, sides: usize) -> String {
+    let mut obj = String::new();
+    for i in 0..rings {
+        let (sin_u, cos_u) = (i as f32 / rings as f32 * TAU).sin_cos();
+        for j in 0..sides {
+            let (sin_v, cos_v) = (j as f32 / sides as f32 * TAU).sin_cos();
+            let normal = Vec3::new(cos_u * cos_v, sin_v, sin_u * cos_v);
+            let p = Vec3::new(cos_u * major, 0.0, sin_u * major) + normal * minor;
+            writeln!(obj, "v {} {} {}", p.x, p.y, p.z).unwrap();
+            writeln!(obj, "vn {} {} {}", normal.x, normal.y, normal.z).unwrap();
+        }
+    }
+    let index = |i: usize, j: usize| (i % rings) * sides + (j % sides) + 1;
+    for i in 0..rings {
+        for j in 0..sides {
+            let [a, b, c, d] = [
+                index(i, j),
+                index(i + 1, j),
+                index(i + 1, j + 1),
+                index(i, j + 1),
+            ];
+            writeln!(obj, "f {a}//{a} {c}//{c} {b}//{b}").unwrap();
+            writeln!(obj, "f {a}//{a} {d}//{d} {c}//{c}").unwrap();
+        }
+    }
+    obj
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+
+    match args.next() {
+        Some(obj_path) => {
+            engine.add_model("model", &obj_path)?;
+            if let Some(texture_path) = args.next() {
+                let texture = Texture::from_file(&texture_path)?;
+                engine.model_mut("model").unwrap().set_texture(texture);
+            }
+        }
+        None => {
+            let obj_path = std::env::temp_dir().join("russsty_toon_torus.obj");
+            std::fs::write(&obj_path, torus_obj(1.2, 0.5, 48, 24))?;
+            engine.add_model("model", obj_path.to_str().unwrap())?;
+            std::fs::remove_file(&obj_path).ok();
+            engine
+                .model_mut("model")
+                .unwrap()
+                .transform_mut()
+                .set_rotation(Vec3::new(0.6, 0.0, 0.0));
+        }
+    }
+
+    // Frame whatever was loaded
+    let (min, max) = engine
+        .model("model")
+        .unwrap()
+        .bounding_box()
+        .ok_or("model has no meshes")?;
+    let center = (min + max) * 0.5;
+    let radius = (max - min).magnitude() * 0.5;
+    engine.set_camera(FpsCamera::looking_at(
+        center + Vec3::new(0.0, radius * 0.8, -radius * 2.5),
+        center,
+    ));
+
+    // The shader lights the surface itself, so hand it unlit colors
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_custom_shader(Some(Box::new(ToonShader::new(Vec3::new(-0.5, -0.7, 0.5)))));
+    engine.set_silhouette_outline(Some(SilhouetteOutline::default()));
+    engine
+        .model_mut("model")
+        .unwrap()
+        .set_animator(Animator::new().spin(Vec3::UP, FRAC_PI_2));
+
+    let mut presenter = PngPresenter::new("target/toon")?;
+    for _ in 0..FRAMES {
+        engine.update_with_dt(0.5);
+        engine.render();
+        let (width, height) = engine.frame_size();
+        presenter.present(engine.frame_buffer(), width, height)?;
+    }
+
+    println!(
+        "Wrote {} frames to {}",
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Ripple a 10,000-vertex plane with `y = sin(x * k + t)` every frame, and
+//! write the frames to PNG.
+//!
+//! Needs no SDL2:
+//!
+//! ```text
+//! cargo run --release --example wave --no-default-features
+//! ```
+//!
+//! Frames land in `target/wave/frame_00000.png` and onward. Each frame
+//! moves the vertices with `Mesh::map_positions`, which recomputes the
+//! normals so the Gouraud lighting follows the crests.
+
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+use russsty::prelude::*;
+
+const WIDTH: u32 = 640;
+const HEIGHT: u32 = 480;
+const FRAMES: u32 = 60;
+const SAVED_FRAMES: u32 = 6;
+const FRAME_DT: f32 = 1.0 / 30.0;
+
+/// Vertices along each side of the plane, 100 x 100 in all.
+const GRID: usize = 100;
+/// Half the plane's side length.
+const HALF_SIZE: f32 = 4.0;
+/// Wave number and height.
+const K: f32 = 2.0;
+const AMPLITUDE: f32 = 0.3;
+/// Crests travel at this many radians per second.
+const SPEED: f32 = 3.0;
+
+/// A flat `GRID` x `GRID` plane in y = 0 as OBJ text, two triangles per
+/// cell.
+fn plane_obj() -> String {
+    let mut obj = String::from("o wave\n");
+    let step = 2.0 * HALF_SIZE / (GRID - 1) as f32;
+    for row in 0..GRID {
+        for col in 0..GRID {
+            let x = -HALF_SIZE + col as f32 * step;
+            let z = -HALF_SIZE + row as f32 * step;
+            writeln!(obj, "v {x} 0 {z}").unwrap();
+        }
+    }
+    // OBJ indices count from 1
+    let index = |row: usize, col: usize| row * GRID + col + 1;
+    for row in 0..GRID - 1 {
+        for col in 0..GRID - 1 {
+            let (a, b) = (index(row, col), index(row, col + 1));
+            let (c, d) = (index(row + 1, col + 1), index(row + 1, col));
+            writeln!(obj, "f {a} {d} {c}\nf {a} {c} {b}").unwrap();
+        }
+    }
+    obj
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let obj_path = std::env::temp_dir().join("russsty_wave_plane.obj");
+    std::fs::write(&obj_path, plane_obj())?;
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.draw_grid = false;
+    engine.add_model("wave", obj_path.to_str().unwrap())?;
+    std::fs::remove_file(&obj_path).ok();
+    engine
+        .model_mut("wave")
+        .unwrap()
+        .mesh_mut("wave")
+        .ok_or("wave mesh missing")?
+        .set_cull_mode(CullMode::None);
+
+    engine.set_camera(FpsCamera::looking_at(Vec3::new(0.0, 4.0, -7.0), Vec3::ZERO));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_light_direction(Vec3::new(0.5, -1.0, 0.8));
+
+    let mut presenter = PngPresenter::new("target/wave")?;
+    let mut deforming = Duration::ZERO;
+    for frame in 0..FRAMES {
+        let t = frame as f32 * FRAME_DT * SPEED;
+        let mesh = engine.model_mut("wave").unwrap().mesh_mut("wave").unwrap();
+        let started = Instant::now();
+        mesh.map_positions(|_, p| Vec3::new(p.x, AMPLITUDE * (p.x * K + t).sin(), p.z));
+        deforming += started.elapsed();
+
+        engine.update_with_dt(FRAME_DT);
+        engine.render();
+        if frame % (FRAMES / SAVED_FRAMES) == 0 {
+            let (width, height) = engine.frame_size();
+            presenter.present(engine.frame_buffer(), width, height)?;
+        }
+    }
+
+    println!(
+        "{} vertices: {:.2} ms/frame moving them and their normals; wrote {} frames to {}",
+        GRID * GRID,
+        deforming.as_secs_f64() * 1000.0 / FRAMES as f64,
+        presenter.frames_written(),
+        presenter.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,16 @@
+//! The demo from `src/demo.rs` on the winit + softbuffer backend.
+//!
+//! ```text
+//! cargo run --example winit --no-default-features --features winit-window
+//! ```
+
+use russsty::window::{WinitWindow, WINDOW_HEIGHT, WINDOW_WIDTH};
+use russsty::Error;
+
+#[path = "../src/demo.rs"]
+mod demo;
+
+fn main() -> Result<(), Error> {
+    let window = WinitWindow::new("Russsty", WINDOW_WIDTH, WINDOW_HEIGHT)?;
+    demo::run(window)
+}
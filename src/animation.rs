@@ -0,0 +1,552 @@
+//! Time-based animation of transforms and cameras.
+//!
+//! An [`Animator`] drives a [`Transform`] from elapsed time rather than frame
+//! count, so motion runs at the same speed regardless of frame rate. It
+//! supports a constant spin and keyframed position/rotation tracks:
+//!
+//! ```ignore
+//! let animator = Animator::new()
+//!     .spin(Vec3::UP, std::f32::consts::FRAC_PI_2) // 90°/sec around +Y
+//!     .position_track(
+//!         KeyframeTrack::new(true)
+//!             .key(0.0, Vec3::ZERO)
+//!             .key(1.0, Vec3::new(0.0, 2.0, 0.0))
+//!             .key(2.0, Vec3::ZERO),
+//!     );
+//! engine.model_mut("crab").unwrap().set_animator(animator);
+//! ```
+//!
+//! Animators attached to models and meshes are advanced by
+//! `Engine::update_with_dt`, or `Engine::animate` on a fixed timestep. A [`CameraPath`] flies the camera through
+//! keyframed poses for recorded fly-throughs; it's advanced separately, by
+//! `Engine::update_path`.
+
+use crate::math::vec3::Vec3;
+use crate::transform::Transform;
+
+/// A single keyframe: a value reached at `time` seconds into the track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe {
+    pub time: f32,
+    pub value: Vec3,
+}
+
+/// A sequence of keyframes sampled with linear interpolation.
+///
+/// Keys are kept sorted by time. Before the first key the track holds the
+/// first value; past the last key it either holds the last value or, when
+/// looping, wraps back to the start.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyframeTrack {
+    keys: Vec<Keyframe>,
+    looping: bool,
+}
+
+impl KeyframeTrack {
+    /// Create an empty track.
+    pub fn new(looping: bool) -> Self {
+        Self {
+            keys: Vec::new(),
+            looping,
+        }
+    }
+
+    /// Add a key at `time` seconds, keeping keys sorted.
+    pub fn key(mut self, time: f32, value: Vec3) -> Self {
+        let index = self.keys.partition_point(|k| k.time <= time);
+        self.keys.insert(index, Keyframe { time, value });
+        self
+    }
+
+    pub fn keys(&self) -> &[Keyframe] {
+        &self.keys
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Time of the last key, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Sample the track at `time` seconds. Returns `None` for an empty track.
+    pub fn sample(&self, time: f32) -> Option<Vec3> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+
+        let time = if self.looping && last.time > first.time {
+            first.time + (time - first.time).rem_euclid(last.time - first.time)
+        } else {
+            time
+        };
+
+        if time <= first.time {
+            return Some(first.value);
+        }
+        if time >= last.time {
+            return Some(last.value);
+        }
+
+        // First key strictly after `time`; the one before it starts the segment
+        let next = self.keys.partition_point(|k| k.time <= time);
+        let a = self.keys[next - 1];
+        let b = self.keys[next];
+        let t = (time - a.time) / (b.time - a.time);
+        Some(a.value + (b.value - a.value) * t)
+    }
+}
+
+/// Drives a [`Transform`] from elapsed time.
+///
+/// - **Spin**: rotation advances by `axis * radians_per_sec * dt`. Rotations
+///   are Euler angles, so the spin is exact for the principal axes and a
+///   per-angle rate for anything else.
+/// - **Position track**: overrides the position each step.
+/// - **Rotation track**: overrides the rotation each step, with any spin
+///   added on top.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Animator {
+    spin: Option<(Vec3, f32)>,
+    position_track: Option<KeyframeTrack>,
+    rotation_track: Option<KeyframeTrack>,
+    time: f32,
+    paused: bool,
+}
+
+impl Animator {
+    /// Create an animator that does nothing until configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spin at a constant rate around `axis`.
+    pub fn spin(mut self, axis: Vec3, radians_per_sec: f32) -> Self {
+        self.spin = Some((axis.normalize(), radians_per_sec));
+        self
+    }
+
+    /// Drive the position from a keyframe track.
+    pub fn position_track(mut self, track: KeyframeTrack) -> Self {
+        self.position_track = Some(track);
+        self
+    }
+
+    /// Drive the rotation (Euler angles in radians) from a keyframe track.
+    pub fn rotation_track(mut self, track: KeyframeTrack) -> Self {
+        self.rotation_track = Some(track);
+        self
+    }
+
+    /// Seconds of animation time elapsed so far.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Restart tracks from time zero.
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advance by `dt` seconds and apply the result to `transform`.
+    pub fn advance(&mut self, dt: f32, transform: &mut Transform) {
+        if self.paused {
+            return;
+        }
+        self.time += dt;
+
+        if let Some(position) = self
+            .position_track
+            .as_ref()
+            .and_then(|t| t.sample(self.time))
+        {
+            transform.set_position(position);
+        }
+
+        match self
+            .rotation_track
+            .as_ref()
+            .and_then(|t| t.sample(self.time))
+        {
+            Some(rotation) => {
+                let spin = self
+                    .spin
+                    .map_or(Vec3::ZERO, |(axis, rate)| axis * (rate * self.time));
+                transform.set_rotation(rotation + spin);
+            }
+            None => {
+                if let Some((axis, rate)) = self.spin {
+                    transform.rotate(axis * (rate * dt));
+                }
+            }
+        }
+    }
+}
+
+/// A camera pose reached at `time` seconds into a [`CameraPath`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraKeyframe {
+    pub time: f32,
+    pub position: Vec3,
+    /// Point the camera looks at.
+    pub target: Vec3,
+}
+
+/// A fly-through: camera positions and look-at targets at given times,
+/// smoothed with Catmull-Rom splines so the camera glides through each key
+/// instead of turning sharply there.
+///
+/// Tangents are scaled by the time between keys, so unevenly spaced keys
+/// still give continuous velocity. A looping path should end on a copy of
+/// its first key; the spline then wraps around the seam so the loop is
+/// smooth as well. Drive it with `Engine::set_camera_path` and
+/// `Engine::update_path`:
+///
+/// ```ignore
+/// let path = CameraPath::new(true)
+///     .key(0.0, Vec3::new(0.0, 2.0, -8.0), Vec3::ZERO)
+///     .key(4.0, Vec3::new(8.0, 3.0, 0.0), Vec3::ZERO)
+///     .key(8.0, Vec3::new(0.0, 2.0, 8.0), Vec3::ZERO)
+///     .key(12.0, Vec3::new(0.0, 2.0, -8.0), Vec3::ZERO);
+/// engine.set_camera_path(path);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraPath {
+    keys: Vec<CameraKeyframe>,
+    looping: bool,
+    time: f32,
+}
+
+impl CameraPath {
+    /// Create an empty path.
+    pub fn new(looping: bool) -> Self {
+        Self {
+            keys: Vec::new(),
+            looping,
+            time: 0.0,
+        }
+    }
+
+    /// Add a key at `time` seconds, keeping keys sorted.
+    pub fn key(mut self, time: f32, position: Vec3, target: Vec3) -> Self {
+        let index = self.keys.partition_point(|k| k.time <= time);
+        self.keys.insert(
+            index,
+            CameraKeyframe {
+                time,
+                position,
+                target,
+            },
+        );
+        self
+    }
+
+    pub fn keys(&self) -> &[CameraKeyframe] {
+        &self.keys
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Time of the last key, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    /// Seconds of path time elapsed so far.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Restart from time zero.
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+    }
+
+    /// Whether a non-looping path has reached its last key.
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.time >= self.duration()
+    }
+
+    /// Advance by `dt` seconds and return the new `(position, target)`.
+    /// `None` for an empty path.
+    pub fn advance(&mut self, dt: f32) -> Option<(Vec3, Vec3)> {
+        self.time += dt;
+        self.sample(self.time)
+    }
+
+    /// The `(position, target)` at `time` seconds. Before the first key the
+    /// path holds the first pose; past the last it holds the last or, when
+    /// looping, wraps back to the start. `None` for an empty path.
+    pub fn sample(&self, time: f32) -> Option<(Vec3, Vec3)> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+
+        let period = last.time - first.time;
+        let time = if self.looping && period > 0.0 {
+            first.time + (time - first.time).rem_euclid(period)
+        } else {
+            time
+        };
+
+        if time <= first.time {
+            return Some((first.position, first.target));
+        }
+        if time >= last.time {
+            return Some((last.position, last.target));
+        }
+
+        // First key strictly after `time`; the one before it starts the segment
+        let next = self.keys.partition_point(|k| k.time <= time);
+        let a = self.keys[next - 1];
+        let b = self.keys[next];
+        let before = self.neighbor_before(next - 1);
+        let after = self.neighbor_after(next);
+
+        let span = b.time - a.time;
+        let s = (time - a.time) / span;
+        let curve = |value: fn(&CameraKeyframe) -> Vec3| {
+            let start_tangent = tangent(&before, &b, value);
+            let end_tangent = tangent(&a, &after, value);
+            hermite(
+                value(&a),
+                start_tangent * span,
+                value(&b),
+                end_tangent * span,
+                s,
+            )
+        };
+        Some((curve(|k| k.position), curve(|k| k.target)))
+    }
+
+    /// The key before `keys[index]` for its tangent: the previous key,
+    /// across the seam when looping, or the key itself at an open end.
+    fn neighbor_before(&self, index: usize) -> CameraKeyframe {
+        let len = self.keys.len();
+        if index > 0 {
+            self.keys[index - 1]
+        } else if self.looping && len > 2 {
+            // The last key doubles the first, so wrap to the one before it
+            let mut key = self.keys[len - 2];
+            key.time -= self.keys[len - 1].time - self.keys[0].time;
+            key
+        } else {
+            self.keys[index]
+        }
+    }
+
+    /// The key after `keys[index]` for its tangent; see
+    /// [`CameraPath::neighbor_before`].
+    fn neighbor_after(&self, index: usize) -> CameraKeyframe {
+        let len = self.keys.len();
+        if index + 1 < len {
+            self.keys[index + 1]
+        } else if self.looping && len > 2 {
+            let mut key = self.keys[1];
+            key.time += self.keys[len - 1].time - self.keys[0].time;
+            key
+        } else {
+            self.keys[index]
+        }
+    }
+}
+
+/// Catmull-Rom tangent, in units per second, at the key between `before`
+/// and `after`.
+fn tangent(
+    before: &CameraKeyframe,
+    after: &CameraKeyframe,
+    value: fn(&CameraKeyframe) -> Vec3,
+) -> Vec3 {
+    let dt = after.time - before.time;
+    if dt > 0.0 {
+        (value(after) - value(before)) / dt
+    } else {
+        Vec3::ZERO
+    }
+}
+
+/// Cubic Hermite curve from `p0` to `p1` with end tangents `m0`, `m1`
+/// (per unit of `s`), at `s` in [0, 1].
+fn hermite(p0: Vec3, m0: Vec3, p1: Vec3, m1: Vec3, s: f32) -> Vec3 {
+    let s2 = s * s;
+    let s3 = s2 * s;
+    p0 * (2.0 * s3 - 3.0 * s2 + 1.0)
+        + m0 * (s3 - 2.0 * s2 + s)
+        + p1 * (-2.0 * s3 + 3.0 * s2)
+        + m1 * (s3 - s2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn approx_eq(a: Vec3, b: Vec3) -> bool {
+        (a - b).magnitude() < EPSILON
+    }
+
+    #[test]
+    fn spin_rate_is_independent_of_step_size() {
+        let mut coarse = Transform::new();
+        let mut fine = Transform::new();
+        let mut a = Animator::new().spin(Vec3::UP, FRAC_PI_2);
+        let mut b = a.clone();
+
+        // One second at 30 FPS and at 144 FPS
+        for _ in 0..30 {
+            a.advance(1.0 / 30.0, &mut coarse);
+        }
+        for _ in 0..144 {
+            b.advance(1.0 / 144.0, &mut fine);
+        }
+
+        assert!((coarse.rotation().y - FRAC_PI_2).abs() < EPSILON);
+        assert!((fine.rotation().y - FRAC_PI_2).abs() < EPSILON);
+    }
+
+    #[test]
+    fn track_interpolates_linearly_and_clamps() {
+        let track = KeyframeTrack::new(false)
+            .key(1.0, Vec3::new(2.0, 0.0, 0.0))
+            .key(0.0, Vec3::ZERO);
+
+        assert!(approx_eq(track.sample(-1.0).unwrap(), Vec3::ZERO));
+        assert!(approx_eq(
+            track.sample(0.25).unwrap(),
+            Vec3::new(0.5, 0.0, 0.0)
+        ));
+        assert!(approx_eq(
+            track.sample(5.0).unwrap(),
+            Vec3::new(2.0, 0.0, 0.0)
+        ));
+        assert_eq!(KeyframeTrack::new(false).sample(0.0), None);
+    }
+
+    #[test]
+    fn looping_track_wraps() {
+        let track = KeyframeTrack::new(true)
+            .key(0.0, Vec3::ZERO)
+            .key(2.0, Vec3::new(0.0, 4.0, 0.0));
+
+        assert!(approx_eq(
+            track.sample(2.5).unwrap(),
+            Vec3::new(0.0, 1.0, 0.0)
+        ));
+        assert!(approx_eq(
+            track.sample(5.0).unwrap(),
+            Vec3::new(0.0, 2.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn rotation_track_combines_with_spin() {
+        let mut transform = Transform::new();
+        let mut animator = Animator::new()
+            .spin(Vec3::UP, 1.0)
+            .rotation_track(KeyframeTrack::new(false).key(0.0, Vec3::new(0.5, 0.0, 0.0)));
+
+        animator.advance(0.5, &mut transform);
+        animator.advance(0.5, &mut transform);
+
+        assert!(approx_eq(transform.rotation(), Vec3::new(0.5, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn paused_animator_leaves_transform_alone() {
+        let mut transform = Transform::new();
+        let mut animator = Animator::new().spin(Vec3::UP, 1.0);
+        animator.set_paused(true);
+        animator.advance(1.0, &mut transform);
+
+        assert_eq!(transform.rotation(), Vec3::ZERO);
+        assert_eq!(animator.time(), 0.0);
+    }
+
+    /// Three poses around the origin and back to the first, 4 s apart
+    fn orbit_path(looping: bool) -> CameraPath {
+        CameraPath::new(looping)
+            .key(0.0, Vec3::new(0.0, 2.0, -8.0), Vec3::ZERO)
+            .key(4.0, Vec3::new(8.0, 3.0, 0.0), Vec3::ZERO)
+            .key(8.0, Vec3::new(0.0, 2.0, 8.0), Vec3::UP)
+            .key(12.0, Vec3::new(0.0, 2.0, -8.0), Vec3::ZERO)
+    }
+
+    /// Velocity of the path's position at `time`, by central difference.
+    fn velocity(path: &CameraPath, time: f32) -> Vec3 {
+        let h = 1e-3;
+        (path.sample(time + h).unwrap().0 - path.sample(time - h).unwrap().0) / (2.0 * h)
+    }
+
+    #[test]
+    fn camera_path_passes_through_its_keys() {
+        let path = orbit_path(false);
+        for key in path.keys() {
+            let (position, target) = path.sample(key.time).unwrap();
+            assert!(approx_eq(position, key.position));
+            assert!(approx_eq(target, key.target));
+        }
+    }
+
+    #[test]
+    fn camera_path_is_smooth_through_keys() {
+        // Linear interpolation would turn a sharp corner at 4 s. Velocity is
+        // continuous, acceleration isn't, so compare just either side
+        let path = orbit_path(false);
+        let before = velocity(&path, 4.0 - 0.01);
+        let after = velocity(&path, 4.0 + 0.01);
+        assert!((before - after).magnitude() < 0.2, "{before:?} {after:?}");
+        assert!(before.magnitude() > 1.0);
+    }
+
+    #[test]
+    fn looping_camera_path_is_smooth_across_the_seam() {
+        let path = orbit_path(true);
+        let before = velocity(&path, 12.0 - 0.01);
+        let after = velocity(&path, 12.0 + 0.01);
+        assert!((before - after).magnitude() < 0.2, "{before:?} {after:?}");
+        assert!(approx_eq(
+            path.sample(16.0).unwrap().0,
+            Vec3::new(8.0, 3.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn evenly_spaced_collinear_keys_give_constant_speed() {
+        let path = CameraPath::new(false)
+            .key(0.0, Vec3::ZERO, Vec3::ZERO)
+            .key(1.0, Vec3::new(1.0, 0.0, 0.0), Vec3::ZERO)
+            .key(2.0, Vec3::new(2.0, 0.0, 0.0), Vec3::ZERO)
+            .key(3.0, Vec3::new(3.0, 0.0, 0.0), Vec3::ZERO);
+        // Interior segment, away from the open ends
+        assert!(approx_eq(
+            path.sample(1.25).unwrap().0,
+            Vec3::new(1.25, 0.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn camera_path_holds_its_last_pose_when_finished() {
+        let mut path = orbit_path(false);
+        assert!(!path.is_finished());
+        let (position, _) = path.advance(20.0).unwrap();
+        assert!(approx_eq(position, Vec3::new(0.0, 2.0, -8.0)));
+        assert!(path.is_finished());
+
+        path.reset();
+        assert!(!path.is_finished());
+        assert_eq!(CameraPath::new(false).sample(1.0), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Rasterizer internals for benchmarks and low-level tests.
+//!
+//! Benchmarks drive a [`Rasterizer`] directly, bypassing the engine. Rather
+//! than spelling out every [`Triangle`] field and wiring buffer slices by
+//! hand — both of which break whenever the pipeline grows a new input —
+//! they build through [`TriangleBuilder`] and draw into an
+//! [`OwnedFrameBuffer`]:
+//!
+//! ```ignore
+//! use russsty::bench::*;
+//!
+//! let mut target = OwnedFrameBuffer::new(640, 480);
+//! let triangle = TriangleBuilder::new([a, b, c]).color(0xFFFF0000).build();
+//! EdgeFunctionRasterizer::new().fill_triangle(
+//!     &triangle,
+//!     &mut target.as_framebuffer(),
+//!     triangle.color,
+//!     None,
+//! );
+//! ```
+//!
+//! New `Triangle` fields get defaults here, so callers keep compiling.
+
+pub use crate::render::{
+    EdgeFunctionRasterizer, FrameBuffer, Rasterizer, Renderer, ScanlineRasterizer, ScreenVertex,
+    Triangle,
+};
+
+use crate::colors::to_rgb_f32;
+use crate::engine::{ShadingMode, TextureMode};
+use crate::math::vec2::Vec2;
+
+/// Builds a [`Triangle`] with defaults suited to benchmarking: white, flat
+/// shaded, untextured, no dithering or fog.
+#[derive(Debug, Clone)]
+pub struct TriangleBuilder {
+    triangle: Triangle,
+}
+
+impl TriangleBuilder {
+    pub fn new(points: [ScreenVertex; 3]) -> Self {
+        const WHITE: u32 = 0xFFFFFFFF;
+        Self {
+            triangle: Triangle::new(
+                points,
+                WHITE,
+                [[1.0; 3]; 3],
+                [Vec2::ZERO; 3],
+                ShadingMode::Flat,
+                TextureMode::None,
+            ),
+        }
+    }
+
+    /// Set the base color, and the lit vertex colors to match.
+    pub fn color(mut self, color: u32) -> Self {
+        self.triangle.color = color;
+        self.triangle.vertex_colors = [to_rgb_f32(color); 3];
+        self
+    }
+
+    /// Set distinct lit colors per vertex, e.g. for a Gouraud gradient.
+    /// Takes packed ARGB for convenience; alpha is ignored.
+    pub fn vertex_colors(mut self, colors: [u32; 3]) -> Self {
+        self.triangle.vertex_colors = colors.map(to_rgb_f32);
+        self
+    }
+
+    pub fn texture_coords(mut self, uvs: [Vec2; 3]) -> Self {
+        self.triangle.texture_coords = uvs;
+        self
+    }
+
+    pub fn shading_mode(mut self, mode: ShadingMode) -> Self {
+        self.triangle.shading_mode = mode;
+        self
+    }
+
+    pub fn texture_mode(mut self, mode: TextureMode) -> Self {
+        self.triangle.texture_mode = mode;
+        self
+    }
+
+    pub fn dither(mut self, dither: bool) -> Self {
+        self.triangle.dither = dither;
+        self
+    }
+
+    pub fn build(self) -> Triangle {
+        self.triangle
+    }
+}
+
+/// Color and depth storage for a [`FrameBuffer`] view.
+///
+/// `FrameBuffer` borrows its buffers, so this can't `Deref` to one; call
+/// [`OwnedFrameBuffer::as_framebuffer`] for a view instead.
+pub struct OwnedFrameBuffer {
+    color: Vec<u32>,
+    depth: Vec<f32>,
+    width: u32,
+    height: u32,
+}
+
+impl OwnedFrameBuffer {
+    /// Allocate a cleared `width` x `height` target.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = (width * height) as usize;
+        Self {
+            color: vec![0; len],
+            depth: vec![0.0; len],
+            width,
+            height,
+        }
+    }
+
+    /// Zero the colors and reset depth to "infinitely far".
+    pub fn clear(&mut self) {
+        self.color.fill(0);
+        self.depth.fill(0.0);
+    }
+
+    pub fn as_framebuffer(&mut self) -> FrameBuffer<'_> {
+        FrameBuffer::new(&mut self.color, &mut self.depth, self.width, self.height)
+    }
+
+    pub fn color(&self) -> &[u32] {
+        &self.color
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sv(x: f32, y: f32) -> ScreenVertex {
+        ScreenVertex::new(Vec2::new(x, y), 1.0)
+    }
+
+    #[test]
+    fn builder_defaults_to_flat_untextured() {
+        let triangle = TriangleBuilder::new([sv(0.0, 0.0), sv(1.0, 0.0), sv(0.0, 1.0)])
+            .color(0xFF102030)
+            .build();
+        assert_eq!(triangle.shading_mode, ShadingMode::Flat);
+        assert_eq!(triangle.texture_mode, TextureMode::None);
+        assert_eq!(triangle.vertex_colors, [to_rgb_f32(0xFF102030); 3]);
+        assert!(triangle.fog.is_none());
+    }
+
+    #[test]
+    fn owned_framebuffer_takes_rasterized_pixels() {
+        let mut target = OwnedFrameBuffer::new(16, 16);
+        let triangle = TriangleBuilder::new([sv(1.0, 1.0), sv(14.0, 1.0), sv(1.0, 14.0)])
+            .color(0xFFFF0000)
+            .build();
+        for rasterizer in [
+            &ScanlineRasterizer::new() as &dyn Rasterizer,
+            &EdgeFunctionRasterizer::new(),
+        ] {
+            target.clear();
+            rasterizer.fill_triangle(
+                &triangle,
+                &mut target.as_framebuffer(),
+                triangle.color,
+                None,
+            );
+            assert_eq!(target.color()[3 * 16 + 3], 0xFFFF0000);
+            assert_eq!(target.color()[15 * 16 + 15], 0);
+        }
+    }
+}
@@ -1,23 +1,61 @@
-//! First-person camera
+//! Cameras and their input controllers.
+//!
+//! Two camera models share the [`Camera`] trait:
+//! - [`FpsCamera`]: free-flying first-person camera
+//! - [`OrbitCamera`]: turntable camera circling a target point
+//!
+//! [`SceneCamera`] wraps either one so the engine can switch between them.
+//! [`FollowCamera`] steers an [`FpsCamera`] after a moving target, and
+//! [`CameraShake`] turns the final view matrix for impacts; both work on
+//! top of the cameras rather than replacing them.
 //!
 //! # Coordinate System
 //!
-//! Uses a **left-handed** coordinate system:
+//! Uses a **left-handed** coordinate system by default:
 //! - X: positive right
 //! - Y: positive up
 //! - Z: positive forward (into screen)
 //!
+//! With [`Handedness::Right`] the camera looks down -Z instead. Its rotation
+//! is the left-handed one mirrored in z, so yaw, pitch, and roll still turn
+//! the view right, down, and clockwise on screen.
+//!
 //! # Orientation
 //!
 //! Orientation is stored as yaw/pitch/roll angles and converted to a rotation
 //! matrix when needed. This is simpler than caching direction vectors.
 //!
 //! - **Yaw**: Rotation around Y-axis (horizontal look, positive = look right)
-//! - **Pitch**: Rotation around X-axis (vertical look, positive = look down)
+//! - **Pitch**: Rotation around X-axis (vertical look, positive = look down,
+//!   toward -Y)
 //! - **Roll**: Rotation around Z-axis (tilt, positive = tilt right)
+//!
+//! A [`CameraMode`] decides how an [`FpsCamera`] uses them: a level
+//! first-person view with roll held at zero, or free flight where turning
+//! and moving follow the camera's own axes.
+
+use std::fmt;
+use std::sync::Arc;
 
 use crate::math::mat4::Mat4;
 use crate::math::vec3::Vec3;
+use crate::projection::Handedness;
+use crate::transform::Transform;
+
+/// How an [`FpsCamera`] turns, and which axes it moves along.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    /// Yaw turns about world Y and pitch stops at the pitch limits. Roll
+    /// is held at zero, so the horizon stays level, and moving up or down
+    /// follows world Y.
+    #[default]
+    FpsYawPitch,
+    /// Full six degrees of freedom: yaw, pitch and roll turn about the
+    /// camera's own up, right and forward axes, pitch has no limits, and
+    /// every movement follows the camera's axes, so a rolled camera climbs
+    /// along its tilted up.
+    FreeFlight,
+}
 
 /// First-person camera with position and yaw/pitch/roll orientation.
 ///
@@ -32,6 +70,8 @@ pub struct FpsCamera {
 
     pitch_min: f32,
     pitch_max: f32,
+    handedness: Handedness,
+    mode: CameraMode,
 }
 
 impl Default for FpsCamera {
@@ -41,7 +81,8 @@ impl Default for FpsCamera {
 }
 
 impl FpsCamera {
-    /// Creates a new FPS camera at the given position, looking along +Z axis.
+    /// Creates a new left-handed FPS camera at the given position, looking
+    /// along +Z axis.
     pub fn new(position: Vec3) -> Self {
         Self {
             position,
@@ -50,6 +91,8 @@ impl FpsCamera {
             roll: 0.0,
             pitch_min: -89.0_f32.to_radians(),
             pitch_max: 89.0_f32.to_radians(),
+            handedness: Handedness::Left,
+            mode: CameraMode::default(),
         }
     }
 
@@ -69,10 +112,41 @@ impl FpsCamera {
     /// Order: Yaw (Y) * Pitch (X) * Roll (Z)
     /// This means roll is applied first (local), then pitch, then yaw.
     ///
-    /// Note: Yaw and roll are negated to match left-handed conventions where
-    /// positive yaw = look right, positive roll = tilt right.
+    /// Note: All three angles are negated to match left-handed conventions
+    /// where positive yaw = look right, positive pitch = look down (a bare
+    /// X rotation would tip +Z toward +Y, i.e. up), and positive roll = tilt
+    /// right. Right-handed cameras mirror the result so the same holds on
+    /// screen.
     fn rotation_matrix(&self) -> Mat4 {
-        Mat4::rotation_y(-self.yaw) * Mat4::rotation_x(self.pitch) * Mat4::rotation_z(-self.roll)
+        self.handedness.mirror_matrix(self.angle_rotation())
+    }
+
+    /// The rotation the angles describe, before mirroring for handedness.
+    fn angle_rotation(&self) -> Mat4 {
+        Mat4::rotation_y(-self.yaw) * Mat4::rotation_x(-self.pitch) * Mat4::rotation_z(-self.roll)
+    }
+
+    /// Turns the camera by `local`, a rotation about its own axes, and
+    /// stores the result back as angles. Pitch comes out within ±90°,
+    /// with yaw and roll turned half a circle once the camera is upside
+    /// down; straight up or down, yaw is kept and roll takes the rest.
+    fn rotate_local(&mut self, local: Mat4) {
+        let rotation = self.angle_rotation() * local;
+        let column =
+            |c: usize| Vec3::new(rotation.get(0, c), rotation.get(1, c), rotation.get(2, c));
+        let (forward, up) = (column(2), column(1));
+
+        let horizontal_len = (forward.x * forward.x + forward.z * forward.z).sqrt();
+        if horizontal_len > f32::EPSILON {
+            self.yaw = forward.x.atan2(forward.z).rem_euclid(std::f32::consts::TAU);
+        }
+        self.pitch = (-forward.y).atan2(horizontal_len);
+
+        // Positive roll tips the level camera's up away from its right
+        let level = Mat4::rotation_y(-self.yaw) * Mat4::rotation_x(-self.pitch);
+        let level_right = Vec3::new(level.get(0, 0), level.get(1, 0), level.get(2, 0));
+        let level_up = Vec3::new(level.get(0, 1), level.get(1, 1), level.get(2, 1));
+        self.roll = (-up.dot(level_right)).atan2(up.dot(level_up));
     }
 
     // =========================================================================
@@ -85,24 +159,37 @@ impl FpsCamera {
         self.rotate_pitch(pitch_delta);
     }
 
-    /// Rotates the camera horizontally (around Y-axis).
+    /// Rotates the camera horizontally: around world Y, or around its own
+    /// up in [`CameraMode::FreeFlight`].
     /// Positive values rotate right, negative values rotate left.
     pub fn rotate_yaw(&mut self, delta: f32) {
+        if self.mode == CameraMode::FreeFlight {
+            self.rotate_local(Mat4::rotation_y(-delta));
+            return;
+        }
         self.yaw += delta;
         self.yaw = self.yaw.rem_euclid(std::f32::consts::TAU);
     }
 
-    /// Rotates the camera vertically (around X-axis).
+    /// Rotates the camera vertically (around its right axis).
     /// Positive values look down, negative values look up.
-    /// Automatically clamped to pitch limits.
+    /// Clamped to pitch limits, except in [`CameraMode::FreeFlight`].
     pub fn rotate_pitch(&mut self, delta: f32) {
+        if self.mode == CameraMode::FreeFlight {
+            self.rotate_local(Mat4::rotation_x(-delta));
+            return;
+        }
         self.pitch += delta;
         self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
     }
 
     /// Rolls the camera (around Z-axis / forward vector).
     /// Positive values tilt right, negative values tilt left.
+    /// Does nothing in [`CameraMode::FpsYawPitch`], which keeps roll at zero.
     pub fn rotate_roll(&mut self, delta: f32) {
+        if self.mode == CameraMode::FpsYawPitch {
+            return;
+        }
         self.roll += delta;
         self.roll = self.roll.rem_euclid(std::f32::consts::TAU);
         if self.roll > std::f32::consts::PI {
@@ -111,8 +198,15 @@ impl FpsCamera {
     }
 
     /// Points the camera at a world position.
+    ///
+    /// A target above the camera gives a negative pitch, one below a
+    /// positive pitch. Targets steeper than the pitch limits are clamped to
+    /// them, so `forward()` then stops short of the target vertically; one
+    /// straight above or below keeps the current yaw. In
+    /// [`CameraMode::FreeFlight`] pitch isn't clamped and roll is kept.
     pub fn look_at(&mut self, target: Vec3) {
-        let direction = target - self.position;
+        // Angles are defined for the left-handed camera
+        let direction = self.handedness.mirror(target - self.position);
         let horizontal_len = (direction.x * direction.x + direction.z * direction.z).sqrt();
 
         if horizontal_len > f32::EPSILON {
@@ -120,16 +214,54 @@ impl FpsCamera {
         }
 
         if direction.magnitude() > f32::EPSILON {
-            self.pitch = direction.y.atan2(horizontal_len);
-            self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+            // +Y is up, and positive pitch looks down
+            self.pitch = (-direction.y).atan2(horizontal_len);
+            if self.mode == CameraMode::FpsYawPitch {
+                self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+            }
         }
     }
 
-    /// Sets the pitch limits (in radians).
+    /// Sets the pitch limits (in radians). They only hold in
+    /// [`CameraMode::FpsYawPitch`].
     pub fn set_pitch_limits(&mut self, min: f32, max: f32) {
         self.pitch_min = min;
         self.pitch_max = max;
-        self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+        if self.mode == CameraMode::FpsYawPitch {
+            self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+        }
+    }
+
+    /// Returns how the camera turns and moves.
+    pub fn mode(&self) -> CameraMode {
+        self.mode
+    }
+
+    /// Switches how the camera turns and moves, keeping its view direction.
+    /// Going back to [`CameraMode::FpsYawPitch`] levels the roll and clamps
+    /// the pitch to its limits again.
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        self.mode = mode;
+        if mode == CameraMode::FpsYawPitch {
+            // Roll is innermost, so dropping it doesn't move `forward()`
+            self.roll = 0.0;
+            self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+        }
+    }
+
+    /// Returns which way the camera looks in view space.
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    /// Switches coordinate convention, keeping the camera's position and
+    /// world-space view direction.
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        if handedness != self.handedness {
+            let target = self.position + self.forward();
+            self.handedness = handedness;
+            self.look_at(target);
+        }
     }
 
     // =========================================================================
@@ -146,10 +278,11 @@ impl FpsCamera {
         self.position = self.position + self.right() * distance;
     }
 
-    /// Moves the camera along the world up direction.
-    /// Positive distance moves up (negative Y in left-handed coords).
+    /// Moves the camera up: along world +Y, or along its own up in
+    /// [`CameraMode::FreeFlight`].
     pub fn move_up(&mut self, distance: f32) {
-        self.position.y -= distance;
+        let [_, up, _] = self.movement_axes();
+        self.position = self.position + up * distance;
     }
 
     /// Moves the camera along its local up direction.
@@ -172,11 +305,13 @@ impl FpsCamera {
     }
 
     /// Returns the camera's forward direction (normalized).
-    /// This is the +Z axis transformed by the rotation matrix.
+    /// This is the +Z axis (-Z when right-handed) transformed by the
+    /// rotation matrix.
     pub fn forward(&self) -> Vec3 {
         let rot = self.rotation_matrix();
         // Transform +Z unit vector: just read the third column of rotation matrix
-        Vec3::new(rot.get(0, 2), rot.get(1, 2), rot.get(2, 2)).normalize()
+        let z = self.handedness.forward_z();
+        Vec3::new(rot.get(0, 2), rot.get(1, 2), rot.get(2, 2)).normalize() * z
     }
 
     /// Returns the camera's right direction (normalized).
@@ -188,11 +323,23 @@ impl FpsCamera {
     }
 
     /// Returns the camera's up direction (normalized).
-    /// This is the -Y axis transformed by the rotation matrix (Y-down system).
+    /// This is the +Y axis transformed by the rotation matrix.
     pub fn up(&self) -> Vec3 {
         let rot = self.rotation_matrix();
-        // Transform -Y unit vector: negate the second column
-        Vec3::new(-rot.get(0, 1), -rot.get(1, 1), -rot.get(2, 1)).normalize()
+        // Transform +Y unit vector: just read the second column
+        Vec3::new(rot.get(0, 1), rot.get(1, 1), rot.get(2, 1)).normalize()
+    }
+
+    /// The right, up and forward directions the camera moves along, per
+    /// its [`CameraMode`]. Right and forward are always the camera's own;
+    /// up is world +Y in [`CameraMode::FpsYawPitch`] and the camera's own
+    /// in [`CameraMode::FreeFlight`].
+    pub fn movement_axes(&self) -> [Vec3; 3] {
+        let up = match self.mode {
+            CameraMode::FpsYawPitch => Vec3::UP,
+            CameraMode::FreeFlight => self.up(),
+        };
+        [self.right(), up, self.forward()]
     }
 
     /// Returns the yaw angle in radians.
@@ -217,39 +364,346 @@ impl FpsCamera {
     /// Computes the view matrix for the rendering pipeline.
     ///
     /// View matrix = inverse of camera's world transform.
-    /// For a camera with rotation R and position P:
-    ///   World transform = T(P) * R
-    ///   View = R^T * T(-P)
     pub fn view_matrix(&self) -> Mat4 {
+        view_matrix_from(self.rotation_matrix(), self.position)
+    }
+}
+
+/// Builds a view matrix from a camera rotation and world position.
+///
+/// For a camera with rotation R and position P:
+///   World transform = T(P) * R
+///   View = R^T * T(-P)
+fn view_matrix_from(rot: Mat4, position: Vec3) -> Mat4 {
+    let rot_transposed = rot.transpose();
+
+    // Apply inverse translation: rotate(-position)
+    let neg_pos = position * -1.0;
+    let translated = rot_transposed * neg_pos;
+
+    // Build the view matrix: rotation transpose with translation in last column
+    Mat4::new([
+        [
+            rot_transposed.get(0, 0),
+            rot_transposed.get(0, 1),
+            rot_transposed.get(0, 2),
+            translated.x,
+        ],
+        [
+            rot_transposed.get(1, 0),
+            rot_transposed.get(1, 1),
+            rot_transposed.get(1, 2),
+            translated.y,
+        ],
+        [
+            rot_transposed.get(2, 0),
+            rot_transposed.get(2, 1),
+            rot_transposed.get(2, 2),
+            translated.z,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ])
+}
+
+// =============================================================================
+// Camera Trait
+// =============================================================================
+
+/// Common interface for anything the engine can render from.
+pub trait Camera {
+    /// Returns the world-to-view matrix.
+    fn view_matrix(&self) -> Mat4;
+
+    /// Returns the camera's world position.
+    fn position(&self) -> Vec3;
+}
+
+impl Camera for FpsCamera {
+    fn view_matrix(&self) -> Mat4 {
+        FpsCamera::view_matrix(self)
+    }
+
+    fn position(&self) -> Vec3 {
+        FpsCamera::position(self)
+    }
+}
+
+// =============================================================================
+// Orbit Camera
+// =============================================================================
+
+/// Turntable camera that circles a target point.
+///
+/// The camera sits `distance` units from `target` and always looks at it.
+/// Yaw and pitch use the same conventions as [`FpsCamera`], so an orbit
+/// camera with yaw = pitch = 0 sits on the -Z side of the target looking
+/// along +Z, and a positive pitch raises it above the target to look down.
+#[derive(Debug, Clone)]
+pub struct OrbitCamera {
+    target: Vec3,
+    distance: f32,
+    yaw: f32,   // Rotation around Y-axis (radians)
+    pitch: f32, // Rotation around X-axis (radians)
+
+    pitch_min: f32,
+    pitch_max: f32,
+    handedness: Handedness,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, 5.0)
+    }
+}
+
+impl OrbitCamera {
+    /// Smallest allowed distance to the target, keeps the view matrix valid.
+    pub const MIN_DISTANCE: f32 = 0.01;
+
+    /// Creates an orbit camera `distance` units behind `target` (along -Z).
+    pub fn new(target: Vec3, distance: f32) -> Self {
+        Self {
+            target,
+            distance: distance.max(Self::MIN_DISTANCE),
+            yaw: 0.0,
+            pitch: 0.0,
+            pitch_min: -89.0_f32.to_radians(),
+            pitch_max: 89.0_f32.to_radians(),
+            handedness: Handedness::Left,
+        }
+    }
+
+    /// Creates an orbit camera at `position` circling `target`.
+    ///
+    /// Distance, yaw, and pitch are derived so the camera starts exactly
+    /// at `position`.
+    pub fn from_position(position: Vec3, target: Vec3) -> Self {
+        let mut camera = Self::new(target, 1.0);
+        camera.set_position(position);
+        camera
+    }
+
+    fn rotation_matrix(&self) -> Mat4 {
+        self.handedness
+            .mirror_matrix(Mat4::rotation_y(-self.yaw) * Mat4::rotation_x(-self.pitch))
+    }
+
+    // =========================================================================
+    // Orbit Controls
+    // =========================================================================
+
+    /// Orbits around the target by yaw (horizontal) and pitch (vertical) deltas.
+    pub fn rotate(&mut self, yaw_delta: f32, pitch_delta: f32) {
+        self.yaw = (self.yaw + yaw_delta).rem_euclid(std::f32::consts::TAU);
+        self.pitch = (self.pitch + pitch_delta).clamp(self.pitch_min, self.pitch_max);
+    }
+
+    /// Multiplies the distance to the target by `factor` (< 1 zooms in).
+    pub fn zoom(&mut self, factor: f32) {
+        self.set_distance(self.distance * factor);
+    }
+
+    /// Sets the distance to the target, clamped to [`Self::MIN_DISTANCE`].
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance = distance.max(Self::MIN_DISTANCE);
+    }
+
+    /// Moves the orbit pivot without changing distance or angles.
+    pub fn set_target(&mut self, target: Vec3) {
+        self.target = target;
+    }
+
+    /// Moves the camera to `position`, keeping the current target.
+    ///
+    /// Distance, yaw, and pitch are recomputed from the new offset.
+    pub fn set_position(&mut self, position: Vec3) {
+        // Angles are defined for the left-handed camera
+        let direction = self.handedness.mirror(self.target - position);
+        let horizontal_len = (direction.x * direction.x + direction.z * direction.z).sqrt();
+
+        if horizontal_len > f32::EPSILON {
+            self.yaw = direction
+                .x
+                .atan2(direction.z)
+                .rem_euclid(std::f32::consts::TAU);
+        }
+        self.pitch = (-direction.y)
+            .atan2(horizontal_len)
+            .clamp(self.pitch_min, self.pitch_max);
+        self.set_distance(direction.magnitude());
+    }
+
+    /// Sets the pitch limits (in radians).
+    pub fn set_pitch_limits(&mut self, min: f32, max: f32) {
+        self.pitch_min = min;
+        self.pitch_max = max;
+        self.pitch = self.pitch.clamp(self.pitch_min, self.pitch_max);
+    }
+
+    /// Returns which way the camera looks in view space.
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    /// Switches coordinate convention, keeping the camera's position and
+    /// target.
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        if handedness != self.handedness {
+            let position = self.position();
+            self.handedness = handedness;
+            self.set_position(position);
+        }
+    }
+
+    // =========================================================================
+    // Queries
+    // =========================================================================
+
+    /// Returns the point the camera orbits and looks at.
+    pub fn target(&self) -> Vec3 {
+        self.target
+    }
+
+    /// Returns the distance from the camera to the target.
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Returns the yaw angle in radians.
+    pub fn yaw(&self) -> f32 {
+        self.yaw
+    }
+
+    /// Returns the pitch angle in radians.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Returns the direction from the camera toward the target (normalized).
+    pub fn forward(&self) -> Vec3 {
         let rot = self.rotation_matrix();
-        let rot_transposed = rot.transpose();
+        let z = self.handedness.forward_z();
+        Vec3::new(rot.get(0, 2), rot.get(1, 2), rot.get(2, 2)).normalize() * z
+    }
 
-        // Apply inverse translation: rotate(-position)
-        let neg_pos = self.position * -1.0;
-        let translated = rot_transposed * neg_pos;
+    /// Returns the camera's world position.
+    pub fn position(&self) -> Vec3 {
+        self.target - self.forward() * self.distance
+    }
 
-        // Build the view matrix: rotation transpose with translation in last column
-        Mat4::new([
-            [
-                rot_transposed.get(0, 0),
-                rot_transposed.get(0, 1),
-                rot_transposed.get(0, 2),
-                translated.x,
-            ],
-            [
-                rot_transposed.get(1, 0),
-                rot_transposed.get(1, 1),
-                rot_transposed.get(1, 2),
-                translated.y,
-            ],
-            [
-                rot_transposed.get(2, 0),
-                rot_transposed.get(2, 1),
-                rot_transposed.get(2, 2),
-                translated.z,
-            ],
-            [0.0, 0.0, 0.0, 1.0],
-        ])
+    /// Computes the view matrix for the rendering pipeline.
+    pub fn view_matrix(&self) -> Mat4 {
+        view_matrix_from(self.rotation_matrix(), self.position())
+    }
+}
+
+impl Camera for OrbitCamera {
+    fn view_matrix(&self) -> Mat4 {
+        OrbitCamera::view_matrix(self)
+    }
+
+    fn position(&self) -> Vec3 {
+        OrbitCamera::position(self)
+    }
+}
+
+// =============================================================================
+// Scene Camera
+// =============================================================================
+
+/// The camera the engine renders from: either first-person or orbit.
+#[derive(Debug, Clone)]
+pub enum SceneCamera {
+    Fps(FpsCamera),
+    Orbit(OrbitCamera),
+}
+
+impl SceneCamera {
+    /// Moves the camera to `position`.
+    ///
+    /// An orbit camera keeps its target and re-derives its angles.
+    pub fn set_position(&mut self, position: Vec3) {
+        match self {
+            SceneCamera::Fps(camera) => camera.set_position(position),
+            SceneCamera::Orbit(camera) => camera.set_position(position),
+        }
+    }
+
+    /// Moves the camera to `position` looking at `target`.
+    ///
+    /// An orbit camera takes `target` as its new pivot.
+    pub fn set_pose(&mut self, position: Vec3, target: Vec3) {
+        match self {
+            SceneCamera::Fps(camera) => {
+                camera.set_position(position);
+                camera.look_at(target);
+            }
+            SceneCamera::Orbit(camera) => {
+                camera.set_target(target);
+                camera.set_position(position);
+            }
+        }
+    }
+
+    /// Returns the direction the camera looks along (normalized).
+    pub fn forward(&self) -> Vec3 {
+        match self {
+            SceneCamera::Fps(camera) => camera.forward(),
+            SceneCamera::Orbit(camera) => camera.forward(),
+        }
+    }
+
+    /// Switches coordinate convention, keeping the camera where it is and
+    /// looking the same way.
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        match self {
+            SceneCamera::Fps(camera) => camera.set_handedness(handedness),
+            SceneCamera::Orbit(camera) => camera.set_handedness(handedness),
+        }
+    }
+
+    /// Returns the FPS camera, if that is the active kind.
+    pub fn as_fps_mut(&mut self) -> Option<&mut FpsCamera> {
+        match self {
+            SceneCamera::Fps(camera) => Some(camera),
+            SceneCamera::Orbit(_) => None,
+        }
+    }
+
+    /// Returns the orbit camera, if that is the active kind.
+    pub fn as_orbit_mut(&mut self) -> Option<&mut OrbitCamera> {
+        match self {
+            SceneCamera::Orbit(camera) => Some(camera),
+            SceneCamera::Fps(_) => None,
+        }
+    }
+}
+
+impl Camera for SceneCamera {
+    fn view_matrix(&self) -> Mat4 {
+        match self {
+            SceneCamera::Fps(camera) => camera.view_matrix(),
+            SceneCamera::Orbit(camera) => camera.view_matrix(),
+        }
+    }
+
+    fn position(&self) -> Vec3 {
+        match self {
+            SceneCamera::Fps(camera) => camera.position(),
+            SceneCamera::Orbit(camera) => camera.position(),
+        }
+    }
+}
+
+impl From<FpsCamera> for SceneCamera {
+    fn from(camera: FpsCamera) -> Self {
+        SceneCamera::Fps(camera)
+    }
+}
+
+impl From<OrbitCamera> for SceneCamera {
+    fn from(camera: OrbitCamera) -> Self {
+        SceneCamera::Orbit(camera)
     }
 }
 
@@ -258,7 +712,12 @@ impl FpsCamera {
 // =============================================================================
 
 /// Configuration and input handling for FPS camera movement.
-#[derive(Debug, Clone)]
+///
+/// Movement is computed as a proposed position, passed through the
+/// optional constraints ([`FpsCameraController::constrain_with`], then
+/// [`FpsCameraController::set_min_height`]) and only then applied, so an
+/// application can keep the camera out of its own geometry.
+#[derive(Clone)]
 pub struct FpsCameraController {
     /// Movement speed in units per second.
     pub move_speed: f32,
@@ -266,15 +725,44 @@ pub struct FpsCameraController {
     pub look_sensitivity: f32,
     /// Roll speed in radians per second.
     pub roll_speed: f32,
+    /// Distance moved forward per scroll wheel notch.
+    pub zoom_step: f32,
+    /// How quickly movement speeds up toward `move_speed` while a key is
+    /// held, per second. Higher is snappier; `f32::INFINITY` starts at full
+    /// speed.
+    pub acceleration: f32,
+    /// How quickly movement coasts to a stop once keys are released, per
+    /// second. `f32::INFINITY` stops dead.
+    pub damping: f32,
+    /// Speed multiplier while sprint (Left Alt) is held.
+    pub sprint_multiplier: f32,
+    /// Current speed along the camera's right, up and forward axes, in
+    /// units per second.
+    velocity: Vec3,
+    min_height: Option<f32>,
+    constraint: Option<Arc<dyn Fn(Vec3) -> Vec3 + Send + Sync>>,
+}
+
+impl fmt::Debug for FpsCameraController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FpsCameraController")
+            .field("move_speed", &self.move_speed)
+            .field("look_sensitivity", &self.look_sensitivity)
+            .field("roll_speed", &self.roll_speed)
+            .field("zoom_step", &self.zoom_step)
+            .field("acceleration", &self.acceleration)
+            .field("damping", &self.damping)
+            .field("sprint_multiplier", &self.sprint_multiplier)
+            .field("velocity", &self.velocity)
+            .field("min_height", &self.min_height)
+            .field("constraint", &self.constraint.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 impl Default for FpsCameraController {
     fn default() -> Self {
-        Self {
-            move_speed: 5.0,
-            look_sensitivity: 0.002,
-            roll_speed: 1.5,
-        }
+        Self::new(5.0, 0.002)
     }
 }
 
@@ -285,42 +773,138 @@ impl FpsCameraController {
             move_speed,
             look_sensitivity,
             roll_speed: 1.5,
+            zoom_step: 0.5,
+            acceleration: 10.0,
+            damping: 8.0,
+            sprint_multiplier: 3.0,
+            velocity: Vec3::ZERO,
+            min_height: None,
+            constraint: None,
+        }
+    }
+
+    /// Keep the camera at or above `height` on the world y axis, e.g. just
+    /// over the y = 0 ground grid. Applied after any
+    /// [`FpsCameraController::constrain_with`] constraint, so it always holds.
+    pub fn set_min_height(&mut self, height: f32) {
+        self.min_height = Some(height);
+    }
+
+    /// Let the camera fly at any height again.
+    pub fn clear_min_height(&mut self) {
+        self.min_height = None;
+    }
+
+    /// The height set with [`FpsCameraController::set_min_height`], if any.
+    pub fn min_height(&self) -> Option<f32> {
+        self.min_height
+    }
+
+    /// Run `constraint` on every position the controller moves the camera
+    /// to, and move it to the returned position instead. For collision
+    /// against the application's own geometry, e.g. by raycasting down
+    /// from the proposed position. Replaces any previous constraint.
+    pub fn constrain_with(&mut self, constraint: impl Fn(Vec3) -> Vec3 + Send + Sync + 'static) {
+        self.constraint = Some(Arc::new(constraint));
+    }
+
+    /// Remove the [`FpsCameraController::constrain_with`] constraint.
+    pub fn clear_constraint(&mut self) {
+        self.constraint = None;
+    }
+
+    /// Where the constraints allow the camera to be, given it wants to
+    /// move to `proposed`.
+    pub fn constrain(&self, proposed: Vec3) -> Vec3 {
+        let mut position = match &self.constraint {
+            Some(constraint) => constraint(proposed),
+            None => proposed,
+        };
+        if let Some(min_height) = self.min_height {
+            position.y = position.y.max(min_height);
         }
+        position
+    }
+
+    /// Current speed along the camera's right, up and forward axes, in
+    /// units per second.
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    /// Drop any remaining momentum, e.g. when the controller stops driving
+    /// the camera.
+    pub fn stop(&mut self) {
+        self.velocity = Vec3::ZERO;
     }
 
     /// Updates the camera based on input state.
     ///
+    /// Held keys ease the camera up to speed at `acceleration`; released
+    /// ones let it coast to a stop at `damping`.
+    ///
+    /// Movement follows [`FpsCamera::movement_axes`], so it depends on the
+    /// camera's [`CameraMode`].
+    ///
     /// # Input Mapping
     /// - W/S: Move forward/backward
     /// - A/D: Strafe left/right
-    /// - Q/E: Roll left/right
+    /// - Q/E: Roll left/right (free flight only)
     /// - Space/Shift: Move up/down
+    /// - Left Alt: Sprint
     /// - Mouse: Look around (when captured)
+    /// - Scroll wheel: Move forward/backward
     pub fn update(
-        &self,
+        &mut self,
         camera: &mut FpsCamera,
         input: &crate::window::InputState,
         delta_time: f32,
     ) {
-        let move_amount = self.move_speed * delta_time;
+        let speed = if input.sprint {
+            self.move_speed * self.sprint_multiplier
+        } else {
+            self.move_speed
+        };
+        let axis = |positive: bool, negative: bool| match (positive, negative) {
+            (true, false) => speed,
+            (false, true) => -speed,
+            _ => 0.0,
+        };
+        let wanted = [
+            axis(input.right, input.left),
+            axis(input.up, input.down),
+            axis(input.forward, input.back),
+        ];
 
-        if input.forward {
-            camera.move_forward(move_amount);
-        }
-        if input.back {
-            camera.move_forward(-move_amount);
-        }
-        if input.right {
-            camera.move_right(move_amount);
-        }
-        if input.left {
-            camera.move_right(-move_amount);
+        // Ease each axis toward the speed its keys ask for. Exponential
+        // approach keeps the motion the same at any frame rate.
+        let mut velocity = [self.velocity.x, self.velocity.y, self.velocity.z];
+        for (v, wanted) in velocity.iter_mut().zip(wanted) {
+            let rate = if wanted != 0.0 {
+                self.acceleration
+            } else {
+                self.damping
+            };
+            let blend = if delta_time > 0.0 {
+                1.0 - (-rate * delta_time).exp()
+            } else {
+                0.0
+            };
+            *v += (wanted - *v) * blend;
+            // Don't creep forever after a release
+            if wanted == 0.0 && v.abs() < 1e-3 {
+                *v = 0.0;
+            }
         }
-        if input.up {
-            camera.move_up(move_amount);
-        }
-        if input.down {
-            camera.move_up(-move_amount);
+        self.velocity = Vec3::new(velocity[0], velocity[1], velocity[2]);
+
+        // Propose the move first so the constraints see the position the
+        // input asks for, then commit what they allow
+        let [right, up, forward] = camera.movement_axes();
+        let step = right * self.velocity.x + up * self.velocity.y + forward * self.velocity.z;
+        let proposed = camera.position() + step * delta_time;
+        if proposed != camera.position() {
+            camera.set_position(self.constrain(proposed));
         }
 
         let roll_amount = self.roll_speed * delta_time;
@@ -335,10 +919,297 @@ impl FpsCameraController {
         if dx != 0 || dy != 0 {
             camera.rotate(
                 dx as f32 * self.look_sensitivity,
-                -dy as f32 * self.look_sensitivity,
+                dy as f32 * self.look_sensitivity,
+            );
+        }
+
+        self.zoom(camera, input);
+    }
+
+    /// Dollies the camera along its forward direction by the scroll delta,
+    /// subject to the same constraints as [`FpsCameraController::update`].
+    ///
+    /// Safe to call without mouse capture, the wheel is always reported.
+    pub fn zoom(&self, camera: &mut FpsCamera, input: &crate::window::InputState) {
+        if input.scroll_delta != 0.0 {
+            let proposed =
+                camera.position() + camera.forward() * (input.scroll_delta * self.zoom_step);
+            camera.set_position(self.constrain(proposed));
+        }
+    }
+}
+
+/// Configuration and input handling for orbit camera movement.
+#[derive(Debug, Clone)]
+pub struct OrbitCameraController {
+    /// Orbit speed in radians per pixel of mouse movement.
+    pub rotate_sensitivity: f32,
+    /// Fraction of the current distance zoomed per scroll notch.
+    pub zoom_sensitivity: f32,
+    /// Closest the camera may zoom to its target.
+    pub min_distance: f32,
+    /// Farthest the camera may zoom from its target.
+    pub max_distance: f32,
+}
+
+impl Default for OrbitCameraController {
+    fn default() -> Self {
+        Self {
+            rotate_sensitivity: 0.005,
+            zoom_sensitivity: 0.1,
+            min_distance: 0.5,
+            max_distance: 100.0,
+        }
+    }
+}
+
+impl OrbitCameraController {
+    /// Updates the camera based on input state.
+    ///
+    /// # Input Mapping
+    /// - Mouse: Orbit around the target (when captured, or left-drag)
+    /// - Scroll wheel: Zoom toward/away from the target
+    pub fn update(&self, camera: &mut OrbitCamera, input: &crate::window::InputState) {
+        let (dx, dy) = input.mouse_delta;
+        if dx != 0 || dy != 0 {
+            camera.rotate(
+                dx as f32 * self.rotate_sensitivity,
+                dy as f32 * self.rotate_sensitivity,
             );
         }
+
+        if input.scroll_delta != 0.0 {
+            // Exponential zoom so each notch feels the same at any distance
+            let factor = (-input.scroll_delta * self.zoom_sensitivity).exp();
+            let distance = (camera.distance() * factor).clamp(self.min_distance, self.max_distance);
+            camera.set_distance(distance);
+        }
+    }
+}
+
+// =============================================================================
+// Follow Camera
+// =============================================================================
+
+/// Smoothly chases a target with an [`FpsCamera`], e.g. a third-person
+/// view of a moving model.
+///
+/// Each [`FollowCamera::update`] eases the camera's position toward
+/// `target_offset` from the target, and its look-at point toward the
+/// target itself, so the camera trails behind quick moves and swings
+/// round after turns instead of snapping.
+#[derive(Debug, Clone)]
+pub struct FollowCamera {
+    /// Where the camera wants to be relative to the target, along the
+    /// target's heading: x to its right, y up the world, z ahead of it.
+    /// `(0, 2, -6)` sits behind and above.
+    pub target_offset: Vec3,
+    /// How quickly the camera catches up, per second. Higher is tighter;
+    /// `f32::INFINITY` sticks to the target.
+    pub stiffness: f32,
+    /// Smoothed position and look-at point; `None` until the first update
+    pose: Option<(Vec3, Vec3)>,
+}
+
+impl Default for FollowCamera {
+    fn default() -> Self {
+        Self::new(Vec3::new(0.0, 2.0, -6.0), 4.0)
+    }
+}
+
+impl FollowCamera {
+    pub fn new(target_offset: Vec3, stiffness: f32) -> Self {
+        Self {
+            target_offset,
+            stiffness,
+            pose: None,
+        }
+    }
+
+    /// Snap straight to the target on the next update, e.g. after it
+    /// teleports.
+    pub fn reset(&mut self) {
+        self.pose = None;
+    }
+
+    /// The position and look-at point the camera is easing toward for
+    /// `target`. Only the target's heading turns the offset, so the
+    /// camera stays level when the target pitches or rolls.
+    pub fn goal(&self, target: &Transform) -> (Vec3, Vec3) {
+        let facing = target.rotation_quat().rotate(Vec3::FORWARD);
+        let flat = Vec3::new(facing.x, 0.0, facing.z);
+        let forward = if flat.magnitude() > f32::EPSILON {
+            flat.normalize()
+        } else {
+            Vec3::FORWARD
+        };
+        let right = Vec3::UP.cross(forward);
+        let offset = right * self.target_offset.x
+            + Vec3::UP * self.target_offset.y
+            + forward * self.target_offset.z;
+        (target.position() + offset, target.position())
+    }
+
+    /// Ease `camera` toward its goal for `target` by `delta_time` seconds.
+    ///
+    /// Exponential smoothing, so the motion is the same at any frame
+    /// rate. The first update after [`FollowCamera::new`] or
+    /// [`FollowCamera::reset`] jumps straight to the goal.
+    pub fn update(&mut self, camera: &mut FpsCamera, target: &Transform, delta_time: f32) {
+        let (goal_position, goal_look) = self.goal(target);
+        let (position, look) = match self.pose {
+            Some((position, look)) => {
+                let blend = if delta_time > 0.0 {
+                    1.0 - (-self.stiffness * delta_time).exp()
+                } else {
+                    0.0
+                };
+                (
+                    position + (goal_position - position) * blend,
+                    look + (goal_look - look) * blend,
+                )
+            }
+            None => (goal_position, goal_look),
+        };
+        self.pose = Some((position, look));
+        camera.set_position(position);
+        camera.look_at(look);
+    }
+}
+
+// =============================================================================
+// Camera Shake
+// =============================================================================
+
+/// A decaying wobble of the view, for impacts and explosions.
+///
+/// [`CameraShake::trigger`] starts it and [`CameraShake::update`] lets it
+/// die down; [`CameraShake::apply`] turns any camera's view matrix by the
+/// current wobble, about the eye, so the camera itself never moves. The
+/// wobble is smooth noise drawn from the seed, so the same seed, triggers
+/// and time steps shake the same way every run. Attach one to the engine
+/// with `Engine::set_camera_shake`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraShake {
+    /// Largest turn about each axis at full strength, in radians.
+    pub amplitude: f32,
+    /// Roughly how many wobbles per second.
+    pub frequency: f32,
+    /// How quickly the strength fades, per second.
+    pub decay: f32,
+    seed: u64,
+    time: f32,
+    strength: f32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self::new(0.05, 12.0, 3.0)
+    }
+}
+
+impl CameraShake {
+    pub fn new(amplitude: f32, frequency: f32, decay: f32) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            decay,
+            seed: 0,
+            time: 0.0,
+            strength: 0.0,
+        }
+    }
+
+    /// Draw the wobble from `seed` instead of the default.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
     }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Start shaking at `strength`, 1.0 being full amplitude. A weaker hit
+    /// during a stronger shake doesn't cut it short.
+    pub fn trigger(&mut self, strength: f32) {
+        self.strength = self.strength.max(strength.max(0.0));
+    }
+
+    /// Stop shaking at once.
+    pub fn stop(&mut self) {
+        self.strength = 0.0;
+    }
+
+    /// The current strength, fading from the last trigger toward zero.
+    pub fn strength(&self) -> f32 {
+        self.strength
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.strength > 0.0
+    }
+
+    /// Advance the wobble and fade the strength by `delta_time` seconds.
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.is_active() || delta_time <= 0.0 {
+            return;
+        }
+        self.time += delta_time;
+        self.strength *= (-self.decay * delta_time).exp();
+        // Don't wobble imperceptibly forever
+        if self.strength < 1e-3 {
+            self.strength = 0.0;
+        }
+    }
+
+    /// The current turn about the view's x, y and z axes, in radians.
+    pub fn angles(&self) -> Vec3 {
+        if !self.is_active() {
+            return Vec3::ZERO;
+        }
+        let t = self.time * self.frequency;
+        let scale = self.amplitude * self.strength;
+        Vec3::new(
+            smooth_noise(self.seed, 0, t),
+            smooth_noise(self.seed, 1, t),
+            smooth_noise(self.seed, 2, t),
+        ) * scale
+    }
+
+    /// `view` turned by the current wobble. Unchanged while inactive.
+    pub fn apply(&self, view: Mat4) -> Mat4 {
+        if !self.is_active() {
+            return view;
+        }
+        let angles = self.angles();
+        Mat4::rotation_z(angles.z) * Mat4::rotation_x(angles.x) * Mat4::rotation_y(angles.y) * view
+    }
+}
+
+/// Smooth noise in [-1, 1] along `t`, a different curve per `seed` and
+/// `channel`: random values at whole numbers, eased in between.
+fn smooth_noise(seed: u64, channel: u64, t: f32) -> f32 {
+    let whole = t.floor();
+    let f = t - whole;
+    let eased = f * f * (3.0 - 2.0 * f);
+    let (a, b) = (
+        lattice_value(seed, channel, whole as i64),
+        lattice_value(seed, channel, whole as i64 + 1),
+    );
+    a + (b - a) * eased
+}
+
+/// A random value in [-1, 1] for lattice point `i`, from a SplitMix64 hash.
+fn lattice_value(seed: u64, channel: u64, i: i64) -> f32 {
+    let mut x = seed
+        ^ channel.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (i as u64).wrapping_mul(0xD1B5_4A32_D192_ED03);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    // Top 24 bits, exact in an f32
+    (x >> 40) as f32 / (1u64 << 23) as f32 - 1.0
 }
 
 // =============================================================================
@@ -349,6 +1220,7 @@ impl FpsCameraController {
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use std::f32::consts::FRAC_PI_2;
 
     #[test]
     fn camera_starts_looking_forward() {
@@ -378,6 +1250,99 @@ mod tests {
         assert!(camera.pitch() > 0.0);
     }
 
+    /// `forward()` of a camera at `position` aimed at `target`, next to
+    /// the unit direction it should have.
+    fn aim(position: Vec3, target: Vec3, handedness: Handedness) -> (Vec3, Vec3) {
+        let mut camera = FpsCamera::new(position);
+        camera.set_handedness(handedness);
+        camera.look_at(target);
+        (camera.forward(), (target - position).normalize())
+    }
+
+    #[test]
+    fn looking_at_points_forward_at_the_target() {
+        let position = Vec3::new(1.0, 2.0, -3.0);
+        let targets = [
+            Vec3::new(1.5, 6.0, 1.0),   // above
+            Vec3::new(0.0, -4.0, 0.0),  // below
+            Vec3::new(2.0, 3.0, -10.0), // behind
+            Vec3::new(-1.0, 0.5, -8.0), // behind and below
+        ];
+        for handedness in [Handedness::Left, Handedness::Right] {
+            for target in targets {
+                let (forward, expected) = aim(position, target, handedness);
+                assert!(
+                    (forward - expected).magnitude() < 1e-5,
+                    "{handedness:?} toward {target:?}: {forward:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn positive_pitch_looks_down() {
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.rotate_pitch(0.5);
+        assert!(camera.forward().y < 0.0);
+
+        let above = FpsCamera::looking_at(Vec3::ZERO, Vec3::new(0.0, 1.0, 1.0));
+        assert_relative_eq!(above.pitch(), -std::f32::consts::FRAC_PI_4, epsilon = 1e-5);
+        let below = FpsCamera::looking_at(Vec3::ZERO, Vec3::new(0.0, -1.0, 1.0));
+        assert_relative_eq!(below.pitch(), std::f32::consts::FRAC_PI_4, epsilon = 1e-5);
+
+        // Moving the mouse down looks down
+        let input = crate::window::InputState {
+            mouse_delta: (0, 10),
+            ..Default::default()
+        };
+        FpsCameraController::default().update(&mut camera, &input, 0.0);
+        assert!(camera.pitch() > 0.5);
+    }
+
+    #[test]
+    fn nearly_vertical_targets_clamp_pitch() {
+        let position = Vec3::new(0.0, 0.0, -5.0);
+        let up = FpsCamera::looking_at(position, position + Vec3::new(0.0, 10.0, 0.01));
+        assert_relative_eq!(up.pitch(), -89.0_f32.to_radians());
+        assert!(up.forward().y > 0.99);
+        assert!(up.forward().z > 0.0);
+
+        // Straight down keeps the yaw it had
+        let mut down = FpsCamera::new(position);
+        down.rotate_yaw(1.0);
+        down.look_at(position - Vec3::new(0.0, 3.0, 0.0));
+        assert_relative_eq!(down.pitch(), 89.0_f32.to_radians());
+        assert_relative_eq!(down.yaw(), 1.0);
+        assert!(down.forward().y < -0.99);
+    }
+
+    #[test]
+    fn basis_stays_orthonormal() {
+        let mut camera = FpsCamera::looking_at(Vec3::ZERO, Vec3::new(2.0, 3.0, -1.0));
+        camera.set_mode(CameraMode::FreeFlight);
+        camera.rotate_roll(0.4);
+        for handedness in [Handedness::Left, Handedness::Right] {
+            camera.set_handedness(handedness);
+            let (r, u, f) = (camera.right(), camera.up(), camera.forward());
+            for v in [r, u, f] {
+                assert_relative_eq!(v.magnitude(), 1.0, epsilon = 1e-5);
+            }
+            assert_relative_eq!(r.dot(u), 0.0, epsilon = 1e-5);
+            assert_relative_eq!(u.dot(f), 0.0, epsilon = 1e-5);
+            assert_relative_eq!(f.dot(r), 0.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn orbit_positive_pitch_rises_above_the_target() {
+        let mut camera = OrbitCamera::new(Vec3::ZERO, 4.0);
+        camera.rotate(0.0, 0.5);
+        assert!(camera.position().y > 0.0);
+
+        let camera = OrbitCamera::from_position(Vec3::new(0.0, 3.0, -3.0), Vec3::ZERO);
+        assert_relative_eq!(camera.pitch(), std::f32::consts::FRAC_PI_4, epsilon = 1e-5);
+    }
+
     #[test]
     fn move_forward_changes_position() {
         let mut camera = FpsCamera::new(Vec3::ZERO);
@@ -400,12 +1365,440 @@ mod tests {
     #[test]
     fn roll_works_via_matrix() {
         let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.set_mode(CameraMode::FreeFlight);
         camera.rotate_roll(std::f32::consts::FRAC_PI_2); // 90 degrees
 
         // After 90 degree roll, the "up" direction should have rotated
-        // Original up is -Y (0, -1, 0), after roll should be approximately +X
+        // Original up is +Y (0, 1, 0); the camera's top swings to -X, so
+        // the world turns clockwise on screen
         let up = camera.up();
-        assert_relative_eq!(up.x, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(up.x, -1.0, epsilon = 1e-5);
         assert_relative_eq!(up.y, 0.0, epsilon = 1e-5);
     }
+
+    fn assert_axes(camera: &FpsCamera, expected: [Vec3; 3]) {
+        for (axis, expected) in camera.movement_axes().into_iter().zip(expected) {
+            assert!(
+                (axis - expected).magnitude() < 1e-5,
+                "{:?} != {expected:?}",
+                camera.movement_axes()
+            );
+        }
+    }
+
+    /// Looking along +X and 30° down.
+    fn turned_camera() -> FpsCamera {
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.rotate(FRAC_PI_2, 30f32.to_radians());
+        camera
+    }
+
+    const TURNED_FORWARD: Vec3 = Vec3::new(0.8660254, -0.5, 0.0);
+
+    #[test]
+    fn yaw_pitch_mode_moves_up_along_world_y_and_never_rolls() {
+        let mut camera = turned_camera();
+        assert_eq!(camera.mode(), CameraMode::FpsYawPitch);
+        camera.rotate_roll(0.7);
+        assert_eq!(camera.roll(), 0.0);
+        assert_axes(&camera, [Vec3::BACK, Vec3::UP, TURNED_FORWARD]);
+
+        camera.move_up(2.0);
+        assert_relative_eq!(camera.position().y, 2.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn free_flight_moves_along_the_rolled_axes() {
+        let mut camera = turned_camera();
+        camera.set_mode(CameraMode::FreeFlight);
+        camera.rotate_roll(FRAC_PI_2);
+        // The level camera's up swings to the right; forward stays put
+        let level_up = Vec3::new(0.5, 0.8660254, 0.0);
+        assert_axes(&camera, [level_up, Vec3::FORWARD, TURNED_FORWARD]);
+
+        // Back to level, the roll goes and the axes are as they were
+        camera.set_mode(CameraMode::FpsYawPitch);
+        assert_axes(&camera, [Vec3::BACK, Vec3::UP, TURNED_FORWARD]);
+    }
+
+    #[test]
+    fn free_flight_turns_about_the_cameras_own_axes() {
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.set_mode(CameraMode::FreeFlight);
+        camera.rotate_roll(FRAC_PI_2);
+        assert_axes(&camera, [Vec3::UP, Vec3::LEFT, Vec3::FORWARD]);
+
+        // Yaw turns about the rolled up, toward world +Y
+        camera.rotate_yaw(FRAC_PI_2);
+        assert_axes(&camera, [Vec3::BACK, Vec3::LEFT, Vec3::UP]);
+        assert_relative_eq!(camera.pitch(), -FRAC_PI_2, epsilon = 1e-5);
+
+        // Pitch has no limits: past straight down the camera looks behind
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.set_mode(CameraMode::FreeFlight);
+        camera.rotate_pitch(100f32.to_radians());
+        let (sin, cos) = 100f32.to_radians().sin_cos();
+        assert_axes(
+            &camera,
+            [
+                Vec3::RIGHT,
+                Vec3::new(0.0, cos, sin),
+                Vec3::new(0.0, -sin, cos),
+            ],
+        );
+    }
+
+    #[test]
+    fn controller_moves_along_the_modes_axes() {
+        let mut controller = FpsCameraController {
+            acceleration: f32::INFINITY,
+            ..Default::default()
+        };
+        let input = crate::window::InputState {
+            up: true,
+            roll_right: true,
+            ..Default::default()
+        };
+
+        let mut level = FpsCamera::new(Vec3::ZERO);
+        controller.update(&mut level, &input, 1.0);
+        assert_eq!(level.roll(), 0.0);
+        assert!((level.position() - Vec3::UP * controller.move_speed).magnitude() < 1e-4);
+
+        let mut free = FpsCamera::new(Vec3::ZERO);
+        free.set_mode(CameraMode::FreeFlight);
+        free.rotate_roll(FRAC_PI_2);
+        controller.stop();
+        controller.update(&mut free, &input, 1.0);
+        assert!((free.position() - Vec3::LEFT * controller.move_speed).magnitude() < 1e-4);
+        assert!(free.roll() > FRAC_PI_2);
+    }
+
+    #[test]
+    fn orbit_camera_looks_at_target() {
+        let mut camera = OrbitCamera::new(Vec3::new(1.0, 2.0, 3.0), 4.0);
+        camera.rotate(0.7, 0.3);
+
+        // Target should land on the view-space +Z axis at `distance`
+        let target = camera.view_matrix() * camera.target();
+        assert_relative_eq!(target.x, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(target.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(target.z, 4.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn orbit_camera_from_position_round_trips() {
+        let position = Vec3::new(3.0, -2.0, -6.0);
+        let camera = OrbitCamera::from_position(position, Vec3::ZERO);
+
+        assert_relative_eq!(camera.position().x, position.x, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().y, position.y, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().z, position.z, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn orbit_zoom_is_clamped_by_controller() {
+        let controller = OrbitCameraController::default();
+        let mut camera = OrbitCamera::new(Vec3::ZERO, 1.0);
+        let input = crate::window::InputState {
+            scroll_delta: 1000.0,
+            ..Default::default()
+        };
+
+        controller.update(&mut camera, &input);
+        assert_relative_eq!(camera.distance(), controller.min_distance);
+    }
+
+    #[test]
+    fn right_handed_camera_looks_down_negative_z() {
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        camera.set_handedness(Handedness::Right);
+        camera.look_at(Vec3::new(0.0, 0.0, -1.0));
+        assert_relative_eq!(camera.yaw(), 0.0, epsilon = 1e-5);
+        assert_relative_eq!(camera.forward().z, -1.0, epsilon = 1e-5);
+
+        // Positive yaw still turns right (+X)
+        camera.rotate_yaw(std::f32::consts::FRAC_PI_2);
+        assert_relative_eq!(camera.forward().x, 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn set_handedness_keeps_view_direction() {
+        let target = Vec3::new(2.0, 1.0, 3.0);
+        let mut camera = FpsCamera::looking_at(Vec3::new(0.0, 0.0, -5.0), target);
+        let forward = camera.forward();
+        camera.set_handedness(Handedness::Right);
+
+        assert_relative_eq!(camera.forward().x, forward.x, epsilon = 1e-5);
+        assert_relative_eq!(camera.forward().y, forward.y, epsilon = 1e-5);
+        assert_relative_eq!(camera.forward().z, forward.z, epsilon = 1e-5);
+
+        // The target now lies along view-space -Z
+        let view_target = camera.view_matrix() * target;
+        assert_relative_eq!(view_target.x, 0.0, epsilon = 1e-4);
+        assert!(view_target.z < 0.0);
+    }
+
+    #[test]
+    fn right_handed_orbit_keeps_position_and_target() {
+        let position = Vec3::new(3.0, -2.0, -6.0);
+        let mut camera = OrbitCamera::from_position(position, Vec3::ZERO);
+        camera.set_handedness(Handedness::Right);
+
+        assert_relative_eq!(camera.position().x, position.x, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().y, position.y, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().z, position.z, epsilon = 1e-4);
+        let target = camera.view_matrix() * camera.target();
+        assert_relative_eq!(target.x, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(target.y, 0.0, epsilon = 1e-4);
+        assert_relative_eq!(target.z, -position.magnitude(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn min_height_stops_flying_below_the_ground() {
+        let mut controller = FpsCameraController::default();
+        controller.set_min_height(0.5);
+        let mut camera = FpsCamera::new(Vec3::new(0.0, 2.0, -10.0));
+
+        // Long enough with either key to pass y = 0 whichever way it flies
+        for (up, down) in [(true, false), (false, true)] {
+            let input = crate::window::InputState {
+                up,
+                down,
+                ..Default::default()
+            };
+            for _ in 0..10 {
+                controller.update(&mut camera, &input, 0.1);
+            }
+            assert!(camera.position().y >= 0.5, "{:?}", camera.position());
+        }
+    }
+
+    #[test]
+    fn min_height_leaves_other_movement_alone() {
+        let mut controller = FpsCameraController {
+            acceleration: f32::INFINITY,
+            ..Default::default()
+        };
+        controller.set_min_height(0.5);
+        let mut camera = FpsCamera::new(Vec3::new(0.0, 0.5, -10.0));
+        let input = crate::window::InputState {
+            forward: true,
+            right: true,
+            ..Default::default()
+        };
+
+        controller.update(&mut camera, &input, 1.0);
+        assert_relative_eq!(camera.position().x, 5.0, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().y, 0.5, epsilon = 1e-4);
+        assert_relative_eq!(camera.position().z, -5.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn constraint_sees_the_proposed_position() {
+        let mut controller = FpsCameraController::default();
+        // A wall at z = 0
+        controller.constrain_with(|p| Vec3::new(p.x, p.y, p.z.min(0.0)));
+        let mut camera = FpsCamera::new(Vec3::new(0.0, 0.0, -1.0));
+        let walk = crate::window::InputState {
+            forward: true,
+            ..Default::default()
+        };
+        controller.update(&mut camera, &walk, 1.0);
+        assert_relative_eq!(camera.position().z, 0.0, epsilon = 1e-4);
+
+        // Scrolling goes through the same constraint
+        let scroll = crate::window::InputState {
+            scroll_delta: 10.0,
+            ..Default::default()
+        };
+        controller.zoom(&mut camera, &scroll);
+        assert_relative_eq!(camera.position().z, 0.0, epsilon = 1e-4);
+
+        controller.clear_constraint();
+        controller.zoom(&mut camera, &scroll);
+        assert_relative_eq!(camera.position().z, 5.0, epsilon = 1e-4);
+    }
+
+    /// Distance flown forward over `seconds` at `fps`, holding W for the
+    /// first `held` seconds
+    fn glide(controller: &mut FpsCameraController, fps: f32, held: f32, seconds: f32) -> f32 {
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        let frames = (seconds * fps).round() as u32;
+        for frame in 0..frames {
+            let input = crate::window::InputState {
+                forward: (frame as f32) < held * fps,
+                ..Default::default()
+            };
+            controller.update(&mut camera, &input, 1.0 / fps);
+        }
+        camera.position().z
+    }
+
+    #[test]
+    fn movement_eases_in_and_out() {
+        let mut controller = FpsCameraController::default();
+        let walk = crate::window::InputState {
+            forward: true,
+            ..Default::default()
+        };
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        controller.update(&mut camera, &walk, 1.0 / 60.0);
+        assert!(controller.velocity().z > 0.0);
+        assert!(controller.velocity().z < controller.move_speed / 2.0);
+
+        for _ in 0..60 {
+            controller.update(&mut camera, &walk, 1.0 / 60.0);
+        }
+        assert_relative_eq!(
+            controller.velocity().z,
+            controller.move_speed,
+            epsilon = 1e-2
+        );
+
+        // Released, it coasts on before stopping
+        let released = glide(&mut controller, 60.0, 0.0, 2.0);
+        assert!(released > 0.1, "coasted {released}");
+        assert_eq!(controller.velocity(), Vec3::ZERO);
+    }
+
+    #[test]
+    fn eased_movement_is_frame_rate_independent() {
+        let mut controller = FpsCameraController::default();
+        let coarse = glide(&mut controller, 30.0, 0.5, 1.0);
+        controller.stop();
+        let fine = glide(&mut controller, 144.0, 0.5, 1.0);
+        assert_relative_eq!(coarse, fine, epsilon = 0.1);
+    }
+
+    #[test]
+    fn sprint_multiplies_top_speed() {
+        let mut controller = FpsCameraController {
+            acceleration: f32::INFINITY,
+            ..Default::default()
+        };
+        let sprint = crate::window::InputState {
+            forward: true,
+            sprint: true,
+            ..Default::default()
+        };
+        let mut camera = FpsCamera::new(Vec3::ZERO);
+        controller.update(&mut camera, &sprint, 1.0);
+        assert_relative_eq!(
+            camera.position().z,
+            controller.move_speed * controller.sprint_multiplier,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn follow_camera_snaps_behind_the_target_first() {
+        let mut target = Transform::new();
+        target
+            .set_position(Vec3::new(3.0, 0.0, 1.0))
+            .set_rotation_xyz(0.0, FRAC_PI_2, 0.0);
+        let facing = target.rotation_quat().rotate(Vec3::FORWARD);
+        assert_relative_eq!(facing.x.abs(), 1.0, epsilon = 1e-5);
+
+        let mut follow = FollowCamera::new(Vec3::new(1.0, 2.0, -6.0), 4.0);
+        let mut camera = FpsCamera::default();
+        follow.update(&mut camera, &target, 0.1);
+        let expected = target.position() + Vec3::UP.cross(facing) + Vec3::UP * 2.0 - facing * 6.0;
+        assert_relative_eq!(
+            (camera.position() - expected).magnitude(),
+            0.0,
+            epsilon = 1e-5
+        );
+        // Looking at the target, and level
+        let to_target = (target.position() - camera.position()).normalize();
+        assert_relative_eq!(camera.forward().dot(to_target), 1.0, epsilon = 1e-5);
+        assert_eq!(camera.roll(), 0.0);
+    }
+
+    #[test]
+    fn follow_camera_eases_the_same_at_any_frame_rate() {
+        let mut target = Transform::new();
+        let start = |follow: &mut FollowCamera, camera: &mut FpsCamera| {
+            follow.update(camera, &Transform::new(), 0.0);
+        };
+        target.set_position(Vec3::new(10.0, 0.0, 0.0));
+
+        let mut coarse = FollowCamera::default();
+        let mut coarse_camera = FpsCamera::default();
+        start(&mut coarse, &mut coarse_camera);
+        coarse.update(&mut coarse_camera, &target, 0.1);
+        // Part of the way: 1 - e^(-4 * 0.1) of it
+        let moved = coarse_camera.position().x;
+        assert_relative_eq!(moved, 10.0 * (1.0 - (-0.4f32).exp()), epsilon = 1e-4);
+
+        let mut fine = FollowCamera::default();
+        let mut fine_camera = FpsCamera::default();
+        start(&mut fine, &mut fine_camera);
+        for _ in 0..4 {
+            fine.update(&mut fine_camera, &target, 0.025);
+        }
+        assert_relative_eq!(fine_camera.position().x, moved, epsilon = 1e-4);
+
+        // Caught up after a while; reset snaps at once
+        for _ in 0..100 {
+            coarse.update(&mut coarse_camera, &target, 0.1);
+        }
+        assert_relative_eq!(coarse_camera.position().x, 10.0, epsilon = 1e-3);
+        fine.reset();
+        fine.update(&mut fine_camera, &target, 0.0);
+        assert_relative_eq!(fine_camera.position().x, 10.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn shake_is_reproducible_from_its_seed() {
+        let run = |seed: u64| {
+            let mut shake = CameraShake::default().with_seed(seed);
+            shake.trigger(1.0);
+            (0..20)
+                .map(|_| {
+                    shake.update(1.0 / 60.0);
+                    shake.angles()
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = run(7);
+        assert_eq!(first, run(7));
+        assert_ne!(first, run(8));
+
+        let amplitude = CameraShake::default().amplitude;
+        for angles in &first {
+            assert!(angles.x.abs().max(angles.y.abs()).max(angles.z.abs()) <= amplitude);
+        }
+        // It moves smoothly: no jumps between frames
+        for pair in first.windows(2) {
+            assert!((pair[1] - pair[0]).magnitude() < amplitude * 0.5);
+        }
+    }
+
+    #[test]
+    fn shake_turns_the_view_about_the_eye_and_dies_down() {
+        let camera = FpsCamera::looking_at(Vec3::new(1.0, 2.0, -5.0), Vec3::ZERO);
+        let view = camera.view_matrix();
+        let mut shake = CameraShake::default();
+        assert_eq!(shake.apply(view), view);
+
+        shake.trigger(1.0);
+        shake.update(0.1);
+        let shaken = shake.apply(view);
+        assert_ne!(shaken, view);
+        // The eye stays at the view-space origin
+        let eye = shaken * camera.position();
+        assert_relative_eq!(eye.magnitude(), 0.0, epsilon = 1e-5);
+
+        // A weaker hit doesn't cut a strong shake short
+        let strength = shake.strength();
+        shake.trigger(0.1);
+        assert_eq!(shake.strength(), strength);
+
+        for _ in 0..300 {
+            shake.update(1.0 / 60.0);
+        }
+        assert!(!shake.is_active());
+        assert_eq!(shake.apply(view), view);
+    }
 }
@@ -14,41 +14,75 @@
 //! - No need to rebuild when projection parameters change
 //! - This is how GPU hardware performs clipping
 
-use crate::colors;
-use crate::prelude::{Vec2, Vec4};
+use crate::prelude::{Vec2, Vec3, Vec4};
 
-/// A vertex in homogeneous clip space with interpolatable attributes.
-#[derive(Clone, Copy)]
+/// A vertex in homogeneous clip space with the attributes the rasterizer
+/// interpolates.
+///
+/// Every attribute is kept in floating point, so a vertex created by
+/// clipping is as precise as the ones it was cut from; colors are only
+/// quantized to 8 bits per channel when a shader writes the pixel.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ClipSpaceVertex {
     /// Position in clip space (x, y, z, w) - before perspective divide
     pub position: Vec4,
+    /// World-space position
+    pub world_position: Vec3,
+    /// World-space normal, facing out of the drawn side. Interpolated
+    /// linearly, so renormalize before lighting with it.
+    pub normal: Vec3,
     /// Texture coordinates
-    pub texcoord: Vec2,
-    /// Packed ARGB color
-    pub color: u32,
+    pub uv: Vec2,
+    /// Lit RGB color, each channel in [0.0, 1.0]
+    pub color: [f32; 3],
+    /// Object-space position, carried for procedural surface patterns
+    pub surface: Vec3,
+    /// World-space directions of increasing `u` and `v`, for normal
+    /// mapping. Zero unless set with [`ClipSpaceVertex::with_tangents`].
+    pub tangent: Vec3,
+    pub bitangent: Vec3,
 }
 
 impl ClipSpaceVertex {
-    pub fn new(position: Vec4, texcoord: Vec2, color: u32) -> Self {
+    pub fn new(
+        position: Vec4,
+        world_position: Vec3,
+        normal: Vec3,
+        uv: Vec2,
+        color: [f32; 3],
+        surface: Vec3,
+    ) -> Self {
         Self {
             position,
-            texcoord,
+            world_position,
+            normal,
+            uv,
             color,
+            surface,
+            tangent: Vec3::ZERO,
+            bitangent: Vec3::ZERO,
         }
     }
 
-    /// Linearly interpolate all attributes between two vertices.
+    /// Attach world-space tangent and bitangent.
+    pub fn with_tangents(mut self, tangent: Vec3, bitangent: Vec3) -> Self {
+        self.tangent = tangent;
+        self.bitangent = bitangent;
+        self
+    }
+
+    /// Linearly interpolate every attribute between two vertices.
     /// Used when a polygon edge crosses a clipping plane.
     pub fn lerp(&self, other: &Self, t: f32) -> Self {
         Self {
             position: self.position.lerp(other.position, t),
-            texcoord: self.texcoord + (other.texcoord - self.texcoord) * t,
-            color: {
-                let c1 = colors::unpack_color(self.color);
-                let c2 = colors::unpack_color(other.color);
-                let (r, g, b) = colors::lerp_color(c1, c2, t);
-                colors::pack_color(r, g, b, 1.0)
-            },
+            world_position: self.world_position + (other.world_position - self.world_position) * t,
+            normal: self.normal + (other.normal - self.normal) * t,
+            uv: self.uv + (other.uv - self.uv) * t,
+            color: std::array::from_fn(|i| self.color[i] + (other.color[i] - self.color[i]) * t),
+            surface: self.surface + (other.surface - self.surface) * t,
+            tangent: self.tangent + (other.tangent - self.tangent) * t,
+            bitangent: self.bitangent + (other.bitangent - self.bitangent) * t,
         }
     }
 }
@@ -89,6 +123,18 @@ impl ClipPlane {
     }
 }
 
+/// Where a triangle lies relative to the clip cube, from
+/// [`ClipSpaceClipper::classify`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipClass {
+    /// Every vertex is inside every plane; no clipping needed.
+    Inside,
+    /// Every vertex is outside the same plane; nothing can be visible.
+    Outside,
+    /// Anything else. Needs the full clipper, and may still turn out empty.
+    Straddling,
+}
+
 /// A polygon in clip space, represented as a list of vertices.
 ///
 /// Used as an intermediate representation during clipping. After clipping
@@ -96,6 +142,9 @@ impl ClipPlane {
 /// rasterization.
 pub struct ClipSpacePolygon {
     pub vertices: Vec<ClipSpaceVertex>,
+    /// Whether the edge from `vertices[i]` to the next vertex lies on an
+    /// edge of the source triangle, rather than along a clip plane.
+    pub original_edges: Vec<bool>,
 }
 
 impl ClipSpacePolygon {
@@ -103,6 +152,7 @@ impl ClipSpacePolygon {
     pub fn from_triangle(v0: ClipSpaceVertex, v1: ClipSpaceVertex, v2: ClipSpaceVertex) -> Self {
         Self {
             vertices: vec![v0, v1, v2],
+            original_edges: vec![true; 3],
         }
     }
 
@@ -115,14 +165,19 @@ impl ClipSpacePolygon {
     /// Returns a new polygon with the clipped vertices.
     pub fn clip_against_plane(&self, plane: ClipPlane) -> Self {
         if self.vertices.len() < 3 {
-            return Self { vertices: vec![] };
+            return Self {
+                vertices: vec![],
+                original_edges: vec![],
+            };
         }
 
         let mut output = Vec::new();
+        let mut original_edges = Vec::new();
 
         for i in 0..self.vertices.len() {
             let current = &self.vertices[i];
             let next = &self.vertices[(i + 1) % self.vertices.len()];
+            let original = self.original_edges[i];
 
             let d1 = plane.signed_distance(current);
             let d2 = plane.signed_distance(next);
@@ -133,32 +188,63 @@ impl ClipSpacePolygon {
             if current_inside {
                 // Current vertex is inside, add it
                 output.push(*current);
+                original_edges.push(original);
 
                 if !next_inside {
-                    // Going from inside to outside, add intersection
+                    // Going from inside to outside, add intersection. The
+                    // edge leaving it runs along the plane.
                     let t = d1 / (d1 - d2);
                     output.push(current.lerp(next, t));
+                    original_edges.push(false);
                 }
             } else if next_inside {
-                // Going from outside to inside, add intersection
+                // Going from outside to inside, add intersection. The edge
+                // leaving it is what's left of the current edge.
                 let t = d1 / (d1 - d2);
                 output.push(current.lerp(next, t));
+                original_edges.push(original);
             }
             // If both outside, add nothing
         }
 
-        Self { vertices: output }
+        Self {
+            vertices: output,
+            original_edges,
+        }
     }
 
     /// Triangulate this convex polygon using fan triangulation.
     ///
-    /// Returns an iterator of (v0, v1, v2) triangles.
+    /// Returns an iterator of (v0, v1, v2, edge flags) triangles. Bit `i` of
+    /// the flags is set when the triangle's edge from its `i`th vertex to
+    /// the next is an edge of the source triangle, so the fan's spokes and
+    /// edges along clip planes are left out (see [`Triangle::edge_flags`]).
     /// Assumes the polygon is convex (which is guaranteed after clipping).
+    ///
+    /// [`Triangle::edge_flags`]: crate::render::rasterizer::Triangle::edge_flags
     pub fn triangulate(
         &self,
-    ) -> impl Iterator<Item = (&ClipSpaceVertex, &ClipSpaceVertex, &ClipSpaceVertex)> {
-        (1..self.vertices.len().saturating_sub(1))
-            .map(move |i| (&self.vertices[0], &self.vertices[i], &self.vertices[i + 1]))
+    ) -> impl Iterator<Item = (&ClipSpaceVertex, &ClipSpaceVertex, &ClipSpaceVertex, u8)> {
+        let last = self.vertices.len().saturating_sub(1);
+        (1..last).map(move |i| {
+            // v0 -> vi and v(i+1) -> v0 are polygon edges only at the ends
+            // of the fan; otherwise they are spokes
+            let edges = [
+                i == 1 && self.original_edges[0],
+                self.original_edges[i],
+                i + 1 == last && self.original_edges[last],
+            ];
+            let flags = edges
+                .iter()
+                .enumerate()
+                .fold(0, |flags, (bit, &edge)| flags | (u8::from(edge) << bit));
+            (
+                &self.vertices[0],
+                &self.vertices[i],
+                &self.vertices[i + 1],
+                flags,
+            )
+        })
     }
 }
 
@@ -188,6 +274,29 @@ impl ClipSpaceClipper {
         }
     }
 
+    /// Trivially accept or reject a triangle from its vertices' signed
+    /// distances, so only triangles that straddle a plane pay for a
+    /// [`ClipSpacePolygon`] and the full clipper.
+    pub fn classify(&self, vertices: &[ClipSpaceVertex; 3]) -> ClipClass {
+        let mut inside = true;
+        for plane in &self.planes {
+            let outside = vertices
+                .iter()
+                .filter(|v| plane.signed_distance(v) < 0.0)
+                .count();
+            match outside {
+                0 => {}
+                3 => return ClipClass::Outside,
+                _ => inside = false,
+            }
+        }
+        if inside {
+            ClipClass::Inside
+        } else {
+            ClipClass::Straddling
+        }
+    }
+
     /// Clip a polygon against all 6 planes of the clip cube.
     ///
     /// Returns the clipped polygon, which may be empty if the original
@@ -211,3 +320,171 @@ impl Default for ClipSpaceClipper {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(x: f32, y: f32, z: f32, w: f32) -> ClipSpaceVertex {
+        ClipSpaceVertex::new(
+            Vec4::new(x, y, z, w),
+            Vec3::ZERO,
+            Vec3::ZERO,
+            Vec2::ZERO,
+            [0.0; 3],
+            Vec3::ZERO,
+        )
+    }
+
+    fn endpoints() -> (ClipSpaceVertex, ClipSpaceVertex) {
+        let a = ClipSpaceVertex::new(
+            Vec4::new(-2.0, 0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, 4.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            [1.0, 0.25, 0.0],
+            Vec3::new(-1.0, 0.0, 0.0),
+        );
+        let b = ClipSpaceVertex::new(
+            Vec4::new(2.0, 0.0, 2.0, 3.0),
+            Vec3::new(8.0, 2.0, 4.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            [0.0, 0.75, 0.5],
+            Vec3::new(1.0, 2.0, 0.0),
+        );
+        (a, b)
+    }
+
+    #[test]
+    fn lerp_interpolates_position() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.25).position, Vec4::new(-1.0, 0.0, 0.5, 1.5));
+    }
+
+    #[test]
+    fn lerp_interpolates_world_position() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.5).world_position, Vec3::new(4.0, 1.0, 4.0));
+    }
+
+    #[test]
+    fn lerp_interpolates_normal_without_renormalizing() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.5).normal, Vec3::new(0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn lerp_interpolates_uv() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.75).uv, Vec2::new(0.75, 0.25));
+    }
+
+    #[test]
+    fn lerp_keeps_color_precision() {
+        let (a, b) = endpoints();
+        // Finer than one 8-bit step, which packing would have rounded away
+        let color = a.lerp(&b, 0.001).color;
+        for (actual, expected) in color.iter().zip([0.999, 0.2505, 0.0005]) {
+            assert!((actual - expected).abs() < 1e-6, "{color:?}");
+        }
+    }
+
+    #[test]
+    fn lerp_interpolates_surface() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.5).surface, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn lerp_endpoints_reproduce_the_inputs() {
+        let (a, b) = endpoints();
+        assert_eq!(a.lerp(&b, 0.0), a);
+        assert_eq!(a.lerp(&b, 1.0), b);
+    }
+
+    #[test]
+    fn classify_accepts_triangles_inside_the_cube() {
+        let clipper = ClipSpaceClipper::new();
+        let triangle = [
+            vertex(-0.5, -0.5, 0.0, 1.0),
+            vertex(0.0, 0.5, 0.0, 1.0),
+            vertex(0.5, -0.5, 0.0, 1.0),
+        ];
+        assert_eq!(clipper.classify(&triangle), ClipClass::Inside);
+    }
+
+    #[test]
+    fn classify_rejects_triangles_beyond_one_plane() {
+        let clipper = ClipSpaceClipper::new();
+        // All three right of x = w
+        let triangle = [
+            vertex(2.0, -0.5, 0.0, 1.0),
+            vertex(3.0, 0.5, 0.0, 1.0),
+            vertex(4.0, -0.5, 0.0, 1.0),
+        ];
+        assert_eq!(clipper.classify(&triangle), ClipClass::Outside);
+    }
+
+    #[test]
+    fn classify_leaves_corner_cases_to_the_clipper() {
+        let clipper = ClipSpaceClipper::new();
+        // Each vertex is outside a different plane, so no single plane
+        // rejects it, though the clipper may still remove it entirely
+        let triangle = [
+            vertex(2.0, 0.0, 0.0, 1.0),
+            vertex(0.0, 2.0, 0.0, 1.0),
+            vertex(-2.0, -2.0, 0.0, 1.0),
+        ];
+        assert_eq!(clipper.classify(&triangle), ClipClass::Straddling);
+
+        // Crossing a single plane
+        let triangle = [
+            vertex(0.0, 0.0, 0.0, 1.0),
+            vertex(2.0, 0.5, 0.0, 1.0),
+            vertex(0.5, -0.5, 0.0, 1.0),
+        ];
+        assert_eq!(clipper.classify(&triangle), ClipClass::Straddling);
+    }
+
+    #[test]
+    fn clipping_marks_edges_along_the_plane() {
+        // Apex pokes out past x = w; cutting it off leaves a quad whose
+        // edge along the plane isn't an edge of the triangle
+        let polygon = ClipSpacePolygon::from_triangle(
+            vertex(-0.5, -0.5, 0.0, 1.0),
+            vertex(2.0, 0.0, 0.0, 1.0),
+            vertex(-0.5, 0.5, 0.0, 1.0),
+        );
+        let clipped = polygon.clip_against_plane(ClipPlane::Right);
+        assert_eq!(clipped.vertices.len(), 4);
+        assert_eq!(clipped.original_edges, [true, false, true, true]);
+        // The cut runs between the two new vertices on x = w
+        assert_eq!(clipped.vertices[1].position.x, 1.0);
+        assert_eq!(clipped.vertices[2].position.x, 1.0);
+    }
+
+    #[test]
+    fn triangulate_flags_only_polygon_edges() {
+        let polygon = ClipSpacePolygon {
+            vertices: (0..5).map(|i| vertex(i as f32, 0.0, 0.0, 1.0)).collect(),
+            original_edges: vec![true, true, false, true, true],
+        };
+        let flags: Vec<u8> = polygon.triangulate().map(|(.., f)| f).collect();
+        // (0, 1, 2): edges 0 and 1, spoke 2 -> 0
+        // (0, 2, 3): spoke, cut edge 2, spoke
+        // (0, 3, 4): spoke, edges 3 and 4
+        assert_eq!(flags, [0b011, 0b000, 0b110]);
+    }
+
+    #[test]
+    fn unclipped_triangle_keeps_every_edge() {
+        let polygon = ClipSpacePolygon::from_triangle(
+            vertex(-0.5, -0.5, 0.0, 1.0),
+            vertex(0.0, 0.5, 0.0, 1.0),
+            vertex(0.5, -0.5, 0.0, 1.0),
+        );
+        let flags: Vec<u8> = polygon.triangulate().map(|(.., f)| f).collect();
+        assert_eq!(flags, [0b111]);
+    }
+}
@@ -8,4 +8,4 @@
 
 pub mod clip_space;
 
-pub use clip_space::{ClipSpaceClipper, ClipSpacePolygon, ClipSpaceVertex};
+pub use clip_space::{ClipClass, ClipSpaceClipper, ClipSpacePolygon, ClipSpaceVertex};
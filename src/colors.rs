@@ -1,37 +1,190 @@
-//! Color constants used throughout the renderer.
+//! Color packing, conversion and palettes.
 //!
-//! All colors are in ARGB8888 format (0xAARRGGBB).
+//! All packed colors are ARGB8888 (`0xAARRGGBB`), the format of the frame
+//! buffer, textures and vertex colors. Float components are in [0.0, 1.0].
+//!
+//! ```
+//! use russsty::colors::{from_hsv, from_rgb_f32, to_rgb_f32};
+//!
+//! let orange = from_rgb_f32([1.0, 0.5, 0.0]);
+//! assert_eq!(orange, 0xFFFF8000);
+//! assert_eq!(to_rgb_f32(orange)[0], 1.0);
+//! assert_eq!(from_hsv(120.0, 1.0, 1.0), 0xFF00FF00);
+//! ```
 
 /// Background color for the frame buffer (dark gray).
-pub const BACKGROUND: u32 = 0xFF1E1E1E;
+pub(crate) const BACKGROUND: u32 = 0xFF1E1E1E;
 
 /// Grid line color (medium gray).
-pub const GRID: u32 = 0xFF333333;
+pub(crate) const GRID: u32 = 0xFF333333;
 
 /// Default triangle fill color (gray).
-pub const FILL: u32 = 0xFF888888;
+pub(crate) const FILL: u32 = 0xFF888888;
 
 /// Wireframe edge color (green).
-pub const WIREFRAME: u32 = 0xFF00FF00;
+pub(crate) const WIREFRAME: u32 = 0xFF00FF00;
 
 /// Vertex marker color (red).
-pub const VERTEX: u32 = 0xFFFF0000;
+pub(crate) const VERTEX: u32 = 0xFFFF0000;
+
+/// Debug bounding box color (amber).
+pub(crate) const BOUNDS: u32 = 0xFFFFB000;
+
+/// Frustum outline drawn by `Engine::set_debug_draw_frustum` (cyan).
+pub(crate) const FRUSTUM: u32 = 0xFF00FFFF;
+
+/// Bounds of meshes wholly inside the frustum (green).
+pub(crate) const CULL_INSIDE: u32 = 0xFF40FF40;
+
+/// Bounds of meshes straddling the frustum (yellow).
+pub(crate) const CULL_INTERSECTING: u32 = 0xFFFFFF40;
+
+/// Bounds of culled meshes (red).
+pub(crate) const CULL_CULLED: u32 = 0xFFFF4040;
+
+/// Debug HUD panel color (black, blended translucently).
+pub(crate) const HUD_PANEL: u32 = 0xFF000000;
+
+/// Debug HUD text color (white).
+pub(crate) const HUD_TEXT: u32 = 0xFFFFFFFF;
+
+/// Runtime-configurable colors used by the engine when drawing a frame.
+///
+/// [`Default`] is a dark theme. Swap the whole palette with
+/// `Engine::set_palette` or tweak fields through `Engine::palette_mut`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderPalette {
+    /// Clear color for each frame.
+    pub background: u32,
+    /// Screen-space grid lines.
+    pub grid: u32,
+    /// Base surface color that lighting is applied to (untextured modes).
+    pub fill: u32,
+    /// Wireframe edges.
+    pub wireframe: u32,
+    /// Vertex markers.
+    pub vertex: u32,
+    /// Mesh bounding boxes drawn by `Engine::set_debug_draw`.
+    pub bounds: u32,
+    /// Debug HUD panel (blended translucently).
+    pub hud_panel: u32,
+    /// Debug HUD text.
+    pub hud_text: u32,
+}
 
-/// Modulate a color by an intensity factor (0.0 to 1.0).
+impl Default for RenderPalette {
+    fn default() -> Self {
+        Self {
+            background: BACKGROUND,
+            grid: GRID,
+            fill: FILL,
+            wireframe: WIREFRAME,
+            vertex: VERTEX,
+            bounds: BOUNDS,
+            hud_panel: HUD_PANEL,
+            hud_text: HUD_TEXT,
+        }
+    }
+}
+
+impl RenderPalette {
+    /// Light theme: white background with dark lines.
+    pub fn light() -> Self {
+        Self {
+            background: 0xFFFFFFFF,
+            grid: 0xFFDDDDDD,
+            fill: 0xFFB0B0B0,
+            wireframe: 0xFF000000,
+            vertex: 0xFFD00000,
+            bounds: 0xFFC07000,
+            hud_panel: 0xFFFFFFFF,
+            hud_text: 0xFF000000,
+        }
+    }
+}
+
+/// Modulate a color by a lighting intensity.
+///
+/// Same as [`scale_rgb`]: intensities above 1.0 brighten the color, each
+/// channel saturating at 255, and negative intensities give black. Alpha is
+/// preserved.
+///
+/// ```
+/// use russsty::colors::modulate;
 ///
-/// Preserves the alpha channel while scaling the RGB channels.
-/// Useful for applying lighting intensity to a base color.
+/// assert_eq!(modulate(0xFF804020, 0.5), 0xFF402010);
+/// ```
 pub fn modulate(color: u32, intensity: f32) -> u32 {
-    let a = (color >> 24) & 0xFF;
-    let r = ((((color >> 16) & 0xFF) as f32 * intensity) as u32).min(255);
-    let g = ((((color >> 8) & 0xFF) as f32 * intensity) as u32).min(255);
-    let b = (((color & 0xFF) as f32 * intensity) as u32).min(255);
-    (a << 24) | (r << 16) | (g << 8) | b
+    scale_rgb(color, intensity)
+}
+
+/// Multiply the RGB channels by `factor`, saturating each at 255 (and at 0
+/// for a negative or NaN factor). Alpha is preserved.
+///
+/// A channel that saturates leaves the others alone, so an over-bright
+/// color drifts toward white in its own hue instead of wrapping into a
+/// neighboring channel.
+///
+/// ```
+/// use russsty::colors::scale_rgb;
+///
+/// assert_eq!(scale_rgb(0x80C06020, 2.0), 0x80FFC040);
+/// ```
+#[inline]
+pub fn scale_rgb(color: u32, factor: f32) -> u32 {
+    // Float-to-int `as` saturates to [0, u32::MAX] and maps NaN to 0
+    let channel = |shift: u32| ((((color >> shift) & 0xFF) as f32 * factor) as u32).min(255);
+    (color & 0xFF000000) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
+
+/// Add the RGB channels of two colors, saturating each at 255. Alpha is
+/// taken from `a`.
+///
+/// Used to sum light contributions, so several bright lights clip to white
+/// rather than overflowing.
+///
+/// ```
+/// use russsty::colors::add_saturating;
+///
+/// assert_eq!(add_saturating(0xFFC00000, 0xFF808080), 0xFFFF8080);
+/// ```
+#[inline]
+pub fn add_saturating(a: u32, b: u32) -> u32 {
+    let channel = |shift: u32| (((a >> shift) & 0xFF) + ((b >> shift) & 0xFF)).min(255);
+    (a & 0xFF000000) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
+
+/// Keep the top `bits` bits of each RGB channel, spread back over the
+/// full 0 to 255 range; 5 gives the 15-bit color of RGB555. `bits` is
+/// clamped to 1 to 8, and 8 leaves the color alone. Alpha is preserved.
+///
+/// ```
+/// use russsty::colors::quantize;
+///
+/// assert_eq!(quantize(0xFF0F80FF, 5), 0xFF0884FF);
+/// assert_eq!(quantize(0xFF0F80FF, 1), 0xFF00FFFF);
+/// ```
+pub fn quantize(color: u32, bits: u8) -> u32 {
+    let bits = bits.clamp(1, 8) as u32;
+    let max = (1u32 << bits) - 1;
+    let channel = |shift: u32| {
+        let level = ((color >> shift) & 0xFF) >> (8 - bits);
+        // Rounded, so the top level comes back as 255
+        ((level * 255 + max / 2) / max) << shift
+    };
+    (color & 0xFF000000) | channel(16) | channel(8) | channel(0)
 }
 
 /// Unpack an ARGB8888 color into its constituent RGB components constrained to the range [0.0, 1.0].
 ///
 /// Returns a tuple of floats representing the red, green, and blue components.
+/// Alpha is dropped.
+///
+/// ```
+/// use russsty::colors::unpack_color;
+///
+/// assert_eq!(unpack_color(0xFFFF0000), (1.0, 0.0, 0.0));
+/// ```
 pub fn unpack_color(color: u32) -> (f32, f32, f32) {
     let r = ((color >> 16) & 0xFF) as f32 / 255.0;
     let g = ((color >> 8) & 0xFF) as f32 / 255.0;
@@ -40,13 +193,91 @@ pub fn unpack_color(color: u32) -> (f32, f32, f32) {
 }
 
 /// Pack RGB components and an alpha value into an ARGB8888 color.
-/// Assumes the input r,g,b values are in the range [0.0, 1.0].
+/// Components are expected in [0.0, 1.0]; anything outside is clamped per
+/// channel.
+///
+/// ```
+/// use russsty::colors::pack_color;
+///
+/// assert_eq!(pack_color(1.0, 0.0, 0.5, 1.0), 0xFFFF0080);
+/// assert_eq!(pack_color(2.0, -1.0, 0.0, 0.0), 0x00FF0000);
+/// ```
 pub fn pack_color(r: f32, g: f32, b: f32, a: f32) -> u32 {
-    let a = (a * 255.0).round() as u32;
-    let r = (r * 255.0).round() as u32;
-    let g = (g * 255.0).round() as u32;
-    let b = (b * 255.0).round() as u32;
-    (a << 24) | (r << 16) | (g << 8) | b
+    let q = |c: f32| ((c * 255.0).round() as u32).min(255);
+    (q(a) << 24) | (q(r) << 16) | (q(g) << 8) | q(b)
+}
+
+/// Unpack an ARGB8888 color into `[r, g, b]`, each in [0.0, 1.0]. The
+/// array form of [`unpack_color`], used for per-vertex colors.
+#[inline]
+pub fn to_rgb_f32(color: u32) -> [f32; 3] {
+    let (r, g, b) = unpack_color(color);
+    [r, g, b]
+}
+
+/// Pack `[r, g, b]` into an opaque ARGB8888 color, clamping each channel.
+///
+/// Inverse of [`to_rgb_f32`] for opaque colors.
+///
+/// ```
+/// use russsty::colors::{from_rgb_f32, to_rgb_f32};
+///
+/// assert_eq!(from_rgb_f32(to_rgb_f32(0xFF336699)), 0xFF336699);
+/// ```
+#[inline]
+pub fn from_rgb_f32([r, g, b]: [f32; 3]) -> u32 {
+    pack_color(r, g, b, 1.0)
+}
+
+/// Pack RGB components into an opaque ARGB8888 color using a custom
+/// quantization threshold.
+///
+/// Each channel becomes `floor(c * 255 + threshold)`, clamped to [0, 255].
+/// A threshold of 0.5 is ordinary rounding; varying it per pixel (e.g. from
+/// a Bayer matrix) turns the quantization error into ordered dither.
+#[inline]
+pub fn pack_color_dithered(r: f32, g: f32, b: f32, threshold: f32) -> u32 {
+    let q = |c: f32| (c * 255.0 + threshold).floor().clamp(0.0, 255.0) as u32;
+    0xFF000000 | (q(r) << 16) | (q(g) << 8) | q(b)
+}
+
+/// Convert a hue/saturation/value color into an opaque ARGB8888 color.
+///
+/// `hue` is in degrees and wraps, so any value is valid: 0 is red, 120
+/// green, 240 blue. `saturation` and `value` are clamped to [0.0, 1.0].
+/// Stepping the hue gives evenly spaced palettes for picking or debug
+/// coloring.
+///
+/// ```
+/// use russsty::colors::from_hsv;
+///
+/// assert_eq!(from_hsv(0.0, 1.0, 1.0), 0xFFFF0000);
+/// assert_eq!(from_hsv(-120.0, 1.0, 1.0), 0xFF0000FF);
+/// assert_eq!(from_hsv(42.0, 0.0, 0.5), 0xFF808080);
+/// ```
+pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> u32 {
+    let s = saturation.clamp(0.0, 1.0);
+    let v = value.clamp(0.0, 1.0);
+    // Sector 0..6 around the color wheel and how far into it we are
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let chroma = v * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = v - chroma;
+    pack_color(r + m, g + m, b + m, 1.0)
+}
+
+/// `color` with its alpha byte replaced by that of `alpha_from`.
+#[inline]
+pub fn with_alpha_of(color: u32, alpha_from: u32) -> u32 {
+    (color & 0x00FF_FFFF) | (alpha_from & 0xFF00_0000)
 }
 
 /// Linearly interpolates between two RGB colors.
@@ -66,6 +297,15 @@ pub fn pack_color(r: f32, g: f32, b: f32, a: f32) -> u32 {
 /// # Returns
 ///
 /// Interpolated RGB color as (r, g, b) tuple with components in [0, 1]
+///
+/// # Example
+///
+/// ```
+/// use russsty::colors::{lerp_color, pack_color, unpack_color};
+///
+/// let (r, g, b) = lerp_color(unpack_color(0xFF000000), unpack_color(0xFFFFFFFF), 0.5);
+/// assert_eq!(pack_color(r, g, b, 1.0), 0xFF808080);
+/// ```
 #[inline]
 pub fn lerp_color(c1: (f32, f32, f32), c2: (f32, f32, f32), t: f32) -> (f32, f32, f32) {
     (
@@ -74,3 +314,95 @@ pub fn lerp_color(c1: (f32, f32, f32), c2: (f32, f32, f32), t: f32) -> (f32, f32
         c1.2 + (c2.2 - c1.2) * t,
     )
 }
+
+/// Blend `src` over `dst` with the given coverage (0.0 = keep `dst`, 1.0 = `src`).
+///
+/// The result is opaque. Used for anti-aliased line drawing, where coverage
+/// is the fraction of the pixel the line covers.
+#[inline]
+pub fn blend(dst: u32, src: u32, coverage: f32) -> u32 {
+    let (r, g, b) = lerp_color(
+        unpack_color(dst),
+        unpack_color(src),
+        coverage.clamp(0.0, 1.0),
+    );
+    pack_color(r, g, b, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLOR: u32 = 0x80C06020;
+
+    #[test]
+    fn scale_rgb_boundaries() {
+        assert_eq!(scale_rgb(COLOR, 0.0), 0x80000000);
+        assert_eq!(scale_rgb(COLOR, 1.0), COLOR);
+        assert_eq!(scale_rgb(COLOR, -1.0), 0x80000000);
+        assert_eq!(scale_rgb(COLOR, f32::NAN), 0x80000000);
+        // 0xC0 and 0x60 saturate, 0x20 * 4 = 0x80 fits
+        assert_eq!(scale_rgb(COLOR, 4.0), 0x80FFFF80);
+        assert_eq!(scale_rgb(0xFFFFFFFF, f32::INFINITY), 0xFFFFFFFF);
+    }
+
+    #[test]
+    fn modulate_saturates_instead_of_wrapping() {
+        for intensity in [0.0, 0.5, 1.0, 1.5, 4.0, -2.0] {
+            assert_eq!(modulate(COLOR, intensity), scale_rgb(COLOR, intensity));
+        }
+        assert_eq!(modulate(0xFF808080, 4.0), 0xFFFFFFFF);
+    }
+
+    #[test]
+    fn add_saturating_clips_each_channel() {
+        assert_eq!(add_saturating(COLOR, 0), COLOR);
+        assert_eq!(add_saturating(COLOR, 0xFF404040), 0x80FFA060);
+        assert_eq!(add_saturating(0xFFFFFFFF, 0xFFFFFFFF), 0xFFFFFFFF);
+        // Alpha comes from the first color only
+        assert_eq!(add_saturating(0x00000000, 0xFF010203), 0x00010203);
+    }
+
+    #[test]
+    fn pack_color_clamps_out_of_range_components() {
+        assert_eq!(pack_color(1.0, 0.0, 0.5, 1.0), 0xFFFF0080);
+        assert_eq!(pack_color(4.0, -1.0, 2.0, 1.0), 0xFFFF00FF);
+    }
+
+    #[test]
+    fn pack_unpack_round_trips_every_channel_value() {
+        for v in 0..=255u32 {
+            let color = 0xFF000000 | (v << 16) | ((255 - v) << 8) | (v / 2);
+            let (r, g, b) = unpack_color(color);
+            assert_eq!(pack_color(r, g, b, 1.0), color);
+            assert_eq!(from_rgb_f32(to_rgb_f32(color)), color);
+        }
+    }
+
+    #[test]
+    fn hsv_primaries_and_wrapping() {
+        assert_eq!(from_hsv(0.0, 1.0, 1.0), 0xFFFF0000);
+        assert_eq!(from_hsv(60.0, 1.0, 1.0), 0xFFFFFF00);
+        assert_eq!(from_hsv(180.0, 1.0, 1.0), 0xFF00FFFF);
+        assert_eq!(from_hsv(300.0, 1.0, 1.0), 0xFFFF00FF);
+        assert_eq!(from_hsv(360.0 + 240.0, 1.0, 1.0), 0xFF0000FF);
+        assert_eq!(from_hsv(90.0, 1.0, 0.0), 0xFF000000);
+        // Out-of-range saturation and value clamp
+        assert_eq!(from_hsv(0.0, 3.0, 2.0), 0xFFFF0000);
+        assert_eq!(from_hsv(200.0, -1.0, 1.0), 0xFFFFFFFF);
+    }
+
+    #[test]
+    fn lerp_color_hits_both_ends() {
+        let (a, b) = (unpack_color(0xFF102030), unpack_color(0xFFF0E0D0));
+        assert_eq!(lerp_color(a, b, 0.0), a);
+        assert_eq!(lerp_color(a, b, 1.0), b);
+    }
+
+    #[test]
+    fn rgb_arrays_round_trip_opaque() {
+        assert_eq!(to_rgb_f32(0xFFFF0080)[..2], [1.0, 0.0]);
+        // Alpha is dropped on unpacking and comes back opaque
+        assert_eq!(from_rgb_f32(to_rgb_f32(COLOR)), 0xFFC06020);
+    }
+}
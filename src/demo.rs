@@ -0,0 +1,611 @@
+//! The interactive demo: the F-22 on a turntable, with every engine
+//! setting on a key. Shared by the `russsty` binary (SDL2) and the `winit`
+//! example, which hand [`run`] their own window.
+
+use std::f32::consts::FRAC_PI_2;
+use std::time::Duration;
+
+use russsty::animation::{Animator, CameraPath};
+use russsty::camera::{
+    Camera, CameraMode, CameraShake, FpsCameraController, OrbitCamera, OrbitCameraController,
+    SceneCamera,
+};
+use russsty::colors::RenderPalette;
+use russsty::engine::{
+    DebugDraw, DebugView, Engine, HiddenSurface, RasterizerType, RetroSettings, SelectionOutline,
+    SilhouetteOutline, TextureMode,
+};
+use russsty::light::SphericalLight;
+use russsty::loading::{LoadHandle, LoadStatus};
+use russsty::math::vec3::Vec3;
+use russsty::post::{PostEffect, Vignette};
+use russsty::window::{Action, GameLoop, WindowBackend, WindowEvent};
+use russsty::{Error, Model, ShadingMode, WireMesh};
+
+/// Length of a K-key recording: a few seconds of turntable at 30-60 FPS.
+const RECORD_FRAMES: u32 = 120;
+const RECORD_DIR: &str = "recording";
+/// Where F12 screenshots go.
+const SCREENSHOT_DIR: &str = "screenshots";
+
+/// How often the profiler (J) prints its summary.
+const PROFILE_LOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How far one +/- press zooms the field of view.
+const FOV_STEP_DEGREES: f32 = 5.0;
+
+/// Outline drawn around the mesh picked with a left click.
+const SELECTION_COLOR: u32 = 0xFFFFA000;
+const SELECTION_THICKNESS: u32 = 2;
+
+/// Collision capsule drawn around the model with the bounding boxes (N).
+const COLLISION_COLOR: u32 = 0xFFFF60C0;
+
+/// Light direction gizmo, shown while L is held: an arrow this long
+/// pointing at the middle of the scene.
+const LIGHT_GIZMO_COLOR: u32 = 0xFFFFE040;
+const LIGHT_GIZMO_LENGTH: f32 = 4.0;
+/// Radians the light turns per pixel of mouse movement.
+const LIGHT_AIM_SENSITIVITY: f32 = 0.01;
+
+/// Time each frame spends filling triangles in progressive mode (I), so a
+/// heavy scene paints in over several frames without stalling input.
+const PROGRESSIVE_BUDGET_MS: f32 = 12.0;
+
+/// Finish a recording and report where the frames went.
+fn stop_recording(engine: &mut Engine) -> Result<(), Error> {
+    if let Some(stats) = engine.stop_recording()? {
+        println!(
+            "Recorded {} frames to {}/ ({} dropped)",
+            stats.written, RECORD_DIR, stats.dropped
+        );
+    }
+    Ok(())
+}
+
+/// A looping fly-through circling `center` in three keyframes, for
+/// recordings (Y to start and stop).
+fn fly_around(center: Vec3) -> CameraPath {
+    let target = center + Vec3::new(0.0, 0.5, 0.0);
+    let start = center + Vec3::new(0.0, 2.0, -10.0);
+    CameraPath::new(true)
+        .key(0.0, start, target)
+        .key(4.0, center + Vec3::new(9.0, 4.0, 5.0), target)
+        .key(8.0, center + Vec3::new(-9.0, 1.0, 5.0), target)
+        .key(12.0, start, target)
+}
+
+/// The demo scene, also written out in `scene.ron`.
+fn build_demo_scene(engine: &mut Engine) -> LoadHandle {
+    // Load the jet in the background; `finish_demo_model` dresses it once
+    // it's in
+    let handle = engine.load_model_async("f22", "assets/f22.obj");
+
+    // Start with texture mode enabled so we can see it
+    engine.set_texture_mode(TextureMode::Replace);
+
+    // On-screen stats overlay (toggle with H)
+    engine.draw_debug_hud(true);
+
+    // Record face IDs so left-click can report what is under the cursor
+    engine.set_picking(true);
+
+    // Position camera to see the mesh
+    engine.camera_mut().set_position(Vec3::new(0.0, 2.0, -10.0));
+
+    handle
+}
+
+/// Texture and spin the demo model once it has loaded.
+fn finish_demo_model(engine: &mut Engine) {
+    engine.load_texture_async(Some("f22"), "assets/f22.png");
+
+    // Turntable spin at 90°/sec, independent of frame rate
+    if let Some(model) = engine.model_mut("f22") {
+        model.set_animator(Animator::new().spin(Vec3::UP, FRAC_PI_2));
+    }
+}
+
+/// What mouse movement drives.
+enum InputMode {
+    Camera,
+    /// L is held: the mouse turns the light around the scene.
+    AimLight(SphericalLight),
+}
+
+/// The middle of the demo model, or the origin before it's loaded.
+fn scene_center(engine: &Engine) -> Vec3 {
+    engine
+        .model("f22")
+        .map(|m| m.transform().position())
+        .unwrap_or(Vec3::ZERO)
+}
+
+/// A capsule standing around `model`'s bounds, as a stand-in collision
+/// volume.
+fn collision_capsule(model: &Model) -> Option<WireMesh> {
+    let (min, max) = model.bounding_box()?;
+    let size = max - min;
+    let radius = size.x.max(size.z) * 0.5;
+    let mut capsule = WireMesh::capsule(radius, (size.y * 0.5 - radius).max(0.0), 24);
+    capsule.translate((min + max) * 0.5);
+    Some(capsule)
+}
+
+/// Set the scene up from a RON or JSON file, warning about fields the
+/// loader skipped.
+#[cfg(feature = "serde")]
+fn load_scene(engine: &mut Engine, path: &str) -> Result<(), Error> {
+    let scene = engine.load_scene(path)?;
+    for field in scene.unknown_fields() {
+        eprintln!("warning: {path}: ignoring unknown field `{field}`");
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "serde"))]
+fn load_scene(_engine: &mut Engine, _path: &str) -> Result<(), Error> {
+    Err(Error::InvalidArgument(
+        "scene files need the `serde` feature",
+    ))
+}
+
+fn format_window_title(
+    fps: f64,
+    game_loop: &GameLoop,
+    engine: &Engine,
+    mouse_captured: bool,
+) -> String {
+    let timer = game_loop.frame_timer();
+    let recording = match engine.recorder() {
+        Some(recorder) => {
+            let stats = recorder.stats();
+            format!(
+                " | REC {}/{} (dropped {})",
+                stats.captured,
+                recorder.max_frames(),
+                stats.dropped
+            )
+        }
+        None => String::new(),
+    };
+    let painting = match engine.incremental_progress() {
+        Some(progress) => format!(" | paint {:.0}%", progress * 100.0),
+        None => String::new(),
+    };
+    let profile = match engine.frame_profile() {
+        Some(profile) => format!(" | {}", profile.summary()),
+        None => String::new(),
+    };
+    let frozen = if engine.culling_frozen() {
+        " | culling frozen (F3)"
+    } else {
+        ""
+    };
+    let retro = if engine.retro_mode().is_some() {
+        " | retro (F4)"
+    } else {
+        ""
+    };
+    format!(
+        "Russsty{recording}{painting}{profile}{frozen}{retro} | FPS: {:.1} (1% low: {:.1}, {:.1} ms, cap: {}, sim {:.1} ms, draw {:.1} ms) | {} | SSAA: {}x | Cam: {} | Cull: {} | render: {:?} | shade: {:?} | tex: {:?} | {}",
+        fps,
+        timer.one_percent_low_fps().unwrap_or(0.0),
+        timer.frame_time() * 1000.0,
+        match timer.target_fps() {
+            Some(target) => format!("{target:.0}"),
+            None => "off".to_string(),
+        },
+        game_loop.update_time().as_secs_f64() * 1000.0,
+        game_loop.render_time().as_secs_f64() * 1000.0,
+        engine.rasterizer_name(),
+        engine.supersampling(),
+        match engine.camera() {
+            SceneCamera::Fps(camera) if camera.mode() == CameraMode::FreeFlight => {
+                "Free flight (F1)"
+            }
+            SceneCamera::Fps(_) => "FPS",
+            SceneCamera::Orbit(_) => "Orbit (O)",
+        },
+        if engine.backface_culling { "ON" } else { "OFF" },
+        engine.render_mode(),
+        engine.shading_mode(),
+        engine.texture_mode(),
+        if mouse_captured {
+            "WASD to move, mouse to look, M/RMB to release"
+        } else {
+            "M/RMB to capture mouse"
+        }
+    )
+}
+
+/// Run the demo in `window` until it's closed or Escape is pressed.
+pub fn run<W: WindowBackend>(mut window: W) -> Result<(), Error> {
+    let mut engine = Engine::new(window.width(), window.height());
+
+    // A scene file named on the command line replaces the built-in demo
+    let demo_model = match std::env::args().nth(1) {
+        Some(scene_path) => {
+            load_scene(&mut engine, &scene_path)?;
+            None
+        }
+        None => Some(build_demo_scene(&mut engine)),
+    };
+
+    let mut capsule = engine.model("f22").and_then(collision_capsule);
+
+    // Pick up re-exported models and textures without a restart
+    engine.enable_hot_reload(true);
+
+    // Sitting still costs next to nothing: unchanged frames show the last
+    engine.set_skip_unchanged_frames(true);
+
+    // Quiet until F2 sets it off
+    engine.set_camera_shake(CameraShake::default());
+
+    // Camera controllers for FPS-style movement and model inspection
+    let mut camera_controller = FpsCameraController::default();
+    let orbit_controller = OrbitCameraController::default();
+
+    // The camera not currently in use, swapped in by the O key
+    let mut inactive_camera: Option<SceneCamera> = None;
+
+    // Where the camera stood when F3 froze culling, to jump back on thaw
+    let mut culling_camera: Option<SceneCamera> = None;
+
+    // Animation and fly-throughs step at a fixed rate, apart from drawing
+    let mut game_loop = GameLoop::default();
+
+    // Progressive mode (I) paints heavy frames in over several frames
+    let mut progressive = false;
+
+    // Holding L hands the mouse to the light until it's let go
+    let mut input_mode = InputMode::Camera;
+
+    'running: loop {
+        for event in window.poll_events() {
+            match event {
+                WindowEvent::Quit => break 'running,
+                WindowEvent::Action {
+                    action: Action::Quit,
+                    ..
+                } => break 'running, // Escape by default
+                WindowEvent::Resize(w, h) => {
+                    window.resize(w, h);
+                    engine.resize(w, h);
+                }
+                WindowEvent::RightMouseDown => window.toggle_mouse_capture(),
+                WindowEvent::LeftMouseDown => {
+                    // Into frame pixels, in case the frame is letterboxed
+                    let (x, y) = window.input_state().cursor_position;
+                    let hit = window
+                        .window_to_frame(x, y)
+                        .and_then(|(x, y)| engine.pick(x as i32, y as i32));
+                    match hit {
+                        Some(hit) => {
+                            println!(
+                                "Picked mesh {} face {} at ({x}, {y}), depth {:.2}",
+                                hit.mesh_index, hit.face_index, hit.depth
+                            );
+                            engine.set_selection_outline(SelectionOutline {
+                                model_index: hit.model_index,
+                                mesh_index: hit.mesh_index,
+                                color: SELECTION_COLOR,
+                                thickness: SELECTION_THICKNESS,
+                            });
+                        }
+                        None => {
+                            println!("Picked nothing at ({x}, {y})");
+                            engine.clear_selection_outline();
+                        }
+                    }
+                }
+                WindowEvent::Action { action, .. } => {
+                    // Whatever the key changed shows from a fresh frame
+                    engine.restart_incremental();
+                    match action {
+                        Action::SetRenderMode(mode) => {
+                            // Every mesh in one mode; the same key again gives
+                            // meshes back their own
+                            let mode =
+                                (engine.render_mode_override() != Some(mode)).then_some(mode);
+                            engine.set_render_mode_override(mode);
+                        }
+                        Action::ToggleCulling => engine.backface_culling = !engine.backface_culling,
+                        Action::ToggleGrid => engine.draw_grid = !engine.draw_grid,
+                        Action::CycleRasterizer => {
+                            let next = match engine.rasterizer() {
+                                RasterizerType::Scanline => RasterizerType::EdgeFunction,
+                                RasterizerType::EdgeFunction
+                                    if engine.custom_rasterizer().is_some() =>
+                                {
+                                    RasterizerType::Custom
+                                }
+                                RasterizerType::EdgeFunction | RasterizerType::Custom => {
+                                    RasterizerType::Scanline
+                                }
+                            };
+                            engine.set_rasterizer(next);
+                        }
+                        Action::CycleShading => {
+                            let next = match engine.shading_mode() {
+                                ShadingMode::None => ShadingMode::Flat,
+                                ShadingMode::Flat => ShadingMode::Gouraud,
+                                ShadingMode::Gouraud => ShadingMode::Toon { bands: 3 },
+                                ShadingMode::Toon { .. } => ShadingMode::None,
+                            };
+                            engine.set_shading_mode(next);
+                            // Cel shading reads best inked
+                            let toon = matches!(next, ShadingMode::Toon { .. });
+                            engine.set_silhouette_outline(toon.then(SilhouetteOutline::default));
+                        }
+                        Action::CycleTexture => {
+                            let next = match engine.texture_mode() {
+                                TextureMode::None => TextureMode::Replace,
+                                TextureMode::Replace => TextureMode::Modulate,
+                                TextureMode::Modulate => TextureMode::SphereMap,
+                                TextureMode::SphereMap => TextureMode::Checker { scale: 2.0 },
+                                TextureMode::Checker { .. } => TextureMode::VertexColor,
+                                TextureMode::VertexColor => TextureMode::NormalMapped,
+                                TextureMode::NormalMapped => TextureMode::None,
+                            };
+                            engine.set_texture_mode(next);
+                        }
+                        Action::CycleDebugView => {
+                            let next = match engine.debug_view() {
+                                DebugView::None => DebugView::Barycentric,
+                                DebugView::Barycentric => DebugView::Uv,
+                                DebugView::Uv => DebugView::Overdraw,
+                                DebugView::Overdraw => DebugView::Normals,
+                                DebugView::Normals => DebugView::None,
+                            };
+                            engine.set_debug_view(next);
+                        }
+                        Action::CycleDebugDraw => {
+                            // Off, bounding boxes, normals, both
+                            let draw = engine.debug_draw();
+                            let (aabb, normals) = match (draw.aabb, draw.normals) {
+                                (false, false) => (true, false),
+                                (true, false) => (false, true),
+                                (false, true) => (true, true),
+                                (true, true) => (false, false),
+                            };
+                            engine.set_debug_draw(DebugDraw {
+                                aabb,
+                                normals,
+                                ..draw
+                            });
+                        }
+                        Action::ToggleHiddenSurface => {
+                            let next = match engine.hidden_surface() {
+                                HiddenSurface::DepthBuffer => HiddenSurface::PainterSort,
+                                HiddenSurface::PainterSort => HiddenSurface::DepthBuffer,
+                            };
+                            engine.set_hidden_surface(next);
+                        }
+                        Action::ToggleMouseCapture => window.toggle_mouse_capture(),
+                        Action::ToggleWireframeAa => {
+                            engine.set_wireframe_aa(!engine.wireframe_aa())
+                        }
+                        Action::ToggleHud => engine.draw_debug_hud(!engine.debug_hud()),
+                        Action::ToggleProfiler => {
+                            engine.set_profiling(!engine.profiling());
+                            if let Some(profile) = engine.frame_profile_mut() {
+                                profile.set_log_interval(Some(PROFILE_LOG_INTERVAL));
+                            }
+                        }
+                        Action::TogglePalette => {
+                            let next = if *engine.palette() == RenderPalette::default() {
+                                RenderPalette::light()
+                            } else {
+                                RenderPalette::default()
+                            };
+                            engine.set_palette(next);
+                        }
+                        Action::ToggleSupersampling => {
+                            let next = if engine.supersampling() > 1 { 1 } else { 2 };
+                            engine.set_supersampling(next);
+                        }
+                        Action::CycleFrameCap => {
+                            // Cycle the frame cap: 60 -> 30 (recording) -> uncapped (benchmarking)
+                            let timer = game_loop.frame_timer_mut();
+                            let next = match timer.target_fps() {
+                                Some(fps) if fps > 45.0 => Some(30.0),
+                                Some(_) => None,
+                                None => Some(60.0),
+                            };
+                            timer.set_target_fps(next);
+                        }
+                        Action::TogglePostEffect => {
+                            let effect: Option<Box<dyn PostEffect>> = match engine.post_effect() {
+                                Some(_) => None,
+                                None => Some(Box::new(Vignette::default())),
+                            };
+                            engine.set_post_effect(effect);
+                        }
+                        // Saves the frame on screen, drawn before this event
+                        Action::Screenshot => match engine.save_screenshot(SCREENSHOT_DIR) {
+                            Ok(path) => println!("Saved screenshot to {}", path.display()),
+                            Err(e) => eprintln!("Screenshot failed: {e}"),
+                        },
+                        Action::ToggleRecording => {
+                            if engine.recorder().is_some() {
+                                stop_recording(&mut engine)?;
+                            } else {
+                                engine.start_recording(RECORD_DIR, RECORD_FRAMES)?;
+                            }
+                        }
+                        Action::ToggleCameraPath => {
+                            if engine.clear_camera_path().is_none() {
+                                engine.set_camera_path(fly_around(scene_center(&engine)));
+                            }
+                            camera_controller.stop();
+                        }
+                        // Zoom by narrowing or widening the field of view
+                        Action::ZoomIn => {
+                            engine.set_fov_degrees(engine.fov_degrees() - FOV_STEP_DEGREES)
+                        }
+                        Action::ZoomOut => {
+                            engine.set_fov_degrees(engine.fov_degrees() + FOV_STEP_DEGREES)
+                        }
+                        Action::ToggleProgressive => progressive = !progressive,
+                        Action::SwitchCamera => {
+                            let next = inactive_camera.take().unwrap_or_else(|| {
+                                // First switch: orbit the model from where we stand
+                                let target = engine
+                                    .model("f22")
+                                    .map(|m| m.transform().position())
+                                    .unwrap_or(Vec3::ZERO);
+                                OrbitCamera::from_position(engine.camera().position(), target)
+                                    .into()
+                            });
+                            inactive_camera = Some(engine.set_camera(next));
+                        }
+                        // Level FPS look or full six degrees of freedom (Q/E roll)
+                        Action::ToggleCameraMode => {
+                            if let Some(camera) = engine.camera_mut().as_fps_mut() {
+                                let next = match camera.mode() {
+                                    CameraMode::FpsYawPitch => CameraMode::FreeFlight,
+                                    CameraMode::FreeFlight => CameraMode::FpsYawPitch,
+                                };
+                                camera.set_mode(next);
+                            }
+                        }
+                        Action::ShakeCamera => {
+                            if let Some(shake) = engine.camera_shake_mut() {
+                                shake.trigger(1.0);
+                            }
+                        }
+                        // Freeze culling and fly out to watch it; again to go back
+                        Action::FreezeCulling => match culling_camera.take() {
+                            Some(camera) => {
+                                engine.unfreeze_culling();
+                                engine.set_debug_draw_frustum(false);
+                                engine.set_camera(camera);
+                            }
+                            None => {
+                                engine.freeze_culling();
+                                engine.set_debug_draw_frustum(true);
+                                culling_camera = Some(engine.camera().clone());
+                                if let Some(camera) = engine.camera_mut().as_fps_mut() {
+                                    camera.set_mode(CameraMode::FreeFlight);
+                                }
+                            }
+                        },
+                        Action::ToggleRetro => {
+                            let retro = match engine.retro_mode() {
+                                Some(_) => None,
+                                None => Some(RetroSettings::default()),
+                            };
+                            engine.set_retro_mode(retro);
+                        }
+                        Action::Quit => {}
+                    }
+                }
+                // Keys without a binding
+                WindowEvent::KeyPress(_) => {}
+            }
+        }
+
+        // Input moves the camera by the last frame's time
+        let delta_time_sec = game_loop.frame_timer().frame_time();
+
+        input_mode = match (input_mode, window.input_state().aim_light) {
+            (InputMode::Camera, true) => {
+                camera_controller.stop();
+                InputMode::AimLight(SphericalLight::new(engine.light()))
+            }
+            (_, false) => InputMode::Camera,
+            (mode, true) => mode,
+        };
+        if let InputMode::AimLight(light) = &mut input_mode {
+            // Sideways turns the light around the scene, up raises it
+            let (dx, dy) = window.input_state().mouse_delta;
+            if (dx, dy) != (0, 0) {
+                light.rotate(
+                    -dx as f32 * LIGHT_AIM_SENSITIVITY,
+                    -dy as f32 * LIGHT_AIM_SENSITIVITY,
+                );
+                engine.set_light(light.light());
+            }
+        }
+
+        // A fly-through (Y) takes over the camera until it's stopped, and
+        // aiming the light holds it still
+        let aiming = matches!(input_mode, InputMode::AimLight(_));
+        if !aiming && engine.camera_path().is_none_or(|path| path.is_finished()) {
+            match engine.camera_mut() {
+                // FPS camera only flies while the mouse is captured; the wheel always zooms
+                SceneCamera::Fps(camera) => {
+                    if window.is_mouse_captured() {
+                        camera_controller.update(camera, window.input_state(), delta_time_sec);
+                    } else {
+                        camera_controller.stop();
+                        camera_controller.zoom(camera, window.input_state());
+                    }
+                }
+                SceneCamera::Orbit(camera) => orbit_controller.update(camera, window.input_state()),
+            }
+        }
+
+        game_loop.frame(
+            &mut engine,
+            |engine, dt| {
+                engine.animate(dt);
+                engine.update_path(dt);
+            },
+            |engine, _alpha| {
+                // Follows the model's transform, hidden behind its body
+                if let Some(capsule) = capsule.as_ref().filter(|_| engine.debug_draw().aabb) {
+                    let transform = *engine.model("f22").unwrap().transform();
+                    engine.draw_wire_mesh(capsule, &transform, COLLISION_COLOR);
+                }
+                if let InputMode::AimLight(light) = &input_mode {
+                    let center = scene_center(engine);
+                    engine.draw_arrow_3d(
+                        center - light.direction() * LIGHT_GIZMO_LENGTH,
+                        center,
+                        LIGHT_GIZMO_COLOR,
+                    );
+                }
+                if progressive {
+                    // Animation holds still until the frame is done; the
+                    // camera restarts it
+                    engine.render_incremental(PROGRESSIVE_BUDGET_MS);
+                } else {
+                    engine.update();
+                    engine.render();
+                }
+            },
+        );
+        for handle in engine.poll_loaded() {
+            match engine.load_status(handle) {
+                Some(LoadStatus::Failed(e)) => eprintln!("Loading failed: {e}"),
+                _ if Some(handle) == demo_model => {
+                    finish_demo_model(&mut engine);
+                    capsule = engine.model("f22").and_then(collision_capsule);
+                }
+                _ => {}
+            }
+        }
+        for event in engine.take_reload_events() {
+            println!("{event}");
+        }
+        let (frame_width, frame_height) = engine.frame_size();
+        window.present(engine.frame_buffer(), frame_width, frame_height)?;
+        if engine.recorder().is_some_and(|r| r.is_complete()) {
+            stop_recording(&mut engine)?;
+        }
+
+        if let Some(fps) = game_loop.fps() {
+            window.set_title(&format_window_title(
+                fps,
+                &game_loop,
+                &engine,
+                window.is_mouse_captured(),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+//! Scenes that check the renderer against answers worked out by hand.
+//!
+//! [`lighting_test_scene`] lights a sphere under a grid of ambient
+//! intensities and diffuse strengths, renders it headless and compares the
+//! pixel in the middle of each sphere with the Lambert term for the surface
+//! under it, so a change to the lighting math that shifts any of them fails
+//! a test rather than waiting to be noticed by eye:
+//!
+//! ```
+//! use russsty::diagnostics::{lighting_test_scene, LIGHTING_TOLERANCE};
+//!
+//! lighting_test_scene().render().assert_within(LIGHTING_TOLERANCE);
+//! ```
+//!
+//! The light is engine-wide, so each sphere is drawn on its own and the
+//! cells are tiled into one image, ambient down the rows and diffuse
+//! strength across the columns.
+
+use crate::camera::FpsCamera;
+use crate::colors::{self, RenderPalette};
+use crate::engine::{Engine, RenderMode, ShadingMode};
+use crate::light::DirectionalLight;
+use crate::math::vec3::Vec3;
+use crate::mesh::Mesh;
+use crate::model::Model;
+
+/// Width and height in pixels of each sphere's cell.
+pub const CELL_SIZE: u32 = 48;
+
+/// How far a measured intensity may stray from the expected one: a couple
+/// of 8-bit steps, for rounding and Gouraud interpolation between vertices.
+pub const LIGHTING_TOLERANCE: f32 = 0.01;
+
+const SPHERE_RADIUS: f32 = 1.0;
+const SPHERE_SEGMENTS: u32 = 64;
+const SPHERE_RINGS: u32 = 32;
+const CAMERA_DISTANCE: f32 = 4.0;
+
+/// A grid of lighting setups to render a sphere under; see the
+/// [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightingTestScene {
+    /// One row of spheres per ambient intensity.
+    pub ambients: Vec<f32>,
+    /// One column of spheres per diffuse strength.
+    pub diffuse_strengths: Vec<f32>,
+    /// The direction the light points, shared by every sphere.
+    pub light_direction: Vec3,
+    pub shading: ShadingMode,
+}
+
+/// Four ambient intensities by four diffuse strengths, Gouraud shaded, lit
+/// from the upper right so the middle of each sphere catches the light at
+/// an angle.
+pub fn lighting_test_scene() -> LightingTestScene {
+    LightingTestScene {
+        ambients: vec![0.0, 0.1, 0.2, 0.4],
+        diffuse_strengths: vec![0.25, 0.5, 0.75, 1.0],
+        light_direction: Vec3::new(-1.0, -1.0, 1.0),
+        shading: ShadingMode::Gouraud,
+    }
+}
+
+impl Default for LightingTestScene {
+    fn default() -> Self {
+        lighting_test_scene()
+    }
+}
+
+impl LightingTestScene {
+    /// Draw every sphere and measure the middle of each.
+    pub fn render(&self) -> LightingReport {
+        let mut engine = Engine::new(CELL_SIZE, CELL_SIZE);
+        engine.draw_grid = false;
+        // White on black, so a pixel's brightness is its light level
+        engine.set_palette(RenderPalette {
+            background: 0xFF000000,
+            fill: 0xFFFFFFFF,
+            ..RenderPalette::default()
+        });
+        engine.set_render_mode(RenderMode::Filled);
+        engine.set_shading_mode(self.shading);
+        engine.set_camera(FpsCamera::looking_at(
+            Vec3::new(0.0, 0.0, -CAMERA_DISTANCE),
+            Vec3::ZERO,
+        ));
+        let mut model = Model::new("sphere");
+        model.add_mesh(Mesh::uv_sphere(
+            "sphere",
+            SPHERE_RADIUS,
+            SPHERE_SEGMENTS,
+            SPHERE_RINGS,
+        ));
+        engine.push_model("sphere", model);
+
+        let middle = (CELL_SIZE / 2) as i32;
+        let normal =
+            sphere_normal_under(&engine, middle, middle).expect("the middle pixel sees the sphere");
+
+        let (columns, rows) = (self.diffuse_strengths.len(), self.ambients.len());
+        let (width, height) = (columns as u32 * CELL_SIZE, rows as u32 * CELL_SIZE);
+        let mut pixels = vec![0; (width * height) as usize];
+        let mut samples = Vec::with_capacity(columns * rows);
+        for (row, &ambient) in self.ambients.iter().enumerate() {
+            for (column, &diffuse_strength) in self.diffuse_strengths.iter().enumerate() {
+                engine.set_light(DirectionalLight {
+                    ambient_intensity: ambient,
+                    diffuse_strength,
+                    ..DirectionalLight::new(self.light_direction)
+                });
+                let cell = engine.render_offscreen();
+                let (left, top) = (column as u32 * CELL_SIZE, row as u32 * CELL_SIZE);
+                for (y, line) in cell.chunks_exact(CELL_SIZE as usize).enumerate() {
+                    let start = ((top + y as u32) * width + left) as usize;
+                    pixels[start..start + CELL_SIZE as usize].copy_from_slice(line);
+                }
+
+                let center = cell[(middle as u32 * CELL_SIZE + middle as u32) as usize];
+                samples.push(LightingSample {
+                    row,
+                    column,
+                    ambient,
+                    diffuse_strength,
+                    pixel: (left + middle as u32, top + middle as u32),
+                    expected: lambert(ambient, diffuse_strength, self.light_direction, normal),
+                    measured: brightness(center),
+                });
+            }
+        }
+
+        LightingReport {
+            samples,
+            pixels,
+            width,
+            height,
+        }
+    }
+}
+
+/// The measurement at the middle of one sphere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LightingSample {
+    pub row: usize,
+    pub column: usize,
+    pub ambient: f32,
+    pub diffuse_strength: f32,
+    /// Where the middle of the sphere is in [`LightingReport::pixels`].
+    pub pixel: (u32, u32),
+    /// The light level on a white surface there, from the Lambert term.
+    pub expected: f32,
+    /// The light level drawn there, 0 to 1.
+    pub measured: f32,
+}
+
+impl LightingSample {
+    /// How far the measured level is from the expected one.
+    pub fn error(&self) -> f32 {
+        (self.measured - self.expected).abs()
+    }
+
+    /// Whether the measured level is within `tolerance` of the expected
+    /// one; never for NaN.
+    pub fn within(&self, tolerance: f32) -> bool {
+        self.error() <= tolerance
+    }
+}
+
+/// The spheres of a [`LightingTestScene`] as drawn, and their
+/// measurements.
+#[derive(Debug, Clone)]
+pub struct LightingReport {
+    samples: Vec<LightingSample>,
+    pixels: Vec<u32>,
+    width: u32,
+    height: u32,
+}
+
+impl LightingReport {
+    /// One sample per sphere, row by row.
+    pub fn samples(&self) -> &[LightingSample] {
+        &self.samples
+    }
+
+    /// The sample furthest from its expected level.
+    pub fn worst(&self) -> Option<&LightingSample> {
+        self.samples
+            .iter()
+            .max_by(|a, b| a.error().total_cmp(&b.error()))
+    }
+
+    /// Panic, listing every sphere off by more than `tolerance`, if any
+    /// is.
+    pub fn assert_within(&self, tolerance: f32) {
+        let failures: Vec<String> = self
+            .samples
+            .iter()
+            .filter(|sample| !sample.within(tolerance))
+            .map(|sample| {
+                format!(
+                    "  ambient {:.2}, diffuse {:.2}: expected {:.3}, measured {:.3}",
+                    sample.ambient, sample.diffuse_strength, sample.expected, sample.measured
+                )
+            })
+            .collect();
+        assert!(
+            failures.is_empty(),
+            "{} of {} spheres are lit off by more than {tolerance}:\n{}",
+            failures.len(),
+            self.samples.len(),
+            failures.join("\n")
+        );
+    }
+
+    /// The cells tiled together, ARGB row-major, e.g. to write out with
+    /// [`PngPresenter`](crate::window::PngPresenter) when a check fails.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+/// The normal of the sphere (the round one, not its triangles) where the
+/// ray through pixel (`x`, `y`) first meets it.
+fn sphere_normal_under(engine: &Engine, x: i32, y: i32) -> Option<Vec3> {
+    let ray = engine.screen_ray(x, y);
+    let direction = ray.direction.normalize();
+    let b = ray.origin.dot(direction);
+    let c = ray.origin.dot(ray.origin) - SPHERE_RADIUS * SPHERE_RADIUS;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = -b - discriminant.sqrt();
+    Some((ray.origin + direction * t).normalize())
+}
+
+/// Ambient plus diffuse strength times the cosine between the surface and
+/// the light, capped at full brightness.
+fn lambert(ambient: f32, diffuse_strength: f32, light_direction: Vec3, normal: Vec3) -> f32 {
+    let cosine = (-light_direction.normalize()).dot(normal).max(0.0);
+    (ambient.max(0.0) + diffuse_strength.max(0.0) * cosine).min(1.0)
+}
+
+/// A gray pixel's level, 0 to 1.
+fn brightness(pixel: u32) -> f32 {
+    let [r, g, b] = colors::to_rgb_f32(pixel);
+    (r + g + b) / 3.0
+}
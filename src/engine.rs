@@ -5,20 +5,43 @@
 //! rasterization.
 
 use std::collections::HashMap;
-
-use crate::camera::FpsCamera;
-use crate::clipper::{ClipSpaceClipper, ClipSpacePolygon, ClipSpaceVertex};
-use crate::colors;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::animation::CameraPath;
+use crate::camera::{Camera, CameraShake, FpsCamera, SceneCamera};
+use crate::colors::{self, RenderPalette};
+use crate::error::Error;
+use crate::fog::Fog;
 use crate::frustum::{Frustum, FrustumTest};
-use crate::light::DirectionalLight;
-use crate::mesh::{LoadError, Texel, Vertex};
+use crate::geometry::{GeometryProcessor, MeshView};
+use crate::hot_reload::{HotReload, ReloadEvent};
+use crate::light::{DirectionalLight, PixelLighting, PointLight};
+use crate::loading::{LoadHandle, LoadStatus, Loaded, Loader};
+use crate::lod::LodGroup;
+use crate::math::ray::Ray;
+use crate::mesh::{CullMode, Face, Mesh, Vertex};
 use crate::model::Model;
+use crate::post::PostEffect;
 use crate::prelude::{Mat4, Vec2, Vec3, Vec4};
-use crate::projection::Projection;
-use crate::render::{Rasterizer, RasterizerDispatcher, Renderer, ScreenVertex, Triangle};
-
-pub use crate::render::RasterizerType;
-use crate::texture::Texture;
+use crate::profile::{self, FrameProfile};
+use crate::projection::{FovAxis, Handedness, Projection};
+use crate::render::rasterizer::shader::DebugShader;
+use crate::render::renderer::{export_pixels, pixels_as_bytes, upscale_nearest, ByteOrder};
+use crate::render::target::PixelRays;
+use crate::render::{DepthEncoding, RasterizerDispatcher, Renderer};
+use crate::transform::Transform;
+
+pub use crate::render::{
+    BlendMode, DepthMode, DepthPass, Fragment, FrameBuffer, MaskTest, PixelShader, Rasterizer,
+    RasterizerType, RenderTarget, ScreenVertex, Triangle, MASK_SET,
+};
+use crate::texture::{Texture, TextureArena, TextureHandle, TextureRegion};
+use crate::viewport::Viewport;
+use crate::window::{
+    save_png, screenshot_path, FrameRecorder, FrameTimer, PresentError, RecordingStats,
+};
+use crate::wire_mesh::WireMesh;
 
 /// What primitives get drawn for each triangle.
 ///
@@ -36,6 +59,7 @@ use crate::texture::Texture;
 /// When only wireframe lines are drawn, `ShadingMode` and `TextureMode` are
 /// irrelevant — line drawing always uses `Triangle::color`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum RenderMode {
     /// Wireframe only (key: 1)
     Wireframe,
@@ -64,8 +88,11 @@ pub enum RenderMode {
 ///   texture sample is used verbatim, so `ShadingMode` has no visible effect.
 ///
 /// Lighting is precomputed once per frame in `Engine::update` and baked into
-/// `Triangle::vertex_colors`; the rasterizer never re-evaluates the light.
+/// `Triangle::vertex_colors`; the rasterizer never re-evaluates the light,
+/// except under `ShadingMode::Toon` and `TextureMode::NormalMapped`, which
+/// light every pixel.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum ShadingMode {
     /// No lighting applied. All three entries of `vertex_colors` are set to
     /// `Triangle::color` so downstream code can treat `vertex_colors`
@@ -80,6 +107,12 @@ pub enum ShadingMode {
     /// interpolated across the triangle via barycentric coordinates,
     /// producing a smooth gradient.
     Gouraud,
+    /// Cel shading: the diffuse term is evaluated per pixel from the
+    /// interpolated normal and snapped to `bands` evenly spaced levels, from
+    /// unlit to fully lit, so surfaces show hard-edged steps. Fewer than two
+    /// bands count as two. `vertex_colors` hold the unlit base color. Pair
+    /// with [`Engine::set_silhouette_outline`] for the inked look.
+    Toon { bands: u8 },
 }
 
 /// How a texture sample (if any) combines with the lit vertex color.
@@ -95,11 +128,16 @@ pub enum ShadingMode {
 /// | `None` | interpolated `vertex_colors` | full — this *is* the lit color |
 /// | `Replace` | texture sample (texel) | none — lighting is ignored |
 /// | `Modulate` | texel × interpolated `vertex_colors` | full — lighting tints the texel |
+/// | `SphereMap` | texel at the reflection-derived UV | none — the texture *is* the lighting |
+/// | `Checker` | procedural checker × interpolated `vertex_colors` | full — lighting tints the pattern |
+/// | `VertexColor` | interpolated mesh vertex colors × light | full — lighting shades the baked colors |
+/// | `NormalMapped` | base color × light at the normal-mapped normal | lit per pixel unless `None` |
 ///
 /// Naming note: `Replace` and `Modulate` mirror the classic fixed-function
 /// OpenGL `glTexEnv` terminology. Think of them as "texture only" (unlit)
 /// and "texture × light" (lit) respectively.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum TextureMode {
     /// No texture sampled; the lit `vertex_colors` (or `color` when
     /// `ShadingMode::None`) are used directly.
@@ -113,6 +151,180 @@ pub enum TextureMode {
     /// `vertex_colors`. This is the standard "textured and lit" path:
     /// the texture provides surface detail, lighting provides shading.
     Modulate,
+    /// Environment-mapped reflection. The mesh's own UVs are ignored; each
+    /// vertex gets a UV from reflecting the view direction about its
+    /// world-space normal and projecting the result onto a sphere map
+    /// (a "matcap"-style image of a mirrored ball). The sample is used
+    /// verbatim, as with `Replace`.
+    SphereMap,
+    /// Procedural 3D checkerboard, no texture or UVs needed. Cells are
+    /// `1 / scale` object-space units on a side, so the pattern sticks to
+    /// the surface as the mesh moves, and it is lit like `Modulate`.
+    Checker { scale: f32 },
+    /// The mesh's baked per-vertex colors (the `v x y z r g b` OBJ
+    /// extension) stand in for the palette fill, lit per `ShadingMode` and
+    /// interpolated across each face even under `Flat` and `None`. Meshes
+    /// without vertex colors use the palette fill.
+    VertexColor,
+    /// Tangent-space normal mapping with the map from
+    /// `Engine::set_normal_map`: the palette fill is lit per pixel at the
+    /// interpolated normal tilted by the sampled one, so flat geometry
+    /// shows relief that follows the light. Any `ShadingMode` but `None`
+    /// lights this way; without a normal map it falls back to the plain
+    /// lit fill. Needs UVs, and the tangents `Mesh::compute_tangents`
+    /// derives from them.
+    NormalMapped,
+}
+
+/// False-color views of what goes into shading, for tracking down whether
+/// a wrong-looking surface comes from its UVs, its normals or the
+/// rasterizer. Set with [`Engine::set_debug_view`]; any view but `None`
+/// replaces the texture and shading modes, the custom shader and fog for
+/// filled mesh triangles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugView {
+    /// Normal shading.
+    #[default]
+    None,
+    /// Screen-space barycentric weights λ₀, λ₁, λ₂ as red, green and blue:
+    /// each corner in its own pure color, blending across the face.
+    Barycentric,
+    /// Interpolated texture coordinates, u as red and v as green, wrapped
+    /// into [0, 1) so tiling shows as repeated ramps.
+    Uv,
+    /// Every pixel written adds [`OVERDRAW_STEP`] to a black frame, hidden
+    /// or not, so the most-overdrawn regions glow brightest (red, then
+    /// yellow, then white). With the depth pre-pass on, only the visible
+    /// surface is shaded and the frame comes out flat.
+    Overdraw,
+    /// Interpolated world-space normals, each axis mapped from [-1, 1] to a
+    /// color channel. Meshes without normals show their face normals.
+    Normals,
+}
+
+impl std::fmt::Display for DebugView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebugView::None => write!(f, "None"),
+            DebugView::Barycentric => write!(f, "Barycentric"),
+            DebugView::Uv => write!(f, "UV"),
+            DebugView::Overdraw => write!(f, "Overdraw"),
+            DebugView::Normals => write!(f, "Normals"),
+        }
+    }
+}
+
+/// Color each pixel write adds under [`DebugView::Overdraw`]. Red fills up
+/// after 5 layers, green after 10 and blue after 21.
+pub const OVERDRAW_STEP: u32 = 0xFF30180C;
+
+/// How filled triangles hide the ones behind them. Set with
+/// [`Engine::set_hidden_surface`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HiddenSurface {
+    /// Per-pixel depth testing against a depth buffer. Correct wherever
+    /// surfaces cross.
+    #[default]
+    DepthBuffer,
+    /// The painter's algorithm: triangles are sorted by the average view
+    /// depth of their corners and drawn back to front, each covering what
+    /// came before, with no depth buffer at all. Saves a buffer the size
+    /// of the frame, but interpenetrating or cyclically overlapping
+    /// triangles come out in the wrong order. Picking, the depth pre-pass
+    /// and silhouette outlines need depth and are skipped; wireframes,
+    /// billboards and the selection outline draw over every mesh.
+    PainterSort,
+}
+
+/// How filled pixels are lit. Set with [`Engine::set_pipeline`].
+///
+/// The two give the same picture; they differ in when lighting runs. A
+/// directional light alone is lit per vertex in the forward pipeline, so
+/// its Gouraud shading and the deferred pipeline's per-pixel lighting
+/// differ by a level or two where the light changes fastest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pipeline {
+    /// Light each fragment as it is shaded: per vertex for the
+    /// directional light, per fragment for point lights. Fragments later
+    /// covered by nearer ones are lit for nothing.
+    #[default]
+    Forward,
+    /// Shade unlit albedo and normals into a G-buffer, then light each
+    /// visible pixel once in a screen-space pass, so the cost of lighting
+    /// follows the pixel count rather than the overdraw. Toon bands are
+    /// lit smoothly, and debug views draw forward.
+    Deferred,
+}
+
+impl std::fmt::Display for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pipeline::Forward => write!(f, "Forward"),
+            Pipeline::Deferred => write!(f, "Deferred"),
+        }
+    }
+}
+
+impl std::fmt::Display for HiddenSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HiddenSurface::DepthBuffer => write!(f, "Depth buffer"),
+            HiddenSurface::PainterSort => write!(f, "Painter sort"),
+        }
+    }
+}
+
+/// How the reference grid is drawn when `Engine::draw_grid` is enabled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridMode {
+    /// Fixed 2D pixel grid over the whole screen; ignores the camera.
+    ScreenSpace,
+    /// Line grid on the world y=0 plane, centered under the camera and
+    /// depth-tested so meshes occlude it. Lines are `spacing` world units
+    /// apart and reach `extent` units from the camera along x and z.
+    WorldPlane { spacing: f32, extent: f32 },
+}
+
+impl Default for GridMode {
+    /// 41x41 lines, one unit apart.
+    fn default() -> Self {
+        GridMode::WorldPlane {
+            spacing: 1.0,
+            extent: 20.0,
+        }
+    }
+}
+
+impl RenderMode {
+    fn draws_filled(self) -> bool {
+        !matches!(self, RenderMode::Wireframe | RenderMode::WireframeVertices)
+    }
+
+    fn draws_wireframe(self) -> bool {
+        self != RenderMode::Filled
+    }
+
+    fn draws_vertices(self) -> bool {
+        matches!(
+            self,
+            RenderMode::WireframeVertices | RenderMode::FilledWireframeVertices
+        )
+    }
+
+    /// The [`Triangle::pass_flags`] of triangles drawn in this mode.
+    pub(crate) fn pass_flags(self) -> u8 {
+        let mut flags = 0;
+        if self.draws_filled() {
+            flags |= Triangle::FILL_PASS;
+        }
+        if self.draws_wireframe() {
+            flags |= Triangle::WIREFRAME_PASS;
+        }
+        if self.draws_vertices() {
+            flags |= Triangle::VERTEX_PASS;
+        }
+        flags
+    }
 }
 
 impl std::fmt::Display for ShadingMode {
@@ -121,6 +333,7 @@ impl std::fmt::Display for ShadingMode {
             ShadingMode::None => write!(f, "None"),
             ShadingMode::Flat => write!(f, "Flat"),
             ShadingMode::Gouraud => write!(f, "Gouraud"),
+            ShadingMode::Toon { bands } => write!(f, "Toon ({bands} bands)"),
         }
     }
 }
@@ -131,28 +344,548 @@ impl std::fmt::Display for TextureMode {
             TextureMode::None => write!(f, "None"),
             TextureMode::Replace => write!(f, "Replace"),
             TextureMode::Modulate => write!(f, "Modulate"),
+            TextureMode::SphereMap => write!(f, "SphereMap"),
+            TextureMode::Checker { .. } => write!(f, "Checker"),
+            TextureMode::VertexColor => write!(f, "VertexColor"),
+            TextureMode::NormalMapped => write!(f, "NormalMapped"),
+        }
+    }
+}
+
+/// One mesh in the scene, by model and mesh index. See
+/// [`Engine::mesh_handle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MeshHandle {
+    /// Index into [`Engine::models`].
+    pub model_index: usize,
+    /// Index into that model's [`Model::meshes`].
+    pub mesh_index: usize,
+}
+
+/// An outline drawn around one mesh's visible pixels, e.g. to highlight
+/// what [`Engine::pick`] returned. See [`Engine::set_selection_outline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectionOutline {
+    /// Index of the model in [`Engine::models`].
+    pub model_index: usize,
+    /// Index of the mesh within that model.
+    pub mesh_index: usize,
+    /// Packed ARGB outline color.
+    pub color: u32,
+    /// Outline width in display pixels.
+    pub thickness: u32,
+}
+
+/// Debug geometry drawn over the scene for checking transforms and
+/// lighting. See [`Engine::set_debug_draw`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugDraw {
+    /// Outline each drawn mesh's bounding box, in the palette's `bounds`
+    /// color. The box is the mesh's local one carried through its
+    /// transform, so it turns with the mesh.
+    pub aabb: bool,
+    /// A line from every vertex along its world-space normal, colored by
+    /// direction (x, y, z mapped to red, green, blue).
+    pub normals: bool,
+    /// Length of the normal lines in world units.
+    pub normal_length: f32,
+}
+
+impl Default for DebugDraw {
+    /// Nothing drawn; normals a tenth of a unit long once enabled.
+    fn default() -> Self {
+        Self {
+            aabb: false,
+            normals: false,
+            normal_length: 0.1,
+        }
+    }
+}
+
+impl DebugDraw {
+    /// Queue the enabled lines for one placement of `mesh`.
+    fn lines_for(
+        &self,
+        mesh: &Mesh,
+        world_matrix: Mat4,
+        normal_matrix: Mat4,
+        bounds_color: u32,
+        lines: &mut Vec<DebugLine>,
+    ) {
+        if self.aabb {
+            let corners = mesh.aabb().corners().map(|c| world_matrix * c);
+            box_lines(corners, bounds_color, lines);
+        }
+        if self.normals {
+            for vertex in mesh.vertices() {
+                let normal = (normal_matrix * vertex.normal).normalize();
+                let start = world_matrix * vertex.position;
+                lines.push(DebugLine {
+                    a: start,
+                    b: start + normal * self.normal_length,
+                    color: colors::from_rgb_f32([
+                        normal.x * 0.5 + 0.5,
+                        normal.y * 0.5 + 0.5,
+                        normal.z * 0.5 + 0.5,
+                    ]),
+                });
+            }
+        }
+    }
+}
+
+/// Queue the 12 edges of a box given its corners, corner `i` at the max
+/// x, y, z where bits 0, 1, 2 are set.
+fn box_lines(corners: [Vec3; 8], color: u32, lines: &mut Vec<DebugLine>) {
+    // Each edge joins two corners a bit apart
+    for i in 0..8 {
+        for bit in [1, 2, 4] {
+            if i & bit == 0 {
+                lines.push(DebugLine {
+                    a: corners[i],
+                    b: corners[i | bit],
+                    color,
+                });
+            }
+        }
+    }
+}
+
+/// What frustum culling made of one placement of a mesh in the last
+/// [`Engine::update`], from its bounding sphere and box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CullResult {
+    /// Wholly inside the frustum.
+    Inside,
+    /// Drawn, but straddling at least one of the frustum's planes.
+    Intersecting,
+    /// Outside the frustum and skipped.
+    Culled,
+}
+
+/// The [`CullResult`] of one placement of a mesh. See
+/// [`Engine::cull_results`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshCull {
+    /// Index of the model in [`Engine::models`].
+    pub model_index: usize,
+    /// Index of the mesh within that model.
+    pub mesh_index: usize,
+    /// Which of the mesh's instances, for instanced meshes.
+    pub instance: Option<usize>,
+    pub result: CullResult,
+}
+
+/// A world-space segment drawn depth-tested over the meshes: from
+/// [`DebugDraw`], or queued with [`Engine::draw_line_3d`].
+#[derive(Debug, Clone, Copy)]
+struct DebugLine {
+    a: Vec3,
+    b: Vec3,
+    color: u32,
+}
+
+/// Lines drawn around mesh silhouettes and wherever a mesh overlaps itself
+/// or another one, for the inked look of toon shading. See
+/// [`Engine::set_silhouette_outline`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SilhouetteOutline {
+    /// Packed ARGB outline color.
+    pub color: u32,
+    /// Outline width in display pixels.
+    pub thickness: u32,
+}
+
+impl Default for SilhouetteOutline {
+    /// Black, two pixels wide.
+    fn default() -> Self {
+        Self {
+            color: 0xFF000000,
+            thickness: 2,
+        }
+    }
+}
+
+/// Deliberate artifacts of late-90s console hardware. See
+/// [`Engine::set_retro_mode`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetroSettings {
+    /// Cells across and down the grid projected vertices snap to. Small
+    /// grids make vertices jump between cells as things move, so polygons
+    /// wobble and their edges crawl.
+    pub virtual_resolution: (u32, u32),
+    /// Map textures linearly in screen space, without perspective
+    /// correction, so they swim and bend across polygons seen at an angle.
+    pub affine_textures: bool,
+    /// Bits kept per color channel, 1 to 8: 5 for 15-bit RGB555 color, 8
+    /// to leave colors alone.
+    pub color_depth: u8,
+}
+
+impl Default for RetroSettings {
+    /// A 320x240 grid, affine textures and RGB555 color.
+    fn default() -> Self {
+        Self {
+            virtual_resolution: (320, 240),
+            affine_textures: true,
+            color_depth: 5,
         }
     }
 }
 
+/// The surface under a screen point, as returned by [`Engine::pick`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickResult {
+    /// Index of the model in [`Engine::models`].
+    pub model_index: usize,
+    /// Index of the mesh within that model.
+    pub mesh_index: usize,
+    /// Index of the face within that mesh.
+    pub face_index: usize,
+    /// View-space depth (clip-space `w`) of the picked pixel.
+    pub depth: f32,
+}
+
+/// Per-frame counters: geometry from [`Engine::update`], pixels from
+/// [`Engine::render`].
+///
+/// Every face of every model is counted exactly once in `faces_total` and
+/// then in at most one of the rejection buckets. A face that survives
+/// becomes one or more rasterized triangles (clipping can split it).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Meshes across all models in the scene. An instanced mesh counts
+    /// once, however many copies it draws.
+    pub meshes_total: usize,
+    /// Copies drawn or culled across all instanced meshes.
+    pub instances_total: usize,
+    /// Instances skipped because their bounds were off screen. Their
+    /// faces are also counted in `faces_frustum_culled`.
+    pub instances_culled: usize,
+    /// Faces across all models in the scene, once per instance of
+    /// instanced meshes.
+    pub faces_total: usize,
+    /// Faces skipped because their model or mesh bounds were off screen.
+    pub faces_frustum_culled: usize,
+    /// Faces rejected by backface culling.
+    pub faces_backface_culled: usize,
+    /// Faces the clipper removed entirely, whether trivially or after
+    /// clipping.
+    pub faces_clipped_away: usize,
+    /// Faces wholly inside the view volume, which skipped clipping.
+    /// With the two counters below this covers every face that reached
+    /// the clipper.
+    pub faces_trivially_accepted: usize,
+    /// Faces wholly outside one clip plane, dropped without clipping.
+    /// Also counted in `faces_clipped_away`.
+    pub faces_trivially_rejected: usize,
+    /// Faces straddling the view volume that ran the full clipper.
+    pub faces_clipped: usize,
+    /// Faces left out by drawing a simplified level of detail in place of
+    /// the full mesh; see [`crate::lod`].
+    pub faces_lod_reduced: usize,
+    /// Faces skipped because they refer to a vertex their mesh doesn't
+    /// have. Loaders reject these, so only meshes built in code count here.
+    pub faces_invalid: usize,
+    /// Screen-space triangles handed to the rasterizer.
+    pub triangles_drawn: usize,
+    /// Screen-space triangles dropped for covering no pixel center while
+    /// under the minimum area; see [`Engine::set_min_triangle_area`]. Not
+    /// counted in `triangles_drawn`.
+    pub triangles_too_small: usize,
+    /// Pixels the filled pass ran a shader for. Hidden pixels drawn before
+    /// whatever covers them count too, unless a depth pre-pass is on; see
+    /// [`Engine::set_depth_prepass`].
+    pub pixels_shaded: usize,
+    /// Pixels lit after shading: every shaded fragment while point lights
+    /// are on, or each visible pixel once under [`Pipeline::Deferred`].
+    pub pixels_lit: usize,
+}
+
+impl std::ops::AddAssign for FrameStats {
+    /// Sum every counter, to combine stats gathered in parts.
+    fn add_assign(&mut self, other: Self) {
+        self.meshes_total += other.meshes_total;
+        self.instances_total += other.instances_total;
+        self.instances_culled += other.instances_culled;
+        self.faces_total += other.faces_total;
+        self.faces_frustum_culled += other.faces_frustum_culled;
+        self.faces_backface_culled += other.faces_backface_culled;
+        self.faces_clipped_away += other.faces_clipped_away;
+        self.faces_trivially_accepted += other.faces_trivially_accepted;
+        self.faces_trivially_rejected += other.faces_trivially_rejected;
+        self.faces_clipped += other.faces_clipped;
+        self.faces_lod_reduced += other.faces_lod_reduced;
+        self.faces_invalid += other.faces_invalid;
+        self.triangles_drawn += other.triangles_drawn;
+        self.triangles_too_small += other.triangles_too_small;
+        self.pixels_shaded += other.pixels_shaded;
+        self.pixels_lit += other.pixels_lit;
+    }
+}
+
+/// The nearest surface along a ray, as returned by [`Engine::raycast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Index of the model in [`Engine::models`].
+    pub model_index: usize,
+    /// Index of the mesh within that model.
+    pub mesh_index: usize,
+    /// Index of the face within that mesh.
+    pub face_index: usize,
+    /// World-space distance from the ray origin to the hit.
+    pub distance: f32,
+    /// Barycentric weights of the hit for the face's vertices `a, b, c`.
+    pub barycentric: [f32; 3],
+    /// World-space hit position.
+    pub world_point: Vec3,
+}
+
+/// Source face for a picking ID. ID `n` maps to `pick_targets[n - 1]`.
+#[derive(Debug, Clone, Copy)]
+struct PickTarget {
+    model_index: usize,
+    mesh_index: usize,
+    face_index: usize,
+}
+
+/// A camera-facing sprite queued by `Engine::draw_billboard`.
+#[derive(Debug, Clone, Copy)]
+struct Billboard {
+    position: Vec3,
+    size: f32,
+    region: Option<TextureRegion>,
+    color: u32,
+}
+
+/// The two triangles of a billboard quad, over corners bottom-left,
+/// top-left, top-right, bottom-right.
+const BILLBOARD_FACES: [Face; 2] = [Face::new(0, 1, 2), Face::new(0, 2, 3)];
+
+/// Triangles from one mesh, drawn with that mesh's texture.
+struct TriangleBatch {
+    model_index: usize,
+    mesh_index: usize,
+    triangles: Vec<Triangle>,
+}
+
+/// Carry a world-space segment through `view_projection` into `viewport`
+/// pixels, as `(x, y, w)` per endpoint. Only the near plane is clipped
+/// here; the line drawers clip to the framebuffer themselves. `None` when
+/// the segment is entirely behind it.
+fn project_line(
+    view_projection: &Mat4,
+    viewport: &Viewport,
+    a: Vec3,
+    b: Vec3,
+) -> Option<[(i32, i32, f32); 2]> {
+    let mut a = *view_projection * Vec4::from_vec3(a, 1.0);
+    let mut b = *view_projection * Vec4::from_vec3(b, 1.0);
+
+    // Near plane inside test: z + w >= 0
+    let da = a.z + a.w;
+    let db = b.z + b.w;
+    if da < 0.0 && db < 0.0 {
+        return None;
+    }
+    if da < 0.0 {
+        a = a + (b - a) * (da / (da - db));
+    } else if db < 0.0 {
+        b = b + (a - b) * (db / (db - da));
+    }
+    if a.w <= 0.0 || b.w <= 0.0 {
+        return None;
+    }
+
+    let to_screen = |p: Vec4| {
+        let screen = viewport.project(p.to_vec3() / p.w, p.w);
+        (screen.x as i32, screen.y as i32, screen.z)
+    };
+    Some([to_screen(a), to_screen(b)])
+}
+
+/// Reorder `batches` for the painter's algorithm: every triangle, whatever
+/// its mesh, farthest first by [`Triangle::avg_depth`], regrouped into
+/// runs from the same mesh so each still draws with its own texture. The
+/// sort is stable, so triangles at equal depth keep submission order.
+fn painter_sort(batches: Vec<TriangleBatch>) -> Vec<TriangleBatch> {
+    let mut triangles: Vec<(usize, usize, Triangle)> = batches
+        .into_iter()
+        .flat_map(|batch| {
+            let (model_index, mesh_index) = (batch.model_index, batch.mesh_index);
+            batch
+                .triangles
+                .into_iter()
+                .map(move |triangle| (model_index, mesh_index, triangle))
+        })
+        .collect();
+    for (_, _, triangle) in &mut triangles {
+        let [a, b, c] = triangle.points;
+        triangle.avg_depth = (a.w + b.w + c.w) / 3.0;
+    }
+    triangles.sort_by(|a, b| b.2.avg_depth.total_cmp(&a.2.avg_depth));
+
+    let mut sorted: Vec<TriangleBatch> = Vec::new();
+    for (model_index, mesh_index, triangle) in triangles {
+        match sorted.last_mut() {
+            Some(run) if run.model_index == model_index && run.mesh_index == mesh_index => {
+                run.triangles.push(triangle);
+            }
+            _ => sorted.push(TriangleBatch {
+                model_index,
+                mesh_index,
+                triangles: vec![triangle],
+            }),
+        }
+    }
+    sorted
+}
+
+/// Where filling stopped in the batched triangles: batch index, then
+/// triangle index within it.
+#[derive(Debug, Clone, Copy, Default)]
+struct FillCursor {
+    batch: usize,
+    triangle: usize,
+}
+
+/// A frame [`Engine::render_incremental`] is partway through.
+struct IncrementalFrame {
+    /// `Engine::batches_generation` of the triangles being drawn
+    generation: u64,
+    cursor: FillCursor,
+    finished: bool,
+    /// The frame so far, before the HUD and post effect go on top
+    canvas: Vec<u32>,
+}
+
+/// Triangles filled between checks of the incremental frame's deadline.
+const DEADLINE_CHECK_INTERVAL: usize = 64;
+
+/// Largest supported supersampling factor (16 samples per pixel).
+pub const MAX_SUPERSAMPLING: u32 = 4;
+
+/// Smallest [`Engine::set_resolution_scale`] accepted.
+pub const MIN_RESOLUTION_SCALE: f32 = 0.1;
+
+/// What a frame's image depends on besides the scene and settings behind
+/// [`Engine`]'s change counter, compared between frames to find the ones
+/// that can be skipped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FrameKey {
+    changes: u64,
+    view_projection: Mat4,
+    render_size: (u32, u32),
+    backface_culling: bool,
+    draw_grid: bool,
+}
+
 pub struct Engine {
     renderer: Renderer,
+    // Display (presented) size; the renderer runs at this times
+    // `resolution_scale`, times `supersampling`
+    display_width: u32,
+    display_height: u32,
+    supersampling: u32,
+    resolution_scale: f32,
+    // Display-size frame, box-filtered and/or upscaled from the render
+    // target; only used when the two sizes differ
+    resolved: Vec<u32>,
+    // Box-filtered frame at the scaled size, only used when supersampling
+    // and scaling at once
+    downsampled: Vec<u32>,
     rasterizer: RasterizerDispatcher,
-    // Triangles grouped by model index for per-model texture support
-    triangles_per_model: Vec<Vec<Triangle>>,
+    // Replaces the built-in shader selection for filled mesh triangles
+    custom_shader: Option<Box<dyn PixelShader + Send + Sync>>,
+    debug_view: DebugView,
+    hidden_surface: HiddenSurface,
+    // Triangles grouped by mesh for per-mesh and per-model textures
+    batches: Vec<TriangleBatch>,
+    // Bumped by every `update`, with the view-projection it built the
+    // batches for, so an incremental frame knows when they're stale
+    batches_generation: u64,
+    batches_view_projection: Mat4,
+    incremental: Option<IncrementalFrame>,
+    // Bumped by every setter and mutable accessor that can change the image
+    changes: u64,
+    skip_unchanged_frames: bool,
+    // What the last full update built the batches from, and whether it
+    // had billboards or lines queued
+    updated_from: Option<FrameKey>,
+    updated_queue: bool,
+    frame_changed: bool,
+    // The last fully drawn frame before the HUD and post effect, and what
+    // it was drawn from, for skipping unchanged frames
+    static_canvas: Vec<u32>,
+    static_canvas_key: Option<(u64, FrameKey)>,
+    // Sprites queued since the last `update`, then the triangles it built
+    billboards: Vec<Billboard>,
+    // From `draw_line_3d` and friends, until the next update
+    queued_lines: Vec<DebugLine>,
+    billboard_triangles: Vec<Triangle>,
+    billboard_texture: Option<Texture>,
+    billboard_blend: BlendMode,
     models: Vec<Model>,
     model_names: HashMap<String, usize>,
     // Global texture fallback (used when model doesn't have its own)
     global_texture: Option<Texture>,
-    camera: FpsCamera,
+    // Textures meshes share by handle
+    textures: TextureArena,
+    // Sampled instead of any color texture under `TextureMode::NormalMapped`
+    normal_map: Option<Texture>,
+    camera: SceneCamera,
+    // Fly-through driven by `update_path`
+    camera_path: Option<CameraPath>,
+    // Turns the view on top of the camera, advanced by `animate`
+    camera_shake: Option<CameraShake>,
     projection: Projection,
     projection_matrix: Mat4,
-    clipper: ClipSpaceClipper,
     render_mode: RenderMode,
+    // Beats every mesh's own mode while set
+    render_mode_override: Option<RenderMode>,
     texture_mode: TextureMode,
     shading_mode: ShadingMode,
     light: DirectionalLight,
+    point_lights: Vec<PointLight>,
+    pipeline: Pipeline,
+    dithering: bool,
+    fog: Option<Fog>,
+    selection_outline: Option<SelectionOutline>,
+    debug_draw: DebugDraw,
+    // Bounding box and normal lines from the last update
+    debug_lines: Vec<DebugLine>,
+    // Culls with this view-projection rather than the camera's while set
+    frozen_cull_view: Option<Mat4>,
+    debug_draw_frustum: bool,
+    cull_results: Vec<MeshCull>,
+    silhouette_outline: Option<SilhouetteOutline>,
+    retro: Option<RetroSettings>,
+    post_effect: Option<Box<dyn PostEffect>>,
+    // Seconds the post effect took last frame, for the HUD
+    post_process_time: f32,
+    // Stage timings, when profiling
+    profile: Option<FrameProfile>,
+    // Active frame capture, fed at the end of every `render()`
+    recorder: Option<FrameRecorder>,
+    hot_reload: Option<HotReload>,
+    // Models and textures being loaded in the background
+    loader: Loader,
+    palette: RenderPalette,
+    wireframe_aa: bool,
+    depth_prepass: bool,
+    min_triangle_area: f32,
+    debug_hud: bool,
+    stats: FrameStats,
+    // Frame times measured between `render()` calls, for the HUD
+    last_render: Option<Instant>,
+    frame_timer: FrameTimer,
+    picking: bool,
+    pick_targets: Vec<PickTarget>,
+    /// Master switch for face culling. When off, every mesh draws both
+    /// sides regardless of its [`CullMode`].
     pub backface_culling: bool,
+    grid: GridMode,
+    vertex_marker_size: u32,
     pub draw_grid: bool,
 }
 
@@ -163,25 +896,84 @@ impl Engine {
 
         Self {
             renderer: Renderer::new(width, height),
+            display_width: width,
+            display_height: height,
+            supersampling: 1,
+            resolution_scale: 1.0,
+            resolved: Vec::new(),
+            downsampled: Vec::new(),
             rasterizer: RasterizerDispatcher::new(RasterizerType::default()),
-            triangles_per_model: Vec::new(),
+            custom_shader: None,
+            debug_view: DebugView::None,
+            hidden_surface: HiddenSurface::DepthBuffer,
+            batches: Vec::new(),
+            batches_generation: 0,
+            batches_view_projection: Mat4::identity(),
+            incremental: None,
+            changes: 0,
+            skip_unchanged_frames: false,
+            updated_from: None,
+            updated_queue: false,
+            frame_changed: true,
+            static_canvas: Vec::new(),
+            static_canvas_key: None,
+            billboards: Vec::new(),
+            queued_lines: Vec::new(),
+            billboard_triangles: Vec::new(),
+            billboard_texture: None,
+            billboard_blend: BlendMode::Alpha,
             models: Vec::new(),
             model_names: HashMap::new(),
             global_texture: None,
-            camera: FpsCamera::new(Vec3::new(0.0, 0.0, -5.0)),
+            textures: TextureArena::default(),
+            normal_map: None,
+            camera: SceneCamera::Fps(FpsCamera::new(Vec3::new(0.0, 0.0, -5.0))),
+            camera_path: None,
+            camera_shake: None,
             projection_matrix: projection.matrix(),
-            clipper: ClipSpaceClipper::new(),
             projection,
             texture_mode: TextureMode::default(),
             render_mode: RenderMode::default(),
+            render_mode_override: None,
             shading_mode: ShadingMode::default(),
             light: DirectionalLight::new(Vec3::new(0.0, 0.0, 1.0)),
+            point_lights: Vec::new(),
+            pipeline: Pipeline::Forward,
+            dithering: false,
+            fog: None,
+            selection_outline: None,
+            debug_draw: DebugDraw::default(),
+            debug_lines: Vec::new(),
+            frozen_cull_view: None,
+            debug_draw_frustum: false,
+            cull_results: Vec::new(),
+            silhouette_outline: None,
+            retro: None,
+            post_effect: None,
+            post_process_time: 0.0,
+            profile: None,
+            recorder: None,
+            hot_reload: None,
+            loader: Loader::new(),
+            palette: RenderPalette::default(),
+            wireframe_aa: false,
+            depth_prepass: false,
+            min_triangle_area: 0.0,
+            debug_hud: false,
+            stats: FrameStats::default(),
+            last_render: None,
+            frame_timer: FrameTimer::uncapped(),
+            picking: false,
+            pick_targets: Vec::new(),
             backface_culling: true,
+            grid: GridMode::default(),
+            vertex_marker_size: 4,
             draw_grid: true,
         }
     }
 
     pub fn set_shading_mode(&mut self, mode: ShadingMode) {
+        self.mark_changed();
         self.shading_mode = mode;
     }
 
@@ -189,7 +981,10 @@ impl Engine {
         self.shading_mode
     }
 
+    /// Set how meshes without a mode of their own
+    /// ([`Mesh::set_render_mode`]) are drawn.
     pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.mark_changed();
         self.render_mode = mode;
     }
 
@@ -197,24 +992,435 @@ impl Engine {
         self.render_mode
     }
 
+    /// Draw every mesh in `mode`, ignoring their own modes, until cleared
+    /// with `None`. The demo's number keys set this.
+    pub fn set_render_mode_override(&mut self, mode: Option<RenderMode>) {
+        self.mark_changed();
+        self.render_mode_override = mode;
+    }
+
+    /// The render mode forced on every mesh, if any.
+    pub fn render_mode_override(&self) -> Option<RenderMode> {
+        self.render_mode_override
+    }
+
+    /// The mode `mesh` is drawn in: the override, else its own mode, else
+    /// the default.
+    pub fn effective_render_mode(&self, mesh: &Mesh) -> RenderMode {
+        self.render_mode_override
+            .or(mesh.render_mode())
+            .unwrap_or(self.render_mode)
+    }
+
+    /// The mode for everything that isn't a mesh, like billboards.
+    fn scene_render_mode(&self) -> RenderMode {
+        self.render_mode_override.unwrap_or(self.render_mode)
+    }
+
+    /// Switch rasterizers. `RasterizerType::Custom` is ignored until one
+    /// has been registered with [`Engine::set_custom_rasterizer`].
     pub fn set_rasterizer(&mut self, rasterizer_type: RasterizerType) {
+        self.mark_changed();
         self.rasterizer.set_type(rasterizer_type);
     }
 
+    /// Register a user-supplied rasterizer and switch to it. It replaces any
+    /// earlier custom rasterizer; the built-in ones stay available through
+    /// [`Engine::set_rasterizer`].
+    pub fn set_custom_rasterizer(&mut self, rasterizer: Box<dyn Rasterizer>) {
+        self.mark_changed();
+        self.rasterizer.set_custom(rasterizer);
+    }
+
+    /// Remove the custom rasterizer, returning it. If it was active the
+    /// engine falls back to the default rasterizer.
+    pub fn clear_custom_rasterizer(&mut self) -> Option<Box<dyn Rasterizer>> {
+        self.mark_changed();
+        self.rasterizer.clear_custom()
+    }
+
+    /// The rasterizer installed with [`Engine::set_custom_rasterizer`], if any.
+    pub fn custom_rasterizer(&self) -> Option<&dyn Rasterizer> {
+        self.rasterizer.custom()
+    }
+
+    /// Install (or with `None`, remove) a shader that colors every filled
+    /// mesh triangle in place of the one the texture and shading modes
+    /// would pick.
+    ///
+    /// Both built-in rasterizers honor it; it sees each pixel as a
+    /// [`Fragment`], with the mesh's texture bound. The engine still lights
+    /// `vertex_colors` according to the shading mode, so a shader that does
+    /// its own lighting usually pairs with `ShadingMode::None`. Fog still
+    /// applies on top; billboards and the wireframe pass are unaffected.
+    pub fn set_custom_shader(&mut self, shader: Option<Box<dyn PixelShader + Send + Sync>>) {
+        self.mark_changed();
+        self.custom_shader = shader;
+    }
+
+    /// The shader filled meshes are drawn with instead of the built-in ones, if any.
+    pub fn custom_shader(&self) -> Option<&(dyn PixelShader + Send + Sync)> {
+        self.custom_shader.as_deref()
+    }
+
+    /// Show a false-color [`DebugView`] of filled meshes instead of their
+    /// shading, or `DebugView::None` to go back. Takes effect from the next
+    /// [`Engine::update`].
+    pub fn set_debug_view(&mut self, view: DebugView) {
+        self.mark_changed();
+        self.debug_view = view;
+    }
+
+    /// The false-color view filled meshes are drawn with.
+    pub fn debug_view(&self) -> DebugView {
+        self.debug_view
+    }
+
+    /// Choose what the depth buffer stores. See [`DepthMode`] for the
+    /// precision trade-offs; takes effect from the next [`Engine::render`].
+    pub fn set_depth_mode(&mut self, mode: DepthMode) {
+        self.mark_changed();
+        let encoding = DepthEncoding::new(mode, self.projection.z_near(), self.projection.z_far());
+        self.renderer.set_depth_encoding(encoding);
+    }
+
+    /// How depth is encoded in the depth buffer.
+    pub fn depth_mode(&self) -> DepthMode {
+        self.renderer.depth_encoding().mode()
+    }
+
+    /// Rasterize filled triangles twice: first writing only depth, then
+    /// shading just the pixels whose depth matches, so each visible pixel
+    /// runs its shader once however much the scene overlaps. Worth it when
+    /// overdraw is high and shading is expensive (perspective-correct
+    /// textures, fog); otherwise the extra pass costs more than it saves.
+    pub fn set_depth_prepass(&mut self, enabled: bool) {
+        self.mark_changed();
+        self.depth_prepass = enabled;
+    }
+
+    /// Whether a depth-only pass runs before filled meshes are shaded.
+    pub fn depth_prepass(&self) -> bool {
+        self.depth_prepass
+    }
+
+    /// Drop projected triangles under `area` square pixels whose bounds hold
+    /// no pixel center, before they reach any pass. They would draw no
+    /// filled pixels anyway, so this only saves their setup cost; their
+    /// wireframe edges and vertex markers go too. Thin triangles spanning a
+    /// pixel center stay however small they are. Counted in
+    /// [`FrameStats::triangles_too_small`]; `0.0`, the default, keeps
+    /// every triangle. Takes effect from the next [`Engine::update`].
+    pub fn set_min_triangle_area(&mut self, area: f32) {
+        self.mark_changed();
+        self.min_triangle_area = area;
+    }
+
+    /// Screen area in square pixels below which triangles may be dropped.
+    pub fn min_triangle_area(&self) -> f32 {
+        self.min_triangle_area
+    }
+
+    /// Skip the work for frames that would come out the same as the last,
+    /// for editors and viewers that often sit still.
+    ///
+    /// When nothing that shows has changed since the last update (the
+    /// camera, the projection, models, meshes or any setting), and no
+    /// billboards or lines are queued, [`Engine::update`] keeps its
+    /// triangles and [`Engine::render`] presents the last frame again,
+    /// still drawing the HUD and post effect over it. Changes through the
+    /// engine's setters and `_mut` accessors are noticed; meshes changed
+    /// through anything they lent out earlier aren't. The frame's stats
+    /// stay those of the last frame drawn.
+    pub fn set_skip_unchanged_frames(&mut self, enabled: bool) {
+        self.skip_unchanged_frames = enabled;
+        if !enabled {
+            self.static_canvas = Vec::new();
+            self.static_canvas_key = None;
+        }
+    }
+
+    /// Whether unchanged frames are presented again instead of drawn.
+    pub fn skip_unchanged_frames(&self) -> bool {
+        self.skip_unchanged_frames
+    }
+
+    /// Whether the last [`Engine::update`] found anything changed and
+    /// rebuilt its triangles. Always `true` unless
+    /// [`Engine::set_skip_unchanged_frames`] is on.
+    pub fn frame_changed(&self) -> bool {
+        self.frame_changed
+    }
+
+    /// Note that something the image depends on may have changed.
+    fn mark_changed(&mut self) {
+        self.changes += 1;
+    }
+
+    /// The state of everything the next frame is drawn from, as far as
+    /// skipping unchanged frames goes.
+    fn frame_key(&self) -> FrameKey {
+        FrameKey {
+            changes: self.changes,
+            view_projection: self.projection_matrix * self.view_matrix(),
+            render_size: (self.renderer.width(), self.renderer.height()),
+            backface_culling: self.backface_culling,
+            draw_grid: self.draw_grid,
+        }
+    }
+
+    /// Choose how filled triangles hide each other. Switching to
+    /// [`HiddenSurface::PainterSort`] frees the depth buffer, and switching
+    /// back allocates it again; the order takes effect from the next
+    /// [`Engine::update`].
+    pub fn set_hidden_surface(&mut self, mode: HiddenSurface) {
+        self.mark_changed();
+        self.hidden_surface = mode;
+        self.renderer
+            .set_depth_buffer(mode == HiddenSurface::DepthBuffer);
+    }
+
+    /// How hidden surfaces are removed.
+    pub fn hidden_surface(&self) -> HiddenSurface {
+        self.hidden_surface
+    }
+
     pub fn rasterizer(&self) -> RasterizerType {
         self.rasterizer.active_type()
     }
 
+    /// [`Rasterizer::name`] of the active rasterizer, so a custom one shows
+    /// up by its own name rather than as "Custom".
+    pub fn rasterizer_name(&self) -> &str {
+        self.rasterizer.name()
+    }
+
     // ============ Model Management ============
 
     /// Add a model from an OBJ file with the given name.
     /// Returns the model index for efficient access.
-    pub fn add_model(&mut self, name: &str, file_path: &str) -> Result<usize, LoadError> {
+    pub fn add_model(&mut self, name: &str, file_path: &str) -> Result<usize, Error> {
+        let model = Model::from_obj(name, file_path)?;
+        Ok(self.push_model(name, model))
+    }
+
+    /// Add a model from a binary or ASCII STL file with the given name. See
+    /// [`crate::Mesh::from_stl`] for how vertices are shared.
+    /// Returns the model index for efficient access.
+    pub fn add_stl_model(&mut self, name: &str, file_path: &str) -> Result<usize, Error> {
+        let model = Model::from_stl(name, file_path)?;
+        Ok(self.push_model(name, model))
+    }
+
+    /// Add a model from a glTF 2.0 file with the given name. See
+    /// [`Model::from_gltf`] for what is loaded.
+    /// Returns the model index for efficient access.
+    #[cfg(feature = "gltf")]
+    pub fn add_gltf_model(&mut self, name: &str, file_path: &str) -> Result<usize, Error> {
+        let model = Model::from_gltf(name, file_path)?;
+        Ok(self.push_model(name, model))
+    }
+
+    /// Add a model from an OBJ file, sized and placed so it is in view
+    /// whatever the file's own scale and offset.
+    ///
+    /// The vertices are normalized with [`Model::normalize_to_unit`] (largest
+    /// extent 1.0, centroid at the origin). The model transform then scales
+    /// that to `target_size` world units and sets it in front of the current
+    /// camera, far enough back that all of it fits the view.
+    pub fn load_mesh_fitted(
+        &mut self,
+        name: &str,
+        file_path: &str,
+        target_size: f32,
+    ) -> Result<usize, Error> {
+        let mut model = Model::from_obj(name, file_path)?;
+        model.normalize_to_unit(true);
+
+        // Distance at which a sphere around the scaled model, centered on
+        // its origin, touches the sides of the narrower field of view
+        let bounds = model.bounds();
+        let radius = target_size * (bounds.center.magnitude() + bounds.radius);
+        let half_fov = 0.5 * self.projection.fov_y().min(self.projection.fov_x());
+        let distance = radius / half_fov.sin();
+
+        model
+            .transform_mut()
+            .set_scale_uniform(target_size)
+            .set_position(self.camera.position() + self.camera.forward() * distance);
+        Ok(self.push_model(name, model))
+    }
+
+    /// Load an OBJ file as a model named after the file stem, replacing any
+    /// model of that name. Every object/group in the file becomes its own
+    /// mesh, addressable with [`Model::mesh_mut`].
+    /// Returns the model index for efficient access.
+    pub fn load_model(&mut self, file_path: &str) -> Result<usize, Error> {
+        let name = std::path::Path::new(file_path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(file_path);
         let model = Model::from_obj(name, file_path)?;
+        Ok(self.set_model(model))
+    }
+
+    /// Put an already-built model in the scene under [`Model::name`]. A
+    /// model of the same name is replaced in place, keeping its index;
+    /// otherwise the model is appended.
+    /// Returns the model index for efficient access.
+    pub fn set_model(&mut self, model: Model) -> usize {
+        self.mark_changed();
+        match self.model_names.get(model.name()) {
+            Some(&index) => {
+                self.models[index] = model;
+                index
+            }
+            None => {
+                let name = model.name().to_string();
+                self.push_model(&name, model)
+            }
+        }
+    }
+
+    /// Read a scene file (see [`crate::scene`]) and apply it with
+    /// [`Engine::apply_scene`], resolving asset paths against the file's
+    /// directory. Returns the scene, whose [`Scene::unknown_fields`] are
+    /// worth warning about.
+    ///
+    /// [`Scene::unknown_fields`]: crate::scene::Scene::unknown_fields
+    #[cfg(feature = "serde")]
+    pub fn load_scene<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<crate::scene::Scene, crate::scene::SceneError> {
+        let path = path.as_ref();
+        let scene = crate::scene::Scene::from_file(path)?;
+        let base_dir = path.parent().unwrap_or(std::path::Path::new(""));
+        self.apply_scene(&scene, base_dir)?;
+        Ok(scene)
+    }
+
+    /// Replace the scene's models with `scene`'s and apply its light,
+    /// camera start and settings. Relative asset paths are resolved
+    /// against `base_dir`.
+    ///
+    /// Every missing model and texture is reported together in
+    /// [`SceneError::MissingAssets`], before anything changes. A file that
+    /// exists but fails to load stops the load part way.
+    ///
+    /// [`SceneError::MissingAssets`]: crate::scene::SceneError::MissingAssets
+    #[cfg(feature = "serde")]
+    pub fn apply_scene(
+        &mut self,
+        scene: &crate::scene::Scene,
+        base_dir: &std::path::Path,
+    ) -> Result<(), crate::scene::SceneError> {
+        use crate::scene::SceneError;
+
+        let missing = scene.missing_assets(base_dir);
+        if !missing.is_empty() {
+            return Err(SceneError::MissingAssets(missing));
+        }
+
+        let settings = &scene.settings;
+        if let Some(mode) = settings.render_mode {
+            self.set_render_mode(mode);
+        }
+        if let Some(mode) = settings.shading_mode {
+            self.set_shading_mode(mode);
+        }
+        if let Some(mode) = settings.texture_mode {
+            self.set_texture_mode(mode);
+        }
+        if let Some(rasterizer) = settings.rasterizer {
+            self.set_rasterizer(rasterizer);
+        }
+        if let Some(enabled) = settings.backface_culling {
+            self.backface_culling = enabled;
+        }
+        if let Some(enabled) = settings.draw_grid {
+            self.draw_grid = enabled;
+        }
+        if let Some(enabled) = settings.debug_hud {
+            self.draw_debug_hud(enabled);
+        }
+        if let Some(enabled) = settings.picking {
+            self.set_picking(enabled);
+        }
+        if let Some(factor) = settings.supersampling {
+            self.set_supersampling(factor);
+        }
+        if let Some(degrees) = settings.fov_degrees {
+            self.set_fov_degrees(degrees);
+        }
+
+        self.clear_models();
+        for entry in &scene.models {
+            let path = base_dir.join(&entry.path);
+            let file_path = path.to_string_lossy();
+            let extension = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_ascii_lowercase);
+            let loaded = match extension.as_deref() {
+                Some("stl") => self.add_stl_model(&entry.name, &file_path),
+                #[cfg(feature = "gltf")]
+                Some("gltf" | "glb") => self.add_gltf_model(&entry.name, &file_path),
+                _ => self.add_model(&entry.name, &file_path),
+            };
+            let index = loaded.map_err(|error| SceneError::Model {
+                path: path.clone(),
+                error: Box::new(error),
+            })?;
+
+            let model = &mut self.models[index];
+            model
+                .transform_mut()
+                .set_position(Vec3::from(entry.position))
+                .set_rotation(Vec3::from(entry.rotation))
+                .set_scale(Vec3::from(entry.scale));
+            if let Some(texture_path) = &entry.texture {
+                let path = base_dir.join(texture_path);
+                let texture = Texture::from_file(&path).map_err(|error| SceneError::Texture {
+                    path,
+                    error: Box::new(error),
+                })?;
+                model.set_texture(texture);
+            }
+            if let Some(spin) = entry.spin {
+                model.set_animator(
+                    crate::animation::Animator::new()
+                        .spin(Vec3::from(spin.axis), spin.radians_per_sec),
+                );
+            }
+        }
+
+        if let Some(light) = scene.light {
+            self.light = DirectionalLight {
+                ambient_intensity: light.ambient_intensity,
+                diffuse_strength: light.diffuse_strength,
+                ..DirectionalLight::new(Vec3::from(light.direction))
+            };
+        }
+        if let Some(camera) = scene.camera {
+            let position = Vec3::from(camera.position);
+            match camera.target {
+                Some(target) => {
+                    self.set_camera(FpsCamera::looking_at(position, Vec3::from(target)));
+                }
+                None => self.camera.set_position(position),
+            }
+        }
+        self.mark_changed();
+        Ok(())
+    }
+
+    pub(crate) fn push_model(&mut self, name: &str, model: Model) -> usize {
+        self.mark_changed();
         let index = self.models.len();
         self.model_names.insert(name.to_string(), index);
         self.models.push(model);
-        Ok(index)
+        index
     }
 
     /// Get a model by name.
@@ -224,6 +1430,7 @@ impl Engine {
 
     /// Get a mutable reference to a model by name.
     pub fn model_mut(&mut self, name: &str) -> Option<&mut Model> {
+        self.mark_changed();
         self.model_names
             .get(name)
             .copied()
@@ -237,6 +1444,7 @@ impl Engine {
 
     /// Get a mutable reference to a model by index.
     pub fn model_by_index_mut(&mut self, index: usize) -> Option<&mut Model> {
+        self.mark_changed();
         self.models.get_mut(index)
     }
 
@@ -251,7 +1459,11 @@ impl Engine {
     }
 
     /// Remove a model by name. Returns the removed model if found.
+    ///
+    /// A [`SelectionOutline`] on the removed model is cleared; one on a
+    /// later model follows it down to its new index.
     pub fn remove_model(&mut self, name: &str) -> Option<Model> {
+        self.mark_changed();
         if let Some(&index) = self.model_names.get(name) {
             self.model_names.remove(name);
             let model = self.models.remove(index);
@@ -261,97 +1473,1406 @@ impl Engine {
                     *idx -= 1;
                 }
             }
+            match self.selection_outline {
+                Some(outline) if outline.model_index == index => self.selection_outline = None,
+                Some(ref mut outline) if outline.model_index > index => outline.model_index -= 1,
+                _ => {}
+            }
             Some(model)
         } else {
             None
         }
     }
 
-    /// Clear all models from the scene.
+    /// Clear all models from the scene, and the selection outline with
+    /// them.
     pub fn clear_models(&mut self) {
+        self.mark_changed();
         self.models.clear();
         self.model_names.clear();
+        self.selection_outline = None;
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) {
-        self.renderer.resize(width, height);
-        let aspect_ratio = width as f32 / height as f32;
-        self.projection.set_aspect_ratio(aspect_ratio);
-        self.projection_matrix = self.projection.matrix();
-        // Note: ClipSpaceClipper doesn't need rebuilding - it uses fixed planes
-    }
-
-    pub fn camera(&self) -> &FpsCamera {
-        &self.camera
+    /// Find the mesh named `mesh` in the model named `model`.
+    pub fn mesh_handle(&self, model: &str, mesh: &str) -> Option<MeshHandle> {
+        let model_index = *self.model_names.get(model)?;
+        let mesh_index = self.models[model_index]
+            .meshes()
+            .iter()
+            .position(|m| m.name() == mesh)?;
+        Some(MeshHandle {
+            model_index,
+            mesh_index,
+        })
     }
 
-    pub fn camera_mut(&mut self) -> &mut FpsCamera {
-        &mut self.camera
+    /// Draw the mesh at `handle` once more per `transforms` entry, reusing
+    /// its vertices and faces. See [`Mesh::add_instances`] for how the
+    /// transforms are applied. Returns `false` if there is no such mesh.
+    ///
+    /// [`Mesh::add_instances`]: crate::Mesh::add_instances
+    pub fn add_instances(&mut self, handle: MeshHandle, transforms: &[Transform]) -> bool {
+        self.mark_changed();
+        match self
+            .models
+            .get_mut(handle.model_index)
+            .and_then(|model| model.mesh_by_index_mut(handle.mesh_index))
+        {
+            Some(mesh) => {
+                mesh.add_instances(transforms);
+                true
+            }
+            None => false,
+        }
     }
 
-    pub fn set_camera_position(&mut self, position: Vec3) {
-        self.camera.set_position(position);
+    /// Draw the simplified meshes in `group` in place of the mesh at
+    /// `handle` when the camera is far from it; see [`crate::lod`].
+    /// Picking then reports faces of the level drawn. Returns `false` if
+    /// there is no such mesh.
+    pub fn set_lod_group(&mut self, handle: MeshHandle, group: LodGroup) -> bool {
+        self.mark_changed();
+        match self
+            .models
+            .get_mut(handle.model_index)
+            .and_then(|model| model.mesh_by_index_mut(handle.mesh_index))
+        {
+            Some(mesh) => {
+                mesh.set_lod_group(group);
+                true
+            }
+            None => false,
+        }
     }
 
-    pub fn camera_position(&self) -> Vec3 {
-        self.camera.position()
+    /// The instance transforms of the mesh at `handle`, to animate them.
+    pub fn instances_mut(&mut self, handle: MeshHandle) -> Option<&mut [Transform]> {
+        self.mark_changed();
+        self.models
+            .get_mut(handle.model_index)?
+            .mesh_by_index_mut(handle.mesh_index)
+            .map(|mesh| mesh.instances_mut())
     }
 
-    pub fn set_light_direction(&mut self, direction: Vec3) {
-        self.light = DirectionalLight::new(direction);
+    // ============ Hot Reload ============
+
+    /// Watch the files models and textures were loaded from, and reload
+    /// them in place when they change. See [`crate::hot_reload`].
+    ///
+    /// Turning it on records the current modification times, so only
+    /// later edits trigger a reload; turning it off drops any events not
+    /// yet taken.
+    pub fn enable_hot_reload(&mut self, enabled: bool) {
+        if enabled == self.hot_reload.is_some() {
+            return;
+        }
+        if enabled {
+            self.hot_reload = Some(HotReload::new());
+            self.reload_changed_assets();
+        } else {
+            self.hot_reload = None;
+        }
     }
 
-    pub fn light_direction(&self) -> Vec3 {
-        self.light.direction
+    /// Whether model and texture files are watched for changes.
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload.is_some()
     }
 
-    /// Returns the rendered frame as bytes (ARGB8888 format)
-    pub fn frame_buffer(&self) -> &[u8] {
-        self.renderer.as_bytes()
-    }
+    /// Check the watched files now rather than at the next poll, and
+    /// reload any that changed. Does nothing unless hot reload is on.
+    pub fn reload_changed_assets(&mut self) {
+        let Some(hot_reload) = &mut self.hot_reload else {
+            return;
+        };
+        let model_paths = self.models.iter().filter_map(Model::source_path);
+        let texture_paths = self
+            .models
+            .iter()
+            .filter_map(Model::texture)
+            .chain(&self.global_texture)
+            .chain(&self.normal_map)
+            .chain(&self.billboard_texture)
+            .chain(self.textures.iter())
+            .filter_map(Texture::source_path);
+        let changed = hot_reload.changed(model_paths.chain(texture_paths));
+        if changed.is_empty() {
+            return;
+        }
+        self.changes += 1;
+
+        for model in &mut self.models {
+            if let Some(path) = model.source_path().filter(|p| changed.contains(*p)) {
+                let path = path.to_path_buf();
+                let event = match model.reload() {
+                    Ok(()) => ReloadEvent::Model {
+                        name: model.name().to_string(),
+                        path,
+                    },
+                    Err(error) => ReloadEvent::Failed {
+                        path,
+                        error: error.to_string(),
+                    },
+                };
+                hot_reload.events.push(event);
+            }
+            if let Some(texture) =
+                hot_reload.reload_texture(model.texture(), Some(model.name()), &changed)
+            {
+                model.set_texture(texture);
+            }
+        }
+        for slot in [
+            &mut self.global_texture,
+            &mut self.normal_map,
+            &mut self.billboard_texture,
+        ] {
+            if let Some(texture) = hot_reload.reload_texture(slot.as_ref(), None, &changed) {
+                *slot = Some(texture);
+            }
+        }
+        for shared in self.textures.iter_mut() {
+            if let Some(texture) = hot_reload.reload_texture(Some(shared), None, &changed) {
+                *shared = texture;
+            }
+        }
+    }
+
+    /// Take the reloads (and failed reloads) since the last call, oldest
+    /// first, for logging.
+    pub fn take_reload_events(&mut self) -> Vec<ReloadEvent> {
+        self.hot_reload
+            .as_mut()
+            .map(|hot_reload| std::mem::take(&mut hot_reload.events))
+            .unwrap_or_default()
+    }
+
+    // ============ Background Loading ============
+
+    /// Start loading a model from an OBJ file on a background thread. It's
+    /// added under `name`, as by [`Engine::add_model`], by the first
+    /// [`Engine::poll_loaded`] after the file has been read. See
+    /// [`crate::loading`].
+    pub fn load_model_async(&mut self, name: &str, file_path: &str) -> LoadHandle {
+        let (name, file_path) = (name.to_string(), file_path.to_string());
+        self.loader.start(move || {
+            let model = Model::from_obj(&name, &file_path)?;
+            Ok(Loaded::Model {
+                name,
+                model: Box::new(model),
+            })
+        })
+    }
+
+    /// Start loading a texture on a background thread, for the model named
+    /// `model` or, with `None`, as the global texture of
+    /// [`Engine::set_texture`]. The load fails if the model is gone by the
+    /// time the texture is ready.
+    pub fn load_texture_async(&mut self, model: Option<&str>, file_path: &str) -> LoadHandle {
+        let (model, file_path) = (model.map(str::to_string), file_path.to_string());
+        self.loader.start(move || {
+            let texture = Texture::from_file(&file_path)?;
+            Ok(Loaded::Texture { model, texture })
+        })
+    }
+
+    /// Install the background loads that have finished, returning their
+    /// handles. Call once a frame; their outcome is then in
+    /// [`Engine::load_status`].
+    pub fn poll_loaded(&mut self) -> Vec<LoadHandle> {
+        let finished = self.loader.take_finished();
+        let mut handles = Vec::with_capacity(finished.len());
+        for (handle, result) in finished {
+            let installed = result.and_then(|loaded| self.install(loaded));
+            self.loader.finish(handle, installed);
+            handles.push(handle);
+        }
+        handles
+    }
+
+    fn install(&mut self, loaded: Loaded) -> Result<(), Error> {
+        match loaded {
+            Loaded::Model { name, model } => {
+                self.push_model(&name, *model);
+            }
+            Loaded::Texture {
+                model: Some(name),
+                texture,
+            } => {
+                self.model_mut(&name)
+                    .ok_or(Error::InvalidArgument("no model with that name"))?
+                    .set_texture(texture);
+            }
+            Loaded::Texture {
+                model: None,
+                texture,
+            } => self.set_texture(texture),
+        }
+        Ok(())
+    }
+
+    /// Where the load with `handle` stands.
+    pub fn load_status(&self, handle: LoadHandle) -> Option<&LoadStatus> {
+        self.loader.status(handle)
+    }
+
+    /// How many background loads [`Engine::poll_loaded`] hasn't installed
+    /// yet.
+    pub fn loads_pending(&self) -> usize {
+        self.loader.pending()
+    }
+
+    /// Resize to a new display size.
+    ///
+    /// The internal render target is the display size times the resolution
+    /// scale, then `supersampling` times larger on each axis.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.mark_changed();
+        self.display_width = width;
+        self.display_height = height;
+        let factor = self.supersampling;
+        let (scaled_width, scaled_height) = self.scaled_size();
+        self.renderer
+            .resize(scaled_width * factor, scaled_height * factor);
+        let scaled = (scaled_width, scaled_height) != (width, height);
+        self.resolved = if factor > 1 || scaled {
+            vec![0; (width * height) as usize]
+        } else {
+            Vec::new()
+        };
+        self.downsampled = if factor > 1 && scaled {
+            vec![0; (scaled_width * scaled_height) as usize]
+        } else {
+            Vec::new()
+        };
+        let aspect_ratio = width as f32 / height as f32;
+        self.projection.set_aspect_ratio(aspect_ratio);
+        self.projection_matrix = self.projection.matrix();
+        // Note: ClipSpaceClipper doesn't need rebuilding - it uses fixed planes
+    }
+
+    /// Set the vertical field of view, clamped to
+    /// [`MIN_FOV_DEGREES`](crate::projection::MIN_FOV_DEGREES)..=[`MAX_FOV_DEGREES`](crate::projection::MAX_FOV_DEGREES).
+    /// Takes effect from the next [`Engine::update`]. The vertical axis is
+    /// then the one kept fixed on resize; see [`Engine::set_fov`].
+    pub fn set_fov_degrees(&mut self, degrees: f32) {
+        self.mark_changed();
+        self.projection.set_fov_y(degrees.to_radians());
+        self.projection_matrix = self.projection.matrix();
+    }
+
+    /// Vertical field of view in degrees.
+    pub fn fov_degrees(&self) -> f32 {
+        self.projection.fov_y().to_degrees()
+    }
+
+    /// Set the field of view along `axis`, which then stays fixed when the
+    /// frame is resized while the other axis follows the aspect ratio.
+    /// Clamped like [`Engine::set_fov_degrees`].
+    pub fn set_fov(&mut self, degrees: f32, axis: FovAxis) {
+        self.mark_changed();
+        self.projection.set_fov(degrees.to_radians(), axis);
+        self.projection_matrix = self.projection.matrix();
+    }
+
+    /// The axis the field of view was last given along.
+    pub fn fov_axis(&self) -> FovAxis {
+        self.projection.fov_axis()
+    }
+
+    /// The projection the view frustum and every frame are built from.
+    pub fn projection(&self) -> &Projection {
+        &self.projection
+    }
+
+    /// Set the near and far clip plane distances. The near plane is kept
+    /// above zero and the far plane beyond it; see
+    /// [`Projection::set_clip_planes`]. Also rebuilds the depth encoding,
+    /// which the NDC [`DepthMode`]s derive from the planes.
+    pub fn set_clip_planes(&mut self, near: f32, far: f32) {
+        self.mark_changed();
+        self.projection.set_clip_planes(near, far);
+        self.projection_matrix = self.projection.matrix();
+        // The depth encoding is built from the planes
+        self.set_depth_mode(self.depth_mode());
+    }
+
+    /// `(near, far)` clip plane distances.
+    pub fn clip_planes(&self) -> (f32, f32) {
+        (self.projection.z_near(), self.projection.z_far())
+    }
+
+    /// Switch between left-handed (+Z forward, the default) and
+    /// right-handed (-Z forward) coordinates.
+    ///
+    /// Updates the projection and the active camera, which keeps its
+    /// position and view direction. Meshes and lights are left as they are,
+    /// so a scene authored for the other convention needs mirroring in z.
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        self.mark_changed();
+        self.projection.set_handedness(handedness);
+        self.projection_matrix = self.projection.matrix();
+        self.camera.set_handedness(handedness);
+    }
+
+    /// The handedness of world space.
+    pub fn handedness(&self) -> Handedness {
+        self.projection.handedness()
+    }
+
+    /// The render target's viewport, in render-target pixels (display
+    /// pixels times the resolution scale and supersampling factor).
+    pub fn viewport(&self) -> Viewport {
+        Viewport::full(self.renderer.width(), self.renderer.height())
+    }
+
+    pub fn camera(&self) -> &SceneCamera {
+        &self.camera
+    }
+
+    pub fn camera_mut(&mut self) -> &mut SceneCamera {
+        &mut self.camera
+    }
+
+    /// Replaces the active camera, returning the previous one.
+    ///
+    /// Keep the returned camera around to switch back without losing its state.
+    ///
+    /// The new camera is switched to the engine's [`Handedness`].
+    pub fn set_camera(&mut self, camera: impl Into<SceneCamera>) -> SceneCamera {
+        self.mark_changed();
+        let mut camera = camera.into();
+        camera.set_handedness(self.projection.handedness());
+        std::mem::replace(&mut self.camera, camera)
+    }
+
+    /// Fly the camera along `path`, one [`Engine::update_path`] at a time.
+    /// Replaces any previous path.
+    pub fn set_camera_path(&mut self, path: CameraPath) {
+        self.camera_path = Some(path);
+    }
+
+    /// Stop following the camera path and return it. The camera stays
+    /// where the path left it.
+    pub fn clear_camera_path(&mut self) -> Option<CameraPath> {
+        self.camera_path.take()
+    }
+
+    /// The path the camera is following, if any.
+    pub fn camera_path(&self) -> Option<&CameraPath> {
+        self.camera_path.as_ref()
+    }
+
+    /// Advance the camera path by `dt` seconds and move the camera onto it.
+    ///
+    /// Not called by [`Engine::update_with_dt`], so the application decides
+    /// when the path rather than the player drives the camera. Returns
+    /// `false`, leaving the camera alone, when there is no path or a
+    /// non-looping one has already finished.
+    pub fn update_path(&mut self, dt: f32) -> bool {
+        let Some(path) = self.camera_path.as_mut() else {
+            return false;
+        };
+        if path.is_finished() {
+            return false;
+        }
+        match path.advance(dt) {
+            Some((position, target)) => {
+                self.camera.set_pose(position, target);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shake the view with `shake` whenever it's triggered, e.g.
+    /// `engine.camera_shake_mut().unwrap().trigger(1.0)` on an impact.
+    /// [`Engine::animate`] lets it die down. Replaces any previous shake.
+    pub fn set_camera_shake(&mut self, shake: CameraShake) {
+        self.camera_shake = Some(shake);
+    }
+
+    /// Stop shaking the view and return the shake.
+    pub fn clear_camera_shake(&mut self) -> Option<CameraShake> {
+        self.camera_shake.take()
+    }
+
+    /// The shake applied to the view, if any.
+    pub fn camera_shake(&self) -> Option<&CameraShake> {
+        self.camera_shake.as_ref()
+    }
+
+    /// The shake applied to the view, to adjust while it runs.
+    pub fn camera_shake_mut(&mut self) -> Option<&mut CameraShake> {
+        self.camera_shake.as_mut()
+    }
+
+    /// The view matrix frames are drawn and picked with: the camera's,
+    /// turned by the camera shake while it's active.
+    pub fn view_matrix(&self) -> Mat4 {
+        let view = self.camera.view_matrix();
+        match &self.camera_shake {
+            Some(shake) => shake.apply(view),
+            None => view,
+        }
+    }
+
+    pub fn set_camera_position(&mut self, position: Vec3) {
+        self.mark_changed();
+        self.camera.set_position(position);
+    }
+
+    pub fn camera_position(&self) -> Vec3 {
+        self.camera.position()
+    }
+
+    pub fn set_light_direction(&mut self, direction: Vec3) {
+        self.mark_changed();
+        self.light = DirectionalLight::new(direction);
+    }
+
+    pub fn light_direction(&self) -> Vec3 {
+        self.light.direction
+    }
+
+    /// Replace the light, ambient intensity and diffuse strength included;
+    /// [`Engine::set_light_direction`] resets those to their defaults.
+    pub fn set_light(&mut self, light: DirectionalLight) {
+        self.mark_changed();
+        self.light = light;
+    }
+
+    /// The scene's directional light.
+    pub fn light(&self) -> DirectionalLight {
+        self.light
+    }
+
+    /// Add a point light to the scene. Point lights light filled meshes
+    /// per pixel in both pipelines, in addition to the directional light.
+    pub fn add_point_light(&mut self, light: PointLight) {
+        self.mark_changed();
+        self.point_lights.push(light);
+    }
+
+    /// Replace every point light.
+    pub fn set_point_lights(&mut self, lights: Vec<PointLight>) {
+        self.mark_changed();
+        self.point_lights = lights;
+    }
+
+    /// Remove every point light.
+    pub fn clear_point_lights(&mut self) {
+        self.mark_changed();
+        self.point_lights.clear();
+    }
+
+    /// The scene's point lights.
+    pub fn point_lights(&self) -> &[PointLight] {
+        &self.point_lights
+    }
+
+    /// Choose between forward and deferred lighting; see [`Pipeline`].
+    /// [`Pipeline::Deferred`] allocates G-buffer planes the size of the
+    /// frame (12 bytes per sample) and switching back frees them. Under
+    /// [`Engine::render_incremental`] deferred meshes appear once the
+    /// fill completes, since the lighting pass needs all of them.
+    pub fn set_pipeline(&mut self, pipeline: Pipeline) {
+        self.mark_changed();
+        self.pipeline = pipeline;
+        self.renderer.set_gbuffer(pipeline == Pipeline::Deferred);
+    }
+
+    /// Whether meshes are lit forward or deferred.
+    pub fn pipeline(&self) -> Pipeline {
+        self.pipeline
+    }
+
+    /// Whether this frame draws into the G-buffer. False colors are
+    /// drawn forward, as they are.
+    fn deferred(&self) -> bool {
+        self.pipeline == Pipeline::Deferred && self.debug_view == DebugView::None
+    }
+
+    /// Whether filled pixels are lit after their shader runs, rather than
+    /// from lit vertex colors: always when deferred, and for point lights.
+    fn lit_per_pixel(&self) -> bool {
+        self.deferred() || (!self.point_lights.is_empty() && self.debug_view == DebugView::None)
+    }
+
+    /// Where each pixel of the renderer's target lies along its view ray,
+    /// for the deferred lighting pass.
+    fn pixel_rays(&self) -> PixelRays {
+        let view_projection = self.projection_matrix * self.view_matrix();
+        let inverse = view_projection.inverse().unwrap_or(Mat4::identity());
+        let origin = self.view_matrix().inverse().unwrap_or(Mat4::identity()) * Vec3::ZERO;
+        let viewport = self.viewport();
+        // The point on the far plane under a pixel center
+        let far = |x: i32, y: i32| {
+            let ndc = viewport.unproject(x, y);
+            let p = inverse * Vec4::new(ndc.x, ndc.y, 1.0, 1.0);
+            p.to_vec3() / p.w
+        };
+        let corner = far(0, 0);
+        PixelRays {
+            origin,
+            corner: corner - origin,
+            step_x: far(1, 0) - corner,
+            step_y: far(0, 1) - corner,
+            far_depth: (view_projection * Vec4::from_vec3(corner, 1.0)).w,
+        }
+    }
+
+    /// Render internally at `factor`× the display resolution and box-filter
+    /// down when presenting (supersample anti-aliasing).
+    ///
+    /// Clamped to `1..=MAX_SUPERSAMPLING`; 1 disables it. Cost grows with
+    /// `factor²`: the color, depth, and ID buffers (12 bytes per sample) and
+    /// the per-pixel fill work are all 4× at factor 2 and 16× at factor 4,
+    /// so at 1920×1080 factor 2 uses ~100 MB and factor 4 ~400 MB.
+    pub fn set_supersampling(&mut self, factor: u32) {
+        self.mark_changed();
+        self.supersampling = factor.clamp(1, MAX_SUPERSAMPLING);
+        self.resize(self.display_width, self.display_height);
+    }
+
+    /// The supersampling factor; 1 when it is off.
+    pub fn supersampling(&self) -> u32 {
+        self.supersampling
+    }
+
+    /// Render internally at `scale`× the display resolution on each axis
+    /// and stretch the frame back up (nearest-neighbor) when presenting,
+    /// trading sharpness for speed: 0.5 fills a quarter of the pixels.
+    ///
+    /// [`Engine::frame_buffer`] stays at display size, so windows present
+    /// it unchanged. Clamped to `MIN_RESOLUTION_SCALE..=1.0`; 1.0 (the
+    /// default) renders at full size. Ignores non-finite values. The HUD
+    /// and other pixel-sized overlays are drawn at the internal resolution
+    /// too, so they come out blockier with the rest of the frame.
+    pub fn set_resolution_scale(&mut self, scale: f32) {
+        self.mark_changed();
+        if scale.is_finite() {
+            self.resolution_scale = scale.clamp(MIN_RESOLUTION_SCALE, 1.0);
+            self.resize(self.display_width, self.display_height);
+        }
+    }
+
+    /// The fraction of the display resolution frames are drawn at.
+    pub fn resolution_scale(&self) -> f32 {
+        self.resolution_scale
+    }
+
+    /// Display size times the resolution scale, at least a pixel each way
+    /// unless the display has none.
+    fn scaled_size(&self) -> (u32, u32) {
+        let scale =
+            |size: u32| ((size as f32 * self.resolution_scale).round() as u32).max(size.min(1));
+        (scale(self.display_width), scale(self.display_height))
+    }
+
+    /// Render-target pixels per display pixel along each axis, for sizing
+    /// overlays given in display pixels.
+    fn pixel_scale(&self) -> f32 {
+        self.supersampling as f32 * self.resolution_scale
+    }
+
+    /// `size` display pixels in render-target pixels, at least one unless
+    /// `size` is 0.
+    fn scaled_pixels(&self, size: u32) -> i32 {
+        let scaled = (size as f32 * self.pixel_scale()).round() as i32;
+        scaled.max(size.min(1) as i32)
+    }
+
+    /// Returns the rendered frame as bytes (ARGB8888 format) at display size.
+    /// Each pixel is a `u32` in the host's byte order, ready for SDL's
+    /// ARGB8888 textures and the [`Presenter`](crate::window::Presenter)s
+    /// without a copy; use [`Engine::frame_to_rgba8888`] or
+    /// [`Engine::frame_to_bgra8888`] for a fixed byte order.
+    pub fn frame_buffer(&self) -> &[u8] {
+        pixels_as_bytes(self.frame_pixels())
+    }
+
+    /// Copy the frame [`Engine::frame_buffer`] returns into `out` as
+    /// `R, G, B, A` bytes on any host, rows `pitch` bytes apart for padded
+    /// destinations or packed with `None`.
+    pub fn frame_to_rgba8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        let (width, _) = self.frame_size();
+        let pixels = self.frame_pixels().iter().copied();
+        export_pixels(pixels, width, ByteOrder::Rgba8888, pitch, out)
+    }
+
+    /// Like [`Engine::frame_to_rgba8888`], as `B, G, R, A` bytes.
+    pub fn frame_to_bgra8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        let (width, _) = self.frame_size();
+        let pixels = self.frame_pixels().iter().copied();
+        export_pixels(pixels, width, ByteOrder::Bgra8888, pitch, out)
+    }
+
+    /// The same frame as [`Engine::frame_buffer`], as ARGB pixels row-major
+    /// from the top-left.
+    pub fn frame_pixels(&self) -> &[u32] {
+        if !self.resolved.is_empty() {
+            &self.resolved
+        } else {
+            self.renderer.pixels()
+        }
+    }
+
+    /// [`Engine::update`] and [`Engine::render`] one frame and return its
+    /// pixels. Nothing here needs a window, so this is the entry point for
+    /// offscreen rendering and image tests.
+    pub fn render_offscreen(&mut self) -> &[u32] {
+        self.update();
+        self.render();
+        self.frame_pixels()
+    }
+
+    /// Width and height in pixels of the buffer returned by [`Engine::frame_buffer`].
+    pub fn frame_size(&self) -> (u32, u32) {
+        (self.display_width, self.display_height)
+    }
+
+    /// Set the global texture (used when models don't have their own).
+    pub fn set_texture(&mut self, texture: Texture) {
+        self.mark_changed();
+        self.global_texture = Some(texture);
+    }
+
+    /// Clear the global texture.
+    pub fn clear_texture(&mut self) {
+        self.mark_changed();
+        self.global_texture = None;
+    }
+
+    /// Get the global texture.
+    pub fn texture(&self) -> Option<&Texture> {
+        self.global_texture.as_ref()
+    }
+
+    /// Get the global texture to change in place, e.g. to stream new
+    /// pixels into it every frame with [`Texture::update_from_argb`].
+    pub fn texture_mut(&mut self) -> Option<&mut Texture> {
+        self.mark_changed();
+        self.global_texture.as_mut()
+    }
+
+    /// Hand `texture` to the engine for meshes to share through
+    /// [`Mesh::set_texture_handle`], however many use it, without copying
+    /// its pixels.
+    pub fn add_texture(&mut self, texture: Texture) -> TextureHandle {
+        self.textures.insert(texture)
+    }
+
+    /// Take back the texture `handle` refers to. Meshes still holding the
+    /// handle draw untextured from then on; `None` if it was already
+    /// removed.
+    pub fn remove_texture(&mut self, handle: TextureHandle) -> Option<Texture> {
+        self.mark_changed();
+        self.textures.remove(handle)
+    }
+
+    /// The texture added with [`Engine::add_texture`], if not removed.
+    pub fn shared_texture(&self, handle: TextureHandle) -> Option<&Texture> {
+        self.textures.get(handle)
+    }
+
+    /// The texture added with [`Engine::add_texture`] to change in place,
+    /// for every mesh using it at once.
+    pub fn shared_texture_mut(&mut self, handle: TextureHandle) -> Option<&mut Texture> {
+        self.mark_changed();
+        self.textures.get_mut(handle)
+    }
+
+    /// How many textures [`Engine::add_texture`] holds.
+    pub fn shared_texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Set the tangent-space normal map for `TextureMode::NormalMapped`,
+    /// shared by every model.
+    pub fn set_normal_map(&mut self, normal_map: Texture) {
+        self.mark_changed();
+        self.normal_map = Some(normal_map);
+    }
+
+    /// Clear the normal map.
+    pub fn clear_normal_map(&mut self) {
+        self.mark_changed();
+        self.normal_map = None;
+    }
+
+    /// The tangent-space normal map `TextureMode::NormalMapped` uses, if set.
+    pub fn normal_map(&self) -> Option<&Texture> {
+        self.normal_map.as_ref()
+    }
+
+    // ============ Billboards ============
+
+    /// Queue a camera-facing square sprite for the next [`Engine::update`],
+    /// centered on `world_pos` and `size` world units across.
+    ///
+    /// The sprite shows `texture_region` of the billboard texture (all of
+    /// it when `None`) tinted by `color`, or a solid `color` square without
+    /// a texture. It is unlit, depth-tested against the scene, and blended
+    /// by the texture's alpha per [`Engine::set_billboard_blend`]. The
+    /// queue empties on every update, so call this each frame a sprite
+    /// should show.
+    pub fn draw_billboard(
+        &mut self,
+        world_pos: Vec3,
+        size: f32,
+        texture_region: Option<TextureRegion>,
+        color: u32,
+    ) {
+        self.billboards.push(Billboard {
+            position: world_pos,
+            size,
+            region: texture_region,
+            color,
+        });
+    }
+
+    // ============ 3D Lines ============
+
+    /// Queue a world-space line from `a` to `b` for the next
+    /// [`Engine::update`], e.g. a path, a ray or a gizmo.
+    ///
+    /// Lines are drawn one pixel wide after the meshes and depth-tested
+    /// against them, so anything in front hides them. The part behind the
+    /// camera's near plane is cut off. Like billboards, the queue empties on
+    /// every update, so call this each frame a line should show.
+    pub fn draw_line_3d(&mut self, a: Vec3, b: Vec3, color: u32) {
+        self.queued_lines.push(DebugLine { a, b, color });
+    }
+
+    /// Queue several lines at once, as `(a, b, color)`; see
+    /// [`Engine::draw_line_3d`].
+    pub fn draw_lines_3d(&mut self, lines: &[(Vec3, Vec3, u32)]) {
+        self.queued_lines
+            .extend(lines.iter().map(|&(a, b, color)| DebugLine { a, b, color }));
+    }
+
+    /// Queue the lines of `mesh` placed by `transform`, e.g. a collision
+    /// volume following a model. See [`Engine::draw_line_3d`].
+    pub fn draw_wire_mesh(&mut self, mesh: &WireMesh, transform: &Transform, color: u32) {
+        let matrix = transform.to_matrix();
+        self.queued_lines
+            .extend(mesh.lines().iter().map(|&[a, b]| DebugLine {
+                a: matrix * a,
+                b: matrix * b,
+                color,
+            }));
+    }
+
+    /// Queue an arrow from `from` to `to`: a line with a small four-pronged
+    /// head at `to`, a fifth of the arrow long, which reads as an arrow from
+    /// any side. See [`Engine::draw_line_3d`].
+    pub fn draw_arrow_3d(&mut self, from: Vec3, to: Vec3, color: u32) {
+        self.draw_line_3d(from, to, color);
+        let shaft = to - from;
+        let length = shaft.magnitude();
+        if length <= f32::EPSILON {
+            return;
+        }
+        let direction = shaft / length;
+        // Any axis not along the arrow gives two directions across it
+        let helper = if direction.y.abs() < 0.9 {
+            Vec3::UP
+        } else {
+            Vec3::RIGHT
+        };
+        let side = direction.cross(helper).normalize();
+        let up = direction.cross(side);
+        let (head_length, head_width) = (length * 0.2, length * 0.08);
+        let base = to - direction * head_length;
+        for spread in [side, -side, up, -up] {
+            self.draw_line_3d(base + spread * head_width, to, color);
+        }
+    }
+
+    /// Set the texture billboards sample, e.g. a particle or glyph atlas.
+    pub fn set_billboard_texture(&mut self, texture: Texture) {
+        self.mark_changed();
+        self.billboard_texture = Some(texture);
+    }
+
+    /// Clear the billboard texture, leaving solid sprites.
+    pub fn clear_billboard_texture(&mut self) {
+        self.mark_changed();
+        self.billboard_texture = None;
+    }
+
+    /// The texture billboards are drawn with, if set.
+    pub fn billboard_texture(&self) -> Option<&Texture> {
+        self.billboard_texture.as_ref()
+    }
+
+    /// How billboards combine with the scene behind them.
+    /// [`BlendMode::Alpha`] (the default) sorts them back to front;
+    /// [`BlendMode::Cutout`] gives hard edges but writes depth.
+    pub fn set_billboard_blend(&mut self, mode: BlendMode) {
+        self.mark_changed();
+        self.billboard_blend = mode;
+    }
+
+    /// How billboards blend with what is behind them.
+    pub fn billboard_blend(&self) -> BlendMode {
+        self.billboard_blend
+    }
+
+    pub fn set_texture_mode(&mut self, mode: TextureMode) {
+        self.mark_changed();
+        self.texture_mode = mode;
+    }
+
+    pub fn texture_mode(&self) -> TextureMode {
+        self.texture_mode
+    }
+
+    /// Enable or disable ordered (4x4 Bayer) dithering of interpolated colors.
+    ///
+    /// Breaks up the 8-bit banding visible on large, smoothly shaded
+    /// triangles (Gouraud and texture-modulate paths). Off by default.
+    pub fn set_dithering(&mut self, enabled: bool) {
+        self.mark_changed();
+        self.dithering = enabled;
+    }
+
+    /// Whether interpolated colors are dithered.
+    pub fn dithering(&self) -> bool {
+        self.dithering
+    }
+
+    /// Fade filled geometry toward `fog.color` with distance.
+    ///
+    /// While fog is on the background is cleared to the fog color instead
+    /// of the palette background, so the horizon blends seamlessly.
+    pub fn set_fog(&mut self, fog: Fog) {
+        self.mark_changed();
+        self.fog = Some(fog);
+    }
+
+    /// Turn fog off.
+    pub fn clear_fog(&mut self) {
+        self.mark_changed();
+        self.fog = None;
+    }
+
+    /// The fog blended over distant surfaces, if any.
+    pub fn fog(&self) -> Option<Fog> {
+        self.fog
+    }
+
+    /// Outline a mesh's visible pixels from the next frame on.
+    ///
+    /// The mesh's triangles are redrawn into the renderer's mask wherever
+    /// they won the depth test, then every pixel within `thickness` of the
+    /// mask and outside it is painted, so parts hidden behind other
+    /// geometry get no outline.
+    pub fn set_selection_outline(&mut self, outline: SelectionOutline) {
+        self.mark_changed();
+        self.selection_outline = Some(outline);
+    }
+
+    /// Stop outlining the selection.
+    pub fn clear_selection_outline(&mut self) {
+        self.mark_changed();
+        self.selection_outline = None;
+    }
+
+    /// The outline drawn around the selected mesh, if any.
+    pub fn selection_outline(&self) -> Option<SelectionOutline> {
+        self.selection_outline
+    }
+
+    /// Outline every mesh's silhouette, or with `None`, stop.
+    ///
+    /// Traced in screen space after the opaque pass: wherever a mesh pixel
+    /// borders something much farther away (or nothing), the farther
+    /// pixels within `thickness` are painted. So, like an inverted hull,
+    /// it also inks where a mesh passes in front of itself, holds its
+    /// width at any distance, and leaves the grid and sprites alone.
+    pub fn set_silhouette_outline(&mut self, outline: Option<SilhouetteOutline>) {
+        self.mark_changed();
+        self.silhouette_outline = outline;
+    }
+
+    /// The outline inked around every mesh's silhouette, if any.
+    pub fn silhouette_outline(&self) -> Option<SilhouetteOutline> {
+        self.silhouette_outline
+    }
+
+    /// Render with the wobble, swimming textures and banding of
+    /// late-90s consoles, or with `None`, as usual again.
+    ///
+    /// [`Engine::update`] snaps projected vertices to a grid of
+    /// `virtual_resolution` cells over the viewport and, with
+    /// `affine_textures`, has the edge-function rasterizer skip perspective
+    /// correction. The frame's colors are cut to `color_depth` bits per
+    /// channel once the scene and its lines are drawn, before the HUD.
+    pub fn set_retro_mode(&mut self, retro: Option<RetroSettings>) {
+        self.mark_changed();
+        self.retro = retro;
+    }
+
+    /// The retro look frames are drawn with, if enabled.
+    pub fn retro_mode(&self) -> Option<RetroSettings> {
+        self.retro
+    }
+
+    /// Draw mesh bounding boxes and/or vertex normals as depth-tested
+    /// world-space lines over the scene. Takes effect from the next
+    /// [`Engine::update`].
+    pub fn set_debug_draw(&mut self, debug_draw: DebugDraw) {
+        self.mark_changed();
+        self.debug_draw = debug_draw;
+    }
+
+    /// Which debug overlays are drawn over the scene.
+    pub fn debug_draw(&self) -> DebugDraw {
+        self.debug_draw
+    }
+
+    /// Draw the frustum meshes are culled against as world-space lines,
+    /// and every mesh's bounding box colored by its [`CullResult`]: green
+    /// inside, yellow intersecting, red culled. Takes effect from the next
+    /// [`Engine::update`].
+    ///
+    /// The camera's own frustum only shows once it's frozen with
+    /// [`Engine::freeze_culling`] and the camera moves away.
+    pub fn set_debug_draw_frustum(&mut self, enabled: bool) {
+        self.mark_changed();
+        self.debug_draw_frustum = enabled;
+    }
+
+    /// Whether the culling frustum and cull-colored bounding boxes are drawn.
+    pub fn debug_draw_frustum(&self) -> bool {
+        self.debug_draw_frustum
+    }
+
+    /// Keep culling against the current view, wherever the camera goes
+    /// next, so the camera can fly out and look at what the frozen view
+    /// drew and rejected. Freezing again moves the frozen view to the
+    /// camera.
+    pub fn freeze_culling(&mut self) {
+        self.mark_changed();
+        self.frozen_cull_view = Some(self.projection_matrix * self.view_matrix());
+    }
+
+    /// Cull against the camera's view again.
+    pub fn unfreeze_culling(&mut self) {
+        self.mark_changed();
+        self.frozen_cull_view = None;
+    }
+
+    /// Whether culling is using a frozen view instead of the camera's.
+    pub fn culling_frozen(&self) -> bool {
+        self.frozen_cull_view.is_some()
+    }
+
+    /// How every placement of every mesh fared against the frustum in the
+    /// last [`Engine::update`], in model, mesh and instance order.
+    pub fn cull_results(&self) -> &[MeshCull] {
+        &self.cull_results
+    }
+
+    /// Time the stages of every frame (see [`crate::profile`]) into a
+    /// [`FrameProfile`], shown on the HUD. Scopes the app opens on the
+    /// thread driving the engine are timed too. Turning profiling off drops
+    /// the timings collected so far.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if enabled != self.profile.is_some() {
+            self.profile = enabled.then(FrameProfile::default);
+            profile::take_frame();
+        }
+        profile::set_enabled(enabled);
+    }
+
+    /// Whether per-stage frame timings are being collected.
+    pub fn profiling(&self) -> bool {
+        self.profile.is_some()
+    }
+
+    /// Per-stage timings over recent frames, or `None` unless profiling is
+    /// on. Each [`Engine::render`] closes a frame.
+    pub fn frame_profile(&self) -> Option<&FrameProfile> {
+        self.profile.as_ref()
+    }
+
+    /// [`Engine::frame_profile`], to change its logging.
+    pub fn frame_profile_mut(&mut self) -> Option<&mut FrameProfile> {
+        self.profile.as_mut()
+    }
+
+    /// Install (or with `None`, remove) a full-frame effect.
+    ///
+    /// The effect runs at the end of [`Engine::render`] on the display-size
+    /// frame, after the HUD and supersample resolve, so it applies to
+    /// everything that gets presented.
+    pub fn set_post_effect(&mut self, effect: Option<Box<dyn PostEffect>>) {
+        self.post_effect = effect;
+        self.post_process_time = 0.0;
+    }
+
+    /// The effect applied to finished frames, if any.
+    pub fn post_effect(&self) -> Option<&dyn PostEffect> {
+        self.post_effect.as_deref()
+    }
+
+    /// Time in seconds the post effect took in the last [`Engine::render`].
+    pub fn post_process_time(&self) -> f32 {
+        self.post_process_time
+    }
 
-    /// Set the global texture (used when models don't have their own).
-    pub fn set_texture(&mut self, texture: Texture) {
-        self.global_texture = Some(texture);
+    // ============ Recording ============
+
+    /// Record the next `max_frames` rendered frames to `<dir>/000.png`
+    /// onward. Files are written on a background thread; see
+    /// [`FrameRecorder`]. Any recording already running is finished first.
+    pub fn start_recording(
+        &mut self,
+        dir: impl Into<PathBuf>,
+        max_frames: u32,
+    ) -> Result<(), PresentError> {
+        // Dropping the old recorder waits for its queued frames
+        self.recorder = None;
+        self.recorder = Some(FrameRecorder::new(dir, max_frames)?);
+        Ok(())
     }
 
-    /// Clear the global texture.
-    pub fn clear_texture(&mut self) {
-        self.global_texture = None;
+    /// Stop recording and wait for queued frames to reach disk. Returns the
+    /// final counts, `None` if nothing was recording, or the first error
+    /// writing a file.
+    pub fn stop_recording(&mut self) -> Result<Option<RecordingStats>, PresentError> {
+        self.recorder.take().map(FrameRecorder::finish).transpose()
     }
 
-    /// Get the global texture.
-    pub fn texture(&self) -> Option<&Texture> {
-        self.global_texture.as_ref()
+    /// The recording in progress, for its [`FrameRecorder::stats`] and
+    /// [`FrameRecorder::is_complete`].
+    pub fn recorder(&self) -> Option<&FrameRecorder> {
+        self.recorder.as_ref()
     }
 
-    pub fn set_texture_mode(&mut self, mode: TextureMode) {
-        self.texture_mode = mode;
+    /// Queue the current frame on the recording, as [`Engine::render`] does
+    /// at the end of every frame. Useful to also record a frame that was
+    /// drawn on after rendering. Returns whether the frame was queued.
+    pub fn capture(&mut self) -> Result<bool, PresentError> {
+        let (width, height) = self.frame_size();
+        let Some(recorder) = &mut self.recorder else {
+            return Ok(false);
+        };
+        let frame = if !self.resolved.is_empty() {
+            pixels_as_bytes(&self.resolved)
+        } else {
+            self.renderer.as_argb8888_bytes()
+        };
+        recorder.capture(frame, width, height)
     }
 
-    pub fn texture_mode(&self) -> TextureMode {
-        self.texture_mode
+    /// Save the last rendered frame, exactly as presented (HUD and post
+    /// effect included), to `<dir>/screenshot_YYYYMMDD_HHMMSS.png` with the
+    /// current UTC time. `dir` is created if missing, and a number is added
+    /// to the name rather than overwriting an earlier screenshot. Returns
+    /// the path written.
+    pub fn save_screenshot(&self, dir: impl AsRef<Path>) -> Result<PathBuf, PresentError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(|e| PresentError::Image(e.to_string()))?;
+        let path = screenshot_path(dir, SystemTime::now());
+        let (width, height) = self.frame_size();
+        save_png(&path, self.frame_buffer(), width, height)?;
+        Ok(path)
+    }
+
+    /// Choose between the screen-space and world-space ground grid.
+    pub fn set_grid(&mut self, mode: GridMode) {
+        self.mark_changed();
+        self.grid = mode;
+    }
+
+    /// Which ground grid is drawn.
+    pub fn grid(&self) -> GridMode {
+        self.grid
+    }
+
+    /// Set the side length, in display pixels, of vertex markers.
+    pub fn set_vertex_marker_size(&mut self, size: u32) {
+        self.mark_changed();
+        self.vertex_marker_size = size;
+    }
+
+    /// Side length, in display pixels, of vertex markers.
+    pub fn vertex_marker_size(&self) -> u32 {
+        self.vertex_marker_size
+    }
+
+    /// Replace the colors used for clearing, grid, fill, wireframe, vertices, and HUD.
+    pub fn set_palette(&mut self, palette: RenderPalette) {
+        self.mark_changed();
+        self.palette = palette;
+    }
+
+    /// The colors frames are drawn with.
+    pub fn palette(&self) -> &RenderPalette {
+        &self.palette
+    }
+
+    /// The colors frames are drawn with, to change in place.
+    pub fn palette_mut(&mut self) -> &mut RenderPalette {
+        self.mark_changed();
+        &mut self.palette
+    }
+
+    /// Enable or disable anti-aliased (Xiaolin Wu) wireframe lines.
+    pub fn set_wireframe_aa(&mut self, enabled: bool) {
+        self.mark_changed();
+        self.wireframe_aa = enabled;
+    }
+
+    /// Whether wireframe lines are antialiased.
+    pub fn wireframe_aa(&self) -> bool {
+        self.wireframe_aa
+    }
+
+    /// Show or hide the on-screen debug HUD (FPS, triangle counts, modes).
+    ///
+    /// Drawn at the end of `render()`, on top of the geometry.
+    pub fn draw_debug_hud(&mut self, enabled: bool) {
+        self.debug_hud = enabled;
+    }
+
+    /// Whether the debug HUD is drawn over the frame.
+    pub fn debug_hud(&self) -> bool {
+        self.debug_hud
+    }
+
+    /// Times between [`Engine::render`] calls, with the averages and lows
+    /// the HUD shows.
+    pub fn frame_timer(&self) -> &FrameTimer {
+        &self.frame_timer
+    }
+
+    /// Geometry counters from the most recent `update()`, and pixel
+    /// counters from the most recent `render()`.
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    /// Enable or disable the picking ID buffer.
+    ///
+    /// While enabled, `render()` records which face won the depth test at
+    /// every pixel so [`Engine::pick`] can answer "what is under the mouse".
+    /// Only the filled pass writes IDs, so wireframe-only modes pick nothing.
+    pub fn set_picking(&mut self, enabled: bool) {
+        self.mark_changed();
+        self.picking = enabled;
+    }
+
+    /// Whether the ID buffer is written so [`Engine::pick`] works.
+    pub fn picking(&self) -> bool {
+        self.picking
+    }
+
+    /// Returns the face drawn at display pixel (x, y) in the last rendered frame.
+    ///
+    /// Returns None for background pixels, out-of-bounds coordinates, or
+    /// when picking is disabled.
+    pub fn pick(&self, x: i32, y: i32) -> Option<PickResult> {
+        if !self.picking {
+            return None;
+        }
+        if x < 0 || y < 0 || x >= self.display_width as i32 || y >= self.display_height as i32 {
+            return None;
+        }
+        // Sample the center of the display pixel in the internal target
+        let (x, y) = (
+            (x * 2 + 1) * self.renderer.width() as i32 / (self.display_width as i32 * 2),
+            (y * 2 + 1) * self.renderer.height() as i32 / (self.display_height as i32 * 2),
+        );
+        let id = self.renderer.id_at(x, y)?;
+        let target = self.pick_targets.get((id as usize).checked_sub(1)?)?;
+        let inv_depth = self.renderer.depth_at(x, y)?;
+        Some(PickResult {
+            model_index: target.model_index,
+            mesh_index: target.mesh_index,
+            face_index: target.face_index,
+            depth: 1.0 / inv_depth,
+        })
+    }
+
+    /// Builds the world-space ray through the center of display pixel (x, y).
+    ///
+    /// The pixel is unprojected through the inverse projection into view
+    /// space, then rotated into world space by the inverse view matrix. The
+    /// ray starts at the camera position.
+    pub fn screen_ray(&self, x: i32, y: i32) -> Ray {
+        // Screen -> NDC, at the pixel's center. Display pixels, so the
+        // display-sized viewport rather than the supersampled one.
+        let ndc = Viewport::full(self.display_width, self.display_height).unproject(x, y);
+
+        // NDC -> view space. Any depth works for a direction; use the far
+        // plane (z_ndc = 1) for the best precision.
+        let view = self.projection.inverse_matrix() * Vec4::new(ndc.x, ndc.y, 1.0, 1.0);
+        let view_dir = view.to_vec3() / view.w;
+
+        // View -> world. w = 0 so only the camera rotation applies.
+        let inverse_view = self.view_matrix().inverse().unwrap_or(Mat4::identity());
+        let world_dir = (inverse_view * Vec4::from_vec3(view_dir, 0.0)).to_vec3();
+
+        Ray::new(self.camera.position(), world_dir)
+    }
+
+    /// Returns the nearest face hit by a ray from `origin` along `dir`.
+    ///
+    /// Tests every face of every model in world space with Möller–Trumbore.
+    /// Faces are two-sided and frustum culling does not apply, so faces
+    /// outside the current view can still be hit.
+    pub fn raycast(&self, origin: Vec3, dir: Vec3) -> Option<RayHit> {
+        if dir.magnitude() <= f32::EPSILON {
+            return None;
+        }
+        let ray = Ray::new(origin, dir);
+        let mut nearest: Option<RayHit> = None;
+
+        for (model_index, model) in self.models.iter().enumerate() {
+            let model_world_matrix = model.transform().to_matrix();
+
+            for (mesh_index, mesh) in model.meshes().iter().enumerate() {
+                for placement in mesh.placements() {
+                    let world_matrix = model_world_matrix * placement.matrix;
+
+                    for (face_index, face) in mesh.faces().iter().enumerate() {
+                        let Some([a, b, c]) = mesh.face_vertices(face) else {
+                            continue;
+                        };
+                        let a = world_matrix * a.position;
+                        let b = world_matrix * b.position;
+                        let c = world_matrix * c.position;
+
+                        let Some(hit) = ray.intersect_triangle(a, b, c) else {
+                            continue;
+                        };
+                        if nearest.is_some_and(|n| n.distance <= hit.t) {
+                            continue;
+                        }
+                        nearest = Some(RayHit {
+                            model_index,
+                            mesh_index,
+                            face_index,
+                            distance: hit.t,
+                            barycentric: hit.barycentric,
+                            world_point: ray.at(hit.t),
+                        });
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
+    /// Advance model and mesh animators by `dt` seconds, then [`Engine::update`].
+    ///
+    /// Use this instead of `update` when anything in the scene has an
+    /// [`Animator`](crate::animation::Animator) attached, so motion follows
+    /// wall-clock time rather than frame count.
+    pub fn update_with_dt(&mut self, dt: f32) {
+        self.animate(dt);
+        self.update();
+    }
+
+    /// Advance model and mesh animators and the camera shake by `dt`
+    /// seconds, leaving the triangles alone until the next
+    /// [`Engine::update`]. For stepping animation at a fixed rate apart
+    /// from rendering; see [`GameLoop`](crate::window::GameLoop).
+    pub fn animate(&mut self, dt: f32) {
+        for model in &mut self.models {
+            if model.animator().is_some() || model.meshes().iter().any(|m| m.animator().is_some()) {
+                self.changes += 1;
+            }
+            model.animate(dt);
+        }
+        if let Some(shake) = &mut self.camera_shake {
+            shake.update(dt);
+        }
     }
 
     /// Update the engine state - transforms vertices and builds triangles to render.
+    ///
+    /// Culls models and meshes against the view frustum, then hands each
+    /// remaining mesh to a [`GeometryProcessor`] for the per-face pipeline
+    /// and keeps the triangles it returns for [`Engine::render`].
+    ///
+    /// [`GeometryProcessor`]: crate::geometry::GeometryProcessor
     pub fn update(&mut self) {
-        let buffer_width = self.renderer.width();
-        let buffer_height = self.renderer.height();
+        profile::set_enabled(self.profile.is_some());
+        if self.hot_reload.as_ref().is_some_and(HotReload::due) {
+            self.reload_changed_assets();
+        }
+        let key = self.frame_key();
+        let queued = !self.billboards.is_empty() || !self.queued_lines.is_empty();
+        if self.skip_unchanged_frames
+            && !queued
+            && !self.updated_queue
+            && self.updated_from == Some(key)
+        {
+            self.frame_changed = false;
+            return;
+        }
+        (self.updated_from, self.updated_queue) = (Some(key), queued);
+        self.frame_changed = true;
+        let viewport = self.viewport();
         let camera_position = self.camera.position();
-        let view_matrix = self.camera.view_matrix();
+        let view_matrix = self.view_matrix();
         // Extract world-space frustum planes from VP via Gribb-Hartmann.
         // World-space planes let us skip a per-mesh view_matrix multiply in
         // every cull test below.
-        let frustum = Frustum::from_matrix(&(self.projection_matrix * view_matrix));
+        let view_projection = self.projection_matrix * view_matrix;
+        let cull_view_projection = self.frozen_cull_view.unwrap_or(view_projection);
+        let frustum = Frustum::from_matrix(&cull_view_projection);
         let backface_culling = self.backface_culling;
-        let shading_mode = self.shading_mode;
+        let processor = GeometryProcessor {
+            view_matrix,
+            projection_matrix: self.projection_matrix,
+            camera_position,
+            viewport,
+            handedness: self.projection.handedness(),
+            light: self.light,
+            shading_mode: self.shading_mode,
+            texture_mode: self.texture_mode,
+            pixel_lighting: self.lit_per_pixel(),
+            // Normal mapping defers lighting to the shader
+            per_pixel_light: self.texture_mode == TextureMode::NormalMapped
+                && self.normal_map.is_some()
+                && self.shading_mode != ShadingMode::None,
+            dithering: self.dithering,
+            snap_grid: self.retro.map(|retro| {
+                let (columns, rows) = retro.virtual_resolution;
+                Vec2::new(
+                    viewport.width / columns.max(1) as f32,
+                    viewport.height / rows.max(1) as f32,
+                )
+            }),
+            affine_textures: self.retro.is_some_and(|retro| retro.affine_textures),
+            // False colors are only readable unfogged, and pixels lit
+            // after shading are fogged after lighting
+            fog: self
+                .fog
+                .filter(|_| self.debug_view == DebugView::None && !self.lit_per_pixel()),
+            fill_color: self.palette.fill,
+            fill_normals: self.debug_view == DebugView::Normals,
+            profile: self.profile.is_some(),
+            min_triangle_area: self.min_triangle_area,
+        };
 
-        let mut triangles_per_model: Vec<Vec<Triangle>> = Vec::with_capacity(self.models.len());
+        let mut batches = Vec::new();
+        let mut pick_targets = Vec::new();
+        let mut debug_lines = std::mem::take(&mut self.queued_lines);
+        let mut cull_results = std::mem::take(&mut self.cull_results);
+        cull_results.clear();
+        let mut stats = FrameStats::default();
 
         // Iterate over all models in the scene
-        for model in &self.models {
-            let mut model_triangles = Vec::new();
-
+        for (model_index, model) in self.models.iter().enumerate() {
             // Model world matrix from transform
             let model_world_matrix = model.transform().to_matrix();
 
@@ -361,14 +2882,48 @@ impl Engine {
             // the per-mesh frustum tests (they're guaranteed to pass).
             let model_bounds = model.bounds();
             let model_world_center = model_world_matrix * model_bounds.center;
-            let m_scl = model.transform().scale();
-            let model_scale_max = m_scl.x.abs().max(m_scl.y.abs()).max(m_scl.z.abs());
+            let model_scl = model.transform().scale();
+            let model_scale_max = model_scl
+                .x
+                .abs()
+                .max(model_scl.y.abs())
+                .max(model_scl.z.abs());
             let model_world_radius = model_bounds.radius * model_scale_max;
+            let model_normal_matrix = model.transform().normal_matrix();
+            // Instanced meshes draw their faces once per instance
+            let model_faces: usize = model
+                .meshes()
+                .iter()
+                .map(|m| m.faces().len() * m.instances().len().max(1))
+                .sum();
+            stats.faces_total += model_faces;
 
             let skip_mesh_cull =
                 match frustum.classify_sphere(model_world_center, model_world_radius) {
                     FrustumTest::Outside => {
-                        triangles_per_model.push(model_triangles);
+                        stats.faces_frustum_culled += model_faces;
+                        for (mesh_index, mesh) in model.meshes().iter().enumerate() {
+                            stats.meshes_total += 1;
+                            if mesh.is_instanced() {
+                                stats.instances_total += mesh.instances().len();
+                                stats.instances_culled += mesh.instances().len();
+                            }
+                            for (instance, placement) in mesh.placements().enumerate() {
+                                let cull = MeshCull {
+                                    model_index,
+                                    mesh_index,
+                                    instance: mesh.is_instanced().then_some(instance),
+                                    result: CullResult::Culled,
+                                };
+                                self.record_cull(
+                                    cull,
+                                    mesh,
+                                    model_world_matrix * placement.matrix,
+                                    &mut cull_results,
+                                    &mut debug_lines,
+                                );
+                            }
+                        }
                         continue;
                     }
                     FrustumTest::FullyInside => true,
@@ -376,316 +2931,1066 @@ impl Engine {
                 };
 
             // Iterate over all meshes in this model
-            for mesh in model.meshes() {
-                // Mesh local matrix from transform
-                let mesh_local_matrix = mesh.transform().to_matrix();
-
-                // Combined world matrix: model_world * mesh_local
-                let world_matrix = model_world_matrix * mesh_local_matrix;
-
-                // Scales are needed both for the cull radius and the normal matrix.
-                let model_scl = model.transform().scale();
-                let mesh_scl = mesh.transform().scale();
-
-                if !skip_mesh_cull {
-                    // --- Layer 1: bounding-sphere test (with coherency cache) ---
-                    let bounds_world_center = world_matrix * mesh.bounds().center;
-                    let scale_max = (model_scl.x * mesh_scl.x)
-                        .abs()
-                        .max((model_scl.y * mesh_scl.y).abs())
-                        .max((model_scl.z * mesh_scl.z).abs());
-                    let world_radius = scale_max * mesh.bounds().radius;
-
-                    if !frustum.contains_sphere_cached(
-                        bounds_world_center,
-                        world_radius,
-                        mesh.cull_cache(),
-                    ) {
-                        continue;
+            for (mesh_index, mesh) in model.meshes().iter().enumerate() {
+                stats.meshes_total += 1;
+                let instanced = mesh.is_instanced();
+
+                // The global flag overrides every mesh's own mode
+                let cull_mode = if backface_culling {
+                    mesh.cull_mode()
+                } else {
+                    CullMode::None
+                };
+                let pass_flags = self.effective_render_mode(mesh).pass_flags();
+
+                // Every placement of the mesh (each instance, or just its
+                // own transform) shares one triangle list and batch
+                let mut mesh_triangles = Vec::new();
+                let mut placed = false;
+                for (instance, placement) in mesh.placements().enumerate() {
+                    if instanced {
+                        stats.instances_total += 1;
                     }
 
-                    // --- Layer 2: AABB n/p-vertex test for a tighter answer ---
-                    // Transform the 8 local-space AABB corners into world space,
-                    // then take their enclosing axis-aligned box.
-                    let mut world_min = Vec3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
-                    let mut world_max =
-                        Vec3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
-                    for c in mesh.aabb().corners() {
-                        let v = world_matrix * c;
-                        world_min.x = world_min.x.min(v.x);
-                        world_min.y = world_min.y.min(v.y);
-                        world_min.z = world_min.z.min(v.z);
-                        world_max.x = world_max.x.max(v.x);
-                        world_max.y = world_max.y.max(v.y);
-                        world_max.z = world_max.z.max(v.z);
-                    }
-                    if frustum.aabb_outside(world_min, world_max) {
-                        continue;
-                    }
-                }
+                    // Combined world matrix: model_world * mesh_local
+                    let world_matrix = model_world_matrix * placement.matrix;
+                    let bounds_world_center = world_matrix * mesh.bounds().center;
+                    let mut cull = MeshCull {
+                        model_index,
+                        mesh_index,
+                        instance: instanced.then_some(instance),
+                        result: CullResult::Inside,
+                    };
 
-                let faces = mesh.faces();
-                let vertices = mesh.vertices();
-
-                // Normal matrix = inverse transpose of rotation+scale (excludes translation)
-                // Combine model and mesh rotation+scale for correct normal transformation
-                let model_rot = model.transform().rotation();
-                let mesh_rot = mesh.transform().rotation();
-
-                let combined_rotation_scale = Mat4::rotation_x(model_rot.x)
-                    * Mat4::rotation_y(model_rot.y)
-                    * Mat4::rotation_z(model_rot.z)
-                    * Mat4::scaling(model_scl.x, model_scl.y, model_scl.z)
-                    * Mat4::rotation_x(mesh_rot.x)
-                    * Mat4::rotation_y(mesh_rot.y)
-                    * Mat4::rotation_z(mesh_rot.z)
-                    * Mat4::scaling(mesh_scl.x, mesh_scl.y, mesh_scl.z);
-
-                let normal_matrix = combined_rotation_scale
-                    .inverse()
-                    .unwrap_or(Mat4::identity())
-                    .transpose();
-
-                for face in faces.iter() {
-                    let face_vertices: [Vertex; 3] = [
-                        vertices[face.a as usize],
-                        vertices[face.b as usize],
-                        vertices[face.c as usize],
-                    ];
-
-                    let face_texcoords: [Texel; 3] = [
-                        face_vertices[0].texel,
-                        face_vertices[1].texel,
-                        face_vertices[2].texel,
-                    ];
-
-                    // Model Space --> World Space (positions)
-                    let world_space_positions = [
-                        world_matrix * face_vertices[0].position,
-                        world_matrix * face_vertices[1].position,
-                        world_matrix * face_vertices[2].position,
-                    ];
-
-                    // Calculate face normal (needed for backface culling).
-                    // Note: this is a left-handed coordinate system, so under
-                    // the left-hand rule (B-A) × (C-A) points toward the
-                    // camera exactly when the triangle is wound CW from the
-                    // viewer's side. CW is therefore "front-facing" here.
-                    let vec_ab = world_space_positions[1] - world_space_positions[0];
-                    let vec_ac = world_space_positions[2] - world_space_positions[0];
-                    let face_normal = vec_ab.cross(vec_ac);
-
-                    // Backface cull: if the face normal points away from the
-                    // camera (dot with the camera-ward ray is negative), the
-                    // triangle is facing away and we skip it. Flip this sign
-                    // if the scene's meshes are CCW-wound.
-                    if backface_culling {
-                        let camera_ray = camera_position - world_space_positions[0];
-                        if face_normal.dot(camera_ray) < 0.0 {
+                    if !skip_mesh_cull {
+                        // --- Layer 1: bounding-sphere test (with coherency cache) ---
+                        // Scales multiply per axis, like the transforms.
+                        let scale = placement.scale;
+                        let scale_max = (model_scl.x * scale.x)
+                            .abs()
+                            .max((model_scl.y * scale.y).abs())
+                            .max((model_scl.z * scale.z).abs());
+                        let world_radius = scale_max * mesh.bounds().radius;
+
+                        if !frustum.contains_sphere_cached(
+                            bounds_world_center,
+                            world_radius,
+                            mesh.cull_cache(),
+                        ) {
+                            stats.faces_frustum_culled += mesh.faces().len();
+                            stats.instances_culled += instanced as usize;
+                            cull.result = CullResult::Culled;
+                            self.record_cull(
+                                cull,
+                                mesh,
+                                world_matrix,
+                                &mut cull_results,
+                                &mut debug_lines,
+                            );
                             continue;
                         }
-                    }
 
-                    // Transform to view (camera) space
-                    let view_space_positions = [
-                        view_matrix * world_space_positions[0],
-                        view_matrix * world_space_positions[1],
-                        view_matrix * world_space_positions[2],
-                    ];
-
-                    // Calculate colors based on shading mode
-                    // Use white for textured modulate mode so lighting doesn't darken the texture
-                    let base_color = if self.texture_mode == TextureMode::Modulate {
-                        0xFFFFFFFF // White - full brightness when lit
-                    } else {
-                        colors::FILL
-                    };
-                    let (flat_color, vertex_colors) = match shading_mode {
-                        ShadingMode::None => {
-                            // No lighting - use base color
-                            (base_color, [base_color, base_color, base_color])
-                        }
-                        ShadingMode::Flat => {
-                            // Flat shading - one color per face based on face normal
-                            let normal = face_normal.normalize();
-                            let diffuse =
-                                self.light.intensity(normal) * self.light.diffuse_strength;
-                            let intensity = (diffuse + self.light.ambient_intensity).min(1.0);
-                            let color = colors::modulate(base_color, intensity);
-                            (color, [color, color, color])
+                        // --- Layer 2: AABB n/p-vertex test for a tighter answer ---
+                        // Transform the 8 local-space AABB corners into world space,
+                        // then take their enclosing axis-aligned box.
+                        let mut world_min = Vec3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+                        let mut world_max =
+                            Vec3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+                        for c in mesh.aabb().corners() {
+                            let v = world_matrix * c;
+                            world_min.x = world_min.x.min(v.x);
+                            world_min.y = world_min.y.min(v.y);
+                            world_min.z = world_min.z.min(v.z);
+                            world_max.x = world_max.x.max(v.x);
+                            world_max.y = world_max.y.max(v.y);
+                            world_max.z = world_max.z.max(v.z);
                         }
-                        ShadingMode::Gouraud => {
-                            // Gouraud shading - per-vertex lighting
-                            let mut vert_colors = [0u32; 3];
-                            for i in 0..3 {
-                                let world_normal =
-                                    (normal_matrix * face_vertices[i].normal).normalize();
-                                let diffuse = self.light.intensity(world_normal)
-                                    * self.light.diffuse_strength;
-                                let intensity = (diffuse + self.light.ambient_intensity).min(1.0);
-                                vert_colors[i] = colors::modulate(base_color, intensity);
-                            }
-                            let avg_color = vert_colors[0];
-                            (avg_color, vert_colors)
+                        if frustum.aabb_outside(world_min, world_max) {
+                            stats.faces_frustum_culled += mesh.faces().len();
+                            stats.instances_culled += instanced as usize;
+                            cull.result = CullResult::Culled;
+                            self.record_cull(
+                                cull,
+                                mesh,
+                                world_matrix,
+                                &mut cull_results,
+                                &mut debug_lines,
+                            );
+                            continue;
                         }
-                    };
 
-                    // ==================== PROJECT TO CLIP SPACE ====================
-                    // Transform from view space to clip space (homogeneous coordinates)
-                    let clip_space_positions = [
-                        self.projection_matrix * Vec4::from_vec3(view_space_positions[0], 1.0),
-                        self.projection_matrix * Vec4::from_vec3(view_space_positions[1], 1.0),
-                        self.projection_matrix * Vec4::from_vec3(view_space_positions[2], 1.0),
-                    ];
-
-                    // ==================== CLIP IN CLIP SPACE ====================
-                    // Create ClipSpaceVertex instances with homogeneous positions
-                    let clip_vertices = [
-                        ClipSpaceVertex::new(
-                            clip_space_positions[0],
-                            face_texcoords[0],
-                            vertex_colors[0],
-                        ),
-                        ClipSpaceVertex::new(
-                            clip_space_positions[1],
-                            face_texcoords[1],
-                            vertex_colors[1],
-                        ),
-                        ClipSpaceVertex::new(
-                            clip_space_positions[2],
-                            face_texcoords[2],
-                            vertex_colors[2],
-                        ),
-                    ];
-
-                    // Clip against the canonical clip cube: -w <= x,y,z <= w
-                    let polygon = ClipSpacePolygon::from_triangle(
-                        clip_vertices[0],
-                        clip_vertices[1],
-                        clip_vertices[2],
+                        if frustum.classify_sphere(bounds_world_center, world_radius)
+                            != FrustumTest::FullyInside
+                        {
+                            cull.result = CullResult::Intersecting;
+                        }
+                    }
+                    self.record_cull(
+                        cull,
+                        mesh,
+                        world_matrix,
+                        &mut cull_results,
+                        &mut debug_lines,
                     );
-                    let clipped_polygon = self.clipper.clip_polygon(polygon);
 
-                    // Skip if polygon was completely clipped away
-                    if clipped_polygon.is_empty() {
-                        continue;
+                    // Normal matrix = inverse transpose of rotation+scale (excludes
+                    // translation). (A * B)^-T = A^-T * B^-T, so the model's and
+                    // the placement's own normal matrices combine like their transforms.
+                    let normal_matrix = model_normal_matrix * placement.normal_matrix;
+
+                    // Far placements draw a simplified level in its place
+                    let lod = mesh.lod_for((bounds_world_center - camera_position).magnitude());
+                    stats.faces_lod_reduced += mesh.faces().len().saturating_sub(lod.faces().len());
+
+                    let mut view = MeshView::new(lod, world_matrix, normal_matrix);
+                    view.cull_mode = cull_mode;
+                    view.pass_flags = pass_flags;
+                    // One picking ID per source face and placement, in face order
+                    if self.picking {
+                        view.first_id = pick_targets.len() as u32 + 1;
+                        pick_targets.extend((0..lod.faces().len()).map(|face_index| PickTarget {
+                            model_index,
+                            mesh_index,
+                            face_index,
+                        }));
                     }
 
-                    // ==================== PERSPECTIVE DIVIDE & VIEWPORT TRANSFORM ====================
-                    // Triangulate the clipped polygon and transform to screen space
-                    for (v0, v1, v2) in clipped_polygon.triangulate() {
-                        let clipped_positions = [v0.position, v1.position, v2.position];
-                        let clipped_texcoords = [v0.texcoord, v1.texcoord, v2.texcoord];
-                        let clipped_colors = [v0.color, v1.color, v2.color];
-
-                        let mut screen_vertices = [ScreenVertex::new(Vec2::ZERO, 0.0); 3];
-                        let mut all_valid = true;
-
-                        for (i, clip_pos) in clipped_positions.iter().enumerate() {
-                            // After clipping, w should always be positive
-                            // but check anyway for safety
-                            if clip_pos.w <= 0.0 {
-                                all_valid = false;
-                                break;
-                            }
-
-                            // Perspective divide: clip space -> NDC [-1, 1]
-                            let ndc_x = clip_pos.x / clip_pos.w;
-                            let ndc_y = clip_pos.y / clip_pos.w;
+                    processor.process_mesh_into(&view, &mut mesh_triangles, &mut stats);
+                    placed = true;
+                    self.debug_draw.lines_for(
+                        mesh,
+                        world_matrix,
+                        normal_matrix,
+                        self.palette.bounds,
+                        &mut debug_lines,
+                    );
+                }
 
-                            // Viewport transform: NDC -> screen coordinates
-                            let screen_x = (ndc_x + 1.0) * 0.5 * buffer_width as f32;
-                            let screen_y = (1.0 - ndc_y) * 0.5 * buffer_height as f32;
+                if placed {
+                    batches.push(TriangleBatch {
+                        model_index,
+                        mesh_index,
+                        triangles: mesh_triangles,
+                    });
+                }
+            }
+        }
 
-                            // Store w for depth buffer (1/w) and perspective-correct interpolation
-                            screen_vertices[i] =
-                                ScreenVertex::new(Vec2::new(screen_x, screen_y), clip_pos.w);
-                        }
+        let billboards = std::mem::take(&mut self.billboards);
+        self.billboard_triangles =
+            self.billboard_triangles(&billboards, &processor, &frustum, &mut stats);
 
-                        if all_valid {
-                            // Use flat_color for flat shading, interpolated colors for Gouraud
-                            let tri_color = if shading_mode == ShadingMode::Gouraud {
-                                clipped_colors[0] // Use first vertex color as representative
-                            } else {
-                                flat_color
-                            };
-
-                            model_triangles.push(Triangle::new(
-                                screen_vertices,
-                                tri_color,
-                                clipped_colors,
-                                clipped_texcoords,
-                                shading_mode,
-                                self.texture_mode,
-                            ));
-                        }
-                    }
-                }
+        // The depth buffer sorts per pixel; without it, whole triangles
+        // are ordered here
+        self.batches = match self.hidden_surface {
+            HiddenSurface::DepthBuffer => batches,
+            HiddenSurface::PainterSort => painter_sort(batches),
+        };
+        self.batches_generation += 1;
+        self.batches_view_projection = view_projection;
+        self.pick_targets = pick_targets;
+        if self.debug_draw_frustum {
+            if let Some(inverse) = cull_view_projection.inverse() {
+                // Corners of the clip-space cube, back into the world
+                let corners = std::array::from_fn(|i| {
+                    let bit = |b: usize| if i & b == 0 { -1.0 } else { 1.0 };
+                    (inverse * Vec4::new(bit(1), bit(2), bit(4), 1.0)).to_vec3_perspective()
+                });
+                box_lines(corners, colors::FRUSTUM, &mut debug_lines);
             }
+        }
+        self.debug_lines = debug_lines;
+        self.cull_results = cull_results;
+        self.stats = FrameStats {
+            pixels_shaded: self.stats.pixels_shaded,
+            ..stats
+        };
+    }
+
+    /// Keep `cull` for [`Engine::cull_results`] and, while the frustum is
+    /// drawn, outline the placement's bounds in its result's color.
+    fn record_cull(
+        &self,
+        cull: MeshCull,
+        mesh: &Mesh,
+        world_matrix: Mat4,
+        results: &mut Vec<MeshCull>,
+        lines: &mut Vec<DebugLine>,
+    ) {
+        results.push(cull);
+        if self.debug_draw_frustum {
+            let color = match cull.result {
+                CullResult::Inside => colors::CULL_INSIDE,
+                CullResult::Intersecting => colors::CULL_INTERSECTING,
+                CullResult::Culled => colors::CULL_CULLED,
+            };
+            box_lines(
+                mesh.aabb().corners().map(|c| world_matrix * c),
+                color,
+                lines,
+            );
+        }
+    }
 
-            triangles_per_model.push(model_triangles);
+    /// Expand queued billboards into quads facing the camera and run them
+    /// through `processor`, unlit. Alpha-blended ones come out farthest
+    /// first so nearer sprites blend over them.
+    fn billboard_triangles(
+        &self,
+        billboards: &[Billboard],
+        processor: &GeometryProcessor,
+        frustum: &Frustum,
+        stats: &mut FrameStats,
+    ) -> Vec<Triangle> {
+        let camera_position = processor.camera_position;
+        // The view matrix's first two rows are the camera's right and up
+        // axes in world space
+        let view = processor.view_matrix;
+        let right = Vec3::new(view.get(0, 0), view.get(0, 1), view.get(0, 2));
+        let up = Vec3::new(view.get(1, 0), view.get(1, 1), view.get(1, 2));
+        let textured = self.billboard_texture.is_some();
+        let processor = GeometryProcessor {
+            shading_mode: ShadingMode::None,
+            texture_mode: if textured {
+                TextureMode::Modulate
+            } else {
+                TextureMode::None
+            },
+            per_pixel_light: false,
+            ..*processor
+        };
+
+        let mut order: Vec<&Billboard> = billboards.iter().collect();
+        if self.billboard_blend == BlendMode::Alpha {
+            let distance = |b: &Billboard| (b.position - camera_position).magnitude();
+            order.sort_by(|a, b| distance(b).total_cmp(&distance(a)));
         }
 
-        // No sorting needed - depth buffer handles hidden surface removal
-        self.triangles_per_model = triangles_per_model;
+        let mut triangles = Vec::with_capacity(order.len() * 2);
+        for billboard in order {
+            stats.faces_total += BILLBOARD_FACES.len();
+            let half = billboard.size * 0.5;
+            // Radius of the quad's circumscribed sphere
+            let radius = half * std::f32::consts::SQRT_2;
+            if frustum.classify_sphere(billboard.position, radius) == FrustumTest::Outside {
+                stats.faces_frustum_culled += BILLBOARD_FACES.len();
+                continue;
+            }
+
+            let (right, up) = (right * half, up * half);
+            let normal = (camera_position - billboard.position).normalize();
+            let corner = |offset: Vec3, u: f32, v: f32| Vertex {
+                position: billboard.position + offset,
+                normal,
+                texel: Vec2::new(u, v),
+                color: billboard.color,
+                tangent: Vec4::ZERO,
+            };
+            let vertices = [
+                corner(-right - up, 0.0, 0.0),
+                corner(-right + up, 0.0, 1.0),
+                corner(right + up, 1.0, 1.0),
+                corner(right - up, 1.0, 0.0),
+            ];
+            let view = MeshView {
+                vertices: &vertices,
+                faces: &BILLBOARD_FACES,
+                world_matrix: Mat4::identity(),
+                normal_matrix: Mat4::identity(),
+                cull_mode: CullMode::None,
+                has_vertex_colors: false,
+                texture_region: billboard.region,
+                pass_flags: Triangle::FILL_PASS,
+                first_id: 0,
+            };
+            let processor = GeometryProcessor {
+                fill_color: billboard.color,
+                ..processor
+            };
+            let (mut quad, quad_stats) = processor.process_mesh(&view);
+            *stats += quad_stats;
+            if textured {
+                // Modulate starts from white; tint the texels instead
+                let tint = colors::to_rgb_f32(billboard.color);
+                for triangle in &mut quad {
+                    triangle.vertex_colors = [tint; 3];
+                }
+            }
+            triangles.append(&mut quad);
+        }
+        triangles
     }
 
     /// Render the current frame
     pub fn render(&mut self) {
-        self.renderer.clear(colors::BACKGROUND);
-        self.renderer.clear_depth();
+        profile::set_enabled(self.profile.is_some());
+        // A full frame replaces whatever an incremental one had drawn
+        self.incremental = None;
+        self.tick_frame_time();
+        let key = (self.batches_generation, self.frame_key());
+        if self.skip_unchanged_frames
+            && self.static_canvas_key == Some(key)
+            && self.static_canvas.len() == self.renderer.pixels().len()
+        {
+            // Nothing changed: the last frame again, under fresh overlays
+            self.renderer
+                .pixels_mut()
+                .copy_from_slice(&self.static_canvas);
+        } else {
+            self.draw_frame();
+            if self.skip_unchanged_frames {
+                self.static_canvas.clear();
+                self.static_canvas.extend_from_slice(self.renderer.pixels());
+                self.static_canvas_key = Some(key);
+            }
+        }
+        self.present();
+        self.close_profile_frame();
+    }
+
+    /// Update and draw the scene into `target` at the target's own size
+    /// and aspect ratio, leaving the engine's frame as it was.
+    ///
+    /// Draws what [`Engine::render`] does short of presenting: no HUD,
+    /// supersampling, resolution scale, post effect or recording. Queued
+    /// billboards and lines are drawn but stay queued for the next
+    /// [`Engine::update`], and [`Engine::stats`] keeps describing the
+    /// engine's own frame.
+    pub fn render_to(&mut self, target: &mut RenderTarget) {
+        let (width, height) = (target.width(), target.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        target.set_depth_encoding(self.renderer.depth_encoding());
+        target.set_depth_plane(self.hidden_surface == HiddenSurface::DepthBuffer);
+        target.set_gbuffer_planes(self.pipeline == Pipeline::Deferred);
+        let renderer = Renderer::with_target(std::mem::take(target));
+        let renderer = std::mem::replace(&mut self.renderer, renderer);
+        let display = (
+            self.display_width,
+            self.display_height,
+            self.supersampling,
+            self.resolution_scale,
+        );
+        (
+            self.display_width,
+            self.display_height,
+            self.supersampling,
+            self.resolution_scale,
+        ) = (width, height, 1, 1.0);
+        let aspect_ratio = self.projection.aspect_ratio();
+        self.projection
+            .set_aspect_ratio(width as f32 / height as f32);
+        self.projection_matrix = self.projection.matrix();
+        // Set the engine's own frame aside
+        let billboards = self.billboards.clone();
+        let queued_lines = self.queued_lines.clone();
+        let batches = std::mem::take(&mut self.batches);
+        let billboard_triangles = std::mem::take(&mut self.billboard_triangles);
+        let pick_targets = std::mem::take(&mut self.pick_targets);
+        let debug_lines = std::mem::take(&mut self.debug_lines);
+        let (generation, view_projection) = (self.batches_generation, self.batches_view_projection);
+        // Always drawn in full, without disturbing the engine's own frame
+        let updated = (
+            self.updated_from.take(),
+            self.updated_queue,
+            self.frame_changed,
+        );
+        let stats = self.stats;
+        // Offscreen frames stay out of the profile
+        let frame_profile = self.profile.take();
+
+        self.update();
+        self.draw_frame();
+
+        self.profile = frame_profile;
+        profile::set_enabled(self.profile.is_some());
+        self.billboards = billboards;
+        self.queued_lines = queued_lines;
+        self.batches = batches;
+        self.billboard_triangles = billboard_triangles;
+        self.pick_targets = pick_targets;
+        self.debug_lines = debug_lines;
+        (self.batches_generation, self.batches_view_projection) = (generation, view_projection);
+        (self.updated_from, self.updated_queue, self.frame_changed) = updated;
+        self.stats = stats;
+        self.projection.set_aspect_ratio(aspect_ratio);
+        self.projection_matrix = self.projection.matrix();
+        (
+            self.display_width,
+            self.display_height,
+            self.supersampling,
+            self.resolution_scale,
+        ) = display;
+        *target = std::mem::replace(&mut self.renderer, renderer).into_target();
+    }
+
+    /// Render part of a frame too heavy to draw at once: fill as many of
+    /// the triangles from the last [`Engine::update`] as fit in
+    /// `budget_ms` milliseconds, then present the frame so far. Returns
+    /// the fraction of triangles drawn, reaching 1.0 when the frame is
+    /// complete; later calls then only present it again.
+    ///
+    /// Call this every frame in place of `update` and `render`. The queue
+    /// and the partial image are kept between calls, and the frame starts
+    /// over when the camera moves or the window resizes (rebuilding the
+    /// triangles itself), or after an explicit `update`. Other setting
+    /// changes take effect on [`Engine::restart_incremental`]. Wireframes,
+    /// outlines and billboards are drawn once the fill completes; the
+    /// depth pre-pass is skipped.
+    pub fn render_incremental(&mut self, budget_ms: f32) -> f32 {
+        let deadline = Instant::now() + Duration::from_secs_f32(budget_ms.max(0.0) / 1000.0);
+        profile::set_enabled(self.profile.is_some());
+        self.tick_frame_time();
+
+        let view_projection = self.projection_matrix * self.view_matrix();
+        if view_projection != self.batches_view_projection {
+            self.update();
+        }
+        let mut frame = match self.incremental.take() {
+            Some(frame)
+                if frame.generation == self.batches_generation
+                    && frame.canvas.len() == self.renderer.pixels().len() =>
+            {
+                // Pick up from the last call, without its overlays
+                self.renderer.pixels_mut().copy_from_slice(&frame.canvas);
+                frame
+            }
+            _ => {
+                self.begin_frame();
+                IncrementalFrame {
+                    generation: self.batches_generation,
+                    cursor: FillCursor::default(),
+                    finished: false,
+                    canvas: Vec::new(),
+                }
+            }
+        };
+
+        if !frame.finished {
+            let stopped = if self.batch_pass_flags() & Triangle::FILL_PASS != 0 {
+                let _fill = profile::scope(profile::FILL);
+                self.fill_batches(DepthPass::Full, frame.cursor, Some(deadline))
+            } else {
+                None
+            };
+            match stopped {
+                Some(cursor) => frame.cursor = cursor,
+                None => {
+                    frame.finished = true;
+                    self.finish_frame();
+                }
+            }
+        }
+        frame.canvas.clear();
+        frame.canvas.extend_from_slice(self.renderer.pixels());
+        self.incremental = Some(frame);
+        self.present();
+        self.close_profile_frame();
+        self.incremental_progress().unwrap_or(1.0)
+    }
+
+    /// Throw away the incremental frame in progress, so the next
+    /// [`Engine::render_incremental`] starts over with the current settings.
+    pub fn restart_incremental(&mut self) {
+        self.incremental = None;
+    }
+
+    /// How much of the incremental frame in progress is drawn, from 0.0 to
+    /// 1.0, or `None` when there isn't one.
+    pub fn incremental_progress(&self) -> Option<f32> {
+        let frame = self.incremental.as_ref()?;
+        if frame.finished {
+            return Some(1.0);
+        }
+        let total: usize = self.batches.iter().map(|b| b.triangles.len()).sum();
+        let drawn: usize = self.batches[..frame.cursor.batch]
+            .iter()
+            .map(|b| b.triangles.len())
+            .sum::<usize>()
+            + frame.cursor.triangle;
+        Some(if total == 0 {
+            1.0
+        } else {
+            drawn as f32 / total as f32
+        })
+    }
+
+    /// Every pass some triangle from the last [`Engine::update`] is drawn
+    /// in.
+    fn batch_pass_flags(&self) -> u8 {
+        self.batches
+            .iter()
+            .flat_map(|batch| &batch.triangles)
+            .fold(0, |flags, triangle| flags | triangle.pass_flags)
+    }
+
+    /// Record the time since the last frame for the HUD.
+    fn tick_frame_time(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_render {
+            self.frame_timer
+                .record_frame(now.duration_since(last).as_secs_f32());
+        }
+        self.last_render = Some(now);
+    }
+
+    /// Hand the stage times recorded since the last frame to the profile.
+    fn close_profile_frame(&mut self) {
+        if let Some(frame_profile) = &mut self.profile {
+            frame_profile.push_frame(profile::take_frame());
+        }
+    }
+
+    /// Draw the triangles from the last [`Engine::update`] and everything
+    /// over them into the renderer's target.
+    fn draw_frame(&mut self) {
+        self.begin_frame();
+        if self.batch_pass_flags() & Triangle::FILL_PASS != 0 {
+            let prepass = self.depth_prepass && self.hidden_surface == HiddenSurface::DepthBuffer;
+            let passes: &[DepthPass] = if prepass {
+                &[DepthPass::DepthOnly, DepthPass::Equal]
+            } else {
+                &[DepthPass::Full]
+            };
+            let _fill = profile::scope(profile::FILL);
+            for &pass in passes {
+                self.fill_batches(pass, FillCursor::default(), None);
+            }
+        }
+        self.finish_frame();
+    }
 
-        if self.draw_grid {
-            self.renderer.draw_grid(50, colors::GRID);
+    /// Clear the buffers and draw the grid, ready for the meshes.
+    fn begin_frame(&mut self) {
+        // Overdraw counts up from black, with nothing under the meshes
+        let overdraw = self.debug_view == DebugView::Overdraw;
+        let background = if overdraw {
+            0xFF000000
+        } else {
+            self.fog.map_or(self.palette.background, |fog| fog.color)
+        };
+        self.renderer.clear(background);
+        self.renderer.clear_depth();
+        if self.picking {
+            self.renderer.clear_ids();
+        }
+        if self.silhouette_outline.is_some() {
+            self.renderer.clear_mask();
+        }
+        if self.deferred() {
+            self.renderer.clear_gbuffer();
         }
+        self.stats.pixels_shaded = 0;
+        self.stats.pixels_lit = 0;
+
+        if self.draw_grid && !overdraw {
+            match self.grid {
+                GridMode::ScreenSpace => {
+                    // Screen-space sizes are specified at display resolution
+                    let spacing = self.scaled_pixels(50);
+                    self.renderer.draw_grid(spacing, self.palette.grid);
+                }
+                GridMode::WorldPlane { spacing, extent } => {
+                    self.render_world_grid(spacing, extent);
+                }
+            }
+        }
+    }
 
-        // Determine what to draw based on render mode
-        let (draw_filled, draw_wireframe, draw_vertices) = match self.render_mode {
-            RenderMode::Wireframe => (false, true, false),
-            RenderMode::WireframeVertices => (false, true, true),
-            RenderMode::FilledWireframe => (true, true, false),
-            RenderMode::FilledWireframeVertices => (true, true, true),
-            RenderMode::Filled => (true, false, false),
+    /// Fill the batched triangles from `start` on in one depth pass. With
+    /// a `deadline`, stops soon after it passes and returns where to
+    /// resume; otherwise (or once every triangle is drawn) returns `None`.
+    fn fill_batches(
+        &mut self,
+        pass: DepthPass,
+        start: FillCursor,
+        deadline: Option<Instant>,
+    ) -> Option<FillCursor> {
+        // Mesh pixels are marked in the mask, which silhouettes are
+        // traced from
+        self.renderer
+            .set_mask_write(self.silhouette_outline.is_some());
+        let blend_mode = if self.debug_view == DebugView::Overdraw {
+            BlendMode::Additive
+        } else {
+            BlendMode::Opaque
+        };
+        let (deferred, lit_per_pixel) = (self.deferred(), self.lit_per_pixel());
+        let mut fb = match (deferred, self.picking) {
+            (true, true) => self.renderer.as_gbuffer_framebuffer_with_ids(),
+            (true, false) => self.renderer.as_gbuffer_framebuffer(),
+            (false, true) => self.renderer.as_framebuffer_with_ids(),
+            (false, false) => self.renderer.as_framebuffer(),
+        }
+        .with_depth_pass(pass)
+        .with_blend_mode(blend_mode);
+        if lit_per_pixel && !deferred {
+            fb = fb.with_lighting(PixelLighting {
+                directional: self.light,
+                point_lights: &self.point_lights,
+                fog: self.fog,
+            });
+        }
+        let debug_shader = DebugShader::new(self.debug_view);
+        let shader: Option<&dyn PixelShader> = match &debug_shader {
+            Some(debug) => Some(debug),
+            None => self
+                .custom_shader
+                .as_deref()
+                .map(|shader| shader as &dyn PixelShader),
         };
 
-        // Fill triangles first (requires framebuffer borrow)
-        if draw_filled {
-            let mut fb = self.renderer.as_framebuffer();
-            // Render each model's triangles with its own texture
-            for (model_idx, triangles) in self.triangles_per_model.iter().enumerate() {
-                // Use model's texture if available, otherwise global texture
-                let texture = self
-                    .models
-                    .get(model_idx)
-                    .and_then(|m| m.texture())
-                    .or(self.global_texture.as_ref());
-
-                for triangle in triangles {
-                    self.rasterizer
-                        .fill_triangle(triangle, &mut fb, triangle.color, texture);
+        let mut stopped = None;
+        let mut drawn = 0;
+        // Render each mesh's triangles with its own texture
+        'batches: for (batch_index, batch) in self.batches.iter().enumerate().skip(start.batch) {
+            // Use the mesh's shared texture if it has a handle, else the
+            // mesh's or model's texture if available, otherwise the global
+            // texture. Normal mapping samples the normal map instead.
+            let model = self.models.get(batch.model_index);
+            let texture = match self.texture_mode {
+                TextureMode::NormalMapped => self.normal_map.as_ref(),
+                _ => match model
+                    .and_then(|m| m.meshes().get(batch.mesh_index))
+                    .and_then(Mesh::texture_handle)
+                {
+                    // Removed textures leave their meshes untextured
+                    Some(handle) => self.textures.get(handle),
+                    None => model
+                        .and_then(|m| m.mesh_texture(batch.mesh_index))
+                        .or(self.global_texture.as_ref()),
+                },
+            };
+            let first = if batch_index == start.batch {
+                start.triangle
+            } else {
+                0
+            };
+
+            for (triangle_index, triangle) in batch.triangles.iter().enumerate().skip(first) {
+                if triangle.pass_flags & Triangle::FILL_PASS == 0 {
+                    continue;
+                }
+                // Reading the clock per triangle would cost more than
+                // small triangles do
+                if drawn > 0
+                    && drawn % DEADLINE_CHECK_INTERVAL == 0
+                    && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    stopped = Some(FillCursor {
+                        batch: batch_index,
+                        triangle: triangle_index,
+                    });
+                    break 'batches;
+                }
+                drawn += 1;
+                match shader {
+                    Some(shader) => self
+                        .rasterizer
+                        .fill_triangle_with_shader(triangle, &mut fb, shader, texture),
+                    None => {
+                        self.rasterizer
+                            .fill_triangle(triangle, &mut fb, triangle.color, texture)
+                    }
                 }
             }
         }
+        self.stats.pixels_shaded += fb.shaded_pixels();
+        self.stats.pixels_lit += fb.lit_pixels();
+        self.renderer.set_mask_write(false);
+        stopped
+    }
+
+    /// The deferred lighting pass, if on, then everything drawn over the
+    /// filled meshes: silhouettes, billboards, wireframes, vertex markers
+    /// and the selection outline.
+    fn finish_frame(&mut self) {
+        if self.deferred() {
+            let _lighting = profile::scope(profile::LIGHTING);
+            let rays = self.pixel_rays();
+            let lighting = PixelLighting {
+                directional: self.light,
+                point_lights: &self.point_lights,
+                fog: self.fog,
+            };
+            self.stats.pixels_lit += self.renderer.light_gbuffer(&lighting, &rays);
+        }
+
+        // Screen-space sizes are specified at display resolution
+        let marker_size = self.scaled_pixels(self.vertex_marker_size);
+        let pass_flags = self.batch_pass_flags();
+        let (draw_wireframe, draw_vertices) = (
+            pass_flags & Triangle::WIREFRAME_PASS != 0,
+            pass_flags & Triangle::VERTEX_PASS != 0,
+        );
+
+        // Only filled triangles mark the mask
+        if pass_flags & Triangle::FILL_PASS != 0 {
+            if let Some(outline) = self.silhouette_outline {
+                let radius = self.scaled_pixels(outline.thickness);
+                self.renderer.draw_silhouette_outline(radius, outline.color);
+            }
+        }
+
+        if self.scene_render_mode().draws_filled() {
+            // Sprites last, depth-tested against the finished opaque scene
+            let mut fb = if self.picking {
+                self.renderer.as_framebuffer_with_ids()
+            } else {
+                self.renderer.as_framebuffer()
+            }
+            .with_blend_mode(self.billboard_blend);
+            for triangle in &self.billboard_triangles {
+                self.rasterizer.fill_triangle(
+                    triangle,
+                    &mut fb,
+                    triangle.color,
+                    self.billboard_texture.as_ref(),
+                );
+            }
+            self.stats.pixels_shaded += fb.shaded_pixels();
+        }
 
         // Wireframe and vertices (uses renderer methods)
-        for triangles in &self.triangles_per_model {
-            for triangle in triangles {
-                if draw_wireframe {
-                    self.renderer
-                        .draw_triangle_wireframe(triangle, colors::WIREFRAME);
+        let wireframe =
+            (draw_wireframe || draw_vertices).then(|| profile::scope(profile::WIREFRAME));
+        for batch in &self.batches {
+            for triangle in &batch.triangles {
+                if triangle.pass_flags & Triangle::WIREFRAME_PASS != 0 {
+                    if self.wireframe_aa {
+                        self.renderer
+                            .draw_triangle_wireframe_aa(triangle, self.palette.wireframe);
+                    } else {
+                        self.renderer
+                            .draw_triangle_wireframe(triangle, self.palette.wireframe);
+                    }
                 }
-                if draw_vertices {
+                if triangle.pass_flags & Triangle::VERTEX_PASS != 0 {
                     for vertex in &triangle.points {
-                        self.renderer.draw_rect(
-                            vertex.position.x as i32,
-                            vertex.position.y as i32,
-                            4,
-                            4,
-                            colors::VERTEX,
+                        self.renderer.draw_vertex_marker(
+                            vertex.position.x,
+                            vertex.position.y,
+                            vertex.w,
+                            marker_size,
+                            self.palette.vertex,
                         );
                     }
                 }
             }
         }
+        drop(wireframe);
+
+        // Depth-tested against the meshes like the grid
+        if !self.debug_lines.is_empty() {
+            let view_projection = self.projection_matrix * self.view_matrix();
+            let lines = std::mem::take(&mut self.debug_lines);
+            for line in &lines {
+                self.draw_world_line(&view_projection, line.a, line.b, line.color);
+            }
+            self.debug_lines = lines;
+        }
+
+        if let Some(outline) = self.selection_outline {
+            self.render_selection_outline(outline);
+        }
+
+        // Fewer colors last, over everything drawn
+        if let Some(bits) = self.retro.map(|retro| retro.color_depth) {
+            if bits < 8 {
+                for pixel in self.renderer.pixels_mut() {
+                    *pixel = colors::quantize(*pixel, bits);
+                }
+            }
+        }
+    }
+
+    /// Overlay the HUD and bring the frame to display size for the window.
+    fn present(&mut self) {
+        let _present = profile::scope(profile::PRESENT);
+        if self.debug_hud {
+            let scale = self.pixel_scale().round().max(1.0) as i32;
+            self.render_debug_hud(scale);
+        }
+
+        self.resolve();
+        self.post_process();
+
+        // A writer failure is kept by the recorder for `stop_recording`
+        if self.recorder.is_some() {
+            self.capture().ok();
+        }
+    }
+
+    /// Mark the visible pixels of the outlined mesh in the mask, then ring
+    /// them with the outline color.
+    fn render_selection_outline(&mut self, outline: SelectionOutline) {
+        // Painter sorting can split a mesh over several batches
+        let outlined = |batch: &&TriangleBatch| {
+            batch.model_index == outline.model_index && batch.mesh_index == outline.mesh_index
+        };
+        if !self.batches.iter().any(|batch| outlined(&batch)) {
+            // Culled, clipped away, or no longer in the scene
+            return;
+        }
+
+        self.renderer.clear_mask();
+        self.renderer.set_mask_write(true);
+        self.renderer.set_color_write(false);
+        // The equal pass keeps only the surface already in the depth
+        // buffer, so occluded parts stay out of the mask
+        let mut fb = self
+            .renderer
+            .as_framebuffer()
+            .with_depth_pass(DepthPass::Equal);
+        for triangle in self
+            .batches
+            .iter()
+            .filter(outlined)
+            .flat_map(|b| &b.triangles)
+        {
+            self.rasterizer
+                .fill_triangle(triangle, &mut fb, triangle.color, None);
+        }
+        self.renderer.set_mask_write(false);
+        self.renderer.set_color_write(true);
+
+        let radius = self.scaled_pixels(outline.thickness);
+        self.renderer.draw_mask_outline(radius, outline.color);
+    }
+
+    /// Draw grid lines on the y=0 plane around the camera.
+    ///
+    /// The grid is snapped to multiples of `spacing` so it stays fixed in the
+    /// world as the camera moves. Each line is transformed to clip space,
+    /// clipped against the near plane, and drawn with the depth-tested
+    /// Bresenham line so filled geometry hides the lines behind it.
+    fn render_world_grid(&mut self, spacing: f32, extent: f32) {
+        if !spacing.is_finite() || !extent.is_finite() || spacing <= 0.0 || extent <= 0.0 {
+            return;
+        }
+
+        let view_projection = self.projection_matrix * self.view_matrix();
+        let eye = self.camera.position();
+        let center_x = (eye.x / spacing).round() * spacing;
+        let center_z = (eye.z / spacing).round() * spacing;
+        let half_lines = (extent / spacing).floor() as i32;
+        let reach = half_lines as f32 * spacing;
+        let color = self.palette.grid;
+
+        for i in -half_lines..=half_lines {
+            let offset = i as f32 * spacing;
+            // Line parallel to z at fixed x, then parallel to x at fixed z
+            let x = center_x + offset;
+            self.draw_world_line(
+                &view_projection,
+                Vec3::new(x, 0.0, center_z - reach),
+                Vec3::new(x, 0.0, center_z + reach),
+                color,
+            );
+            let z = center_z + offset;
+            self.draw_world_line(
+                &view_projection,
+                Vec3::new(center_x - reach, 0.0, z),
+                Vec3::new(center_x + reach, 0.0, z),
+                color,
+            );
+        }
+    }
+
+    /// Project a world-space segment and draw it with depth testing.
+    fn draw_world_line(&mut self, view_projection: &Mat4, a: Vec3, b: Vec3, color: u32) {
+        if let Some([(x0, y0, w0), (x1, y1, w1)]) =
+            project_line(view_projection, &self.viewport(), a, b)
+        {
+            self.renderer
+                .draw_line_bresenham(x0, y0, w0, x1, y1, w1, color);
+        }
+    }
+
+    /// Draw the debug HUD panel in the top-left corner.
+    fn render_debug_hud(&mut self, scale: i32) {
+        let timer = &self.frame_timer;
+        let stats = self.stats;
+        let mut lines = vec![
+            format!(
+                "{:.1} fps (1% low: {:.1})  99%: {:.1} ms",
+                timer.average_fps().unwrap_or(0.0),
+                timer.one_percent_low_fps().unwrap_or(0.0),
+                timer.frame_time_percentile(99.0).unwrap_or(0.0) * 1000.0
+            ),
+            format!(
+                "Tris: {} drawn / {} faces ({} too small)",
+                stats.triangles_drawn, stats.faces_total, stats.triangles_too_small
+            ),
+            format!(
+                "Meshes: {} ({} instances, {} culled)",
+                stats.meshes_total, stats.instances_total, stats.instances_culled
+            ),
+            format!(
+                "Culled: {} frustum, {} back, {} clip",
+                stats.faces_frustum_culled, stats.faces_backface_culled, stats.faces_clipped_away
+            ),
+            format!("LOD: {} faces left out", stats.faces_lod_reduced),
+            format!(
+                "Clip: {} in, {} out, {} split",
+                stats.faces_trivially_accepted, stats.faces_trivially_rejected, stats.faces_clipped
+            ),
+            format!(
+                "Shaded: {} px{}",
+                stats.pixels_shaded,
+                if self.depth_prepass {
+                    " (pre-pass)"
+                } else {
+                    ""
+                }
+            ),
+            format!("Raster: {}", self.rasterizer.name()),
+            match self.render_mode_override {
+                Some(mode) => format!("Render: {:?} (all)", mode),
+                None => format!("Render: {:?}", self.render_mode),
+            },
+            format!("Shade: {}  Tex: {}", self.shading_mode, self.texture_mode),
+            // As code, to paste into a scene
+            format!(
+                "Light: Vec3::new({:.3}, {:.3}, {:.3})",
+                self.light.direction.x, self.light.direction.y, self.light.direction.z
+            ),
+        ];
+        if self.debug_view != DebugView::None {
+            lines.push(format!("Debug view: {}", self.debug_view));
+        }
+        if self.hidden_surface != HiddenSurface::DepthBuffer {
+            lines.push(format!("Hidden surface: {}", self.hidden_surface));
+        }
+        if self.post_effect.is_some() {
+            // Measured on the previous frame; this one hasn't run yet
+            lines.push(format!("Post: {:.2} ms", self.post_process_time * 1000.0));
+        }
+        if let Some(frame_profile) = &self.profile {
+            lines.push(frame_profile.summary());
+        }
+        if self.loader.pending() > 0 {
+            lines.push(format!(
+                "Loading {} file(s)... {}",
+                self.loader.pending(),
+                self.loader.spinner()
+            ));
+        }
+
+        const PADDING: i32 = 6;
+        const LINE_HEIGHT: i32 = 10;
+        // Recent frame times below the text, full height at this many seconds
+        const SPARKLINE_HEIGHT: i32 = 16;
+        const SPARKLINE_MAX: f32 = 0.05;
+        let longest = lines.iter().map(|l| l.len()).max().unwrap_or(0) as i32;
+        let panel_width = (longest * 8).max(FrameTimer::HISTORY as i32) + PADDING * 2;
+        let text_height = lines.len() as i32 * LINE_HEIGHT;
+        let panel_height = text_height + SPARKLINE_HEIGHT + PADDING * 3;
+
+        self.renderer.draw_filled_rect_alpha(
+            4 * scale,
+            4 * scale,
+            panel_width * scale,
+            panel_height * scale,
+            self.palette.hud_panel,
+            0.6,
+        );
+        for (i, line) in lines.iter().enumerate() {
+            self.renderer.draw_text_scaled(
+                (4 + PADDING) * scale,
+                (4 + PADDING + i as i32 * LINE_HEIGHT) * scale,
+                line,
+                self.palette.hud_text,
+                scale,
+            );
+        }
+        let baseline = 4 + PADDING * 2 + text_height + SPARKLINE_HEIGHT;
+        for (i, &time) in self.frame_timer.recent_frame_times().iter().enumerate() {
+            let height = ((time / SPARKLINE_MAX).min(1.0) * SPARKLINE_HEIGHT as f32).ceil() as i32;
+            self.renderer.draw_rect(
+                (4 + PADDING + i as i32) * scale,
+                (baseline - height) * scale,
+                scale,
+                height * scale,
+                self.palette.hud_text,
+            );
+        }
+    }
+
+    /// Run the post effect (if any) over the display-size frame.
+    fn post_process(&mut self) {
+        let Some(effect) = self.post_effect.as_mut() else {
+            return;
+        };
+        let start = Instant::now();
+        let pixels = if !self.resolved.is_empty() {
+            &mut self.resolved[..]
+        } else {
+            self.renderer.pixels_mut()
+        };
+        effect.apply(pixels, self.display_width, self.display_height);
+        self.post_process_time = start.elapsed().as_secs_f32();
+    }
+
+    /// Downsample the supersampled render target and/or stretch the scaled
+    /// one into the display-size frame.
+    fn resolve(&mut self) {
+        if self.resolved.is_empty() {
+            return;
+        }
+        let factor = self.supersampling;
+        let (width, scaled_width) = (self.display_width, self.renderer.width() / factor);
+        if self.downsampled.is_empty() && factor > 1 {
+            self.renderer.resolve_into(factor, &mut self.resolved);
+        } else if factor > 1 {
+            self.renderer.resolve_into(factor, &mut self.downsampled);
+            upscale_nearest(&self.downsampled, scaled_width, &mut self.resolved, width);
+        } else {
+            upscale_nearest(
+                self.renderer.pixels(),
+                scaled_width,
+                &mut self.resolved,
+                width,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Face, Mesh, Vertex};
+    use crate::prelude::Vec2;
+
+    fn vertex(x: f32, y: f32) -> Vertex {
+        Vertex {
+            position: Vec3::new(x, y, 0.0),
+            normal: Vec3::new(0.0, 0.0, -1.0),
+            texel: Vec2::ZERO,
+            color: 0xFFFFFFFF,
+            tangent: Vec4::ZERO,
+        }
+    }
+
+    #[test]
+    fn faces_past_the_vertex_list_are_skipped() {
+        // Built in code, so no loader checked the indices
+        let vertices = vec![vertex(-1.0, -1.0), vertex(0.0, 1.0), vertex(1.0, -1.0)];
+        let faces = vec![Face::new(0, 1, 2), Face::new(0, 1, 7)];
+        let mut model = Model::new("scene");
+        model.add_mesh(Mesh::new("mesh".to_string(), vertices, faces));
+
+        let mut engine = Engine::new(32, 32);
+        engine.push_model("scene", model);
+        engine.update();
+
+        let stats = engine.stats();
+        assert_eq!(stats.faces_total, 2);
+        assert_eq!(stats.faces_invalid, 1);
+        assert_eq!(stats.triangles_drawn, 1);
+        // Picking walks the same faces
+        let hit = engine.raycast(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(hit.map(|h| h.face_index), Some(0));
     }
 }
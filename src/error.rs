@@ -0,0 +1,118 @@
+//! The crate-wide error type.
+//!
+//! Windows, loaders and texture constructors return [`Error`] directly. The
+//! narrower errors ([`LoadError`], [`PresentError`], [`TextureUpdateError`]
+//! and, with the `serde` feature, `SceneError`) convert into it with `?`,
+//! so an application can handle every failure through one type.
+
+use std::fmt;
+
+use crate::mesh::LoadError;
+#[cfg(feature = "serde")]
+use crate::scene::SceneError;
+use crate::texture::TextureUpdateError;
+use crate::window::PresentError;
+
+/// Anything that can go wrong in the crate's public API.
+#[derive(Debug)]
+pub enum Error {
+    /// Creating the window or its drawing surface failed.
+    Window(String),
+    /// A mesh or model file couldn't be loaded.
+    MeshLoad(LoadError),
+    /// An image file couldn't be read or decoded as a texture.
+    Texture(image::ImageError),
+    /// New pixels didn't fit the texture they were written to.
+    TextureUpdate(TextureUpdateError),
+    /// A frame couldn't be shown or written out.
+    Present(PresentError),
+    /// A scene file couldn't be read or applied.
+    #[cfg(feature = "serde")]
+    Scene(SceneError),
+    /// An argument was out of range, such as a zero texture size.
+    InvalidArgument(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Window(e) => write!(f, "window error: {}", e),
+            Error::MeshLoad(e) => write!(f, "{}", e),
+            Error::Texture(e) => write!(f, "failed to load texture: {}", e),
+            Error::TextureUpdate(e) => write!(f, "texture update rejected: {}", e),
+            Error::Present(e) => write!(f, "{}", e),
+            #[cfg(feature = "serde")]
+            Error::Scene(e) => write!(f, "{}", e),
+            Error::InvalidArgument(e) => write!(f, "invalid argument: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::MeshLoad(e) => Some(e),
+            Error::Texture(e) => Some(e),
+            Error::TextureUpdate(e) => Some(e),
+            Error::Present(e) => Some(e),
+            #[cfg(feature = "serde")]
+            Error::Scene(e) => Some(e),
+            Error::Window(_) | Error::InvalidArgument(_) => None,
+        }
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(e: LoadError) -> Self {
+        Error::MeshLoad(e)
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(e: image::ImageError) -> Self {
+        Error::Texture(e)
+    }
+}
+
+impl From<TextureUpdateError> for Error {
+    fn from(e: TextureUpdateError) -> Self {
+        Error::TextureUpdate(e)
+    }
+}
+
+impl From<PresentError> for Error {
+    fn from(e: PresentError) -> Self {
+        Error::Present(e)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SceneError> for Error {
+    fn from(e: SceneError) -> Self {
+        Error::Scene(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn narrower_errors_convert_with_question_mark() {
+        fn load() -> Result<(), Error> {
+            Err(LoadError::NoVertices)?
+        }
+        let error = load().unwrap_err();
+        assert!(matches!(error, Error::MeshLoad(LoadError::NoVertices)));
+        assert_eq!(error.to_string(), "mesh has no vertices");
+        assert!(error.source().is_some());
+
+        let error = Error::InvalidArgument("texture size must be non-zero");
+        assert_eq!(
+            error.to_string(),
+            "invalid argument: texture size must be non-zero"
+        );
+        assert!(error.source().is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Distance fog for depth cueing.
+//!
+//! Fog blends each filled pixel toward a fixed color by its view-space depth,
+//! so distant geometry fades out instead of ending abruptly against the
+//! background. Enable it with `Engine::set_fog`; the background is cleared to
+//! the fog color while fog is on, so fully fogged geometry disappears into it.
+//!
+//! ```ignore
+//! engine.set_fog(Fog {
+//!     color: 0xFF1E1E1E,
+//!     mode: FogMode::Linear { start: 10.0, end: 60.0 },
+//! });
+//! ```
+
+use crate::colors;
+
+/// How fog density grows with distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FogMode {
+    /// No fog before `start`, fully fogged at `end`, linear in between.
+    Linear { start: f32, end: f32 },
+    /// Visibility falls off as `exp(-density * depth)`; never quite reaches
+    /// the fog color.
+    Exp { density: f32 },
+}
+
+/// Fog settings: the color distant geometry fades to, and how fast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fog {
+    /// Packed ARGB fog color.
+    pub color: u32,
+    pub mode: FogMode,
+}
+
+impl Fog {
+    /// Fraction of the surface color still visible at view-space `depth`,
+    /// from 1.0 (no fog) down to 0.0 (only fog).
+    pub fn visibility(&self, depth: f32) -> f32 {
+        let visibility = match self.mode {
+            FogMode::Linear { start, end } => {
+                if end <= start {
+                    // Degenerate range: a hard cut at `start`
+                    if depth < start {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                } else {
+                    (end - depth) / (end - start)
+                }
+            }
+            FogMode::Exp { density } => (-density * depth).exp(),
+        };
+        visibility.clamp(0.0, 1.0)
+    }
+
+    /// Blend `color` toward the fog color for a pixel at view-space `depth`.
+    #[inline]
+    pub fn apply(&self, color: u32, depth: f32) -> u32 {
+        colors::blend(self.color, color, self.visibility(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOG: u32 = 0xFF808080;
+    const RED: u32 = 0xFFFF0000;
+
+    #[test]
+    fn linear_fog_ramps_between_start_and_end() {
+        let fog = Fog {
+            color: FOG,
+            mode: FogMode::Linear {
+                start: 10.0,
+                end: 20.0,
+            },
+        };
+
+        assert_eq!(fog.visibility(5.0), 1.0);
+        assert!((fog.visibility(15.0) - 0.5).abs() < 1e-6);
+        assert_eq!(fog.visibility(25.0), 0.0);
+
+        assert_eq!(fog.apply(RED, 5.0), RED);
+        assert_eq!(fog.apply(RED, 25.0), FOG);
+    }
+
+    #[test]
+    fn exponential_fog_decays_monotonically() {
+        let fog = Fog {
+            color: FOG,
+            mode: FogMode::Exp { density: 0.1 },
+        };
+
+        assert_eq!(fog.visibility(0.0), 1.0);
+        assert!((fog.visibility(10.0) - (-1.0f32).exp()).abs() < 1e-6);
+        assert!(fog.visibility(20.0) < fog.visibility(10.0));
+    }
+
+    #[test]
+    fn degenerate_linear_range_is_a_hard_cut() {
+        let fog = Fog {
+            color: FOG,
+            mode: FogMode::Linear {
+                start: 10.0,
+                end: 10.0,
+            },
+        };
+
+        assert_eq!(fog.visibility(9.9), 1.0);
+        assert_eq!(fog.visibility(10.0), 0.0);
+    }
+}
@@ -51,9 +51,10 @@ impl Frustum {
     ///   - `projection * view`     → world-space planes
     ///   - `projection * view * M` → model-space planes (rebuild per object)
     ///
-    /// Assumes clip-space z ∈ [-1, 1] (OpenGL / left-handed `perspective_lh`
-    /// convention used in this codebase). For DX-style [0, 1] z, the near
-    /// plane would be just `row2` instead of `row3 + row2`.
+    /// Assumes clip-space z ∈ [-1, 1], which both `perspective_lh` and
+    /// `perspective_rh` produce, so the planes follow either handedness
+    /// without special cases. For DX-style [0, 1] z, the near plane would be
+    /// just `row2` instead of `row3 + row2`.
     pub fn from_matrix(m: &Mat4) -> Self {
         // Rows of m. Row i dotted with (p, 1) is the i-th clip coordinate.
         let (r00, r01, r02, r03) = (m.get(0, 0), m.get(0, 1), m.get(0, 2), m.get(0, 3));
@@ -71,7 +72,7 @@ impl Frustum {
                 Plane::from_equation(r30 - r10, r31 - r11, r32 - r12, r33 - r13),
                 // Bottom: row3 + row1   ⇔   y_clip + w_clip ≥ 0
                 Plane::from_equation(r30 + r10, r31 + r11, r32 + r12, r33 + r13),
-                // Near:   row3 + row2   ⇔   z_clip + w_clip ≥ 0   (LH [-1,1] z)
+                // Near:   row3 + row2   ⇔   z_clip + w_clip ≥ 0   ([-1,1] z)
                 Plane::from_equation(r30 + r20, r31 + r21, r32 + r22, r33 + r23),
                 // Far:    row3 - row2   ⇔   w_clip - z_clip ≥ 0
                 Plane::from_equation(r30 - r20, r31 - r21, r32 - r22, r33 - r23),
@@ -179,11 +180,24 @@ impl Frustum {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::vec4::Vec4;
+    use crate::projection::{Handedness, Projection};
     use std::f32::consts::FRAC_PI_4;
 
     /// `from_matrix(projection)` should produce view-space planes where a
     /// point dead-center in the frustum is inside and points behind the
     /// camera / past the far plane are outside.
+    #[test]
+    fn from_matrix_follows_right_handed_projection() {
+        let proj = Mat4::perspective_rh(FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+        let frustum = Frustum::from_matrix(&proj);
+
+        // In front of a right-handed camera is -z
+        assert!(frustum.contains_sphere(Vec3::new(0.0, 0.0, -50.0), 0.0));
+        assert!(!frustum.contains_sphere(Vec3::new(0.0, 0.0, 50.0), 0.0));
+        assert!(!frustum.contains_sphere(Vec3::new(0.0, 0.0, -1000.0), 0.0));
+    }
+
     #[test]
     fn from_matrix_produces_valid_frustum() {
         let proj = Mat4::perspective_lh(FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
@@ -202,6 +216,20 @@ mod tests {
         assert!(!frustum.contains_sphere(Vec3::new(1000.0, 0.0, 10.0), 0.0));
     }
 
+    #[test]
+    fn extreme_fields_of_view_give_finite_planes() {
+        for degrees in [1.0, 170.0, 179.0] {
+            let projection = Projection::from_degrees(degrees, 16.0 / 9.0, 0.01, 1000.0);
+            let frustum = Frustum::from_matrix(&projection.matrix());
+
+            for plane in &frustum.planes {
+                let finite = |v: Vec3| v.x.is_finite() && v.y.is_finite() && v.z.is_finite();
+                assert!(finite(plane.point) && finite(plane.normal), "{degrees}°: {plane:?}");
+            }
+            assert!(frustum.contains_sphere(Vec3::new(0.0, 0.0, 50.0), 0.0));
+        }
+    }
+
     #[test]
     fn classify_returns_three_states() {
         let frustum =
@@ -225,4 +253,37 @@ mod tests {
             FrustumTest::Outside,
         );
     }
+
+    /// Planes extracted from the matrix sit exactly on the NDC cube's faces,
+    /// whatever the aspect ratio: just inside each face is inside, just
+    /// past it is outside.
+    #[test]
+    fn planes_match_the_ndc_boundaries() {
+        for aspect in [4.0 / 3.0, 16.0 / 9.0, 1.0 / 3.0] {
+            for handedness in [Handedness::Left, Handedness::Right] {
+                let mut projection = Projection::from_degrees(60.0, aspect, 1.0, 10.0);
+                projection.set_handedness(handedness);
+                let frustum = Frustum::from_matrix(&projection.matrix());
+                let inverse = projection.inverse_matrix();
+                // View-space point that projects to `ndc`
+                let unproject = |ndc: [f32; 3]| {
+                    let p = inverse * Vec4::new(ndc[0], ndc[1], ndc[2], 1.0);
+                    Vec3::new(p.x / p.w, p.y / p.w, p.z / p.w)
+                };
+
+                for axis in 0..3 {
+                    for sign in [-1.0, 1.0] {
+                        let at = |offset: f32| {
+                            let mut ndc = [0.0, 0.0, 0.5];
+                            ndc[axis] = sign * offset;
+                            unproject(ndc)
+                        };
+                        let label = format!("{aspect:.2} {handedness:?} axis {axis} sign {sign}");
+                        assert!(frustum.contains_sphere(at(0.99), 0.0), "{label}: inside");
+                        assert!(!frustum.contains_sphere(at(1.01), 0.0), "{label}: outside");
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,916 @@
+//! Per-face geometry pipeline.
+//!
+//! [`GeometryProcessor`] takes one mesh's faces from model space to
+//! screen-space [`Triangle`]s: world transform, backface cull, lighting,
+//! clipping, perspective divide and viewport transform. It reads only
+//! immutable inputs, the frame's camera and settings and a [`MeshView`],
+//! so faces can be processed independently. With the `parallel` feature,
+//! large meshes are split across threads with rayon.
+//!
+//! [`Engine::update`](crate::engine::Engine::update) keeps the stateful
+//! parts: model and mesh frustum culling, picking IDs, and storing the
+//! resulting batches.
+
+use std::ops::AddAssign;
+use std::time::{Duration, Instant};
+
+use crate::clipper::{ClipClass, ClipSpaceClipper, ClipSpacePolygon, ClipSpaceVertex};
+use crate::colors;
+use crate::engine::{FrameStats, ShadingMode, TextureMode};
+use crate::fog::Fog;
+use crate::light::DirectionalLight;
+use crate::math::mat4::Mat4;
+use crate::mesh::{CullMode, Face, Mesh, Texel, Vertex};
+use crate::prelude::{Vec2, Vec3, Vec4};
+use crate::profile;
+use crate::projection::Handedness;
+use crate::render::rasterizer::misses_pixel_centers;
+use crate::render::{ScreenVertex, Triangle};
+use crate::texture::{sphere_map_uv, TextureRegion};
+use crate::viewport::Viewport;
+
+/// Smallest mesh worth splitting across threads; below it the per-task
+/// overhead outweighs the work.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_FACES: usize = 1024;
+
+/// One mesh's faces and placement, borrowed for a frame.
+///
+/// Holds only plain data, so it can be shared across threads where the
+/// [`Mesh`] itself (with its culling cache) can't.
+#[derive(Debug, Clone, Copy)]
+pub struct MeshView<'a> {
+    pub vertices: &'a [Vertex],
+    pub faces: &'a [Face],
+    /// Model space to world space.
+    pub world_matrix: Mat4,
+    /// Inverse transpose of `world_matrix`'s rotation and scale.
+    pub normal_matrix: Mat4,
+    pub cull_mode: CullMode,
+    pub has_vertex_colors: bool,
+    pub texture_region: Option<TextureRegion>,
+    /// [`Triangle::pass_flags`] for every triangle the faces produce.
+    pub pass_flags: u8,
+    /// Picking ID of face 0, each later face taking the next; 0 leaves
+    /// every face unpickable.
+    pub first_id: u32,
+}
+
+impl<'a> MeshView<'a> {
+    /// View `mesh` placed at `world_matrix`, drawn unpickable. Takes the
+    /// mesh's own cull mode, vertex colors and texture region, and the
+    /// passes of its render mode (the default mode when it has none).
+    pub fn new(mesh: &'a Mesh, world_matrix: Mat4, normal_matrix: Mat4) -> Self {
+        Self {
+            vertices: mesh.vertices(),
+            faces: mesh.faces(),
+            world_matrix,
+            normal_matrix,
+            cull_mode: mesh.cull_mode(),
+            has_vertex_colors: mesh.has_vertex_colors(),
+            texture_region: mesh.texture_region(),
+            pass_flags: mesh.render_mode().unwrap_or_default().pass_flags(),
+            first_id: 0,
+        }
+    }
+
+    /// Corners of `face`, or `None` if it indexes past the vertices.
+    fn face_vertices(&self, face: &Face) -> Option<[Vertex; 3]> {
+        Some([
+            *self.vertices.get(face.a as usize)?,
+            *self.vertices.get(face.b as usize)?,
+            *self.vertices.get(face.c as usize)?,
+        ])
+    }
+}
+
+/// The camera, light and render settings one frame's faces are processed
+/// with.
+#[derive(Debug, Clone, Copy)]
+pub struct GeometryProcessor {
+    pub view_matrix: Mat4,
+    pub projection_matrix: Mat4,
+    pub camera_position: Vec3,
+    pub viewport: Viewport,
+    pub handedness: Handedness,
+    pub light: DirectionalLight,
+    pub shading_mode: ShadingMode,
+    pub texture_mode: TextureMode,
+    /// Leave `vertex_colors` unlit and the normals ready for lighting after
+    /// the shader runs, for point lights and the deferred pipeline: the
+    /// face normal for flat shading, none for unlit faces.
+    pub pixel_lighting: bool,
+    /// Leave `vertex_colors` unlit and hand the light to the shader, for
+    /// normal mapping.
+    pub per_pixel_light: bool,
+    pub dithering: bool,
+    /// Size in pixels of the grid projected vertices snap to, for the
+    /// jitter of `Engine::set_retro_mode`.
+    pub snap_grid: Option<Vec2>,
+    /// Stamped on every triangle; see [`Triangle::affine_textures`].
+    pub affine_textures: bool,
+    pub fog: Option<Fog>,
+    /// Base color of faces without a texture or vertex colors.
+    pub fill_color: u32,
+    /// Stand the face normal in for missing vertex normals, as per-pixel
+    /// lighting does, for shaders that read them.
+    pub fill_normals: bool,
+    /// Time the world transform, clipping and projection stages for
+    /// [`crate::profile`].
+    pub profile: bool,
+    /// Drop projected triangles smaller than this many square pixels whose
+    /// bounds hold no pixel center; `0.0` keeps them all.
+    pub min_triangle_area: f32,
+}
+
+/// Time spent in each geometry stage, summed over faces. With the
+/// `parallel` feature this adds up time across threads.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StageTimes {
+    pub world_transform: Duration,
+    pub clipping: Duration,
+    pub projection: Duration,
+}
+
+impl StageTimes {
+    /// Add the times to the current frame's profile.
+    fn record(self) {
+        profile::record(profile::WORLD_TRANSFORM, self.world_transform);
+        profile::record(profile::CLIPPING, self.clipping);
+        profile::record(profile::PROJECTION, self.projection);
+    }
+}
+
+impl AddAssign for StageTimes {
+    fn add_assign(&mut self, other: Self) {
+        self.world_transform += other.world_transform;
+        self.clipping += other.clipping;
+        self.projection += other.projection;
+    }
+}
+
+/// A mesh's vertex positions in world and view space, indexed like its
+/// vertices.
+struct VertexPositions {
+    world: Vec<Vec3>,
+    view: Vec<Vec3>,
+}
+
+/// Add the time since `clock` to `stage` and restart the clock, when
+/// there is one.
+fn lap(clock: &mut Option<Instant>, stage: &mut Duration) {
+    if let Some(start) = clock {
+        let now = Instant::now();
+        *stage += now - *start;
+        *start = now;
+    }
+}
+
+impl GeometryProcessor {
+    /// Process every face of `mesh`, returning its triangles in face order
+    /// and the counters for what happened to its faces.
+    ///
+    /// `faces_total` and `faces_frustum_culled` are left at zero; mesh-level
+    /// culling happens before a mesh gets here.
+    pub fn process_mesh(&self, mesh: &MeshView) -> (Vec<Triangle>, FrameStats) {
+        let mut triangles = Vec::new();
+        let mut stats = FrameStats::default();
+        self.process_mesh_into(mesh, &mut triangles, &mut stats);
+        (triangles, stats)
+    }
+
+    /// [`GeometryProcessor::process_mesh`], appending to `out` and adding to
+    /// `stats` instead, so many placements of one mesh can share a list.
+    pub fn process_mesh_into(
+        &self,
+        mesh: &MeshView,
+        out: &mut Vec<Triangle>,
+        stats: &mut FrameStats,
+    ) {
+        #[cfg(feature = "parallel")]
+        if mesh.faces.len() >= PARALLEL_MIN_FACES {
+            let (mut triangles, mesh_stats, times) = self.process_mesh_parallel(mesh);
+            out.append(&mut triangles);
+            *stats += mesh_stats;
+            if self.profile {
+                times.record();
+            }
+            return;
+        }
+
+        let mut times = StageTimes::default();
+        let positions = self.vertex_positions(mesh, &mut times);
+        for face_index in 0..mesh.faces.len() {
+            self.process_face(mesh, Some(&positions), face_index, out, stats, &mut times);
+        }
+        if self.profile {
+            times.record();
+        }
+    }
+
+    /// [`GeometryProcessor::process_mesh`] with the faces split across
+    /// rayon's threads. Each thread fills its own triangle list; they are
+    /// joined in face order, so the output matches the sequential path.
+    #[cfg(feature = "parallel")]
+    fn process_mesh_parallel(&self, mesh: &MeshView) -> (Vec<Triangle>, FrameStats, StageTimes) {
+        use rayon::prelude::*;
+
+        let mut batch_times = StageTimes::default();
+        let positions = self.vertex_positions(mesh, &mut batch_times);
+        let (triangles, stats, times) = (0..mesh.faces.len())
+            .into_par_iter()
+            .fold(
+                || (Vec::new(), FrameStats::default(), StageTimes::default()),
+                |(mut triangles, mut stats, mut times), face_index| {
+                    self.process_face(
+                        mesh,
+                        Some(&positions),
+                        face_index,
+                        &mut triangles,
+                        &mut stats,
+                        &mut times,
+                    );
+                    (triangles, stats, times)
+                },
+            )
+            .reduce(
+                || (Vec::new(), FrameStats::default(), StageTimes::default()),
+                |(mut triangles, mut stats, mut times), (mut more, more_stats, more_times)| {
+                    triangles.append(&mut more);
+                    stats += more_stats;
+                    times += more_times;
+                    (triangles, stats, times)
+                },
+            );
+        batch_times += times;
+        (triangles, stats, batch_times)
+    }
+
+    /// Every vertex of `mesh` in world and view space, transformed in two
+    /// batches rather than once per face corner. The time it takes counts
+    /// toward the world transform.
+    fn vertex_positions(&self, mesh: &MeshView, times: &mut StageTimes) -> VertexPositions {
+        let mut clock = self.profile.then(Instant::now);
+        let model: Vec<Vec3> = mesh.vertices.iter().map(|v| v.position).collect();
+        let mut homogeneous = Vec::new();
+        mesh.world_matrix
+            .transform_points_batch(&model, &mut homogeneous);
+        // Divided by w just as `Mat4 * Vec3` is, for the same results
+        let world: Vec<Vec3> = homogeneous
+            .iter()
+            .map(|p| p.to_vec3_perspective())
+            .collect();
+        self.view_matrix
+            .transform_points_batch(&world, &mut homogeneous);
+        let view = homogeneous
+            .iter()
+            .map(|p| p.to_vec3_perspective())
+            .collect();
+        lap(&mut clock, &mut times.world_transform);
+        VertexPositions { world, view }
+    }
+
+    /// Run face `face_index` of `mesh` through the pipeline, appending the
+    /// triangles it becomes (none, one, or several once clipped) to `out`
+    /// and counting its fate in `stats`. The corners' world and view space
+    /// positions come from `positions` when given, and are transformed here
+    /// otherwise. With [`GeometryProcessor::profile`] set, the time it took
+    /// is added to `times`.
+    fn process_face(
+        &self,
+        mesh: &MeshView,
+        positions: Option<&VertexPositions>,
+        face_index: usize,
+        out: &mut Vec<Triangle>,
+        stats: &mut FrameStats,
+        times: &mut StageTimes,
+    ) {
+        let face = &mesh.faces[face_index];
+        let Some(face_vertices) = mesh.face_vertices(face) else {
+            stats.faces_invalid += 1;
+            return;
+        };
+        let mut clock = self.profile.then(Instant::now);
+        let world_matrix = mesh.world_matrix;
+        let view_matrix = self.view_matrix;
+        let camera_position = self.camera_position;
+        let shading_mode = self.shading_mode;
+        let per_pixel_light = self.per_pixel_light;
+        let pixel_lighting = self.pixel_lighting;
+        // Toon bands are picked per pixel, from the interpolated normal,
+        // unless lighting comes after shading
+        let toon = matches!(shading_mode, ShadingMode::Toon { .. }) && !pixel_lighting;
+
+        let mut face_texcoords: [Texel; 3] = [
+            face_vertices[0].texel,
+            face_vertices[1].texel,
+            face_vertices[2].texel,
+        ];
+
+        // Model Space --> World Space (positions)
+        let corners = [face.a, face.b, face.c].map(|i| i as usize);
+        let world_space_positions = match positions {
+            Some(positions) => corners.map(|i| positions.world[i]),
+            None => face_vertices.map(|v| world_matrix * v.position),
+        };
+
+        // Calculate face normal (needed for backface culling).
+        // Note: in the default left-handed coordinate system,
+        // (B-A) × (C-A) points toward the camera exactly when the
+        // triangle is wound CW from the viewer's side, so CW is
+        // "front-facing". The same test makes CCW front-facing
+        // when right-handed; see `Handedness`.
+        let vec_ab = world_space_positions[1] - world_space_positions[0];
+        let vec_ac = world_space_positions[2] - world_space_positions[0];
+        let face_normal = vec_ab.cross(vec_ac);
+
+        // Cull by facing: the face points toward the camera when
+        // its normal and the camera-ward ray agree. Flip this
+        // sign if the scene's meshes are CCW-wound.
+        let camera_ray = camera_position - world_space_positions[0];
+        let front_facing = face_normal.dot(camera_ray) >= 0.0;
+        let culled = match mesh.cull_mode {
+            CullMode::Back => !front_facing,
+            CullMode::Front => front_facing,
+            CullMode::None => false,
+        };
+        if culled {
+            stats.faces_backface_culled += 1;
+            lap(&mut clock, &mut times.world_transform);
+            return;
+        }
+
+        // A back face that survived culling is lit from the side
+        // the camera sees, so flip the normals toward the camera.
+        let normal_sign = if front_facing { 1.0 } else { -1.0 };
+        let face_normal = face_normal * normal_sign;
+        let world_normals =
+            face_vertices.map(|v| (mesh.normal_matrix * v.normal).normalize() * normal_sign);
+
+        // Per-pixel lighting needs a usable normal at every
+        // corner, and normal mapping the tangent frame that
+        // orients the map too. The bitangent comes from the
+        // unflipped normal so it still points along +v on back
+        // faces.
+        let world_normals = if per_pixel_light || toon || self.fill_normals || pixel_lighting {
+            world_normals.map(|n| {
+                if n.magnitude() > 0.0 {
+                    n
+                } else {
+                    face_normal.normalize()
+                }
+            })
+        } else {
+            world_normals
+        };
+        // Lit after shading, faces carry the normals they are lit by
+        let world_normals = match shading_mode {
+            _ if !pixel_lighting => world_normals,
+            ShadingMode::None => [Vec3::ZERO; 3],
+            ShadingMode::Flat => [face_normal.normalize(); 3],
+            _ => world_normals,
+        };
+        let (world_tangents, world_bitangents) = if per_pixel_light {
+            let tangents = face_vertices
+                .map(|v| (world_matrix * Vec4::from_vec3(v.tangent.to_vec3(), 0.0)).to_vec3());
+            let bitangents: [Vec3; 3] = std::array::from_fn(|i| {
+                world_normals[i].cross(tangents[i]) * (face_vertices[i].tangent.w * normal_sign)
+            });
+            (tangents, bitangents)
+        } else {
+            ([Vec3::ZERO; 3], [Vec3::ZERO; 3])
+        };
+
+        // One picking ID per source face, shared by every
+        // triangle the clipper splits it into. 0 = unpickable.
+        let pick_id = if mesh.first_id == 0 {
+            0
+        } else {
+            mesh.first_id + face_index as u32
+        };
+
+        // Transform to view (camera) space
+        let view_space_positions = match positions {
+            Some(positions) => corners.map(|i| positions.view[i]),
+            None => world_space_positions.map(|p| view_matrix * p),
+        };
+
+        // Sphere mapping: replace the mesh UVs with ones derived
+        // from the eye ray reflected about each vertex normal,
+        // expressed in view space so the map follows the camera.
+        if self.texture_mode == TextureMode::SphereMap {
+            for i in 0..3 {
+                let eye_dir = (world_space_positions[i] - camera_position).normalize();
+                let reflected =
+                    view_matrix * Vec4::from_vec3(eye_dir.reflect(world_normals[i]), 0.0);
+                // The map is laid out for a +z-forward view
+                let reflected = self.handedness.mirror(reflected.to_vec3());
+                let (u, v) = sphere_map_uv(reflected);
+                face_texcoords[i] = Texel::new(u, v);
+            }
+        }
+
+        // Calculate colors based on shading mode
+        // Use white for textured modulate mode so lighting doesn't darken the texture
+        let base_color = if self.texture_mode == TextureMode::Modulate {
+            0xFFFFFFFF // White - full brightness when lit
+        } else {
+            self.fill_color
+        };
+        // Baked mesh colors, when asked for and present, replace
+        // the base color vertex by vertex
+        let base_colors = if self.texture_mode == TextureMode::VertexColor && mesh.has_vertex_colors
+        {
+            face_vertices.map(|v| colors::to_rgb_f32(v.color))
+        } else {
+            [colors::to_rgb_f32(base_color); 3]
+        };
+        // Light contributions are summed per channel, saturating
+        // at white. Colors stay in floating point until a shader
+        // packs the pixel, so clipping doesn't round them.
+        let lit = |base: [f32; 3], diffuse: f32| {
+            let level = self.light.ambient_intensity.max(0.0) + diffuse.max(0.0);
+            base.map(|c| (c * level).min(1.0))
+        };
+        let vertex_colors = match shading_mode {
+            // No lighting - use base color
+            ShadingMode::None => base_colors,
+            // The shader, or lighting after it, lights the base color at
+            // each pixel
+            _ if per_pixel_light || pixel_lighting => base_colors,
+            ShadingMode::Toon { .. } => base_colors,
+            ShadingMode::Flat => {
+                // Flat shading - one light level per face based on face normal
+                let normal = face_normal.normalize();
+                let diffuse = self.light.intensity(normal) * self.light.diffuse_strength;
+                base_colors.map(|base| lit(base, diffuse))
+            }
+            ShadingMode::Gouraud => {
+                // Gouraud shading - per-vertex lighting
+                let mut vert_colors = [[0.0; 3]; 3];
+                for i in 0..3 {
+                    let diffuse =
+                        self.light.intensity(world_normals[i]) * self.light.diffuse_strength;
+                    vert_colors[i] = lit(base_colors[i], diffuse);
+                }
+                vert_colors
+            }
+        };
+        // Representative single color (identical at every vertex
+        // unless Gouraud or vertex colors vary it)
+        let flat_color = colors::from_rgb_f32(vertex_colors[0]);
+        lap(&mut clock, &mut times.world_transform);
+
+        // ==================== PROJECT TO CLIP SPACE ====================
+        // Transform from view space to clip space (homogeneous coordinates)
+        let clip_space_positions = [
+            self.projection_matrix * Vec4::from_vec3(view_space_positions[0], 1.0),
+            self.projection_matrix * Vec4::from_vec3(view_space_positions[1], 1.0),
+            self.projection_matrix * Vec4::from_vec3(view_space_positions[2], 1.0),
+        ];
+
+        // ==================== CLIP IN CLIP SPACE ====================
+        // Bundle each homogeneous position with the attributes
+        // the clipper interpolates
+        let clip_vertices: [ClipSpaceVertex; 3] = std::array::from_fn(|i| {
+            ClipSpaceVertex::new(
+                clip_space_positions[i],
+                world_space_positions[i],
+                world_normals[i],
+                face_texcoords[i],
+                vertex_colors[i],
+                face_vertices[i].position,
+            )
+            .with_tangents(world_tangents[i], world_bitangents[i])
+        });
+
+        // Clip against the canonical clip cube: -w <= x,y,z <= w.
+        // Most faces are wholly inside or wholly outside it; only
+        // those straddling a plane build a polygon and clip.
+        let clipper = ClipSpaceClipper::new();
+        let (accepted, clipped_polygon) = match clipper.classify(&clip_vertices) {
+            ClipClass::Inside => {
+                stats.faces_trivially_accepted += 1;
+                (Some(&clip_vertices), None)
+            }
+            ClipClass::Outside => {
+                stats.faces_trivially_rejected += 1;
+                stats.faces_clipped_away += 1;
+                lap(&mut clock, &mut times.clipping);
+                return;
+            }
+            ClipClass::Straddling => {
+                stats.faces_clipped += 1;
+                let polygon = ClipSpacePolygon::from_triangle(
+                    clip_vertices[0],
+                    clip_vertices[1],
+                    clip_vertices[2],
+                );
+                let clipped_polygon = clipper.clip_polygon(polygon);
+
+                // Skip if polygon was completely clipped away
+                if clipped_polygon.is_empty() {
+                    stats.faces_clipped_away += 1;
+                    lap(&mut clock, &mut times.clipping);
+                    return;
+                }
+                (None, Some(clipped_polygon))
+            }
+        };
+        let pieces = accepted
+            .map(|[v0, v1, v2]| (v0, v1, v2, Triangle::ALL_EDGES))
+            .into_iter()
+            .chain(clipped_polygon.iter().flat_map(|p| p.triangulate()));
+        lap(&mut clock, &mut times.clipping);
+
+        // ==================== PERSPECTIVE DIVIDE & VIEWPORT TRANSFORM ====================
+        // Transform each (possibly clipped) triangle to screen space
+        for (v0, v1, v2, edge_flags) in pieces {
+            let clipped_positions = [v0.position, v1.position, v2.position];
+            let clipped_texcoords = [v0.uv, v1.uv, v2.uv];
+            let clipped_colors = [v0.color, v1.color, v2.color];
+            let clipped_surface = [v0.surface, v1.surface, v2.surface];
+            let clipped_world = [v0.world_position, v1.world_position, v2.world_position];
+            let clipped_normals = [v0.normal, v1.normal, v2.normal];
+            let clipped_tangents = [v0.tangent, v1.tangent, v2.tangent];
+            let clipped_bitangents = [v0.bitangent, v1.bitangent, v2.bitangent];
+
+            let mut screen_vertices = [ScreenVertex::new(Vec2::ZERO, 0.0); 3];
+            let mut all_valid = true;
+
+            for (i, clip_pos) in clipped_positions.iter().enumerate() {
+                // After clipping, w should always be positive
+                // but check anyway for safety
+                if clip_pos.w <= 0.0 {
+                    all_valid = false;
+                    break;
+                }
+
+                // Perspective divide: clip space -> NDC [-1, 1]
+                let ndc = clip_pos.to_vec3() / clip_pos.w;
+
+                // Viewport transform: NDC -> screen coordinates,
+                // keeping w for depth buffer (1/w) and
+                // perspective-correct interpolation
+                let screen = self.viewport.project(ndc, clip_pos.w);
+                let mut position = Vec2::new(screen.x, screen.y);
+                if let Some(cell) = self.snap_grid {
+                    // Counted from the viewport's corner, so cells line up with its edges
+                    let snap = |p: f32, origin: f32, size: f32| {
+                        origin + ((p - origin) / size).round() * size
+                    };
+                    position.x = snap(position.x, self.viewport.x, cell.x);
+                    position.y = snap(position.y, self.viewport.y, cell.y);
+                }
+                screen_vertices[i] = ScreenVertex::new(position, screen.z);
+            }
+
+            // Specks that cover no pixel still cost setup in every pass
+            if all_valid && self.min_triangle_area > 0.0 {
+                let [a, b, c] = screen_vertices.map(|v| v.position);
+                let area = (b - a).cross(c - a).abs() * 0.5;
+                if area < self.min_triangle_area && misses_pixel_centers([a, b, c]) {
+                    stats.triangles_too_small += 1;
+                    continue;
+                }
+            }
+
+            if all_valid {
+                // Use flat_color for flat shading, interpolated colors for Gouraud
+                let tri_color = if shading_mode == ShadingMode::Gouraud {
+                    // Use first vertex color as representative
+                    colors::from_rgb_f32(clipped_colors[0])
+                } else {
+                    flat_color
+                };
+
+                let mut triangle = Triangle::new(
+                    screen_vertices,
+                    tri_color,
+                    clipped_colors,
+                    clipped_texcoords,
+                    shading_mode,
+                    self.texture_mode,
+                );
+                triangle.texture_region = mesh.texture_region;
+                triangle.dither = self.dithering;
+                triangle.affine_textures = self.affine_textures;
+                triangle.fog = self.fog;
+                triangle.surface_positions = clipped_surface;
+                triangle.world_positions = clipped_world;
+                triangle.normals = clipped_normals;
+                triangle.tangents = clipped_tangents;
+                triangle.bitangents = clipped_bitangents;
+                triangle.light = (per_pixel_light || toon).then_some(self.light);
+                triangle.edge_flags = edge_flags;
+                triangle.pass_flags = mesh.pass_flags;
+                triangle.id = pick_id;
+                out.push(triangle);
+                stats.triangles_drawn += 1;
+            }
+        }
+        lap(&mut clock, &mut times.projection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projection::Projection;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn vertex(x: f32, y: f32, z: f32) -> Vertex {
+        Vertex {
+            position: Vec3::new(x, y, z),
+            normal: Vec3::new(0.0, 0.0, -1.0),
+            texel: Vec2::ZERO,
+            color: 0xFFFFFFFF,
+            tangent: Vec4::ZERO,
+        }
+    }
+
+    /// A camera at the origin looking down +z with a 90 degree field of
+    /// view, onto a 100x100 viewport: a point at depth `z` lands `z` units
+    /// from the center at the viewport's edge.
+    fn processor() -> GeometryProcessor {
+        GeometryProcessor {
+            view_matrix: Mat4::identity(),
+            projection_matrix: Projection::from_degrees(90.0, 1.0, 0.1, 100.0).matrix(),
+            camera_position: Vec3::ZERO,
+            viewport: Viewport::full(100, 100),
+            handedness: Handedness::Left,
+            light: DirectionalLight::new(Vec3::new(0.0, 0.0, 1.0)),
+            shading_mode: ShadingMode::None,
+            texture_mode: TextureMode::None,
+            per_pixel_light: false,
+            pixel_lighting: false,
+            dithering: false,
+            snap_grid: None,
+            affine_textures: false,
+            fog: None,
+            fill_color: 0xFFFFFFFF,
+            fill_normals: false,
+            profile: false,
+            min_triangle_area: 0.0,
+        }
+    }
+
+    fn view<'a>(vertices: &'a [Vertex], faces: &'a [Face]) -> MeshView<'a> {
+        MeshView {
+            vertices,
+            faces,
+            world_matrix: Mat4::identity(),
+            normal_matrix: Mat4::identity(),
+            cull_mode: CullMode::Back,
+            has_vertex_colors: false,
+            texture_region: None,
+            pass_flags: Triangle::FILL_PASS,
+            first_id: 0,
+        }
+    }
+
+    /// Wound clockwise as seen from the camera, at depth 5
+    fn facing_triangle() -> [Vertex; 3] {
+        [
+            vertex(-1.0, -1.0, 5.0),
+            vertex(0.0, 1.0, 5.0),
+            vertex(1.0, -1.0, 5.0),
+        ]
+    }
+
+    #[test]
+    fn known_face_projects_to_exact_screen_triangle() {
+        let vertices = facing_triangle();
+        let faces = [Face::new(0, 1, 2)];
+        let mut mesh = view(&vertices, &faces);
+        mesh.first_id = 7;
+        let (triangles, stats) = processor().process_mesh(&mesh);
+
+        assert_eq!(triangles.len(), 1);
+        let triangle = triangles[0];
+        let expected = [
+            Vec2::new(40.0, 60.0),
+            Vec2::new(50.0, 40.0),
+            Vec2::new(60.0, 60.0),
+        ];
+        for (point, expected) in triangle.points.iter().zip(expected) {
+            assert!((point.position - expected).magnitude() < EPSILON);
+            assert!((point.w - 5.0).abs() < EPSILON);
+        }
+        assert_eq!(triangle.vertex_colors, [[1.0; 3]; 3]);
+        assert_eq!(triangle.edge_flags, Triangle::ALL_EDGES);
+        assert_eq!(triangle.id, 7);
+        assert_eq!(
+            stats,
+            FrameStats {
+                faces_trivially_accepted: 1,
+                triangles_drawn: 1,
+                ..FrameStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn back_faces_are_culled_or_turned_to_the_camera() {
+        let [a, b, c] = facing_triangle();
+        let vertices = [a, c, b];
+        let faces = [Face::new(0, 1, 2)];
+        let mut mesh = view(&vertices, &faces);
+
+        let (triangles, stats) = processor().process_mesh(&mesh);
+        assert!(triangles.is_empty());
+        assert_eq!(stats.faces_backface_culled, 1);
+
+        // Drawn from behind, its normals flip to face the camera
+        mesh.cull_mode = CullMode::None;
+        let (triangles, _) = processor().process_mesh(&mesh);
+        assert_eq!(triangles.len(), 1);
+        for normal in triangles[0].normals {
+            assert!((normal - Vec3::new(0.0, 0.0, 1.0)).magnitude() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn face_crossing_the_near_plane_is_clipped() {
+        // One corner behind the camera
+        let vertices = [
+            vertex(-1.0, -1.0, 5.0),
+            vertex(0.0, 1.0, -1.0),
+            vertex(1.0, -1.0, 5.0),
+        ];
+        let faces = [Face::new(0, 1, 2)];
+        let mut mesh = view(&vertices, &faces);
+        mesh.cull_mode = CullMode::None;
+        let (triangles, stats) = processor().process_mesh(&mesh);
+
+        assert_eq!(stats.faces_clipped, 1);
+        assert_eq!(stats.triangles_drawn, triangles.len());
+        assert!(triangles.len() >= 2);
+        for triangle in &triangles {
+            assert!(triangle.points.iter().all(|p| p.w >= 0.1 - EPSILON));
+        }
+    }
+
+    #[test]
+    fn stage_times_are_only_taken_when_profiling() {
+        let vertices = facing_triangle();
+        let faces = [Face::new(0, 1, 2)];
+        let mesh = view(&vertices, &faces);
+        let mut processor = processor();
+        let mut times = StageTimes::default();
+        processor.process_face(
+            &mesh,
+            None,
+            0,
+            &mut Vec::new(),
+            &mut FrameStats::default(),
+            &mut times,
+        );
+        assert_eq!(times, StageTimes::default());
+
+        processor.profile = true;
+        processor.process_face(
+            &mesh,
+            None,
+            0,
+            &mut Vec::new(),
+            &mut FrameStats::default(),
+            &mut times,
+        );
+        assert!(times.world_transform > Duration::ZERO);
+        assert!(times.clipping > Duration::ZERO);
+        assert!(times.projection > Duration::ZERO);
+    }
+
+    #[test]
+    fn invalid_faces_are_counted_and_skipped() {
+        let vertices = facing_triangle();
+        let faces = [Face::new(0, 1, 3), Face::new(0, 1, 2)];
+        let mut mesh = view(&vertices, &faces);
+        mesh.first_id = 1;
+        let (triangles, stats) = processor().process_mesh(&mesh);
+
+        assert_eq!(stats.faces_invalid, 1);
+        // The valid face keeps its own ID
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(triangles[0].id, 2);
+    }
+
+    #[test]
+    fn specks_below_the_minimum_area_are_dropped() {
+        // 10 px per unit at depth 5: a speck between pixel centers, and a
+        // sliver 60 px long but under a pixel high that spans a row of
+        // centers
+        let vertices = [
+            vertex(0.02, 0.02, 5.0),
+            vertex(0.04, 0.02, 5.0),
+            vertex(0.02, 0.04, 5.0),
+            vertex(-3.0, 0.04, 5.0),
+            vertex(3.0, 0.05, 5.0),
+            vertex(-3.0, 0.06, 5.0),
+        ];
+        let faces = [Face::new(0, 1, 2), Face::new(3, 4, 5)];
+        let mut mesh = view(&vertices, &faces);
+        mesh.cull_mode = CullMode::None;
+
+        let (triangles, stats) = processor().process_mesh(&mesh);
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(stats.triangles_too_small, 0);
+
+        let mut processor = processor();
+        processor.min_triangle_area = 100.0;
+        let (triangles, stats) = processor.process_mesh(&mesh);
+        assert_eq!(triangles.len(), 1);
+        assert!(triangles[0].points[1].position.x > 70.0);
+        assert_eq!(stats.triangles_too_small, 1);
+        assert_eq!(stats.triangles_drawn, 1);
+    }
+
+    #[test]
+    fn batched_positions_match_per_face_transforms() {
+        let vertices = [
+            facing_triangle().to_vec(),
+            vec![vertex(2.0, 0.5, 4.0), vertex(-0.5, -2.0, 6.0)],
+        ]
+        .concat();
+        let faces = [Face::new(0, 1, 2), Face::new(0, 3, 1), Face::new(4, 2, 3)];
+        let mut mesh = view(&vertices, &faces);
+        mesh.cull_mode = CullMode::None;
+        mesh.world_matrix = Mat4::from_trs(
+            Vec3::new(0.3, -0.2, 1.5),
+            Vec3::new(0.1, 0.4, -0.2),
+            Vec3::new(1.2, 0.9, 1.1),
+        );
+        let mut processor = processor();
+        processor.camera_position = Vec3::new(0.5, 0.5, -1.0);
+        processor.view_matrix = Mat4::look_at_lh(
+            processor.camera_position,
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::UP,
+        );
+
+        let mut per_face = Vec::new();
+        let mut per_face_stats = FrameStats::default();
+        for face_index in 0..faces.len() {
+            processor.process_face(
+                &mesh,
+                None,
+                face_index,
+                &mut per_face,
+                &mut per_face_stats,
+                &mut StageTimes::default(),
+            );
+        }
+        let (batched, batched_stats) = processor.process_mesh(&mesh);
+
+        assert_eq!(batched.len(), faces.len());
+        assert_eq!(batched_stats, per_face_stats);
+        assert!(batched == per_face);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_sequential() {
+        // A grid of faces, enough to take the parallel path, some of them
+        // hanging off screen to be clipped
+        const N: u32 = 40;
+        let vertices: Vec<Vertex> = (0..=N)
+            .flat_map(|j| (0..=N).map(move |i| (i, j)))
+            .map(|(i, j)| vertex(i as f32 * 0.5 - 4.0, j as f32 * 0.5 - 10.0, 5.0))
+            .collect();
+        let index = |i: u32, j: u32| j * (N + 1) + i;
+        let faces: Vec<Face> = (0..N)
+            .flat_map(|j| (0..N).map(move |i| (i, j)))
+            .flat_map(|(i, j)| {
+                [
+                    Face::new(index(i, j), index(i, j + 1), index(i + 1, j + 1)),
+                    Face::new(index(i, j), index(i + 1, j + 1), index(i + 1, j)),
+                ]
+            })
+            .collect();
+        assert!(faces.len() >= PARALLEL_MIN_FACES);
+        let mut mesh = view(&vertices, &faces);
+        mesh.first_id = 1;
+        let processor = processor();
+
+        let mut sequential = Vec::new();
+        let mut sequential_stats = FrameStats::default();
+        let mut times = StageTimes::default();
+        for face_index in 0..faces.len() {
+            processor.process_face(
+                &mesh,
+                None,
+                face_index,
+                &mut sequential,
+                &mut sequential_stats,
+                &mut times,
+            );
+        }
+        let (parallel, parallel_stats) = processor.process_mesh(&mesh);
+
+        assert!(sequential_stats.faces_clipped > 0);
+        assert_eq!(parallel_stats, sequential_stats);
+        assert!(parallel == sequential);
+    }
+}
@@ -0,0 +1,257 @@
+//! glTF 2.0 loading via the `gltf` crate (`gltf` feature).
+//!
+//! Reads the default scene's node tree into a flat list of meshes, one per
+//! triangle primitive, each with its node's world transform as its own
+//! [`Transform`]. Base-color textures become model textures. Everything
+//! else — skins, animations, cameras, lights, PBR parameters beyond base
+//! color, non-triangle primitives — is skipped.
+//!
+//! glTF is right-handed with counter-clockwise front faces. Meshes are
+//! mirrored in z and rewound on import, so they come out the same way up
+//! and facing the same way in the engine's default left-handed system.
+
+use std::collections::HashMap;
+
+use crate::colors::pack_color;
+use crate::math::{mat4::Mat4, quat::Quat, vec2::Vec2, vec3::Vec3, vec4::Vec4};
+use crate::mesh::{Face, LoadError, Mesh, Vertex};
+use crate::model::Model;
+use crate::texture::Texture;
+use crate::transform::Transform;
+
+/// Load the default scene (or the first) of a `.gltf` or `.glb` file.
+pub(crate) fn load(name: String, file_path: &str) -> Result<Model, LoadError> {
+    let (document, buffers, images) = gltf::import(file_path)?;
+    let scene = document
+        .default_scene()
+        .or_else(|| document.scenes().next())
+        .ok_or(LoadError::NoModels)?;
+
+    let mut loader = Loader {
+        buffers: &buffers,
+        images: &images,
+        model: Model::new(name),
+        textures: HashMap::new(),
+    };
+    for node in scene.nodes() {
+        loader.visit(&node, Mat4::identity());
+    }
+
+    if loader.model.mesh_count() == 0 {
+        return Err(LoadError::NoVertices);
+    }
+    Ok(loader.model)
+}
+
+struct Loader<'a> {
+    buffers: &'a [gltf::buffer::Data],
+    images: &'a [gltf::image::Data],
+    model: Model,
+    /// glTF image index -> model texture index, so shared images load once
+    textures: HashMap<usize, Option<usize>>,
+}
+
+impl Loader<'_> {
+    /// Add `node`'s meshes under `parent`'s world matrix, then recurse.
+    fn visit(&mut self, node: &gltf::Node, parent: Mat4) {
+        let world = parent * mat4_from_columns(node.transform().matrix());
+
+        if let Some(mesh) = node.mesh() {
+            let base_name = mesh
+                .name()
+                .or(node.name())
+                .map_or_else(|| format!("mesh_{}", mesh.index()), str::to_string);
+            let primitive_count = mesh.primitives().len();
+            for primitive in mesh.primitives() {
+                let name = if primitive_count > 1 {
+                    format!("{base_name}_{}", primitive.index())
+                } else {
+                    base_name.clone()
+                };
+                if let Some(mut mesh) = self.primitive(&primitive, self.unique_name(name)) {
+                    *mesh.transform_mut() = mirrored_transform(&world);
+                    self.model.add_mesh(mesh);
+                }
+            }
+        }
+
+        for child in node.children() {
+            self.visit(&child, world);
+        }
+    }
+
+    /// Build a mesh from a triangle primitive, or `None` to skip it.
+    fn primitive(&mut self, primitive: &gltf::Primitive, name: String) -> Option<Mesh> {
+        if primitive.mode() != gltf::mesh::Mode::Triangles {
+            return None;
+        }
+        let reader = primitive.reader(|buffer| self.buffers.get(buffer.index()).map(|d| &*d.0));
+        let positions: Vec<[f32; 3]> = reader.read_positions()?.collect();
+        if positions.is_empty() {
+            return None;
+        }
+
+        let pbr = primitive.material().pbr_metallic_roughness();
+        let base_color_texture = pbr.base_color_texture();
+        let tex_coord_set = base_color_texture
+            .as_ref()
+            .map_or(0, |info| info.tex_coord());
+
+        let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(Iterator::collect);
+        let texcoords: Option<Vec<[f32; 2]>> = reader
+            .read_tex_coords(tex_coord_set)
+            .map(|t| t.into_f32().collect());
+        let colors: Option<Vec<[f32; 4]>> =
+            reader.read_colors(0).map(|c| c.into_rgba_f32().collect());
+        // Without per-vertex colors, a non-white base color factor stands in
+        let factor = pbr.base_color_factor();
+        let uniform_color = (colors.is_none() && factor != [1.0; 4])
+            .then(|| pack_color(factor[0], factor[1], factor[2], factor[3]));
+
+        // z is mirrored for the left-handed engine; see the module docs
+        let vertices: Vec<Vertex> = positions
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Vertex {
+                position: Vec3::new(p[0], p[1], -p[2]),
+                normal: normals
+                    .as_ref()
+                    .and_then(|n| n.get(i))
+                    .map_or(Vec3::ZERO, |n| Vec3::new(n[0], n[1], -n[2])),
+                // glTF puts the UV origin top-left, OBJ (and Texture::sample)
+                // bottom-left
+                texel: texcoords
+                    .as_ref()
+                    .and_then(|t| t.get(i))
+                    .map_or(Vec2::ZERO, |t| Vec2::new(t[0], 1.0 - t[1])),
+                color: colors
+                    .as_ref()
+                    .and_then(|c| c.get(i))
+                    .map(|c| pack_color(c[0], c[1], c[2], c[3]))
+                    .or(uniform_color)
+                    .unwrap_or(0xFFFFFFFF),
+                tangent: Vec4::ZERO,
+            })
+            .collect();
+
+        let indices: Vec<u32> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect(),
+            None => (0..vertices.len() as u32).collect(),
+        };
+        // Reversed winding: mirroring turned CCW fronts into CW ones
+        let faces: Vec<Face> = indices
+            .chunks_exact(3)
+            .filter(|c| c.iter().all(|&i| (i as usize) < vertices.len()))
+            .map(|c| Face::new(c[0], c[2], c[1]))
+            .collect();
+        if faces.is_empty() {
+            return None;
+        }
+
+        let mut mesh = Mesh::new(name, vertices, faces);
+        mesh.set_has_vertex_colors(colors.is_some() || uniform_color.is_some());
+        if texcoords.is_some() {
+            mesh.compute_tangents();
+        }
+        let texture =
+            base_color_texture.and_then(|info| self.texture(info.texture().source().index()));
+        mesh.set_texture_index(texture);
+        Some(mesh)
+    }
+
+    /// Model texture index for glTF image `index`, converting it on first
+    /// use. `None` for pixel formats a [`Texture`] can't hold.
+    fn texture(&mut self, index: usize) -> Option<usize> {
+        if let Some(&texture) = self.textures.get(&index) {
+            return texture;
+        }
+        let texture = self
+            .images
+            .get(index)
+            .and_then(texture_from_image)
+            .map(|texture| self.model.add_texture(texture));
+        self.textures.insert(index, texture);
+        texture
+    }
+
+    /// `name`, suffixed if an earlier mesh already took it.
+    fn unique_name(&self, name: String) -> String {
+        if self.model.mesh(&name).is_none() {
+            return name;
+        }
+        (2..)
+            .map(|n| format!("{name}_{n}"))
+            .find(|candidate| self.model.mesh(candidate).is_none())
+            .unwrap()
+    }
+}
+
+/// glTF stores matrices as columns; `Mat4::new` takes rows.
+fn mat4_from_columns(columns: [[f32; 4]; 4]) -> Mat4 {
+    let mut rows = [[0.0; 4]; 4];
+    for (col, column) in columns.iter().enumerate() {
+        for (row, value) in column.iter().enumerate() {
+            rows[row][col] = *value;
+        }
+    }
+    Mat4::new(rows)
+}
+
+/// The right-handed world matrix `m`, mirrored in z (`S * m * S`) and split
+/// into translation, rotation and scale. Shear from non-uniform scale under
+/// a rotated parent can't be represented and is dropped.
+fn mirrored_transform(m: &Mat4) -> Transform {
+    let s = Mat4::scaling(1.0, 1.0, -1.0);
+    let m = s * *m * s;
+
+    let column = |c: usize| Vec3::new(m.get(0, c), m.get(1, c), m.get(2, c));
+    let (x, y, z) = (column(0), column(1), column(2));
+    let mut scale = Vec3::new(x.magnitude(), y.magnitude(), z.magnitude());
+    // A reflection shows up as a negative determinant; put it in x
+    if x.dot(y.cross(z)) < 0.0 {
+        scale.x = -scale.x;
+    }
+
+    let mut transform = Transform::new();
+    transform
+        .set_position(Vec3::new(m.get(0, 3), m.get(1, 3), m.get(2, 3)))
+        .set_scale(scale);
+    if scale.x != 0.0 && scale.y != 0.0 && scale.z != 0.0 {
+        let (x, y, z) = (x / scale.x, y / scale.y, z / scale.z);
+        let rotation = Mat4::new([
+            [x.x, y.x, z.x, 0.0],
+            [x.y, y.y, z.y, 0.0],
+            [x.z, y.z, z.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        transform.set_rotation_quat(Quat::from_rotation_matrix(&rotation));
+    }
+    transform
+}
+
+/// Convert decoded image pixels to an ARGB [`Texture`].
+fn texture_from_image(image: &gltf::image::Data) -> Option<Texture> {
+    use gltf::image::Format;
+
+    let argb = |r: u8, g: u8, b: u8, a: u8| {
+        ((a as u32) << 24) | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+    };
+    let pixels = &image.pixels;
+    let data: Vec<u32> = match image.format {
+        Format::R8G8B8A8 => pixels
+            .chunks_exact(4)
+            .map(|p| argb(p[0], p[1], p[2], p[3]))
+            .collect(),
+        Format::R8G8B8 => pixels
+            .chunks_exact(3)
+            .map(|p| argb(p[0], p[1], p[2], 0xFF))
+            .collect(),
+        Format::R8G8 => pixels
+            .chunks_exact(2)
+            .map(|p| argb(p[0], p[0], p[0], p[1]))
+            .collect(),
+        Format::R8 => pixels.iter().map(|&l| argb(l, l, l, 0xFF)).collect(),
+        _ => return None,
+    };
+    Texture::from_pixels(data, image.width, image.height).ok()
+}
@@ -0,0 +1,129 @@
+//! Reloading models and textures when their files change on disk.
+//!
+//! With [`Engine::enable_hot_reload`] on, [`Engine::update`] checks the
+//! modification time of every file a model or texture was loaded from, at
+//! most once per [`POLL_INTERVAL`], and loads the changed ones again in
+//! place. Transforms, animators and texture assignments survive the reload;
+//! a file that fails to load leaves the previous data on screen. What
+//! happened is reported through [`Engine::take_reload_events`].
+//!
+//! [`Engine::enable_hot_reload`]: crate::engine::Engine::enable_hot_reload
+//! [`Engine::update`]: crate::engine::Engine::update
+//! [`Engine::take_reload_events`]: crate::engine::Engine::take_reload_events
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::texture::Texture;
+
+/// How often tracked files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// One asset the engine reloaded, or tried to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReloadEvent {
+    /// A model's meshes were loaded again from `path`.
+    Model { name: String, path: PathBuf },
+    /// A texture was loaded again from `path`. `model` names the model it
+    /// belongs to; `None` for the engine's own textures.
+    Texture {
+        model: Option<String>,
+        path: PathBuf,
+    },
+    /// `path` changed but failed to load; the previous data is kept.
+    Failed { path: PathBuf, error: String },
+}
+
+impl fmt::Display for ReloadEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReloadEvent::Model { name, path } => {
+                write!(f, "reloaded model '{}' from {}", name, path.display())
+            }
+            ReloadEvent::Texture { model, path } => match model {
+                Some(name) => write!(f, "reloaded texture of '{}' from {}", name, path.display()),
+                None => write!(f, "reloaded texture from {}", path.display()),
+            },
+            ReloadEvent::Failed { path, error } => {
+                write!(f, "failed to reload {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+/// The modification times last seen for each tracked file, and the events
+/// not yet taken.
+pub(crate) struct HotReload {
+    last_poll: Instant,
+    modified: HashMap<PathBuf, SystemTime>,
+    pub(crate) events: Vec<ReloadEvent>,
+}
+
+impl HotReload {
+    pub(crate) fn new() -> Self {
+        Self {
+            last_poll: Instant::now(),
+            modified: HashMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Whether a poll interval has passed since the last check.
+    pub(crate) fn due(&self) -> bool {
+        self.last_poll.elapsed() >= POLL_INTERVAL
+    }
+
+    /// Of `paths`, the files modified since they were last seen. Files seen
+    /// for the first time are only recorded, and files that can't be read
+    /// (mid-save, say) are skipped until they can.
+    pub(crate) fn changed<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) -> HashSet<PathBuf> {
+        self.last_poll = Instant::now();
+        let mut changed = HashSet::new();
+        for path in paths {
+            let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+                continue;
+            };
+            match self.modified.insert(path.to_path_buf(), modified) {
+                Some(previous) if previous != modified => {
+                    changed.insert(path.to_path_buf());
+                }
+                _ => {}
+            }
+        }
+        changed
+    }
+
+    /// If `texture` came from one of the `changed` files, load it again.
+    /// Returns the fresh texture to put in its place, and records the
+    /// outcome either way.
+    pub(crate) fn reload_texture(
+        &mut self,
+        texture: Option<&Texture>,
+        model: Option<&str>,
+        changed: &HashSet<PathBuf>,
+    ) -> Option<Texture> {
+        let texture = texture?;
+        let path = texture.source_path().filter(|p| changed.contains(*p))?;
+        match Texture::from_file_with(path, texture.load_options()) {
+            Ok(fresh) => {
+                self.events.push(ReloadEvent::Texture {
+                    model: model.map(str::to_string),
+                    path: path.to_path_buf(),
+                });
+                Some(fresh)
+            }
+            Err(error) => {
+                self.events.push(ReloadEvent::Failed {
+                    path: path.to_path_buf(),
+                    error: error.to_string(),
+                });
+                None
+            }
+        }
+    }
+}
@@ -3,6 +3,10 @@
 //! This crate provides a simple 3D rendering pipeline using SDL2 only for
 //! window management and display. All rendering is done on the CPU.
 //!
+//! The SDL2 window sits behind the default `sdl2-window` feature; with
+//! `--no-default-features` the engine renders headless and frames can be
+//! written out with [`window::PngPresenter`].
+//!
 //! # Quick Start
 //!
 //! ```ignore
@@ -12,70 +16,171 @@
 //! let mut engine = Engine::new(800, 600);
 //! engine.load_cube_mesh();
 //! ```
+//!
+//! # API stability
+//!
+//! The crate is pre-1.0, so a breaking change bumps the minor version. Within
+//! that, the public items fall into three groups:
+//!
+//! - **Stable:** everything in [`prelude`] and re-exported at the crate
+//!   root, and the modules they live in. Breaking changes to these are rare,
+//!   called out in the changelog, and go through a deprecation first where
+//!   they can (as with [`window::FrameLimiter`]).
+//! - **Unstable:** [`diagnostics`], [`hot_reload`], [`profile`] and `scene`
+//!   (feature `serde`). Tools for developing against the engine
+//!   rather than building on it; they may change in any minor release.
+//! - **Internal:** `bench`, only built with the `bench-internals` feature.
+//!   Rasterizer internals for the crate's own benchmarks and low-level
+//!   tests, with no stability promise at all.
+//!
+//! Anything else reachable only by a path through `engine` into the
+//! rasterizer ([`engine::FrameBuffer`], [`engine::Triangle`] and friends)
+//! is there for [custom rasterizers](engine::Engine::set_custom_rasterizer)
+//! and is stable with them.
 
 // Public API - exposed to library consumers
+pub mod animation;
 pub mod camera;
 pub mod colors;
 pub mod engine;
+pub mod error;
+pub mod fog;
 pub mod light;
+pub mod loading;
+pub mod lod;
 pub mod math;
 pub mod model;
+pub mod post;
 pub mod projection;
 pub mod texture;
 pub mod transform;
+pub mod viewport;
 pub mod window;
+pub mod wire_mesh;
+
+// Unstable public API - development tools, may change in any minor release
+pub mod diagnostics;
+pub mod hot_reload;
+pub mod profile;
+#[cfg(feature = "serde")]
+pub mod scene;
+
+// Rasterizer internals for benchmarks and low-level tests, no stability
+// promise
+#[cfg(feature = "bench-internals")]
+pub mod bench;
 
 // Internal modules - used within the crate only
 pub(crate) mod clipper;
 pub(crate) mod frustum;
+pub(crate) mod geometry;
+#[cfg(feature = "gltf")]
+pub(crate) mod gltf_loader;
 pub(crate) mod mesh;
 pub(crate) mod render;
+pub(crate) mod stl_loader;
+pub(crate) mod subdivide;
 
 // Re-export commonly needed types at crate root for convenience
-pub use engine::{Engine, RasterizerType, RenderMode, ShadingMode};
-pub use mesh::{LoadError, Mesh};
+pub use engine::{
+    DepthMode, Engine, FrameStats, GridMode, MeshHandle, PickResult, Pipeline, RasterizerType,
+    RayHit, RenderMode, RetroSettings, SelectionOutline, ShadingMode, SilhouetteOutline,
+};
+pub use error::Error;
+pub use mesh::{CullMode, LoadError, Mesh};
 pub use model::Model;
-pub use projection::Projection;
+pub use projection::{Handedness, Projection};
+pub use texture::Texture;
 pub use transform::Transform;
+pub use viewport::Viewport;
+pub use wire_mesh::WireMesh;
 
-/// Prelude module for convenient imports.
+/// Prelude module for convenient imports: everything a program needs to
+/// load, light and draw a scene and put it on screen. Stable; see
+/// [API stability](crate#api-stability).
 ///
 /// # Example
 /// ```ignore
 /// use russsty::prelude::*;
 /// ```
 pub mod prelude {
+    // Animation
+    pub use crate::animation::{Animator, CameraPath, KeyframeTrack};
+
     // Camera
-    pub use crate::camera::{FpsCamera, FpsCameraController};
+    pub use crate::camera::{
+        Camera, CameraMode, CameraShake, FollowCamera, FpsCamera, FpsCameraController,
+        OrbitCamera, OrbitCameraController, SceneCamera,
+    };
 
     // Engine
-    pub use crate::engine::{Engine, RenderMode, ShadingMode, TextureMode};
+    pub use crate::engine::{
+        CullResult, DebugDraw, DebugView, Engine, FrameStats, GridMode, HiddenSurface, MeshCull,
+        MeshHandle, PickResult, Pipeline, RayHit, RenderMode, RetroSettings, SelectionOutline,
+        ShadingMode, SilhouetteOutline, TextureMode,
+    };
+
+    // Errors
+    pub use crate::error::Error;
+    pub use crate::mesh::LoadError;
+    pub use crate::texture::TextureUpdateError;
+
+    // Fog
+    pub use crate::fog::{Fog, FogMode};
+
+    // Lighting
+    pub use crate::light::{DirectionalLight, PointLight, SphericalLight};
 
     // Model
+    pub use crate::loading::{LoadHandle, LoadStatus};
+    pub use crate::lod::LodGroup;
+    pub use crate::mesh::{CullMode, Mesh};
     pub use crate::model::Model;
 
+    // Post-processing
+    pub use crate::post::{Grayscale, PostEffect, Vignette};
+
+    // Texture
+    pub use crate::texture::{
+        AddressMode, Texture, TextureFilter, TextureHandle, TextureLoadOptions, TextureRegion,
+    };
+
     // Projection
-    pub use crate::projection::Projection;
+    pub use crate::projection::{FovAxis, Handedness, Projection};
 
     // Transform
     pub use crate::transform::Transform;
 
+    // Viewport
+    pub use crate::viewport::Viewport;
+
+    // Debug shapes
+    pub use crate::wire_mesh::WireMesh;
+
     // Math
     pub use crate::math::mat4::Mat4;
+    pub use crate::math::plane::Plane;
+    pub use crate::math::quat::Quat;
+    pub use crate::math::ray::Ray;
     pub use crate::math::vec2::Vec2;
     pub use crate::math::vec3::Vec3;
     pub use crate::math::vec4::Vec4;
 
-    // Rendering
-    pub use crate::render::RasterizerType;
+    // Rendering, and custom rasterizers
+    pub use crate::render::{
+        BlendMode, DepthMode, Fragment, FrameBuffer, GBufferSample, PixelShader, Rasterizer,
+        RasterizerType, RenderTarget, ScreenVertex, Triangle,
+    };
 
     // Window & Input
-    pub use crate::window::{FpsCounter, FrameLimiter, InputState, Key, Window, WindowEvent};
-}
-
-/// Module exposing internals for benchmarking. Not part of the stable API.
-pub mod bench {
-    pub use crate::render::{
-        EdgeFunctionRasterizer, FrameBuffer, Rasterizer, ScanlineRasterizer, ScreenVertex, Triangle,
+    pub use crate::window::{
+        Action, FrameTimer, GameLoop, InputMap, InputState, Key, PngPresenter, PresentError,
+        PresentRect, Presenter, ScaleMode, WindowBackend, WindowEvent,
     };
+    #[allow(deprecated)]
+    pub use crate::window::{FpsCounter, FrameLimiter};
+    #[cfg(feature = "sdl2-window")]
+    pub use crate::window::Window;
+    #[cfg(all(feature = "winit-window", not(target_arch = "wasm32")))]
+    pub use crate::window::WinitWindow;
 }
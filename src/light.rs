@@ -1,11 +1,14 @@
 //! Lighting types for the renderer.
 
+use crate::colors::{from_rgb_f32, to_rgb_f32, with_alpha_of};
+use crate::fog::Fog;
 use crate::prelude::Vec3;
 
 /// A directional light that illuminates the scene uniformly from a direction.
 ///
 /// Directional lights are ideal for simulating distant light sources like the sun,
 /// where all rays are effectively parallel.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DirectionalLight {
     /// The normalized direction the light is pointing (not where it comes from).
     pub direction: Vec3,
@@ -33,6 +36,166 @@ impl DirectionalLight {
         // Negate direction: light pointing at surface = positive dot product
         (-self.direction).dot(normal.normalize()).max(0.0)
     }
+
+    /// Total light level on a surface facing `normal`: ambient plus
+    /// scaled diffuse, the factor a base color is multiplied by (and then
+    /// clamped to white).
+    pub fn level(&self, normal: Vec3) -> f32 {
+        self.ambient_intensity.max(0.0) + self.intensity(normal) * self.diffuse_strength.max(0.0)
+    }
+}
+
+/// A [`DirectionalLight`] aimed by two angles, for turning it around the
+/// scene with the mouse.
+///
+/// The angles say where the light shines *from*: `azimuth` turns it about
+/// the y axis, starting from +z, and `elevation` raises it above the xz
+/// plane, so dragging the elevation up lifts a sun over the scene.
+/// Elevation stops just short of straight up or down, where the azimuth
+/// would stop meaning anything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SphericalLight {
+    light: DirectionalLight,
+    azimuth: f32,
+    elevation: f32,
+}
+
+impl SphericalLight {
+    /// Highest (and, negated, lowest) elevation in radians.
+    pub const MAX_ELEVATION: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+    /// Take over `light`, keeping its ambient intensity and diffuse
+    /// strength.
+    pub fn new(light: DirectionalLight) -> Self {
+        let from = -light.direction.normalize();
+        let mut spherical = Self {
+            light,
+            azimuth: from.x.atan2(from.z),
+            elevation: from.y.clamp(-1.0, 1.0).asin(),
+        };
+        spherical.rotate(0.0, 0.0);
+        spherical
+    }
+
+    /// Turn the light by `azimuth` and `elevation` radians.
+    pub fn rotate(&mut self, azimuth: f32, elevation: f32) {
+        self.azimuth = (self.azimuth + azimuth).rem_euclid(std::f32::consts::TAU);
+        self.elevation =
+            (self.elevation + elevation).clamp(-Self::MAX_ELEVATION, Self::MAX_ELEVATION);
+        let (sin_el, cos_el) = self.elevation.sin_cos();
+        let (sin_az, cos_az) = self.azimuth.sin_cos();
+        self.light.direction = -Vec3::new(cos_el * sin_az, sin_el, cos_el * cos_az);
+    }
+
+    /// Radians about the y axis, 0 to 2π.
+    pub fn azimuth(&self) -> f32 {
+        self.azimuth
+    }
+
+    /// Radians above the xz plane.
+    pub fn elevation(&self) -> f32 {
+        self.elevation
+    }
+
+    /// The direction the light points, normalized.
+    pub fn direction(&self) -> Vec3 {
+        self.light.direction
+    }
+
+    pub fn light(&self) -> DirectionalLight {
+        self.light
+    }
+}
+
+/// A light at a point in the scene that fades out with distance.
+///
+/// Point lights only add diffuse light: ambient comes from the
+/// [`DirectionalLight`]. They are always lit per pixel, in the forward
+/// pipeline once for every fragment that passes the depth test and in the
+/// deferred one once per visible pixel; see `Engine::set_pipeline`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+    pub position: Vec3,
+    /// Packed ARGB color of the light; alpha is ignored.
+    pub color: u32,
+    /// Multiplier for the light's diffuse contribution (default: 1.0)
+    pub intensity: f32,
+    /// Distance at which the light has faded out entirely.
+    pub range: f32,
+}
+
+impl PointLight {
+    /// A white light at `position` reaching `range` units.
+    pub fn new(position: Vec3, range: f32) -> Self {
+        PointLight {
+            position,
+            color: 0xFFFFFFFF,
+            intensity: 1.0,
+            range,
+        }
+    }
+
+    /// Diffuse light level on a surface at `position` facing `normal`:
+    /// the Lambert term scaled by intensity and by a falloff that eases
+    /// from 1.0 at the light to 0.0 at its range.
+    pub fn intensity_at(&self, position: Vec3, normal: Vec3) -> f32 {
+        let to_light = self.position - position;
+        let distance = to_light.magnitude();
+        if distance >= self.range || distance <= f32::EPSILON {
+            return 0.0;
+        }
+        let ratio = distance / self.range;
+        let falloff = (1.0 - ratio * ratio).powi(2);
+        let lambert = (to_light / distance).dot(normal.normalize()).max(0.0);
+        lambert * falloff * self.intensity.max(0.0)
+    }
+}
+
+/// The lights and fog applied to a surface after its shader has run,
+/// from the unlit color and normal the shader wrote.
+///
+/// This is what point lights and the deferred pipeline share: forward
+/// rendering evaluates it for every fragment that passes the depth test,
+/// the deferred lighting pass once per pixel from the G-buffer.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PixelLighting<'a> {
+    pub directional: DirectionalLight,
+    pub point_lights: &'a [PointLight],
+    pub fog: Option<Fog>,
+}
+
+impl PixelLighting<'_> {
+    /// Light `albedo` on a surface at world `position` and view depth
+    /// `depth` facing `normal`, then fog it. A zero normal leaves the
+    /// color unlit, as `ShadingMode::None` and replaced textures are.
+    #[inline]
+    pub(crate) fn shade(&self, albedo: u32, normal: Vec3, position: Vec3, depth: f32) -> u32 {
+        let color = if normal.magnitude() > 0.0 {
+            // Ambient plus every diffuse term per channel, saturating at
+            // white like the vertex lighting
+            let mut level = [self.directional.level(normal); 3];
+            for light in self.point_lights {
+                let diffuse = light.intensity_at(position, normal);
+                if diffuse > 0.0 {
+                    let tint = to_rgb_f32(light.color);
+                    for c in 0..3 {
+                        level[c] += diffuse * tint[c];
+                    }
+                }
+            }
+            let base = to_rgb_f32(albedo);
+            with_alpha_of(
+                from_rgb_f32(std::array::from_fn(|c| (base[c] * level[c]).min(1.0))),
+                albedo,
+            )
+        } else {
+            albedo
+        };
+        match self.fog {
+            Some(fog) => with_alpha_of(fog.apply(color, depth), color),
+            None => color,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -64,4 +227,79 @@ mod tests {
         let intensity = light.intensity(normal);
         assert!((intensity - 0.707).abs() < 0.01);
     }
+
+    #[test]
+    fn level_adds_ambient_to_scaled_diffuse() {
+        let mut light = DirectionalLight::new(Vec3::new(0.0, 0.0, -1.0));
+        light.diffuse_strength = 0.5;
+        assert!((light.level(Vec3::new(0.0, 0.0, 1.0)) - 0.6).abs() < 1e-6);
+        // Facing away, only the ambient term is left
+        assert!((light.level(Vec3::new(0.0, 0.0, -1.0)) - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn spherical_light_keeps_the_direction_it_starts_with() {
+        let mut light = DirectionalLight::new(Vec3::new(-1.0, -1.0, 1.0));
+        light.ambient_intensity = 0.3;
+        let spherical = SphericalLight::new(light);
+        assert!((spherical.direction() - light.direction).magnitude() < 1e-5);
+        assert_eq!(spherical.light().ambient_intensity, 0.3);
+        // Shining from above, so raised
+        assert!(spherical.elevation() > 0.0);
+    }
+
+    #[test]
+    fn spherical_light_turns_and_stops_short_of_the_poles() {
+        // From +z, pointing at the scene down -z
+        let mut light = SphericalLight::new(DirectionalLight::new(Vec3::new(0.0, 0.0, -1.0)));
+        light.rotate(std::f32::consts::FRAC_PI_2, 0.0);
+        assert!((light.direction() - Vec3::new(-1.0, 0.0, 0.0)).magnitude() < 1e-5);
+
+        light.rotate(0.0, 10.0);
+        assert_eq!(light.elevation(), SphericalLight::MAX_ELEVATION);
+        assert!(light.direction().y < -0.99);
+        assert!((light.direction().magnitude() - 1.0).abs() < 1e-5);
+        // Still turns about y up there
+        let before = light.direction();
+        light.rotate(1.0, 0.0);
+        assert!((light.direction() - before).magnitude() > 1e-4);
+    }
+
+    #[test]
+    fn point_light_fades_out_at_its_range() {
+        let light = PointLight::new(Vec3::new(0.0, 0.0, -1.0), 4.0);
+        let facing = Vec3::new(0.0, 0.0, -1.0);
+        let near = light.intensity_at(Vec3::ZERO, facing);
+        let far = light.intensity_at(Vec3::new(0.0, 0.0, 2.0), facing);
+        // (1 - (1/4)^2)^2 and (1 - (3/4)^2)^2
+        assert!((near - 0.8789).abs() < 1e-3, "{near}");
+        assert!((far - 0.1914).abs() < 1e-3, "{far}");
+        assert_eq!(light.intensity_at(Vec3::new(0.0, 0.0, 3.0), facing), 0.0);
+        // Facing away from it
+        assert_eq!(light.intensity_at(Vec3::ZERO, -facing), 0.0);
+    }
+
+    #[test]
+    fn pixel_lighting_adds_point_lights_per_channel() {
+        let mut directional = DirectionalLight::new(Vec3::new(0.0, 0.0, 1.0));
+        directional.diffuse_strength = 0.0;
+        let red = PointLight {
+            color: 0xFFFF0000,
+            ..PointLight::new(Vec3::new(0.0, 0.0, -1.0), 1e6)
+        };
+        let lighting = PixelLighting {
+            directional,
+            point_lights: &[red],
+            fog: None,
+        };
+        let normal = Vec3::new(0.0, 0.0, -1.0);
+        // Ambient 0.1 on every channel, plus the light on red
+        let lit = lighting.shade(0xFF808080, normal, Vec3::ZERO, 1.0);
+        assert_eq!(lit, 0xFF8D0D0D);
+        // No normal, no lighting
+        assert_eq!(
+            lighting.shade(0xFF808080, Vec3::ZERO, Vec3::ZERO, 1.0),
+            0xFF808080
+        );
+    }
 }
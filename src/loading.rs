@@ -0,0 +1,195 @@
+//! Loading models and textures on background threads.
+//!
+//! [`Engine::load_model_async`] and [`Engine::load_texture_async`] start
+//! reading a file on a thread of its own and return a [`LoadHandle`] right
+//! away, so a large OBJ or texture doesn't stall the window. Nothing in the
+//! engine changes until [`Engine::poll_loaded`], called once a frame,
+//! installs whatever has finished; [`Engine::load_status`] tells how each
+//! load went.
+//!
+//! [`Engine::load_model_async`]: crate::engine::Engine::load_model_async
+//! [`Engine::load_texture_async`]: crate::engine::Engine::load_texture_async
+//! [`Engine::poll_loaded`]: crate::engine::Engine::poll_loaded
+//! [`Engine::load_status`]: crate::engine::Engine::load_status
+
+use std::collections::HashMap;
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use crate::error::Error;
+use crate::model::Model;
+use crate::texture::Texture;
+
+/// Identifies one load started on the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadHandle(u64);
+
+/// Where a load stands.
+#[derive(Debug)]
+pub enum LoadStatus {
+    /// Still being read on its thread, or read but not yet installed by
+    /// [`Engine::poll_loaded`](crate::engine::Engine::poll_loaded).
+    Loading,
+    /// Installed in the engine.
+    Done,
+    /// The file couldn't be loaded, or what it was for is gone; nothing
+    /// was installed.
+    Failed(Error),
+}
+
+impl LoadStatus {
+    /// Whether the load has finished, either way.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, LoadStatus::Loading)
+    }
+}
+
+/// What a finished load installs, and where.
+pub(crate) enum Loaded {
+    Model {
+        name: String,
+        model: Box<Model>,
+    },
+    /// `model` names the model the texture is for; `None` for the engine's
+    /// global texture.
+    Texture {
+        model: Option<String>,
+        texture: Texture,
+    },
+}
+
+struct PendingLoad {
+    handle: LoadHandle,
+    worker: JoinHandle<Result<Loaded, Error>>,
+}
+
+/// The loads still running, and the status of every load started.
+pub(crate) struct Loader {
+    next_id: u64,
+    pending: Vec<PendingLoad>,
+    statuses: HashMap<LoadHandle, LoadStatus>,
+    // When the current run of loads began, for the HUD's spinner
+    busy_since: Instant,
+}
+
+impl Loader {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 0,
+            pending: Vec::new(),
+            statuses: HashMap::new(),
+            busy_since: Instant::now(),
+        }
+    }
+
+    /// Run `load` on a thread of its own.
+    pub(crate) fn start(
+        &mut self,
+        load: impl FnOnce() -> Result<Loaded, Error> + Send + 'static,
+    ) -> LoadHandle {
+        let handle = LoadHandle(self.next_id);
+        self.next_id += 1;
+        if self.pending.is_empty() {
+            self.busy_since = Instant::now();
+        }
+        let worker = std::thread::Builder::new()
+            .name("asset-loader".to_string())
+            .spawn(load)
+            .expect("failed to start a loader thread");
+        self.pending.push(PendingLoad { handle, worker });
+        self.statuses.insert(handle, LoadStatus::Loading);
+        handle
+    }
+
+    /// Take the loads whose threads have finished, oldest first. Their
+    /// status stays [`LoadStatus::Loading`] until [`Loader::finish`].
+    ///
+    /// A loader thread that panicked panics here too, as the same load
+    /// would have on this thread.
+    pub(crate) fn take_finished(&mut self) -> Vec<(LoadHandle, Result<Loaded, Error>)> {
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.pending.len() {
+            if self.pending[i].worker.is_finished() {
+                let PendingLoad { handle, worker } = self.pending.remove(i);
+                match worker.join() {
+                    Ok(result) => finished.push((handle, result)),
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            } else {
+                i += 1;
+            }
+        }
+        finished
+    }
+
+    /// Record how installing a finished load went.
+    pub(crate) fn finish(&mut self, handle: LoadHandle, result: Result<(), Error>) {
+        let status = match result {
+            Ok(()) => LoadStatus::Done,
+            Err(e) => LoadStatus::Failed(e),
+        };
+        self.statuses.insert(handle, status);
+    }
+
+    pub(crate) fn status(&self, handle: LoadHandle) -> Option<&LoadStatus> {
+        self.statuses.get(&handle)
+    }
+
+    /// How many loads haven't been installed yet.
+    pub(crate) fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// A spinner character that turns while loads are pending.
+    pub(crate) fn spinner(&self) -> char {
+        const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+        FRAMES[(self.busy_since.elapsed().as_millis() / 150) as usize % FRAMES.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_for(loader: &mut Loader) -> Vec<(LoadHandle, Result<Loaded, Error>)> {
+        let mut finished = Vec::new();
+        while loader.pending() > 0 {
+            finished.extend(loader.take_finished());
+            std::thread::yield_now();
+        }
+        finished
+    }
+
+    #[test]
+    fn finished_loads_are_taken_once() {
+        let mut loader = Loader::new();
+        let first = loader.start(|| {
+            Ok(Loaded::Model {
+                name: "a".to_string(),
+                model: Box::new(Model::new("a")),
+            })
+        });
+        let second = loader.start(|| Err(Error::InvalidArgument("nope")));
+        assert_ne!(first, second);
+        assert!(matches!(loader.status(first), Some(LoadStatus::Loading)));
+
+        let finished = wait_for(&mut loader);
+        assert!(matches!(finished[0], (h, Ok(Loaded::Model { .. })) if h == first));
+        assert!(matches!(finished[1], (h, Err(_)) if h == second));
+        assert!(loader.take_finished().is_empty());
+
+        loader.finish(first, Ok(()));
+        assert!(matches!(loader.status(first), Some(LoadStatus::Done)));
+        assert!(loader.status(first).unwrap().is_finished());
+        assert!(!loader.status(second).unwrap().is_finished());
+    }
+
+    #[test]
+    #[should_panic(expected = "bad file")]
+    fn loader_panics_reach_the_caller() {
+        let mut loader = Loader::new();
+        loader.start(|| panic!("bad file"));
+        wait_for(&mut loader);
+    }
+}
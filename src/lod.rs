@@ -0,0 +1,300 @@
+//! Level of detail: lighter stand-ins for meshes far from the camera.
+//!
+//! [`Mesh::simplify`] builds a copy of a mesh with a fraction of its faces.
+//! A [`LodGroup`] lists such copies with the camera distance each one takes
+//! over at; attached with [`Engine::set_lod_group`], [`Engine::update`]
+//! draws whichever level matches the distance from the camera to each
+//! placement's bounding-sphere center. Levels switch abruptly at their
+//! thresholds.
+//!
+//! [`Mesh::simplify`]: crate::mesh::Mesh::simplify
+//! [`Engine::set_lod_group`]: crate::engine::Engine::set_lod_group
+//! [`Engine::update`]: crate::engine::Engine::update
+
+use std::collections::{HashMap, HashSet};
+
+use crate::colors::{from_rgb_f32, to_rgb_f32};
+use crate::math::{vec2::Vec2, vec3::Vec3, vec4::Vec4};
+use crate::mesh::{BoundingAabb, Face, Mesh, Vertex};
+
+/// Finest clustering grid tried, in cells along the longest side.
+const MAX_GRID_CELLS: u32 = 1024;
+
+/// One simplified mesh in a [`LodGroup`] and where it starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LodLevel {
+    /// Camera distance in world units from which this level is drawn.
+    pub min_distance: f32,
+    pub mesh: Mesh,
+}
+
+/// Simplified versions of a mesh, each drawn beyond its own distance.
+/// Closer than every level, the full mesh is drawn.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LodGroup {
+    /// Sorted by `min_distance`
+    levels: Vec<LodLevel>,
+}
+
+impl LodGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Simplify `mesh` once per `(min_distance, face_ratio)` step; see
+    /// [`Mesh::simplify`].
+    pub fn generate(mesh: &Mesh, steps: &[(f32, f32)]) -> Self {
+        let mut group = Self::new();
+        for &(min_distance, face_ratio) in steps {
+            group.add_level(min_distance, mesh.simplify(face_ratio));
+        }
+        group
+    }
+
+    /// Draw `mesh` from `min_distance` on, until a farther level takes over.
+    /// The mesh's own transform and instances are ignored: a level is drawn
+    /// wherever the mesh it stands in for is.
+    pub fn add_level(&mut self, min_distance: f32, mesh: Mesh) -> &mut Self {
+        let at = self
+            .levels
+            .partition_point(|level| level.min_distance <= min_distance);
+        self.levels.insert(at, LodLevel { min_distance, mesh });
+        self
+    }
+
+    /// The levels, nearest first.
+    pub fn levels(&self) -> &[LodLevel] {
+        &self.levels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// The mesh to draw `distance` from the camera, or `None` for the full
+    /// mesh.
+    pub fn select(&self, distance: f32) -> Option<&Mesh> {
+        let count = self
+            .levels
+            .partition_point(|level| level.min_distance <= distance);
+        count.checked_sub(1).map(|i| &self.levels[i].mesh)
+    }
+}
+
+/// The finest vertex clustering of `vertices` and `faces` with at most
+/// `target_faces` faces; see [`cluster`]. Coarser grids can only lose
+/// faces, so when even that leaves none, the coarsest grid that keeps some
+/// is used instead. `None` if no grid keeps any.
+pub(crate) fn simplify(
+    vertices: &[Vertex],
+    faces: &[Face],
+    target_faces: usize,
+) -> Option<(Vec<Vertex>, Vec<Face>)> {
+    // Binary search the grid size; a single cell always satisfies it
+    let (mut low, mut high) = (1, MAX_GRID_CELLS);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if cluster(vertices, faces, mid).1.len() <= target_faces {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    (low..=MAX_GRID_CELLS)
+        .map(|cells| cluster(vertices, faces, cells))
+        .find(|(_, faces)| !faces.is_empty())
+}
+
+/// Running sums of the vertices falling in one grid cell.
+struct Cluster {
+    count: f32,
+    position: Vec3,
+    normal: Vec3,
+    texel: Vec2,
+    color: [f32; 3],
+    /// Index in the output, once a kept face uses the cluster
+    index: Option<u32>,
+}
+
+impl Cluster {
+    const EMPTY: Self = Self {
+        count: 0.0,
+        position: Vec3::ZERO,
+        normal: Vec3::ZERO,
+        texel: Vec2::ZERO,
+        color: [0.0; 3],
+        index: None,
+    };
+}
+
+/// Merge the vertices in each cell of a grid `cells` wide along the
+/// bounds' longest side into their average, and keep the faces whose
+/// corners land in three different cells, once each.
+///
+/// Fast and predictable, but blind to shape: features smaller than a cell
+/// vanish, and UV seams in a cell blur together.
+pub(crate) fn cluster(vertices: &[Vertex], faces: &[Face], cells: u32) -> (Vec<Vertex>, Vec<Face>) {
+    let bounds = BoundingAabb::from_vertices(vertices);
+    let extent = bounds.max - bounds.min;
+    let cell_size = extent.x.max(extent.y).max(extent.z) / cells as f32;
+    let cell_of = |p: Vec3| {
+        let axis = |offset: f32| {
+            if cell_size > 0.0 {
+                ((offset / cell_size) as u32).min(cells - 1)
+            } else {
+                0
+            }
+        };
+        let offset = p - bounds.min;
+        (axis(offset.x), axis(offset.y), axis(offset.z))
+    };
+
+    let mut cell_clusters = HashMap::new();
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let cluster_of: Vec<usize> = vertices
+        .iter()
+        .map(|vertex| {
+            let id = *cell_clusters
+                .entry(cell_of(vertex.position))
+                .or_insert_with(|| {
+                    clusters.push(Cluster::EMPTY);
+                    clusters.len() - 1
+                });
+            let cluster = &mut clusters[id];
+            let [r, g, b] = to_rgb_f32(vertex.color);
+            cluster.count += 1.0;
+            cluster.position = cluster.position + vertex.position;
+            cluster.normal = cluster.normal + vertex.normal;
+            cluster.texel = cluster.texel + vertex.texel;
+            cluster.color = [
+                cluster.color[0] + r,
+                cluster.color[1] + g,
+                cluster.color[2] + b,
+            ];
+            id
+        })
+        .collect();
+
+    let mut out_vertices = Vec::new();
+    let mut out_faces = Vec::new();
+    let mut seen = HashSet::new();
+    for face in faces {
+        let corners = [face.a, face.b, face.c].map(|i| cluster_of.get(i as usize).copied());
+        let [Some(a), Some(b), Some(c)] = corners else {
+            continue;
+        };
+        if a == b || b == c || a == c {
+            continue;
+        }
+        // Rotate so the smallest comes first; winding still tells apart
+        // the two sides of a collapsed sheet
+        let key = if a < b && a < c {
+            (a, b, c)
+        } else if b < c {
+            (b, c, a)
+        } else {
+            (c, a, b)
+        };
+        if !seen.insert(key) {
+            continue;
+        }
+
+        let [a, b, c] = [a, b, c].map(|id| {
+            let cluster = &mut clusters[id];
+            *cluster.index.get_or_insert_with(|| {
+                let n = cluster.count;
+                let [r, g, b] = cluster.color;
+                out_vertices.push(Vertex {
+                    position: cluster.position / n,
+                    normal: if cluster.normal.magnitude() > 0.0 {
+                        cluster.normal.normalize()
+                    } else {
+                        Vec3::ZERO
+                    },
+                    texel: cluster.texel / n,
+                    color: from_rgb_f32([r / n, g / n, b / n]),
+                    tangent: Vec4::ZERO,
+                });
+                out_vertices.len() as u32 - 1
+            })
+        });
+        out_faces.push(Face::new(a, b, c));
+    }
+    (out_vertices, out_faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `cells`×`cells` grid of quads in the z = 0 plane, two faces each.
+    fn grid(cells: u32) -> (Vec<Vertex>, Vec<Face>) {
+        let vertex = |i: u32, j: u32| Vertex {
+            position: Vec3::new(i as f32, j as f32, 0.0),
+            normal: Vec3::new(0.0, 0.0, -1.0),
+            texel: Vec2::new(i as f32 / cells as f32, j as f32 / cells as f32),
+            color: 0xFFFFFFFF,
+            tangent: Vec4::ZERO,
+        };
+        let vertices = (0..=cells)
+            .flat_map(|j| (0..=cells).map(move |i| vertex(i, j)))
+            .collect();
+        let index = |i: u32, j: u32| j * (cells + 1) + i;
+        let faces = (0..cells)
+            .flat_map(|j| (0..cells).map(move |i| (i, j)))
+            .flat_map(|(i, j)| {
+                [
+                    Face::new(index(i, j), index(i, j + 1), index(i + 1, j + 1)),
+                    Face::new(index(i, j), index(i + 1, j + 1), index(i + 1, j)),
+                ]
+            })
+            .collect();
+        (vertices, faces)
+    }
+
+    #[test]
+    fn clustering_merges_vertices_per_cell() {
+        let (vertices, faces) = grid(8);
+        let (merged, kept) = cluster(&vertices, &faces, 4);
+        assert!(kept.len() < faces.len() && !kept.is_empty());
+        assert!(merged.len() < vertices.len());
+        // Every merged vertex stays on the plane, with the plane's normal
+        for v in &merged {
+            assert_eq!(v.position.z, 0.0);
+            assert_eq!(v.normal, Vec3::new(0.0, 0.0, -1.0));
+        }
+        assert!(kept
+            .iter()
+            .all(|f| [f.a, f.b, f.c].iter().all(|&i| (i as usize) < merged.len())));
+    }
+
+    #[test]
+    fn simplify_meets_the_target() {
+        let (vertices, faces) = grid(32);
+        let (_, kept) = simplify(&vertices, &faces, faces.len() / 10).unwrap();
+        assert!(kept.len() <= faces.len() / 10, "{} faces", kept.len());
+        assert!(kept.len() >= faces.len() / 40, "{} faces", kept.len());
+    }
+
+    #[test]
+    fn simplify_keeps_something_for_tiny_targets() {
+        let (vertices, faces) = grid(4);
+        let (_, kept) = simplify(&vertices, &faces, 0).unwrap();
+        assert!(!kept.is_empty());
+    }
+
+    #[test]
+    fn levels_are_picked_by_distance() {
+        let (vertices, faces) = grid(4);
+        let mesh = |name: &str| Mesh::new(name.to_string(), vertices.clone(), faces.clone());
+        let mut group = LodGroup::new();
+        group
+            .add_level(40.0, mesh("far"))
+            .add_level(10.0, mesh("mid"));
+
+        assert_eq!(group.select(5.0), None);
+        assert_eq!(group.select(10.0).map(Mesh::name), Some("mid"));
+        assert_eq!(group.select(39.0).map(Mesh::name), Some("mid"));
+        assert_eq!(group.select(100.0).map(Mesh::name), Some("far"));
+    }
+}
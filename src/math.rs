@@ -1,9 +1,11 @@
 //! Mathematical primitives for 3D graphics.
 //!
-//! Provides vector and matrix types used throughout the rendering pipeline.
+//! Provides vector, matrix, and quaternion types used throughout the rendering pipeline.
 
 pub mod mat4;
 pub mod plane;
+pub mod quat;
+pub mod ray;
 pub mod utils;
 pub mod vec2;
 pub mod vec3;
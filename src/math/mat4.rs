@@ -17,7 +17,11 @@ use super::vec3::Vec3;
 use super::vec4::Vec4;
 
 /// 4x4 matrix stored as `data[row][col]` with column-major convention.
+///
+/// Laid out as 16 contiguous `f32`s, row by row, so it can be handed to
+/// code expecting a plain float array.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
 pub struct Mat4 {
     data: [[f32; 4]; 4],
 }
@@ -27,6 +31,21 @@ impl Mat4 {
         Mat4 { data }
     }
 
+    /// Build a matrix from 16 floats, row by row: `flat[row * 4 + col]`.
+    pub fn from_flat(flat: [f32; 16]) -> Self {
+        Mat4::new(std::array::from_fn(|row| {
+            std::array::from_fn(|col| flat[row * 4 + col])
+        }))
+    }
+
+    /// The 16 elements, row by row, as [`Mat4::from_flat`] takes them.
+    pub fn as_flat(&self) -> &[f32; 16] {
+        self.data
+            .as_flattened()
+            .try_into()
+            .expect("4 rows of 4 are 16 elements")
+    }
+
     pub fn identity() -> Self {
         Mat4::new([
             [1.0, 0.0, 0.0, 0.0],
@@ -94,6 +113,80 @@ impl Mat4 {
         ])
     }
 
+    /// Creates the Euler rotation `rotation_x(x) * rotation_y(y) * rotation_z(z)`,
+    /// with the product written out element by element.
+    pub fn rotation_euler(rotation: Vec3) -> Self {
+        let (sa, ca) = rotation.x.sin_cos();
+        let (sb, cb) = rotation.y.sin_cos();
+        let (sc, cc) = rotation.z.sin_cos();
+        Mat4::new([
+            [cb * cc, cb * sc, -sb, 0.0],
+            [sa * sb * cc - ca * sc, sa * sb * sc + ca * cc, sa * cb, 0.0],
+            [ca * sb * cc + sa * sc, ca * sb * sc - sa * cc, ca * cb, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates `translation(t) * rotation * scaling(s)` from a pure rotation
+    /// matrix, by scaling its columns and filling in the last column instead
+    /// of multiplying full matrices.
+    pub fn compose_trs(translation: Vec3, rotation: &Mat4, scale: Vec3) -> Self {
+        let r = &rotation.data;
+        Mat4::new([
+            [
+                r[0][0] * scale.x,
+                r[0][1] * scale.y,
+                r[0][2] * scale.z,
+                translation.x,
+            ],
+            [
+                r[1][0] * scale.x,
+                r[1][1] * scale.y,
+                r[1][2] * scale.z,
+                translation.y,
+            ],
+            [
+                r[2][0] * scale.x,
+                r[2][1] * scale.y,
+                r[2][2] * scale.z,
+                translation.z,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates the model matrix
+    /// `translation * rotation_x * rotation_y * rotation_z * scaling`
+    /// (Euler angles in radians) without intermediate 4x4 multiplies.
+    pub fn from_trs(translation: Vec3, rotation: Vec3, scale: Vec3) -> Self {
+        Mat4::compose_trs(translation, &Mat4::rotation_euler(rotation), scale)
+    }
+
+    /// Creates `rotation_x * rotation_y * rotation_z * scaling`: the model
+    /// matrix without its translation.
+    pub fn from_rs(rotation: Vec3, scale: Vec3) -> Self {
+        Mat4::from_trs(Vec3::ZERO, rotation, scale)
+    }
+
+    /// Inverse transpose of [`Mat4::from_rs`], the normal matrix.
+    ///
+    /// `(R * S)⁻¹ = S⁻¹ * Rᵀ` since R is orthonormal, so the inverse
+    /// transpose is just `R * S⁻¹` — no general inverse needed. Returns the
+    /// identity for a zero scale, which has no inverse.
+    pub fn inverse_transpose_rs(rotation: Vec3, scale: Vec3) -> Self {
+        Mat4::inverse_transpose_of_rotation_scale(&Mat4::rotation_euler(rotation), scale)
+    }
+
+    /// [`Mat4::inverse_transpose_rs`] for a pure rotation matrix, such as a
+    /// [`Quat`](super::quat::Quat)'s.
+    pub fn inverse_transpose_of_rotation_scale(rotation: &Mat4, scale: Vec3) -> Self {
+        if scale.x == 0.0 || scale.y == 0.0 || scale.z == 0.0 {
+            return Mat4::identity();
+        }
+        let inverse_scale = Vec3::new(1.0 / scale.x, 1.0 / scale.y, 1.0 / scale.z);
+        Mat4::compose_trs(Vec3::ZERO, rotation, inverse_scale)
+    }
+
     /// Creates a perspective matrix with left-handed coordinate system.
     ///
     /// Maps view-space z to clip-space z with [-1, 1] NDC depth range:
@@ -115,6 +208,28 @@ impl Mat4 {
         ])
     }
 
+    /// Creates a perspective matrix with right-handed coordinate system.
+    ///
+    /// The camera looks down view-space -z (OpenGL / glTF convention). Maps
+    /// to the same [-1, 1] NDC depth range as [`Mat4::perspective_lh`]:
+    /// - z = -near → z_ndc = -1
+    /// - z = -far → z_ndc = +1
+    ///
+    /// The w component receives the distance in front of the camera, -z.
+    /// Equivalent to `perspective_lh` with the z axis mirrored.
+    pub fn perspective_rh(fov: f32, aspect_ratio: f32, near: f32, far: f32) -> Self {
+        let t = near * (fov / 2.0).tan();
+        let r = t * aspect_ratio;
+        let a = (far + near) / (far - near);
+        let b = -2.0 * far * near / (far - near);
+        Mat4::new([
+            [near / r, 0.0, 0.0, 0.0],
+            [0.0, near / t, 0.0, 0.0],
+            [0.0, 0.0, -a, b],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
     /// Creates a view matrix with left-handed coordinate system.
     ///
     /// # Arguments
@@ -268,6 +383,25 @@ impl Mat4 {
     }
 
     /// Access element at [row][col].
+    /// Transform every point in `src` (w = 1), replacing the contents of
+    /// `dst` with the results, without dividing by w.
+    ///
+    /// Gives the same values as `self * Vec4::from_vec3(p, 1.0)` for each
+    /// point, in a loop the compiler can vectorize.
+    pub fn transform_points_batch(&self, src: &[Vec3], dst: &mut Vec<Vec4>) {
+        let [r0, r1, r2, r3] = self.data;
+        dst.clear();
+        dst.reserve(src.len());
+        dst.extend(src.iter().map(|p| {
+            Vec4::new(
+                r0[0] * p.x + r0[1] * p.y + r0[2] * p.z + r0[3],
+                r1[0] * p.x + r1[1] * p.y + r1[2] * p.z + r1[3],
+                r2[0] * p.x + r2[1] * p.y + r2[2] * p.z + r2[3],
+                r3[0] * p.x + r3[1] * p.y + r3[2] * p.z + r3[3],
+            )
+        }));
+    }
+
     #[inline]
     pub fn get(&self, row: usize, col: usize) -> f32 {
         self.data[row][col]
@@ -289,9 +423,9 @@ impl Mul<Mat4> for Mat4 {
     fn mul(self, rhs: Mat4) -> Self::Output {
         let mut result = [[0.0f32; 4]; 4];
 
-        for row in 0..4 {
-            for col in 0..4 {
-                result[row][col] = self.data[row][0] * rhs.data[0][col]
+        for (row, out_row) in result.iter_mut().enumerate() {
+            for (col, out) in out_row.iter_mut().enumerate() {
+                *out = self.data[row][0] * rhs.data[0][col]
                     + self.data[row][1] * rhs.data[1][col]
                     + self.data[row][2] * rhs.data[2][col]
                     + self.data[row][3] * rhs.data[3][col];
@@ -351,3 +485,81 @@ impl Mul<Vec3> for Mat4 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const ROTATION: Vec3 = Vec3::new(0.4, -1.2, 2.5);
+    const SCALE: Vec3 = Vec3::new(2.0, 0.5, -3.0);
+    const TRANSLATION: Vec3 = Vec3::new(1.0, -2.0, 7.5);
+
+    fn assert_mat_eq(a: Mat4, b: Mat4) {
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_relative_eq!(a.get(row, col), b.get(row, col), epsilon = 1e-5);
+            }
+        }
+    }
+
+    fn naive_rs(rotation: Vec3, scale: Vec3) -> Mat4 {
+        Mat4::rotation_x(rotation.x)
+            * Mat4::rotation_y(rotation.y)
+            * Mat4::rotation_z(rotation.z)
+            * Mat4::scaling(scale.x, scale.y, scale.z)
+    }
+
+    #[test]
+    fn rotation_euler_matches_product() {
+        let naive = Mat4::rotation_x(ROTATION.x)
+            * Mat4::rotation_y(ROTATION.y)
+            * Mat4::rotation_z(ROTATION.z);
+        assert_mat_eq(Mat4::rotation_euler(ROTATION), naive);
+    }
+
+    #[test]
+    fn from_trs_matches_naive_composition() {
+        let naive = Mat4::translation(TRANSLATION.x, TRANSLATION.y, TRANSLATION.z)
+            * naive_rs(ROTATION, SCALE);
+        assert_mat_eq(Mat4::from_trs(TRANSLATION, ROTATION, SCALE), naive);
+        assert_mat_eq(Mat4::from_rs(ROTATION, SCALE), naive_rs(ROTATION, SCALE));
+    }
+
+    #[test]
+    fn inverse_transpose_rs_matches_general_inverse() {
+        let naive = naive_rs(ROTATION, SCALE).inverse().unwrap().transpose();
+        assert_mat_eq(Mat4::inverse_transpose_rs(ROTATION, SCALE), naive);
+    }
+
+    #[test]
+    fn flat_round_trips_row_by_row() {
+        let flat: [f32; 16] = std::array::from_fn(|i| i as f32);
+        let m = Mat4::from_flat(flat);
+        assert_eq!(m.get(1, 3), 7.0);
+        assert_eq!(m.as_flat(), &flat);
+        assert_eq!(std::mem::size_of::<Mat4>(), 16 * 4);
+    }
+
+    #[test]
+    fn batch_matches_one_point_at_a_time() {
+        let m = Mat4::perspective_lh(1.0, 1.5, 0.1, 100.0)
+            * Mat4::from_trs(TRANSLATION, ROTATION, SCALE);
+        let points: Vec<Vec3> = (0..37)
+            .map(|i| Vec3::new(i as f32 * 0.3 - 5.0, (i as f32).sin() * 4.0, i as f32 * 0.1))
+            .collect();
+        let mut out = vec![Vec4::ONE; 3];
+        m.transform_points_batch(&points, &mut out);
+        assert_eq!(out.len(), points.len());
+        for (&p, &q) in points.iter().zip(&out) {
+            assert_eq!(q, m * Vec4::from_vec3(p, 1.0));
+            assert_eq!(q.to_vec3_perspective(), m * p);
+        }
+    }
+
+    #[test]
+    fn inverse_transpose_rs_of_zero_scale_is_identity() {
+        let flat = Vec3::new(1.0, 0.0, 1.0);
+        assert_eq!(Mat4::inverse_transpose_rs(ROTATION, flat), Mat4::identity());
+    }
+}
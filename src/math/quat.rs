@@ -0,0 +1,353 @@
+//! Unit quaternion for rotations.
+//!
+//! # Convention
+//! - Angles turn the same way as [`Mat4::rotation_x`], [`Mat4::rotation_y`]
+//!   and [`Mat4::rotation_z`], so `Quat::from_axis_angle(Vec3::RIGHT, a)`
+//!   and `Mat4::rotation_x(a)` are the same rotation
+//! - Products chain **right-to-left** like matrices: `(a * b).to_mat4()`
+//!   equals `a.to_mat4() * b.to_mat4()`, applying `b` first
+//!
+//! # Example
+//! ```ignore
+//! let from = Quat::from_euler(0.0, 0.0, 0.0);
+//! let to = Quat::from_axis_angle(Vec3::UP, std::f32::consts::PI * 0.75);
+//! transform.set_rotation_quat(from.slerp(to, 0.5));
+//! ```
+
+use std::ops::Mul;
+
+use super::mat4::Mat4;
+use super::vec3::Vec3;
+
+/// Rotation stored as `x*i + y*j + z*k + w`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quat {
+    /// No rotation.
+    pub const IDENTITY: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Rotation by `angle` radians around `axis`, which need not be
+    /// normalized. A zero axis gives the identity.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let length = axis.magnitude();
+        if length == 0.0 {
+            return Self::IDENTITY;
+        }
+        // Mat4's rotations turn by -angle in the right-hand sense
+        let (s, c) = (-0.5 * angle).sin_cos();
+        let axis = axis * (s / length);
+        Self::new(axis.x, axis.y, axis.z, c)
+    }
+
+    /// Rotation matching Euler angles in radians as [`Transform`] applies
+    /// them: `RotationX * RotationY * RotationZ`.
+    ///
+    /// [`Transform`]: crate::transform::Transform
+    pub fn from_euler(x: f32, y: f32, z: f32) -> Self {
+        Self::from_axis_angle(Vec3::RIGHT, x)
+            * Self::from_axis_angle(Vec3::UP, y)
+            * Self::from_axis_angle(Vec3::FORWARD, z)
+    }
+
+    /// The rotation held in the upper 3x3 of `m`, which must be a pure
+    /// rotation (orthonormal, determinant +1). Inverse of [`Quat::to_mat4`].
+    pub fn from_rotation_matrix(m: &Mat4) -> Self {
+        let (m00, m11, m22) = (m.get(0, 0), m.get(1, 1), m.get(2, 2));
+        let trace = m00 + m11 + m22;
+        // Divide by the largest of w, x, y, z to stay well conditioned
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0; // 4w
+            Self::new(
+                (m.get(2, 1) - m.get(1, 2)) / s,
+                (m.get(0, 2) - m.get(2, 0)) / s,
+                (m.get(1, 0) - m.get(0, 1)) / s,
+                0.25 * s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0; // 4x
+            Self::new(
+                0.25 * s,
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                (m.get(0, 2) + m.get(2, 0)) / s,
+                (m.get(2, 1) - m.get(1, 2)) / s,
+            )
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0; // 4y
+            Self::new(
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                0.25 * s,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+                (m.get(0, 2) - m.get(2, 0)) / s,
+            )
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0; // 4z
+            Self::new(
+                (m.get(0, 2) + m.get(2, 0)) / s,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+                0.25 * s,
+                (m.get(1, 0) - m.get(0, 1)) / s,
+            )
+        };
+        q.normalize()
+    }
+
+    /// Euler angles (x, y, z) in radians that [`Quat::from_euler`] maps back
+    /// to this rotation. The y angle is kept within ±π/2; at exactly ±π/2
+    /// the z angle is folded into x.
+    pub fn to_euler(self) -> Vec3 {
+        let m = self.to_mat4();
+        // Rx * Ry * Rz has row 0 = (cos y cos z, cos y sin z, -sin y). atan2
+        // keeps y accurate near ±π/2, where asin(-m02) loses precision.
+        let sin_y = -m.get(0, 2);
+        let cos_y = m.get(0, 0).hypot(m.get(0, 1));
+        let y = sin_y.atan2(cos_y);
+        if cos_y > 1e-5 {
+            let x = m.get(1, 2).atan2(m.get(2, 2));
+            let z = m.get(0, 1).atan2(m.get(0, 0));
+            Vec3::new(x, y, z)
+        } else {
+            // Gimbal lock: only x ± z is determined; take z = 0
+            let x = (m.get(1, 0) * sin_y.signum()).atan2(m.get(1, 1));
+            Vec3::new(x, y, 0.0)
+        }
+    }
+
+    pub fn dot(&self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn magnitude(&self) -> f32 {
+        self.dot(*self).sqrt()
+    }
+
+    /// Scale to unit length. A zero quaternion gives the identity.
+    pub fn normalize(&self) -> Self {
+        let mag = self.magnitude();
+        if mag == 0.0 {
+            return Self::IDENTITY;
+        }
+        Self::new(self.x / mag, self.y / mag, self.z / mag, self.w / mag)
+    }
+
+    /// The inverse rotation, for a unit quaternion.
+    pub fn conjugate(&self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Rotate a vector.
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        let p = Self::new(v.x, v.y, v.z, 0.0);
+        let r = *self * p * self.conjugate();
+        Vec3::new(r.x, r.y, r.z)
+    }
+
+    /// Rotation matrix for a unit quaternion.
+    pub fn to_mat4(&self) -> Mat4 {
+        let Self { x, y, z, w } = *self;
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (w * x, w * y, w * z);
+        Mat4::new([
+            [1.0 - 2.0 * (yy + zz), 2.0 * (xy - wz), 2.0 * (xz + wy), 0.0],
+            [2.0 * (xy + wz), 1.0 - 2.0 * (xx + zz), 2.0 * (yz - wx), 0.0],
+            [2.0 * (xz - wy), 2.0 * (yz + wx), 1.0 - 2.0 * (xx + yy), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Spherical interpolation at constant angular speed from `self`
+    /// (t = 0) to `other` (t = 1), along the shorter of the two arcs.
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        // q and -q are the same rotation; pick the one on this side so the
+        // path never goes the long way round
+        let mut cos_theta = self.dot(other);
+        let other = if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            Self::new(-other.x, -other.y, -other.z, -other.w)
+        } else {
+            other
+        };
+
+        let (a, b) = if cos_theta > 0.9995 {
+            // Nearly parallel: sin(theta) underflows, and a lerp is exact enough
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+        Self::new(
+            self.x * a + other.x * b,
+            self.y * a + other.y * b,
+            self.z * a + other.z * b,
+            self.w * a + other.w * b,
+        )
+        .normalize()
+    }
+}
+
+/// Hamilton product: `a * b` applies `b`, then `a`.
+impl Mul for Quat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_mat_eq(a: Mat4, b: Mat4) {
+        for row in 0..4 {
+            for col in 0..4 {
+                assert_relative_eq!(a.get(row, col), b.get(row, col), epsilon = 1e-5);
+            }
+        }
+    }
+
+    fn assert_vec_eq(a: Vec3, b: Vec3) {
+        assert_relative_eq!(a.x, b.x, epsilon = 1e-5);
+        assert_relative_eq!(a.y, b.y, epsilon = 1e-5);
+        assert_relative_eq!(a.z, b.z, epsilon = 1e-5);
+    }
+
+    /// Angle between two rotations, in radians.
+    fn angle_between(a: Quat, b: Quat) -> f32 {
+        2.0 * a.dot(b).abs().min(1.0).acos()
+    }
+
+    #[test]
+    fn axis_angle_matches_mat4_rotations() {
+        let angle = 0.7;
+        assert_mat_eq(
+            Quat::from_axis_angle(Vec3::RIGHT, angle).to_mat4(),
+            Mat4::rotation_x(angle),
+        );
+        assert_mat_eq(
+            Quat::from_axis_angle(Vec3::UP, angle).to_mat4(),
+            Mat4::rotation_y(angle),
+        );
+        assert_mat_eq(
+            Quat::from_axis_angle(Vec3::FORWARD, angle).to_mat4(),
+            Mat4::rotation_z(angle),
+        );
+    }
+
+    #[test]
+    fn quarter_turn_about_y_takes_x_to_z() {
+        // Positive yaw turns +X toward +Z, as Mat4::rotation_y does
+        let q = Quat::from_axis_angle(Vec3::UP, FRAC_PI_2);
+        assert_vec_eq(q.rotate(Vec3::RIGHT), Vec3::FORWARD);
+        assert_vec_eq(q.to_mat4() * Vec3::RIGHT, Vec3::FORWARD);
+    }
+
+    #[test]
+    fn product_composes_like_matrices() {
+        let a = Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 0.4);
+        let b = Quat::from_axis_angle(Vec3::new(-2.0, 0.5, 1.0), 1.3);
+        assert_mat_eq((a * b).to_mat4(), a.to_mat4() * b.to_mat4());
+    }
+
+    #[test]
+    fn from_euler_matches_transform_order() {
+        let (x, y, z) = (0.3, -1.1, 2.0);
+        let expected = Mat4::rotation_x(x) * Mat4::rotation_y(y) * Mat4::rotation_z(z);
+        assert_mat_eq(Quat::from_euler(x, y, z).to_mat4(), expected);
+    }
+
+    #[test]
+    fn to_euler_round_trips() {
+        for euler in [
+            Vec3::new(0.3, -1.1, 2.0),
+            Vec3::new(-2.5, 0.2, -0.4),
+            Vec3::ZERO,
+        ] {
+            let back = Quat::from_euler(euler.x, euler.y, euler.z).to_euler();
+            assert_vec_eq(back, euler);
+        }
+
+        // At gimbal lock the angles differ but the rotation must not
+        let q = Quat::from_euler(0.4, FRAC_PI_2, 0.3);
+        let e = q.to_euler();
+        assert_mat_eq(Quat::from_euler(e.x, e.y, e.z).to_mat4(), q.to_mat4());
+    }
+
+    #[test]
+    fn from_rotation_matrix_round_trips() {
+        // One case per branch: large w, then x, y, z dominant
+        for q in [
+            Quat::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), 0.4),
+            Quat::from_axis_angle(Vec3::RIGHT, 3.0),
+            Quat::from_axis_angle(Vec3::UP, 3.0),
+            Quat::from_axis_angle(Vec3::new(0.1, -0.2, 1.0), 3.0),
+        ] {
+            let back = Quat::from_rotation_matrix(&q.to_mat4());
+            assert_relative_eq!(back.dot(q).abs(), 1.0, epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn normalize_gives_unit_length() {
+        let q = Quat::new(1.0, 2.0, 3.0, 4.0).normalize();
+        assert_relative_eq!(q.magnitude(), 1.0, epsilon = 1e-6);
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).normalize(), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn slerp_hits_endpoints_and_midpoint() {
+        let a = Quat::IDENTITY;
+        let b = Quat::from_axis_angle(Vec3::UP, FRAC_PI_2);
+        assert_mat_eq(a.slerp(b, 0.0).to_mat4(), a.to_mat4());
+        assert_mat_eq(a.slerp(b, 1.0).to_mat4(), b.to_mat4());
+        assert_mat_eq(
+            a.slerp(b, 0.5).to_mat4(),
+            Quat::from_axis_angle(Vec3::UP, FRAC_PI_2 / 2.0).to_mat4(),
+        );
+    }
+
+    #[test]
+    fn slerp_takes_the_short_way_at_constant_speed() {
+        let a = Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0), 0.5);
+        // 1.9π one way is 0.1π the other, and the quaternion lands in the
+        // opposite hemisphere
+        let b = a * Quat::from_axis_angle(Vec3::UP, 1.9 * PI);
+        assert!(a.dot(b) < 0.0);
+        let total = angle_between(a, b);
+        assert_relative_eq!(total, 0.1 * PI, epsilon = 1e-4);
+
+        const STEPS: usize = 20;
+        for i in 0..=STEPS {
+            let t = i as f32 / STEPS as f32;
+            let angle = angle_between(a, a.slerp(b, t));
+            assert_relative_eq!(angle, total * t, epsilon = 1e-3);
+        }
+    }
+}
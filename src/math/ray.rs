@@ -0,0 +1,131 @@
+//! Ray primitive and ray/triangle intersection.
+//!
+//! Used by `Engine::raycast` for geometric picking. Triangles are treated as
+//! two-sided — a ray hits regardless of winding.
+
+use super::vec3::Vec3;
+
+/// A half-line starting at `origin` and extending along `direction`.
+///
+/// `direction` is kept normalized by [`Ray::new`], so the `t` returned from
+/// intersection tests is a true world-space distance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+/// Result of a ray/triangle intersection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleHit {
+    /// Distance along the ray to the hit point.
+    pub t: f32,
+    /// Barycentric weights `[w0, w1, w2]` of the hit point for vertices `a, b, c`.
+    pub barycentric: [f32; 3],
+}
+
+impl Ray {
+    /// Creates a ray, normalizing `direction`.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Returns the point `t` units along the ray.
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// Möller–Trumbore ray/triangle intersection.
+    ///
+    /// Returns the hit distance and barycentrics, or None if the ray misses,
+    /// runs parallel to the triangle, or the triangle lies behind the origin.
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<TriangleHit> {
+        const EPSILON: f32 = 1e-7;
+
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+
+        // Ray parallel to the triangle plane (or degenerate triangle)
+        if det.abs() < EPSILON {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(q) * inv_det;
+        if t <= EPSILON {
+            return None;
+        }
+
+        Some(TriangleHit {
+            t,
+            barycentric: [1.0 - u - v, u, v],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn unit_triangle() -> (Vec3, Vec3, Vec3) {
+        (
+            Vec3::new(-1.0, -1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn hits_triangle_in_front() {
+        let (a, b, c) = unit_triangle();
+        let ray = Ray::new(Vec3::new(0.0, 0.0, -3.0), Vec3::new(0.0, 0.0, 2.0));
+        let hit = ray.intersect_triangle(a, b, c).expect("hit");
+
+        assert_relative_eq!(hit.t, 3.0, epsilon = 1e-5);
+        let sum: f32 = hit.barycentric.iter().sum();
+        assert_relative_eq!(sum, 1.0, epsilon = 1e-5);
+        assert_relative_eq!(ray.at(hit.t).z, 0.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn barycentrics_reconstruct_hit_point() {
+        let (a, b, c) = unit_triangle();
+        let ray = Ray::new(Vec3::new(0.2, -0.3, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let hit = ray.intersect_triangle(a, b, c).expect("hit");
+
+        let [w0, w1, w2] = hit.barycentric;
+        let p = a * w0 + b * w1 + c * w2;
+        assert_relative_eq!(p.x, 0.2, epsilon = 1e-5);
+        assert_relative_eq!(p.y, -0.3, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn misses_outside_behind_and_parallel() {
+        let (a, b, c) = unit_triangle();
+        let outside = Ray::new(Vec3::new(2.0, 0.0, -3.0), Vec3::new(0.0, 0.0, 1.0));
+        let behind = Ray::new(Vec3::new(0.0, 0.0, -3.0), Vec3::new(0.0, 0.0, -1.0));
+        let parallel = Ray::new(Vec3::new(0.0, 0.0, -3.0), Vec3::new(1.0, 0.0, 0.0));
+
+        assert_eq!(outside.intersect_triangle(a, b, c), None);
+        assert_eq!(behind.intersect_triangle(a, b, c), None);
+        assert_eq!(parallel.intersect_triangle(a, b, c), None);
+    }
+}
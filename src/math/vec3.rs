@@ -4,6 +4,7 @@ use std::{
 };
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
 pub struct Vec3 {
     pub x: f32,
     pub y: f32,
@@ -126,6 +127,11 @@ impl Vec3 {
         }
     }
 
+    /// Reflect this direction about `normal`, which must be unit length.
+    pub fn reflect(&self, normal: Self) -> Self {
+        *self - normal * (2.0 * self.dot(normal))
+    }
+
     pub fn dot(&self, other: Self) -> f32 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
@@ -224,3 +230,9 @@ impl Sum for Vec3 {
         iter.fold(Vec3::ZERO, |acc, v| acc + v)
     }
 }
+
+impl From<[f32; 3]> for Vec3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
@@ -1,11 +1,24 @@
 //! 3D mesh representation and loading.
 //!
 //! Provides the [`Mesh`] struct for storing vertices, normals, and faces, along with
-//! OBJ file loading support via the `tobj` crate.
+//! OBJ file loading support via the `tobj` crate and STL loading.
 
+use std::f32::consts::{PI, TAU};
 use std::fmt;
 
-use crate::{math::vec3::Vec3, prelude::Vec2, transform::Transform};
+use crate::animation::Animator;
+use crate::colors::pack_color;
+use crate::engine::RenderMode;
+use crate::lod::{self, LodGroup};
+use crate::stl_loader;
+use crate::subdivide;
+use crate::{
+    error::Error,
+    math::{mat4::Mat4, vec3::Vec3, vec4::Vec4},
+    prelude::Vec2,
+    texture::{TextureHandle, TextureRegion},
+    transform::Transform,
+};
 use std::cell::Cell;
 
 /// Represents a triangle face with indices into the vertex array.
@@ -26,18 +39,41 @@ impl Face {
 #[derive(Debug)]
 pub enum LoadError {
     Tobj(tobj::LoadError),
+    Io(std::io::Error),
+    #[cfg(feature = "gltf")]
+    Gltf(gltf::Error),
     NoModels,
     NoVertices,
-    InvalidFaces,
+    /// Face `face` (counting from 0 in file order) is incomplete or refers
+    /// to a vertex, texture coordinate or normal the file doesn't define
+    InvalidFaces {
+        face: usize,
+    },
+    /// Not an STL file: too short for a binary header, and not ASCII
+    MalformedHeader,
+    /// The file ends partway through its facets
+    Truncated,
+    /// Unexpected keyword or number in an ASCII STL
+    InvalidStl {
+        line: usize,
+    },
 }
 
 impl fmt::Display for LoadError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LoadError::Tobj(e) => write!(f, "failed to load OBJ: {}", e),
-            LoadError::NoModels => write!(f, "OBJ file contains no models"),
+            #[cfg(feature = "gltf")]
+            LoadError::Gltf(e) => write!(f, "failed to load glTF: {}", e),
+            LoadError::NoModels => write!(f, "file contains no models"),
             LoadError::NoVertices => write!(f, "mesh has no vertices"),
-            LoadError::InvalidFaces => write!(f, "face indices not divisible by 3"),
+            LoadError::InvalidFaces { face } => {
+                write!(f, "face {} refers to a vertex that doesn't exist", face)
+            }
+            LoadError::Io(e) => write!(f, "failed to read file: {}", e),
+            LoadError::MalformedHeader => write!(f, "malformed STL header"),
+            LoadError::Truncated => write!(f, "file is truncated"),
+            LoadError::InvalidStl { line } => write!(f, "invalid STL on line {}", line),
         }
     }
 }
@@ -46,6 +82,9 @@ impl std::error::Error for LoadError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             LoadError::Tobj(e) => Some(e),
+            LoadError::Io(e) => Some(e),
+            #[cfg(feature = "gltf")]
+            LoadError::Gltf(e) => Some(e),
             _ => None,
         }
     }
@@ -57,14 +96,35 @@ impl From<tobj::LoadError> for LoadError {
     }
 }
 
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+#[cfg(feature = "gltf")]
+impl From<gltf::Error> for LoadError {
+    fn from(e: gltf::Error) -> Self {
+        LoadError::Gltf(e)
+    }
+}
+
 pub type Texel = Vec2;
 
-/// A vertex with position and normal attributes.
+/// A vertex with position, normal, texture coordinate and color attributes.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct Vertex {
     pub position: Vec3,
     pub normal: Vec3,
     pub texel: Texel,
+    /// Packed ARGB color baked into the mesh; opaque white when the OBJ has
+    /// none (check [`Mesh::has_vertex_colors`]).
+    pub color: u32,
+    /// Direction of increasing `u` along the surface in `xyz`, orthogonal
+    /// to `normal`; `w` is +1 or -1, the sign of `normal × tangent` along
+    /// increasing `v`. Zero until [`Mesh::compute_tangents`] runs, and
+    /// where the UVs don't define one.
+    pub tangent: Vec4,
 }
 
 /// A bounding sphere that's computed for each mesh.
@@ -127,6 +187,63 @@ impl BoundingAabb {
     }
 }
 
+/// Uniform scale that brings the largest extent of the box `min..max` to
+/// 1.0. A box with no extent (or a non-finite one) keeps its scale.
+pub(crate) fn unit_scale(min: Vec3, max: Vec3) -> f32 {
+    let extent = max - min;
+    let largest = extent.x.max(extent.y).max(extent.z);
+    if largest > 0.0 && largest.is_finite() {
+        1.0 / largest
+    } else {
+        1.0
+    }
+}
+
+/// One place a mesh is drawn within its model: its own transform, or an
+/// instance transform composed with it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Placement {
+    /// Mesh space to model space.
+    pub matrix: Mat4,
+    /// Inverse transpose of `matrix`'s rotation and scale.
+    pub normal_matrix: Mat4,
+    /// Per-axis scale, for growing bounding radii.
+    pub scale: Vec3,
+}
+
+impl Placement {
+    fn of(transform: &Transform) -> Self {
+        Self {
+            matrix: transform.to_matrix(),
+            normal_matrix: transform.normal_matrix(),
+            scale: transform.scale(),
+        }
+    }
+
+    /// `self` applied after `inner`. Normal matrices compose like the
+    /// transforms, since (A * B)^-T = A^-T * B^-T.
+    fn then(&self, inner: &Placement) -> Self {
+        Self {
+            matrix: self.matrix * inner.matrix,
+            normal_matrix: self.normal_matrix * inner.normal_matrix,
+            scale: Vec3::new(
+                self.scale.x * inner.scale.x,
+                self.scale.y * inner.scale.y,
+                self.scale.z * inner.scale.z,
+            ),
+        }
+    }
+
+    /// Largest absolute per-axis scale.
+    pub fn scale_max(&self) -> f32 {
+        self.scale
+            .x
+            .abs()
+            .max(self.scale.y.abs())
+            .max(self.scale.z.abs())
+    }
+}
+
 /// Cache of the last plane that was rejected by the frustum culling.
 /// Used to avoid re-testing the same plane.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -134,12 +251,45 @@ pub(crate) struct CullCache {
     pub(crate) last_rejecting_plane: Option<i8>,
 }
 
+/// Which faces of a mesh are discarded before rasterization.
+///
+/// Facing is decided by winding: clockwise as seen from the camera is the
+/// front. Faces that survive culling while facing away are lit from the
+/// back, so two-sided surfaces don't turn black from behind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CullMode {
+    /// Discard faces pointing away from the camera. Right for closed meshes.
+    #[default]
+    Back,
+    /// Discard faces pointing toward the camera.
+    Front,
+    /// Draw both sides, e.g. for open surfaces like a cloth plane or a leaf.
+    None,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Mesh {
     name: String,
     vertices: Vec<Vertex>,
     faces: Vec<Face>,
     transform: Transform,
+    /// Model-space placements of extra copies; empty draws the mesh once
+    instances: Vec<Transform>,
+    animator: Option<Animator>,
+    cull_mode: CullMode,
+    /// Overrides the engine's render mode for this mesh
+    render_mode: Option<RenderMode>,
+    has_vertex_colors: bool,
+    /// Index into the owning model's textures, overriding its main texture
+    texture: Option<usize>,
+    /// An engine texture drawn instead of any of the model's
+    texture_handle: Option<TextureHandle>,
+    /// Part of the texture this mesh's UVs map onto, for atlases
+    texture_region: Option<TextureRegion>,
+    /// Name of the OBJ material the faces use, when its MTL file loaded
+    material: Option<String>,
+    /// Simplified stand-ins drawn when the camera is far away
+    lod_group: Option<LodGroup>,
     bounding_sphere: BoundingSphere,
     bounding_aabb: BoundingAabb,
     cull_cache: Cell<CullCache>,
@@ -154,6 +304,16 @@ impl Mesh {
             vertices,
             faces,
             transform: Transform::default(),
+            instances: Vec::new(),
+            animator: None,
+            cull_mode: CullMode::default(),
+            render_mode: None,
+            has_vertex_colors: false,
+            texture: None,
+            texture_handle: None,
+            texture_region: None,
+            material: None,
+            lod_group: None,
             bounding_sphere,
             bounding_aabb,
             cull_cache: Cell::new(CullCache {
@@ -176,7 +336,20 @@ impl Mesh {
             ..Default::default()
         };
 
-        let (models, _materials) = tobj::load_obj(file_path, &load_options)?;
+        let (models, materials) = match tobj::load_obj(file_path, &load_options) {
+            // tobj doesn't say which face was bad; find it for the error
+            Err(
+                e @ (tobj::LoadError::FaceVertexOutOfBounds
+                | tobj::LoadError::FaceTexCoordOutOfBounds
+                | tobj::LoadError::FaceNormalOutOfBounds),
+            ) => {
+                return Err(match find_out_of_range_obj_face(file_path) {
+                    Some(face) => LoadError::InvalidFaces { face },
+                    None => e.into(),
+                })
+            }
+            result => result?,
+        };
 
         if models.is_empty() {
             return Err(LoadError::NoModels);
@@ -192,7 +365,9 @@ impl Mesh {
             }
 
             if tobj_mesh.indices.len() % 3 != 0 {
-                return Err(LoadError::InvalidFaces);
+                return Err(LoadError::InvalidFaces {
+                    face: tobj_mesh.indices.len() / 3,
+                });
             }
 
             // Use the model name from OBJ, or generate a fallback
@@ -207,6 +382,8 @@ impl Mesh {
             //   - positions[i*3 .. i*3+3]  (x, y, z)
             //   - normals[i*3 .. i*3+3]    (nx, ny, nz)
             //   - texcoords[i*2 .. i*2+2]  (u, v)
+            //   - vertex_color[i*3 .. i*3+3] (r, g, b), from the nonstandard
+            //     `v x y z r g b` extension
             //
             // The flat arrays look like:
             //   positions:  [x0, y0, z0, x1, y1, z1, x2, y2, z2, ...]
@@ -214,6 +391,7 @@ impl Mesh {
             //   texcoords:  [u0, v0, u1, v1, u2, v2, ...]
             let has_normals = !tobj_mesh.normals.is_empty();
             let has_texcoords = !tobj_mesh.texcoords.is_empty();
+            let has_colors = !tobj_mesh.vertex_color.is_empty();
             let vertices: Vec<Vertex> = tobj_mesh
                 .positions
                 // chunks_exact(3) yields [x, y, z] slices for each vertex
@@ -237,10 +415,20 @@ impl Mesh {
                         Vec2::ZERO
                     };
 
+                    // Colors are 0..1 floats per channel, like normals laid out in 3s
+                    let color = if has_colors {
+                        let c = &tobj_mesh.vertex_color[i * 3..i * 3 + 3];
+                        pack_color(c[0], c[1], c[2], 1.0)
+                    } else {
+                        0xFFFFFFFF
+                    };
+
                     Vertex {
                         position: Vec3::new(p[0], p[1], p[2]),
                         normal,
                         texel,
+                        color,
+                        tangent: Vec4::ZERO,
                     }
                 })
                 .collect();
@@ -251,7 +439,19 @@ impl Mesh {
                 .map(|c| Face::new(c[0], c[1], c[2]))
                 .collect();
 
-            meshes.push(Self::new(name, vertices, faces));
+            let mut mesh = Self::new(name, vertices, faces);
+            mesh.set_has_vertex_colors(has_colors);
+            // A missing MTL file isn't fatal; the mesh just has no material
+            mesh.material = materials
+                .as_ref()
+                .ok()
+                .zip(tobj_mesh.material_id)
+                .and_then(|(materials, id)| materials.get(id))
+                .map(|material| material.name.clone());
+            if has_texcoords {
+                mesh.compute_tangents();
+            }
+            meshes.push(mesh);
         }
 
         if meshes.is_empty() {
@@ -261,6 +461,155 @@ impl Mesh {
         Ok(meshes)
     }
 
+    /// Load a binary or ASCII STL file as one mesh.
+    ///
+    /// Corners at the same position are merged into shared vertices where
+    /// their facets meet at 30 degrees or less, so Gouraud shading smooths
+    /// curved surfaces but keeps hard edges; see
+    /// [`Mesh::from_stl_with_crease_angle`]. Unusable facet normals are
+    /// recomputed from the winding. The mesh is named after the ASCII
+    /// `solid` line, or the file name.
+    pub fn from_stl(file_path: &str) -> Result<Self, Error> {
+        Self::from_stl_with_crease_angle(file_path, stl_loader::DEFAULT_CREASE_ANGLE)
+    }
+
+    /// Like [`Mesh::from_stl`], merging corners whose facets meet at no more
+    /// than `crease_angle` radians. 0 keeps every facet flat; PI smooths
+    /// everything.
+    pub fn from_stl_with_crease_angle(file_path: &str, crease_angle: f32) -> Result<Self, Error> {
+        let bytes = std::fs::read(file_path).map_err(LoadError::Io)?;
+        let name = std::path::Path::new(file_path)
+            .file_stem()
+            .map_or_else(|| "stl".to_string(), |s| s.to_string_lossy().into_owned());
+        Ok(stl_loader::parse(&name, &bytes, crease_angle)?)
+    }
+
+    /// A sphere of `radius` around the origin with its poles on the y
+    /// axis, cut into `segments` slices around and `rings` bands from pole
+    /// to pole, with smooth normals and UVs wrapping once around. At least
+    /// 3 segments and 2 rings are used.
+    pub fn uv_sphere(name: impl Into<String>, radius: f32, segments: u32, rings: u32) -> Self {
+        let (segments, rings) = (segments.max(3), rings.max(2));
+        let mut vertices = Vec::with_capacity(((segments + 1) * (rings + 1)) as usize);
+        for ring in 0..=rings {
+            let theta = PI * ring as f32 / rings as f32;
+            for segment in 0..=segments {
+                let phi = TAU * segment as f32 / segments as f32;
+                let normal = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.cos(),
+                    theta.sin() * phi.sin(),
+                );
+                vertices.push(Vertex {
+                    position: normal * radius,
+                    normal,
+                    texel: Vec2::new(
+                        segment as f32 / segments as f32,
+                        1.0 - ring as f32 / rings as f32,
+                    ),
+                    color: 0xFFFFFFFF,
+                    tangent: Vec4::ZERO,
+                });
+            }
+        }
+
+        // Each band is a strip of quads, top-left, top-right, bottom-left
+        // and bottom-right seen from outside; the poles' halves of them
+        // would be slivers of no area
+        let index = |ring: u32, segment: u32| ring * (segments + 1) + segment;
+        let mut faces = Vec::with_capacity((segments * (rings - 1) * 2) as usize);
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let (a, b) = (index(ring, segment), index(ring, segment + 1));
+                let (c, d) = (index(ring + 1, segment), index(ring + 1, segment + 1));
+                if ring > 0 {
+                    faces.push(Face::new(a, b, d));
+                }
+                if ring < rings - 1 {
+                    faces.push(Face::new(a, d, c));
+                }
+            }
+        }
+        Self::new(name.into(), vertices, faces)
+    }
+
+    /// Number of vertices, after any merging done on load.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Number of triangles.
+    pub fn face_count(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Whether the OBJ gave every vertex a color (`v x y z r g b`). Used by
+    /// `TextureMode::VertexColor`; meshes without colors fall back to the
+    /// palette fill.
+    pub fn has_vertex_colors(&self) -> bool {
+        self.has_vertex_colors
+    }
+
+    pub(crate) fn set_has_vertex_colors(&mut self, has_vertex_colors: bool) {
+        self.has_vertex_colors = has_vertex_colors;
+    }
+
+    /// Index of this mesh's own texture in its model, if it has one.
+    pub(crate) fn texture_index(&self) -> Option<usize> {
+        self.texture
+    }
+
+    #[cfg(feature = "gltf")]
+    pub(crate) fn set_texture_index(&mut self, texture: Option<usize>) {
+        self.texture = texture;
+    }
+
+    /// Draw this mesh with the engine texture `handle` refers to, in place
+    /// of its model's textures and the global one. Any number of meshes
+    /// can share a texture this way without copying its pixels; see
+    /// [`Engine::add_texture`]. Once the texture is removed the mesh draws
+    /// untextured.
+    ///
+    /// [`Engine::add_texture`]: crate::engine::Engine::add_texture
+    pub fn set_texture_handle(&mut self, handle: TextureHandle) {
+        self.texture_handle = Some(handle);
+    }
+
+    /// Go back to drawing with the model's textures.
+    pub fn clear_texture_handle(&mut self) {
+        self.texture_handle = None;
+    }
+
+    /// The engine texture this mesh draws with, if set.
+    pub fn texture_handle(&self) -> Option<TextureHandle> {
+        self.texture_handle
+    }
+
+    /// Map this mesh's UVs onto `region` of its texture, so meshes can
+    /// share one atlas image. See [`Model::set_material_region`] to set it
+    /// for every mesh using a material.
+    ///
+    /// [`Model::set_material_region`]: crate::model::Model::set_material_region
+    pub fn set_texture_region(&mut self, region: TextureRegion) {
+        self.texture_region = Some(region);
+    }
+
+    /// Go back to mapping UVs onto the whole texture.
+    pub fn clear_texture_region(&mut self) {
+        self.texture_region = None;
+    }
+
+    /// Part of the texture this mesh's UVs map onto, if not all of it.
+    pub fn texture_region(&self) -> Option<TextureRegion> {
+        self.texture_region
+    }
+
+    /// Name of the OBJ material this mesh's faces use, if its MTL file
+    /// loaded. The loader splits an object into one mesh per material.
+    pub fn material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+
     /// Get a reference to the transform.
     pub fn transform(&self) -> &Transform {
         &self.transform
@@ -271,11 +620,364 @@ impl Mesh {
         &mut self.transform
     }
 
+    // ============ Instancing ============
+
+    /// Draw the mesh once per `transforms` entry, on top of any instances
+    /// it already has, sharing its vertices and faces.
+    ///
+    /// An instance transform places the mesh within its model: a copy is
+    /// drawn at model * instance * mesh transform. A mesh with instances
+    /// is drawn only at them, not also at its own placement.
+    pub fn add_instances(&mut self, transforms: &[Transform]) {
+        self.instances.extend_from_slice(transforms);
+    }
+
+    /// Remove every instance, going back to drawing the mesh once.
+    pub fn clear_instances(&mut self) {
+        self.instances.clear();
+    }
+
+    /// The instance transforms, in the order they were added.
+    pub fn instances(&self) -> &[Transform] {
+        &self.instances
+    }
+
+    /// Mutable instance transforms, to move copies from frame to frame.
+    pub fn instances_mut(&mut self) -> &mut [Transform] {
+        &mut self.instances
+    }
+
+    /// Whether the mesh is drawn at instances rather than once.
+    pub fn is_instanced(&self) -> bool {
+        !self.instances.is_empty()
+    }
+
+    /// Where the mesh is drawn within its model: each instance composed
+    /// with the mesh transform, or the mesh transform alone without any.
+    pub(crate) fn placements(&self) -> impl Iterator<Item = Placement> + '_ {
+        let local = Placement::of(&self.transform);
+        let own = self.instances.is_empty().then_some(local);
+        own.into_iter().chain(
+            self.instances
+                .iter()
+                .map(move |instance| Placement::of(instance).then(&local)),
+        )
+    }
+
+    /// Get the animator driving this mesh's local transform, if any.
+    pub fn animator(&self) -> Option<&Animator> {
+        self.animator.as_ref()
+    }
+
+    /// Get a mutable reference to the animator, if any.
+    pub fn animator_mut(&mut self) -> Option<&mut Animator> {
+        self.animator.as_mut()
+    }
+
+    /// Attach an animator, advanced by `Engine::update_with_dt`.
+    pub fn set_animator(&mut self, animator: Animator) {
+        self.animator = Some(animator);
+    }
+
+    /// Detach and return the animator, leaving the transform where it is.
+    pub fn clear_animator(&mut self) -> Option<Animator> {
+        self.animator.take()
+    }
+
+    /// Advance the animator (if any) by `dt` seconds.
+    pub(crate) fn animate(&mut self, dt: f32) {
+        if let Some(animator) = &mut self.animator {
+            animator.advance(dt, &mut self.transform);
+        }
+    }
+
+    /// Which faces of this mesh are culled.
+    pub fn cull_mode(&self) -> CullMode {
+        self.cull_mode
+    }
+
+    /// Set which faces are culled. `Engine::backface_culling = false`
+    /// still disables culling for every mesh.
+    pub fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        self.cull_mode = cull_mode;
+    }
+
+    /// How this mesh is drawn, or `None` to follow
+    /// [`Engine::render_mode`](crate::Engine::render_mode).
+    pub fn render_mode(&self) -> Option<RenderMode> {
+        self.render_mode
+    }
+
+    /// Draw this mesh in `mode` whatever the engine-wide default, e.g. a
+    /// wireframe collision proxy over a filled model.
+    /// [`Engine::set_render_mode_override`](crate::Engine::set_render_mode_override)
+    /// still wins over it.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = Some(mode);
+    }
+
+    /// Follow the engine-wide render mode again, returning the mesh's own.
+    pub fn clear_render_mode(&mut self) -> Option<RenderMode> {
+        self.render_mode.take()
+    }
+
+    /// A copy of the mesh with about `target_face_ratio` of its faces, at
+    /// most, for a [`LodGroup`] level.
+    ///
+    /// Vertices are clustered on a grid over the bounds, each cell's merged
+    /// into their average, with the grid as fine as the target allows.
+    /// Faces whose corners end up in fewer than three cells disappear, so
+    /// detail smaller than a cell goes first; UVs and colors are averaged
+    /// too, blurring across seams. A ratio of 1.0 or more copies the mesh
+    /// unchanged. Everything else about the mesh carries over, except its
+    /// own LOD group.
+    pub fn simplify(&self, target_face_ratio: f32) -> Mesh {
+        let target = (self.faces.len() as f32 * target_face_ratio.max(0.0)) as usize;
+        let simplified = if target < self.faces.len() {
+            lod::simplify(&self.vertices, &self.faces, target)
+        } else {
+            None
+        };
+        let (vertices, faces) =
+            simplified.unwrap_or_else(|| (self.vertices.clone(), self.faces.clone()));
+
+        let mut mesh = Self::new(self.name.clone(), vertices, faces);
+        mesh.transform = self.transform;
+        mesh.instances = self.instances.clone();
+        mesh.animator = self.animator.clone();
+        mesh.cull_mode = self.cull_mode;
+        mesh.render_mode = self.render_mode;
+        mesh.has_vertex_colors = self.has_vertex_colors;
+        mesh.texture = self.texture;
+        mesh.texture_handle = self.texture_handle;
+        mesh.texture_region = self.texture_region;
+        mesh.material = self.material.clone();
+        if self.vertices.iter().any(|v| v.tangent != Vec4::ZERO) {
+            mesh.compute_tangents();
+        }
+        mesh
+    }
+
+    /// Smooth the mesh with `iterations` rounds of Loop subdivision, each
+    /// splitting every face into four, so low-poly models hold up close.
+    ///
+    /// Edges are found by position, so vertices split at UV or normal seams
+    /// still join into one surface, and new vertices take the average UV
+    /// and color of their edge's ends. Closed surfaces round off toward a
+    /// smooth limit; open borders stay in place as curves. Edges shared by
+    /// more than two faces can't be smoothed: they're split at their
+    /// midpoint, their ends stay put, and the count of them over all the
+    /// rounds is returned, zero for a clean mesh.
+    ///
+    /// Normals are recomputed afterwards, smooth across seams, along with
+    /// the tangents when the mesh has any. LOD levels are separate copies
+    /// and don't follow.
+    pub fn subdivide_loop(&mut self, iterations: u32) -> usize {
+        if iterations == 0 {
+            return 0;
+        }
+        let mut non_manifold = 0;
+        for _ in 0..iterations {
+            let (vertices, faces, skipped) = subdivide::subdivide(&self.vertices, &self.faces);
+            self.vertices = vertices;
+            self.faces = faces;
+            non_manifold += skipped;
+        }
+        subdivide::smooth_normals(&mut self.vertices, &self.faces);
+        self.bounding_sphere = BoundingSphere::from_vertices(&self.vertices);
+        self.bounding_aabb = BoundingAabb::from_vertices(&self.vertices);
+        if self.vertices.iter().any(|v| v.tangent != Vec4::ZERO) {
+            self.compute_tangents();
+        }
+        non_manifold
+    }
+
+    /// Draw simplified versions of the mesh when the camera is far enough
+    /// away; see [`crate::lod`]. Replaces any previous group.
+    pub fn set_lod_group(&mut self, group: LodGroup) {
+        self.lod_group = Some(group);
+    }
+
+    /// Always draw the full mesh again, returning the LOD group.
+    pub fn clear_lod_group(&mut self) -> Option<LodGroup> {
+        self.lod_group.take()
+    }
+
+    pub fn lod_group(&self) -> Option<&LodGroup> {
+        self.lod_group.as_ref()
+    }
+
+    /// The mesh to draw `distance` from the camera: a LOD level, or itself.
+    pub(crate) fn lod_for(&self, distance: f32) -> &Mesh {
+        self.lod_group
+            .as_ref()
+            .and_then(|group| group.select(distance))
+            .unwrap_or(self)
+    }
+
+    /// Local-space axis-aligned bounds of the vertices, as `(min, max)`.
+    pub fn bounding_box(&self) -> (Vec3, Vec3) {
+        (self.bounding_aabb.min, self.bounding_aabb.max)
+    }
+
+    /// Rescale vertex positions so the largest bounding-box extent is 1.0,
+    /// and with `center`, move the vertex centroid to the origin.
+    ///
+    /// This is baked into the vertices; the transform is left alone. Within
+    /// a multi-mesh [`Model`](crate::model::Model), prefer
+    /// `Model::normalize_to_unit`, which keeps the meshes' relative sizes.
+    pub fn normalize_to_unit(&mut self, center: bool) {
+        let (min, max) = self.bounding_box();
+        let origin = if center {
+            self.bounding_sphere.center
+        } else {
+            Vec3::ZERO
+        };
+        self.rebase(origin, unit_scale(min, max));
+    }
+
+    /// Map every position `p` to `(p - origin) * scale` and refresh the
+    /// bounds. Normals are unaffected by a uniform scale.
+    pub(crate) fn rebase(&mut self, origin: Vec3, scale: f32) {
+        for v in &mut self.vertices {
+            v.position = (v.position - origin) * scale;
+        }
+        self.bounding_sphere = BoundingSphere::from_vertices(&self.vertices);
+        self.bounding_aabb = BoundingAabb::from_vertices(&self.vertices);
+    }
+
+    /// The vertex positions, in mesh-local space.
+    pub fn positions(&self) -> impl ExactSizeIterator<Item = Vec3> + '_ {
+        self.vertices.iter().map(|v| v.position)
+    }
+
+    /// Each face's three vertex indices, into [`Mesh::positions`].
+    pub fn face_indices(&self) -> impl ExactSizeIterator<Item = [u32; 3]> + '_ {
+        self.faces.iter().map(|f| [f.a, f.b, f.c])
+    }
+
+    /// Move every vertex to `f(index, position)`, for procedural effects
+    /// like waves or runtime deformation.
+    ///
+    /// Only positions change: the faces keep their vertices, so topology,
+    /// UVs and colors stay fixed. Afterwards the bounds are refreshed and
+    /// the normals recomputed with [`Mesh::recompute_normals`] so lighting
+    /// follows the new shape, along with the tangents when the mesh has
+    /// any. LOD levels are separate copies and don't follow.
+    pub fn map_positions(&mut self, mut f: impl FnMut(usize, Vec3) -> Vec3) {
+        for (i, v) in self.vertices.iter_mut().enumerate() {
+            v.position = f(i, v.position);
+        }
+        self.bounding_sphere = BoundingSphere::from_vertices(&self.vertices);
+        self.bounding_aabb = BoundingAabb::from_vertices(&self.vertices);
+        self.recompute_normals();
+        if self.vertices.iter().any(|v| v.tangent != Vec4::ZERO) {
+            self.compute_tangents();
+        }
+    }
+
+    /// Replace every vertex's normal with the area-weighted average of the
+    /// normals of the faces around it.
+    ///
+    /// Faces only share smoothing across vertices they have in common, so
+    /// hard edges split into separate vertices at load stay hard. Vertices
+    /// no face uses, or only degenerate ones, keep their normal.
+    pub fn recompute_normals(&mut self) {
+        let mut normals = vec![Vec3::ZERO; self.vertices.len()];
+        for face in &self.faces {
+            let Some([a, b, c]) = self.face_vertices(face) else {
+                continue;
+            };
+            // The cross product's length is twice the area, weighting it
+            let normal = (b.position - a.position).cross(c.position - a.position);
+            for index in [face.a, face.b, face.c] {
+                let i = index as usize;
+                normals[i] = normals[i] + normal;
+            }
+        }
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            if normal.magnitude() > 0.0 {
+                vertex.normal = normal.normalize();
+            }
+        }
+    }
+
+    /// Derive every vertex's tangent from the UVs of the faces around it,
+    /// for `TextureMode::NormalMapped`.
+    ///
+    /// Each face contributes the directions in which `u` and `v` increase
+    /// across it; a vertex's sums are made orthogonal to its normal (or to
+    /// its faces' normal when it has none), and the `v` direction only
+    /// survives as the handedness sign, so mirrored UVs still light the
+    /// right way. OBJ and glTF meshes with UVs get this on load.
+    pub fn compute_tangents(&mut self) {
+        let mut u_dirs = vec![Vec3::ZERO; self.vertices.len()];
+        let mut v_dirs = vec![Vec3::ZERO; self.vertices.len()];
+        let mut face_normals = vec![Vec3::ZERO; self.vertices.len()];
+
+        for face in &self.faces {
+            let Some([a, b, c]) = self.face_vertices(face) else {
+                continue;
+            };
+            let (e1, e2) = (b.position - a.position, c.position - a.position);
+            let (d1, d2) = (b.texel - a.texel, c.texel - a.texel);
+            let det = d1.x * d2.y - d2.x * d1.y;
+            if det.abs() < f32::EPSILON {
+                // UVs collapse to a line; no direction to take
+                continue;
+            }
+            let u_dir = (e1 * d2.y - e2 * d1.y) / det;
+            let v_dir = (e2 * d1.x - e1 * d2.x) / det;
+            let normal = e1.cross(e2);
+            for index in [face.a, face.b, face.c] {
+                let i = index as usize;
+                u_dirs[i] = u_dirs[i] + u_dir;
+                v_dirs[i] = v_dirs[i] + v_dir;
+                face_normals[i] = face_normals[i] + normal;
+            }
+        }
+
+        for (i, vertex) in self.vertices.iter_mut().enumerate() {
+            let normal = if vertex.normal.magnitude() > 0.0 {
+                vertex.normal.normalize()
+            } else if face_normals[i].magnitude() > 0.0 {
+                face_normals[i].normalize()
+            } else {
+                vertex.tangent = Vec4::ZERO;
+                continue;
+            };
+            // Gram-Schmidt: drop the part of u along the normal
+            let tangent = u_dirs[i] - normal * normal.dot(u_dirs[i]);
+            if tangent.magnitude() <= f32::EPSILON {
+                vertex.tangent = Vec4::ZERO;
+                continue;
+            }
+            let tangent = tangent.normalize();
+            let handedness = if normal.cross(tangent).dot(v_dirs[i]) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            vertex.tangent = Vec4::from_vec3(tangent, handedness);
+        }
+    }
+
     /// Get a reference to the vertices
     pub(crate) fn vertices(&self) -> &[Vertex] {
         &self.vertices
     }
 
+    /// The three corners of `face`, or `None` if it refers past the end of
+    /// this mesh's vertices. Loaders reject such faces, but a mesh built in
+    /// code can still have them.
+    pub(crate) fn face_vertices(&self, face: &Face) -> Option<[Vertex; 3]> {
+        Some([
+            *self.vertices.get(face.a as usize)?,
+            *self.vertices.get(face.b as usize)?,
+            *self.vertices.get(face.c as usize)?,
+        ])
+    }
+
     /// Get a reference to the faces
     pub(crate) fn faces(&self) -> &[Face] {
         &self.faces
@@ -293,3 +995,179 @@ impl Mesh {
         &self.cull_cache
     }
 }
+
+/// Index of the first face in the OBJ at `file_path` whose position,
+/// texture coordinate or normal index points past what the file defines,
+/// resolved the way tobj does: positive indices count from 1, negative
+/// ones back from the elements read so far.
+fn find_out_of_range_obj_face(file_path: &str) -> Option<usize> {
+    let source = std::fs::read_to_string(file_path).ok()?;
+    let keywords = ["v", "vt", "vn"];
+
+    let mut totals = [0isize; 3];
+    for line in source.lines() {
+        if let Some(kind) = line
+            .split_whitespace()
+            .next()
+            .and_then(|word| keywords.iter().position(|k| *k == word))
+        {
+            totals[kind] += 1;
+        }
+    }
+
+    let mut seen = [0isize; 3];
+    let mut face = 0;
+    for line in source.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("f") => {
+                let out_of_range = words.any(|corner| {
+                    corner.split('/').take(3).enumerate().any(|(kind, index)| {
+                        let Ok(index) = index.parse::<isize>() else {
+                            return false;
+                        };
+                        let resolved = if index < 0 {
+                            seen[kind] + index
+                        } else {
+                            index - 1
+                        };
+                        !(0..totals[kind]).contains(&resolved)
+                    })
+                });
+                if out_of_range {
+                    return Some(face);
+                }
+                face += 1;
+            }
+            Some(word) => {
+                if let Some(kind) = keywords.iter().position(|k| *k == word) {
+                    seen[kind] += 1;
+                }
+            }
+            None => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit quad in the z = 0 plane facing -z, with `u` running along
+    /// `u_axis` and `v` along +y.
+    fn quad(u_axis: f32) -> Mesh {
+        let corner = |x: f32, y: f32| Vertex {
+            position: Vec3::new(x, y, 0.0),
+            normal: Vec3::new(0.0, 0.0, -1.0),
+            texel: Vec2::new((x * u_axis + 1.0) / 2.0, (y + 1.0) / 2.0),
+            color: 0xFFFFFFFF,
+            tangent: Vec4::ZERO,
+        };
+        let vertices = vec![
+            corner(-1.0, -1.0),
+            corner(-1.0, 1.0),
+            corner(1.0, 1.0),
+            corner(1.0, -1.0),
+        ];
+        let faces = vec![Face::new(0, 1, 2), Face::new(0, 2, 3)];
+        Mesh::new("quad".to_string(), vertices, faces)
+    }
+
+    fn approx(a: Vec4, b: Vec4) -> bool {
+        (a - b).magnitude() < 1e-5
+    }
+
+    #[test]
+    fn tangents_follow_increasing_u() {
+        let mut mesh = quad(1.0);
+        mesh.compute_tangents();
+        // normal × tangent = -z × +x = -y, against increasing v
+        for v in mesh.vertices() {
+            assert!(approx(v.tangent, Vec4::new(1.0, 0.0, 0.0, -1.0)), "{v:?}");
+        }
+    }
+
+    #[test]
+    fn mirrored_uvs_flip_the_tangent_not_the_bitangent() {
+        let mut mesh = quad(-1.0);
+        mesh.compute_tangents();
+        for v in mesh.vertices() {
+            assert!(approx(v.tangent, Vec4::new(-1.0, 0.0, 0.0, 1.0)), "{v:?}");
+            // The bitangent rebuilt from it still points along +v (+y)
+            let bitangent = v.normal.cross(v.tangent.to_vec3()) * v.tangent.w;
+            assert!((bitangent - Vec3::UP).magnitude() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn collapsed_uvs_leave_no_tangent() {
+        let mut mesh = quad(1.0);
+        for v in &mut mesh.vertices {
+            v.texel = Vec2::new(0.5, 0.5);
+        }
+        mesh.compute_tangents();
+        assert!(mesh.vertices().iter().all(|v| v.tangent == Vec4::ZERO));
+    }
+
+    #[test]
+    fn mapped_positions_refresh_bounds_and_normals() {
+        let mut mesh = quad(1.0);
+        // Tilt the quad by pushing its right edge toward the viewer
+        mesh.map_positions(|_, p| {
+            if p.x > 0.0 {
+                Vec3::new(p.x, p.y, -1.0)
+            } else {
+                p
+            }
+        });
+        assert_eq!(
+            mesh.bounding_box(),
+            (Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 0.0))
+        );
+        assert_eq!(mesh.positions().len(), 4);
+        assert_eq!(
+            mesh.face_indices().collect::<Vec<_>>(),
+            [[0, 1, 2], [0, 2, 3]]
+        );
+
+        let tilted = Vec3::new(-1.0, 0.0, -2.0).normalize();
+        for v in mesh.vertices() {
+            assert!((v.normal - tilted).magnitude() < 1e-5, "{v:?}");
+        }
+    }
+
+    #[test]
+    fn recomputed_normals_average_the_faces_around_a_vertex() {
+        let mut mesh = quad(1.0);
+        // Fold along the diagonal: vertex 3 comes forward, bending face 1
+        mesh.vertices[3].position = Vec3::new(1.0, -1.0, -2.0);
+        mesh.recompute_normals();
+
+        let flat = Vec3::new(0.0, 0.0, -1.0);
+        assert!((mesh.vertices[1].normal - flat).magnitude() < 1e-5);
+        // The corners on the fold lean between the two faces
+        for i in [0, 2] {
+            let n = mesh.vertices[i].normal;
+            assert!((n.magnitude() - 1.0).abs() < 1e-5);
+            assert!(n.dot(flat) > 0.5 && n.dot(flat) < 1.0 - 1e-3, "{n:?}");
+        }
+    }
+
+    #[test]
+    fn uv_spheres_face_outward() {
+        let mesh = Mesh::uv_sphere("sphere", 2.0, 12, 6);
+        assert_eq!(mesh.vertex_count(), 13 * 7);
+        // Two triangles per quad, one per quad touching a pole
+        assert_eq!(mesh.face_count(), 12 * 6 * 2 - 2 * 12);
+        for v in mesh.vertices() {
+            assert!((v.position.magnitude() - 2.0).abs() < 1e-5);
+            assert!((v.normal - v.position / 2.0).magnitude() < 1e-5);
+        }
+        for face in mesh.faces() {
+            let [a, b, c] = [face.a, face.b, face.c].map(|i| mesh.vertices()[i as usize].position);
+            let outward = (b - a).cross(c - a).dot(a + b + c);
+            assert!(outward > 0.0, "{face:?}");
+        }
+    }
+}
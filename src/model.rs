@@ -4,10 +4,13 @@
 //! Each mesh can have its own local transform relative to the model's world transform.
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use crate::animation::Animator;
+use crate::error::Error;
 use crate::math::vec3::Vec3;
-use crate::mesh::{BoundingSphere, LoadError, Mesh};
-use crate::texture::Texture;
+use crate::mesh::{unit_scale, BoundingSphere, Mesh};
+use crate::texture::{Texture, TextureRegion};
 use crate::transform::Transform;
 
 /// Compute an enclosing sphere from a slice of mesh bounding spheres.
@@ -20,21 +23,39 @@ fn bounds_of_meshes(meshes: &[Mesh]) -> BoundingSphere {
             radius: 0.0,
         };
     }
-    let n = meshes.len() as f32;
-    let center = meshes.iter().map(|m| m.bounds().center).sum::<Vec3>() / n;
-    let radius = meshes
+    let spheres: Vec<BoundingSphere> = meshes.iter().flat_map(mesh_bounds_in_model).collect();
+    let n = spheres.len() as f32;
+    let center = spheres.iter().map(|b| b.center).sum::<Vec3>() / n;
+    let radius = spheres
         .iter()
-        .map(|m| {
-            let b = m.bounds();
-            (b.center - center).magnitude() + b.radius
-        })
+        .map(|b| (b.center - center).magnitude() + b.radius)
         .fold(0.0_f32, f32::max);
     BoundingSphere { center, radius }
 }
 
+/// A mesh's bounding sphere moved into model space, once per placement
+/// (its local transform, or each of its instances)
+fn mesh_bounds_in_model(mesh: &Mesh) -> impl Iterator<Item = BoundingSphere> + '_ {
+    let bounds = mesh.bounds();
+    mesh.placements().map(move |placement| BoundingSphere {
+        center: placement.matrix * bounds.center,
+        radius: bounds.radius * placement.scale_max(),
+    })
+}
+
+/// How a model's source file is read, so it can be read again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceFormat {
+    Obj,
+    Stl,
+    #[cfg(feature = "gltf")]
+    Gltf,
+}
+
 /// A 3D model containing one or more meshes.
 ///
-/// Models are loaded from OBJ files and can contain multiple named meshes.
+/// Models are loaded from OBJ (or, with the `gltf` feature, glTF) files and
+/// can contain multiple named meshes.
 /// The model has a world transform (position, rotation, scale), and each
 /// mesh within it can have an additional local transform.
 pub struct Model {
@@ -42,8 +63,12 @@ pub struct Model {
     meshes: Vec<Mesh>,
     mesh_names: HashMap<String, usize>,
     transform: Transform,
+    animator: Option<Animator>,
     texture: Option<Texture>,
-    bounds: BoundingSphere,
+    /// Per-mesh textures, indexed by [`Mesh::texture_index`]
+    textures: Vec<Texture>,
+    /// The file the meshes were loaded from, for [`Model::reload`]
+    source: Option<(PathBuf, SourceFormat)>,
 }
 
 impl Model {
@@ -54,36 +79,85 @@ impl Model {
             meshes: Vec::new(),
             mesh_names: HashMap::new(),
             transform: Transform::default(),
+            animator: None,
             texture: None,
-            bounds: BoundingSphere {
-                center: Vec3::ZERO,
-                radius: 0.0,
-            },
+            textures: Vec::new(),
+            source: None,
         }
     }
 
     /// Load a model from an OBJ file.
     ///
     /// All objects/groups in the OBJ file become separate meshes within this model.
-    pub fn from_obj(name: impl Into<String>, file_path: &str) -> Result<Self, LoadError> {
+    pub fn from_obj(name: impl Into<String>, file_path: &str) -> Result<Self, Error> {
         let meshes = Mesh::load_all_from_obj(file_path)?;
         let mesh_names: HashMap<String, usize> = meshes
             .iter()
             .enumerate()
             .map(|(i, m)| (m.name().to_string(), i))
             .collect();
-        let bounds = bounds_of_meshes(&meshes);
 
         Ok(Self {
             name: name.into(),
             meshes,
             mesh_names,
             transform: Transform::default(),
+            animator: None,
             texture: None,
-            bounds,
+            textures: Vec::new(),
+            source: Some((PathBuf::from(file_path), SourceFormat::Obj)),
         })
     }
 
+    /// Load an STL file as a single-mesh model. See [`Mesh::from_stl`].
+    pub fn from_stl(name: impl Into<String>, file_path: &str) -> Result<Self, Error> {
+        let mut model = Self::new(name);
+        model.add_mesh(Mesh::from_stl(file_path)?);
+        model.source = Some((PathBuf::from(file_path), SourceFormat::Stl));
+        Ok(model)
+    }
+
+    /// Load the default scene of a glTF 2.0 file (`.gltf` or `.glb`).
+    ///
+    /// Each triangle primitive becomes a mesh carrying its node's flattened
+    /// world transform and its material's base-color texture, if any. The
+    /// scene is converted from glTF's right-handed coordinates, so it faces
+    /// the same way it does in other viewers.
+    #[cfg(feature = "gltf")]
+    pub fn from_gltf(name: impl Into<String>, file_path: &str) -> Result<Self, Error> {
+        let mut model = crate::gltf_loader::load(name.into(), file_path)?;
+        model.source = Some((PathBuf::from(file_path), SourceFormat::Gltf));
+        Ok(model)
+    }
+
+    /// The file this model was loaded from, if any.
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    /// Load the meshes again from [`Model::source_path`], replacing the
+    /// current ones. The name, transform, animator and texture are kept;
+    /// per-mesh settings such as local transforms and instances come from
+    /// the file afresh. If the file fails to load, the model is left as it
+    /// was. Does nothing for a model not loaded from a file.
+    pub fn reload(&mut self) -> Result<(), Error> {
+        let Some((path, format)) = &self.source else {
+            return Ok(());
+        };
+        let file_path = path.to_string_lossy();
+        let name = self.name.clone();
+        let fresh = match format {
+            SourceFormat::Obj => Self::from_obj(name, &file_path)?,
+            SourceFormat::Stl => Self::from_stl(name, &file_path)?,
+            #[cfg(feature = "gltf")]
+            SourceFormat::Gltf => Self::from_gltf(name, &file_path)?,
+        };
+        self.meshes = fresh.meshes;
+        self.mesh_names = fresh.mesh_names;
+        self.textures = fresh.textures;
+        Ok(())
+    }
+
     /// Get the model name.
     pub fn name(&self) -> &str {
         &self.name
@@ -101,6 +175,38 @@ impl Model {
         &mut self.transform
     }
 
+    // ============ Animation ============
+
+    /// Get the animator driving the model's world transform, if any.
+    pub fn animator(&self) -> Option<&Animator> {
+        self.animator.as_ref()
+    }
+
+    /// Get a mutable reference to the animator, if any.
+    pub fn animator_mut(&mut self) -> Option<&mut Animator> {
+        self.animator.as_mut()
+    }
+
+    /// Attach an animator, advanced by `Engine::update_with_dt`.
+    pub fn set_animator(&mut self, animator: Animator) {
+        self.animator = Some(animator);
+    }
+
+    /// Detach and return the animator, leaving the transform where it is.
+    pub fn clear_animator(&mut self) -> Option<Animator> {
+        self.animator.take()
+    }
+
+    /// Advance the model's animator and every mesh animator by `dt` seconds.
+    pub(crate) fn animate(&mut self, dt: f32) {
+        if let Some(animator) = &mut self.animator {
+            animator.advance(dt, &mut self.transform);
+        }
+        for mesh in &mut self.meshes {
+            mesh.animate(dt);
+        }
+    }
+
     // ============ Mesh Access ============
 
     /// Get a mesh by name.
@@ -152,12 +258,79 @@ impl Model {
         let index = self.meshes.len();
         self.meshes.push(mesh);
         self.mesh_names.insert(name, index);
-        self.bounds = bounds_of_meshes(&self.meshes);
     }
 
-    /// Model-space enclosing sphere of all meshes. Used for model-level culling.
+    /// Map the UVs of every mesh using OBJ material `material` onto
+    /// `region` of the model's texture, so materials can share one atlas.
+    /// Returns how many meshes it applied to.
+    pub fn set_material_region(&mut self, material: &str, region: TextureRegion) -> usize {
+        let mut count = 0;
+        for mesh in &mut self.meshes {
+            if mesh.material() == Some(material) {
+                mesh.set_texture_region(region);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Model-space enclosing sphere of all meshes, placed by their local
+    /// transforms and instances. Used for model-level culling. Computed on
+    /// demand so it follows meshes edited or moved in place.
     pub(crate) fn bounds(&self) -> BoundingSphere {
-        self.bounds
+        bounds_of_meshes(&self.meshes)
+    }
+
+    /// Model-space axis-aligned bounds over all meshes' vertices, as
+    /// `(min, max)`. Mesh transforms are not applied. `None` for a model
+    /// without meshes.
+    pub fn bounding_box(&self) -> Option<(Vec3, Vec3)> {
+        self.meshes
+            .iter()
+            .map(Mesh::bounding_box)
+            .reduce(|(min_a, max_a), (min_b, max_b)| {
+                (
+                    Vec3::new(
+                        min_a.x.min(min_b.x),
+                        min_a.y.min(min_b.y),
+                        min_a.z.min(min_b.z),
+                    ),
+                    Vec3::new(
+                        max_a.x.max(max_b.x),
+                        max_a.y.max(max_b.y),
+                        max_a.z.max(max_b.z),
+                    ),
+                )
+            })
+    }
+
+    /// Like [`Mesh::normalize_to_unit`], but over all meshes together: the
+    /// model's overall largest extent becomes 1.0 and, with `center`, the
+    /// centroid of every vertex moves to the origin. Meshes keep their
+    /// sizes and positions relative to each other.
+    pub fn normalize_to_unit(&mut self, center: bool) {
+        let Some((min, max)) = self.bounding_box() else {
+            return;
+        };
+        let origin = if center {
+            let (sum, count) = self
+                .meshes
+                .iter()
+                .fold((Vec3::ZERO, 0), |(sum, count), mesh| {
+                    let vertices = mesh.vertices();
+                    (
+                        sum + vertices.iter().map(|v| v.position).sum::<Vec3>(),
+                        count + vertices.len(),
+                    )
+                });
+            sum / count as f32
+        } else {
+            Vec3::ZERO
+        };
+        let scale = unit_scale(min, max);
+        for mesh in &mut self.meshes {
+            mesh.rebase(origin, scale);
+        }
     }
 
     // ============ Texture ============
@@ -176,4 +349,32 @@ impl Model {
     pub fn texture(&self) -> Option<&Texture> {
         self.texture.as_ref()
     }
+
+    /// Get the texture for this model to change in place.
+    pub fn texture_mut(&mut self) -> Option<&mut Texture> {
+        self.texture.as_mut()
+    }
+
+    /// Get the textures owned by individual meshes, such as glTF base-color
+    /// maps.
+    pub fn mesh_textures(&self) -> &[Texture] {
+        &self.textures
+    }
+
+    /// Add a texture for meshes to reference by index.
+    #[cfg(feature = "gltf")]
+    pub(crate) fn add_texture(&mut self, texture: Texture) -> usize {
+        self.textures.push(texture);
+        self.textures.len() - 1
+    }
+
+    /// The texture to draw mesh `index` with: its own if it has one,
+    /// otherwise the model's.
+    pub fn mesh_texture(&self, index: usize) -> Option<&Texture> {
+        self.meshes
+            .get(index)
+            .and_then(Mesh::texture_index)
+            .and_then(|i| self.textures.get(i))
+            .or(self.texture.as_ref())
+    }
 }
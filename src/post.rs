@@ -0,0 +1,130 @@
+//! Full-frame post-processing.
+//!
+//! A [`PostEffect`] runs at the end of `Engine::render`, after the HUD is
+//! drawn and supersampling is resolved, so it sees exactly the pixels that
+//! will be presented. Closures work too:
+//!
+//! ```ignore
+//! engine.set_post_effect(Some(Box::new(Vignette::default())));
+//! engine.set_post_effect(Some(Box::new(|pixels: &mut [u32], _w: u32, _h: u32| {
+//!     for p in pixels {
+//!         *p ^= 0x00FFFFFF; // invert
+//!     }
+//! })));
+//! ```
+
+use crate::colors;
+
+/// An effect applied in place to the finished ARGB8888 frame.
+pub trait PostEffect {
+    /// Modify `pixels`, a row-major `width` x `height` image.
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32);
+}
+
+impl<F: FnMut(&mut [u32], u32, u32)> PostEffect for F {
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        self(pixels, width, height)
+    }
+}
+
+/// Convert to grayscale using Rec. 601 luma weights.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Grayscale;
+
+impl PostEffect for Grayscale {
+    fn apply(&mut self, pixels: &mut [u32], _width: u32, _height: u32) {
+        for p in pixels {
+            let r = (*p >> 16) & 0xFF;
+            let g = (*p >> 8) & 0xFF;
+            let b = *p & 0xFF;
+            // 0.299, 0.587, 0.114 in 8.8 fixed point (weights sum to 256)
+            let y = (77 * r + 150 * g + 29 * b) >> 8;
+            *p = (*p & 0xFF000000) | (y << 16) | (y << 8) | y;
+        }
+    }
+}
+
+/// Darken the frame toward its corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vignette {
+    /// How dark the corners get, from 0.0 (no effect) to 1.0 (black).
+    pub strength: f32,
+    /// Normalized distance from the center (1.0 = corner) where the
+    /// darkening starts.
+    pub radius: f32,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            strength: 0.6,
+            radius: 0.5,
+        }
+    }
+}
+
+impl PostEffect for Vignette {
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        let cx = width as f32 * 0.5;
+        let cy = height as f32 * 0.5;
+        // Squared distance from the center to a corner, for normalizing
+        let corner_sq = cx * cx + cy * cy;
+        if corner_sq == 0.0 {
+            return;
+        }
+        let falloff = (1.0 - self.radius).max(f32::EPSILON);
+
+        for (y, row) in pixels.chunks_exact_mut(width as usize).enumerate() {
+            let dy = y as f32 + 0.5 - cy;
+            for (x, p) in row.iter_mut().enumerate() {
+                let dx = x as f32 + 0.5 - cx;
+                let d = ((dx * dx + dy * dy) / corner_sq).sqrt();
+                let t = ((d - self.radius) / falloff).clamp(0.0, 1.0);
+                let scale = 1.0 - self.strength * t * t;
+                if scale < 1.0 {
+                    *p = colors::modulate(*p, scale.max(0.0));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grayscale_equalizes_channels_and_keeps_alpha() {
+        let mut pixels = [0xFFFF0000, 0x80FFFFFF, 0xFF000000];
+        Grayscale.apply(&mut pixels, 3, 1);
+
+        assert_eq!(pixels[0], 0xFF4C4C4C);
+        assert_eq!(pixels[1], 0x80FFFFFF);
+        assert_eq!(pixels[2], 0xFF000000);
+    }
+
+    #[test]
+    fn vignette_darkens_corners_not_center() {
+        const W: u32 = 16;
+        const H: u32 = 16;
+        let mut pixels = vec![0xFFFFFFFF; (W * H) as usize];
+        Vignette::default().apply(&mut pixels, W, H);
+
+        let center = pixels[(H / 2 * W + W / 2) as usize];
+        let corner = pixels[0];
+        assert_eq!(center, 0xFFFFFFFF);
+        assert!(corner & 0xFF < 0xC0, "corner too bright: {corner:08X}");
+    }
+
+    #[test]
+    fn closures_are_effects() {
+        let mut pixels = [0xFF123456];
+        let mut invert = |pixels: &mut [u32], _: u32, _: u32| {
+            for p in pixels {
+                *p ^= 0x00FFFFFF;
+            }
+        };
+        invert.apply(&mut pixels, 1, 1);
+        assert_eq!(pixels[0], 0xFFEDCBA9);
+    }
+}
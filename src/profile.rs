@@ -0,0 +1,336 @@
+//! Lightweight frame profiling: where a frame's time goes, stage by stage.
+//!
+//! A [`scope`] times the code up to the end of its block and adds it to
+//! the current frame's total for its name:
+//!
+//! ```ignore
+//! {
+//!     let _timer = profile::scope("physics");
+//!     step_physics(dt);
+//! }
+//! ```
+//!
+//! Timers only run on a thread where profiling is on, which
+//! [`crate::Engine::set_profiling`] arranges for the thread driving the
+//! engine; elsewhere a scope costs one flag check. The engine times its own
+//! stages ([`WORLD_TRANSFORM`] through [`PRESENT`]), closes the frame at the
+//! end of each render and keeps min/avg/max over recent frames in a
+//! [`FrameProfile`].
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Model to world space, face culling and lighting.
+pub const WORLD_TRANSFORM: &str = "world transform";
+/// Projection to clip space and clipping against the view volume.
+pub const CLIPPING: &str = "clipping";
+/// Perspective divide and viewport transform into screen triangles.
+pub const PROJECTION: &str = "projection";
+/// Filling triangles, including any depth pre-pass.
+pub const FILL: &str = "fill";
+/// The deferred pipeline's screen-space lighting pass.
+pub const LIGHTING: &str = "lighting";
+/// Wireframe edges and vertex markers.
+pub const WIREFRAME: &str = "wireframe";
+/// The HUD, resolve, post effect and capture after drawing.
+pub const PRESENT: &str = "present";
+
+/// The groups of stages [`FrameProfile::summary`] reports, by label.
+const SUMMARY: [(&str, &[&str]); 4] = [
+    ("geo", &[WORLD_TRANSFORM, CLIPPING, PROJECTION]),
+    ("raster", &[FILL, LIGHTING]),
+    ("lines", &[WIREFRAME]),
+    ("present", &[PRESENT]),
+];
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static CURRENT: RefCell<Vec<(&'static str, Duration)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Turn timing on or off for scopes on this thread.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|flag| flag.set(enabled));
+}
+
+/// Whether scopes on this thread are timed.
+pub fn is_enabled() -> bool {
+    ENABLED.with(Cell::get)
+}
+
+/// Start timing `name` until the returned guard drops. Does nothing when
+/// profiling is off on this thread.
+pub fn scope(name: &'static str) -> Scope {
+    Scope {
+        name,
+        start: is_enabled().then(Instant::now),
+    }
+}
+
+/// Add `elapsed` to this frame's total for `name`, for time measured some
+/// other way than a [`scope`]. Ignored when profiling is off.
+pub fn record(name: &'static str, elapsed: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        match current.iter_mut().find(|(stage, _)| *stage == name) {
+            Some((_, total)) => *total += elapsed,
+            None => current.push((name, elapsed)),
+        }
+    });
+}
+
+/// The totals recorded on this thread since the last call, in the order
+/// the stages first ran.
+pub(crate) fn take_frame() -> Vec<(&'static str, Duration)> {
+    CURRENT.with(|current| std::mem::take(&mut *current.borrow_mut()))
+}
+
+/// Times its stage from [`scope`] until dropped.
+#[must_use = "the scope is timed until the guard drops"]
+pub struct Scope {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.name, start.elapsed());
+        }
+    }
+}
+
+/// One stage's time per frame over a [`FrameProfile`]'s window. Frames in
+/// which the stage didn't run count as zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StageTiming {
+    pub name: &'static str,
+    /// The most recent frame.
+    pub last: Duration,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+}
+
+/// Per-stage frame times over a rolling window of recent frames.
+#[derive(Debug, Clone)]
+pub struct FrameProfile {
+    window: usize,
+    frames: VecDeque<Vec<(&'static str, Duration)>>,
+    // Every stage seen, in the order each first ran
+    names: Vec<&'static str>,
+    log_interval: Option<Duration>,
+    last_log: Option<Instant>,
+}
+
+impl FrameProfile {
+    /// Frames kept by [`FrameProfile::default`]: about a second at 60 FPS.
+    pub const DEFAULT_WINDOW: usize = 60;
+
+    /// Keep the last `window` frames (at least one).
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            frames: VecDeque::new(),
+            names: Vec::new(),
+            log_interval: None,
+            last_log: None,
+        }
+    }
+
+    /// Frames currently in the window.
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Timings for `name`, or `None` if no frame in the window ran it.
+    pub fn stage(&self, name: &str) -> Option<StageTiming> {
+        let name = *self.names.iter().find(|&&n| n == name)?;
+        if !self
+            .frames
+            .iter()
+            .any(|frame| frame.iter().any(|(n, _)| *n == name))
+        {
+            return None;
+        }
+        let per_frame: Vec<Duration> = self
+            .frames
+            .iter()
+            .map(|frame| stage_time(frame, name))
+            .collect();
+        Some(StageTiming {
+            name,
+            last: per_frame.last().copied().unwrap_or_default(),
+            min: per_frame.iter().min().copied().unwrap_or_default(),
+            avg: per_frame.iter().sum::<Duration>() / per_frame.len() as u32,
+            max: per_frame.iter().max().copied().unwrap_or_default(),
+        })
+    }
+
+    /// Timings for every stage in the window, in the order each first ran.
+    pub fn stages(&self) -> Vec<StageTiming> {
+        self.names
+            .iter()
+            .filter_map(|name| self.stage(name))
+            .collect()
+    }
+
+    /// Average milliseconds per frame for the engine's stages, grouped as
+    /// `geo 2.1ms | raster 9.8ms | lines 0.7ms | present 0.4ms`.
+    pub fn summary(&self) -> String {
+        let average = |names: &[&str]| -> Duration {
+            names
+                .iter()
+                .filter_map(|name| self.stage(name))
+                .map(|timing| timing.avg)
+                .sum()
+        };
+        SUMMARY
+            .iter()
+            .map(|(label, names)| format!("{label} {:.1}ms", millis(average(names))))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Print [`FrameProfile::summary`] to stderr at most once per
+    /// `interval` as frames come in, or never with `None` (the default).
+    pub fn set_log_interval(&mut self, interval: Option<Duration>) {
+        self.log_interval = interval;
+        self.last_log = None;
+    }
+
+    pub fn log_interval(&self) -> Option<Duration> {
+        self.log_interval
+    }
+
+    /// Add a finished frame's totals, dropping the oldest frame once the
+    /// window is full.
+    pub fn push_frame(&mut self, frame: Vec<(&'static str, Duration)>) {
+        for (name, _) in &frame {
+            if !self.names.contains(name) {
+                self.names.push(name);
+            }
+        }
+        if self.frames.len() == self.window {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+
+        if let Some(interval) = self.log_interval {
+            let now = Instant::now();
+            let last = *self.last_log.get_or_insert(now);
+            if now.duration_since(last) >= interval {
+                eprintln!("profile: {}", self.summary());
+                self.last_log = Some(now);
+            }
+        }
+    }
+}
+
+impl Default for FrameProfile {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_WINDOW)
+    }
+}
+
+impl fmt::Display for FrameProfile {
+    /// One line per stage: average, then min and max, in milliseconds.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for timing in self.stages() {
+            writeln!(
+                f,
+                "{:<16} {:>6.2} ms  ({:.2} - {:.2})",
+                timing.name,
+                millis(timing.avg),
+                millis(timing.min),
+                millis(timing.max)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn stage_time(frame: &[(&'static str, Duration)], name: &str) -> Duration {
+    frame
+        .iter()
+        .find(|(stage, _)| *stage == name)
+        .map_or(Duration::ZERO, |(_, time)| *time)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn scopes_only_time_while_enabled() {
+        set_enabled(false);
+        drop(scope(FILL));
+        record(FILL, ms(1));
+        assert!(take_frame().is_empty());
+
+        set_enabled(true);
+        drop(scope(FILL));
+        record(FILL, ms(1));
+        record(WIREFRAME, ms(2));
+        let frame = take_frame();
+        set_enabled(false);
+        assert_eq!(frame.len(), 2);
+        assert_eq!(frame[0].0, FILL);
+        assert!(frame[0].1 >= ms(1));
+        assert_eq!(frame[1], (WIREFRAME, ms(2)));
+        assert!(take_frame().is_empty());
+    }
+
+    #[test]
+    fn profile_keeps_min_avg_max_over_its_window() {
+        let mut profile = FrameProfile::new(3);
+        for fill in [4, 1, 2, 6] {
+            profile.push_frame(vec![(FILL, ms(fill))]);
+        }
+        // The first frame has rolled out; wireframe never ran
+        profile.push_frame(vec![(FILL, ms(3)), (WIREFRAME, ms(3))]);
+        assert_eq!(profile.frames(), 3);
+
+        let fill = profile.stage(FILL).unwrap();
+        assert_eq!(
+            (fill.last, fill.min, fill.avg, fill.max),
+            (ms(3), ms(2), ms(11) / 3, ms(6))
+        );
+        // Missing from two of the three frames
+        assert_eq!(profile.stage(WIREFRAME).unwrap().avg, ms(1));
+        assert_eq!(profile.stage(CLIPPING), None);
+        assert_eq!(
+            profile.stages().iter().map(|s| s.name).collect::<Vec<_>>(),
+            [FILL, WIREFRAME]
+        );
+    }
+
+    #[test]
+    fn summary_groups_the_geometry_stages() {
+        let mut profile = FrameProfile::default();
+        profile.push_frame(vec![
+            (WORLD_TRANSFORM, ms(1)),
+            (CLIPPING, Duration::from_micros(500)),
+            (PROJECTION, Duration::from_micros(600)),
+            (FILL, Duration::from_micros(9800)),
+            (WIREFRAME, Duration::from_micros(700)),
+        ]);
+        assert_eq!(
+            profile.summary(),
+            "geo 2.1ms | raster 9.8ms | lines 0.7ms | present 0.0ms"
+        );
+    }
+}
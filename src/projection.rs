@@ -2,9 +2,83 @@
 //!
 //! The [`Projection`] struct is the single source of truth for all perspective
 //! projection parameters (FOV, aspect ratio, near/far planes). It can generate
-//! the projection matrix.
+//! the projection matrix, and owns the [`Handedness`] convention the rest of
+//! the pipeline follows. The view frustum is extracted from that matrix, so
+//! clipping and culling always agree with it.
 
 use crate::math::mat4::Mat4;
+use crate::math::vec3::Vec3;
+
+/// Which way the camera looks along view-space z, and so which winding
+/// faces it.
+///
+/// The two conventions are mirror images across the z = 0 plane: a scene
+/// and camera mirrored in z, with face winding reversed, render the same
+/// image under the other convention. Everything that depends on the choice
+/// derives it from here.
+///
+/// Backface culling needs no sign of its own: `(b - a) × (c - a)` points
+/// at the viewer for clockwise faces under the left-hand rule and for
+/// counter-clockwise faces under the right-hand rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Handedness {
+    /// +Z forward, clockwise front faces (DirectX-style). The default.
+    #[default]
+    Left,
+    /// -Z forward, counter-clockwise front faces (OpenGL / glTF-style).
+    Right,
+}
+
+impl Handedness {
+    /// The z component of the view direction in view space: `1.0` or
+    /// `-1.0`.
+    pub fn forward_z(self) -> f32 {
+        match self {
+            Handedness::Left => 1.0,
+            Handedness::Right => -1.0,
+        }
+    }
+
+    /// Mirror `v` across the z = 0 plane when right-handed; identity when
+    /// left-handed. Maps between a direction and its left-handed equivalent.
+    pub fn mirror(self, v: Vec3) -> Vec3 {
+        Vec3::new(v.x, v.y, v.z * self.forward_z())
+    }
+
+    /// Conjugate `m` by the z mirror (`S * m * S`) when right-handed, so a
+    /// left-handed rotation turns the mirrored way.
+    pub(crate) fn mirror_matrix(self, m: Mat4) -> Mat4 {
+        match self {
+            Handedness::Left => m,
+            Handedness::Right => {
+                let s = Mat4::scaling(1.0, 1.0, -1.0);
+                s * m * s
+            }
+        }
+    }
+}
+
+/// Which screen axis a [`Projection`]'s field of view spans. The other
+/// follows from the aspect ratio, so the chosen one is what stays fixed
+/// when the window is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FovAxis {
+    /// Top to bottom edge (the default): widening the window shows more
+    /// at the sides.
+    #[default]
+    Vertical,
+    /// Left to right edge: widening the window shows less above and below.
+    Horizontal,
+}
+
+/// Narrowest field of view [`Projection::set_fov`] accepts along either
+/// axis, in degrees.
+pub const MIN_FOV_DEGREES: f32 = 1.0;
+/// Widest field of view [`Projection::set_fov`] accepts along either axis,
+/// in degrees. At 180 the projection degenerates.
+pub const MAX_FOV_DEGREES: f32 = 179.0;
+/// Closest near plane [`Projection::set_clip_planes`] accepts.
+pub const MIN_Z_NEAR: f32 = 1e-4;
 
 /// Perspective projection parameters.
 ///
@@ -12,14 +86,17 @@ use crate::math::mat4::Mat4;
 /// the projection matrix.
 #[derive(Debug, Clone, Copy)]
 pub struct Projection {
-    /// Vertical field of view in radians.
-    fov_y: f32,
+    /// Field of view in radians, along `fov_axis`.
+    fov: f32,
+    fov_axis: FovAxis,
     /// Aspect ratio (width / height).
     aspect_ratio: f32,
     /// Near clipping plane distance.
     z_near: f32,
     /// Far clipping plane distance.
     z_far: f32,
+    /// Whether the camera looks down +z or -z.
+    handedness: Handedness,
 }
 
 impl Projection {
@@ -32,10 +109,12 @@ impl Projection {
     /// * `z_far` - Far clipping plane distance (must be > z_near)
     pub fn new(fov_y: f32, aspect_ratio: f32, z_near: f32, z_far: f32) -> Self {
         Self {
-            fov_y,
+            fov: fov_y,
+            fov_axis: FovAxis::Vertical,
             aspect_ratio,
             z_near,
             z_far,
+            handedness: Handedness::Left,
         }
     }
 
@@ -44,16 +123,36 @@ impl Projection {
         Self::new(fov_y_degrees.to_radians(), aspect_ratio, z_near, z_far)
     }
 
+    /// Returns the field of view in radians along [`Projection::fov_axis`].
+    pub fn fov(&self) -> f32 {
+        self.fov
+    }
+
+    /// Returns the axis the field of view was given along.
+    pub fn fov_axis(&self) -> FovAxis {
+        self.fov_axis
+    }
+
     /// Returns the vertical field of view in radians.
+    ///
+    /// Computed from the horizontal FOV and aspect ratio when that is the
+    /// one given.
     pub fn fov_y(&self) -> f32 {
-        self.fov_y
+        match self.fov_axis {
+            FovAxis::Vertical => self.fov,
+            FovAxis::Horizontal => 2.0 * ((self.fov / 2.0).tan() / self.aspect_ratio).atan(),
+        }
     }
 
     /// Returns the horizontal field of view in radians.
     ///
-    /// Computed from the vertical FOV and aspect ratio.
+    /// Computed from the vertical FOV and aspect ratio when that is the
+    /// one given.
     pub fn fov_x(&self) -> f32 {
-        2.0 * (self.aspect_ratio * (self.fov_y / 2.0).tan()).atan()
+        match self.fov_axis {
+            FovAxis::Vertical => 2.0 * (self.aspect_ratio * (self.fov / 2.0).tan()).atan(),
+            FovAxis::Horizontal => self.fov,
+        }
     }
 
     /// Returns the aspect ratio (width / height).
@@ -71,22 +170,85 @@ impl Projection {
         self.z_far
     }
 
+    /// Sets the field of view in radians along `axis`, clamped to
+    /// [`MIN_FOV_DEGREES`]..=[`MAX_FOV_DEGREES`]. From then on the other
+    /// axis follows the aspect ratio. Ignores non-finite values.
+    pub fn set_fov(&mut self, fov: f32, axis: FovAxis) {
+        if fov.is_finite() {
+            self.fov = fov.clamp(MIN_FOV_DEGREES.to_radians(), MAX_FOV_DEGREES.to_radians());
+            self.fov_axis = axis;
+        }
+    }
+
+    /// Sets the vertical field of view in radians; see
+    /// [`Projection::set_fov`].
+    pub fn set_fov_y(&mut self, fov_y: f32) {
+        self.set_fov(fov_y, FovAxis::Vertical);
+    }
+
+    /// Sets the horizontal field of view in radians; see
+    /// [`Projection::set_fov`].
+    pub fn set_fov_x(&mut self, fov_x: f32) {
+        self.set_fov(fov_x, FovAxis::Horizontal);
+    }
+
+    /// Sets the near and far clipping plane distances.
+    ///
+    /// `z_near` is raised to at least [`MIN_Z_NEAR`], and `z_far` to just
+    /// past `z_near`, so the projection stays valid. Ignores non-finite
+    /// values.
+    pub fn set_clip_planes(&mut self, z_near: f32, z_far: f32) {
+        if !z_near.is_finite() || !z_far.is_finite() {
+            return;
+        }
+        self.z_near = z_near.max(MIN_Z_NEAR);
+        // Relative gap so it survives rounding at any scale
+        self.z_far = z_far.max(self.z_near * (1.0 + 1e-3));
+    }
+
     /// Updates the aspect ratio (typically called on window resize).
     pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
         self.aspect_ratio = aspect_ratio;
     }
 
-    /// Generates the left-handed perspective projection matrix.
+    /// Returns the coordinate-system convention.
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    /// Switches between left- and right-handed projection.
+    pub fn set_handedness(&mut self, handedness: Handedness) {
+        self.handedness = handedness;
+    }
+
+    /// Generates the perspective projection matrix for the current
+    /// handedness.
     pub fn matrix(&self) -> Mat4 {
-        Mat4::perspective_lh(self.fov_y, self.aspect_ratio, self.z_near, self.z_far)
+        match self.handedness {
+            Handedness::Left => {
+                Mat4::perspective_lh(self.fov_y(), self.aspect_ratio, self.z_near, self.z_far)
+            }
+            Handedness::Right => {
+                Mat4::perspective_rh(self.fov_y(), self.aspect_ratio, self.z_near, self.z_far)
+            }
+        }
+    }
+
+    /// Maps clip space back to view space. Used to unproject screen points.
+    ///
+    /// A perspective matrix with valid parameters is always invertible, so
+    /// this only falls back to identity for degenerate near/far/FOV values.
+    pub fn inverse_matrix(&self) -> Mat4 {
+        self.matrix().inverse().unwrap_or(Mat4::identity())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::vec4::Vec4;
     use approx::assert_relative_eq;
-    use std::f32::consts::FRAC_PI_4;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
 
     #[test]
     fn fov_x_matches_aspect_ratio() {
@@ -107,4 +269,91 @@ mod tests {
         let proj = Projection::from_degrees(45.0, 1.0, 0.1, 100.0);
         assert_relative_eq!(proj.fov_y(), FRAC_PI_4, epsilon = 1e-6);
     }
+
+    #[test]
+    fn inverse_matrix_round_trips_view_point() {
+        let proj = Projection::new(FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+        let view_point = Vec4::new(1.5, -0.5, 7.0, 1.0);
+
+        let clip = proj.matrix() * view_point;
+        let back = proj.inverse_matrix() * clip;
+        assert_relative_eq!(back.x / back.w, view_point.x, epsilon = 1e-4);
+        assert_relative_eq!(back.y / back.w, view_point.y, epsilon = 1e-4);
+        assert_relative_eq!(back.z / back.w, view_point.z, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn mirrored_points_project_to_the_same_ndc() {
+        let mut proj = Projection::new(FRAC_PI_4, 16.0 / 9.0, 0.1, 100.0);
+        let lh = proj.matrix() * Vec4::new(1.5, -0.5, 7.0, 1.0);
+        proj.set_handedness(Handedness::Right);
+        let rh = proj.matrix() * Vec4::new(1.5, -0.5, -7.0, 1.0);
+
+        assert_relative_eq!(lh.w, 7.0);
+        assert_relative_eq!(rh.w, 7.0);
+        assert_relative_eq!(lh.x / lh.w, rh.x / rh.w, epsilon = 1e-6);
+        assert_relative_eq!(lh.y / lh.w, rh.y / rh.w, epsilon = 1e-6);
+        assert_relative_eq!(lh.z / lh.w, rh.z / rh.w, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn right_handed_depth_range_is_minus_one_to_one() {
+        let proj = Mat4::perspective_rh(FRAC_PI_4, 1.0, 0.1, 100.0);
+        let near = proj * Vec4::new(0.0, 0.0, -0.1, 1.0);
+        let far = proj * Vec4::new(0.0, 0.0, -100.0, 1.0);
+        assert_relative_eq!(near.z / near.w, -1.0, epsilon = 1e-4);
+        assert_relative_eq!(far.z / far.w, 1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn fov_is_clamped_to_a_valid_range() {
+        let mut proj = Projection::new(FRAC_PI_4, 1.0, 0.1, 100.0);
+        proj.set_fov_y(0.0);
+        assert_relative_eq!(proj.fov_y(), MIN_FOV_DEGREES.to_radians());
+        proj.set_fov_y(std::f32::consts::PI);
+        assert_relative_eq!(proj.fov_y(), MAX_FOV_DEGREES.to_radians());
+        proj.set_fov_y(f32::NAN);
+        assert_relative_eq!(proj.fov_y(), MAX_FOV_DEGREES.to_radians());
+    }
+
+    #[test]
+    fn clip_planes_stay_ordered_and_positive() {
+        let mut proj = Projection::new(FRAC_PI_4, 1.0, 0.1, 100.0);
+        proj.set_clip_planes(-1.0, 50.0);
+        assert_relative_eq!(proj.z_near(), MIN_Z_NEAR);
+        assert_relative_eq!(proj.z_far(), 50.0);
+
+        proj.set_clip_planes(10.0, 5.0);
+        assert_relative_eq!(proj.z_near(), 10.0);
+        assert!(proj.z_far() > proj.z_near());
+
+        proj.set_clip_planes(1.0, f32::INFINITY);
+        assert_relative_eq!(proj.z_near(), 10.0);
+
+        let m = proj.matrix();
+        assert!((0..4).all(|r| (0..4).all(|c| m.get(r, c).is_finite())));
+    }
+
+    #[test]
+    fn horizontal_fov_stays_fixed_across_aspect_ratios() {
+        let mut proj = Projection::new(FRAC_PI_4, 1.0, 0.1, 100.0);
+        proj.set_fov_x(FRAC_PI_2);
+        assert_eq!(proj.fov_axis(), FovAxis::Horizontal);
+        for aspect in [0.5, 1.0, 4.0 / 3.0, 16.0 / 9.0, 3.0] {
+            proj.set_aspect_ratio(aspect);
+            assert_relative_eq!(proj.fov_x(), FRAC_PI_2, epsilon = 1e-6);
+            // The derived vertical FOV round-trips
+            let vertical = Projection::new(proj.fov_y(), aspect, 0.1, 100.0);
+            assert_relative_eq!(vertical.fov_x(), FRAC_PI_2, epsilon = 1e-5);
+
+            // A view-space point on the right edge lands on NDC x = 1
+            let clip = proj.matrix() * Vec4::new(5.0, 0.0, 5.0, 1.0);
+            assert_relative_eq!(clip.x / clip.w, 1.0, epsilon = 1e-5);
+        }
+
+        // Setting the vertical FOV switches back
+        proj.set_fov_y(FRAC_PI_4);
+        assert_eq!(proj.fov_axis(), FovAxis::Vertical);
+        assert_relative_eq!(proj.fov(), FRAC_PI_4);
+    }
 }
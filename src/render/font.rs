@@ -0,0 +1,124 @@
+//! Embedded 8x8 bitmap font for debug text.
+//!
+//! Covers printable ASCII (`' '` through `'~'`). Each glyph is eight rows,
+//! top to bottom; in each row byte the most significant bit is the leftmost
+//! pixel. Glyphs are six pixels wide with two columns of spacing built in,
+//! and lowercase descenders use the bottom row.
+
+/// Width and height of a glyph cell in pixels.
+pub const GLYPH_SIZE: i32 = 8;
+
+const FIRST_CHAR: u8 = b' ';
+
+/// Glyph bitmaps for ASCII 0x20..=0x7E.
+const FONT_8X8: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x30, 0x30, 0x30, 0x30, 0x30, 0x00, 0x30, 0x00], // '!'
+    [0x6C, 0x6C, 0x48, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x6C, 0x6C, 0xFE, 0x6C, 0xFE, 0x6C, 0x6C, 0x00], // '#'
+    [0x30, 0x7C, 0xC0, 0x78, 0x0C, 0xF8, 0x30, 0x00], // '$'
+    [0xC6, 0xCC, 0x18, 0x30, 0x60, 0xCC, 0x8C, 0x00], // '%'
+    [0x38, 0x6C, 0x38, 0x76, 0xDC, 0xCC, 0x76, 0x00], // '&'
+    [0x30, 0x30, 0x60, 0x00, 0x00, 0x00, 0x00, 0x00], // "'"
+    [0x18, 0x30, 0x60, 0x60, 0x60, 0x30, 0x18, 0x00], // '('
+    [0x60, 0x30, 0x18, 0x18, 0x18, 0x30, 0x60, 0x00], // ')'
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+    [0x00, 0x30, 0x30, 0xFC, 0x30, 0x30, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x60], // ','
+    [0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x00], // '.'
+    [0x06, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0x80, 0x00], // '/'
+    [0x78, 0xCC, 0xDC, 0xFC, 0xEC, 0xCC, 0x78, 0x00], // '0'
+    [0x30, 0x70, 0x30, 0x30, 0x30, 0x30, 0xFC, 0x00], // '1'
+    [0x78, 0xCC, 0x0C, 0x38, 0x60, 0xC0, 0xFC, 0x00], // '2'
+    [0x78, 0xCC, 0x0C, 0x38, 0x0C, 0xCC, 0x78, 0x00], // '3'
+    [0x1C, 0x3C, 0x6C, 0xCC, 0xFE, 0x0C, 0x0C, 0x00], // '4'
+    [0xFC, 0xC0, 0xF8, 0x0C, 0x0C, 0xCC, 0x78, 0x00], // '5'
+    [0x38, 0x60, 0xC0, 0xF8, 0xCC, 0xCC, 0x78, 0x00], // '6'
+    [0xFC, 0x0C, 0x18, 0x30, 0x30, 0x30, 0x30, 0x00], // '7'
+    [0x78, 0xCC, 0xCC, 0x78, 0xCC, 0xCC, 0x78, 0x00], // '8'
+    [0x78, 0xCC, 0xCC, 0x7C, 0x0C, 0x18, 0x70, 0x00], // '9'
+    [0x00, 0x30, 0x30, 0x00, 0x00, 0x30, 0x30, 0x00], // ':'
+    [0x00, 0x30, 0x30, 0x00, 0x00, 0x30, 0x30, 0x60], // ';'
+    [0x18, 0x30, 0x60, 0xC0, 0x60, 0x30, 0x18, 0x00], // '<'
+    [0x00, 0x00, 0xFC, 0x00, 0x00, 0xFC, 0x00, 0x00], // '='
+    [0x60, 0x30, 0x18, 0x0C, 0x18, 0x30, 0x60, 0x00], // '>'
+    [0x78, 0xCC, 0x0C, 0x18, 0x30, 0x00, 0x30, 0x00], // '?'
+    [0x7C, 0xC6, 0xDE, 0xDE, 0xDE, 0xC0, 0x78, 0x00], // '@'
+    [0x30, 0x78, 0xCC, 0xCC, 0xFC, 0xCC, 0xCC, 0x00], // 'A'
+    [0xF8, 0xCC, 0xCC, 0xF8, 0xCC, 0xCC, 0xF8, 0x00], // 'B'
+    [0x78, 0xCC, 0xC0, 0xC0, 0xC0, 0xCC, 0x78, 0x00], // 'C'
+    [0xF0, 0xD8, 0xCC, 0xCC, 0xCC, 0xD8, 0xF0, 0x00], // 'D'
+    [0xFC, 0xC0, 0xC0, 0xF8, 0xC0, 0xC0, 0xFC, 0x00], // 'E'
+    [0xFC, 0xC0, 0xC0, 0xF8, 0xC0, 0xC0, 0xC0, 0x00], // 'F'
+    [0x78, 0xCC, 0xC0, 0xDC, 0xCC, 0xCC, 0x7C, 0x00], // 'G'
+    [0xCC, 0xCC, 0xCC, 0xFC, 0xCC, 0xCC, 0xCC, 0x00], // 'H'
+    [0x78, 0x30, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00], // 'I'
+    [0x1E, 0x0C, 0x0C, 0x0C, 0xCC, 0xCC, 0x78, 0x00], // 'J'
+    [0xCC, 0xD8, 0xF0, 0xE0, 0xF0, 0xD8, 0xCC, 0x00], // 'K'
+    [0xC0, 0xC0, 0xC0, 0xC0, 0xC0, 0xC0, 0xFC, 0x00], // 'L'
+    [0xC6, 0xEE, 0xFE, 0xD6, 0xC6, 0xC6, 0xC6, 0x00], // 'M'
+    [0xCC, 0xEC, 0xFC, 0xDC, 0xCC, 0xCC, 0xCC, 0x00], // 'N'
+    [0x78, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0x78, 0x00], // 'O'
+    [0xF8, 0xCC, 0xCC, 0xF8, 0xC0, 0xC0, 0xC0, 0x00], // 'P'
+    [0x78, 0xCC, 0xCC, 0xCC, 0xDC, 0x78, 0x0C, 0x00], // 'Q'
+    [0xF8, 0xCC, 0xCC, 0xF8, 0xF0, 0xD8, 0xCC, 0x00], // 'R'
+    [0x78, 0xCC, 0xC0, 0x78, 0x0C, 0xCC, 0x78, 0x00], // 'S'
+    [0xFC, 0x30, 0x30, 0x30, 0x30, 0x30, 0x30, 0x00], // 'T'
+    [0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0x78, 0x00], // 'U'
+    [0xCC, 0xCC, 0xCC, 0xCC, 0xCC, 0x78, 0x30, 0x00], // 'V'
+    [0xC6, 0xC6, 0xC6, 0xD6, 0xFE, 0xEE, 0xC6, 0x00], // 'W'
+    [0xCC, 0xCC, 0x78, 0x30, 0x78, 0xCC, 0xCC, 0x00], // 'X'
+    [0xCC, 0xCC, 0xCC, 0x78, 0x30, 0x30, 0x30, 0x00], // 'Y'
+    [0xFC, 0x0C, 0x18, 0x30, 0x60, 0xC0, 0xFC, 0x00], // 'Z'
+    [0x78, 0x60, 0x60, 0x60, 0x60, 0x60, 0x78, 0x00], // '['
+    [0x80, 0xC0, 0x60, 0x30, 0x18, 0x0C, 0x06, 0x00], // '\\'
+    [0x78, 0x18, 0x18, 0x18, 0x18, 0x18, 0x78, 0x00], // ']'
+    [0x10, 0x38, 0x6C, 0xC6, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+    [0x30, 0x30, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x78, 0x0C, 0x7C, 0xCC, 0x7C, 0x00], // 'a'
+    [0xC0, 0xC0, 0xF8, 0xCC, 0xCC, 0xCC, 0xF8, 0x00], // 'b'
+    [0x00, 0x00, 0x78, 0xC0, 0xC0, 0xC0, 0x78, 0x00], // 'c'
+    [0x0C, 0x0C, 0x7C, 0xCC, 0xCC, 0xCC, 0x7C, 0x00], // 'd'
+    [0x00, 0x00, 0x78, 0xCC, 0xFC, 0xC0, 0x78, 0x00], // 'e'
+    [0x38, 0x60, 0x60, 0xF0, 0x60, 0x60, 0x60, 0x00], // 'f'
+    [0x00, 0x00, 0x7C, 0xCC, 0xCC, 0x7C, 0x0C, 0xF8], // 'g'
+    [0xC0, 0xC0, 0xF8, 0xCC, 0xCC, 0xCC, 0xCC, 0x00], // 'h'
+    [0x30, 0x00, 0x70, 0x30, 0x30, 0x30, 0x78, 0x00], // 'i'
+    [0x0C, 0x00, 0x1C, 0x0C, 0x0C, 0x0C, 0xCC, 0x78], // 'j'
+    [0xC0, 0xC0, 0xCC, 0xD8, 0xF0, 0xD8, 0xCC, 0x00], // 'k'
+    [0x70, 0x30, 0x30, 0x30, 0x30, 0x30, 0x78, 0x00], // 'l'
+    [0x00, 0x00, 0xCC, 0xFE, 0xFE, 0xD6, 0xC6, 0x00], // 'm'
+    [0x00, 0x00, 0xF8, 0xCC, 0xCC, 0xCC, 0xCC, 0x00], // 'n'
+    [0x00, 0x00, 0x78, 0xCC, 0xCC, 0xCC, 0x78, 0x00], // 'o'
+    [0x00, 0x00, 0xF8, 0xCC, 0xCC, 0xF8, 0xC0, 0xC0], // 'p'
+    [0x00, 0x00, 0x7C, 0xCC, 0xCC, 0x7C, 0x0C, 0x0C], // 'q'
+    [0x00, 0x00, 0xDC, 0xE0, 0xC0, 0xC0, 0xC0, 0x00], // 'r'
+    [0x00, 0x00, 0x7C, 0xC0, 0x78, 0x0C, 0xF8, 0x00], // 's'
+    [0x60, 0x60, 0xF8, 0x60, 0x60, 0x60, 0x38, 0x00], // 't'
+    [0x00, 0x00, 0xCC, 0xCC, 0xCC, 0xCC, 0x7C, 0x00], // 'u'
+    [0x00, 0x00, 0xCC, 0xCC, 0xCC, 0x78, 0x30, 0x00], // 'v'
+    [0x00, 0x00, 0xC6, 0xD6, 0xFE, 0xFE, 0x6C, 0x00], // 'w'
+    [0x00, 0x00, 0xCC, 0x78, 0x30, 0x78, 0xCC, 0x00], // 'x'
+    [0x00, 0x00, 0xCC, 0xCC, 0xCC, 0x7C, 0x0C, 0xF8], // 'y'
+    [0x00, 0x00, 0xFC, 0x18, 0x30, 0x60, 0xFC, 0x00], // 'z'
+    [0x1C, 0x30, 0x30, 0xE0, 0x30, 0x30, 0x1C, 0x00], // '{'
+    [0x30, 0x30, 0x30, 0x00, 0x30, 0x30, 0x30, 0x00], // '|'
+    [0xE0, 0x30, 0x30, 0x1C, 0x30, 0x30, 0xE0, 0x00], // '}'
+    [0x76, 0xDC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
+
+/// Returns the bitmap for `c`, or a filled box for characters outside
+/// printable ASCII so missing glyphs are obvious.
+pub fn glyph(c: char) -> [u8; 8] {
+    const MISSING: [u8; 8] = [0xFC, 0x84, 0x84, 0x84, 0x84, 0x84, 0xFC, 0x00];
+    if c.is_ascii() && (c as u8) >= FIRST_CHAR {
+        FONT_8X8
+            .get((c as u8 - FIRST_CHAR) as usize)
+            .copied()
+            .unwrap_or(MISSING)
+    } else {
+        MISSING
+    }
+}
@@ -2,6 +2,181 @@
 //!
 //! Provides a safe view into color and depth buffers with bounds-checked access.
 //! The depth buffer enables proper hidden surface removal via z-buffer algorithm.
+//! An optional ID buffer records which primitive won the depth test at each pixel.
+
+use super::rasterizer::{Fragment, PixelShader};
+use super::target::pack_normal;
+use crate::colors;
+use crate::light::PixelLighting;
+use crate::math::vec3::Vec3;
+
+/// What the depth buffer stores.
+///
+/// Every mode is an affine function of 1/w, so all of them interpolate
+/// linearly in screen space; rasterizers keep interpolating 1/w and the
+/// [`DepthEncoding`] converts on write. They differ in where f32 precision
+/// lands: standard NDC z crowds the far range up against 1.0, while 1/w and
+/// reversed NDC put the far range near 0.0, where floats are densest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthMode {
+    /// Reciprocal clip-space w; larger is closer, cleared to 0.0.
+    #[default]
+    InvW,
+    /// NDC z in [-1, 1]; smaller is closer, cleared to 1.0 (the far plane).
+    Ndc,
+    /// NDC z remapped to [0, 1] and flipped so near = 1.0 and far = 0.0;
+    /// larger is closer, cleared to 0.0.
+    ReversedNdc,
+}
+
+/// Converts interpolated 1/w to a stored depth value for a [`DepthMode`],
+/// and knows which direction of the comparison is "closer".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthEncoding {
+    mode: DepthMode,
+    // stored = offset + scale * (1/w)
+    offset: f32,
+    scale: f32,
+}
+
+impl DepthEncoding {
+    /// Store 1/w unchanged.
+    pub const INV_W: Self = Self {
+        mode: DepthMode::InvW,
+        offset: 0.0,
+        scale: 1.0,
+    };
+
+    /// Encoding for `mode` under a perspective projection with the given
+    /// near and far planes.
+    pub fn new(mode: DepthMode, near: f32, far: f32) -> Self {
+        // perspective_lh: z_ndc = a + b / w
+        let a = (far + near) / (far - near);
+        let b = -2.0 * far * near / (far - near);
+        let (offset, scale) = match mode {
+            DepthMode::InvW => (0.0, 1.0),
+            DepthMode::Ndc => (a, b),
+            // (1 - z_ndc) / 2, simplified so far depths don't cancel
+            DepthMode::ReversedNdc => (-near / (far - near), far * near / (far - near)),
+        };
+        Self {
+            mode,
+            offset,
+            scale,
+        }
+    }
+
+    pub fn mode(&self) -> DepthMode {
+        self.mode
+    }
+
+    /// Stored depth for an interpolated 1/w.
+    #[inline]
+    pub fn encode(&self, inv_w: f32) -> f32 {
+        self.offset + self.scale * inv_w
+    }
+
+    /// Recover 1/w from a stored depth.
+    #[inline]
+    pub fn decode(&self, depth: f32) -> f32 {
+        (depth - self.offset) / self.scale
+    }
+
+    /// Depth test: does `depth` lie in front of `stored`?
+    #[inline]
+    pub fn passes(&self, depth: f32, stored: f32) -> bool {
+        match self.mode {
+            DepthMode::Ndc => depth < stored,
+            DepthMode::InvW | DepthMode::ReversedNdc => depth > stored,
+        }
+    }
+
+    /// The "nothing drawn yet" value the buffer is cleared to.
+    pub fn clear_value(&self) -> f32 {
+        match self.mode {
+            DepthMode::Ndc => 1.0,
+            DepthMode::InvW | DepthMode::ReversedNdc => 0.0,
+        }
+    }
+}
+
+impl Default for DepthEncoding {
+    fn default() -> Self {
+        Self::INV_W
+    }
+}
+
+/// Which part of a depth-tested write a [`FrameBuffer`] performs.
+///
+/// Rendering opaque geometry twice, first [`DepthPass::DepthOnly`] and then
+/// [`DepthPass::Equal`], shades every visible pixel exactly once however
+/// much the triangles overlap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthPass {
+    /// Write color, depth and ID wherever the depth test passes.
+    #[default]
+    Full,
+    /// Write depth only, leaving color and ID alone; nothing is shaded.
+    DepthOnly,
+    /// Write color and ID only where the depth is no farther than what is
+    /// stored, without writing depth: after a depth-only pass, exactly the
+    /// surface that pass kept.
+    Equal,
+}
+
+/// Which pixels a mask-tested write may touch, by the value in the mask
+/// buffer. Like a GPU stencil test, it runs alongside the depth test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MaskTest {
+    /// Ignore the mask.
+    #[default]
+    Always,
+    /// Only pixels whose mask holds this value.
+    Equal(u8),
+    /// Only pixels whose mask doesn't hold this value.
+    NotEqual(u8),
+}
+
+impl MaskTest {
+    /// Whether a pixel with mask value `stored` passes.
+    #[inline]
+    pub fn passes(self, stored: u8) -> bool {
+        match self {
+            MaskTest::Always => true,
+            MaskTest::Equal(value) => stored == value,
+            MaskTest::NotEqual(value) => stored != value,
+        }
+    }
+}
+
+/// How a shaded pixel combines with the color already in the buffer, by
+/// the alpha byte of the shaded color. Like GPU blending, it runs after
+/// the depth and mask tests and only for shaded writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Replace the stored color, whatever the alpha.
+    #[default]
+    Opaque,
+    /// Discard pixels whose alpha is below one half, write the rest as
+    /// `Opaque`. Order-independent, but edges are hard.
+    Cutout,
+    /// Blend over the stored color by alpha, without writing depth, so
+    /// translucent pixels never hide what is drawn after them. Draw
+    /// back to front.
+    Alpha,
+    /// Add the shaded color to the stored one per channel, saturating at
+    /// white, without writing depth: every layer drawn over a pixel
+    /// brightens it, in any order.
+    Additive,
+}
+
+/// Lowest alpha [`BlendMode::Cutout`] keeps.
+const CUTOUT_ALPHA: u32 = 0x80;
+
+/// Value a mask write stores; [`Renderer::clear_mask`] resets pixels to 0.
+///
+/// [`Renderer::clear_mask`]: super::Renderer::clear_mask
+pub const MASK_SET: u8 = 1;
 
 /// A view into color and depth buffers.
 ///
@@ -11,13 +186,52 @@
 ///
 /// # Depth Buffer
 ///
-/// The depth buffer stores 1/w values (reciprocal of clip-space W) for each pixel.
+/// Callers pass 1/w values (reciprocal of clip-space W) for each pixel.
 /// Using 1/w instead of z because it can be linearly interpolated in screen space.
 /// Larger values are closer to the camera (since w increases with distance in
-/// left-handed coordinates, 1/w decreases).
+/// left-handed coordinates, 1/w decreases). What is actually stored, and how
+/// it is compared, follows the attached [`DepthEncoding`] (1/w by default).
+/// An empty depth slice turns depth testing off: every write passes, so
+/// whatever is drawn last covers what came before.
+///
+/// # ID Buffer
+///
+/// When attached via [`FrameBuffer::with_id_buffer`], every pixel that passes
+/// the depth test in [`FrameBuffer::set_pixel_with_depth_and_id`] also stores
+/// a 32-bit identifier. Used for picking; 0 is reserved for "nothing drawn".
+///
+/// # Mask Buffer
+///
+/// When attached via [`FrameBuffer::with_mask_buffer`], depth-tested writes
+/// must also pass its [`MaskTest`], and with mask writes on they store
+/// [`MASK_SET`] there. Turning color writes off then draws into the mask
+/// alone, e.g. to mark one mesh's visible pixels.
+///
+/// # G-buffer
+///
+/// When attached via [`FrameBuffer::with_gbuffer`], the color buffer takes
+/// each surface's unlit albedo, and every opaque write also stores the
+/// packed normal and the 1/w it was drawn at, for a lighting pass to read
+/// later. [`FrameBuffer::shade_fragment`] asks the shader for all three.
 pub struct FrameBuffer<'a> {
     color_buffer: &'a mut [u32],
     depth_buffer: &'a mut [f32],
+    id_buffer: Option<&'a mut [u32]>,
+    mask_buffer: Option<&'a mut [u8]>,
+    // Packed normals and 1/w, beside albedo in the color buffer
+    gbuffer: Option<(&'a mut [u32], &'a mut [f32])>,
+    // Lights applied to each shaded fragment, for forward point lights
+    lighting: Option<PixelLighting<'a>>,
+    mask_test: MaskTest,
+    mask_write: bool,
+    color_write: bool,
+    blend_mode: BlendMode,
+    depth_encoding: DepthEncoding,
+    depth_pass: DepthPass,
+    // Calls to the `shade` closure of `shade_pixel_with_depth_and_id`
+    shaded_pixels: usize,
+    // Fragments `lighting` was evaluated for
+    lit_pixels: usize,
     width: u32,
     height: u32,
 }
@@ -26,7 +240,8 @@ impl<'a> FrameBuffer<'a> {
     /// Create a new FrameBuffer view from buffer slices and dimensions.
     ///
     /// # Panics
-    /// Panics if buffer lengths don't match width * height
+    /// Panics if buffer lengths don't match width * height (the depth
+    /// buffer may also be empty)
     pub fn new(
         color_buffer: &'a mut [u32],
         depth_buffer: &'a mut [f32],
@@ -38,19 +253,139 @@ impl<'a> FrameBuffer<'a> {
             (width * height) as usize,
             "Color buffer size doesn't match dimensions"
         );
-        debug_assert_eq!(
-            depth_buffer.len(),
-            (width * height) as usize,
+        debug_assert!(
+            depth_buffer.is_empty() || depth_buffer.len() == (width * height) as usize,
             "Depth buffer size doesn't match dimensions"
         );
         Self {
             color_buffer,
             depth_buffer,
+            id_buffer: None,
+            mask_buffer: None,
+            gbuffer: None,
+            lighting: None,
+            mask_test: MaskTest::Always,
+            mask_write: false,
+            color_write: true,
+            blend_mode: BlendMode::Opaque,
+            depth_encoding: DepthEncoding::INV_W,
+            depth_pass: DepthPass::Full,
+            shaded_pixels: 0,
+            lit_pixels: 0,
             width,
             height,
         }
     }
 
+    /// Attach an ID buffer that depth-tested writes will also fill.
+    ///
+    /// # Panics
+    /// Panics if the buffer length doesn't match width * height
+    pub fn with_id_buffer(mut self, id_buffer: &'a mut [u32]) -> Self {
+        debug_assert_eq!(
+            id_buffer.len(),
+            (self.width * self.height) as usize,
+            "ID buffer size doesn't match dimensions"
+        );
+        self.id_buffer = Some(id_buffer);
+        self
+    }
+
+    /// Attach a mask buffer, tested and written as set by
+    /// [`FrameBuffer::with_mask_test`] and [`FrameBuffer::with_mask_write`].
+    ///
+    /// # Panics
+    /// Panics if the buffer length doesn't match width * height
+    pub fn with_mask_buffer(mut self, mask_buffer: &'a mut [u8]) -> Self {
+        debug_assert_eq!(
+            mask_buffer.len(),
+            (self.width * self.height) as usize,
+            "Mask buffer size doesn't match dimensions"
+        );
+        self.mask_buffer = Some(mask_buffer);
+        self
+    }
+
+    /// Attach G-buffer planes for packed normals and 1/w. See the
+    /// [G-buffer](FrameBuffer#g-buffer) section.
+    ///
+    /// # Panics
+    /// Panics if the buffer lengths don't match width * height
+    pub fn with_gbuffer(mut self, normals: &'a mut [u32], inv_w: &'a mut [f32]) -> Self {
+        assert!(
+            normals.len() == (self.width * self.height) as usize && inv_w.len() == normals.len(),
+            "G-buffer size doesn't match dimensions"
+        );
+        self.gbuffer = Some((normals, inv_w));
+        self
+    }
+
+    /// Whether G-buffer planes are attached.
+    pub fn has_gbuffer(&self) -> bool {
+        self.gbuffer.is_some()
+    }
+
+    /// Light every fragment [`FrameBuffer::shade_fragment`] shades with
+    /// `lighting`, from what the shader writes to a G-buffer. Ignored
+    /// with a G-buffer attached, whose lighting comes later.
+    pub(crate) fn with_lighting(mut self, lighting: PixelLighting<'a>) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    /// Only write pixels whose mask passes `test`. Ignored without a mask
+    /// buffer.
+    pub fn with_mask_test(mut self, test: MaskTest) -> Self {
+        self.mask_test = test;
+        self
+    }
+
+    /// Store [`MASK_SET`] in the mask wherever a pixel is written.
+    pub fn with_mask_write(mut self, enabled: bool) -> Self {
+        self.mask_write = enabled;
+        self
+    }
+
+    /// With `false`, depth-tested writes leave color and IDs alone and
+    /// nothing is shaded; depth and mask are still written.
+    pub fn with_color_write(mut self, enabled: bool) -> Self {
+        self.color_write = enabled;
+        self
+    }
+
+    /// Combine shaded pixels with the buffer per `mode`. See [`BlendMode`].
+    pub fn with_blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+
+    /// Store and compare depth with `encoding` instead of raw 1/w.
+    pub fn with_depth_encoding(mut self, encoding: DepthEncoding) -> Self {
+        self.depth_encoding = encoding;
+        self
+    }
+
+    /// Perform only part of each depth-tested write. See [`DepthPass`].
+    pub fn with_depth_pass(mut self, pass: DepthPass) -> Self {
+        self.depth_pass = pass;
+        self
+    }
+
+    pub fn depth_pass(&self) -> DepthPass {
+        self.depth_pass
+    }
+
+    /// Pixels [`FrameBuffer::shade_pixel_with_depth_and_id`] has shaded
+    /// through this view.
+    pub fn shaded_pixels(&self) -> usize {
+        self.shaded_pixels
+    }
+
+    /// Fragments lit through [`FrameBuffer::with_lighting`].
+    pub(crate) fn lit_pixels(&self) -> usize {
+        self.lit_pixels
+    }
+
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -61,9 +396,9 @@ impl<'a> FrameBuffer<'a> {
 
     /// Set a pixel at (x, y) with depth testing.
     ///
-    /// The pixel is only written if the depth value is greater than the existing
-    /// depth at that location (closer to camera, since we store 1/w).
-    /// Silently ignores out-of-bounds coordinates.
+    /// The pixel is only written if it is closer to the camera than the
+    /// existing depth at that location, per the depth encoding, and passes
+    /// the mask test. Silently ignores out-of-bounds coordinates.
     ///
     /// # Arguments
     /// * `x`, `y` - Pixel coordinates
@@ -73,10 +408,199 @@ impl<'a> FrameBuffer<'a> {
     pub fn set_pixel_with_depth(&mut self, x: i32, y: i32, inv_depth: f32, color: u32) {
         if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
             let idx = (y as u32 * self.width + x as u32) as usize;
-            // Depth test: larger 1/w means closer to camera
-            if inv_depth > self.depth_buffer[idx] {
-                self.depth_buffer[idx] = inv_depth;
-                self.color_buffer[idx] = color;
+            let depth = self.depth_encoding.encode(inv_depth);
+            if self.depth_test(idx, depth) {
+                self.write(idx, depth, color, None, (inv_depth, Vec3::ZERO));
+            }
+        }
+    }
+
+    /// Set a pixel with depth testing, also recording `id` in the ID buffer.
+    ///
+    /// Identical to [`FrameBuffer::set_pixel_with_depth`] when no ID buffer
+    /// is attached.
+    #[inline]
+    pub fn set_pixel_with_depth_and_id(
+        &mut self,
+        x: i32,
+        y: i32,
+        inv_depth: f32,
+        color: u32,
+        id: u32,
+    ) {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            let idx = (y as u32 * self.width + x as u32) as usize;
+            let depth = self.depth_encoding.encode(inv_depth);
+            if self.depth_test(idx, depth) {
+                self.write(idx, depth, color, Some(id), (inv_depth, Vec3::ZERO));
+            }
+        }
+    }
+
+    /// Depth-test pixel (x, y) and, only if it passes, call `shade` for its
+    /// color and write it as [`FrameBuffer::set_pixel_with_depth_and_id`]
+    /// would.
+    ///
+    /// Testing before shading means hidden pixels never run the shader,
+    /// and under [`DepthPass::DepthOnly`] or with color writes off no
+    /// pixel does. The shaded color is then written per the
+    /// [`BlendMode`]. With a G-buffer attached the color is stored unlit,
+    /// with a zero normal.
+    #[inline]
+    pub fn shade_pixel_with_depth_and_id(
+        &mut self,
+        x: i32,
+        y: i32,
+        inv_depth: f32,
+        id: u32,
+        shade: impl FnOnce() -> u32,
+    ) {
+        self.shade_surface(x, y, inv_depth, id, || (shade(), Vec3::ZERO));
+    }
+
+    /// Depth-test `fragment`'s pixel and, only if it passes, shade it with
+    /// `shader` and write it as
+    /// [`FrameBuffer::shade_pixel_with_depth_and_id`] would, recording the
+    /// fragment's triangle ID.
+    ///
+    /// With a G-buffer attached the shader's
+    /// [`shade_gbuffer`](PixelShader::shade_gbuffer) output is stored for
+    /// the lighting pass; with lighting attached it is lit on the spot.
+    /// Otherwise the shader's color is written as it is.
+    #[inline]
+    pub fn shade_fragment<S: PixelShader + ?Sized>(
+        &mut self,
+        inv_depth: f32,
+        fragment: &Fragment,
+        shader: &S,
+    ) {
+        let (x, y, id) = (fragment.x, fragment.y, fragment.triangle.id);
+        if self.gbuffer.is_some() {
+            self.shade_surface(x, y, inv_depth, id, || {
+                let sample = shader.shade_gbuffer(fragment);
+                (sample.albedo, sample.normal)
+            });
+        } else {
+            match self.lighting {
+                Some(lighting) => {
+                    let lit_before = self.shaded_pixels;
+                    self.shade_pixel_with_depth_and_id(x, y, inv_depth, id, || {
+                        let sample = shader.shade_gbuffer(fragment);
+                        lighting.shade(
+                            sample.albedo,
+                            sample.normal,
+                            fragment.world_position(),
+                            1.0 / inv_depth,
+                        )
+                    });
+                    self.lit_pixels += self.shaded_pixels - lit_before;
+                }
+                None => self
+                    .shade_pixel_with_depth_and_id(x, y, inv_depth, id, || shader.shade(fragment)),
+            }
+        }
+    }
+
+    /// [`FrameBuffer::shade_pixel_with_depth_and_id`] for a `shade` that
+    /// also returns the surface normal, stored in the G-buffer if one is
+    /// attached.
+    #[inline]
+    fn shade_surface(
+        &mut self,
+        x: i32,
+        y: i32,
+        inv_depth: f32,
+        id: u32,
+        shade: impl FnOnce() -> (u32, Vec3),
+    ) {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            let idx = (y as u32 * self.width + x as u32) as usize;
+            let depth = self.depth_encoding.encode(inv_depth);
+            if self.depth_test(idx, depth) {
+                if self.depth_pass == DepthPass::DepthOnly || !self.color_write {
+                    self.write(idx, depth, 0, Some(id), (inv_depth, Vec3::ZERO));
+                    return;
+                }
+                self.shaded_pixels += 1;
+                let (color, normal) = shade();
+                let surface = (inv_depth, normal);
+                let alpha = color >> 24;
+                match self.blend_mode {
+                    BlendMode::Opaque => self.write(idx, depth, color, Some(id), surface),
+                    BlendMode::Cutout if alpha >= CUTOUT_ALPHA => {
+                        self.write(idx, depth, color, Some(id), surface);
+                    }
+                    BlendMode::Cutout => {}
+                    // Blended colors are no one surface, so the G-buffer
+                    // keeps what is behind them
+                    BlendMode::Alpha if alpha > 0 => {
+                        let coverage = alpha as f32 / 255.0;
+                        let blended = colors::blend(self.color_buffer[idx], color, coverage);
+                        self.write_color(idx, blended, None);
+                    }
+                    BlendMode::Alpha => {}
+                    BlendMode::Additive => {
+                        let summed = colors::add_saturating(self.color_buffer[idx], color);
+                        self.write_color(idx, summed, None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The depth test for the current [`DepthPass`], and the mask test.
+    #[inline]
+    fn depth_test(&self, idx: usize, depth: f32) -> bool {
+        if let Some(mask) = self.mask_buffer.as_deref() {
+            if !self.mask_test.passes(mask[idx]) {
+                return false;
+            }
+        }
+        let Some(&stored) = self.depth_buffer.get(idx) else {
+            // No depth buffer: draw order decides
+            return true;
+        };
+        match self.depth_pass {
+            DepthPass::Full | DepthPass::DepthOnly => self.depth_encoding.passes(depth, stored),
+            // Not behind the stored depth means it is the stored surface
+            DepthPass::Equal => !self.depth_encoding.passes(stored, depth),
+        }
+    }
+
+    /// Write a pixel that passed [`FrameBuffer::depth_test`], storing
+    /// whatever the current [`DepthPass`] stores. `surface` is the 1/w and
+    /// normal for the G-buffer.
+    #[inline]
+    fn write(&mut self, idx: usize, depth: f32, color: u32, id: Option<u32>, surface: (f32, Vec3)) {
+        if self.depth_pass != DepthPass::Equal {
+            if let Some(stored) = self.depth_buffer.get_mut(idx) {
+                *stored = depth;
+            }
+        }
+        if self.depth_pass == DepthPass::DepthOnly {
+            return;
+        }
+        self.write_color(idx, color, id);
+        if self.color_write {
+            if let Some((normals, inv_w)) = self.gbuffer.as_mut() {
+                normals[idx] = pack_normal(surface.1);
+                inv_w[idx] = surface.0;
+            }
+        }
+    }
+
+    /// The part of [`FrameBuffer::write`] past depth: mask, color and ID.
+    #[inline]
+    fn write_color(&mut self, idx: usize, color: u32, id: Option<u32>) {
+        if self.mask_write {
+            if let Some(mask) = self.mask_buffer.as_deref_mut() {
+                mask[idx] = MASK_SET;
+            }
+        }
+        if self.color_write {
+            self.color_buffer[idx] = color;
+            if let (Some(ids), Some(id)) = (self.id_buffer.as_deref_mut(), id) {
+                ids[idx] = id;
             }
         }
     }
@@ -99,3 +623,121 @@ impl<'a> FrameBuffer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEAR: f32 = 0.5;
+    const FAR: f32 = 0.25;
+
+    /// Draw a far then a near fragment at (0, 0) in each of `passes` and
+    /// return the pixel's color and how many times it was shaded.
+    fn draw_far_then_near(passes: &[DepthPass]) -> (u32, usize) {
+        let mut color = [0u32];
+        let mut depth = [DepthEncoding::INV_W.clear_value()];
+        let mut fb = FrameBuffer::new(&mut color, &mut depth, 1, 1);
+        for &pass in passes {
+            fb = fb.with_depth_pass(pass);
+            fb.shade_pixel_with_depth_and_id(0, 0, FAR, 1, || 0xFF0000FF);
+            fb.shade_pixel_with_depth_and_id(0, 0, NEAR, 2, || 0xFFFF0000);
+        }
+        let shaded = fb.shaded_pixels();
+        (color[0], shaded)
+    }
+
+    #[test]
+    fn full_pass_shades_every_fragment_that_wins_so_far() {
+        assert_eq!(draw_far_then_near(&[DepthPass::Full]), (0xFFFF0000, 2));
+    }
+
+    #[test]
+    fn prepass_shades_only_the_visible_fragment() {
+        let passes = [DepthPass::DepthOnly, DepthPass::Equal];
+        assert_eq!(draw_far_then_near(&passes), (0xFFFF0000, 1));
+    }
+
+    #[test]
+    fn depth_only_pass_leaves_color_and_ids_alone() {
+        let mut color = [7u32];
+        let mut depth = [0.0f32];
+        let mut ids = [0u32];
+        let mut fb = FrameBuffer::new(&mut color, &mut depth, 1, 1)
+            .with_id_buffer(&mut ids)
+            .with_depth_pass(DepthPass::DepthOnly);
+        fb.set_pixel_with_depth_and_id(0, 0, NEAR, 0xFFFFFFFF, 3);
+        assert_eq!((color[0], depth[0], ids[0]), (7, NEAR, 0));
+    }
+
+    #[test]
+    fn mask_write_without_color_marks_only_the_mask() {
+        let mut color = [7u32, 7];
+        let mut depth = [0.0f32; 2];
+        let mut mask = [0u8; 2];
+        let mut fb = FrameBuffer::new(&mut color, &mut depth, 2, 1)
+            .with_mask_buffer(&mut mask)
+            .with_mask_write(true)
+            .with_color_write(false);
+        let mut shaded = false;
+        fb.shade_pixel_with_depth_and_id(0, 0, NEAR, 1, || {
+            shaded = true;
+            0xFFFFFFFF
+        });
+        assert!(!shaded);
+        assert_eq!((color, depth[0], mask), ([7, 7], NEAR, [MASK_SET, 0]));
+    }
+
+    #[test]
+    fn mask_test_limits_writes() {
+        let mut color = [0u32; 2];
+        let mut depth = [0.0f32; 2];
+        let mut mask = [MASK_SET, 0];
+        let mut fb = FrameBuffer::new(&mut color, &mut depth, 2, 1)
+            .with_mask_buffer(&mut mask)
+            .with_mask_test(MaskTest::NotEqual(MASK_SET));
+        fb.set_pixel_with_depth(0, 0, NEAR, 0xFFFF0000);
+        fb.set_pixel_with_depth(1, 0, NEAR, 0xFFFF0000);
+        fb = fb.with_mask_test(MaskTest::Equal(MASK_SET));
+        // Nearer still, so only the mask can turn them away
+        fb.set_pixel_with_depth(0, 0, NEAR * 2.0, 0xFF00FF00);
+        fb.set_pixel_with_depth(1, 0, NEAR * 2.0, 0xFF00FF00);
+        // Each pixel took only the write its mask allowed
+        assert_eq!(color, [0xFF00FF00, 0xFFFF0000]);
+    }
+
+    #[test]
+    fn cutout_discards_mostly_transparent_pixels() {
+        let mut color = [7u32; 2];
+        let mut depth = [0.0f32; 2];
+        let mut fb =
+            FrameBuffer::new(&mut color, &mut depth, 2, 1).with_blend_mode(BlendMode::Cutout);
+        fb.shade_pixel_with_depth_and_id(0, 0, NEAR, 1, || 0x7FFF0000);
+        fb.shade_pixel_with_depth_and_id(1, 0, NEAR, 1, || 0x80FF0000);
+        assert_eq!((color, depth), ([7, 0x80FF0000], [0.0, NEAR]));
+    }
+
+    #[test]
+    fn alpha_blends_without_writing_depth() {
+        let mut color = [0xFF000000u32];
+        let mut depth = [FAR];
+        let mut fb =
+            FrameBuffer::new(&mut color, &mut depth, 1, 1).with_blend_mode(BlendMode::Alpha);
+        fb.shade_pixel_with_depth_and_id(0, 0, NEAR, 1, || 0x80FFFFFF);
+        // Still behind the opaque surface: hidden
+        fb.shade_pixel_with_depth_and_id(0, 0, FAR * 0.5, 1, || 0xFF00FF00);
+        assert_eq!((color[0], depth[0]), (0xFF808080, FAR));
+    }
+
+    #[test]
+    fn additive_sums_every_layer_without_writing_depth() {
+        let mut color = [0xFF000000u32];
+        let mut depth = [FAR];
+        let mut fb =
+            FrameBuffer::new(&mut color, &mut depth, 1, 1).with_blend_mode(BlendMode::Additive);
+        for _ in 0..3 {
+            fb.shade_pixel_with_depth_and_id(0, 0, NEAR, 1, || 0xFF600000);
+        }
+        // Saturates instead of wrapping into green
+        assert_eq!((color[0], depth[0]), (0xFFFF0000, FAR));
+    }
+}
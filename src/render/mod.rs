@@ -2,16 +2,25 @@
 //!
 //! This module contains all rendering-related components:
 //! - [`FrameBuffer`]: A view into a 2D pixel buffer for safe pixel access
-//! - [`Renderer`]: Owns the color buffer and provides primitive drawing operations
+//! - [`RenderTarget`]: Owned color and depth buffers, for the screen or offscreen
+//! - [`Renderer`]: Draws into a render target and provides primitive drawing operations
 //! - [`rasterizer`]: Triangle rasterization algorithms
+//! - `font`: Embedded 8x8 bitmap font used for debug text
 
+mod font;
 pub mod framebuffer;
 pub mod rasterizer;
 pub mod renderer;
+pub mod target;
 
-pub use framebuffer::FrameBuffer;
+pub use framebuffer::{
+    BlendMode, DepthEncoding, DepthMode, DepthPass, FrameBuffer, MaskTest, MASK_SET,
+};
+#[cfg(feature = "bench-internals")]
+pub use rasterizer::{EdgeFunctionRasterizer, ScanlineRasterizer};
 pub use rasterizer::{
-    EdgeFunctionRasterizer, Rasterizer, RasterizerDispatcher, RasterizerType, ScanlineRasterizer,
+    Fragment, GBufferSample, PixelShader, Rasterizer, RasterizerDispatcher, RasterizerType,
     ScreenVertex, Triangle,
 };
 pub use renderer::Renderer;
+pub use target::RenderTarget;
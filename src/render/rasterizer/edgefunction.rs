@@ -40,17 +40,39 @@
 //! The algorithm handles both clockwise and counter-clockwise triangles by
 //! checking the sign of the total signed area. For CW triangles, all edge
 //! functions will be negative for interior points; for CCW, all positive.
+//! Edge functions are flipped by that sign up front so interior points are
+//! always positive.
+//!
+//! # Sub-pixel Precision
+//!
+//! Vertices are snapped to a 1/16-pixel grid (`SUBPIXEL_BITS`) and the
+//! edge functions are evaluated in `i64`, so coverage is exact: a pixel
+//! only changes when a vertex crosses a grid step, not whenever rounding
+//! error moves it.
+//!
+//! # Fill Rule
+//!
+//! A pixel center exactly on an edge is drawn only if the edge is a *top*
+//! edge (horizontal, interior below) or a *left* edge (interior to its
+//! right) — the top-left rule used by Direct3D and OpenGL. Two triangles
+//! sharing an edge see it as top/left from one side and bottom/right from
+//! the other, so each pixel on it is drawn exactly once. With integer edge
+//! functions the rule is a bias of -1 on the other edges.
 //!
 //! # References
 //!
 //! - Juan Pineda, "A Parallel Algorithm for Polygon Rasterization" (1988)
+//! - Fabian Giesen, "Triangle rasterization in practice":
+//!   <https://fgiesen.wordpress.com/2013/02/08/triangle-rasterization-in-practice/>
 //! - Scratchapixel: <https://www.scratchapixel.com/lessons/3d-basic-rendering/rasterization-practical-implementation>
 
-use super::shader::{FlatShader, GouraudShader, PixelShader};
-use super::{Rasterizer, ScreenVertex, Triangle};
+use super::shader::{
+    CheckerShader, FlatShader, FogShader, Fragment, GouraudShader, NormalMapShader, NullShader,
+    PixelShader, TextureModulateShader, TextureShader, ToonShader,
+};
+use super::{first_pixel_from, snapped_edge_function, Rasterizer, SnappedPoint, Triangle};
 use crate::engine::TextureMode;
-use crate::math::vec2::Vec2;
-use crate::render::framebuffer::FrameBuffer;
+use crate::render::framebuffer::{DepthPass, FrameBuffer};
 use crate::render::rasterizer::shader::{
     PerspectiveCorrectTextureModulateShader, PerspectiveCorrectTextureShader,
 };
@@ -84,29 +106,53 @@ impl EdgeFunctionRasterizer {
         EdgeFunctionRasterizer {}
     }
 
-    /// Computes the edge function value for point P relative to edge (A -> B).
-    ///
-    /// The edge function is the signed area of the parallelogram formed by
-    /// vectors (B - A) and (P - A), computed as their 2D cross product:
-    ///
-    /// ```text
-    /// E(P) = (B.x - A.x) * (P.y - A.y) - (B.y - A.y) * (P.x - A.x)
-    /// ```
-    ///
-    /// # Returns
+    /// Whether edge (A -> B) is a top or left edge, for a triangle whose
+    /// edge functions are multiplied by `sign` to make the interior positive.
     ///
-    /// - Positive: P is to the right of edge AB
-    /// - Negative: P is to the left of edge AB
-    /// - Zero: P lies exactly on the edge AB
-    ///
-    /// # Arguments
-    ///
-    /// * `a` - Start point of the edge
-    /// * `b` - End point of the edge
-    /// * `p` - Point to test against the edge
+    /// The edge function's gradient `(-(B.y - A.y), B.x - A.x)` points into
+    /// the triangle. A left edge has the interior to its right (gradient
+    /// `+x`); a top edge is horizontal with the interior below it (`+y`,
+    /// since screen y grows downward).
     #[inline]
-    fn edge_function(a: Vec2, b: Vec2, p: Vec2) -> f32 {
-        (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+    fn is_top_left(a: SnappedPoint, b: SnappedPoint, sign: i64) -> bool {
+        let inward_x = -(b.y - a.y) * sign;
+        let inward_y = (b.x - a.x) * sign;
+        inward_x > 0 || (inward_x == 0 && inward_y > 0)
+    }
+
+    /// [`Self::rasterize_shaded`] for shaders that color from the lit
+    /// `vertex_colors`. Under toon shading those are unlit, so the shader is
+    /// wrapped in a [`ToonShader`] that lights it in bands.
+    fn rasterize_lit<S: PixelShader>(
+        triangle: &Triangle,
+        texture: Option<&Texture>,
+        buffer: &mut FrameBuffer,
+        shader: S,
+    ) {
+        match (triangle.shading_mode, triangle.light) {
+            (ShadingMode::Toon { bands }, Some(light)) => {
+                let shader = ToonShader::new(shader, light, bands, triangle);
+                Self::rasterize_shaded(triangle, texture, buffer, shader);
+            }
+            _ => Self::rasterize_shaded(triangle, texture, buffer, shader),
+        }
+    }
+
+    /// Rasterize `triangle` with `shader`, wrapped in a [`FogShader`] when
+    /// the triangle carries fog.
+    fn rasterize_shaded<S: PixelShader>(
+        triangle: &Triangle,
+        texture: Option<&Texture>,
+        buffer: &mut FrameBuffer,
+        shader: S,
+    ) {
+        match triangle.fog {
+            Some(fog) => {
+                let shader = FogShader::new(shader, fog, triangle.points);
+                Self::rasterize_with_shader(triangle, texture, buffer, &shader);
+            }
+            None => Self::rasterize_with_shader(triangle, texture, buffer, &shader),
+        }
     }
 
     /// Rasterize a triangle using the provided pixel shader.
@@ -122,35 +168,38 @@ impl EdgeFunctionRasterizer {
     /// the final color. Depth testing uses interpolated 1/w values.
     ///
     /// # Arguments
-    /// * `v0, v1, v2` - Triangle vertices in screen space, with clip-space W in `.w`
+    /// * `triangle` - Triangle to cover; its vertices are in screen space,
+    ///   with clip-space W in `.w`, and its `id` is written alongside each
+    ///   pixel
+    /// * `texture` - Texture handed to the shader with each [`Fragment`]
     /// * `buffer` - Framebuffer with color and depth buffers
     /// * `shader` - Pixel shader for color computation
     fn rasterize_with_shader<S: PixelShader>(
-        v0: ScreenVertex,
-        v1: ScreenVertex,
-        v2: ScreenVertex,
+        triangle: &Triangle,
+        texture: Option<&Texture>,
         buffer: &mut FrameBuffer,
         shader: &S,
     ) {
+        let [v0, v1, v2] = triangle.points;
+
         // Precompute 1/w — linear in screen space, so it can be
         // barycentrically interpolated for depth testing.
         let inv_w0 = 1.0 / v0.w;
         let inv_w1 = 1.0 / v1.w;
         let inv_w2 = 1.0 / v2.w;
 
-        // 2D positions for coverage math — edge functions and the
-        // bounding box only need pixel-space (x, y).
-        let p0 = v0.position;
-        let p1 = v1.position;
-        let p2 = v2.position;
+        // Coverage is decided on the sub-pixel grid, in exact integer math
+        let p0 = SnappedPoint::new(v0.position);
+        let p1 = SnappedPoint::new(v1.position);
+        let p2 = SnappedPoint::new(v2.position);
 
         // ─────────────────────────────────────────────────────────────────────
-        // Step 1: Compute bounding box
+        // Step 1: Compute bounding box of the pixel centers it can cover
         // ─────────────────────────────────────────────────────────────────────
-        let min_x = p0.x.min(p1.x).min(p2.x).floor() as i32;
-        let max_x = p0.x.max(p1.x).max(p2.x).ceil() as i32;
-        let min_y = p0.y.min(p1.y).min(p2.y).floor() as i32;
-        let max_y = p0.y.max(p1.y).max(p2.y).ceil() as i32;
+        let min_x = first_pixel_from(p0.x.min(p1.x).min(p2.x));
+        let max_x = first_pixel_from(p0.x.max(p1.x).max(p2.x) + 1) - 1;
+        let min_y = first_pixel_from(p0.y.min(p1.y).min(p2.y));
+        let max_y = first_pixel_from(p0.y.max(p1.y).max(p2.y) + 1) - 1;
 
         // Clip to framebuffer bounds
         let min_x = min_x.max(0);
@@ -161,11 +210,24 @@ impl EdgeFunctionRasterizer {
         // ─────────────────────────────────────────────────────────────────────
         // Step 2: Compute signed area (2x triangle area)
         // ─────────────────────────────────────────────────────────────────────
-        let area = Self::edge_function(p0, p1, p2);
-        if area.abs() < f32::EPSILON {
+        let area = snapped_edge_function(p0, p1, p2);
+        if area == 0 {
             return; // Degenerate triangle
         }
-        let inv_area = 1.0 / area;
+        // Flip CW triangles so interior edge functions are positive; the
+        // barycentrics are unchanged since area flips with them
+        let sign = area.signum();
+        let inv_area = 1.0 / (area * sign) as f32;
+
+        // Pixel centers exactly on an edge only count for top and left
+        // edges: the others are biased by the smallest step, which only
+        // pushes out pixels that were tied at zero
+        let bias = [
+            Self::is_top_left(p1, p2, sign),
+            Self::is_top_left(p2, p0, sign),
+            Self::is_top_left(p0, p1, sign),
+        ]
+        .map(|top_left| if top_left { 0 } else { -1 });
 
         // ─────────────────────────────────────────────────────────────────────
         // Step 3: Iterate over all pixels in bounding box
@@ -173,32 +235,30 @@ impl EdgeFunctionRasterizer {
         for y in min_y..=max_y {
             for x in min_x..=max_x {
                 // Sample at pixel center
-                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let p = SnappedPoint::pixel_center(x, y);
 
-                // Compute edge functions
-                let w0 = Self::edge_function(p1, p2, p);
-                let w1 = Self::edge_function(p2, p0, p);
-                let w2 = Self::edge_function(p0, p1, p);
+                // Compute edge functions, positive inside for either winding
+                let w0 = snapped_edge_function(p1, p2, p) * sign;
+                let w1 = snapped_edge_function(p2, p0, p) * sign;
+                let w2 = snapped_edge_function(p0, p1, p) * sign;
 
-                // Inside test (handles both CW and CCW winding)
-                let inside = if area > 0.0 {
-                    // CCW winding: positive edge functions for interior
-                    w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0
-                } else {
-                    // CW winding: negative edge functions for interior
-                    w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0
-                };
+                let inside = w0 + bias[0] >= 0 && w1 + bias[1] >= 0 && w2 + bias[2] >= 0;
 
                 if inside {
                     // Compute barycentric coordinates
-                    let lambda = [w0 * inv_area, w1 * inv_area, w2 * inv_area];
+                    let lambda = [
+                        w0 as f32 * inv_area,
+                        w1 as f32 * inv_area,
+                        w2 as f32 * inv_area,
+                    ];
 
                     // Interpolate 1/w for depth testing (linear in screen space)
                     let depth = lambda[0] * inv_w0 + lambda[1] * inv_w1 + lambda[2] * inv_w2;
 
-                    // Delegate to shader for color computation
-                    let color = shader.shade(lambda);
-                    buffer.set_pixel_with_depth(x, y, depth, color);
+                    // Delegate to shader for color computation, only once
+                    // the pixel is known to be visible
+                    let fragment = Fragment::new(lambda, x, y, triangle, texture);
+                    buffer.shade_fragment(depth, &fragment, shader);
                 }
             }
         }
@@ -222,10 +282,20 @@ impl Rasterizer for EdgeFunctionRasterizer {
     ///
     /// | texture_mode | shading_mode | Shader Used |
     /// |--------------|--------------|-------------|
+    /// | (depth-only pass) | * | NullShader |
+    /// | NormalMapped (with `light`) | * | NormalMapShader |
+    /// | Checker | * | CheckerShader |
     /// | Replace | * | TextureShader |
     /// | Modulate | * | TextureModulateShader |
-    /// | None | Gouraud | GouraudShader |
+    /// | VertexColor | * | GouraudShader |
+    /// | None | Gouraud/Toon | GouraudShader |
     /// | None | Flat/None | FlatShader |
+    ///
+    /// Textures are mapped perspective-correctly, except for triangles with
+    /// `affine_textures` set.
+    ///
+    /// Under `Toon`, every shader but the normal-map and texture-replace ones
+    /// is wrapped in a `ToonShader`.
     fn fill_triangle(
         &self,
         triangle: &Triangle,
@@ -233,18 +303,65 @@ impl Rasterizer for EdgeFunctionRasterizer {
         color: u32,
         texture: Option<&Texture>,
     ) {
-        let [v0, v1, v2] = triangle.points;
+        // A depth-only pass never shades, so skip building the real shader
+        if buffer.depth_pass() == DepthPass::DepthOnly {
+            Self::rasterize_with_shader(triangle, None, buffer, &NullShader);
+            return;
+        }
+
+        // Lit per pixel, so `vertex_colors` hold the unlit base color
+        if let (TextureMode::NormalMapped, Some(normal_map), Some(light)) =
+            (triangle.texture_mode, texture, triangle.light)
+        {
+            let shader = NormalMapShader::new(normal_map, triangle, light);
+            Self::rasterize_shaded(triangle, texture, buffer, shader);
+            return;
+        }
 
         // Select shader based on texture_mode and shading_mode
         match (triangle.texture_mode, texture) {
+            // Procedural; needs no texture
+            (TextureMode::Checker { scale }, _) => {
+                let shader = CheckerShader::new(
+                    triangle.surface_positions,
+                    triangle.points,
+                    triangle.vertex_colors,
+                    scale,
+                    triangle.dither,
+                );
+                Self::rasterize_lit(triangle, texture, buffer, shader);
+            }
+            // Baked mesh colors vary per vertex whatever the shading mode
+            (TextureMode::VertexColor, _) => {
+                let shader = GouraudShader::new(triangle.vertex_colors, triangle.dither);
+                Self::rasterize_lit(triangle, texture, buffer, shader);
+            }
             // Textured paths (when texture is available)
-            (TextureMode::Replace, Some(tex)) => {
+            (TextureMode::Replace | TextureMode::SphereMap, Some(tex))
+                if triangle.affine_textures =>
+            {
+                let shader = TextureShader::new(tex, triangle.texture_coords)
+                    .with_region(triangle.texture_region);
+                Self::rasterize_shaded(triangle, texture, buffer, shader);
+            }
+            (TextureMode::Modulate, Some(tex)) if triangle.affine_textures => {
+                let shader = TextureModulateShader::new(
+                    tex,
+                    triangle.texture_coords,
+                    triangle.vertex_colors,
+                    triangle.dither,
+                )
+                .with_region(triangle.texture_region);
+                Self::rasterize_lit(triangle, texture, buffer, shader);
+            }
+            (TextureMode::Replace | TextureMode::SphereMap, Some(tex)) => {
                 let shader = PerspectiveCorrectTextureShader::new(
                     tex,
                     triangle.texture_coords,
                     triangle.points,
-                );
-                Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                )
+                .with_region(triangle.texture_region);
+                Self::rasterize_shaded(triangle, texture, buffer, shader);
             }
             (TextureMode::Modulate, Some(tex)) => {
                 let shader = PerspectiveCorrectTextureModulateShader::new(
@@ -252,21 +369,41 @@ impl Rasterizer for EdgeFunctionRasterizer {
                     triangle.texture_coords,
                     triangle.points,
                     triangle.vertex_colors,
-                );
-                Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                    triangle.dither,
+                )
+                .with_region(triangle.texture_region);
+                Self::rasterize_lit(triangle, texture, buffer, shader);
             }
 
             // Non-textured paths (texture_mode is None, or no texture loaded)
             _ => match triangle.shading_mode {
-                ShadingMode::Gouraud => {
-                    let shader = GouraudShader::new(triangle.vertex_colors);
-                    Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                ShadingMode::Gouraud | ShadingMode::Toon { .. } => {
+                    let shader = GouraudShader::new(triangle.vertex_colors, triangle.dither);
+                    Self::rasterize_lit(triangle, texture, buffer, shader);
                 }
                 ShadingMode::Flat | ShadingMode::None => {
                     let shader = FlatShader::new(color);
-                    Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                    Self::rasterize_lit(triangle, texture, buffer, shader);
                 }
             },
         }
     }
+
+    fn fill_triangle_with_shader(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        shader: &dyn PixelShader,
+        texture: Option<&Texture>,
+    ) {
+        if buffer.depth_pass() == DepthPass::DepthOnly {
+            Self::rasterize_with_shader(triangle, None, buffer, &NullShader);
+            return;
+        }
+        Self::rasterize_shaded(triangle, texture, buffer, shader);
+    }
+
+    fn name(&self) -> &str {
+        "EdgeFunction"
+    }
 }
@@ -13,9 +13,17 @@ pub mod shader;
 
 pub use edgefunction::EdgeFunctionRasterizer;
 pub use scanline::ScanlineRasterizer;
+pub use shader::{Fragment, GBufferSample, PixelShader};
 
 use super::framebuffer::FrameBuffer;
-use crate::{engine::TextureMode, prelude::Vec2, texture::Texture, ShadingMode};
+use crate::{
+    engine::TextureMode,
+    fog::Fog,
+    light::DirectionalLight,
+    prelude::{Vec2, Vec3},
+    texture::{Texture, TextureRegion},
+    ShadingMode,
+};
 
 /// A projected vertex in screen space, paired with its clip-space `w`.
 ///
@@ -49,6 +57,88 @@ impl ScreenVertex {
     }
 }
 
+/// Bits of sub-pixel precision in snapped screen positions: both built-in
+/// rasterizers round vertices to a 1/16-pixel grid before deciding
+/// coverage.
+pub const SUBPIXEL_BITS: u32 = 4;
+
+/// Sub-pixel steps per pixel.
+const SUBPIXEL_STEPS: i64 = 1 << SUBPIXEL_BITS;
+
+/// A screen position snapped to the sub-pixel grid, in fixed point
+/// (1/16-pixel units).
+///
+/// Deciding coverage from raw `f32` positions lets a pixel on an edge flip
+/// in and out as a vertex drifts by a rounding error, so edges crawl under
+/// slow motion. Snapping first, as GPUs do, makes every coverage decision
+/// exact integer math: identical snapped input gives identical pixels, and
+/// a vertex has to cross a grid step before any pixel changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SnappedPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl SnappedPoint {
+    /// Round `position` to the nearest grid point.
+    #[inline]
+    pub fn new(position: Vec2) -> Self {
+        let snap = |c: f32| (c * SUBPIXEL_STEPS as f32).round() as i64;
+        Self {
+            x: snap(position.x),
+            y: snap(position.y),
+        }
+    }
+
+    /// The center of pixel `(x, y)`, which is where coverage is sampled.
+    #[inline]
+    pub fn pixel_center(x: i32, y: i32) -> Self {
+        Self {
+            x: x as i64 * SUBPIXEL_STEPS + SUBPIXEL_STEPS / 2,
+            y: y as i64 * SUBPIXEL_STEPS + SUBPIXEL_STEPS / 2,
+        }
+    }
+}
+
+/// The edge function of `p` against edge (A -> B), exactly, in
+/// sub-pixel² units:
+///
+/// ```text
+/// E(P) = (B.x - A.x) * (P.y - A.y) - (B.y - A.y) * (P.x - A.x)
+/// ```
+///
+/// Zero when `p` is on the edge; the sign says which side it is on.
+#[inline]
+pub(crate) fn snapped_edge_function(a: SnappedPoint, b: SnappedPoint, p: SnappedPoint) -> i64 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// The first pixel row or column whose center is at or past the fixed-point
+/// coordinate `c`.
+#[inline]
+pub(crate) fn first_pixel_from(c: i64) -> i32 {
+    ceil_div(c - SUBPIXEL_STEPS / 2, SUBPIXEL_STEPS) as i32
+}
+
+/// Whether the box around `points`, snapped as the rasterizers snap them,
+/// holds no pixel center. A triangle with such a box covers no pixel,
+/// however long it is; one whose box holds a center may still miss it.
+pub(crate) fn misses_pixel_centers(points: [Vec2; 3]) -> bool {
+    let [a, b, c] = points.map(SnappedPoint::new);
+    let misses = |lo: i64, hi: i64| {
+        let center = first_pixel_from(lo) as i64 * SUBPIXEL_STEPS + SUBPIXEL_STEPS / 2;
+        center > hi
+    };
+    misses(a.x.min(b.x).min(c.x), a.x.max(b.x).max(c.x))
+        || misses(a.y.min(b.y).min(c.y), a.y.max(b.y).max(c.y))
+}
+
+/// `ceil(n / d)` for a positive `d`.
+#[inline]
+pub(crate) fn ceil_div(n: i64, d: i64) -> i64 {
+    -((-n).div_euclid(d))
+}
+
 /// A triangle ready for rasterization in screen space.
 ///
 /// After the engine has transformed, lit, clipped, and projected a face, it
@@ -85,20 +175,61 @@ impl ScreenVertex {
 ///   projected and viewport-transformed.
 /// * **`color`** — a single packed ARGB color. Used for wireframe lines and
 ///   as the fill color when no lighting is applied.
-/// * **`vertex_colors`** — three packed ARGB colors, one per vertex. The
+/// * **`vertex_colors`** — three `[r, g, b]` colors in [0.0, 1.0], one per
+///   vertex, kept in floating point so clipping and interpolation don't
+///   round them; shaders pack the final pixel. The
 ///   engine bakes the directional light into these during `update()` — for
 ///   `Flat` shading all three entries are identical; for `Gouraud` each is
 ///   lit independently at its vertex. The rasterizer interpolates them via
-///   barycentric coordinates.
+///   barycentric coordinates. Under `TextureMode::VertexColor` they start
+///   from the mesh's baked colors, so they differ per vertex in every
+///   shading mode.
 /// * **`texture_coords`** — three `(u, v)` pairs, one per vertex. Only read
 ///   when `texture_mode` is `Replace` or `Modulate`. Interpolated
 ///   perspective-correctly inside the shader.
+/// * **`texture_region`** — the sub-rectangle of the texture those UVs
+///   cover, for meshes sharing an atlas. UVs repeat within it and samples
+///   are clamped to its pixels, so neighbouring entries never bleed in.
 /// * **`shading_mode`** — how `vertex_colors` was computed. The rasterizer
 ///   uses it to pick between `FlatShader` and `GouraudShader` on the
-///   untextured path.
+///   untextured path, and under `Toon` wraps every shader that uses the
+///   lit color in a `ToonShader`.
 /// * **`texture_mode`** — whether a texture is sampled, and how its sample
 ///   combines with `vertex_colors`. Drives the main shader selection in
 ///   `fill_triangle`.
+/// * **`dither`** — whether interpolated colors are ordered-dithered when
+///   packed to 8 bits per channel. Only the gradient shaders (Gouraud and
+///   texture modulate) honor it; flat fills have nothing to dither.
+/// * **`surface_positions`** — object-space position of each corner, for
+///   `TextureMode::Checker`. Clipping interpolates them like any other
+///   attribute, and the shader interpolates them perspective-correctly.
+/// * **`world_positions`**, **`normals`** — world-space position and
+///   normal of each corner, interpolated through clipping, for per-pixel
+///   lighting (`TextureMode::NormalMapped` lights from the normals). Normals
+///   face out of the drawn side but are not renormalized after clipping.
+/// * **`tangents`**, **`bitangents`** — world-space directions of
+///   increasing `u` and `v` at each corner, which orient a tangent-space
+///   normal map on the surface. Zero for meshes without UVs.
+/// * **`light`** — set when the triangle is lit per pixel rather than
+///   through `vertex_colors`; those then hold the unlit base color.
+///   `ShadingMode::Toon` always does this, and `TextureMode::NormalMapped`
+///   while a normal map is set (its shader then lights smoothly, without
+///   bands).
+/// * **`edge_flags`** — which edges the wireframe pass draws. Bit `i`
+///   covers the edge from `points[i]` to `points[(i + 1) % 3]`. All set
+///   from [`Triangle::new`]; when clipping splits a face, the fan's spokes
+///   and the cuts along clip planes are cleared, so only the face's real
+///   edges are outlined.
+/// * **`pass_flags`** — which of the engine's passes draw the triangle:
+///   the fill, the wireframe and the vertex markers. `Engine::update`
+///   stamps them from the mesh's render mode, so meshes in one frame can
+///   be drawn differently.
+/// * **`avg_depth`** — mean clip-space `w` (view depth) of the corners,
+///   the key `HiddenSurface::PainterSort` orders triangles by. Only filled
+///   in that mode.
+/// * **`id`** — identifier written to the framebuffer's ID buffer (if one
+///   is attached) wherever this triangle wins the depth test. Every piece a
+///   face is clipped into shares its face's `id`, so picking stays stable.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Triangle {
     /// Per-vertex screen-space positions plus clip-space `w`.
@@ -106,23 +237,78 @@ pub struct Triangle {
     pub points: [ScreenVertex; 3],
     /// Packed ARGB. Used for wireframe lines and unlit filled triangles.
     pub color: u32,
-    /// Per-vertex lit colors. Populated by `Engine::update` from the
-    /// directional light according to `shading_mode`.
-    pub vertex_colors: [u32; 3],
+    /// Per-vertex lit RGB colors in [0.0, 1.0]. Populated by
+    /// `Engine::update` from the directional light according to
+    /// `shading_mode`.
+    pub vertex_colors: [[f32; 3]; 3],
     /// Per-vertex UVs. Only read when `texture_mode` samples a texture.
     pub texture_coords: [Vec2; 3],
+    /// Part of the texture the UVs map onto. `None` (all of it) from
+    /// [`Triangle::new`]; `Engine::update` stamps it from the mesh.
+    pub texture_region: Option<TextureRegion>,
     /// How `vertex_colors` was lit. Selects the untextured shader.
     pub shading_mode: ShadingMode,
     /// How a texture sample (if any) combines with `vertex_colors`.
     /// Drives top-level shader dispatch.
     pub texture_mode: TextureMode,
+    /// Ordered-dither interpolated colors to hide 8-bit banding.
+    /// `false` from [`Triangle::new`]; `Engine::update` stamps it from
+    /// `Engine::set_dithering`.
+    pub dither: bool,
+    /// Map textures linearly in screen space rather than
+    /// perspective-correctly, for the retro look. `false` from
+    /// [`Triangle::new`]; `Engine::update` stamps it from
+    /// `Engine::set_retro_mode`. The scanline rasterizer maps textures this
+    /// way regardless.
+    pub affine_textures: bool,
+    /// Distance fog blended over the shaded color. `None` from
+    /// [`Triangle::new`]; `Engine::update` stamps it from `Engine::set_fog`.
+    pub fog: Option<Fog>,
+    /// Per-vertex object-space positions, interpolated through clipping.
+    /// Only read by `TextureMode::Checker`; zero from [`Triangle::new`].
+    pub surface_positions: [Vec3; 3],
+    /// Per-vertex world-space positions. Zero from [`Triangle::new`].
+    pub world_positions: [Vec3; 3],
+    /// Per-vertex world-space normals. Zero from [`Triangle::new`].
+    pub normals: [Vec3; 3],
+    /// Per-vertex world-space tangents. Zero from [`Triangle::new`].
+    pub tangents: [Vec3; 3],
+    /// Per-vertex world-space bitangents. Zero from [`Triangle::new`].
+    pub bitangents: [Vec3; 3],
+    /// Light evaluated per pixel, with `vertex_colors` left unlit. `None`
+    /// from [`Triangle::new`].
+    pub light: Option<DirectionalLight>,
+    /// Edges the wireframe pass draws, bit `i` for `points[i]` to
+    /// `points[(i + 1) % 3]`. [`Triangle::ALL_EDGES`] from [`Triangle::new`].
+    pub edge_flags: u8,
+    /// Passes that draw the triangle, from [`Triangle::FILL_PASS`],
+    /// [`Triangle::WIREFRAME_PASS`] and [`Triangle::VERTEX_PASS`]. Fill
+    /// and wireframe, as in the default `RenderMode`, from
+    /// [`Triangle::new`].
+    pub pass_flags: u8,
+    /// Mean view depth of the corners, for back-to-front sorting. `0.0`
+    /// from [`Triangle::new`]; `Engine::update` fills it in only for
+    /// `HiddenSurface::PainterSort`.
+    pub avg_depth: f32,
+    /// Picking identifier. `0` (from [`Triangle::new`]) means "unpickable";
+    /// `Engine::update` assigns one per source face.
+    pub id: u32,
 }
 
 impl Triangle {
+    /// [`Triangle::edge_flags`] with every edge drawn.
+    pub const ALL_EDGES: u8 = 0b111;
+    /// [`Triangle::pass_flags`] bit for the filled pass.
+    pub const FILL_PASS: u8 = 0b001;
+    /// [`Triangle::pass_flags`] bit for the wireframe pass.
+    pub const WIREFRAME_PASS: u8 = 0b010;
+    /// [`Triangle::pass_flags`] bit for the vertex marker pass.
+    pub const VERTEX_PASS: u8 = 0b100;
+
     pub fn new(
         points: [ScreenVertex; 3],
         color: u32,
-        vertex_colors: [u32; 3],
+        vertex_colors: [[f32; 3]; 3],
         texture_coords: [Vec2; 3],
         shading_mode: ShadingMode,
         texture_mode: TextureMode,
@@ -132,8 +318,22 @@ impl Triangle {
             color,
             vertex_colors,
             texture_coords,
+            texture_region: None,
             shading_mode,
             texture_mode,
+            dither: false,
+            affine_textures: false,
+            fog: None,
+            surface_positions: [Vec3::ZERO; 3],
+            world_positions: [Vec3::ZERO; 3],
+            normals: [Vec3::ZERO; 3],
+            tangents: [Vec3::ZERO; 3],
+            bitangents: [Vec3::ZERO; 3],
+            light: None,
+            edge_flags: Self::ALL_EDGES,
+            pass_flags: Self::FILL_PASS | Self::WIREFRAME_PASS,
+            avg_depth: 0.0,
+            id: 0,
         }
     }
 }
@@ -142,7 +342,8 @@ impl Triangle {
 ///
 /// Implementors define how triangles are filled into a pixel buffer.
 /// This allows swapping between different rasterization strategies
-/// (scanline, edge functions, etc.) for testing and benchmarking.
+/// (scanline, edge functions, etc.) for testing and benchmarking. The trait
+/// is object-safe; register your own with `Engine::set_custom_rasterizer`.
 pub trait Rasterizer {
     /// Fill a triangle into the frame buffer.
     ///
@@ -157,6 +358,27 @@ pub trait Rasterizer {
         color: u32,
         texture: Option<&Texture>,
     );
+
+    /// Fill a triangle, coloring its pixels with `shader` instead of the
+    /// shader its modes would select. `Engine::set_custom_shader` routes
+    /// filled triangles here.
+    ///
+    /// The default ignores `shader` and calls [`Rasterizer::fill_triangle`],
+    /// so rasterizers that only cover pixels keep working unchanged.
+    fn fill_triangle_with_shader(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        _shader: &dyn PixelShader,
+        texture: Option<&Texture>,
+    ) {
+        self.fill_triangle(triangle, buffer, triangle.color, texture);
+    }
+
+    /// Short display name, shown in the debug HUD and benchmark groups.
+    fn name(&self) -> &str {
+        "Custom"
+    }
 }
 
 /// Available rasterization algorithms.
@@ -164,6 +386,7 @@ pub trait Rasterizer {
 /// Use this enum to select which rasterizer the engine should use.
 /// Can be changed at runtime via `Engine::set_rasterizer`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum RasterizerType {
     /// Scanline rasterizer using flat-top/flat-bottom triangle decomposition.
     /// Generally faster for larger triangles due to efficient horizontal span filling.
@@ -173,6 +396,10 @@ pub enum RasterizerType {
     /// Simpler algorithm, forms the basis for GPU rasterization.
     /// Better for small triangles or when barycentric coordinates are needed.
     EdgeFunction,
+    /// The user-supplied rasterizer registered with
+    /// `Engine::set_custom_rasterizer`. Selecting it before one is
+    /// registered leaves the current rasterizer active.
+    Custom,
 }
 
 impl std::fmt::Display for RasterizerType {
@@ -180,14 +407,17 @@ impl std::fmt::Display for RasterizerType {
         match self {
             RasterizerType::Scanline => write!(f, "Scanline"),
             RasterizerType::EdgeFunction => write!(f, "EdgeFunction"),
+            RasterizerType::Custom => write!(f, "Custom"),
         }
     }
 }
 
-/// Internal dispatcher that holds both rasterizer implementations.
+/// Internal dispatcher that holds both built-in rasterizers and an optional
+/// user-supplied one.
 pub struct RasterizerDispatcher {
     scanline: ScanlineRasterizer,
     edge_function: EdgeFunctionRasterizer,
+    custom: Option<Box<dyn Rasterizer>>,
     active: RasterizerType,
 }
 
@@ -196,17 +426,49 @@ impl RasterizerDispatcher {
         Self {
             scanline: ScanlineRasterizer::new(),
             edge_function: EdgeFunctionRasterizer::new(),
+            custom: None,
             active: rasterizer_type,
         }
     }
 
+    /// Switch rasterizers. `Custom` is ignored until one is registered.
     pub fn set_type(&mut self, rasterizer_type: RasterizerType) {
-        self.active = rasterizer_type;
+        if rasterizer_type != RasterizerType::Custom || self.custom.is_some() {
+            self.active = rasterizer_type;
+        }
     }
 
     pub fn active_type(&self) -> RasterizerType {
         self.active
     }
+
+    /// Register `rasterizer` and make it active.
+    pub fn set_custom(&mut self, rasterizer: Box<dyn Rasterizer>) {
+        self.custom = Some(rasterizer);
+        self.active = RasterizerType::Custom;
+    }
+
+    /// Drop the custom rasterizer, falling back to the default if it was
+    /// active.
+    pub fn clear_custom(&mut self) -> Option<Box<dyn Rasterizer>> {
+        if self.active == RasterizerType::Custom {
+            self.active = RasterizerType::default();
+        }
+        self.custom.take()
+    }
+
+    pub fn custom(&self) -> Option<&dyn Rasterizer> {
+        self.custom.as_deref()
+    }
+
+    fn active(&self) -> &dyn Rasterizer {
+        match (self.active, &self.custom) {
+            (RasterizerType::Scanline, _) => &self.scanline,
+            (RasterizerType::EdgeFunction, _) => &self.edge_function,
+            (RasterizerType::Custom, Some(custom)) => custom.as_ref(),
+            (RasterizerType::Custom, None) => unreachable!("Custom selected without a rasterizer"),
+        }
+    }
 }
 
 impl Rasterizer for RasterizerDispatcher {
@@ -225,6 +487,67 @@ impl Rasterizer for RasterizerDispatcher {
             RasterizerType::EdgeFunction => self
                 .edge_function
                 .fill_triangle(triangle, buffer, color, texture),
+            RasterizerType::Custom => self
+                .active()
+                .fill_triangle(triangle, buffer, color, texture),
         }
     }
+
+    #[inline]
+    fn fill_triangle_with_shader(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        shader: &dyn PixelShader,
+        texture: Option<&Texture>,
+    ) {
+        self.active()
+            .fill_triangle_with_shader(triangle, buffer, shader, texture);
+    }
+
+    /// Name of the active rasterizer.
+    fn name(&self) -> &str {
+        self.active().name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapping_rounds_to_the_sub_pixel_grid() {
+        let p = SnappedPoint::new(Vec2::new(10.0 + 1.0 / 16.0 + 0.01, -0.02));
+        assert_eq!(p, SnappedPoint { x: 161, y: 0 });
+        assert_eq!(
+            SnappedPoint::pixel_center(2, 0),
+            SnappedPoint::new(Vec2::new(2.5, 0.5))
+        );
+    }
+
+    #[test]
+    fn first_pixel_includes_a_center_exactly_on_the_bound() {
+        // Center of pixel 3 is 3.5, i.e. 56 sixteenths
+        assert_eq!(first_pixel_from(56), 3);
+        assert_eq!(first_pixel_from(57), 4);
+        assert_eq!(first_pixel_from(55), 3);
+        // Negative coordinates round toward +infinity as well
+        assert_eq!(first_pixel_from(-8), -1);
+        assert_eq!(first_pixel_from(-9), -1);
+        assert_eq!(first_pixel_from(-25), -2);
+    }
+
+    #[test]
+    fn only_boxes_without_a_pixel_center_miss() {
+        let misses =
+            |points: [[f32; 2]; 3]| misses_pixel_centers(points.map(|[x, y]| Vec2::new(x, y)));
+        // A speck between the centers of pixels 3 and 4
+        assert!(misses([[3.6, 3.6], [3.9, 3.6], [3.6, 3.9]]));
+        // Long and thin, but it spans centers along x and y
+        assert!(!misses([[0.0, 3.4], [40.0, 3.6], [0.0, 3.5]]));
+        // Long, but between two rows of centers
+        assert!(misses([[0.0, 3.6], [40.0, 3.9], [0.0, 3.7]]));
+        // Touching a center counts as holding it
+        assert!(!misses([[3.5, 3.5], [3.5, 3.5], [3.5, 3.5]]));
+    }
 }
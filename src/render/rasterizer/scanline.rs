@@ -31,6 +31,9 @@
 //! ```
 //!
 //! Special cases (already flat-top or flat-bottom) require no splitting.
+//! The split vertex is never built: the top part is bounded by edges
+//! v0 -> v1 and v0 -> v2, the bottom part by v1 -> v2 and the same long
+//! edge, so both parts follow it exactly.
 //!
 //! # Inverse Slope Method
 //!
@@ -43,6 +46,26 @@
 //!
 //! For each scanline: `x = x_start + inv_slope * (y - y_start)`
 //!
+//! Vertices are first snapped to a 1/16-pixel grid (`SUBPIXEL_BITS`) and
+//! this is evaluated in `i64` as one exact division per edge and row, so
+//! which pixels a span covers never depends on rounding error and edges
+//! don't crawl as a vertex moves within a grid step.
+//!
+//! # Fill Convention
+//!
+//! Rows and spans are sampled at pixel centers and are half-open: a row
+//! `y` is drawn when its center `y + 0.5` lies in `[y_top, y_bottom)`, and
+//! within it the pixels are
+//!
+//! ```text
+//! x in [ceil(x_left - 0.5), ceil(x_right - 0.5))
+//! ```
+//!
+//! with the edges evaluated at the row's center. A center exactly on a
+//! left or top edge is drawn, one on a right or bottom edge is not — the
+//! same top-left rule as the edge function rasterizer, so the two agree
+//! and triangles sharing an edge draw each pixel on it exactly once.
+//!
 //! # Gouraud Shading
 //!
 //! For smooth shading, we perform bilinear interpolation:
@@ -67,12 +90,15 @@
 //! - Foley, van Dam et al., "Computer Graphics: Principles and Practice"
 //! - Abrash, Michael, "Graphics Programming Black Book"
 
-use super::shader::{FlatShader, GouraudShader, PixelShader, TextureModulateShader, TextureShader};
-use super::{Rasterizer, ScreenVertex, Triangle};
+use super::shader::{
+    CheckerShader, FlatShader, FogShader, Fragment, GouraudShader, NormalMapShader, NullShader,
+    PixelShader, TextureModulateShader, TextureShader, ToonShader,
+};
+use super::{
+    ceil_div, first_pixel_from, snapped_edge_function, Rasterizer, SnappedPoint, Triangle,
+};
 use crate::engine::TextureMode;
-use crate::math::utils::{edge_function, triangle_area};
-use crate::math::vec2::Vec2;
-use crate::render::framebuffer::FrameBuffer;
+use crate::render::framebuffer::{DepthPass, FrameBuffer};
 use crate::texture::Texture;
 use crate::ShadingMode;
 
@@ -82,11 +108,33 @@ use crate::ShadingMode;
 /// Returns [λ0, λ1, λ2] where each λ represents the weight of the
 /// corresponding vertex. These sum to 1.0 for points inside the triangle.
 #[inline]
-fn barycentric(v0: Vec2, v1: Vec2, v2: Vec2, p: Vec2, inv_area: f32) -> [f32; 3] {
-    let w0 = edge_function(v1, v2, p);
-    let w1 = edge_function(v2, v0, p);
-    let w2 = edge_function(v0, v1, p);
-    [w0 * inv_area, w1 * inv_area, w2 * inv_area]
+fn barycentric(
+    v0: SnappedPoint,
+    v1: SnappedPoint,
+    v2: SnappedPoint,
+    p: SnappedPoint,
+    inv_area: f32,
+) -> [f32; 3] {
+    let w0 = snapped_edge_function(v1, v2, p);
+    let w1 = snapped_edge_function(v2, v0, p);
+    let w2 = snapped_edge_function(v0, v1, p);
+    [
+        w0 as f32 * inv_area,
+        w1 as f32 * inv_area,
+        w2 as f32 * inv_area,
+    ]
+}
+
+/// The first pixel whose center, on the row through `y`, is at or right of
+/// edge (A -> B). `y` must be within the edge's span, and `b.y > a.y`.
+///
+/// The edge's x there is `a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y)`;
+/// this is that divided into pixels without rounding, so a center exactly
+/// on the edge is found exactly.
+#[inline]
+fn first_pixel_right_of(a: SnappedPoint, b: SnappedPoint, y: i64) -> i32 {
+    let dy = b.y - a.y;
+    first_pixel_from(ceil_div((b.x - a.x) * (y - a.y) + a.x * dy, dy))
 }
 
 /// Scanline-based triangle rasterizer.
@@ -122,15 +170,15 @@ impl ScanlineRasterizer {
     /// # Arguments
     ///
     /// * `v0`, `v1`, `v2` - Mutable references to vertices to be sorted in-place
-    fn sort_vertices(v0: &mut ScreenVertex, v1: &mut ScreenVertex, v2: &mut ScreenVertex) {
+    fn sort_vertices(v0: &mut SnappedPoint, v1: &mut SnappedPoint, v2: &mut SnappedPoint) {
         // Three comparisons suffice for 3 elements (bubble sort)
-        if v1.position.y < v0.position.y {
+        if v1.y < v0.y {
             std::mem::swap(v0, v1);
         }
-        if v2.position.y < v1.position.y {
+        if v2.y < v1.y {
             std::mem::swap(v1, v2);
         }
-        if v1.position.y < v0.position.y {
+        if v1.y < v0.y {
             std::mem::swap(v0, v1);
         }
     }
@@ -139,6 +187,41 @@ impl ScanlineRasterizer {
     // Shader-based rasterization methods
     // =========================================================================
 
+    /// [`Self::rasterize_shaded`] for shaders that color from the lit
+    /// `vertex_colors`. Under toon shading those are unlit, so the shader is
+    /// wrapped in a [`ToonShader`] that lights it in bands.
+    fn rasterize_lit<S: PixelShader>(
+        triangle: &Triangle,
+        texture: Option<&Texture>,
+        buffer: &mut FrameBuffer,
+        shader: S,
+    ) {
+        match (triangle.shading_mode, triangle.light) {
+            (ShadingMode::Toon { bands }, Some(light)) => {
+                let shader = ToonShader::new(shader, light, bands, triangle);
+                Self::rasterize_shaded(triangle, texture, buffer, shader);
+            }
+            _ => Self::rasterize_shaded(triangle, texture, buffer, shader),
+        }
+    }
+
+    /// Rasterize `triangle` with `shader`, wrapped in a [`FogShader`] when
+    /// the triangle carries fog.
+    fn rasterize_shaded<S: PixelShader>(
+        triangle: &Triangle,
+        texture: Option<&Texture>,
+        buffer: &mut FrameBuffer,
+        shader: S,
+    ) {
+        match triangle.fog {
+            Some(fog) => {
+                let shader = FogShader::new(shader, fog, triangle.points);
+                Self::rasterize_with_shader(triangle, texture, buffer, &shader);
+            }
+            None => Self::rasterize_with_shader(triangle, texture, buffer, &shader),
+        }
+    }
+
     /// Rasterize a triangle using the provided pixel shader.
     ///
     /// This method combines scanline traversal (for efficiency) with barycentric
@@ -147,196 +230,123 @@ impl ScanlineRasterizer {
     /// using the original vertex order.
     ///
     /// # Arguments
-    /// * `v0, v1, v2` - Original (unsorted) triangle vertices
+    /// * `triangle` - Triangle to cover, with its original (unsorted)
+    ///   vertices
+    /// * `texture` - Texture handed to the shader with each [`Fragment`]
     /// * `buffer` - Framebuffer to write to
     /// * `shader` - Pixel shader for color computation
     fn rasterize_with_shader<S: PixelShader>(
-        v0: ScreenVertex,
-        v1: ScreenVertex,
-        v2: ScreenVertex,
+        triangle: &Triangle,
+        texture: Option<&Texture>,
         buffer: &mut FrameBuffer,
         shader: &S,
     ) {
+        let [v0, v1, v2] = triangle.points;
+
         // Precompute 1/w for each vertex — linear in screen space,
         // so it can be barycentrically interpolated for depth testing.
         let inv_w = [1.0 / v0.w, 1.0 / v1.w, 1.0 / v2.w];
 
-        // Convert to Vec2 for barycentric calculations (only x, y matter)
-        let v0_2d = Vec2::new(v0.position.x, v0.position.y);
-        let v1_2d = Vec2::new(v1.position.x, v1.position.y);
-        let v2_2d = Vec2::new(v2.position.x, v2.position.y);
+        // Coverage is decided on the sub-pixel grid, in exact integer math
+        let original = [
+            SnappedPoint::new(v0.position),
+            SnappedPoint::new(v1.position),
+            SnappedPoint::new(v2.position),
+        ];
 
         // Compute area for barycentric normalization
-        let area = triangle_area(v0_2d, v1_2d, v2_2d);
-        if area.abs() < f32::EPSILON {
+        let area = snapped_edge_function(original[0], original[1], original[2]);
+        if area == 0 {
             return; // Degenerate triangle
         }
-        let inv_area = 1.0 / area;
+        let inv_area = 1.0 / area as f32;
 
         // Sort vertices for scanline traversal
-        // IMPORTANT: We sort copies, keeping original v0, v1, v2 for barycentrics
-        let mut sv0 = v0;
-        let mut sv1 = v1;
-        let mut sv2 = v2;
+        // IMPORTANT: We sort copies, keeping the originals for barycentrics
+        let [mut sv0, mut sv1, mut sv2] = original;
         Self::sort_vertices(&mut sv0, &mut sv1, &mut sv2);
 
-        // Check triangle type and call appropriate fill method
-        if (sv1.position.y - sv2.position.y).abs() < f32::EPSILON {
-            // Flat-bottom triangle
-            Self::fill_flat_bottom_with_shader(
-                sv0, sv1, sv2, v0_2d, v1_2d, v2_2d, inv_w, inv_area, buffer, shader,
-            );
-        } else if (sv0.position.y - sv1.position.y).abs() < f32::EPSILON {
-            // Flat-top triangle
-            Self::fill_flat_top_with_shader(
-                sv0, sv1, sv2, v0_2d, v1_2d, v2_2d, inv_w, inv_area, buffer, shader,
-            );
-        } else {
-            // General triangle - split into flat-bottom + flat-top
-
-            // t is the ratio of the height of the triangle from sv0 to sv1 to the total height of the triangle
-            let t = (sv1.position.y - sv0.position.y) / (sv2.position.y - sv0.position.y);
-            // We calculate the midpoint x coordinate by interpolating the x coordinates of sv0 and sv2 based on the ratio t
-            let split_x = sv0.position.x + (sv2.position.x - sv0.position.x) * t;
-            // The split point's W is never read downstream — the helpers only
-            // use `.position` for scanline traversal, and depth interpolation
-            // pulls from the original `inv_w` array. Pass `sv0.w` so the
-            // ScreenVertex has a valid (positive) W if anyone later reads it.
-            let split_point = ScreenVertex::new(Vec2::new(split_x, sv1.position.y), sv0.w);
-
-            // Fill top half (flat-bottom)
-            Self::fill_flat_bottom_with_shader(
-                sv0,
-                split_point,
-                sv1,
-                v0_2d,
-                v1_2d,
-                v2_2d, // Always use original for barycentrics
-                inv_w,
-                inv_area,
-                buffer,
-                shader,
-            );
-
-            // Fill bottom half (flat-top)
-            Self::fill_flat_top_with_shader(
-                sv1,
-                split_point,
-                sv2,
-                v0_2d,
-                v1_2d,
-                v2_2d,
-                inv_w,
-                inv_area,
-                buffer,
-                shader,
-            );
-        }
+        // Top part (flat-bottom): rows between sv0 and sv1, bounded by the
+        // short edge sv0 -> sv1 and the long edge sv0 -> sv2
+        Self::fill_rows_with_shader(
+            (sv0, sv1),
+            (sv0, sv2),
+            original,
+            inv_w,
+            inv_area,
+            triangle,
+            texture,
+            buffer,
+            shader,
+        );
+
+        // Bottom part (flat-top): rows between sv1 and sv2, bounded by the
+        // short edge sv1 -> sv2 and the same long edge
+        Self::fill_rows_with_shader(
+            (sv1, sv2),
+            (sv0, sv2),
+            original,
+            inv_w,
+            inv_area,
+            triangle,
+            texture,
+            buffer,
+            shader,
+        );
     }
 
-    /// Fill a flat-bottom triangle using a pixel shader.
+    /// Fill the rows spanned by `short_edge`, between it and `long_edge`,
+    /// using a pixel shader.
     ///
-    /// # Arguments
-    /// * `sv0, sv1, sv2` - Sorted vertices for scanline traversal
-    /// * `v0, v1, v2` - Original vertices (Vec2) for barycentric computation
-    /// * `inv_w` - 1/w values for each original vertex (for depth interpolation)
-    /// * `inv_area` - 1/area for barycentric normalization
-    fn fill_flat_bottom_with_shader<S: PixelShader>(
-        sv0: ScreenVertex, // Top vertex (sorted)
-        sv1: ScreenVertex, // Bottom-left (sorted)
-        sv2: ScreenVertex, // Bottom-right (sorted)
-        v0: Vec2,          // Original vertices for barycentrics
-        v1: Vec2,
-        v2: Vec2,
-        inv_w: [f32; 3], // 1/w for each original vertex
-        inv_area: f32,
-        buffer: &mut FrameBuffer,
-        shader: &S,
-    ) {
-        let height = sv1.position.y - sv0.position.y;
-        if height.abs() < f32::EPSILON {
-            return;
-        }
-
-        let inv_slope_1 = (sv1.position.x - sv0.position.x) / height;
-        let inv_slope_2 = (sv2.position.x - sv0.position.x) / height;
-
-        let y_start = sv0.position.y.ceil() as i32;
-        let y_end = sv1.position.y.floor() as i32;
-
-        for y in y_start..=y_end {
-            let dy = y as f32 - sv0.position.y;
-            let x1 = sv0.position.x + inv_slope_1 * dy;
-            let x2 = sv0.position.x + inv_slope_2 * dy;
-
-            let (x_left, x_right) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
-
-            let x_start = x_left.ceil() as i32;
-            let x_end = x_right.floor() as i32;
-
-            for x in x_start..=x_end {
-                // Compute barycentric coords using ORIGINAL vertices
-                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
-                let lambda = barycentric(v0, v1, v2, p, inv_area);
-
-                // Interpolate 1/w for depth testing (linear in screen space)
-                let depth = lambda[0] * inv_w[0] + lambda[1] * inv_w[1] + lambda[2] * inv_w[2];
-
-                let color = shader.shade(lambda);
-                buffer.set_pixel_with_depth(x, y, depth, color);
-            }
-        }
-    }
-
-    /// Fill a flat-top triangle using a pixel shader.
+    /// Both edges run top to bottom, and `long_edge` spans every row
+    /// `short_edge` does. A flat short edge spans no rows and draws nothing.
     ///
     /// # Arguments
-    /// * `sv0, sv1, sv2` - Sorted vertices for scanline traversal
-    /// * `v0, v1, v2` - Original vertices (Vec2) for barycentric computation
+    /// * `short_edge`, `long_edge` - Sorted endpoints of the bounding edges
+    /// * `original` - Unsorted vertices for barycentric computation
     /// * `inv_w` - 1/w values for each original vertex (for depth interpolation)
     /// * `inv_area` - 1/area for barycentric normalization
-    fn fill_flat_top_with_shader<S: PixelShader>(
-        sv0: ScreenVertex, // Top-left (sorted)
-        sv1: ScreenVertex, // Top-right (sorted)
-        sv2: ScreenVertex, // Bottom vertex (sorted)
-        v0: Vec2,          // Original vertices for barycentrics
-        v1: Vec2,
-        v2: Vec2,
-        inv_w: [f32; 3], // 1/w for each original vertex
+    /// * `triangle` - The triangle being drawn; its `id` is written
+    ///   alongside each pixel
+    /// * `texture` - Texture handed to the shader with each [`Fragment`]
+    #[allow(clippy::too_many_arguments)]
+    fn fill_rows_with_shader<S: PixelShader>(
+        short_edge: (SnappedPoint, SnappedPoint),
+        long_edge: (SnappedPoint, SnappedPoint),
+        original: [SnappedPoint; 3],
+        inv_w: [f32; 3],
         inv_area: f32,
+        triangle: &Triangle,
+        texture: Option<&Texture>,
         buffer: &mut FrameBuffer,
         shader: &S,
     ) {
-        let height = sv2.position.y - sv0.position.y;
-        if height.abs() < f32::EPSILON {
-            return;
-        }
-
-        let inv_slope_1 = (sv2.position.x - sv0.position.x) / height;
-        let inv_slope_2 = (sv2.position.x - sv1.position.x) / height;
-
-        let y_start = sv0.position.y.ceil() as i32;
-        let y_end = sv2.position.y.floor() as i32;
+        let (top, bottom) = short_edge;
+        let [v0, v1, v2] = original;
 
-        for y in y_start..=y_end {
-            let dy = y as f32 - sv0.position.y;
-            let x1 = sv0.position.x + inv_slope_1 * dy;
-            let x2 = sv1.position.x + inv_slope_2 * dy;
+        // Half-open: rows whose centers are in [top.y, bottom.y)
+        let y_start = first_pixel_from(top.y).max(0);
+        let y_end = first_pixel_from(bottom.y).min(buffer.height() as i32);
 
-            let (x_left, x_right) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+        for y in y_start..y_end {
+            // Edges are evaluated at the row's pixel centers
+            let center_y = SnappedPoint::pixel_center(0, y).y;
+            let x1 = first_pixel_right_of(top, bottom, center_y);
+            let x2 = first_pixel_right_of(long_edge.0, long_edge.1, center_y);
 
-            let x_start = x_left.ceil() as i32;
-            let x_end = x_right.floor() as i32;
+            // Half-open: pixels from the left edge up to the right edge
+            let (x_start, x_end) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
 
-            for x in x_start..=x_end {
-                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            for x in x_start.max(0)..x_end.min(buffer.width() as i32) {
+                // Compute barycentric coords using ORIGINAL vertices
+                let p = SnappedPoint::pixel_center(x, y);
                 let lambda = barycentric(v0, v1, v2, p, inv_area);
 
                 // Interpolate 1/w for depth testing (linear in screen space)
                 let depth = lambda[0] * inv_w[0] + lambda[1] * inv_w[1] + lambda[2] * inv_w[2];
 
-                let color = shader.shade(lambda);
-                buffer.set_pixel_with_depth(x, y, depth, color);
+                let fragment = Fragment::new(lambda, x, y, triangle, texture);
+                buffer.shade_fragment(depth, &fragment, shader);
             }
         }
     }
@@ -358,11 +368,17 @@ impl Rasterizer for ScanlineRasterizer {
     /// # Shader Selection
     ///
     /// The shader is selected based on texture mode and shading mode:
+    /// - Depth-only pass: NullShader (nothing is shaded)
+    /// - NormalMapped with a per-pixel light: NormalMapShader
+    /// - Checker: CheckerShader (procedural pattern * lighting)
     /// - Texture Replace: TextureShader (texture color only)
     /// - Texture Modulate: TextureModulateShader (texture * lighting)
-    /// - Gouraud: GouraudShader (interpolated vertex colors)
+    /// - Gouraud/Toon: GouraudShader (interpolated vertex colors)
     /// - Flat/None: FlatShader (single color)
     ///
+    /// Under Toon, every shader but NormalMapShader and TextureShader is
+    /// wrapped in a ToonShader that lights it in bands.
+    ///
     /// # Arguments
     ///
     /// * `triangle` - Triangle to rasterize with vertices, colors, UVs, and modes
@@ -376,32 +392,82 @@ impl Rasterizer for ScanlineRasterizer {
         color: u32,
         texture: Option<&Texture>,
     ) {
-        let [v0, v1, v2] = triangle.points;
+        // A depth-only pass never shades, so skip building the real shader
+        if buffer.depth_pass() == DepthPass::DepthOnly {
+            Self::rasterize_with_shader(triangle, None, buffer, &NullShader);
+            return;
+        }
+
+        // Lit per pixel, so `vertex_colors` hold the unlit base color
+        if let (TextureMode::NormalMapped, Some(normal_map), Some(light)) =
+            (triangle.texture_mode, texture, triangle.light)
+        {
+            let shader = NormalMapShader::new(normal_map, triangle, light);
+            Self::rasterize_shaded(triangle, texture, buffer, shader);
+            return;
+        }
 
         // Select shader based on texture_mode and shading_mode
         match (triangle.texture_mode, texture) {
-            (TextureMode::Replace, Some(tex)) => {
-                let shader = TextureShader::new(tex, triangle.texture_coords);
-                Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+            // Procedural; needs no texture
+            (TextureMode::Checker { scale }, _) => {
+                let shader = CheckerShader::new(
+                    triangle.surface_positions,
+                    triangle.points,
+                    triangle.vertex_colors,
+                    scale,
+                    triangle.dither,
+                );
+                Self::rasterize_lit(triangle, texture, buffer, shader);
+            }
+            // Baked mesh colors vary per vertex whatever the shading mode
+            (TextureMode::VertexColor, _) => {
+                let shader = GouraudShader::new(triangle.vertex_colors, triangle.dither);
+                Self::rasterize_lit(triangle, texture, buffer, shader);
+            }
+            (TextureMode::Replace | TextureMode::SphereMap, Some(tex)) => {
+                let shader = TextureShader::new(tex, triangle.texture_coords)
+                    .with_region(triangle.texture_region);
+                Self::rasterize_shaded(triangle, texture, buffer, shader);
             }
             (TextureMode::Modulate, Some(tex)) => {
                 let shader = TextureModulateShader::new(
                     tex,
                     triangle.texture_coords,
                     triangle.vertex_colors,
-                );
-                Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                    triangle.dither,
+                )
+                .with_region(triangle.texture_region);
+                Self::rasterize_lit(triangle, texture, buffer, shader);
             }
             _ => match triangle.shading_mode {
-                ShadingMode::Gouraud => {
-                    let shader = GouraudShader::new(triangle.vertex_colors);
-                    Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                ShadingMode::Gouraud | ShadingMode::Toon { .. } => {
+                    let shader = GouraudShader::new(triangle.vertex_colors, triangle.dither);
+                    Self::rasterize_lit(triangle, texture, buffer, shader);
                 }
                 ShadingMode::Flat | ShadingMode::None => {
                     let shader = FlatShader::new(color);
-                    Self::rasterize_with_shader(v0, v1, v2, buffer, &shader);
+                    Self::rasterize_lit(triangle, texture, buffer, shader);
                 }
             },
         }
     }
+
+    fn fill_triangle_with_shader(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        shader: &dyn PixelShader,
+        texture: Option<&Texture>,
+    ) {
+        if buffer.depth_pass() == DepthPass::DepthOnly {
+            Self::rasterize_with_shader(triangle, None, buffer, &NullShader);
+            return;
+        }
+        Self::rasterize_shaded(triangle, texture, buffer, shader);
+    }
+
+    fn name(&self) -> &str {
+        "Scanline"
+    }
 }
@@ -17,29 +17,238 @@
 //! - Texture sampling
 //! - Final color computation
 
-use super::ScreenVertex;
-use crate::colors::{pack_color, unpack_color};
-use crate::prelude::Vec2;
-use crate::texture::Texture;
+use super::{ScreenVertex, Triangle};
+use crate::colors::{
+    from_rgb_f32, pack_color, pack_color_dithered, scale_rgb, unpack_color, with_alpha_of,
+};
+use crate::engine::{DebugView, OVERDRAW_STEP};
+use crate::fog::Fog;
+use crate::light::DirectionalLight;
+use crate::prelude::{Vec2, Vec3};
+use crate::texture::{Texture, TextureRegion};
 
 /// Trait for per-pixel shading computations.
 ///
-/// The rasterizer calls `shade()` for each pixel inside the triangle,
-/// providing the barycentric coordinates for attribute interpolation.
+/// The rasterizer calls `shade()` for each visible pixel inside the
+/// triangle with a [`Fragment`]: the barycentric coordinates for attribute
+/// interpolation, the integer pixel coordinates (used for screen-space
+/// effects such as ordered dithering), and the triangle and texture being
+/// drawn.
+///
+/// The built-in shaders are built per triangle with their inputs
+/// pre-divided and only read `lambda`, `x` and `y`. A shader registered
+/// with `Engine::set_custom_shader` is built once and pulls what it needs
+/// through the [`Fragment`] helpers instead.
 ///
 /// # Barycentric Coordinates
 ///
-/// The `lambda` parameter contains three weights [λ₀, λ₁, λ₂] that:
+/// The `lambda` field contains three weights [λ₀, λ₁, λ₂] that:
 /// - Sum to 1.0 for any point inside the triangle
 /// - Represent the "influence" of each vertex on the current pixel
 /// - Can be used to interpolate any per-vertex attribute:
 ///   `attr_at_pixel = λ₀*attr₀ + λ₁*attr₁ + λ₂*attr₂`
+///
+/// # Example
+///
+/// ```
+/// use russsty::prelude::*;
+///
+/// /// Paints every surface by its world-space normal.
+/// struct NormalShader;
+///
+/// impl PixelShader for NormalShader {
+///     fn shade(&self, fragment: &Fragment) -> u32 {
+///         let n = fragment.normal();
+///         russsty::colors::pack_color(n.x * 0.5 + 0.5, n.y * 0.5 + 0.5, n.z * 0.5 + 0.5, 1.0)
+///     }
+/// }
+///
+/// let mut engine = Engine::new(64, 64);
+/// engine.set_custom_shader(Some(Box::new(NormalShader)));
+/// ```
 pub trait PixelShader {
-    /// Compute the color for a pixel given its barycentric coordinates.
+    /// Compute the packed ARGB color of `fragment`'s pixel.
+    fn shade(&self, fragment: &Fragment) -> u32;
+
+    /// Compute what `fragment`'s pixel writes when it is lit afterwards
+    /// rather than by the shader: under `Pipeline::Deferred` into the
+    /// G-buffer, and with point lights for the forward lighting step.
     ///
-    /// # Arguments
-    /// * `lambda` - Barycentric coordinates [λ₀, λ₁, λ₂] that sum to 1.0
-    fn shade(&self, lambda: [f32; 3]) -> u32;
+    /// The default takes [`PixelShader::shade`] as the unlit color and
+    /// lights it by the interpolated normal. Shaders whose color is
+    /// already final return a zero normal, and shaders that perturb the
+    /// normal return the perturbed one.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        GBufferSample {
+            albedo: self.shade(fragment),
+            normal: fragment.normal(),
+        }
+    }
+}
+
+impl<S: PixelShader + ?Sized> PixelShader for &S {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        (**self).shade(fragment)
+    }
+
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        (**self).shade_gbuffer(fragment)
+    }
+}
+
+/// One pixel's surface before lighting, as written by
+/// [`PixelShader::shade_gbuffer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GBufferSample {
+    /// Packed ARGB color of the surface, unlit.
+    pub albedo: u32,
+    /// World-space normal to light by; zero leaves `albedo` unlit.
+    pub normal: Vec3,
+}
+
+/// One covered pixel of a triangle, as handed to [`PixelShader::shade`].
+///
+/// The fields are what the rasterizer knows directly; the methods
+/// interpolate the triangle's per-vertex attributes at the pixel.
+/// Positions, normals and UVs are interpolated perspective-correctly;
+/// colors are interpolated linearly in screen space, as the built-in
+/// Gouraud shader does.
+#[derive(Clone, Copy)]
+pub struct Fragment<'a> {
+    /// Screen-space barycentric coordinates [λ₀, λ₁, λ₂], summing to 1.0.
+    pub lambda: [f32; 3],
+    /// Pixel column.
+    pub x: i32,
+    /// Pixel row.
+    pub y: i32,
+    /// The triangle being drawn, with every per-vertex attribute.
+    pub triangle: &'a Triangle,
+    /// The texture bound for the triangle's mesh, if any. With
+    /// `TextureMode::NormalMapped` this is the normal map.
+    pub texture: Option<&'a Texture>,
+}
+
+impl<'a> Fragment<'a> {
+    pub fn new(
+        lambda: [f32; 3],
+        x: i32,
+        y: i32,
+        triangle: &'a Triangle,
+        texture: Option<&'a Texture>,
+    ) -> Self {
+        Self {
+            lambda,
+            x,
+            y,
+            triangle,
+            texture,
+        }
+    }
+
+    /// Barycentric weights corrected for perspective: interpolating any
+    /// attribute with these matches interpolating it in 3D.
+    #[inline]
+    pub fn perspective_lambda(&self) -> [f32; 3] {
+        let weighted: [f32; 3] =
+            std::array::from_fn(|i| self.lambda[i] / self.triangle.points[i].w);
+        let sum = weighted[0] + weighted[1] + weighted[2];
+        weighted.map(|l| l / sum)
+    }
+
+    /// View-space depth of the pixel (the interpolated clip `w`).
+    #[inline]
+    pub fn depth(&self) -> f32 {
+        let inv_w: f32 = (0..3)
+            .map(|i| self.lambda[i] / self.triangle.points[i].w)
+            .sum();
+        1.0 / inv_w
+    }
+
+    /// Interpolated `vertex_colors`: lit by the engine unless the shading
+    /// mode is `ShadingMode::None`, in which case it is the base color.
+    #[inline]
+    pub fn color(&self) -> [f32; 3] {
+        let colors = &self.triangle.vertex_colors;
+        std::array::from_fn(|c| {
+            self.lambda[0] * colors[0][c]
+                + self.lambda[1] * colors[1][c]
+                + self.lambda[2] * colors[2][c]
+        })
+    }
+
+    /// Interpolated texture coordinates.
+    #[inline]
+    pub fn uv(&self) -> Vec2 {
+        let [l0, l1, l2] = self.perspective_lambda();
+        let uvs = &self.triangle.texture_coords;
+        uvs[0] * l0 + uvs[1] * l1 + uvs[2] * l2
+    }
+
+    /// Interpolated world-space normal, facing the camera and renormalized.
+    /// Zero if the mesh has no normals.
+    #[inline]
+    pub fn normal(&self) -> Vec3 {
+        let [l0, l1, l2] = self.perspective_lambda();
+        let normals = &self.triangle.normals;
+        let normal = normals[0] * l0 + normals[1] * l1 + normals[2] * l2;
+        if normal.magnitude() > 0.0 {
+            normal.normalize()
+        } else {
+            normal
+        }
+    }
+
+    /// Interpolated world-space position.
+    #[inline]
+    pub fn world_position(&self) -> Vec3 {
+        let [l0, l1, l2] = self.perspective_lambda();
+        let positions = &self.triangle.world_positions;
+        positions[0] * l0 + positions[1] * l1 + positions[2] * l2
+    }
+
+    /// The texture sampled at [`Fragment::uv`], through the triangle's
+    /// atlas region. `None` when no texture is bound.
+    #[inline]
+    pub fn sample_texture(&self) -> Option<u32> {
+        let uv = self.uv();
+        self.texture
+            .map(|texture| sample(texture, self.triangle.texture_region.as_ref(), uv.x, uv.y))
+    }
+}
+
+/// 4x4 Bayer matrix for ordered dithering.
+///
+/// Each entry is a rank in `0..16`; neighbouring pixels get thresholds that
+/// are as far apart as possible, so quantization error is spread into a
+/// fine, regular pattern instead of visible bands.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Quantization threshold in (0, 1) for pixel (x, y).
+///
+/// Without dithering the threshold is a constant 0.5 (plain rounding).
+#[inline]
+fn quantize_threshold(dither: bool, x: i32, y: i32) -> f32 {
+    if dither {
+        (BAYER_4X4[(y & 3) as usize][(x & 3) as usize] as f32 + 0.5) / 16.0
+    } else {
+        0.5
+    }
+}
+
+/// Null shader - colors nothing.
+///
+/// Used for depth-only passes, where the framebuffer writes depth and
+/// never asks for a color, so there is no point building a real shader.
+pub struct NullShader;
+
+impl PixelShader for NullShader {
+    #[inline]
+    fn shade(&self, _fragment: &Fragment) -> u32 {
+        0
+    }
 }
 
 /// Flat shader - returns a constant color for all pixels.
@@ -58,7 +267,7 @@ impl FlatShader {
 
 impl PixelShader for FlatShader {
     #[inline]
-    fn shade(&self, _lambda: [f32; 3]) -> u32 {
+    fn shade(&self, _fragment: &Fragment) -> u32 {
         self.color
     }
 }
@@ -68,35 +277,45 @@ impl PixelShader for FlatShader {
 /// Used for smooth shading where colors are computed per-vertex from
 /// vertex normals and then interpolated across the triangle.
 pub struct GouraudShader {
-    /// Unpacked RGB colors for each vertex, in [0.0, 1.0] range
-    colors: [(f32, f32, f32); 3],
+    /// RGB colors for each vertex, in [0.0, 1.0] range
+    colors: [[f32; 3]; 3],
+    /// Apply ordered dithering when packing the interpolated color
+    dither: bool,
 }
 
 impl GouraudShader {
-    pub fn new(vertex_colors: [u32; 3]) -> Self {
+    pub fn new(vertex_colors: [[f32; 3]; 3], dither: bool) -> Self {
         Self {
-            colors: [
-                unpack_color(vertex_colors[0]),
-                unpack_color(vertex_colors[1]),
-                unpack_color(vertex_colors[2]),
-            ],
+            colors: vertex_colors,
+            dither,
         }
     }
 }
 
 impl PixelShader for GouraudShader {
     #[inline]
-    fn shade(&self, lambda: [f32; 3]) -> u32 {
-        let r = lambda[0] * self.colors[0].0
-            + lambda[1] * self.colors[1].0
-            + lambda[2] * self.colors[2].0;
-        let g = lambda[0] * self.colors[0].1
-            + lambda[1] * self.colors[1].1
-            + lambda[2] * self.colors[2].1;
-        let b = lambda[0] * self.colors[0].2
-            + lambda[1] * self.colors[1].2
-            + lambda[2] * self.colors[2].2;
-        pack_color(r, g, b, 1.0)
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let Fragment { lambda, x, y, .. } = *fragment;
+        let r = lambda[0] * self.colors[0][0]
+            + lambda[1] * self.colors[1][0]
+            + lambda[2] * self.colors[2][0];
+        let g = lambda[0] * self.colors[0][1]
+            + lambda[1] * self.colors[1][1]
+            + lambda[2] * self.colors[2][1];
+        let b = lambda[0] * self.colors[0][2]
+            + lambda[1] * self.colors[1][2]
+            + lambda[2] * self.colors[2][2];
+        pack_color_dithered(r, g, b, quantize_threshold(self.dither, x, y))
+    }
+}
+
+/// Sample `texture` at `(u, v)`, through `region` when the triangle maps
+/// onto part of an atlas.
+#[inline]
+fn sample(texture: &Texture, region: Option<&TextureRegion>, u: f32, v: f32) -> u32 {
+    match region {
+        Some(region) => texture.sample_region(u, v, region),
+        None => texture.sample(u, v),
     }
 }
 
@@ -106,12 +325,24 @@ impl PixelShader for GouraudShader {
 /// vertex colors entirely (no lighting modulation).
 pub struct TextureShader<'a> {
     texture: &'a Texture,
+    /// Part of `texture` the UVs map onto; all of it when `None`
+    region: Option<TextureRegion>,
     uvs: [Vec2; 3],
 }
 
 impl<'a> TextureShader<'a> {
+    /// Map the UVs onto `region` of the texture rather than all of it.
+    pub fn with_region(mut self, region: Option<TextureRegion>) -> Self {
+        self.region = region;
+        self
+    }
+
     pub fn new(texture: &'a Texture, uvs: [Vec2; 3]) -> Self {
-        Self { texture, uvs }
+        Self {
+            texture,
+            region: None,
+            uvs,
+        }
     }
 
     /// Interpolate UV coordinates using barycentric weights
@@ -125,9 +356,19 @@ impl<'a> TextureShader<'a> {
 
 impl PixelShader for TextureShader<'_> {
     #[inline]
-    fn shade(&self, lambda: [f32; 3]) -> u32 {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let lambda = fragment.lambda;
         let (u, v) = self.interpolate_uv(lambda);
-        self.texture.sample(u, v)
+        sample(self.texture, self.region.as_ref(), u, v)
+    }
+
+    /// Replaced textures ignore lighting, so the texel stays unlit.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        GBufferSample {
+            albedo: self.shade(fragment),
+            normal: Vec3::ZERO,
+        }
     }
 }
 
@@ -141,21 +382,34 @@ impl PixelShader for TextureShader<'_> {
 /// the texture detail.
 pub struct TextureModulateShader<'a> {
     texture: &'a Texture,
+    /// Part of `texture` the UVs map onto; all of it when `None`
+    region: Option<TextureRegion>,
     uvs: [Vec2; 3],
-    /// Unpacked vertex colors representing lighting intensity
-    colors: [(f32, f32, f32); 3],
+    /// Vertex colors representing lighting intensity
+    colors: [[f32; 3]; 3],
+    /// Apply ordered dithering when packing the modulated color
+    dither: bool,
 }
 
 impl<'a> TextureModulateShader<'a> {
-    pub fn new(texture: &'a Texture, uvs: [Vec2; 3], vertex_colors: [u32; 3]) -> Self {
+    /// Map the UVs onto `region` of the texture rather than all of it.
+    pub fn with_region(mut self, region: Option<TextureRegion>) -> Self {
+        self.region = region;
+        self
+    }
+
+    pub fn new(
+        texture: &'a Texture,
+        uvs: [Vec2; 3],
+        vertex_colors: [[f32; 3]; 3],
+        dither: bool,
+    ) -> Self {
         Self {
             texture,
+            region: None,
             uvs,
-            colors: [
-                unpack_color(vertex_colors[0]),
-                unpack_color(vertex_colors[1]),
-                unpack_color(vertex_colors[2]),
-            ],
+            colors: vertex_colors,
+            dither,
         }
     }
 
@@ -170,33 +424,43 @@ impl<'a> TextureModulateShader<'a> {
     /// Interpolate lighting color using barycentric weights (per-channel, like Gouraud)
     #[inline]
     fn interpolate_lighting(&self, lambda: [f32; 3]) -> (f32, f32, f32) {
-        let r = lambda[0] * self.colors[0].0
-            + lambda[1] * self.colors[1].0
-            + lambda[2] * self.colors[2].0;
-        let g = lambda[0] * self.colors[0].1
-            + lambda[1] * self.colors[1].1
-            + lambda[2] * self.colors[2].1;
-        let b = lambda[0] * self.colors[0].2
-            + lambda[1] * self.colors[1].2
-            + lambda[2] * self.colors[2].2;
+        let r = lambda[0] * self.colors[0][0]
+            + lambda[1] * self.colors[1][0]
+            + lambda[2] * self.colors[2][0];
+        let g = lambda[0] * self.colors[0][1]
+            + lambda[1] * self.colors[1][1]
+            + lambda[2] * self.colors[2][1];
+        let b = lambda[0] * self.colors[0][2]
+            + lambda[1] * self.colors[1][2]
+            + lambda[2] * self.colors[2][2];
         (r, g, b)
     }
 }
 
 impl PixelShader for TextureModulateShader<'_> {
     #[inline]
-    fn shade(&self, lambda: [f32; 3]) -> u32 {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let Fragment { lambda, x, y, .. } = *fragment;
         let (u, v) = self.interpolate_uv(lambda);
-        let tex_color = self.texture.sample(u, v);
+        let tex_color = sample(self.texture, self.region.as_ref(), u, v);
         let (light_r, light_g, light_b) = self.interpolate_lighting(lambda);
         let (tex_r, tex_g, tex_b) = unpack_color(tex_color);
-        pack_color(tex_r * light_r, tex_g * light_g, tex_b * light_b, 1.0)
+        let color = pack_color_dithered(
+            tex_r * light_r,
+            tex_g * light_g,
+            tex_b * light_b,
+            quantize_threshold(self.dither, x, y),
+        );
+        // Texel alpha passes through for blending
+        with_alpha_of(color, tex_color)
     }
 }
 
 /// Texture shader with perspective-correct UV interpolation
 pub struct PerspectiveCorrectTextureShader<'a> {
     texture: &'a Texture,
+    /// Part of `texture` the UVs map onto; all of it when `None`
+    region: Option<TextureRegion>,
     /// Pre-divided: [u₀/w₀, u₁/w₁, u₂/w₂]
     u_over_w: [f32; 3],
     /// Pre-divided: [v₀/w₀, v₁/w₁, v₂/w₂]
@@ -206,6 +470,12 @@ pub struct PerspectiveCorrectTextureShader<'a> {
 }
 
 impl<'a> PerspectiveCorrectTextureShader<'a> {
+    /// Map the UVs onto `region` of the texture rather than all of it.
+    pub fn with_region(mut self, region: Option<TextureRegion>) -> Self {
+        self.region = region;
+        self
+    }
+
     /// Create a perspective-correct texture shader.
     ///
     /// # Arguments
@@ -217,6 +487,7 @@ impl<'a> PerspectiveCorrectTextureShader<'a> {
 
         Self {
             texture,
+            region: None,
             u_over_w: [uvs[0].x / w[0], uvs[1].x / w[1], uvs[2].x / w[2]],
             v_over_w: [uvs[0].y / w[0], uvs[1].y / w[1], uvs[2].y / w[2]],
             inv_w: [1.0 / w[0], 1.0 / w[1], 1.0 / w[2]],
@@ -225,7 +496,8 @@ impl<'a> PerspectiveCorrectTextureShader<'a> {
 }
 
 impl PixelShader for PerspectiveCorrectTextureShader<'_> {
-    fn shade(&self, lambda: [f32; 3]) -> u32 {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let lambda = fragment.lambda;
         // Interpolate u/w, v/w and 1/w linearly
         let u_over_w = lambda[0] * self.u_over_w[0]
             + lambda[1] * self.u_over_w[1]
@@ -240,45 +512,63 @@ impl PixelShader for PerspectiveCorrectTextureShader<'_> {
         let u = u_over_w / inv_w;
         let v = v_over_w / inv_w;
 
-        self.texture.sample(u, v)
+        sample(self.texture, self.region.as_ref(), u, v)
+    }
+
+    /// Replaced textures ignore lighting, so the texel stays unlit.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        GBufferSample {
+            albedo: self.shade(fragment),
+            normal: Vec3::ZERO,
+        }
     }
 }
 
 /// Perspective-correct texture + lighting modulation
 pub struct PerspectiveCorrectTextureModulateShader<'a> {
     texture: &'a Texture,
+    /// Part of `texture` the UVs map onto; all of it when `None`
+    region: Option<TextureRegion>,
     u_over_w: [f32; 3],
     v_over_w: [f32; 3],
     inv_w: [f32; 3],
-    colors: [(f32, f32, f32); 3],
+    colors: [[f32; 3]; 3],
+    dither: bool,
 }
 
 impl<'a> PerspectiveCorrectTextureModulateShader<'a> {
+    /// Map the UVs onto `region` of the texture rather than all of it.
+    pub fn with_region(mut self, region: Option<TextureRegion>) -> Self {
+        self.region = region;
+        self
+    }
+
     pub fn new(
         texture: &'a Texture,
         uvs: [Vec2; 3],
         points: [ScreenVertex; 3],
-        vertex_colors: [u32; 3],
+        vertex_colors: [[f32; 3]; 3],
+        dither: bool,
     ) -> Self {
         let w = [points[0].w, points[1].w, points[2].w];
 
         Self {
             texture,
+            region: None,
             u_over_w: [uvs[0].x / w[0], uvs[1].x / w[1], uvs[2].x / w[2]],
             v_over_w: [uvs[0].y / w[0], uvs[1].y / w[1], uvs[2].y / w[2]],
             inv_w: [1.0 / w[0], 1.0 / w[1], 1.0 / w[2]],
-            colors: [
-                unpack_color(vertex_colors[0]),
-                unpack_color(vertex_colors[1]),
-                unpack_color(vertex_colors[2]),
-            ],
+            colors: vertex_colors,
+            dither,
         }
     }
 }
 
 impl PixelShader for PerspectiveCorrectTextureModulateShader<'_> {
     #[inline]
-    fn shade(&self, lambda: [f32; 3]) -> u32 {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let Fragment { lambda, x, y, .. } = *fragment;
         // Perspective-correct UV interpolation
         let u_over_w = lambda[0] * self.u_over_w[0]
             + lambda[1] * self.u_over_w[1]
@@ -293,23 +583,518 @@ impl PixelShader for PerspectiveCorrectTextureModulateShader<'_> {
         let v = v_over_w / one_over_w;
 
         // Sample texture
-        let tex_color = self.texture.sample(u, v);
+        let tex_color = sample(self.texture, self.region.as_ref(), u, v);
 
         // Lighting interpolation (can be affine - less noticeable artifacts)
         let (light_r, light_g, light_b) = (
-            lambda[0] * self.colors[0].0
-                + lambda[1] * self.colors[1].0
-                + lambda[2] * self.colors[2].0,
-            lambda[0] * self.colors[0].1
-                + lambda[1] * self.colors[1].1
-                + lambda[2] * self.colors[2].1,
-            lambda[0] * self.colors[0].2
-                + lambda[1] * self.colors[1].2
-                + lambda[2] * self.colors[2].2,
+            lambda[0] * self.colors[0][0]
+                + lambda[1] * self.colors[1][0]
+                + lambda[2] * self.colors[2][0],
+            lambda[0] * self.colors[0][1]
+                + lambda[1] * self.colors[1][1]
+                + lambda[2] * self.colors[2][1],
+            lambda[0] * self.colors[0][2]
+                + lambda[1] * self.colors[1][2]
+                + lambda[2] * self.colors[2][2],
         );
 
-        // Modulate
+        // Modulate, keeping the texel alpha for blending
         let (tex_r, tex_g, tex_b) = unpack_color(tex_color);
-        pack_color(tex_r * light_r, tex_g * light_g, tex_b * light_b, 1.0)
+        let color = pack_color_dithered(
+            tex_r * light_r,
+            tex_g * light_g,
+            tex_b * light_b,
+            quantize_threshold(self.dither, x, y),
+        );
+        with_alpha_of(color, tex_color)
+    }
+}
+
+/// Procedural 3D checkerboard, lit by the interpolated vertex colors.
+///
+/// The object-space position is interpolated perspective-correctly and the
+/// cell parity is taken from all three axes, so the pattern is a solid
+/// checker that any surface cuts through. Dark cells are the lit color at
+/// half intensity.
+pub struct CheckerShader {
+    /// Pre-divided: [p₀/w₀, p₁/w₁, p₂/w₂]
+    surface_over_w: [Vec3; 3],
+    inv_w: [f32; 3],
+    colors: [[f32; 3]; 3],
+    scale: f32,
+    dither: bool,
+}
+
+impl CheckerShader {
+    pub fn new(
+        surface_positions: [Vec3; 3],
+        points: [ScreenVertex; 3],
+        vertex_colors: [[f32; 3]; 3],
+        scale: f32,
+        dither: bool,
+    ) -> Self {
+        let inv_w = [1.0 / points[0].w, 1.0 / points[1].w, 1.0 / points[2].w];
+
+        Self {
+            surface_over_w: [
+                surface_positions[0] * inv_w[0],
+                surface_positions[1] * inv_w[1],
+                surface_positions[2] * inv_w[2],
+            ],
+            inv_w,
+            colors: vertex_colors,
+            scale,
+            dither,
+        }
+    }
+}
+
+impl PixelShader for CheckerShader {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let Fragment { lambda, x, y, .. } = *fragment;
+        let one_over_w =
+            lambda[0] * self.inv_w[0] + lambda[1] * self.inv_w[1] + lambda[2] * self.inv_w[2];
+        let p = (self.surface_over_w[0] * lambda[0]
+            + self.surface_over_w[1] * lambda[1]
+            + self.surface_over_w[2] * lambda[2])
+            * (self.scale / one_over_w);
+
+        let cell = p.x.floor() as i64 + p.y.floor() as i64 + p.z.floor() as i64;
+        let shade = if cell & 1 == 0 { 1.0 } else { 0.5 };
+
+        let r = lambda[0] * self.colors[0][0]
+            + lambda[1] * self.colors[1][0]
+            + lambda[2] * self.colors[2][0];
+        let g = lambda[0] * self.colors[0][1]
+            + lambda[1] * self.colors[1][1]
+            + lambda[2] * self.colors[2][1];
+        let b = lambda[0] * self.colors[0][2]
+            + lambda[1] * self.colors[1][2]
+            + lambda[2] * self.colors[2][2];
+
+        pack_color_dithered(
+            r * shade,
+            g * shade,
+            b * shade,
+            quantize_threshold(self.dither, x, y),
+        )
+    }
+}
+
+/// Tangent-space normal mapping, lit per pixel.
+///
+/// The normal map's RGB is a unit vector remapped from [-1, 1] to [0, 1]:
+/// red along the tangent (increasing `u`), green along the bitangent
+/// (increasing `v`) and blue along the surface normal, so the flat
+/// "no relief" color is `(0.5, 0.5, 1.0)`. Each pixel rebuilds that basis
+/// from the perspective-correct interpolated world-space normal, tangent
+/// and bitangent, turns the sampled normal into world space and evaluates
+/// the light there. The unlit base color is interpolated like Gouraud.
+pub struct NormalMapShader<'a> {
+    normal_map: &'a Texture,
+    u_over_w: [f32; 3],
+    v_over_w: [f32; 3],
+    inv_w: [f32; 3],
+    /// Pre-divided world-space normal, tangent and bitangent per vertex
+    normals_over_w: [Vec3; 3],
+    tangents_over_w: [Vec3; 3],
+    bitangents_over_w: [Vec3; 3],
+    /// Unlit base colors
+    colors: [[f32; 3]; 3],
+    light: DirectionalLight,
+    dither: bool,
+}
+
+impl<'a> NormalMapShader<'a> {
+    /// Shade `triangle` with `normal_map` under `light`; the triangle's
+    /// `vertex_colors` are taken as the unlit base color.
+    pub fn new(normal_map: &'a Texture, triangle: &Triangle, light: DirectionalLight) -> Self {
+        let inv_w = triangle.points.map(|p| 1.0 / p.w);
+        let uvs = triangle.texture_coords;
+        let over_w = |v: [Vec3; 3]| std::array::from_fn(|i| v[i] * inv_w[i]);
+
+        Self {
+            normal_map,
+            u_over_w: std::array::from_fn(|i| uvs[i].x * inv_w[i]),
+            v_over_w: std::array::from_fn(|i| uvs[i].y * inv_w[i]),
+            inv_w,
+            normals_over_w: over_w(triangle.normals),
+            tangents_over_w: over_w(triangle.tangents),
+            bitangents_over_w: over_w(triangle.bitangents),
+            colors: triangle.vertex_colors,
+            light,
+            dither: triangle.dither,
+        }
+    }
+}
+
+impl NormalMapShader<'_> {
+    /// The unlit base color at `lambda` and the mapped world-space normal.
+    #[inline]
+    fn surface(&self, lambda: [f32; 3]) -> ([f32; 3], Vec3) {
+        let one_over_w =
+            lambda[0] * self.inv_w[0] + lambda[1] * self.inv_w[1] + lambda[2] * self.inv_w[2];
+        let w = 1.0 / one_over_w;
+        let interpolate =
+            |v: &[f32; 3]| (lambda[0] * v[0] + lambda[1] * v[1] + lambda[2] * v[2]) * w;
+        let interpolate_vec =
+            |v: &[Vec3; 3]| (v[0] * lambda[0] + v[1] * lambda[1] + v[2] * lambda[2]) * w;
+
+        let u = interpolate(&self.u_over_w);
+        let v = interpolate(&self.v_over_w);
+        let (r, g, b) = unpack_color(self.normal_map.sample(u, v));
+        let mapped = Vec3::new(r * 2.0 - 1.0, g * 2.0 - 1.0, b * 2.0 - 1.0);
+
+        let normal = interpolate_vec(&self.normals_over_w).normalize();
+        // Interpolation skews the basis; square the tangent up again
+        let tangent = interpolate_vec(&self.tangents_over_w);
+        let tangent = tangent - normal * normal.dot(tangent);
+        let bitangent = interpolate_vec(&self.bitangents_over_w);
+        let normal = if tangent.magnitude() > 1e-6 && bitangent.magnitude() > 1e-6 {
+            tangent.normalize() * mapped.x + bitangent.normalize() * mapped.y + normal * mapped.z
+        } else {
+            // No UV directions to orient the map by
+            normal
+        };
+
+        let base = std::array::from_fn(|c| {
+            lambda[0] * self.colors[0][c]
+                + lambda[1] * self.colors[1][c]
+                + lambda[2] * self.colors[2][c]
+        });
+        (base, normal)
+    }
+}
+
+impl PixelShader for NormalMapShader<'_> {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let Fragment { lambda, x, y, .. } = *fragment;
+        let (base, normal) = self.surface(lambda);
+        let level = self.light.level(normal);
+        let channel = |c: usize| (base[c] * level).min(1.0);
+        pack_color_dithered(
+            channel(0),
+            channel(1),
+            channel(2),
+            quantize_threshold(self.dither, x, y),
+        )
+    }
+
+    /// The base color, lit afterwards by the mapped normal.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        let (base, normal) = self.surface(fragment.lambda);
+        GBufferSample {
+            albedo: from_rgb_f32(base),
+            normal: normal.normalize(),
+        }
+    }
+}
+
+/// Adapter that lights the output of another shader in hard-edged bands,
+/// for `ShadingMode::Toon`.
+///
+/// The inner shader colors the pixel from the unlit base color. This
+/// interpolates the normal perspective-correctly, snaps the light's diffuse
+/// term to one of `bands` evenly spaced levels from unlit to fully lit, and
+/// scales the color by ambient plus that level. Alpha is preserved.
+pub struct ToonShader<S> {
+    inner: S,
+    light: DirectionalLight,
+    bands: u8,
+    /// Pre-divided world-space normals: [n₀/w₀, n₁/w₁, n₂/w₂]
+    normals_over_w: [Vec3; 3],
+}
+
+impl<S: PixelShader> ToonShader<S> {
+    /// Wrap `inner` to light `triangle` with `light` in `bands` bands;
+    /// fewer than two count as two.
+    pub fn new(inner: S, light: DirectionalLight, bands: u8, triangle: &Triangle) -> Self {
+        Self {
+            inner,
+            light,
+            bands: bands.max(2),
+            normals_over_w: std::array::from_fn(|i| triangle.normals[i] / triangle.points[i].w),
+        }
+    }
+
+    /// `intensity` in [0, 1] snapped to its band's level: the range is cut
+    /// into `bands` equal parts, the lowest unlit and the highest fully lit.
+    #[inline]
+    fn band_level(&self, intensity: f32) -> f32 {
+        let steps = (self.bands - 1) as f32;
+        let band = (intensity * self.bands as f32).floor().min(steps);
+        band / steps
+    }
+}
+
+impl<S: PixelShader> PixelShader for ToonShader<S> {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let lambda = fragment.lambda;
+        let color = self.inner.shade(fragment);
+        // Only the direction matters, so the 1/w sum needn't be divided out
+        let normal = self.normals_over_w[0] * lambda[0]
+            + self.normals_over_w[1] * lambda[1]
+            + self.normals_over_w[2] * lambda[2];
+        let diffuse = self.band_level(self.light.intensity(normal));
+        let level =
+            self.light.ambient_intensity.max(0.0) + diffuse * self.light.diffuse_strength.max(0.0);
+        scale_rgb(color, level)
+    }
+
+    /// The inner shader's surface: bands are a forward-only look, and the
+    /// lighting applied afterwards is smooth.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        self.inner.shade_gbuffer(fragment)
+    }
+}
+
+/// Adapter that fogs the output of another shader by view depth.
+///
+/// Clip `w` is view-space depth, and `1/w` interpolates linearly in screen
+/// space, so depth at a pixel is the reciprocal of the interpolated `1/w`.
+pub struct FogShader<S> {
+    inner: S,
+    fog: Fog,
+    /// Reciprocal depths: [1/w₀, 1/w₁, 1/w₂]
+    inv_w: [f32; 3],
+}
+
+impl<S: PixelShader> FogShader<S> {
+    /// Wrap `inner`; `points` supplies the per-vertex `.w`.
+    pub fn new(inner: S, fog: Fog, points: [ScreenVertex; 3]) -> Self {
+        Self {
+            inner,
+            fog,
+            inv_w: [1.0 / points[0].w, 1.0 / points[1].w, 1.0 / points[2].w],
+        }
+    }
+}
+
+impl<S: PixelShader> PixelShader for FogShader<S> {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let lambda = fragment.lambda;
+        let color = self.inner.shade(fragment);
+        let inv_w =
+            lambda[0] * self.inv_w[0] + lambda[1] * self.inv_w[1] + lambda[2] * self.inv_w[2];
+        with_alpha_of(self.fog.apply(color, 1.0 / inv_w), color)
+    }
+
+    /// The inner shader's surface; fog is applied after lighting.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        self.inner.shade_gbuffer(fragment)
+    }
+}
+
+/// False-color shader for `Engine::set_debug_view`: paints one input of
+/// the shading rather than the shaded color. See [`DebugView`].
+pub struct DebugShader {
+    view: DebugView,
+}
+
+impl DebugShader {
+    /// The shader for `view`, or `None` for `DebugView::None`.
+    pub fn new(view: DebugView) -> Option<Self> {
+        (view != DebugView::None).then_some(Self { view })
+    }
+}
+
+impl PixelShader for DebugShader {
+    #[inline]
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        match self.view {
+            DebugView::None => 0,
+            DebugView::Barycentric => {
+                let [l0, l1, l2] = fragment.lambda;
+                pack_color(l0, l1, l2, 1.0)
+            }
+            DebugView::Uv => {
+                let uv = fragment.uv();
+                pack_color(uv.x.rem_euclid(1.0), uv.y.rem_euclid(1.0), 0.0, 1.0)
+            }
+            DebugView::Overdraw => OVERDRAW_STEP,
+            DebugView::Normals => {
+                let n = fragment.normal();
+                pack_color(n.x * 0.5 + 0.5, n.y * 0.5 + 0.5, n.z * 0.5 + 0.5, 1.0)
+            }
+        }
+    }
+
+    /// False colors are shown as they are, unlit.
+    #[inline]
+    fn shade_gbuffer(&self, fragment: &Fragment) -> GBufferSample {
+        GBufferSample {
+            albedo: self.shade(fragment),
+            normal: Vec3::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::colors::to_rgb_f32;
+    use crate::engine::TextureMode;
+    use crate::ShadingMode;
+
+    /// A small screen-space triangle with its corners at clip depths `w`.
+    fn triangle_with_w(w: [f32; 3]) -> Triangle {
+        let corners = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+        let points =
+            std::array::from_fn(|i| ScreenVertex::new(Vec2::new(corners[i].0, corners[i].1), w[i]));
+        Triangle::new(
+            points,
+            0,
+            [[0.5; 3]; 3],
+            [Vec2::ZERO; 3],
+            ShadingMode::Flat,
+            TextureMode::None,
+        )
+    }
+
+    /// Shade pixel (x, y) at barycentrics `lambda`; the built-in shaders
+    /// carry their own inputs, so the fragment's triangle is a stand-in.
+    fn shade_at(shader: &impl PixelShader, lambda: [f32; 3], x: i32, y: i32) -> u32 {
+        let triangle = triangle_with_w([1.0; 3]);
+        shader.shade(&Fragment::new(lambda, x, y, &triangle, None))
+    }
+
+    #[test]
+    fn undithered_gouraud_matches_rounding() {
+        let shader = GouraudShader::new([to_rgb_f32(0xFF404040); 3], false);
+        for (x, y) in [(0, 0), (1, 2), (3, 3)] {
+            assert_eq!(shade_at(&shader, [1.0, 0.0, 0.0], x, y), 0xFF404040);
+        }
+    }
+
+    #[test]
+    fn fragment_interpolates_perspective_correctly() {
+        let mut triangle = triangle_with_w([1.0, 3.0, 3.0]);
+        triangle.texture_coords = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::ZERO];
+        triangle.normals = [
+            Vec3::new(0.0, 0.0, -2.0),
+            Vec3::new(0.0, 0.0, -4.0),
+            Vec3::ZERO,
+        ];
+        triangle.vertex_colors = [[1.0; 3], [0.0; 3], [0.0; 3]];
+        let fragment = Fragment::new([0.5, 0.5, 0.0], 0, 0, &triangle, None);
+
+        // Halfway across the screen is only a quarter of the way in 3D,
+        // since the far corner is foreshortened
+        assert!((fragment.uv().x - 0.25).abs() < 1e-6);
+        assert!((fragment.depth() - 1.5).abs() < 1e-6);
+        assert_eq!(fragment.normal(), Vec3::new(0.0, 0.0, -1.0));
+        // Colors interpolate in screen space, like the Gouraud shader
+        assert_eq!(fragment.color(), [0.5; 3]);
+        assert_eq!(fragment.sample_texture(), None);
+    }
+
+    #[test]
+    fn dither_averages_to_true_value_over_tile() {
+        // 0x40 + 0.25 sits a quarter of the way between two 8-bit steps.
+        // Plain rounding would produce 0x40 everywhere; the dithered 4x4 tile
+        // should mix 0x40 and 0x41 so that its mean lands on the true value.
+        let level = (0x40 as f32 + 0.25) / 255.0;
+        let mut sum = 0u32;
+        for y in 0..4 {
+            for x in 0..4 {
+                let c = pack_color_dithered(level, level, level, quantize_threshold(true, x, y));
+                let r = (c >> 16) & 0xFF;
+                assert!(r == 0x40 || r == 0x41, "unexpected channel {r:#x}");
+                sum += r;
+            }
+        }
+        let mean = sum as f32 / 16.0;
+        assert!((mean - (0x40 as f32 + 0.25)).abs() < 1.0 / 16.0 + 1e-6);
+    }
+
+    #[test]
+    fn gouraud_blends_float_colors_before_packing() {
+        let a = (0x40 as f32 + 0.6) / 255.0;
+        let b = (0x3F as f32 + 0.6) / 255.0;
+        let shader = GouraudShader::new([[a; 3], [b; 3], [b; 3]], false);
+        // The true midpoint is 0x40.1; rounding each vertex first (to 0x41
+        // and 0x40) would land halfway and round up to 0x41
+        assert_eq!(shade_at(&shader, [0.5, 0.5, 0.0], 0, 0), 0xFF404040);
+    }
+
+    /// A screen-facing triangle with the surface normal toward the viewer
+    /// (-z), `u` along +x and `v` along +y, lit head-on.
+    fn normal_mapped(normal_map: &Texture) -> NormalMapShader<'_> {
+        let mut triangle = triangle_with_w([1.0; 3]);
+        triangle.texture_mode = TextureMode::NormalMapped;
+        triangle.normals = [Vec3::new(0.0, 0.0, -1.0); 3];
+        triangle.tangents = [Vec3::RIGHT; 3];
+        triangle.bitangents = [Vec3::UP; 3];
+        let light = DirectionalLight::new(Vec3::new(0.0, 0.0, 1.0));
+        NormalMapShader::new(normal_map, &triangle, light)
+    }
+
+    fn red(color: u32) -> i32 {
+        ((color >> 16) & 0xFF) as i32
+    }
+
+    #[test]
+    fn flat_normal_map_lights_like_the_surface() {
+        let flat = Texture::from_fn(1, 1, |_, _| 0xFF8080FF).unwrap();
+        // Full diffuse plus ambient on a half-gray base: 0.5 * 1.1
+        let expected = (0.55f32 * 255.0).round() as i32;
+        let shaded = shade_at(&normal_mapped(&flat), [1.0 / 3.0; 3], 0, 0);
+        assert!((red(shaded) - expected).abs() <= 1, "{shaded:#x}");
+    }
+
+    #[test]
+    fn tilted_normal_map_turns_the_surface_from_the_light() {
+        // (0.6, 0, 0.8) in tangent space: leaning toward +u
+        let tilted = Texture::from_fn(1, 1, |_, _| 0xFFCC80E6).unwrap();
+        // 0.5 * (0.1 + 0.8)
+        let expected = (0.45f32 * 255.0).round() as i32;
+        let shaded = shade_at(&normal_mapped(&tilted), [1.0 / 3.0; 3], 0, 0);
+        assert!((red(shaded) - expected).abs() <= 1, "{shaded:#x}");
+    }
+
+    #[test]
+    fn toon_snaps_diffuse_to_bands() {
+        let mut triangle = triangle_with_w([1.0; 3]);
+        // Lit head-on from the viewer's side, no ambient
+        let mut light = DirectionalLight::new(Vec3::new(0.0, 0.0, 1.0));
+        light.ambient_intensity = 0.0;
+        let toon_at = |triangle: &Triangle, normal: Vec3| {
+            let mut triangle = *triangle;
+            triangle.normals = [normal; 3];
+            let shader = ToonShader::new(FlatShader::new(0xFFFFFFFF), light, 3, &triangle);
+            shader.shade(&Fragment::new([1.0 / 3.0; 3], 0, 0, &triangle, None))
+        };
+        let tilted = |degrees: f32| {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            Vec3::new(sin, 0.0, -cos)
+        };
+        triangle.vertex_colors = [[1.0; 3]; 3];
+
+        // cos 30° = 0.87 and cos 45° = 0.71 share the top band
+        assert_eq!(toon_at(&triangle, tilted(30.0)), 0xFFFFFFFF);
+        assert_eq!(toon_at(&triangle, tilted(45.0)), 0xFFFFFFFF);
+        // cos 60° = 0.5 is the middle band, at half light
+        assert_eq!(toon_at(&triangle, tilted(60.0)), scale_rgb(0xFFFFFFFF, 0.5));
+        // cos 80° = 0.17 is unlit
+        assert_eq!(toon_at(&triangle, tilted(80.0)), 0xFF000000);
+    }
+
+    #[test]
+    fn dither_never_overflows_channels() {
+        for y in 0..4 {
+            for x in 0..4 {
+                let c = pack_color_dithered(1.0, 1.0, 1.0, quantize_threshold(true, x, y));
+                assert_eq!(c, 0xFFFFFFFF);
+                let c = pack_color_dithered(0.0, 0.0, 0.0, quantize_threshold(true, x, y));
+                assert_eq!(c, 0xFF000000);
+            }
+        }
     }
 }
@@ -1,70 +1,284 @@
 //! Low-level rendering primitives.
 //!
-//! Provides the [`Renderer`] struct which owns the color buffer and implements
-//! basic drawing operations like lines, rectangles, and wireframes.
+//! Provides the [`Renderer`] struct which draws into a [`RenderTarget`] and
+//! implements basic drawing operations like lines, rectangles, and wireframes.
 
-use super::framebuffer::FrameBuffer;
-use super::rasterizer::Triangle;
+use super::font::{self, GLYPH_SIZE};
+use super::framebuffer::{DepthEncoding, FrameBuffer, MaskTest, MASK_SET};
+use super::rasterizer::{ScreenVertex, Triangle};
+use super::target::{PixelRays, RenderTarget};
 use crate::colors;
+use crate::error::Error;
+use crate::light::PixelLighting;
 
-pub struct Renderer {
-    color_buffer: Vec<u32>,
-    depth_buffer: Vec<f32>,
+/// Depth bias so lines and vertex markers render slightly in front of
+/// the filled triangles they sit on.
+const WIREFRAME_DEPTH_BIAS: f32 = 0.0001;
+
+/// How much farther (as a ratio of view depths) a neighboring pixel must
+/// be before [`Renderer::draw_silhouette_outline`] treats it as a separate
+/// surface. Loose enough that steeply sloped surfaces stay unbroken.
+const SILHOUETTE_DEPTH_RATIO: f32 = 1.05;
+
+/// Reinterpret ARGB8888 pixels as raw bytes for presentation. Each pixel's
+/// bytes come in the host's order: `B, G, R, A` on little-endian machines,
+/// `A, R, G, B` on big-endian ones, which is what SDL's ARGB8888 textures
+/// expect.
+pub(crate) fn pixels_as_bytes(pixels: &[u32]) -> &[u8] {
+    // SAFETY: u32 has no padding and any byte pattern is a valid u8; the
+    // byte length covers exactly the same allocation.
+    unsafe { std::slice::from_raw_parts(pixels.as_ptr() as *const u8, pixels.len() * 4) }
+}
+
+/// Byte order of each pixel in [`export_pixels`] output, the same on every
+/// host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ByteOrder {
+    Rgba8888,
+    Bgra8888,
+}
+
+impl ByteOrder {
+    fn bytes(self, argb: u32) -> [u8; 4] {
+        let [a, r, g, b] = argb.to_be_bytes();
+        match self {
+            ByteOrder::Rgba8888 => [r, g, b, a],
+            ByteOrder::Bgra8888 => [b, g, r, a],
+        }
+    }
+}
+
+/// Replace the contents of `out` with ARGB `pixels`, `width` to a row, as
+/// four bytes each in `order`. Rows start `pitch` bytes apart with the
+/// padding zeroed, or follow each other directly with `None`.
+pub(crate) fn export_pixels(
+    pixels: impl IntoIterator<Item = u32>,
     width: u32,
-    height: u32,
+    order: ByteOrder,
+    pitch: Option<usize>,
+    out: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let row_bytes = width as usize * 4;
+    let padding = match pitch {
+        Some(pitch) if pitch < row_bytes => {
+            return Err(Error::InvalidArgument(
+                "row pitch is shorter than a row of pixels",
+            ))
+        }
+        Some(pitch) => pitch - row_bytes,
+        None => 0,
+    };
+    out.clear();
+    let mut column = 0;
+    for argb in pixels {
+        out.extend_from_slice(&order.bytes(argb));
+        column += 1;
+        if column == width {
+            out.resize(out.len() + padding, 0);
+            column = 0;
+        }
+    }
+    Ok(())
+}
+
+/// Stretch `src` (`src_width` pixels wide) over `dst` (`dst_width` wide)
+/// by nearest-neighbor, each destination pixel taking the source pixel
+/// under its center.
+pub(crate) fn upscale_nearest(src: &[u32], src_width: u32, dst: &mut [u32], dst_width: u32) {
+    let src_height = src.len() as u32 / src_width;
+    let dst_height = dst.len() as u32 / dst_width;
+    // Source column per destination column, shared by every row
+    let columns: Vec<usize> = (0..dst_width)
+        .map(|x| ((2 * x + 1) * src_width / (2 * dst_width)) as usize)
+        .collect();
+    for (y, row) in dst.chunks_exact_mut(dst_width as usize).enumerate() {
+        let sy = (2 * y as u32 + 1) * src_height / (2 * dst_height);
+        let src_row = &src[(sy * src_width) as usize..][..src_width as usize];
+        for (pixel, &sx) in row.iter_mut().zip(&columns) {
+            *pixel = src_row[sx];
+        }
+    }
+}
+
+/// Endpoints of the edges of `triangle` its [`Triangle::edge_flags`] keep.
+fn visible_edges(triangle: &Triangle) -> impl Iterator<Item = (ScreenVertex, ScreenVertex)> {
+    let points = triangle.points;
+    let flags = triangle.edge_flags;
+    (0..3)
+        .filter(move |i| flags & (1 << i) != 0)
+        .map(move |i| (points[i], points[(i + 1) % 3]))
+}
+
+pub struct Renderer {
+    // Color and depth planes
+    target: RenderTarget,
+    // Per-pixel picking identifiers, 0 = nothing drawn
+    id_buffer: Vec<u32>,
+    // Stencil-like marks, MASK_SET where a mask write landed
+    mask_buffer: Vec<u8>,
+    mask_test: MaskTest,
+    mask_write: bool,
+    color_write: bool,
 }
 
 impl Renderer {
     pub fn new(width: u32, height: u32) -> Self {
-        let size = (width * height) as usize;
+        Self::with_target(RenderTarget::new(width, height))
+    }
+
+    /// A renderer drawing into `target`, with ID and mask buffers to match
+    /// its size.
+    pub fn with_target(target: RenderTarget) -> Self {
+        let size = target.color.len();
         Self {
-            color_buffer: vec![colors::BACKGROUND; size],
-            depth_buffer: vec![0.0; size], // 0.0 = infinitely far (1/w where w -> infinity)
-            width,
-            height,
+            target,
+            id_buffer: vec![0; size],
+            mask_buffer: vec![0; size],
+            mask_test: MaskTest::Always,
+            mask_write: false,
+            color_write: true,
         }
     }
 
+    /// Give up the color and depth planes drawn so far.
+    pub fn into_target(self) -> RenderTarget {
+        self.target
+    }
+
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn target(&self) -> &RenderTarget {
+        &self.target
+    }
+
     pub fn resize(&mut self, width: u32, height: u32) {
-        let size = (width * height) as usize;
-        self.color_buffer = vec![colors::BACKGROUND; size];
-        self.depth_buffer = vec![0.0; size];
-        self.width = width;
-        self.height = height;
+        self.target.resize(width, height);
+        let size = self.target.color.len();
+        self.id_buffer = vec![0; size];
+        self.mask_buffer = vec![0; size];
     }
 
     pub fn width(&self) -> u32 {
-        self.width
+        self.target.width
     }
 
     pub fn height(&self) -> u32 {
-        self.height
+        self.target.height
     }
 
     pub fn clear(&mut self, color: u32) {
-        self.color_buffer.fill(color);
+        self.target.clear(color);
+    }
+
+    /// Change what the depth buffer stores. Takes effect from the next
+    /// [`Renderer::clear_depth`].
+    pub fn set_depth_encoding(&mut self, encoding: DepthEncoding) {
+        self.target.set_depth_encoding(encoding);
+    }
+
+    pub fn depth_encoding(&self) -> DepthEncoding {
+        self.target.depth_encoding
+    }
+
+    /// Allocate or free the depth buffer. Without one, depth-tested
+    /// drawing lands in draw order. See [`RenderTarget::set_depth_plane`].
+    pub fn set_depth_buffer(&mut self, enabled: bool) {
+        self.target.set_depth_plane(enabled);
+    }
+
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn has_depth_buffer(&self) -> bool {
+        self.target.has_depth()
     }
 
     #[inline]
     /// Clear the depth buffer to prepare for a new frame.
-    /// Sets all depths to 0.0 (infinitely far, since we store 1/w).
+    /// Sets all depths to the encoding's "infinitely far" value.
     pub fn clear_depth(&mut self) {
-        self.depth_buffer.fill(0.0);
+        self.target.clear_depth();
+    }
+
+    /// Allocate or free the G-buffer planes the deferred pipeline draws
+    /// into. See [`RenderTarget::set_gbuffer_planes`].
+    pub fn set_gbuffer(&mut self, enabled: bool) {
+        self.target.set_gbuffer_planes(enabled);
+    }
+
+    /// Mark every G-buffer pixel empty for a new frame.
+    pub fn clear_gbuffer(&mut self) {
+        self.target.clear_gbuffer();
+    }
+
+    /// Light the G-buffer into the color plane; see
+    /// [`RenderTarget::light_gbuffer`]. Returns how many pixels were lit.
+    pub(crate) fn light_gbuffer(&mut self, lighting: &PixelLighting, rays: &PixelRays) -> usize {
+        self.target.light_gbuffer(lighting, rays)
+    }
+
+    /// Clear the ID buffer so every pixel reads as "nothing drawn".
+    pub fn clear_ids(&mut self) {
+        self.id_buffer.fill(0);
+    }
+
+    /// Clear the mask buffer to 0.
+    pub fn clear_mask(&mut self) {
+        self.mask_buffer.fill(0);
+    }
+
+    /// Store [`MASK_SET`] in the mask wherever a depth-tested write lands.
+    pub fn set_mask_write(&mut self, enabled: bool) {
+        self.mask_write = enabled;
+    }
+
+    /// Only let depth-tested writes through where the mask passes `test`.
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn set_mask_test(&mut self, test: MaskTest) {
+        self.mask_test = test;
+    }
+
+    /// With `false`, depth-tested writes leave color and IDs alone, so
+    /// geometry can be drawn into just the depth and mask buffers.
+    pub fn set_color_write(&mut self, enabled: bool) {
+        self.color_write = enabled;
+    }
+
+    /// Returns the mask value at (x, y), or None if out of bounds.
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn mask_at(&self, x: i32, y: i32) -> Option<u8> {
+        if x >= 0 && x < self.target.width as i32 && y >= 0 && y < self.target.height as i32 {
+            Some(self.mask_buffer[(y as u32 * self.target.width + x as u32) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the picking ID at (x, y), or None if out of bounds.
+    pub fn id_at(&self, x: i32, y: i32) -> Option<u32> {
+        if x >= 0 && x < self.target.width as i32 && y >= 0 && y < self.target.height as i32 {
+            Some(self.id_buffer[(y as u32 * self.target.width + x as u32) as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the stored depth at (x, y) as 1/w, or None if out of bounds.
+    pub fn depth_at(&self, x: i32, y: i32) -> Option<f32> {
+        self.target.depth_at(x, y)
     }
 
     #[inline]
     pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
-        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
-            let index = (y as u32 * self.width + x as u32) as usize;
-            self.color_buffer[index] = color;
+        if x >= 0 && x < self.target.width as i32 && y >= 0 && y < self.target.height as i32 {
+            let index = (y as u32 * self.target.width + x as u32) as usize;
+            self.target.color[index] = color;
         }
     }
 
     /// Set a pixel at (x, y) with depth testing.
     ///
-    /// The pixel is only written if the depth value is greater than the existing
-    /// depth at that location (closer to camera, since we store 1/w).
-    /// Silently ignores out-of-bounds coordinates.
+    /// The pixel is only written if it is closer to the camera than the
+    /// existing depth at that location, per the depth encoding, and its
+    /// mask passes the mask test. Silently ignores out-of-bounds
+    /// coordinates.
     ///
     /// # Arguments
     /// * `x`, `y` - Pixel coordinates
@@ -72,22 +286,77 @@ impl Renderer {
     /// * `color` - The color to write if depth test passes
     #[inline]
     pub fn set_pixel_with_depth(&mut self, x: i32, y: i32, inv_depth: f32, color: u32) {
-        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
-            let idx = (y as u32 * self.width + x as u32) as usize;
-            // Depth test: larger 1/w means closer to camera
-            if inv_depth > self.depth_buffer[idx] {
-                self.depth_buffer[idx] = inv_depth;
-                self.color_buffer[idx] = color;
+        if x >= 0 && x < self.target.width as i32 && y >= 0 && y < self.target.height as i32 {
+            let idx = (y as u32 * self.target.width + x as u32) as usize;
+            let depth = self.target.depth_encoding.encode(inv_depth);
+            if self.mask_test.passes(self.mask_buffer[idx]) && self.depth_passes(idx, depth) {
+                if let Some(stored) = self.target.depth.get_mut(idx) {
+                    *stored = depth;
+                }
+                if self.mask_write {
+                    self.mask_buffer[idx] = MASK_SET;
+                }
+                if self.color_write {
+                    self.target.color[idx] = color;
+                }
+            }
+        }
+    }
+
+    /// Blend a pixel at (x, y) over the existing color, with depth testing.
+    ///
+    /// Uses the same depth and mask tests as
+    /// [`Renderer::set_pixel_with_depth`] but does not write depth or mask:
+    /// a partially covered pixel shouldn't hide what is behind it. `coverage` in [0, 1] is the blend weight of `color`.
+    #[inline]
+    pub fn blend_pixel_with_depth(
+        &mut self,
+        x: i32,
+        y: i32,
+        inv_depth: f32,
+        color: u32,
+        coverage: f32,
+    ) {
+        if coverage <= 0.0 {
+            return;
+        }
+        if x >= 0 && x < self.target.width as i32 && y >= 0 && y < self.target.height as i32 {
+            let idx = (y as u32 * self.target.width + x as u32) as usize;
+            let depth = self.target.depth_encoding.encode(inv_depth);
+            if self.color_write
+                && self.mask_test.passes(self.mask_buffer[idx])
+                && self.depth_passes(idx, depth)
+            {
+                self.target.color[idx] = colors::blend(self.target.color[idx], color, coverage);
             }
         }
     }
 
+    /// Whether `depth` lies in front of what pixel `idx` stores. Always
+    /// true without a depth plane.
+    #[inline]
+    fn depth_passes(&self, idx: usize, depth: f32) -> bool {
+        self.target
+            .depth
+            .get(idx)
+            .is_none_or(|&stored| self.target.depth_encoding.passes(depth, stored))
+    }
+
+    /// Draw a screen-space grid with lines every `spacing` pixels.
+    ///
+    /// Lines run through the middle pixel of each axis, so the margins
+    /// left at the edges match on both sides: exactly for odd sizes, to
+    /// within a pixel for even ones.
     pub fn draw_grid(&mut self, spacing: i32, color: u32) {
-        for y in 0..self.height as i32 {
-            for x in 0..self.width as i32 {
-                if x % spacing == 0 || y % spacing == 0 {
-                    self.set_pixel(x, y, color);
-                }
+        let spacing = spacing.max(1) as usize;
+        let (width, height) = (self.target.width as usize, self.target.height as usize);
+        let first = |size: usize| (size / 2) % spacing;
+        for y in (first(height)..height).step_by(spacing) {
+            self.target.color[y * width..(y + 1) * width].fill(color);
+        }
+        for row in self.target.color.chunks_exact_mut(width.max(1)) {
+            for x in (first(width)..width).step_by(spacing) {
+                row[x] = color;
             }
         }
     }
@@ -101,36 +370,118 @@ impl Renderer {
         }
     }
 
+    /// Draw a square vertex marker centered on `(x, y)` with depth testing.
+    ///
+    /// The whole marker uses the vertex's 1/w plus the wireframe depth bias,
+    /// so markers on the far side of a mesh are hidden by its front faces.
+    pub fn draw_vertex_marker(&mut self, x: f32, y: f32, w: f32, size: i32, color: u32) {
+        let inv_depth = 1.0 / w + WIREFRAME_DEPTH_BIAS;
+        let left = x.floor() as i32 - size / 2;
+        let top = y.floor() as i32 - size / 2;
+        for dy in 0..size {
+            for dx in 0..size {
+                self.set_pixel_with_depth(left + dx, top + dy, inv_depth, color);
+            }
+        }
+    }
+
+    /// Outline `triangle` with depth-tested Bresenham lines, skipping
+    /// edges cleared in [`Triangle::edge_flags`].
     pub fn draw_triangle_wireframe(&mut self, triangle: &Triangle, color: u32) {
-        let [p0, p1, p2] = triangle.points;
-
-        self.draw_line_bresenham(
-            p0.position.x as i32,
-            p0.position.y as i32,
-            p0.w,
-            p1.position.x as i32,
-            p1.position.y as i32,
-            p1.w,
-            color,
-        );
-        self.draw_line_bresenham(
-            p1.position.x as i32,
-            p1.position.y as i32,
-            p1.w,
-            p2.position.x as i32,
-            p2.position.y as i32,
-            p2.w,
-            color,
-        );
-        self.draw_line_bresenham(
-            p2.position.x as i32,
-            p2.position.y as i32,
-            p2.w,
-            p0.position.x as i32,
-            p0.position.y as i32,
-            p0.w,
-            color,
-        );
+        for (a, b) in visible_edges(triangle) {
+            self.draw_line_bresenham(
+                a.position.x as i32,
+                a.position.y as i32,
+                a.w,
+                b.position.x as i32,
+                b.position.y as i32,
+                b.w,
+                color,
+            );
+        }
+    }
+
+    /// Blend a solid rectangle over the color buffer (no depth).
+    ///
+    /// `alpha` in [0, 1] is the opacity of `color`. Used for translucent
+    /// overlay panels behind debug text.
+    pub fn draw_filled_rect_alpha(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        color: u32,
+        alpha: f32,
+    ) {
+        let x_start = x.max(0);
+        let y_start = y.max(0);
+        let x_end = (x + width).min(self.target.width as i32);
+        let y_end = (y + height).min(self.target.height as i32);
+
+        for py in y_start..y_end {
+            let row = (py as u32 * self.target.width) as usize;
+            for px in x_start..x_end {
+                let idx = row + px as usize;
+                self.target.color[idx] = colors::blend(self.target.color[idx], color, alpha);
+            }
+        }
+    }
+
+    /// Draw ASCII text with the embedded 8x8 font (no depth).
+    ///
+    /// `(x, y)` is the top-left of the first glyph. `'\n'` starts a new line.
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: u32) {
+        self.draw_text_scaled(x, y, text, color, 1);
+    }
+
+    /// Like [`Renderer::draw_text`], with each font pixel drawn as a
+    /// `scale`×`scale` block.
+    pub fn draw_text_scaled(&mut self, x: i32, y: i32, text: &str, color: u32, scale: i32) {
+        let advance = GLYPH_SIZE * scale;
+        let mut cursor_x = x;
+        let mut cursor_y = y;
+
+        for c in text.chars() {
+            if c == '\n' {
+                cursor_x = x;
+                cursor_y += advance;
+                continue;
+            }
+
+            for (row, bits) in font::glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_SIZE {
+                    if bits & (0x80 >> col) != 0 {
+                        self.draw_rect(
+                            cursor_x + col * scale,
+                            cursor_y + row as i32 * scale,
+                            scale,
+                            scale,
+                            color,
+                        );
+                    }
+                }
+            }
+            cursor_x += advance;
+        }
+    }
+
+    /// Anti-aliased variant of [`Renderer::draw_triangle_wireframe`].
+    ///
+    /// Uses sub-pixel vertex positions, so edges stay smooth as they move.
+    pub fn draw_triangle_wireframe_aa(&mut self, triangle: &Triangle, color: u32) {
+        for (a, b) in visible_edges(triangle) {
+            self.draw_line_wu(
+                a.position.x,
+                a.position.y,
+                a.w,
+                b.position.x,
+                b.position.y,
+                b.w,
+                color,
+            );
+        }
     }
 
     /// Draws a line between two points using Bresenham's line algorithm with depth testing.
@@ -145,7 +496,13 @@ impl Renderer {
     /// instead of straight.
     ///
     /// Depth (1/w) is linearly interpolated along the line for proper depth testing.
+    ///
+    /// The segment is first clipped to the framebuffer (see
+    /// [`Renderer::visible_step_range`]) so that endpoints projected far off
+    /// screen don't cost a walk over thousands of invisible pixels. Clipping
+    /// only skips steps; the pixels that are drawn match the unclipped walk.
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_line_bresenham(
         &mut self,
         x0: i32,
@@ -156,23 +513,29 @@ impl Renderer {
         w1: f32,
         color: u32,
     ) {
+        // Work in i64: projected endpoints can land far outside i32-safe
+        // range for the doubled error term.
+        let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+
         // Calculate the absolute distances in each axis.
         // These represent how far we need to travel horizontally and vertically.
         let dx = (x1 - x0).abs();
         let dy = (y1 - y0).abs();
 
-        // Depth bias so wireframes render slightly in front of filled triangles
-        const WIREFRAME_DEPTH_BIAS: f32 = 0.0001;
-
         // Total number of steps (max of dx, dy)
         let steps = dx.max(dy);
         if steps == 0 {
             // Single pixel line
             let inv_depth = 1.0 / w0 + WIREFRAME_DEPTH_BIAS;
-            self.set_pixel_with_depth(x0, y0, inv_depth, color);
+            self.set_pixel_with_depth(x0 as i32, y0 as i32, inv_depth, color);
             return;
         }
 
+        // Clip: only the steps in [first_step, last_step] can land on screen
+        let Some((first_step, last_step)) = self.visible_step_range(x0, y0, x1, y1, steps) else {
+            return;
+        };
+
         // Precompute 1/w for depth interpolation (linear in screen space)
         let inv_w0 = 1.0 / w0 + WIREFRAME_DEPTH_BIAS;
         let inv_w1 = 1.0 / w1 + WIREFRAME_DEPTH_BIAS;
@@ -183,28 +546,30 @@ impl Renderer {
         let x_incr_direction = if x0 < x1 { 1 } else { -1 };
         let y_incr_direction = if y0 < y1 { 1 } else { -1 };
 
+        // Fast-forward to the first visible step. The major axis advances
+        // exactly one pixel per step; the minor axis has advanced
+        // (2*k*minor + major - 1) / (2*major) pixels after k steps.
+        let (x_steps, y_steps) = if dx >= dy {
+            (first_step, (2 * first_step * dy + dx - 1) / (2 * dx))
+        } else {
+            ((2 * first_step * dx + dy - 1) / (2 * dy), first_step)
+        };
+
         // The error term tracks how far off we are from the ideal line.
-        // Initialized to dx - dy, which balances the algorithm for lines
-        // of any slope. A positive error favors x movement, negative favors y.
-        let mut err = dx - dy;
+        // It starts at dx - dy, which balances the algorithm for lines of
+        // any slope, and moves by -dy per x step and +dx per y step.
+        // A positive error favors x movement, negative favors y.
+        let mut err = dx - dy - x_steps * dy + y_steps * dx;
 
-        let mut x = x0;
-        let mut y = y0;
-        let mut step = 0;
+        let mut x = x0 + x_steps * x_incr_direction;
+        let mut y = y0 + y_steps * y_incr_direction;
 
-        loop {
+        for step in first_step..=last_step {
             // Interpolate depth along the line
             let t = step as f32 / steps as f32;
             let inv_depth = inv_w0 + t * (inv_w1 - inv_w0);
 
-            self.set_pixel_with_depth(x, y, inv_depth, color);
-
-            // Check if we've reached the destination
-            if x == x1 && y == y1 {
-                break;
-            }
-
-            step += 1;
+            self.set_pixel_with_depth(x as i32, y as i32, inv_depth, color);
 
             // Double the error for comparison (avoids floating point).
             // We compare against -dy and dx to decide movement direction.
@@ -228,6 +593,201 @@ impl Renderer {
         }
     }
 
+    /// Draws an anti-aliased line using Xiaolin Wu's algorithm with depth testing.
+    ///
+    /// Wu's algorithm steps one pixel at a time along the major axis and
+    /// splits each step's coverage between the two pixels straddling the
+    /// ideal line on the minor axis, weighted by distance. Endpoints get an
+    /// extra weight for how much of their pixel the segment actually spans.
+    ///
+    /// Coordinates are pixel-space with pixel centers at `+0.5`, matching
+    /// the rasterizers. Depth (1/w) is interpolated along the major axis and
+    /// tested with the same bias as [`Renderer::draw_line_bresenham`], but
+    /// pixels are blended rather than overwritten.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_line_wu(
+        &mut self,
+        x0: f32,
+        y0: f32,
+        w0: f32,
+        x1: f32,
+        y1: f32,
+        w1: f32,
+        color: u32,
+    ) {
+        // Shift so integer coordinates are pixel centers, as Wu's algorithm expects
+        let (mut x0, mut y0, mut x1, mut y1) = (x0 - 0.5, y0 - 0.5, x1 - 0.5, y1 - 0.5);
+        let (mut inv_w0, mut inv_w1) = (
+            1.0 / w0 + WIREFRAME_DEPTH_BIAS,
+            1.0 / w1 + WIREFRAME_DEPTH_BIAS,
+        );
+
+        // Steep lines walk along y: swap axes so the loop is always along x,
+        // and swap back when plotting.
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            std::mem::swap(&mut x0, &mut y0);
+            std::mem::swap(&mut x1, &mut y1);
+        }
+        // Always walk left to right
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+            std::mem::swap(&mut inv_w0, &mut inv_w1);
+        }
+
+        let dx = x1 - x0;
+        let dy = y1 - y0;
+        let gradient = if dx.abs() < f32::EPSILON {
+            1.0
+        } else {
+            dy / dx
+        };
+
+        let depth_at = |x: f32| {
+            let t = if dx.abs() < f32::EPSILON {
+                0.0
+            } else {
+                ((x - x0) / dx).clamp(0.0, 1.0)
+            };
+            inv_w0 + t * (inv_w1 - inv_w0)
+        };
+
+        let (major_size, minor_size) = if steep {
+            (self.target.height as f32, self.target.width as f32)
+        } else {
+            (self.target.width as f32, self.target.height as f32)
+        };
+
+        // Plot with axes un-swapped
+        let mut plot = |major: i32, minor: i32, depth: f32, coverage: f32| {
+            if steep {
+                self.blend_pixel_with_depth(minor, major, depth, color, coverage);
+            } else {
+                self.blend_pixel_with_depth(major, minor, depth, color, coverage);
+            }
+        };
+
+        // First endpoint: coverage scaled by how much of the pixel the
+        // segment covers along the major axis.
+        let x_end = x0.round();
+        let y_end = y0 + gradient * (x_end - x0);
+        let x_gap = 1.0 - (x0 + 0.5).fract();
+        let x_start_px = x_end as i32;
+        let y_floor = y_end.floor();
+        let frac = y_end - y_floor;
+        let depth = depth_at(x_end);
+        plot(x_start_px, y_floor as i32, depth, (1.0 - frac) * x_gap);
+        plot(
+            x_start_px,
+            (y_floor as i32).saturating_add(1),
+            depth,
+            frac * x_gap,
+        );
+        let (x_first, y_first) = (x_end, y_end);
+
+        // Second endpoint
+        let x_end = x1.round();
+        let y_end = y1 + gradient * (x_end - x1);
+        let x_gap = (x1 + 0.5).fract();
+        let x_end_px = x_end as i32;
+        let y_floor = y_end.floor();
+        let frac = y_end - y_floor;
+        let depth = depth_at(x_end);
+        plot(x_end_px, y_floor as i32, depth, (1.0 - frac) * x_gap);
+        plot(
+            x_end_px,
+            (y_floor as i32).saturating_add(1),
+            depth,
+            frac * x_gap,
+        );
+
+        // Interior span, clipped to where the line can touch the screen
+        let mut lo = (x_first + 1.0).max(0.0);
+        let mut hi = (x_end - 1.0).min(major_size - 1.0);
+        if gradient.abs() > f32::EPSILON {
+            // Minor coordinate must stay within [-1, minor_size] to touch a pixel
+            let a = x_first + (-1.0 - y_first) / gradient;
+            let b = x_first + (minor_size - y_first) / gradient;
+            lo = lo.max(a.min(b).floor());
+            hi = hi.min(a.max(b).ceil());
+        } else if y_first < -1.0 || y_first > minor_size {
+            return;
+        }
+        if lo > hi {
+            return;
+        }
+
+        for x in lo as i32..=hi as i32 {
+            let inter_y = y_first + gradient * (x as f32 - x_first);
+            let y_floor = inter_y.floor();
+            let frac = inter_y - y_floor;
+            let depth = depth_at(x as f32);
+            plot(x, y_floor as i32, depth, 1.0 - frac);
+            plot(x, y_floor as i32 + 1, depth, frac);
+        }
+    }
+
+    /// Liang–Barsky clip of a Bresenham walk against the framebuffer.
+    ///
+    /// Returns the inclusive range of step indices (0..=steps) whose pixels
+    /// may be visible, or None if the whole line is off screen. A Bresenham
+    /// pixel sits on the ideal line along the major axis and within half a
+    /// pixel of it along the minor axis, so clipping the ideal line against
+    /// the screen grown by one pixel, then padding by one step for float
+    /// error, never drops a visible pixel.
+    fn visible_step_range(
+        &self,
+        x0: i64,
+        y0: i64,
+        x1: i64,
+        y1: i64,
+        steps: i64,
+    ) -> Option<(i64, i64)> {
+        let (x0, y0) = (x0 as f64, y0 as f64);
+        let dx = x1 as f64 - x0;
+        let dy = y1 as f64 - y0;
+
+        let x_min = -1.0;
+        let y_min = -1.0;
+        let x_max = self.target.width as f64;
+        let y_max = self.target.height as f64;
+
+        let mut t_enter = 0.0_f64;
+        let mut t_exit = 1.0_f64;
+
+        // For each boundary: p * t <= q keeps the point inside
+        for (p, q) in [
+            (-dx, x0 - x_min),
+            (dx, x_max - x0),
+            (-dy, y0 - y_min),
+            (dy, y_max - y0),
+        ] {
+            if p == 0.0 {
+                // Parallel to this boundary: fully outside or irrelevant
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = q / p;
+                if p < 0.0 {
+                    t_enter = t_enter.max(t);
+                } else {
+                    t_exit = t_exit.min(t);
+                }
+            }
+        }
+
+        if t_enter > t_exit {
+            return None;
+        }
+
+        let steps_f = steps as f64;
+        let first = ((t_enter * steps_f).floor() as i64 - 1).max(0);
+        let last = ((t_exit * steps_f).ceil() as i64 + 1).min(steps);
+        Some((first, last))
+    }
+
     #[allow(dead_code)]
     pub fn draw_line_dda(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
         let dx = x1 - x0;
@@ -250,22 +810,531 @@ impl Renderer {
         }
     }
 
-    pub fn as_bytes(&self) -> &[u8] {
-        unsafe {
-            std::slice::from_raw_parts(
-                self.color_buffer.as_ptr() as *const u8,
-                self.color_buffer.len() * 4,
-            )
+    /// The color buffer as mutable pixels, for in-place post-processing.
+    pub fn pixels(&self) -> &[u32] {
+        &self.target.color
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.target.color
+    }
+
+    /// The color buffer as ARGB8888 bytes in the host's byte order; see
+    /// [`RenderTarget::as_argb8888_bytes`].
+    pub fn as_argb8888_bytes(&self) -> &[u8] {
+        self.target.as_argb8888_bytes()
+    }
+
+    /// The color buffer as `R, G, B, A` bytes; see
+    /// [`RenderTarget::to_rgba8888`].
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn to_rgba8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        self.target.to_rgba8888(out, pitch)
+    }
+
+    /// The color buffer as `B, G, R, A` bytes; see
+    /// [`RenderTarget::to_bgra8888`].
+    #[cfg_attr(not(feature = "bench-internals"), allow(dead_code))]
+    pub fn to_bgra8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        self.target.to_bgra8888(out, pitch)
+    }
+
+    /// Box-filter the color buffer down by `factor` into `dst`.
+    ///
+    /// Each output pixel is the per-channel average of a `factor`×`factor`
+    /// block of samples. `dst` must hold `(width / factor) * (height / factor)`
+    /// pixels. Used to resolve supersampled frames.
+    pub fn resolve_into(&self, factor: u32, dst: &mut [u32]) {
+        let out_width = self.target.width / factor;
+        let out_height = self.target.height / factor;
+        debug_assert_eq!(dst.len(), (out_width * out_height) as usize);
+
+        let samples = factor * factor;
+        for oy in 0..out_height {
+            for ox in 0..out_width {
+                let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+                for sy in 0..factor {
+                    let row = ((oy * factor + sy) * self.target.width + ox * factor) as usize;
+                    for &c in &self.target.color[row..row + factor as usize] {
+                        r += (c >> 16) & 0xFF;
+                        g += (c >> 8) & 0xFF;
+                        b += c & 0xFF;
+                    }
+                }
+                // Round to nearest rather than truncate
+                let avg = |sum: u32| (sum + samples / 2) / samples;
+                dst[(oy * out_width + ox) as usize] =
+                    0xFF000000 | (avg(r) << 16) | (avg(g) << 8) | avg(b);
+            }
         }
     }
 
-    /// Get a mutable FrameBuffer view into the color and depth buffers.
+    /// Draw `color` on every pixel outside the mask within `radius` pixels
+    /// of one inside it, outlining the masked shape.
+    ///
+    /// Only pixels on the mask's edge stamp the surrounding disc, so the
+    /// cost follows the outline's length rather than the shape's area.
+    pub fn draw_mask_outline(&mut self, radius: i32, color: u32) {
+        if radius <= 0 {
+            return;
+        }
+        let (width, height) = (self.target.width as i32, self.target.height as i32);
+        let masked = |mask: &[u8], x: i32, y: i32| {
+            x >= 0 && x < width && y >= 0 && y < height && mask[(y * width + x) as usize] != 0
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let on_edge = masked(&self.mask_buffer, x, y)
+                    && [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                        .iter()
+                        .any(|(dx, dy)| !masked(&self.mask_buffer, x + dx, y + dy));
+                if !on_edge {
+                    continue;
+                }
+                for dy in -radius..=radius {
+                    for dx in -radius..=radius {
+                        let (px, py) = (x + dx, y + dy);
+                        if dx * dx + dy * dy > radius * radius
+                            || px < 0
+                            || px >= width
+                            || py < 0
+                            || py >= height
+                        {
+                            continue;
+                        }
+                        let idx = (py * width + px) as usize;
+                        if self.mask_buffer[idx] == 0 {
+                            self.target.color[idx] = color;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw `color` around the silhouettes of the masked pixels: wherever a
+    /// masked pixel borders one that is much farther away (or empty), every
+    /// pixel within `radius` that is also that much farther is painted.
+    ///
+    /// This outlines a masked surface against the background and against
+    /// other parts of itself it overlaps, as an inverted hull would, while
+    /// unmasked depth (such as grid lines) never starts an outline. Depth
+    /// and mask are left unchanged. Draws nothing without a depth plane.
+    pub fn draw_silhouette_outline(&mut self, radius: i32, color: u32) {
+        if radius <= 0 || !self.target.has_depth() {
+            return;
+        }
+        let (width, height) = (self.target.width as i32, self.target.height as i32);
+        let encoding = self.target.depth_encoding;
+        let clear = encoding.clear_value();
+        let depth_buffer = &self.target.depth;
+        // View-space depth, infinite where nothing was drawn
+        let view_depth = |idx: usize| {
+            let stored = depth_buffer[idx];
+            if stored == clear {
+                f32::INFINITY
+            } else {
+                1.0 / encoding.decode(stored)
+            }
+        };
+        let behind = |near: f32, idx: usize| view_depth(idx) > near * SILHOUETTE_DEPTH_RATIO;
+        let index = |x: i32, y: i32| {
+            (x >= 0 && x < width && y >= 0 && y < height).then(|| (y * width + x) as usize)
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) as usize;
+                if self.mask_buffer[idx] == 0 {
+                    continue;
+                }
+                let near = view_depth(idx);
+                let on_edge = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .filter_map(|(dx, dy)| index(x + dx, y + dy))
+                    .any(|neighbor| behind(near, neighbor));
+                if !on_edge {
+                    continue;
+                }
+                for dy in -radius..=radius {
+                    for dx in -radius..=radius {
+                        if dx * dx + dy * dy > radius * radius {
+                            continue;
+                        }
+                        if let Some(target) = index(x + dx, y + dy) {
+                            if behind(near, target) {
+                                self.target.color[target] = color;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get a mutable FrameBuffer view into the color and depth buffers,
+    /// with the mask buffer and write settings attached.
     pub fn as_framebuffer(&mut self) -> FrameBuffer<'_> {
-        FrameBuffer::new(
-            &mut self.color_buffer,
-            &mut self.depth_buffer,
-            self.width,
-            self.height,
-        )
+        self.target
+            .as_framebuffer()
+            .with_mask_buffer(&mut self.mask_buffer)
+            .with_mask_test(self.mask_test)
+            .with_mask_write(self.mask_write)
+            .with_color_write(self.color_write)
+    }
+
+    /// Like [`Renderer::as_framebuffer`], drawing into the G-buffer planes
+    /// instead of the color plane; see
+    /// [`RenderTarget::as_gbuffer_framebuffer`].
+    pub fn as_gbuffer_framebuffer(&mut self) -> FrameBuffer<'_> {
+        self.target
+            .as_gbuffer_framebuffer()
+            .with_mask_buffer(&mut self.mask_buffer)
+            .with_mask_test(self.mask_test)
+            .with_mask_write(self.mask_write)
+            .with_color_write(self.color_write)
+    }
+
+    /// Like [`Renderer::as_gbuffer_framebuffer`], with the ID buffer
+    /// attached for picking.
+    pub fn as_gbuffer_framebuffer_with_ids(&mut self) -> FrameBuffer<'_> {
+        self.target
+            .as_gbuffer_framebuffer()
+            .with_id_buffer(&mut self.id_buffer)
+            .with_mask_buffer(&mut self.mask_buffer)
+            .with_mask_test(self.mask_test)
+            .with_mask_write(self.mask_write)
+            .with_color_write(self.color_write)
+    }
+
+    /// Like [`Renderer::as_framebuffer`], with the ID buffer attached for picking.
+    pub fn as_framebuffer_with_ids(&mut self) -> FrameBuffer<'_> {
+        self.target
+            .as_framebuffer()
+            .with_id_buffer(&mut self.id_buffer)
+            .with_mask_buffer(&mut self.mask_buffer)
+            .with_mask_test(self.mask_test)
+            .with_mask_write(self.mask_write)
+            .with_color_write(self.color_write)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const W: u32 = 64;
+    const H: u32 = 48;
+    const LINE: u32 = 0xFFFF00FF;
+
+    /// Unclipped Bresenham walk, filtered to the screen afterwards.
+    fn reference_pixels(x0: i32, y0: i32, x1: i32, y1: i32) -> HashSet<(i32, i32)> {
+        let dx = (x1 - x0).abs();
+        let dy = (y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx - dy;
+        let (mut x, mut y) = (x0, y0);
+        let mut pixels = HashSet::new();
+        loop {
+            if x >= 0 && x < W as i32 && y >= 0 && y < H as i32 {
+                pixels.insert((x, y));
+            }
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 > -dy {
+                err -= dy;
+                x += sx;
+            }
+            if e2 < dx {
+                err += dx;
+                y += sy;
+            }
+        }
+        pixels
+    }
+
+    fn drawn_pixels(x0: i32, y0: i32, x1: i32, y1: i32) -> HashSet<(i32, i32)> {
+        let mut renderer = Renderer::new(W, H);
+        renderer.draw_line_bresenham(x0, y0, 1.0, x1, y1, 1.0, LINE);
+        let mut pixels = HashSet::new();
+        for y in 0..H as i32 {
+            for x in 0..W as i32 {
+                if renderer.target.color[(y as u32 * W + x as u32) as usize] == LINE {
+                    pixels.insert((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn clipped_line_across_corner_matches_unclipped() {
+        // Enters through the top-left corner from far off screen
+        let (x0, y0, x1, y1) = (-5000, -3731, 40, 30);
+        let drawn = drawn_pixels(x0, y0, x1, y1);
+        assert!(!drawn.is_empty());
+        assert_eq!(drawn, reference_pixels(x0, y0, x1, y1));
+    }
+
+    #[test]
+    fn clipped_lines_match_unclipped_in_every_octant() {
+        let endpoints = [
+            (-300, 20, 400, 30),
+            (30, -300, 35, 400),
+            (70, -10, -10, 60),
+            (-7, 55, 80, -19),
+            (100, 100, -50, -60),
+            (20, 47, 63, 0),
+            (-1, -1, 64, 48),
+        ];
+        for (x0, y0, x1, y1) in endpoints {
+            assert_eq!(
+                drawn_pixels(x0, y0, x1, y1),
+                reference_pixels(x0, y0, x1, y1),
+                "line ({x0}, {y0}) -> ({x1}, {y1})"
+            );
+        }
+    }
+
+    #[test]
+    fn fully_offscreen_line_draws_nothing() {
+        assert!(drawn_pixels(-100, -50, -10, -200).is_empty());
+        assert!(drawn_pixels(100, 10, 5000, 20).is_empty());
+    }
+
+    #[test]
+    fn wu_line_on_pixel_centers_is_solid() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.draw_line_wu(5.5, 10.5, 1.0, 30.5, 10.5, 1.0, LINE);
+
+        for x in 6..30 {
+            assert_eq!(renderer.target.color[(10 * W + x) as usize], LINE);
+            assert_eq!(renderer.target.color[(11 * W + x) as usize], 0xFF000000);
+        }
+    }
+
+    #[test]
+    fn wu_line_splits_coverage_between_neighbors() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        // Halfway between rows 10 and 11: each gets half coverage
+        renderer.draw_line_wu(5.5, 11.0, 1.0, 30.5, 11.0, 1.0, 0xFFFFFFFF);
+
+        for x in 6..30 {
+            let upper = renderer.target.color[(10 * W + x) as usize] & 0xFF;
+            let lower = renderer.target.color[(11 * W + x) as usize] & 0xFF;
+            assert!((upper as i32 - 128).abs() <= 1, "upper = {upper}");
+            assert!((lower as i32 - 128).abs() <= 1, "lower = {lower}");
+        }
+    }
+
+    #[test]
+    fn wu_line_respects_depth_and_steep_lines() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        // Occluder much closer than the line
+        renderer.target.depth.fill(10.0);
+        renderer.draw_line_wu(2.5, 2.5, 1.0, 20.5, 40.5, 1.0, LINE);
+        assert!(renderer.target.color.iter().all(|&c| c == 0xFF000000));
+
+        renderer.target.depth.fill(0.0);
+        renderer.draw_line_wu(20.5, 2.5, 1.0, 20.5, 40.5, 1.0, LINE);
+        for y in 3..40 {
+            assert_eq!(renderer.target.color[(y * W + 20) as usize], LINE);
+        }
+    }
+
+    #[test]
+    fn wu_line_far_off_screen_is_clipped() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.draw_line_wu(-1.0e6, 20.5, 1.0, 1.0e6, 20.5, 1.0, LINE);
+        for x in 0..W {
+            assert_eq!(renderer.target.color[(20 * W + x) as usize], LINE);
+        }
+    }
+
+    #[test]
+    fn resolve_averages_sample_blocks() {
+        let mut renderer = Renderer::new(4, 2);
+        // Left 2x2 block: two white, two black -> mid gray. Right block: solid red.
+        renderer.target.color = vec![
+            0xFFFFFFFF, 0xFF000000, 0xFFFF0000, 0xFFFF0000, //
+            0xFF000000, 0xFFFFFFFF, 0xFFFF0000, 0xFFFF0000,
+        ];
+        let mut out = [0u32; 2];
+        renderer.resolve_into(2, &mut out);
+        assert_eq!(out, [0xFF808080, 0xFFFF0000]);
+    }
+
+    #[test]
+    fn upscale_repeats_each_source_pixel() {
+        let src = [1, 2, 3, 4, 5, 6];
+        let mut out = [0u32; 24];
+        upscale_nearest(&src, 3, &mut out, 6);
+        assert_eq!(
+            out,
+            [
+                1, 1, 2, 2, 3, 3, //
+                1, 1, 2, 2, 3, 3, //
+                4, 4, 5, 5, 6, 6, //
+                4, 4, 5, 5, 6, 6,
+            ]
+        );
+
+        // Uneven ratios pick the pixel under each center
+        let mut out = [0u32; 5];
+        upscale_nearest(&[1, 2], 2, &mut out, 5);
+        assert_eq!(out, [1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn draw_text_sets_glyph_pixels_only() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.draw_text(2, 3, "I", LINE);
+
+        // 'I' has a full-width top bar from column 1 to 4
+        for x in 3..7 {
+            assert_eq!(renderer.target.color[(3 * W + x) as usize], LINE);
+        }
+        // Nothing drawn outside the 8x8 cell
+        for y in 0..H {
+            for x in 0..W {
+                let inside = (2..10).contains(&x) && (3..11).contains(&y);
+                if !inside {
+                    assert_eq!(renderer.target.color[(y * W + x) as usize], 0xFF000000);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn filled_rect_alpha_blends_and_clips() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.draw_filled_rect_alpha(-10, -10, 20, 20, 0xFFFFFFFF, 0.5);
+
+        assert_eq!(renderer.target.color[0], 0xFF808080);
+        assert_eq!(renderer.target.color[(9 * W + 9) as usize], 0xFF808080);
+        assert_eq!(renderer.target.color[(10 * W + 10) as usize], 0xFF000000);
+    }
+
+    #[test]
+    fn mask_outline_rings_the_masked_pixels() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.clear_depth();
+        // Mark a 3x3 block at (10, 10)..(12, 12) without drawing it
+        renderer.set_mask_write(true);
+        renderer.set_color_write(false);
+        for y in 10..13 {
+            for x in 10..13 {
+                renderer.set_pixel_with_depth(x, y, 1.0, 0xFFFFFFFF);
+            }
+        }
+        renderer.set_mask_write(false);
+        renderer.set_color_write(true);
+        assert_eq!(renderer.mask_at(11, 11), Some(MASK_SET));
+
+        renderer.draw_mask_outline(1, 0xFFFF0000);
+        let at = |x: u32, y: u32| renderer.target.color[(y * W + x) as usize];
+        // Inside stays untouched; one pixel out is outlined; the diagonal
+        // corner is outside the radius
+        assert_eq!(at(11, 11), 0xFF000000);
+        assert_eq!(at(9, 11), 0xFFFF0000);
+        assert_eq!(at(11, 13), 0xFFFF0000);
+        assert_eq!(at(9, 9), 0xFF000000);
+        assert_eq!(at(8, 11), 0xFF000000);
+    }
+
+    #[test]
+    fn silhouette_outline_rings_masked_pixels_in_front() {
+        let mut renderer = Renderer::new(W, H);
+        renderer.clear(0xFF000000);
+        renderer.clear_depth();
+        // Unmasked floor at w = 10, like a grid line
+        for x in 0..W as i32 {
+            renderer.set_pixel_with_depth(x, 20, 1.0 / 10.0, 0xFF00FF00);
+        }
+        // Masked 3x3 block at w = 2 on the floor, plus a masked pixel at
+        // w = 2.02 beside it, too close to count as a separate surface
+        renderer.set_mask_write(true);
+        for y in 19..22 {
+            for x in 10..13 {
+                renderer.set_pixel_with_depth(x, y, 1.0 / 2.0, 0xFFFFFFFF);
+            }
+        }
+        renderer.set_pixel_with_depth(13, 20, 1.0 / 2.02, 0xFFFFFFFF);
+        renderer.set_mask_write(false);
+
+        renderer.draw_silhouette_outline(1, 0xFFFF0000);
+        let at = |x: u32, y: u32| renderer.target.color[(y * W + x) as usize];
+        assert_eq!(at(11, 20), 0xFFFFFFFF);
+        assert_eq!(at(13, 20), 0xFFFFFFFF);
+        // Background and the farther floor are painted around it
+        assert_eq!(at(9, 20), 0xFFFF0000);
+        assert_eq!(at(14, 20), 0xFFFF0000);
+        assert_eq!(at(11, 18), 0xFFFF0000);
+        // The unmasked floor starts no outline of its own
+        assert_eq!(at(30, 19), 0xFF000000);
+        assert_eq!(at(30, 20), 0xFF00FF00);
+    }
+
+    #[test]
+    fn depth_encodings_agree_on_which_is_closer() {
+        use crate::render::framebuffer::DepthMode;
+
+        for mode in [DepthMode::InvW, DepthMode::Ndc, DepthMode::ReversedNdc] {
+            let mut renderer = Renderer::new(W, H);
+            renderer.set_depth_encoding(DepthEncoding::new(mode, 0.1, 100.0));
+            renderer.clear_depth();
+
+            // w = 10 drawn first, then w = 20 behind it, then w = 5 in front
+            renderer.set_pixel_with_depth(0, 0, 1.0 / 10.0, 1);
+            renderer.set_pixel_with_depth(0, 0, 1.0 / 20.0, 2);
+            assert_eq!(renderer.target.color[0], 1, "{mode:?}");
+            renderer.set_pixel_with_depth(0, 0, 1.0 / 5.0, 3);
+            assert_eq!(renderer.target.color[0], 3, "{mode:?}");
+
+            let inv_w = renderer.depth_at(0, 0).unwrap();
+            assert!((1.0 / inv_w - 5.0).abs() < 1e-3, "{mode:?}: {inv_w}");
+        }
+    }
+
+    #[test]
+    fn grids_are_centered_at_odd_sizes() {
+        for (width, height) in [(799, 601), (3, 3), (1, 1)] {
+            let mut renderer = Renderer::new(width, height);
+            renderer.clear(0);
+            renderer.draw_grid(50, LINE);
+            let color = &renderer.target.color;
+            let row = |y: u32| &color[(y * width) as usize..((y + 1) * width) as usize];
+            // Through the middle, and mirrored about it
+            assert!(row(height / 2).iter().all(|&c| c == LINE));
+            for y in 0..height {
+                let (top, bottom) = (row(y), row(height - 1 - y));
+                assert_eq!(top, bottom, "{width}x{height}, row {y}");
+                assert!(top.iter().eq(top.iter().rev()), "{width}x{height}, row {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn frame_bytes_cover_odd_lengths_exactly() {
+        for len in [1, 3, 9, 799 * 601] {
+            let pixels: Vec<u32> = (0..len as u32)
+                .map(|i| i.wrapping_mul(0x9E37_79B9))
+                .collect();
+            let bytes = pixels_as_bytes(&pixels);
+            // The same bytes the safe conversion gives, and no more
+            let expected: Vec<u8> = pixels.iter().flat_map(|p| p.to_ne_bytes()).collect();
+            assert!(bytes == expected.as_slice(), "{len} pixels");
+        }
     }
 }
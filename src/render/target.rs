@@ -0,0 +1,471 @@
+//! Owned color and depth buffers to render into.
+//!
+//! [`RenderTarget`] is the storage behind a [`FrameBuffer`]: the
+//! [`Renderer`](super::Renderer) draws into one, and any number of others
+//! can be kept around for offscreen passes and filled with
+//! [`Engine::render_to`](crate::engine::Engine::render_to).
+//!
+//! For the deferred pipeline a target also carries G-buffer planes:
+//! albedo, packed normals and 1/w, which the lighting pass reads to fill
+//! the color plane.
+
+use std::path::Path;
+
+use super::framebuffer::{DepthEncoding, FrameBuffer};
+use super::renderer::{export_pixels, pixels_as_bytes, ByteOrder};
+use crate::colors;
+use crate::error::Error;
+use crate::light::PixelLighting;
+use crate::math::vec3::Vec3;
+use crate::window::{save_png, PresentError};
+
+/// Largest value of a signed 10-bit normal component.
+const NORMAL_SCALE: f32 = 511.0;
+
+/// Pack a normal into 10 bits per component, x in the low bits. Components
+/// are clamped to [-1, 1]; a zero normal packs to 0.
+#[inline]
+pub fn pack_normal(normal: Vec3) -> u32 {
+    let q = |c: f32| ((c.clamp(-1.0, 1.0) * NORMAL_SCALE).round() as i32 as u32) & 0x3FF;
+    q(normal.x) | (q(normal.y) << 10) | (q(normal.z) << 20)
+}
+
+/// Unpack a normal stored by [`pack_normal`], not renormalized.
+#[inline]
+pub fn unpack_normal(packed: u32) -> Vec3 {
+    // Shift each field to the top and back down to sign-extend it
+    let c = |shift: u32| ((packed << (22 - shift)) as i32 >> 22) as f32 / NORMAL_SCALE;
+    Vec3::new(c(0), c(10), c(20))
+}
+
+/// Where each pixel of a target's view lies in the world, for lighting a
+/// G-buffer: the point on the far plane under each pixel center, which is
+/// affine across the screen, scaled back toward the camera by the
+/// pixel's depth.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PixelRays {
+    /// Camera position in world space.
+    pub origin: Vec3,
+    /// Far-plane point under the center of pixel (0, 0), relative to
+    /// `origin`.
+    pub corner: Vec3,
+    /// Change in that point one pixel right and one pixel down.
+    pub step_x: Vec3,
+    pub step_y: Vec3,
+    /// View depth (clip w) of the far plane.
+    pub far_depth: f32,
+}
+
+impl PixelRays {
+    /// World position of pixel (x, y) at view depth `depth`.
+    #[inline]
+    fn position(&self, x: u32, y: u32, depth: f32) -> Vec3 {
+        let far = self.corner + self.step_x * x as f32 + self.step_y * y as f32;
+        self.origin + far * (depth / self.far_depth)
+    }
+}
+
+/// A color plane and a depth plane of the same size, and G-buffer planes
+/// when asked for.
+///
+/// ```ignore
+/// let mut target = RenderTarget::new(256, 256);
+/// engine.render_to(&mut target);
+/// target.save_png("thumbnail.png")?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderTarget {
+    pub(crate) color: Vec<u32>,
+    pub(crate) depth: Vec<f32>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) depth_encoding: DepthEncoding,
+    // G-buffer planes, empty unless enabled
+    pub(crate) gbuffer: bool,
+    pub(crate) albedo: Vec<u32>,
+    pub(crate) normals: Vec<u32>,
+    pub(crate) inv_w: Vec<f32>,
+}
+
+impl RenderTarget {
+    /// A target cleared to the background color and "infinitely far" 1/w
+    /// depth.
+    pub fn new(width: u32, height: u32) -> Self {
+        let size = (width * height) as usize;
+        let depth_encoding = DepthEncoding::INV_W;
+        Self {
+            color: vec![colors::BACKGROUND; size],
+            depth: vec![depth_encoding.clear_value(); size],
+            width,
+            height,
+            depth_encoding,
+            gbuffer: false,
+            albedo: Vec::new(),
+            normals: Vec::new(),
+            inv_w: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Reallocate every plane at the new size, cleared as in
+    /// [`RenderTarget::new`]. The depth encoding is kept, as are a missing
+    /// depth plane and present G-buffer planes.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let size = (width * height) as usize;
+        let depth_size = if self.has_depth() { size } else { 0 };
+        let gbuffer = self.has_gbuffer();
+        self.color = vec![colors::BACKGROUND; size];
+        self.depth = vec![self.depth_encoding.clear_value(); depth_size];
+        self.width = width;
+        self.height = height;
+        self.gbuffer = false;
+        self.set_gbuffer_planes(gbuffer);
+    }
+
+    /// Fill the color plane with `color`.
+    pub fn clear(&mut self, color: u32) {
+        self.color.fill(color);
+    }
+
+    /// Fill the depth plane with the encoding's "infinitely far" value.
+    pub fn clear_depth(&mut self) {
+        self.depth.fill(self.depth_encoding.clear_value());
+    }
+
+    /// Allocate a cleared depth plane, or free it. Without one nothing is
+    /// depth tested: every write lands, and the last one drawn wins.
+    pub fn set_depth_plane(&mut self, enabled: bool) {
+        if enabled == self.has_depth() {
+            return;
+        }
+        self.depth = if enabled {
+            vec![self.depth_encoding.clear_value(); self.color.len()]
+        } else {
+            Vec::new()
+        };
+    }
+
+    pub fn has_depth(&self) -> bool {
+        !self.depth.is_empty() || self.color.is_empty()
+    }
+
+    /// Allocate cleared albedo, normal and 1/w planes for the deferred
+    /// pipeline, or free them.
+    pub fn set_gbuffer_planes(&mut self, enabled: bool) {
+        if enabled == self.has_gbuffer() {
+            return;
+        }
+        let size = if enabled { self.color.len() } else { 0 };
+        self.gbuffer = enabled;
+        self.albedo = vec![0; size];
+        self.normals = vec![0; size];
+        self.inv_w = vec![0.0; size];
+    }
+
+    pub fn has_gbuffer(&self) -> bool {
+        self.gbuffer
+    }
+
+    /// Mark every G-buffer pixel empty, with 1/w 0 ("nothing drawn").
+    /// Albedo and normals are left as they are: only pixels drawn over
+    /// are read.
+    pub fn clear_gbuffer(&mut self) {
+        self.inv_w.fill(0.0);
+    }
+
+    /// Unlit surface colors the deferred pipeline drew. Empty without
+    /// G-buffer planes.
+    pub fn albedo(&self) -> &[u32] {
+        &self.albedo
+    }
+
+    /// Surface normals the deferred pipeline drew, packed 10 signed bits
+    /// per axis with x lowest; [`RenderTarget::normal_at`] unpacks one.
+    /// Empty without G-buffer planes.
+    pub fn normals(&self) -> &[u32] {
+        &self.normals
+    }
+
+    /// 1/w of the surface at each pixel, 0.0 where nothing was drawn.
+    /// Unlike [`RenderTarget::depth`] it is never encoded, and it is kept
+    /// without a depth plane too. Empty without G-buffer planes.
+    pub fn inv_w(&self) -> &[f32] {
+        &self.inv_w
+    }
+
+    /// The unpacked normal drawn at (x, y), or None if out of bounds,
+    /// nothing was drawn there or there are no G-buffer planes.
+    pub fn normal_at(&self, x: i32, y: i32) -> Option<Vec3> {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            let idx = (y as u32 * self.width + x as u32) as usize;
+            let drawn = *self.inv_w.get(idx)? > 0.0;
+            drawn.then(|| unpack_normal(self.normals[idx]))
+        } else {
+            None
+        }
+    }
+    /// Change what the depth plane stores. Takes effect from the next
+    /// [`RenderTarget::clear_depth`].
+    pub fn set_depth_encoding(&mut self, encoding: DepthEncoding) {
+        self.depth_encoding = encoding;
+    }
+
+    pub fn depth_encoding(&self) -> DepthEncoding {
+        self.depth_encoding
+    }
+
+    /// ARGB pixels, row-major from the top-left.
+    pub fn pixels(&self) -> &[u32] {
+        &self.color
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.color
+    }
+
+    /// Stored depths, in the target's [`DepthEncoding`]. Empty without a
+    /// depth plane.
+    pub fn depth(&self) -> &[f32] {
+        &self.depth
+    }
+
+    /// Returns the stored depth at (x, y) as 1/w, or None if out of bounds
+    /// or there is no depth plane.
+    pub fn depth_at(&self, x: i32, y: i32) -> Option<f32> {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            let depth = self
+                .depth
+                .get((y as u32 * self.width + x as u32) as usize)?;
+            Some(self.depth_encoding.decode(*depth))
+        } else {
+            None
+        }
+    }
+
+    /// The pixels as ARGB8888 bytes without copying, as
+    /// [`Presenter`](crate::window::Presenter)s take them. Each pixel is a
+    /// `u32` in the host's byte order (`B, G, R, A` on little-endian
+    /// machines), so for a fixed byte order use
+    /// [`RenderTarget::to_rgba8888`] or [`RenderTarget::to_bgra8888`].
+    pub fn as_argb8888_bytes(&self) -> &[u8] {
+        pixels_as_bytes(&self.color)
+    }
+
+    /// Copy the pixels into `out` as `R, G, B, A` bytes on any host, rows
+    /// `pitch` bytes apart for padded destinations (at least four per
+    /// pixel) or packed with `None`.
+    pub fn to_rgba8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        export_pixels(
+            self.color.iter().copied(),
+            self.width,
+            ByteOrder::Rgba8888,
+            pitch,
+            out,
+        )
+    }
+
+    /// Like [`RenderTarget::to_rgba8888`], as `B, G, R, A` bytes.
+    pub fn to_bgra8888(&self, out: &mut Vec<u8>, pitch: Option<usize>) -> Result<(), Error> {
+        export_pixels(
+            self.color.iter().copied(),
+            self.width,
+            ByteOrder::Bgra8888,
+            pitch,
+            out,
+        )
+    }
+
+    /// A [`FrameBuffer`] view over both planes, depth tested with the
+    /// target's encoding (or not at all without a depth plane).
+    pub fn as_framebuffer(&mut self) -> FrameBuffer<'_> {
+        FrameBuffer::new(&mut self.color, &mut self.depth, self.width, self.height)
+            .with_depth_encoding(self.depth_encoding)
+    }
+
+    /// A [`FrameBuffer`] drawing into the G-buffer planes: albedo in place
+    /// of color, with normals and 1/w alongside, depth tested as by
+    /// [`RenderTarget::as_framebuffer`].
+    ///
+    /// # Panics
+    /// Panics without G-buffer planes; see
+    /// [`RenderTarget::set_gbuffer_planes`].
+    pub fn as_gbuffer_framebuffer(&mut self) -> FrameBuffer<'_> {
+        assert!(self.has_gbuffer(), "render target has no G-buffer planes");
+        FrameBuffer::new(&mut self.albedo, &mut self.depth, self.width, self.height)
+            .with_depth_encoding(self.depth_encoding)
+            .with_gbuffer(&mut self.normals, &mut self.inv_w)
+    }
+
+    /// The deferred lighting pass: light every pixel the G-buffer holds a
+    /// surface for and write it to the color plane, leaving the others
+    /// alone. Returns how many pixels were lit.
+    pub(crate) fn light_gbuffer(&mut self, lighting: &PixelLighting, rays: &PixelRays) -> usize {
+        if !self.has_gbuffer() {
+            return 0;
+        }
+        let width = self.width as usize;
+        let mut lit = 0;
+        for (y, row) in self.inv_w.chunks_exact(width).enumerate() {
+            let start = y * width;
+            for (x, &inv_w) in row.iter().enumerate() {
+                if inv_w <= 0.0 {
+                    continue;
+                }
+                let idx = start + x;
+                let depth = 1.0 / inv_w;
+                let position = rays.position(x as u32, y as u32, depth);
+                let normal = unpack_normal(self.normals[idx]);
+                self.color[idx] = lighting.shade(self.albedo[idx], normal, position, depth);
+                lit += 1;
+            }
+        }
+        lit
+    }
+
+    /// Write the color plane to a PNG file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), PresentError> {
+        save_png(
+            path.as_ref(),
+            self.as_argb8888_bytes(),
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl Default for RenderTarget {
+    /// An empty 0×0 target.
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::DepthMode;
+
+    #[test]
+    fn clears_and_resizes() {
+        let mut target = RenderTarget::new(4, 3);
+        assert_eq!(target.pixels().len(), 12);
+        target.clear(0xFF102030);
+        assert!(target.pixels().iter().all(|&p| p == 0xFF102030));
+
+        target.set_depth_encoding(DepthEncoding::new(DepthMode::Ndc, 0.1, 100.0));
+        target.clear_depth();
+        assert!(target.depth().iter().all(|&d| d == 1.0));
+
+        target.resize(2, 2);
+        assert_eq!((target.width(), target.height()), (2, 2));
+        assert_eq!(target.pixels(), &[colors::BACKGROUND; 4]);
+        assert_eq!(target.depth(), &[1.0; 4]);
+        assert_eq!(target.depth_at(2, 0), None);
+    }
+
+    #[test]
+    fn normals_survive_packing() {
+        for n in [
+            Vec3::new(0.6, 0.0, -0.8),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, -0.28, 0.96),
+        ] {
+            assert!(
+                (unpack_normal(pack_normal(n)) - n).magnitude() < 0.01,
+                "{n:?}"
+            );
+        }
+        assert_eq!(pack_normal(Vec3::ZERO), 0);
+        assert_eq!(unpack_normal(0), Vec3::ZERO);
+    }
+
+    #[test]
+    fn gbuffer_writes_store_normal_and_inv_w() {
+        let mut target = RenderTarget::new(2, 2);
+        target.set_gbuffer_planes(true);
+        target.clear_gbuffer();
+        let mut fb = target.as_gbuffer_framebuffer();
+        assert!(fb.has_gbuffer());
+        fb.shade_pixel_with_depth_and_id(1, 0, 0.25, 0, || 0xFF336699);
+        assert_eq!(target.albedo()[1], 0xFF336699);
+        assert_eq!(target.inv_w(), &[0.0, 0.25, 0.0, 0.0]);
+        assert_eq!(target.normal_at(0, 0), None);
+
+        target.resize(3, 1);
+        assert_eq!(target.normals().len(), 3);
+        target.set_gbuffer_planes(false);
+        assert!(target.albedo().is_empty() && target.inv_w().is_empty());
+    }
+
+    #[test]
+    fn framebuffer_writes_land_in_the_target() {
+        let mut target = RenderTarget::new(3, 3);
+        target
+            .as_framebuffer()
+            .set_pixel_with_depth(1, 2, 0.5, 0xFFFF0000);
+        assert_eq!(target.pixels()[7], 0xFFFF0000);
+        assert_eq!(target.depth_at(1, 2), Some(0.5));
+    }
+
+    #[test]
+    fn exports_each_byte_order_whatever_the_host() {
+        let mut target = RenderTarget::new(2, 1);
+        target
+            .pixels_mut()
+            .copy_from_slice(&[0x80112233, 0xFF445566]);
+        // Zero-copy bytes are the u32s as the host stores them
+        assert_eq!(
+            target.as_argb8888_bytes(),
+            [0x80112233u32.to_ne_bytes(), 0xFF445566u32.to_ne_bytes()].concat()
+        );
+
+        let mut bytes = Vec::new();
+        target.to_rgba8888(&mut bytes, None).unwrap();
+        assert_eq!(bytes, [0x11, 0x22, 0x33, 0x80, 0x44, 0x55, 0x66, 0xFF]);
+        target.to_bgra8888(&mut bytes, None).unwrap();
+        assert_eq!(bytes, [0x33, 0x22, 0x11, 0x80, 0x66, 0x55, 0x44, 0xFF]);
+    }
+
+    #[test]
+    fn padded_rows_start_a_pitch_apart() {
+        let mut target = RenderTarget::new(1, 2);
+        target
+            .pixels_mut()
+            .copy_from_slice(&[0xFF010203, 0xFF040506]);
+        let mut bytes = vec![9; 3];
+        target.to_rgba8888(&mut bytes, Some(6)).unwrap();
+        assert_eq!(bytes, [1, 2, 3, 0xFF, 0, 0, 4, 5, 6, 0xFF, 0, 0]);
+
+        // A row of pixels doesn't fit in three bytes
+        assert!(matches!(
+            target.to_bgra8888(&mut bytes, Some(3)),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn depth_plane_can_be_left_out() {
+        let mut target = RenderTarget::new(2, 2);
+        target.set_depth_plane(false);
+        assert!(!target.has_depth());
+        target.resize(3, 2);
+        assert_eq!((target.pixels().len(), target.depth().len()), (6, 0));
+        assert_eq!(target.depth_at(0, 0), None);
+
+        // Later writes win regardless of depth
+        let mut fb = target.as_framebuffer();
+        fb.set_pixel_with_depth(0, 0, 0.5, 0xFFFF0000);
+        fb.set_pixel_with_depth(0, 0, 0.1, 0xFF00FF00);
+        assert_eq!(target.pixels()[0], 0xFF00FF00);
+
+        target.set_depth_plane(true);
+        assert_eq!(target.depth(), &[0.0; 6]);
+    }
+}
@@ -0,0 +1,252 @@
+//! Scene descriptions: the models, light, camera start and render settings
+//! of a scene, read from a RON or JSON file instead of set up in code.
+//!
+//! [`Engine::load_scene`] reads a file and applies it. A scene that places
+//! one spinning, textured model:
+//!
+//! ```ron
+//! (
+//!     models: [
+//!         (
+//!             name: "f22",
+//!             path: "assets/f22.obj",
+//!             texture: "assets/f22.png",
+//!             spin: (axis: (0.0, 1.0, 0.0), radians_per_sec: 1.5707964),
+//!         ),
+//!     ],
+//!     camera: (position: (0.0, 2.0, -10.0)),
+//!     settings: (texture_mode: Replace, debug_hud: true),
+//! )
+//! ```
+//!
+//! Every field but a model's `name` and `path` may be left out; settings
+//! that are left out keep the engine's current value. Optional fields take
+//! a bare value in RON, without `Some(..)`. Asset paths are
+//! relative to the scene file's directory. Fields the loader doesn't know
+//! are skipped and listed by [`Scene::unknown_fields`], so a typo doesn't
+//! stop a scene loading but needn't go unnoticed.
+//!
+//! Needs the `serde` feature.
+//!
+//! [`Engine::load_scene`]: crate::engine::Engine::load_scene
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+use crate::engine::{RasterizerType, RenderMode, ShadingMode, TextureMode};
+use crate::error::Error;
+
+/// A whole scene, as read from a scene file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Scene {
+    /// Models to load, replacing any already in the engine.
+    pub models: Vec<SceneModel>,
+    /// Replaces the engine's light when given.
+    pub light: Option<SceneLight>,
+    /// Where the camera starts, when given.
+    pub camera: Option<SceneCameraStart>,
+    pub settings: SceneSettings,
+    /// Dotted paths of the fields the loader skipped.
+    #[serde(skip)]
+    unknown_fields: Vec<String>,
+}
+
+/// A model file and where it sits in the world.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SceneModel {
+    pub name: String,
+    /// OBJ file, or STL by its `.stl` extension (glTF too, by `.gltf` or
+    /// `.glb`, with the `gltf` feature).
+    pub path: PathBuf,
+    /// Image applied to every mesh of the model.
+    #[serde(default)]
+    pub texture: Option<PathBuf>,
+    #[serde(default)]
+    pub position: [f32; 3],
+    /// Euler angles in radians.
+    #[serde(default)]
+    pub rotation: [f32; 3],
+    #[serde(default = "unit_scale")]
+    pub scale: [f32; 3],
+    /// Turntable animation, see [`crate::animation::Animator::spin`].
+    #[serde(default)]
+    pub spin: Option<SceneSpin>,
+}
+
+fn unit_scale() -> [f32; 3] {
+    [1.0; 3]
+}
+
+/// Constant rotation about an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SceneSpin {
+    pub axis: [f32; 3],
+    pub radians_per_sec: f32,
+}
+
+/// The directional light; see [`crate::light::DirectionalLight`].
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SceneLight {
+    /// The way the light points, normalized on load.
+    pub direction: [f32; 3],
+    #[serde(default = "default_ambient")]
+    pub ambient_intensity: f32,
+    #[serde(default = "default_diffuse")]
+    pub diffuse_strength: f32,
+}
+
+fn default_ambient() -> f32 {
+    0.1
+}
+
+fn default_diffuse() -> f32 {
+    1.0
+}
+
+/// The camera's starting pose. Without a `target` the camera keeps its
+/// orientation and only moves.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SceneCameraStart {
+    pub position: [f32; 3],
+    #[serde(default)]
+    pub target: Option<[f32; 3]>,
+}
+
+/// Engine settings. Each one left out keeps the engine's current value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SceneSettings {
+    pub render_mode: Option<RenderMode>,
+    pub shading_mode: Option<ShadingMode>,
+    pub texture_mode: Option<TextureMode>,
+    pub rasterizer: Option<RasterizerType>,
+    pub backface_culling: Option<bool>,
+    pub draw_grid: Option<bool>,
+    pub debug_hud: Option<bool>,
+    pub picking: Option<bool>,
+    pub supersampling: Option<u32>,
+    pub fov_degrees: Option<f32>,
+}
+
+/// Why a scene couldn't be loaded.
+#[derive(Debug)]
+pub enum SceneError {
+    /// The scene file itself couldn't be read.
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    /// Every model and texture path that doesn't exist. Nothing was loaded.
+    MissingAssets(Vec<PathBuf>),
+    /// A model file exists but failed to load.
+    Model {
+        path: PathBuf,
+        error: Box<Error>,
+    },
+    /// A texture file exists but failed to load.
+    Texture {
+        path: PathBuf,
+        error: Box<Error>,
+    },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(e) => write!(f, "failed to read scene: {}", e),
+            SceneError::Ron(e) => write!(f, "invalid RON scene: {}", e),
+            SceneError::Json(e) => write!(f, "invalid JSON scene: {}", e),
+            SceneError::MissingAssets(paths) => {
+                write!(f, "scene refers to missing files:")?;
+                for path in paths {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            SceneError::Model { path, error } => {
+                write!(f, "{}: {}", path.display(), error)
+            }
+            SceneError::Texture { path, error } => {
+                write!(f, "{}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SceneError::Io(e) => Some(e),
+            SceneError::Ron(e) => Some(e),
+            SceneError::Json(e) => Some(e),
+            SceneError::Model { error, .. } => Some(error.as_ref()),
+            SceneError::Texture { error, .. } => Some(error.as_ref()),
+            SceneError::MissingAssets(_) => None,
+        }
+    }
+}
+
+impl Scene {
+    /// Read a scene file: JSON if the extension is `.json`, RON otherwise.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SceneError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(SceneError::Io)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            Self::from_json(&text)
+        } else {
+            Self::from_ron(&text)
+        }
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, SceneError> {
+        // Optional fields take a bare value, no `Some(..)` needed
+        let options = ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let mut deserializer =
+            ron::Deserializer::from_str_with_options(text, options).map_err(SceneError::Ron)?;
+        let mut unknown_fields = Vec::new();
+        let scene: Result<Scene, _> = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        });
+        let mut scene = scene.map_err(|e| SceneError::Ron(deserializer.span_error(e)))?;
+        deserializer
+            .end()
+            .map_err(|e| SceneError::Ron(deserializer.span_error(e)))?;
+        scene.unknown_fields = unknown_fields;
+        Ok(scene)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, SceneError> {
+        let mut deserializer = serde_json::Deserializer::from_str(text);
+        let mut unknown_fields = Vec::new();
+        let mut scene: Scene = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown_fields.push(path.to_string())
+        })
+        .map_err(SceneError::Json)?;
+        deserializer.end().map_err(SceneError::Json)?;
+        scene.unknown_fields = unknown_fields;
+        Ok(scene)
+    }
+
+    /// Dotted paths (`models[0].textur`, say) of fields in the file the
+    /// loader didn't recognize and skipped.
+    pub fn unknown_fields(&self) -> &[String] {
+        &self.unknown_fields
+    }
+
+    /// Every model and texture path, resolved against `base_dir`, that
+    /// doesn't exist, in file order.
+    pub fn missing_assets(&self, base_dir: &Path) -> Vec<PathBuf> {
+        self.models
+            .iter()
+            .flat_map(|model| std::iter::once(&model.path).chain(&model.texture))
+            .map(|path| base_dir.join(path))
+            .filter(|path| !path.exists())
+            .collect()
+    }
+}
@@ -0,0 +1,380 @@
+//! STL loading, binary and ASCII.
+//!
+//! STL stores every triangle with its own three corners and a facet normal,
+//! so nothing is shared. On load, corners at identical positions are merged
+//! into one vertex when their facets meet at no more than a crease angle,
+//! which lets Gouraud shading smooth curved surfaces while hard edges (a
+//! cube's, say) stay sharp. Each merged vertex's normal is the average of
+//! its facets' normals.
+//!
+//! Facet normals in the wild are often zero or stale, so one that isn't
+//! unit length or points away from the winding's normal is replaced by the
+//! normal computed from the corners.
+//!
+//! Coordinates and winding are used as-is, as with OBJ: STL's
+//! counter-clockwise-outward winding is what the backface test treats as
+//! front-facing. STL is usually z-up; no axis conversion is done.
+
+use std::collections::HashMap;
+
+use crate::math::{vec2::Vec2, vec3::Vec3, vec4::Vec4};
+use crate::mesh::{Face, LoadError, Mesh, Vertex};
+
+/// Default crease angle for [`Mesh::from_stl`], in radians (30 degrees).
+pub(crate) const DEFAULT_CREASE_ANGLE: f32 = std::f32::consts::PI / 6.0;
+
+const HEADER_LEN: usize = 80;
+const FACET_LEN: usize = 50;
+
+/// One triangle as stored in the file.
+#[derive(Debug, Clone, Copy)]
+struct Facet {
+    normal: Vec3,
+    corners: [Vec3; 3],
+}
+
+/// Parse STL `bytes` into a mesh named `name` (ASCII files use their own
+/// `solid` name when they have one).
+pub(crate) fn parse(name: &str, bytes: &[u8], crease_angle: f32) -> Result<Mesh, LoadError> {
+    let (solid_name, facets) = if is_binary(bytes) {
+        (None, parse_binary(bytes)?)
+    } else {
+        parse_ascii(bytes)?
+    };
+    if facets.is_empty() {
+        return Err(LoadError::NoVertices);
+    }
+    let (vertices, faces) = weld(&facets, crease_angle);
+    let name = solid_name.unwrap_or_else(|| name.to_string());
+    Ok(Mesh::new(name, vertices, faces))
+}
+
+/// Binary files may also start with "solid", so trust the facet count when
+/// it accounts for the file size exactly.
+fn is_binary(bytes: &[u8]) -> bool {
+    if let Some(count) = facet_count(bytes) {
+        if HEADER_LEN as u64 + 4 + count as u64 * FACET_LEN as u64 == bytes.len() as u64 {
+            return true;
+        }
+    }
+    !bytes.trim_ascii_start().starts_with(b"solid")
+}
+
+fn facet_count(bytes: &[u8]) -> Option<u32> {
+    let count = bytes.get(HEADER_LEN..HEADER_LEN + 4)?;
+    Some(u32::from_le_bytes(count.try_into().unwrap()))
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Vec<Facet>, LoadError> {
+    let count = facet_count(bytes).ok_or(LoadError::MalformedHeader)? as usize;
+    let body = &bytes[HEADER_LEN + 4..];
+    // Trailing bytes past the last facet are tolerated, missing ones aren't
+    if body.len() / FACET_LEN < count {
+        return Err(LoadError::Truncated);
+    }
+
+    let f32_at =
+        |chunk: &[u8], i: usize| f32::from_le_bytes(chunk[i * 4..i * 4 + 4].try_into().unwrap());
+    let vec3_at = |chunk: &[u8], i: usize| {
+        Vec3::new(f32_at(chunk, i), f32_at(chunk, i + 1), f32_at(chunk, i + 2))
+    };
+    Ok(body
+        .chunks_exact(FACET_LEN)
+        .take(count)
+        .map(|chunk| Facet {
+            normal: vec3_at(chunk, 0),
+            corners: [vec3_at(chunk, 3), vec3_at(chunk, 6), vec3_at(chunk, 9)],
+        })
+        .collect())
+}
+
+/// Parse the `solid` / `facet normal` / `vertex` / `endfacet` / `endsolid`
+/// keywords line by line. `outer loop` and `endloop` carry nothing and
+/// aren't checked.
+fn parse_ascii(bytes: &[u8]) -> Result<(Option<String>, Vec<Facet>), LoadError> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut name = None;
+    let mut facets = Vec::new();
+    // The facet being read and how many corners it has so far
+    let mut open: Option<(Facet, usize)> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid = LoadError::InvalidStl { line: line_number };
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let vec3 = |words: &mut std::str::SplitWhitespace| -> Result<Vec3, LoadError> {
+            let mut component = || {
+                words
+                    .next()
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .ok_or(LoadError::InvalidStl { line: line_number })
+            };
+            Ok(Vec3::new(component()?, component()?, component()?))
+        };
+
+        match keyword {
+            "solid" => {
+                if facets.is_empty() && open.is_none() {
+                    let rest = line.trim_start()["solid".len()..].trim();
+                    name = (!rest.is_empty()).then(|| rest.to_string());
+                }
+            }
+            "facet" => {
+                if open.is_some() || words.next() != Some("normal") {
+                    return Err(invalid);
+                }
+                let normal = vec3(&mut words)?;
+                let facet = Facet {
+                    normal,
+                    corners: [Vec3::ZERO; 3],
+                };
+                open = Some((facet, 0));
+            }
+            "vertex" => match &mut open {
+                Some((facet, corners)) if *corners < 3 => {
+                    facet.corners[*corners] = vec3(&mut words)?;
+                    *corners += 1;
+                }
+                _ => return Err(invalid),
+            },
+            "endfacet" => match open.take() {
+                Some((facet, 3)) => facets.push(facet),
+                _ => return Err(invalid),
+            },
+            "outer" | "endloop" => {}
+            "endsolid" => {
+                if open.is_some() {
+                    return Err(invalid);
+                }
+                return Ok((name, facets));
+            }
+            _ => return Err(invalid),
+        }
+    }
+
+    // Ran out of lines before `endsolid`
+    if facets.is_empty() && open.is_none() && name.is_none() {
+        return Err(LoadError::MalformedHeader);
+    }
+    Err(LoadError::Truncated)
+}
+
+/// The facet's stored normal if it's usable, otherwise the one computed
+/// from its corners (zero for a degenerate facet).
+fn facet_normal(facet: &Facet) -> Vec3 {
+    let [a, b, c] = facet.corners;
+    let computed = (b - a).cross(c - a);
+    let computed = if computed.magnitude() > 0.0 {
+        computed.normalize()
+    } else {
+        Vec3::ZERO
+    };
+
+    let stored = facet.normal;
+    let unit = (stored.magnitude() - 1.0).abs() < 1e-2;
+    if unit && stored.dot(computed) >= 0.0 {
+        stored.normalize()
+    } else {
+        computed
+    }
+}
+
+/// Merge corners at identical positions whose facets meet within
+/// `crease_angle` radians, averaging their normals.
+fn weld(facets: &[Facet], crease_angle: f32) -> (Vec<Vertex>, Vec<Face>) {
+    let cos_crease = crease_angle.cos();
+    let normals: Vec<Vec3> = facets.iter().map(facet_normal).collect();
+
+    // Per distinct position, the vertices made there so far, each with the
+    // normal of the facet that started it to compare later facets against
+    let mut at_position: HashMap<[u32; 3], Vec<(u32, Vec3)>> = HashMap::new();
+    let mut vertices: Vec<Vertex> = Vec::new();
+    // Running sum of facet normals per vertex
+    let mut normal_sums: Vec<Vec3> = Vec::new();
+    let mut faces = Vec::with_capacity(facets.len());
+
+    for (facet, &normal) in facets.iter().zip(&normals) {
+        let mut indices = [0u32; 3];
+        for (slot, &position) in indices.iter_mut().zip(&facet.corners) {
+            let candidates = at_position.entry(position_key(position)).or_default();
+            let existing = candidates
+                .iter()
+                .find(|(_, seed)| seed.dot(normal) >= cos_crease)
+                .map(|&(index, _)| index);
+            let index = existing.unwrap_or_else(|| {
+                let index = vertices.len() as u32;
+                vertices.push(Vertex {
+                    position,
+                    normal: Vec3::ZERO,
+                    texel: Vec2::ZERO,
+                    color: 0xFFFFFFFF,
+                    tangent: Vec4::ZERO,
+                });
+                normal_sums.push(Vec3::ZERO);
+                candidates.push((index, normal));
+                index
+            });
+            normal_sums[index as usize] = normal_sums[index as usize] + normal;
+            *slot = index;
+        }
+        faces.push(Face::new(indices[0], indices[1], indices[2]));
+    }
+
+    for (vertex, sum) in vertices.iter_mut().zip(normal_sums) {
+        if sum.magnitude() > 0.0 {
+            vertex.normal = sum.normalize();
+        }
+    }
+    (vertices, faces)
+}
+
+/// Hashable bit pattern of a position, with -0.0 folded into 0.0.
+fn position_key(p: Vec3) -> [u32; 3] {
+    [
+        (p.x + 0.0).to_bits(),
+        (p.y + 0.0).to_bits(),
+        (p.z + 0.0).to_bits(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const RIGHT_ANGLE_ASCII: &str = "\
+solid corner
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 -1
+      vertex 1 0 0
+    endloop
+  endfacet
+endsolid corner
+";
+
+    fn binary(facets: &[([f32; 3], [[f32; 3]; 3])], header: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![b' '; HEADER_LEN];
+        bytes[..header.len()].copy_from_slice(header);
+        bytes.extend_from_slice(&(facets.len() as u32).to_le_bytes());
+        for (normal, corners) in facets {
+            for v in std::iter::once(normal).chain(corners) {
+                for c in v {
+                    bytes.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Two triangles of a flat square, sharing the diagonal
+    fn square() -> Vec<([f32; 3], [[f32; 3]; 3])> {
+        vec![
+            (
+                [0.0, 0.0, 1.0],
+                [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            ),
+            (
+                [0.0, 0.0, 1.0],
+                [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+            ),
+        ]
+    }
+
+    #[test]
+    fn ascii_keeps_hard_edges_apart() {
+        let mesh = parse("file", RIGHT_ANGLE_ASCII.as_bytes(), DEFAULT_CREASE_ANGLE).unwrap();
+        assert_eq!(mesh.name(), "corner");
+        assert_eq!(mesh.face_count(), 2);
+        // The two facets meet at 90 degrees, so the shared edge is doubled
+        assert_eq!(mesh.vertex_count(), 6);
+    }
+
+    #[test]
+    fn wide_crease_angle_smooths_across_the_edge() {
+        let mesh = parse("file", RIGHT_ANGLE_ASCII.as_bytes(), 2.0).unwrap();
+        assert_eq!(mesh.vertex_count(), 4);
+        let shared = mesh.vertices()[0].normal;
+        let expected = Vec3::new(0.0, -1.0, 1.0).normalize();
+        assert_relative_eq!(shared.y, expected.y, epsilon = 1e-6);
+        assert_relative_eq!(shared.z, expected.z, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn binary_welds_shared_corners() {
+        let mesh = parse(
+            "square",
+            &binary(&square(), b"binary"),
+            DEFAULT_CREASE_ANGLE,
+        )
+        .unwrap();
+        assert_eq!(mesh.name(), "square");
+        assert_eq!(mesh.face_count(), 2);
+        assert_eq!(mesh.vertex_count(), 4);
+    }
+
+    #[test]
+    fn binary_header_may_start_with_solid() {
+        let mesh = parse("square", &binary(&square(), b"solid exported"), 0.5).unwrap();
+        assert_eq!(mesh.vertex_count(), 4);
+    }
+
+    #[test]
+    fn garbage_normals_are_recomputed() {
+        let mut facets = square();
+        facets[0].0 = [0.0, 0.0, 0.0];
+        facets[1].0 = [0.0, 0.0, -1.0];
+        let mesh = parse("square", &binary(&facets, b""), DEFAULT_CREASE_ANGLE).unwrap();
+        for vertex in mesh.vertices() {
+            assert_relative_eq!(vertex.normal.z, 1.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn truncated_files_are_rejected() {
+        let bytes = binary(&square(), b"");
+        let cut = &bytes[..bytes.len() - 10];
+        assert!(matches!(
+            parse("cut", cut, DEFAULT_CREASE_ANGLE),
+            Err(LoadError::Truncated)
+        ));
+
+        let ascii = &RIGHT_ANGLE_ASCII[..RIGHT_ANGLE_ASCII.find("endloop").unwrap()];
+        assert!(matches!(
+            parse("cut", ascii.as_bytes(), DEFAULT_CREASE_ANGLE),
+            Err(LoadError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn short_or_garbled_files_are_rejected() {
+        assert!(matches!(
+            parse("short", b"not an stl", DEFAULT_CREASE_ANGLE),
+            Err(LoadError::MalformedHeader)
+        ));
+        assert!(matches!(
+            parse(
+                "bad",
+                b"solid x\n  facet normal 0 0 q\n",
+                DEFAULT_CREASE_ANGLE
+            ),
+            Err(LoadError::InvalidStl { line: 2 })
+        ));
+        assert!(matches!(
+            parse("empty", &binary(&[], b""), DEFAULT_CREASE_ANGLE),
+            Err(LoadError::NoVertices)
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+//! Loop subdivision: every triangle split into four, with the vertices
+//! pulled toward a smooth limit surface.
+//!
+//! The surface's topology comes from vertex positions rather than indices,
+//! so a mesh split into separate vertices at UV or normal seams still
+//! subdivides as one piece, with the seam keeping its UVs on both sides.
+//! See [`Mesh::subdivide_loop`](crate::mesh::Mesh::subdivide_loop).
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::colors::{from_rgb_f32, to_rgb_f32};
+use crate::math::{vec3::Vec3, vec4::Vec4};
+use crate::mesh::{Face, Vertex};
+
+/// One welded edge, smaller point first.
+type Edge = (usize, usize);
+
+fn edge(a: usize, b: usize) -> Edge {
+    (a.min(b), a.max(b))
+}
+
+/// Each vertex's point, merging vertices at the same position, and the
+/// number of points.
+fn weld(vertices: &[Vertex]) -> (Vec<usize>, usize) {
+    let mut points = HashMap::new();
+    let point_of = vertices
+        .iter()
+        .map(|v| {
+            // Adding zero turns -0.0 into 0.0, so the two weld
+            let p = v.position;
+            let key = [p.x + 0.0, p.y + 0.0, p.z + 0.0].map(f32::to_bits);
+            let next = points.len();
+            *points.entry(key).or_insert(next)
+        })
+        .collect();
+    (point_of, points.len())
+}
+
+/// The vertex halfway along the edge from `a` to `b`, apart from its
+/// position.
+fn halfway(a: &Vertex, b: &Vertex) -> Vertex {
+    let normal = a.normal + b.normal;
+    let ([r1, g1, b1], [r2, g2, b2]) = (to_rgb_f32(a.color), to_rgb_f32(b.color));
+    Vertex {
+        position: (a.position + b.position) * 0.5,
+        normal: if normal.magnitude() > 0.0 {
+            normal.normalize()
+        } else {
+            Vec3::ZERO
+        },
+        texel: (a.texel + b.texel) * 0.5,
+        color: from_rgb_f32([(r1 + r2) * 0.5, (g1 + g2) * 0.5, (b1 + b2) * 0.5]),
+        tangent: Vec4::ZERO,
+    }
+}
+
+/// One round of Loop subdivision.
+///
+/// Returns the new vertices and faces, four per face, and how many edges
+/// more than two faces share. Those can't be smoothed: they're split at
+/// their midpoint and their ends stay where they are. Open boundaries
+/// use the boundary rules, so they stay curves rather than shrinking
+/// inward. Faces with a corner past the end of `vertices`, or two corners
+/// at the same position, are dropped.
+pub(crate) fn subdivide(vertices: &[Vertex], faces: &[Face]) -> (Vec<Vertex>, Vec<Face>, usize) {
+    let (point_of, points) = weld(vertices);
+    let mut positions = vec![Vec3::ZERO; points];
+    for (vertex, &point) in vertices.iter().zip(&point_of) {
+        positions[point] = vertex.position;
+    }
+
+    let faces: Vec<[usize; 3]> = faces
+        .iter()
+        .map(|f| [f.a, f.b, f.c].map(|i| i as usize))
+        .filter(|corners| {
+            let [Some(a), Some(b), Some(c)] = corners.map(|i| point_of.get(i).copied()) else {
+                return false;
+            };
+            a != b && b != c && a != c
+        })
+        .collect();
+
+    // The corner opposite each edge in every face around it. Ordered, so
+    // the sums below come out the same on every run
+    let mut edges: BTreeMap<Edge, Vec<usize>> = BTreeMap::new();
+    for corners in &faces {
+        let [a, b, c] = corners.map(|i| point_of[i]);
+        for (from, to, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+            edges.entry(edge(from, to)).or_default().push(opposite);
+        }
+    }
+
+    let mut neighbours = vec![Vec::new(); points];
+    let mut boundary = vec![Vec::new(); points];
+    let mut pinned = vec![false; points];
+    let mut non_manifold = 0;
+    for (&(a, b), opposite) in &edges {
+        neighbours[a].push(b);
+        neighbours[b].push(a);
+        match opposite.len() {
+            1 => {
+                boundary[a].push(b);
+                boundary[b].push(a);
+            }
+            2 => {}
+            _ => {
+                non_manifold += 1;
+                pinned[a] = true;
+                pinned[b] = true;
+            }
+        }
+    }
+
+    // Original points, moved toward their neighbours
+    let smoothed: Vec<Vec3> = (0..points)
+        .map(|p| {
+            let position = positions[p];
+            if pinned[p] {
+                return position;
+            }
+            match boundary[p][..] {
+                [] if !neighbours[p].is_empty() => {
+                    let n = neighbours[p].len();
+                    let beta = if n == 3 {
+                        3.0 / 16.0
+                    } else {
+                        3.0 / (8.0 * n as f32)
+                    };
+                    let sum = neighbours[p]
+                        .iter()
+                        .fold(Vec3::ZERO, |sum, &q| sum + positions[q]);
+                    position * (1.0 - n as f32 * beta) + sum * beta
+                }
+                [q, r] => position * 0.75 + (positions[q] + positions[r]) * 0.125,
+                // Where several boundaries meet, or unused
+                _ => position,
+            }
+        })
+        .collect();
+
+    // New points on the edges, weighted toward the faces on both sides
+    let edge_position = |(a, b): Edge| {
+        let ends = positions[a] + positions[b];
+        match edges[&(a, b)][..] {
+            [c, d] => ends * 0.375 + (positions[c] + positions[d]) * 0.125,
+            _ => ends * 0.5,
+        }
+    };
+
+    let mut out_vertices: Vec<Vertex> = vertices
+        .iter()
+        .zip(&point_of)
+        .map(|(vertex, &point)| Vertex {
+            position: smoothed[point],
+            ..*vertex
+        })
+        .collect();
+    // One new vertex per edge between two vertices, so a seam gets one on
+    // each side, at the same position
+    let mut midpoints: HashMap<Edge, u32> = HashMap::new();
+    let mut midpoint = |i: usize, j: usize| {
+        *midpoints.entry(edge(i, j)).or_insert_with(|| {
+            out_vertices.push(Vertex {
+                position: edge_position(edge(point_of[i], point_of[j])),
+                ..halfway(&vertices[i], &vertices[j])
+            });
+            out_vertices.len() as u32 - 1
+        })
+    };
+
+    let mut out_faces = Vec::with_capacity(faces.len() * 4);
+    for &[a, b, c] in &faces {
+        let (ab, bc, ca) = (midpoint(a, b), midpoint(b, c), midpoint(c, a));
+        let [a, b, c] = [a, b, c].map(|i| i as u32);
+        out_faces.extend([
+            Face::new(a, ab, ca),
+            Face::new(ab, b, bc),
+            Face::new(ca, bc, c),
+            Face::new(ab, bc, ca),
+        ]);
+    }
+    (out_vertices, out_faces, non_manifold)
+}
+
+/// Give every vertex the area-weighted average normal of the faces around
+/// its position, shared across seams so a subdivided surface shades
+/// smoothly everywhere. Vertices no face uses keep their normal.
+pub(crate) fn smooth_normals(vertices: &mut [Vertex], faces: &[Face]) {
+    let (point_of, points) = weld(vertices);
+    let mut normals = vec![Vec3::ZERO; points];
+    for face in faces {
+        let [Some(a), Some(b), Some(c)] =
+            [face.a, face.b, face.c].map(|i| vertices.get(i as usize).map(|v| v.position))
+        else {
+            continue;
+        };
+        // The cross product's length is twice the area, weighting it
+        let normal = (b - a).cross(c - a);
+        for index in [face.a, face.b, face.c] {
+            let p = point_of[index as usize];
+            normals[p] = normals[p] + normal;
+        }
+    }
+    for (vertex, &point) in vertices.iter_mut().zip(&point_of) {
+        if normals[point].magnitude() > 0.0 {
+            vertex.normal = normals[point].normalize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::vec2::Vec2;
+
+    fn vertex(x: f32, y: f32, z: f32) -> Vertex {
+        Vertex {
+            position: Vec3::new(x, y, z),
+            normal: Vec3::ZERO,
+            texel: Vec2::new(x, y),
+            color: 0xFFFFFFFF,
+            tangent: Vec4::ZERO,
+        }
+    }
+
+    /// A tetrahedron, closed and wound consistently.
+    fn tetrahedron() -> (Vec<Vertex>, Vec<Face>) {
+        let vertices = vec![
+            vertex(1.0, 1.0, 1.0),
+            vertex(1.0, -1.0, -1.0),
+            vertex(-1.0, 1.0, -1.0),
+            vertex(-1.0, -1.0, 1.0),
+        ];
+        let faces = vec![
+            Face::new(0, 1, 2),
+            Face::new(0, 3, 1),
+            Face::new(0, 2, 3),
+            Face::new(1, 3, 2),
+        ];
+        (vertices, faces)
+    }
+
+    #[test]
+    fn closed_surfaces_gain_one_vertex_per_edge() {
+        let (vertices, faces) = tetrahedron();
+        let (vertices, faces, non_manifold) = subdivide(&vertices, &faces);
+        assert_eq!((vertices.len(), faces.len(), non_manifold), (10, 16, 0));
+        // Corners pull in: 1 - 3 * 3/16 of the corner plus 3/16 of each neighbour
+        assert!((vertices[0].position - Vec3::new(0.25, 0.25, 0.25)).magnitude() < 1e-6);
+        assert!(faces.iter().all(|f| [f.a, f.b, f.c]
+            .iter()
+            .all(|&i| (i as usize) < vertices.len())));
+    }
+
+    #[test]
+    fn seams_subdivide_as_one_surface() {
+        let (mut vertices, mut faces) = tetrahedron();
+        // Split vertex 0 for the last face, as a UV seam would
+        vertices.push(Vertex {
+            texel: Vec2::new(5.0, 5.0),
+            ..vertices[0]
+        });
+        faces[2] = Face::new(4, 2, 3);
+
+        let (out, _, non_manifold) = subdivide(&vertices, &faces);
+        assert_eq!(non_manifold, 0);
+        // Both copies move the same way, keeping their own UVs
+        assert_eq!(out[0].position, out[4].position);
+        assert_eq!(out[4].texel, Vec2::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn boundaries_and_non_manifold_edges_fall_back_to_midpoints() {
+        // Three triangles sharing the edge from 0 to 1
+        let vertices = vec![
+            vertex(0.0, 0.0, 0.0),
+            vertex(0.0, 2.0, 0.0),
+            vertex(2.0, 1.0, 0.0),
+            vertex(-2.0, 1.0, 0.0),
+            vertex(0.0, 1.0, 2.0),
+        ];
+        let faces = vec![Face::new(0, 1, 2), Face::new(1, 0, 3), Face::new(0, 1, 4)];
+        let (out, out_faces, non_manifold) = subdivide(&vertices, &faces);
+        assert_eq!((non_manifold, out_faces.len()), (1, 12));
+        // The shared edge's ends stay, and its midpoint is plain
+        assert_eq!(out[0].position, vertices[0].position);
+        assert_eq!(out[1].position, vertices[1].position);
+        assert_eq!(
+            out[out_faces[0].b as usize].position,
+            Vec3::new(0.0, 1.0, 0.0)
+        );
+        // A boundary edge's new point is its midpoint too
+        assert_eq!(
+            out[out_faces[1].c as usize].position,
+            Vec3::new(1.0, 1.5, 0.0)
+        );
+    }
+}
@@ -1,16 +1,244 @@
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::math::vec3::Vec3;
+
+/// A rectangle of a texture that a mesh's UVs map onto, so several
+/// materials can share one atlas image.
+///
+/// Given in normalized UVs with the OBJ convention (v up from the bottom
+/// edge): UV (0, 0) lands on `(u_offset, v_offset)` and (1, 1) on the
+/// opposite corner. Build one from a pixel rectangle with
+/// [`Texture::region`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureRegion {
+    pub u_offset: f32,
+    pub v_offset: f32,
+    pub u_scale: f32,
+    pub v_scale: f32,
+}
+
+impl TextureRegion {
+    /// The whole texture; sampling through it matches [`Texture::sample`].
+    pub const FULL: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+
+    pub const fn new(u_offset: f32, v_offset: f32, u_scale: f32, v_scale: f32) -> Self {
+        Self {
+            u_offset,
+            v_offset,
+            u_scale,
+            v_scale,
+        }
+    }
+}
+
+impl Default for TextureRegion {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// How [`Texture::sample`] treats UVs outside [0, 1], set per axis with
+/// [`Texture::set_address_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressMode {
+    /// Tile the texture (the default).
+    #[default]
+    Repeat,
+    /// Stretch the edge texels outward, so nothing from the opposite edge
+    /// bleeds in — the mode for decals and other non-tiling images.
+    ClampToEdge,
+    /// Tile, flipping every other copy so neighbouring tiles meet seamlessly.
+    MirrorRepeat,
+    /// A constant ARGB color outside the texture.
+    Border(u32),
+}
+
+impl AddressMode {
+    /// Map texel index `i`, which may lie outside `0..size`, to the texel
+    /// it reads, or `None` where the border color shows instead.
+    fn texel(self, i: i64, size: u32) -> Option<u32> {
+        let size = size as i64;
+        let i = match self {
+            AddressMode::Repeat => i.rem_euclid(size),
+            AddressMode::ClampToEdge => i.clamp(0, size - 1),
+            AddressMode::MirrorRepeat => {
+                let i = i.rem_euclid(2 * size);
+                if i < size {
+                    i
+                } else {
+                    2 * size - 1 - i
+                }
+            }
+            AddressMode::Border(_) if !(0..size).contains(&i) => return None,
+            AddressMode::Border(_) => i,
+        };
+        Some(i as u32)
+    }
+
+    /// Bring a UV coordinate into [0, 1] per the mode, or `None` outside
+    /// the texture in [`AddressMode::Border`].
+    fn wrap(self, t: f32) -> Option<f32> {
+        match self {
+            AddressMode::Repeat => Some(t.rem_euclid(1.0)),
+            AddressMode::ClampToEdge => Some(t.clamp(0.0, 1.0)),
+            AddressMode::MirrorRepeat => {
+                let t = t.rem_euclid(2.0);
+                Some(if t > 1.0 { 2.0 - t } else { t })
+            }
+            AddressMode::Border(_) => (0.0..=1.0).contains(&t).then_some(t),
+        }
+    }
+
+    fn border_color(self) -> u32 {
+        match self {
+            AddressMode::Border(color) => color,
+            _ => 0,
+        }
+    }
+}
+
+/// How [`Texture::sample`] reconstructs the image between texel centers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextureFilter {
+    /// The texel the UV falls in (the default).
+    #[default]
+    Nearest,
+    /// Weighted blend of the four texels around the UV.
+    Bilinear,
+}
+
+/// Why [`Texture::update_from_argb`] or [`Texture::update_region`]
+/// rejected new pixels. The texture is left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextureUpdateError {
+    /// The pixel slice doesn't hold exactly one pixel per texel updated.
+    SizeMismatch { expected: usize, actual: usize },
+    /// The rectangle runs past the texture's edges.
+    OutOfBounds {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for TextureUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextureUpdateError::SizeMismatch { expected, actual } => {
+                write!(f, "expected {} pixels, got {}", expected, actual)
+            }
+            TextureUpdateError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "{}x{} region at ({}, {}) runs past the texture",
+                width, height, x, y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TextureUpdateError {}
+
+/// Processing [`Texture::from_file_with`] applies to an image after
+/// loading it. The default leaves it as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextureLoadOptions {
+    /// Texels to grow every UV island by with [`Texture::dilate_borders`],
+    /// so bilinear filtering at island edges doesn't pick up the empty
+    /// texels around them; 0 skips the pass.
+    pub dilate_borders: u32,
+}
+
+/// Texels of an opaque image darker than this luminance (out of 255) count
+/// as empty for [`Texture::dilate_borders`]. Above 0, as exporters pad with
+/// black that lossy compression then speckles.
+const DILATE_BLACK_LEVEL: f32 = 4.0;
 
 /// Represents a 2D texture for texture mapping.
 pub struct Texture {
     data: Vec<u32>, // The pixel data of the texture in ARGB format.
     width: u32,     // The width of the texture in pixels.
     height: u32,    // The height of the texture in pixels.
+    /// The image file it was loaded from, if any.
+    source: Option<PathBuf>,
+    address_u: AddressMode,
+    address_v: AddressMode,
+    filter: TextureFilter,
+    load_options: TextureLoadOptions,
 }
 
 impl Texture {
+    /// Wrap ARGB pixels already in memory, row-major from the top-left.
+    ///
+    /// Fails with [`Error::InvalidArgument`] if either dimension is 0 or
+    /// `data.len() != width * height`.
+    pub fn from_pixels(data: Vec<u32>, width: u32, height: u32) -> Result<Self, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidArgument(
+                "texture dimensions must be non-zero",
+            ));
+        }
+        if data.len() as u64 != width as u64 * height as u64 {
+            return Err(Error::InvalidArgument(
+                "pixel data doesn't match texture dimensions",
+            ));
+        }
+        Ok(Self {
+            data,
+            width,
+            height,
+            source: None,
+            address_u: AddressMode::default(),
+            address_v: AddressMode::default(),
+            filter: TextureFilter::default(),
+            load_options: TextureLoadOptions::default(),
+        })
+    }
+
+    /// An opaque black texture to fill in later with
+    /// [`Texture::update_from_argb`] or [`Texture::update_region`].
+    ///
+    /// Fails with [`Error::InvalidArgument`] if either dimension is 0.
+    pub fn new(width: u32, height: u32) -> Result<Self, Error> {
+        Self::from_fn(width, height, |_, _| 0xFF000000)
+    }
+
+    /// Generate a texture procedurally: `f(x, y)` gives the ARGB pixel at
+    /// column `x`, row `y` from the top-left.
+    ///
+    /// Fails with [`Error::InvalidArgument`] if either dimension is 0.
+    pub fn from_fn(
+        width: u32,
+        height: u32,
+        mut f: impl FnMut(u32, u32) -> u32,
+    ) -> Result<Self, Error> {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self::from_pixels(data, width, height)
+    }
+
     // Load a texture from an image file (PNG, JPG, etc.)
-    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, image::ImageError> {
-        let img = image::open(path)?.to_rgba8();
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_file_with(path, TextureLoadOptions::default())
+    }
+
+    /// Load a texture from an image file like [`Texture::from_file`], then
+    /// process it as `options` ask. Hot reloading loads it again the same
+    /// way.
+    pub fn from_file_with<P: AsRef<Path>>(
+        path: P,
+        options: TextureLoadOptions,
+    ) -> Result<Self, Error> {
+        let img = image::open(path.as_ref())?.to_rgba8();
         let (width, height) = img.dimensions();
 
         // Convert RGBA bytes to ARGB u32
@@ -22,14 +250,172 @@ impl Texture {
             })
             .collect();
 
-        Ok(Self {
+        let mut texture = Self {
             data,
             width,
             height,
-        })
+            source: Some(path.as_ref().to_path_buf()),
+            address_u: AddressMode::default(),
+            address_v: AddressMode::default(),
+            filter: TextureFilter::default(),
+            load_options: options,
+        };
+        texture.dilate_borders(options.dilate_borders);
+        Ok(texture)
+    }
+
+    /// The options the texture was loaded with; the default for textures
+    /// not loaded from a file.
+    pub fn load_options(&self) -> TextureLoadOptions {
+        self.load_options
+    }
+
+    /// The image file this texture was loaded from, if any.
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    /// Replace every pixel, row-major from the top-left, in place.
+    ///
+    /// Cheap enough to call every frame for procedural or video textures:
+    /// the pixels are copied into the existing storage.
+    pub fn update_from_argb(&mut self, pixels: &[u32]) -> Result<(), TextureUpdateError> {
+        if pixels.len() != self.data.len() {
+            return Err(TextureUpdateError::SizeMismatch {
+                expected: self.data.len(),
+                actual: pixels.len(),
+            });
+        }
+        self.data.copy_from_slice(pixels);
+        Ok(())
+    }
+
+    /// Replace the `width` x `height` pixels whose top-left corner is
+    /// pixel (`x`, `y`) with `pixels`, row-major, in place.
+    pub fn update_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        pixels: &[u32],
+    ) -> Result<(), TextureUpdateError> {
+        let fits = |start: u32, extent: u32, size: u32| {
+            start.checked_add(extent).is_some_and(|end| end <= size)
+        };
+        if !fits(x, width, self.width) || !fits(y, height, self.height) {
+            return Err(TextureUpdateError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            });
+        }
+        let expected = width as usize * height as usize;
+        if pixels.len() != expected {
+            return Err(TextureUpdateError::SizeMismatch {
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        if width == 0 {
+            return Ok(());
+        }
+        for (row, src) in pixels.chunks_exact(width as usize).enumerate() {
+            let start = (y as usize + row) * self.width as usize + x as usize;
+            self.data[start..start + width as usize].copy_from_slice(src);
+        }
+        Ok(())
+    }
+
+    /// Grow the filled parts of the image (the UV islands) outward by
+    /// `iterations` texels, so bilinear filtering and mipmapping at island
+    /// edges blend in the island's own colors rather than the empty
+    /// padding around it. Returns how many texels were filled.
+    ///
+    /// With any texel not fully opaque, the empty texels are the fully
+    /// transparent ones; in an opaque image, the black ones. Each pass
+    /// gives every empty texel next to a filled one (diagonals included)
+    /// the average color of those neighbours, keeping its own alpha, so
+    /// cutouts stay transparent. Filled texels are never changed.
+    pub fn dilate_borders(&mut self, iterations: u32) -> usize {
+        let has_alpha = self.data.iter().any(|&p| p >> 24 != 0xFF);
+        let mut filled: Vec<bool> = self
+            .data
+            .iter()
+            .map(|&p| {
+                if has_alpha {
+                    p >> 24 != 0
+                } else {
+                    let [r, g, b] = [16, 8, 0].map(|shift| ((p >> shift) & 0xFF) as f32);
+                    0.299 * r + 0.587 * g + 0.114 * b >= DILATE_BLACK_LEVEL
+                }
+            })
+            .collect();
+
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut total = 0;
+        let mut ring = Vec::new();
+        for _ in 0..iterations {
+            // Colors for this ring, from texels filled before it
+            ring.clear();
+            for y in 0..height {
+                for x in 0..width {
+                    if filled[y * width + x] {
+                        continue;
+                    }
+                    let mut sum = [0; 3];
+                    let mut count = 0;
+                    for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                        for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                            let i = ny * width + nx;
+                            if filled[i] {
+                                for (channel, shift) in sum.iter_mut().zip([16, 8, 0]) {
+                                    *channel += (self.data[i] >> shift) & 0xFF;
+                                }
+                                count += 1;
+                            }
+                        }
+                    }
+                    if count > 0 {
+                        let [r, g, b] = sum.map(|c| (c + count / 2) / count);
+                        ring.push((y * width + x, (r << 16) | (g << 8) | b));
+                    }
+                }
+            }
+            if ring.is_empty() {
+                break;
+            }
+            for &(i, rgb) in &ring {
+                self.data[i] = (self.data[i] & 0xFF000000) | rgb;
+                filled[i] = true;
+            }
+            total += ring.len();
+        }
+        total
+    }
+
+    /// Set how UVs outside [0, 1] are handled along u and v.
+    pub fn set_address_mode(&mut self, u: AddressMode, v: AddressMode) {
+        self.address_u = u;
+        self.address_v = v;
+    }
+
+    /// The address modes along u and v.
+    pub fn address_mode(&self) -> (AddressMode, AddressMode) {
+        (self.address_u, self.address_v)
+    }
+
+    pub fn set_filter(&mut self, filter: TextureFilter) {
+        self.filter = filter;
+    }
+
+    pub fn filter(&self) -> TextureFilter {
+        self.filter
     }
 
-    /// Sample the texture at UV coordinates using nearest-neighbor filtering.
+    /// Sample the texture at UV coordinates with its filter and address
+    /// modes.
     ///
     /// # UV Coordinate Convention
     /// - UV coordinates are in [0,1] range
@@ -37,24 +423,127 @@ impl Texture {
     /// - We flip V to correct for this: v_corrected = 1.0 - v
     ///
     /// # Wrapping
-    /// Uses repeat/wrap mode via rem_euclid for UVs outside [0,1]
+    /// UVs outside [0,1] follow [`Texture::address_mode`]; the default
+    /// repeats.
     #[inline]
     pub fn sample(&self, u: f32, v: f32) -> u32 {
-        // Wrap UV coordinates to [0, 1) range using rem_euclid
-        // (handles negative values correctly, unlike % operator)
-        let u = u.rem_euclid(1.0);
+        match self.filter {
+            TextureFilter::Nearest => self.sample_nearest(u, v),
+            TextureFilter::Bilinear => self.sample_bilinear(u, v),
+        }
+    }
 
+    #[inline]
+    fn sample_nearest(&self, u: f32, v: f32) -> u32 {
         // Flip V: OBJ uses bottom-left origin, textures use top-left
-        let v = (1.0 - v).rem_euclid(1.0);
+        let v = 1.0 - v;
+        let x = self.address_u.wrap(u).map(|u| pixel(u, self.width));
+        let y = self.address_v.wrap(v).map(|v| pixel(v, self.height));
+        match (x, y) {
+            // Sample from flat array: index = y * width + x
+            (Some(x), Some(y)) => self.data[(y * self.width + x) as usize],
+            (None, _) => self.address_u.border_color(),
+            (_, None) => self.address_v.border_color(),
+        }
+    }
+
+    /// Blend the four texels whose centers surround the UV, picking the
+    /// neighbours across each edge by that axis's address mode.
+    #[inline]
+    fn sample_bilinear(&self, u: f32, v: f32) -> u32 {
+        // Texel centers sit at half-integer pixel coordinates
+        let x = u * self.width as f32 - 0.5;
+        let y = (1.0 - v) * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let texel = |i: i64, j: i64| {
+            let x = self.address_u.texel(i, self.width);
+            let y = self.address_v.texel(j, self.height);
+            match (x, y) {
+                (Some(x), Some(y)) => self.data[(y * self.width + x) as usize],
+                (None, _) => self.address_u.border_color(),
+                (_, None) => self.address_v.border_color(),
+            }
+        };
+        let weights = [
+            (1.0 - fx) * (1.0 - fy),
+            fx * (1.0 - fy),
+            (1.0 - fx) * fy,
+            fx * fy,
+        ];
+        let texels = [
+            texel(x0, y0),
+            texel(x0 + 1, y0),
+            texel(x0, y0 + 1),
+            texel(x0 + 1, y0 + 1),
+        ];
+
+        // Blend each 8-bit channel, alpha included
+        (0..4).fold(0, |color, channel| {
+            let shift = channel * 8;
+            let value: f32 = texels
+                .iter()
+                .zip(weights)
+                .map(|(&t, w)| ((t >> shift) & 0xFF) as f32 * w)
+                .sum();
+            color | ((value.round() as u32).min(0xFF) << shift)
+        })
+    }
+
+    /// Sample like [`Texture::sample`], with the UV square mapped onto
+    /// `region`.
+    ///
+    /// UVs follow the address modes within the region, and the texel is
+    /// clamped to the region's own pixels, so however far the UVs run past
+    /// its edges a sample never picks up a neighbouring atlas entry. Always
+    /// nearest-neighbor, whatever the texture's filter.
+    #[inline]
+    pub fn sample_region(&self, u: f32, v: f32, region: &TextureRegion) -> u32 {
+        let Some(u) = self.address_u.wrap(u) else {
+            return self.address_u.border_color();
+        };
+        let Some(v) = self.address_v.wrap(1.0 - v) else {
+            return self.address_v.border_color();
+        };
 
-        // Convert normalized [0,1) UV to pixel coordinates [0, width-1]
-        let x = ((u * self.width as f32) as u32).min(self.width - 1);
-        let y = ((v * self.height as f32) as u32).min(self.height - 1);
+        // Region edges in pixels, top-left origin
+        let (w, h) = (self.width as f32, self.height as f32);
+        let left = region.u_offset * w;
+        let top = (1.0 - region.v_offset - region.v_scale) * h;
+        let (x_min, x_max) = Self::pixel_span(left, region.u_scale * w, self.width);
+        let (y_min, y_max) = Self::pixel_span(top, region.v_scale * h, self.height);
 
-        // Sample from flat array: index = y * width + x
+        let x = ((left + u * region.u_scale * w) as u32).clamp(x_min, x_max);
+        let y = ((top + v * region.v_scale * h) as u32).clamp(y_min, y_max);
         self.data[(y * self.width + x) as usize]
     }
 
+    /// First and last pixel of the span `start..start + extent`, kept
+    /// inside `0..size` and at least one pixel wide.
+    fn pixel_span(start: f32, extent: f32, size: u32) -> (u32, u32) {
+        let first = (start.round().max(0.0) as u32).min(size - 1);
+        let last = ((start + extent).round() as u32).saturating_sub(1);
+        (first, last.clamp(first, size - 1))
+    }
+
+    /// The region covering the `width` x `height` pixels whose top-left
+    /// corner is pixel (`x`, `y`), clipped to the texture.
+    pub fn region(&self, x: u32, y: u32, width: u32, height: u32) -> TextureRegion {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+        let (w, h) = (self.width as f32, self.height as f32);
+        TextureRegion::new(
+            x as f32 / w,
+            1.0 - (y + height) as f32 / h,
+            width as f32 / w,
+            height as f32 / h,
+        )
+    }
+
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -62,3 +551,508 @@ impl Texture {
         self.height
     }
 }
+
+/// A texture added to the engine with [`Engine::add_texture`], for any
+/// number of meshes to draw with through [`Mesh::set_texture_handle`]
+/// without copying its pixels.
+///
+/// Handles outlive their texture safely: once [`Engine::remove_texture`]
+/// takes it away the handle resolves to nothing, even after the slot is
+/// reused, and meshes still holding it draw untextured.
+///
+/// [`Engine::add_texture`]: crate::engine::Engine::add_texture
+/// [`Engine::remove_texture`]: crate::engine::Engine::remove_texture
+/// [`Mesh::set_texture_handle`]: crate::Mesh::set_texture_handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureHandle {
+    index: u32,
+    generation: u32,
+}
+
+/// Textures owned by the engine and shared by handle.
+#[derive(Default)]
+pub(crate) struct TextureArena {
+    slots: Vec<TextureSlot>,
+    // Empty slots, reused before growing
+    free: Vec<u32>,
+}
+
+#[derive(Default)]
+struct TextureSlot {
+    // Bumped on removal, so handles to the old texture stop resolving
+    generation: u32,
+    texture: Option<Texture>,
+}
+
+impl TextureArena {
+    pub(crate) fn insert(&mut self, texture: Texture) -> TextureHandle {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.slots.push(TextureSlot::default());
+            (self.slots.len() - 1) as u32
+        });
+        let slot = &mut self.slots[index as usize];
+        slot.texture = Some(texture);
+        TextureHandle {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    pub(crate) fn remove(&mut self, handle: TextureHandle) -> Option<Texture> {
+        let slot = self.slot_mut(handle)?;
+        let texture = slot.texture.take();
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        texture
+    }
+
+    pub(crate) fn get(&self, handle: TextureHandle) -> Option<&Texture> {
+        self.slots
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.texture.as_ref())
+    }
+
+    pub(crate) fn get_mut(&mut self, handle: TextureHandle) -> Option<&mut Texture> {
+        self.slot_mut(handle)?.texture.as_mut()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Texture> {
+        self.slots.iter().filter_map(|slot| slot.texture.as_ref())
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Texture> {
+        self.slots
+            .iter_mut()
+            .filter_map(|slot| slot.texture.as_mut())
+    }
+
+    fn slot_mut(&mut self, handle: TextureHandle) -> Option<&mut TextureSlot> {
+        self.slots
+            .get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+    }
+}
+
+/// The texel a coordinate in [0, 1] falls in along an axis of `size`
+/// texels, with 1.0 landing on the last one.
+fn pixel(t: f32, size: u32) -> u32 {
+    ((t * size as f32) as u32).min(size - 1)
+}
+
+/// Map a view-space reflection vector to sphere-map UVs.
+///
+/// The sphere map is the image of a mirrored ball seen head-on by a camera
+/// looking down +z. The ball point that reflects the eye ray into `r` has
+/// normal proportional to `r - (0, 0, 1)`; its x/y, scaled to [0, 1], is
+/// the UV. `v` increases upward, matching [`Texture::sample`].
+pub(crate) fn sphere_map_uv(r: Vec3) -> (f32, f32) {
+    let m = 2.0 * (r.x * r.x + r.y * r.y + (r.z - 1.0) * (r.z - 1.0)).sqrt();
+    if m == 0.0 {
+        // Reflecting straight away from the viewer: the ball's far rim
+        return (0.5, 0.5);
+    }
+    (r.x / m + 0.5, r.y / m + 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn approx(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < EPSILON && (a.1 - b.1).abs() < EPSILON
+    }
+
+    #[test]
+    fn reflection_back_at_viewer_hits_map_center() {
+        // Looking down +z at a surface facing the camera bounces the ray back
+        let r = Vec3::new(0.0, 0.0, 1.0).reflect(Vec3::new(0.0, 0.0, -1.0));
+        assert!(approx(sphere_map_uv(r), (0.5, 0.5)));
+    }
+
+    #[test]
+    fn grazing_reflections_reach_the_rim() {
+        // A ray reflected sideways or upward lands halfway to the map edge
+        let right = sphere_map_uv(Vec3::new(1.0, 0.0, 0.0));
+        let up = sphere_map_uv(Vec3::new(0.0, 1.0, 0.0));
+        let half = 0.5 + 0.5 / 2f32.sqrt();
+        assert!(approx(right, (half, 0.5)));
+        assert!(approx(up, (0.5, half)));
+
+        // Nearly straight through the ball lands on the outer rim
+        let rim = sphere_map_uv(Vec3::new(0.0, 1e-3, 1.0).normalize());
+        assert!((rim.1 - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn reflect_mirrors_about_normal() {
+        let r = Vec3::new(1.0, -1.0, 0.0).reflect(Vec3::UP);
+        assert!((r - Vec3::new(1.0, 1.0, 0.0)).magnitude() < EPSILON);
+    }
+
+    #[test]
+    fn from_pixels_samples_top_row_at_high_v() {
+        // 2x2: top row red/green, bottom row blue/white
+        let texture =
+            Texture::from_pixels(vec![0xFFFF0000, 0xFF00FF00, 0xFF0000FF, 0xFFFFFFFF], 2, 2)
+                .unwrap();
+        assert_eq!(texture.sample(0.25, 0.75), 0xFFFF0000);
+        assert_eq!(texture.sample(0.75, 0.75), 0xFF00FF00);
+        assert_eq!(texture.sample(0.25, 0.25), 0xFF0000FF);
+    }
+
+    #[test]
+    fn from_fn_fills_row_major_from_top_left() {
+        let texture = Texture::from_fn(3, 2, |x, y| 0xFF000000 | (y << 8) | x).unwrap();
+        assert_eq!((texture.width(), texture.height()), (3, 2));
+        // Top-right texel, then bottom-left
+        assert_eq!(texture.sample(0.9, 0.9), 0xFF000002);
+        assert_eq!(texture.sample(0.1, 0.1), 0xFF000100);
+    }
+
+    /// 8x4 atlas: left half pixels tagged 0x10 + x, right half 0x20 + x,
+    /// with the row in the green byte
+    fn atlas() -> Texture {
+        Texture::from_fn(8, 4, |x, y| {
+            let tag = if x < 4 { 0x10 } else { 0x20 };
+            0xFF000000 | (y << 8) | (tag + x)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn full_region_matches_plain_sampling() {
+        let texture = atlas();
+        for i in -12..=12 {
+            for j in -12..=12 {
+                let (u, v) = (i as f32 * 0.17, j as f32 * 0.13);
+                assert_eq!(
+                    texture.sample_region(u, v, &TextureRegion::FULL),
+                    texture.sample(u, v)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn region_maps_pixel_rect_to_uvs() {
+        let texture = atlas();
+        // Right half, bottom three rows
+        let region = texture.region(4, 1, 4, 3);
+        assert_eq!(region, TextureRegion::new(0.5, 0.0, 0.5, 0.75));
+        // UV (0, 1) is the region's top-left pixel, (1, 0) its bottom-right
+        assert_eq!(texture.sample_region(0.0, 0.999, &region), 0xFF000124);
+        assert_eq!(texture.sample_region(0.999, 0.001, &region), 0xFF000327);
+
+        // Rects running off the texture are clipped to it
+        let clipped = texture.region(6, 2, 10, 10);
+        assert_eq!(clipped, TextureRegion::new(0.75, 0.0, 0.25, 0.5));
+    }
+
+    #[test]
+    fn region_sampling_never_leaves_the_region() {
+        let texture = atlas();
+        let left = texture.region(0, 0, 4, 4);
+        let right = texture.region(4, 0, 4, 4);
+        // Including UVs on and past the edges, which wrap within the region
+        for i in -40..=40 {
+            for j in -40..=40 {
+                let (u, v) = (i as f32 * 0.05, j as f32 * 0.05);
+                assert_eq!(texture.sample_region(u, v, &left) & 0xF0, 0x10);
+                assert_eq!(texture.sample_region(u, v, &right) & 0xF0, 0x20);
+            }
+        }
+    }
+
+    /// 4x1 ramp with texel x's blue byte at 0x40 * x
+    fn ramp() -> Texture {
+        Texture::from_fn(4, 1, |x, _| 0xFF000000 | (0x40 * x)).unwrap()
+    }
+
+    const BORDER: u32 = 0x00000000;
+
+    const MODES: [AddressMode; 4] = [
+        AddressMode::Repeat,
+        AddressMode::ClampToEdge,
+        AddressMode::MirrorRepeat,
+        AddressMode::Border(BORDER),
+    ];
+
+    /// Sample `texture` along u with each of [`MODES`] in turn.
+    fn sample_modes(texture: &mut Texture, u: f32) -> [u32; 4] {
+        MODES.map(|mode| {
+            texture.set_address_mode(mode, AddressMode::ClampToEdge);
+            texture.sample(u, 0.5)
+        })
+    }
+
+    #[test]
+    fn nearest_sampling_honors_address_modes() {
+        let mut texture = ramp();
+        let texel = |x: u32| 0xFF000000 | (0x40 * x);
+        // Each row: u, then the texel under Repeat, ClampToEdge,
+        // MirrorRepeat and Border
+        let cases = [
+            (0.0, [texel(0), texel(0), texel(0), texel(0)]),
+            (1.0, [texel(0), texel(3), texel(3), texel(3)]),
+            (-0.1, [texel(3), texel(0), texel(0), BORDER]),
+            (1.1, [texel(0), texel(3), texel(3), BORDER]),
+            (3.3, [texel(1), texel(3), texel(2), BORDER]),
+            (-2.7, [texel(1), texel(0), texel(2), BORDER]),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(sample_modes(&mut texture, u), expected, "u = {u}");
+        }
+    }
+
+    #[test]
+    fn bilinear_sampling_picks_edge_neighbors_per_mode() {
+        let mut texture = ramp();
+        texture.set_filter(TextureFilter::Bilinear);
+        let texel = |x: u32| 0xFF000000 | (0x40 * x);
+        // On the edges the sample lies halfway between the edge texel
+        // and its neighbour across the edge
+        let cases = [
+            (0.0, [0xFF000060, texel(0), texel(0), 0x80000000]),
+            (1.0, [0xFF000060, texel(3), texel(3), 0x80000060]),
+            // On texel -1 / 4 / -11 / 21's center
+            (-0.125, [texel(3), texel(0), texel(0), BORDER]),
+            (1.125, [texel(0), texel(3), texel(3), BORDER]),
+            (-2.625, [texel(1), texel(0), texel(2), BORDER]),
+            (5.375, [texel(1), texel(3), texel(2), BORDER]),
+        ];
+        for (u, expected) in cases {
+            assert_eq!(sample_modes(&mut texture, u), expected, "u = {u}");
+        }
+
+        // Between two inner texels it's a plain blend
+        texture.set_address_mode(AddressMode::Repeat, AddressMode::Repeat);
+        assert_eq!(texture.sample(0.5, 0.5), 0xFF000060);
+    }
+
+    #[test]
+    fn address_modes_apply_to_v_independently() {
+        // 1x4: rows tagged 0..3 from the top
+        let mut texture = Texture::from_fn(1, 4, |_, y| 0xFF000000 | y).unwrap();
+        texture.set_address_mode(AddressMode::Repeat, AddressMode::ClampToEdge);
+        // v runs bottom to top
+        assert_eq!(texture.sample(0.5, 1.0), 0xFF000000);
+        assert_eq!(texture.sample(0.5, 0.0), 0xFF000003);
+        assert_eq!(texture.sample(0.5, 1.2), 0xFF000000);
+        assert_eq!(texture.sample(0.5, -5.0), 0xFF000003);
+
+        texture.set_address_mode(AddressMode::ClampToEdge, AddressMode::Border(0xFFFFFFFF));
+        assert_eq!(texture.sample(0.5, 1.01), 0xFFFFFFFF);
+        assert_eq!(texture.sample(0.5, 0.99), 0xFF000000);
+        assert_eq!(
+            texture.address_mode(),
+            (AddressMode::ClampToEdge, AddressMode::Border(0xFFFFFFFF))
+        );
+    }
+
+    #[test]
+    fn region_sampling_honors_address_modes() {
+        let mut texture = atlas();
+        let left = texture.region(0, 0, 4, 4);
+        texture.set_address_mode(AddressMode::ClampToEdge, AddressMode::Border(BORDER));
+        // Past the right edge stays on the region's last column
+        assert_eq!(texture.sample_region(1.5, 0.9, &left), 0xFF000013);
+        assert_eq!(texture.sample_region(0.5, 1.5, &left), BORDER);
+    }
+
+    #[test]
+    fn updates_replace_pixels_in_place() {
+        let mut texture = Texture::new(4, 2).unwrap();
+        assert_eq!(texture.sample(0.5, 0.5), 0xFF000000);
+
+        let pixels: Vec<u32> = (0..8).map(|i| 0xFF000000 | i).collect();
+        texture.update_from_argb(&pixels).unwrap();
+        let storage = texture.data.as_ptr();
+        // Top-right, then bottom-left
+        assert_eq!(texture.sample(0.9, 0.9), 0xFF000003);
+        assert_eq!(texture.sample(0.1, 0.1), 0xFF000004);
+
+        // A 2x2 block in the middle columns
+        texture
+            .update_region(1, 0, 2, 2, &[0xA, 0xB, 0xC, 0xD])
+            .unwrap();
+        assert_eq!(
+            texture.data,
+            [0xFF000000, 0xA, 0xB, 0xFF000003, 0xFF000004, 0xC, 0xD, 0xFF000007]
+        );
+        assert_eq!(texture.data.as_ptr(), storage);
+    }
+
+    #[test]
+    fn updates_that_dont_fit_are_rejected() {
+        let mut texture = Texture::new(4, 2).unwrap();
+        assert_eq!(
+            texture.update_from_argb(&[0; 7]),
+            Err(TextureUpdateError::SizeMismatch {
+                expected: 8,
+                actual: 7
+            })
+        );
+        assert!(matches!(
+            texture.update_region(3, 0, 2, 1, &[0; 2]),
+            Err(TextureUpdateError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            texture.update_region(0, u32::MAX, 1, 2, &[0; 2]),
+            Err(TextureUpdateError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            texture.update_region(0, 0, 2, 2, &[0; 3]),
+            Err(TextureUpdateError::SizeMismatch { .. })
+        ));
+        // Nothing changed
+        assert!(texture.data.iter().all(|&p| p == 0xFF000000));
+        assert_eq!(texture.update_region(4, 2, 0, 0, &[]), Ok(()));
+    }
+
+    #[test]
+    fn bad_sizes_are_errors_not_panics() {
+        assert!(matches!(Texture::new(0, 4), Err(Error::InvalidArgument(_))));
+        assert!(matches!(
+            Texture::from_fn(4, 0, |_, _| 0),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Texture::from_pixels(vec![0; 5], 2, 2),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Texture::from_file("does/not/exist.png"),
+            Err(Error::Texture(_))
+        ));
+    }
+
+    const RED: u32 = 0xFFC00000;
+    const BLUE: u32 = 0xFF0000C0;
+
+    /// 7x3 opaque atlas: a red island in columns 0-1 and a blue one in
+    /// columns 5-6, with black padding between them
+    fn padded_atlas() -> Texture {
+        Texture::from_fn(7, 3, |x, _| match x {
+            0..=1 => RED,
+            5..=6 => BLUE,
+            _ => 0xFF000000,
+        })
+        .unwrap()
+    }
+
+    fn column(texture: &Texture, x: u32) -> Vec<u32> {
+        (0..texture.height())
+            .map(|y| texture.data[(y * texture.width() + x) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn dilation_grows_islands_into_black_padding() {
+        let mut texture = padded_atlas();
+        assert_eq!(texture.dilate_borders(1), 6);
+        assert_eq!(column(&texture, 2), [RED; 3]);
+        assert_eq!(column(&texture, 3), [0xFF000000; 3]);
+        assert_eq!(column(&texture, 4), [BLUE; 3]);
+        // Island interiors are untouched
+        for x in [0, 1] {
+            assert_eq!(column(&texture, x), [RED; 3]);
+        }
+        for x in [5, 6] {
+            assert_eq!(column(&texture, x), [BLUE; 3]);
+        }
+
+        // The last gap texel sits between both islands and takes their mix
+        assert_eq!(texture.dilate_borders(5), 3);
+        assert_eq!(column(&texture, 3), [0xFF600060; 3]);
+        assert_eq!(texture.dilate_borders(1), 0);
+    }
+
+    #[test]
+    fn dilation_stops_bilinear_seams() {
+        let mut texture = padded_atlas();
+        texture.set_filter(TextureFilter::Bilinear);
+        // On the red island's edge, halfway to the padding
+        let edge = 2.0 / 7.0;
+        assert_ne!(texture.sample(edge, 0.5), RED);
+        texture.dilate_borders(1);
+        assert_eq!(texture.sample(edge, 0.5), RED);
+    }
+
+    #[test]
+    fn transparent_images_dilate_into_clear_texels_only() {
+        // 3x3: a transparent ring around an opaque black texel, and one
+        // opaque red corner
+        let mut texture = Texture::from_fn(3, 3, |x, y| match (x, y) {
+            (1, 1) => 0xFF000000,
+            (0, 0) => RED,
+            _ => 0x00000000,
+        })
+        .unwrap();
+        assert_eq!(texture.dilate_borders(1), 7);
+        // Black is filled when there's alpha; the clear texels take color
+        // and stay clear
+        assert_eq!(texture.data[4], 0xFF000000);
+        assert_eq!(texture.data[1], 0x00600000);
+        assert_eq!(texture.data[8], 0x00000000);
+        assert!(texture
+            .data
+            .iter()
+            .all(|&p| p >> 24 == 0 || p == RED || p == 0xFF000000));
+    }
+
+    #[test]
+    fn load_options_dilate_loaded_images() {
+        let path = std::env::temp_dir().join(format!("russsty_dilate_{}.png", std::process::id()));
+        let atlas = padded_atlas();
+        let rgba: Vec<u8> = atlas
+            .data
+            .iter()
+            .flat_map(|&p| {
+                let [a, r, g, b] = p.to_be_bytes();
+                [r, g, b, a]
+            })
+            .collect();
+        image::save_buffer(&path, &rgba, 7, 3, image::ExtendedColorType::Rgba8).unwrap();
+
+        let plain = Texture::from_file(&path).unwrap();
+        let options = TextureLoadOptions { dilate_borders: 1 };
+        let dilated = Texture::from_file_with(&path, options).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(plain.data, atlas.data);
+        assert_eq!(plain.load_options(), TextureLoadOptions::default());
+        assert_eq!(column(&dilated, 2), [RED; 3]);
+        assert_eq!(dilated.load_options(), options);
+    }
+
+    #[test]
+    fn removed_textures_leave_dangling_handles_empty() {
+        let solid = |color| Texture::from_fn(1, 1, move |_, _| color).unwrap();
+        let mut arena = TextureArena::default();
+        let red = arena.insert(solid(0xFFFF0000));
+        let green = arena.insert(solid(0xFF00FF00));
+        assert_eq!(arena.len(), 2);
+
+        assert_eq!(
+            arena.remove(red).map(|t| t.sample(0.5, 0.5)),
+            Some(0xFFFF0000)
+        );
+        assert!(arena.get(red).is_none());
+        assert!(arena.remove(red).is_none());
+
+        // The slot is reused, but the old handle still finds nothing
+        let blue = arena.insert(solid(0xFF0000FF));
+        assert_ne!(blue, red);
+        assert!(arena.get(red).is_none());
+        assert_eq!(arena.get(blue).unwrap().sample(0.5, 0.5), 0xFF0000FF);
+        assert_eq!(arena.get(green).unwrap().sample(0.5, 0.5), 0xFF00FF00);
+        assert_eq!(arena.len(), 2);
+    }
+}
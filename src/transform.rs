@@ -1,11 +1,33 @@
 //! Transform component for 3D objects.
 //!
 //! Provides a [`Transform`] struct with a fluent API for managing position,
-//! rotation (Euler angles), and scale.
+//! rotation (Euler angles or a quaternion), and scale.
 
-use crate::math::{mat4::Mat4, vec3::Vec3};
+use crate::math::{mat4::Mat4, quat::Quat, vec3::Vec3};
 
-/// A 3D transform with position, rotation (Euler angles), and scale.
+/// How a [`Transform`] stores its rotation: whichever form was set last.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rotation {
+    /// Euler angles in radians: x=pitch, y=yaw, z=roll
+    Euler(Vec3),
+    Quat(Quat),
+}
+
+impl Rotation {
+    fn to_matrix(self) -> Mat4 {
+        match self {
+            Rotation::Euler(r) => Mat4::rotation_euler(r),
+            Rotation::Quat(q) => q.to_mat4(),
+        }
+    }
+}
+
+/// A 3D transform with position, rotation, and scale.
+///
+/// Rotation is either Euler angles or a [`Quat`], whichever was set last.
+/// The Euler methods keep working on a quaternion rotation by converting
+/// it to angles first; animate with quaternions to avoid gimbal lock and
+/// to interpolate between orientations.
 ///
 /// Provides a fluent API where mutating methods return `&mut Self` for chaining:
 ///
@@ -18,7 +40,7 @@ use crate::math::{mat4::Mat4, vec3::Vec3};
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Transform {
     position: Vec3,
-    rotation: Vec3, // Euler angles in radians: x=pitch, y=yaw, z=roll
+    rotation: Rotation,
     scale: Vec3,
 }
 
@@ -26,7 +48,7 @@ impl Default for Transform {
     fn default() -> Self {
         Self {
             position: Vec3::ZERO,
-            rotation: Vec3::ZERO,
+            rotation: Rotation::Euler(Vec3::ZERO),
             scale: Vec3::ONE,
         }
     }
@@ -84,44 +106,58 @@ impl Transform {
     // ============ Rotation ============
 
     /// Get the rotation (Euler angles in radians).
+    ///
+    /// A quaternion rotation is converted; see [`Quat::to_euler`].
     pub fn rotation(&self) -> Vec3 {
-        self.rotation
+        match self.rotation {
+            Rotation::Euler(r) => r,
+            Rotation::Quat(q) => q.to_euler(),
+        }
     }
 
     /// Set the rotation (Euler angles in radians).
     pub fn set_rotation(&mut self, rotation: Vec3) -> &mut Self {
-        self.rotation = rotation;
+        self.rotation = Rotation::Euler(rotation);
         self
     }
 
     /// Set the rotation from x, y, z components (radians).
     pub fn set_rotation_xyz(&mut self, x: f32, y: f32, z: f32) -> &mut Self {
-        self.rotation = Vec3::new(x, y, z);
+        self.set_rotation(Vec3::new(x, y, z))
+    }
+
+    /// Get the rotation as a quaternion, converting Euler angles if needed.
+    pub fn rotation_quat(&self) -> Quat {
+        match self.rotation {
+            Rotation::Euler(r) => Quat::from_euler(r.x, r.y, r.z),
+            Rotation::Quat(q) => q,
+        }
+    }
+
+    /// Set the rotation from a quaternion, which is normalized.
+    pub fn set_rotation_quat(&mut self, rotation: Quat) -> &mut Self {
+        self.rotation = Rotation::Quat(rotation.normalize());
         self
     }
 
     /// Add a delta rotation (Euler angles in radians).
     pub fn rotate(&mut self, delta: Vec3) -> &mut Self {
-        self.rotation = self.rotation + delta;
-        self
+        self.set_rotation(self.rotation() + delta)
     }
 
     /// Rotate around the X axis (pitch).
     pub fn rotate_x(&mut self, angle: f32) -> &mut Self {
-        self.rotation.x += angle;
-        self
+        self.rotate(Vec3::new(angle, 0.0, 0.0))
     }
 
     /// Rotate around the Y axis (yaw).
     pub fn rotate_y(&mut self, angle: f32) -> &mut Self {
-        self.rotation.y += angle;
-        self
+        self.rotate(Vec3::new(0.0, angle, 0.0))
     }
 
     /// Rotate around the Z axis (roll).
     pub fn rotate_z(&mut self, angle: f32) -> &mut Self {
-        self.rotation.z += angle;
-        self
+        self.rotate(Vec3::new(0.0, 0.0, angle))
     }
 
     // ============ Scale ============
@@ -163,14 +199,11 @@ impl Transform {
 
     /// Generate the transformation matrix.
     ///
-    /// Order: Translation * RotationX * RotationY * RotationZ * Scale
+    /// Order: Translation * Rotation * Scale, where an Euler rotation is
+    /// RotationX * RotationY * RotationZ
     /// (Scale applied first, then rotations, then translation)
     pub fn to_matrix(&self) -> Mat4 {
-        Mat4::translation(self.position.x, self.position.y, self.position.z)
-            * Mat4::rotation_x(self.rotation.x)
-            * Mat4::rotation_y(self.rotation.y)
-            * Mat4::rotation_z(self.rotation.z)
-            * Mat4::scaling(self.scale.x, self.scale.y, self.scale.z)
+        Mat4::compose_trs(self.position, &self.rotation.to_matrix(), self.scale)
     }
 
     /// Generate the normal matrix for lighting calculations.
@@ -178,15 +211,7 @@ impl Transform {
     /// This is the inverse transpose of the rotation+scale matrix (excludes translation).
     /// Correctly handles non-uniform scaling.
     pub fn normal_matrix(&self) -> Mat4 {
-        let rotation_scale = Mat4::rotation_x(self.rotation.x)
-            * Mat4::rotation_y(self.rotation.y)
-            * Mat4::rotation_z(self.rotation.z)
-            * Mat4::scaling(self.scale.x, self.scale.y, self.scale.z);
-
-        rotation_scale
-            .inverse()
-            .unwrap_or(Mat4::identity())
-            .transpose()
+        Mat4::inverse_transpose_of_rotation_scale(&self.rotation.to_matrix(), self.scale)
     }
 }
 
@@ -236,4 +261,44 @@ mod tests {
         // Default transform should produce identity matrix
         assert_eq!(m, Mat4::identity());
     }
+
+    #[test]
+    fn quat_rotation_matches_euler_matrix() {
+        let (x, y, z) = (0.3, -0.8, 1.2);
+        let mut euler = Transform::new();
+        euler
+            .set_position_xyz(1.0, 2.0, 3.0)
+            .set_rotation_xyz(x, y, z)
+            .set_scale(Vec3::new(1.0, 2.0, 0.5));
+        let mut quat = euler;
+        quat.set_rotation_quat(Quat::from_euler(x, y, z));
+
+        for (a, b) in [
+            (euler.to_matrix(), quat.to_matrix()),
+            (euler.normal_matrix(), quat.normal_matrix()),
+        ] {
+            for row in 0..4 {
+                for col in 0..4 {
+                    assert_relative_eq!(a.get(row, col), b.get(row, col), epsilon = 1e-5);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn last_set_rotation_wins() {
+        let mut t = Transform::new();
+        t.set_rotation_quat(Quat::from_axis_angle(Vec3::UP, 0.5));
+        assert_relative_eq!(t.rotation().y, 0.5, epsilon = 1e-5);
+
+        // Euler edits convert and switch back to angles
+        t.rotate_x(0.25);
+        assert_relative_eq!(t.rotation().x, 0.25, epsilon = 1e-5);
+        assert_relative_eq!(t.rotation().y, 0.5, epsilon = 1e-5);
+
+        t.set_rotation_xyz(0.0, 0.0, 1.0);
+        let q = t.rotation_quat();
+        let expected = Quat::from_axis_angle(Vec3::FORWARD, 1.0);
+        assert_relative_eq!(q.dot(expected).abs(), 1.0, epsilon = 1e-5);
+    }
 }
@@ -0,0 +1,111 @@
+//! Viewport transform between normalized device coordinates and pixels.
+//!
+//! # Convention
+//! - NDC x and y span [-1, 1] with +y up; screen y grows downward
+//! - NDC ±1 land on the outer *edges* of the viewport, not on pixel centers
+//! - Pixel `(i, j)` covers `[i, i + 1) x [j, j + 1)` and is sampled at its
+//!   center `(i + 0.5, j + 0.5)`, which is where the rasterizers test
+//!   coverage
+//!
+//! So no half-pixel offset is added when projecting; it appears when going
+//! the other way, in [`Viewport::unproject`].
+
+use crate::math::vec2::Vec2;
+use crate::math::vec3::Vec3;
+
+/// A rectangle of the render target that NDC maps onto, in pixels.
+///
+/// Usually the whole target, but any sub-rectangle works, e.g. one half of a
+/// split screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Viewport {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// A viewport covering a whole `width` x `height` target.
+    pub fn full(width: u32, height: u32) -> Self {
+        Self::new(0.0, 0.0, width as f32, height as f32)
+    }
+
+    /// Map an NDC position to screen coordinates.
+    ///
+    /// Returns `(screen_x, screen_y, w)`: the clip-space `w` is carried
+    /// through unchanged for the depth buffer and perspective-correct
+    /// interpolation. `ndc.z` is not needed and ignored.
+    #[inline]
+    pub fn project(&self, ndc: Vec3, w: f32) -> Vec3 {
+        Vec3::new(
+            self.x + (ndc.x + 1.0) * 0.5 * self.width,
+            self.y + (1.0 - ndc.y) * 0.5 * self.height,
+            w,
+        )
+    }
+
+    /// NDC x and y of the center of pixel `(x, y)`. The inverse of
+    /// [`Viewport::project`] at that pixel's sample point.
+    pub fn unproject(&self, x: i32, y: i32) -> Vec2 {
+        Vec2::new(
+            (x as f32 + 0.5 - self.x) / self.width * 2.0 - 1.0,
+            1.0 - (y as f32 + 0.5 - self.y) / self.height * 2.0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn ndc_origin_maps_to_exact_center() {
+        let viewport = Viewport::full(640, 480);
+        let p = viewport.project(Vec3::ZERO, 2.0);
+        assert_eq!((p.x, p.y, p.z), (320.0, 240.0, 2.0));
+    }
+
+    #[test]
+    fn ndc_corners_map_to_outer_pixel_edges() {
+        let viewport = Viewport::full(640, 480);
+        let bottom_left = viewport.project(Vec3::new(-1.0, -1.0, 0.0), 1.0);
+        let top_right = viewport.project(Vec3::new(1.0, 1.0, 0.0), 1.0);
+        assert_eq!((bottom_left.x, bottom_left.y), (0.0, 480.0));
+        assert_eq!((top_right.x, top_right.y), (640.0, 0.0));
+
+        // The corner pixels' sample points lie half a pixel inside
+        let first = viewport.unproject(0, 479);
+        let first = viewport.project(Vec3::new(first.x, first.y, 0.0), 1.0);
+        assert_relative_eq!(first.x, 0.5, epsilon = 1e-4);
+        assert_relative_eq!(first.y, 479.5, epsilon = 1e-4);
+        let last = viewport.unproject(639, 0);
+        let last = viewport.project(Vec3::new(last.x, last.y, 0.0), 1.0);
+        assert_relative_eq!(last.x, 639.5, epsilon = 1e-4);
+        assert_relative_eq!(last.y, 0.5, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn sub_rectangle_offsets_projection() {
+        // Right half of a 640x480 split screen
+        let viewport = Viewport::new(320.0, 0.0, 320.0, 480.0);
+        let center = viewport.project(Vec3::ZERO, 1.0);
+        assert_eq!((center.x, center.y), (480.0, 240.0));
+        let left_edge = viewport.project(Vec3::new(-1.0, 1.0, 0.0), 1.0);
+        assert_eq!((left_edge.x, left_edge.y), (320.0, 0.0));
+
+        let ndc = viewport.unproject(320, 0);
+        assert_relative_eq!(ndc.x, -1.0 + 1.0 / 320.0, epsilon = 1e-6);
+        assert_relative_eq!(ndc.y, 1.0 - 1.0 / 480.0, epsilon = 1e-6);
+    }
+}
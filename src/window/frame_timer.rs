@@ -0,0 +1,271 @@
+//! Frame pacing and frame time statistics.
+
+use std::time::{Duration, Instant};
+
+/// Paces the main loop to a target frame rate and keeps the last
+/// [`FrameTimer::HISTORY`] frame times for statistics.
+///
+/// Sleeps for most of the remaining frame budget, then spin-waits the last
+/// millisecond, since OS sleeps routinely overshoot by about that much.
+/// An uncapped timer never waits, which is what benchmarks want.
+///
+/// An average alone hides hitching, so alongside [`FrameTimer::average_fps`]
+/// there's the [`FrameTimer::one_percent_low_fps`] and any percentile of
+/// frame time over the recorded frames; at 60 FPS a stall stays in them for
+/// four seconds.
+///
+/// ```ignore
+/// let mut timer = FrameTimer::with_target_fps(30.0);
+/// loop {
+///     let dt = timer.wait_and_get_delta(); // seconds
+///     // ...
+///     if let Some(fps) = timer.tick() {
+///         println!("{fps:.1} fps (1% low: {:.1})", timer.one_percent_low_fps().unwrap());
+///     }
+/// }
+/// ```
+pub struct FrameTimer {
+    target_frame_time: Option<Duration>,
+    previous_frame: Instant,
+    frame_time: f32,
+    // Frame times in seconds, newest last. Grows to twice the history
+    // before the oldest half is dropped, so the recent ones stay one slice
+    history: Vec<f32>,
+    frames_since_report: u32,
+    last_report: Instant,
+}
+
+impl FrameTimer {
+    /// How many frame times are kept for statistics.
+    pub const HISTORY: usize = 240;
+
+    /// Portion of the frame budget spent spinning instead of sleeping.
+    const SPIN_THRESHOLD: Duration = Duration::from_millis(1);
+
+    /// Create a timer capped at [`FPS`](super::FPS).
+    pub fn new() -> Self {
+        Self::with_target_fps(super::FPS as f64)
+    }
+
+    /// Create a timer capped at `fps` frames per second.
+    ///
+    /// A non-positive or non-finite `fps` gives an uncapped timer.
+    pub fn with_target_fps(fps: f64) -> Self {
+        let mut timer = Self::uncapped();
+        timer.set_target_fps(Some(fps));
+        timer
+    }
+
+    /// Create a timer that never waits.
+    pub fn uncapped() -> Self {
+        let now = Instant::now();
+        Self {
+            target_frame_time: None,
+            previous_frame: now,
+            frame_time: 0.0,
+            history: Vec::with_capacity(Self::HISTORY * 2),
+            frames_since_report: 0,
+            last_report: now,
+        }
+    }
+
+    /// Change the cap; `None` (or a non-positive rate) removes it.
+    pub fn set_target_fps(&mut self, fps: Option<f64>) {
+        self.target_frame_time = fps
+            .filter(|fps| fps.is_finite() && *fps > 0.0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps));
+    }
+
+    /// The frame rate being targeted, or `None` when uncapped.
+    pub fn target_fps(&self) -> Option<f64> {
+        self.target_frame_time.map(|t| 1.0 / t.as_secs_f64())
+    }
+
+    /// Waits if necessary to hold the target frame rate, records the frame
+    /// and returns the delta time in seconds since the previous call.
+    pub fn wait_and_get_delta(&mut self) -> f32 {
+        if let Some(target) = self.target_frame_time {
+            let deadline = self.previous_frame + target;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining > Self::SPIN_THRESHOLD {
+                std::thread::sleep(remaining - Self::SPIN_THRESHOLD);
+            }
+            while Instant::now() < deadline {
+                std::hint::spin_loop();
+            }
+        }
+
+        let now = Instant::now();
+        let frame_time = (now - self.previous_frame).as_secs_f32();
+        self.previous_frame = now;
+        self.record_frame(frame_time);
+        frame_time
+    }
+
+    /// Record a frame that took `seconds`, for frames timed some other way,
+    /// e.g. a fixed step when rendering offline.
+    pub fn record_frame(&mut self, seconds: f32) {
+        self.frame_time = seconds;
+        if self.history.len() == Self::HISTORY * 2 {
+            self.history.drain(..Self::HISTORY);
+        }
+        self.history.push(seconds);
+    }
+
+    /// Duration of the last frame in seconds, as returned by
+    /// [`FrameTimer::wait_and_get_delta`].
+    pub fn frame_time(&self) -> f32 {
+        self.frame_time
+    }
+
+    /// Call each frame. Returns `Some(fps)` once per second, `None`
+    /// otherwise.
+    pub fn tick(&mut self) -> Option<f64> {
+        self.frames_since_report += 1;
+        let elapsed = self.last_report.elapsed();
+        if elapsed.as_secs() >= 1 {
+            let fps = self.frames_since_report as f64 / elapsed.as_secs_f64();
+            self.frames_since_report = 0;
+            self.last_report = Instant::now();
+            Some(fps)
+        } else {
+            None
+        }
+    }
+
+    /// Up to the last [`FrameTimer::HISTORY`] frame times in seconds,
+    /// oldest first, e.g. for a sparkline.
+    pub fn recent_frame_times(&self) -> &[f32] {
+        let start = self.history.len().saturating_sub(Self::HISTORY);
+        &self.history[start..]
+    }
+
+    /// Frames per second over the recent frames, or `None` before any.
+    pub fn average_fps(&self) -> Option<f64> {
+        let times = self.recent_frame_times();
+        let total: f64 = times.iter().map(|&t| t as f64).sum();
+        (total > 0.0).then(|| times.len() as f64 / total)
+    }
+
+    /// Frames per second over the slowest 1% of the recent frames (at
+    /// least one), or `None` before any.
+    pub fn one_percent_low_fps(&self) -> Option<f64> {
+        let sorted = self.sorted_frame_times();
+        let count = (sorted.len() / 100).max(1).min(sorted.len());
+        let slowest = &sorted[sorted.len() - count..];
+        let total: f64 = slowest.iter().map(|&t| t as f64).sum();
+        (total > 0.0).then(|| slowest.len() as f64 / total)
+    }
+
+    /// The frame time in seconds that `percentile` percent of the recent
+    /// frames took no longer than, by nearest rank: the 99th is the
+    /// hitch threshold, the 50th the median. `None` before any frames.
+    pub fn frame_time_percentile(&self, percentile: f64) -> Option<f32> {
+        let sorted = self.sorted_frame_times();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.clamp(1, sorted.len().max(1)) - 1).copied()
+    }
+
+    fn sorted_frame_times(&self) -> Vec<f32> {
+        let mut sorted = self.recent_frame_times().to_vec();
+        sorted.sort_by(f32::total_cmp);
+        sorted
+    }
+}
+
+impl Default for FrameTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The frame limiting half of [`FrameTimer`], under its old name.
+#[deprecated(note = "use `FrameTimer`, which also keeps frame time statistics")]
+pub type FrameLimiter = FrameTimer;
+
+/// The once-a-second [`FrameTimer::tick`], under its old name.
+#[deprecated(note = "use `FrameTimer::tick`")]
+pub type FpsCounter = FrameTimer;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An uncapped timer fed `times` in milliseconds.
+    fn timer_with(times: impl IntoIterator<Item = f32>) -> FrameTimer {
+        let mut timer = FrameTimer::uncapped();
+        for ms in times {
+            timer.record_frame(ms / 1000.0);
+        }
+        timer
+    }
+
+    #[test]
+    fn capped_timer_waits_out_the_frame() {
+        let mut timer = FrameTimer::with_target_fps(100.0);
+        assert!((timer.target_fps().unwrap() - 100.0).abs() < 1e-6);
+
+        for _ in 0..3 {
+            let dt = timer.wait_and_get_delta();
+            assert!(dt >= 0.01, "frame ended early: {dt}");
+            assert_eq!(timer.frame_time(), dt);
+        }
+        assert_eq!(timer.recent_frame_times().len(), 3);
+    }
+
+    #[test]
+    fn non_positive_target_is_uncapped() {
+        assert_eq!(FrameTimer::with_target_fps(0.0).target_fps(), None);
+        assert_eq!(FrameTimer::with_target_fps(f64::NAN).target_fps(), None);
+
+        let mut timer = FrameTimer::uncapped();
+        assert!(timer.wait_and_get_delta() < 0.01);
+    }
+
+    #[test]
+    fn a_stall_shows_in_the_lows_until_it_ages_out() {
+        let steady = vec![10.0; FrameTimer::HISTORY];
+        let timer = timer_with(steady.iter().copied());
+        assert!((timer.average_fps().unwrap() - 100.0).abs() < 1e-3);
+        assert!((timer.one_percent_low_fps().unwrap() - 100.0).abs() < 1e-3);
+
+        // One 100 ms frame among the steady ones
+        let mut timer = timer_with(steady.iter().copied().chain([100.0]));
+        let low = timer.one_percent_low_fps().unwrap();
+        assert!(low < 20.0, "{low}");
+        assert!(timer.average_fps().unwrap() > 90.0);
+        // A few of them reach the 99th percentile too
+        for _ in 0..3 {
+            timer.record_frame(0.1);
+        }
+        assert_eq!(timer.frame_time_percentile(99.0), Some(0.1));
+        assert_eq!(timer.frame_time_percentile(50.0), Some(0.01));
+
+        // A history of frames later, it's gone
+        for _ in 0..FrameTimer::HISTORY {
+            timer.record_frame(0.01);
+        }
+        assert!((timer.one_percent_low_fps().unwrap() - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn recent_frame_times_are_the_newest_in_order() {
+        let timer = timer_with((0..FrameTimer::HISTORY * 3 + 7).map(|i| i as f32));
+        let recent = timer.recent_frame_times();
+        assert_eq!(recent.len(), FrameTimer::HISTORY);
+        assert_eq!(
+            recent.last().copied(),
+            Some((FrameTimer::HISTORY * 3 + 6) as f32 / 1000.0)
+        );
+        assert!(recent.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn no_frames_no_stats() {
+        let timer = FrameTimer::uncapped();
+        assert!(timer.recent_frame_times().is_empty());
+        assert_eq!(timer.average_fps(), None);
+        assert_eq!(timer.one_percent_low_fps(), None);
+        assert_eq!(timer.frame_time_percentile(99.0), None);
+    }
+}
@@ -0,0 +1,289 @@
+//! Fixed-timestep simulation paced by the frame timer.
+
+use std::time::{Duration, Instant};
+
+use super::FrameTimer;
+
+/// Runs a simulation at a fixed tick rate, independent of how fast frames
+/// are drawn.
+///
+/// Each frame adds the time since the last one to an accumulator and calls
+/// the update closure once per whole tick it holds, always with the same
+/// `dt`; the remainder carries over. The render closure then gets the
+/// fraction of a tick left over as `alpha`, to draw the state that far
+/// between the last two ticks. The same elapsed times therefore produce
+/// the same ticks whatever the frame rate.
+///
+/// State both closures touch, like the engine, is passed in as `state`
+/// rather than captured, so each can borrow it mutably:
+///
+/// ```ignore
+/// let mut game_loop = GameLoop::new(60.0);
+/// loop {
+///     game_loop.frame(
+///         &mut engine,
+///         |engine, dt| engine.animate(dt),
+///         |engine, _alpha| {
+///             engine.update();
+///             engine.render();
+///         },
+///     );
+/// }
+/// ```
+pub struct GameLoop {
+    tick: Duration,
+    accumulator: Duration,
+    max_ticks_per_frame: u32,
+    ticks: u64,
+    alpha: f32,
+    update_time: Duration,
+    render_time: Duration,
+    timer: FrameTimer,
+    fps: Option<f64>,
+}
+
+impl GameLoop {
+    /// Ticks run in one frame before the loop gives up catching up.
+    pub const DEFAULT_MAX_TICKS_PER_FRAME: u32 = 8;
+
+    /// Simulate `tick_rate` times per second, drawing frames at the
+    /// default [`FrameTimer`] cap. A non-positive or non-finite rate
+    /// falls back to [`FPS`](super::FPS).
+    pub fn new(tick_rate: f64) -> Self {
+        let mut game_loop = Self {
+            tick: Duration::ZERO,
+            accumulator: Duration::ZERO,
+            max_ticks_per_frame: Self::DEFAULT_MAX_TICKS_PER_FRAME,
+            ticks: 0,
+            alpha: 0.0,
+            update_time: Duration::ZERO,
+            render_time: Duration::ZERO,
+            timer: FrameTimer::new(),
+            fps: None,
+        };
+        game_loop.set_tick_rate(tick_rate);
+        game_loop
+    }
+
+    /// Change the simulation rate. Time already accumulated is kept. The
+    /// tick is at least a nanosecond, so rates above 1e9 run at 1e9.
+    pub fn set_tick_rate(&mut self, tick_rate: f64) {
+        let rate = if tick_rate.is_finite() && tick_rate > 0.0 {
+            tick_rate
+        } else {
+            super::FPS as f64
+        };
+        self.tick = Duration::from_secs_f64(1.0 / rate).max(Duration::from_nanos(1));
+    }
+
+    /// Simulation ticks per second.
+    pub fn tick_rate(&self) -> f64 {
+        1.0 / self.tick.as_secs_f64()
+    }
+
+    /// The fixed `dt` handed to the update closure, in seconds.
+    pub fn tick_dt(&self) -> f32 {
+        self.tick.as_secs_f32()
+    }
+
+    /// Cap how many ticks one frame may run. When a frame falls further
+    /// behind than this (a debugger pause, a slow load), the excess time
+    /// is dropped and the simulation runs slow for that frame rather than
+    /// spiraling further behind. At least 1.
+    pub fn set_max_ticks_per_frame(&mut self, max: u32) {
+        self.max_ticks_per_frame = max.max(1);
+    }
+
+    /// Ticks run since the loop was created.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// How far between the last tick and the next the latest frame was
+    /// drawn, from 0.0 up to (not including) 1.0.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Time the latest frame spent in the update closure, over all its
+    /// ticks.
+    pub fn update_time(&self) -> Duration {
+        self.update_time
+    }
+
+    /// Time the latest frame spent in the render closure.
+    pub fn render_time(&self) -> Duration {
+        self.render_time
+    }
+
+    /// The timer pacing [`GameLoop::frame`], to change the frame cap or
+    /// read frame time statistics.
+    pub fn frame_timer(&self) -> &FrameTimer {
+        &self.timer
+    }
+
+    pub fn frame_timer_mut(&mut self) -> &mut FrameTimer {
+        &mut self.timer
+    }
+
+    #[deprecated(note = "use `GameLoop::frame_timer`")]
+    pub fn frame_limiter(&self) -> &FrameTimer {
+        &self.timer
+    }
+
+    #[deprecated(note = "use `GameLoop::frame_timer_mut`")]
+    pub fn frame_limiter_mut(&mut self) -> &mut FrameTimer {
+        &mut self.timer
+    }
+
+    /// The frame rate, on the frames where a new once-a-second measurement
+    /// came in (like [`FrameTimer::tick`]); `None` on the others.
+    pub fn fps(&self) -> Option<f64> {
+        self.fps
+    }
+
+    /// Wait out the frame timer, then [`GameLoop::advance`] by the time
+    /// since the previous frame. Returns what `render` returned.
+    pub fn frame<S, R>(
+        &mut self,
+        state: &mut S,
+        update: impl FnMut(&mut S, f32),
+        render: impl FnOnce(&mut S, f32) -> R,
+    ) -> R {
+        let elapsed = self.timer.wait_and_get_delta();
+        self.fps = self.timer.tick();
+        self.advance(Duration::from_secs_f32(elapsed), state, update, render)
+    }
+
+    /// Account for `elapsed` more time: call `update` with the tick `dt`
+    /// for each tick now due, then `render` once with the leftover
+    /// [`GameLoop::alpha`]. Returns what `render` returned.
+    ///
+    /// [`GameLoop::frame`] calls this with the real frame time; call it
+    /// directly to drive the loop from a clock of your own, e.g. a fixed
+    /// step when rendering offline or in tests.
+    pub fn advance<S, R>(
+        &mut self,
+        elapsed: Duration,
+        state: &mut S,
+        mut update: impl FnMut(&mut S, f32),
+        render: impl FnOnce(&mut S, f32) -> R,
+    ) -> R {
+        self.accumulator += elapsed;
+        let dt = self.tick_dt();
+
+        let update_start = Instant::now();
+        let mut ran = 0;
+        while self.accumulator >= self.tick {
+            if ran == self.max_ticks_per_frame {
+                // Too far behind to catch up; keep less than a tick
+                self.accumulator = Duration::from_nanos(
+                    (self.accumulator.as_nanos() % self.tick.as_nanos()) as u64,
+                );
+                break;
+            }
+            update(state, dt);
+            self.accumulator -= self.tick;
+            self.ticks += 1;
+            ran += 1;
+        }
+        self.update_time = update_start.elapsed();
+
+        self.alpha = (self.accumulator.as_secs_f64() / self.tick.as_secs_f64()) as f32;
+        let render_start = Instant::now();
+        let result = render(state, self.alpha);
+        self.render_time = render_start.elapsed();
+        result
+    }
+}
+
+impl Default for GameLoop {
+    /// Simulate at [`FPS`](super::FPS) ticks per second.
+    fn default() -> Self {
+        Self::new(super::FPS as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `frames` frames of `frame_time` each at 50 ticks per second,
+    /// returning the sim position after each tick: something moving at
+    /// 1 unit per second.
+    fn positions(frame_time: Duration, frames: u32) -> Vec<f32> {
+        let mut game_loop = GameLoop::new(50.0);
+        let mut state = (0.0f32, Vec::new());
+        for _ in 0..frames {
+            game_loop.advance(
+                frame_time,
+                &mut state,
+                |(position, log), dt| {
+                    *position += dt;
+                    log.push(*position);
+                },
+                |_, alpha| assert!((0.0..1.0).contains(&alpha)),
+            );
+        }
+        state.1
+    }
+
+    #[test]
+    fn ticks_do_not_depend_on_the_frame_rate() {
+        // One second drawn at 25, 100 and 8 frames per second
+        let slow = positions(Duration::from_millis(40), 25);
+        let fast = positions(Duration::from_millis(10), 100);
+        let stutter = positions(Duration::from_millis(125), 8);
+        assert_eq!(slow.len(), 50);
+        assert_eq!(slow, fast);
+        assert_eq!(slow, stutter);
+    }
+
+    #[test]
+    fn alpha_is_the_leftover_fraction_of_a_tick() {
+        let mut game_loop = GameLoop::new(10.0);
+        let mut ticks = 0;
+        let alpha = game_loop.advance(
+            Duration::from_millis(250),
+            &mut ticks,
+            |t, _| *t += 1,
+            |_, a| a,
+        );
+        assert_eq!(ticks, 2);
+        assert!((alpha - 0.5).abs() < 1e-6);
+        assert_eq!(game_loop.alpha(), alpha);
+    }
+
+    #[test]
+    fn falling_far_behind_drops_time() {
+        let mut game_loop = GameLoop::new(60.0);
+        game_loop.set_max_ticks_per_frame(4);
+        let mut ticks = 0;
+        game_loop.advance(
+            Duration::from_secs(10),
+            &mut ticks,
+            |t, _| *t += 1,
+            |_, _| (),
+        );
+        assert_eq!(ticks, 4);
+        // Caught up from there on
+        game_loop.advance(game_loop.tick, &mut ticks, |t, _| *t += 1, |_, _| ());
+        assert_eq!(ticks, 5);
+        assert_eq!(game_loop.ticks(), 5);
+    }
+
+    #[test]
+    fn absurd_tick_rates_keep_a_nonzero_tick() {
+        let mut game_loop = GameLoop::new(4e9);
+        assert_eq!(game_loop.tick, Duration::from_nanos(1));
+        let mut ticks = 0;
+        let alpha = game_loop.advance(
+            Duration::from_millis(1),
+            &mut ticks,
+            |t, _| *t += 1,
+            |_, a| a,
+        );
+        assert_eq!(ticks, GameLoop::DEFAULT_MAX_TICKS_PER_FRAME);
+        assert_eq!(alpha, 0.0);
+    }
+}
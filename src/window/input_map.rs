@@ -0,0 +1,193 @@
+//! Rebindable mapping from discrete keys to actions.
+
+use std::collections::HashMap;
+
+use super::{Key, WindowEvent};
+use crate::engine::RenderMode;
+
+/// Something a discrete key press asks the app to do. The windows turn
+/// bound keys into [`WindowEvent::Action`] through their [`InputMap`];
+/// what each action does is up to the app (the demo's handling is in
+/// `main.rs`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    SetRenderMode(RenderMode),
+    ToggleCulling,
+    ToggleGrid,
+    CycleRasterizer,
+    CycleShading,
+    CycleTexture,
+    CycleDebugView,
+    CycleDebugDraw,
+    ToggleHiddenSurface,
+    ToggleMouseCapture,
+    ToggleWireframeAa,
+    ToggleHud,
+    ToggleProfiler,
+    TogglePalette,
+    ToggleSupersampling,
+    CycleFrameCap,
+    TogglePostEffect,
+    Screenshot,
+    ToggleRecording,
+    ToggleCameraPath,
+    ZoomIn,
+    ZoomOut,
+    ToggleProgressive,
+    SwitchCamera,
+    ToggleCameraMode,
+    ShakeCamera,
+    FreezeCulling,
+    ToggleRetro,
+}
+
+/// Which [`Action`] each [`Key`] triggers. Keys can be bound to at most
+/// one action each; an action can have any number of keys.
+///
+/// ```ignore
+/// // Cycle rasterizers with Tab instead of R
+/// let map = window.input_map_mut();
+/// map.unbind(Key::R);
+/// map.bind(Key::Tab, Action::CycleRasterizer);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputMap {
+    bindings: HashMap<Key, Action>,
+}
+
+impl InputMap {
+    /// A map with no bindings: every key arrives as
+    /// [`WindowEvent::KeyPress`].
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Make `key` trigger `action`, returning what it was bound to before.
+    pub fn bind(&mut self, key: Key, action: Action) -> Option<Action> {
+        self.bindings.insert(key, action)
+    }
+
+    /// Remove the binding for `key`, returning the action it had.
+    pub fn unbind(&mut self, key: Key) -> Option<Action> {
+        self.bindings.remove(&key)
+    }
+
+    /// The action `key` triggers, if any.
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings.get(&key).copied()
+    }
+
+    /// Every key bound to `action`, in no particular order.
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = Key> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, &bound)| bound == action)
+            .map(|(&key, _)| key)
+    }
+
+    /// The event a press of `key` produces: its action if it is bound, the
+    /// raw key otherwise.
+    pub fn event_for(&self, key: Key) -> WindowEvent {
+        match self.action(key) {
+            Some(action) => WindowEvent::Action { action, key },
+            None => WindowEvent::KeyPress(key),
+        }
+    }
+}
+
+impl Default for InputMap {
+    /// The demo's controls.
+    fn default() -> Self {
+        let bindings = [
+            (Key::Escape, Action::Quit),
+            (Key::Num1, Action::SetRenderMode(RenderMode::Wireframe)),
+            (
+                Key::Num2,
+                Action::SetRenderMode(RenderMode::WireframeVertices),
+            ),
+            (
+                Key::Num3,
+                Action::SetRenderMode(RenderMode::FilledWireframe),
+            ),
+            (
+                Key::Num4,
+                Action::SetRenderMode(RenderMode::FilledWireframeVertices),
+            ),
+            (Key::Num5, Action::SetRenderMode(RenderMode::Filled)),
+            (Key::C, Action::ToggleCulling),
+            (Key::G, Action::ToggleGrid),
+            (Key::R, Action::CycleRasterizer),
+            (Key::F, Action::CycleShading),
+            (Key::T, Action::CycleTexture),
+            (Key::X, Action::CycleDebugView),
+            (Key::N, Action::CycleDebugDraw),
+            (Key::Z, Action::ToggleHiddenSurface),
+            (Key::M, Action::ToggleMouseCapture),
+            (Key::H, Action::ToggleHud),
+            (Key::J, Action::ToggleProfiler),
+            (Key::B, Action::TogglePalette),
+            (Key::P, Action::ToggleSupersampling),
+            (Key::U, Action::CycleFrameCap),
+            (Key::V, Action::TogglePostEffect),
+            (Key::F12, Action::Screenshot),
+            (Key::K, Action::ToggleRecording),
+            (Key::Y, Action::ToggleCameraPath),
+            (Key::Plus, Action::ZoomIn),
+            (Key::Minus, Action::ZoomOut),
+            (Key::I, Action::ToggleProgressive),
+            (Key::O, Action::SwitchCamera),
+            (Key::F1, Action::ToggleCameraMode),
+            (Key::F2, Action::ShakeCamera),
+            (Key::F3, Action::FreezeCulling),
+            (Key::F4, Action::ToggleRetro),
+            (Key::F5, Action::ToggleWireframeAa),
+        ];
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_map_binds_the_demo_keys() {
+        let map = InputMap::default();
+        assert_eq!(map.action(Key::R), Some(Action::CycleRasterizer));
+        assert_eq!(map.action(Key::Escape), Some(Action::Quit));
+        assert_eq!(map.action(Key::Tab), None);
+        assert_eq!(
+            map.event_for(Key::G),
+            WindowEvent::Action {
+                action: Action::ToggleGrid,
+                key: Key::G
+            }
+        );
+        assert_eq!(map.event_for(Key::Tab), WindowEvent::KeyPress(Key::Tab));
+    }
+
+    #[test]
+    fn keys_rebind_and_unbind() {
+        let mut map = InputMap::default();
+        assert_eq!(map.unbind(Key::R), Some(Action::CycleRasterizer));
+        assert_eq!(map.bind(Key::Tab, Action::CycleRasterizer), None);
+        assert_eq!(map.event_for(Key::R), WindowEvent::KeyPress(Key::R));
+        assert_eq!(
+            map.keys_for(Action::CycleRasterizer).collect::<Vec<_>>(),
+            [Key::Tab]
+        );
+
+        // Binding a bound key replaces its action
+        assert_eq!(
+            map.bind(Key::Tab, Action::Quit),
+            Some(Action::CycleRasterizer)
+        );
+        assert_eq!(map.keys_for(Action::CycleRasterizer).count(), 0);
+        assert_eq!(InputMap::empty().action(Key::Escape), None);
+    }
+}
@@ -0,0 +1,469 @@
+//! Windowing, event handling, input state, and frame presentation.
+//!
+//! The types here are backend-independent: [`WindowEvent`] and [`InputState`]
+//! describe input, [`FrameTimer`] paces frames and keeps frame time
+//! statistics, [`GameLoop`] steps a simulation at a fixed rate on top of them, and the
+//! [`Presenter`] trait is how a finished frame leaves the engine. Backends:
+//!
+//! - [`Window`] — an SDL2 window (feature `sdl2-window`, on by default).
+//! - `WinitWindow` — a winit window presenting through softbuffer (feature
+//!   `winit-window`; desktop only, while `WinitInput` event translation
+//!   also builds for wasm).
+//! - [`PngPresenter`] — writes each frame to a numbered PNG file, for
+//!   headless use.
+//!
+//! [`WindowBackend`] covers what the two windows have in common, for apps
+//! written once for either.
+//!
+//! Discrete key presses go through an [`InputMap`], which turns bound keys
+//! into [`Action`]s so apps can rebind controls.
+//!
+//! [`FrameRecorder`] saves a fixed-length run of frames to PNG on a
+//! background thread, for capturing animations from an interactive session.
+//!
+//! # Input System
+//!
+//! The window tracks both discrete events ([`WindowEvent`]) and continuous input
+//! state ([`InputState`]). Use `poll_events()` for one-shot events like quit or
+//! resize, which returns every event since the last call in the order they
+//! happened, and `input_state()` for held keys and mouse movement.
+//!
+//! # Mouse Capture
+//!
+//! Call `capture_mouse()` to enable FPS-style mouse look. When captured:
+//! - The cursor is hidden
+//! - Mouse movement is reported as relative deltas
+//! - The mouse is constrained to the window
+//!
+//! Call `release_mouse()` to restore normal mouse behavior.
+
+use std::fmt;
+
+mod frame_timer;
+mod game_loop;
+mod input_map;
+mod png;
+mod recorder;
+#[cfg(feature = "sdl2-window")]
+mod sdl;
+#[cfg(feature = "winit-window")]
+mod winit;
+
+#[cfg(all(feature = "winit-window", not(target_arch = "wasm32")))]
+pub use self::winit::WinitWindow;
+#[cfg(feature = "winit-window")]
+pub use self::winit::{map_key as map_winit_key, WinitInput};
+pub use frame_timer::FrameTimer;
+#[allow(deprecated)]
+pub use frame_timer::{FpsCounter, FrameLimiter};
+pub use game_loop::GameLoop;
+pub use input_map::{Action, InputMap};
+pub use png::PngPresenter;
+pub(crate) use png::{save_png, screenshot_path};
+pub use recorder::{FrameRecorder, RecordingStats};
+#[cfg(feature = "sdl2-window")]
+pub use sdl::Window;
+
+pub const WINDOW_WIDTH: u32 = 1920;
+pub const WINDOW_HEIGHT: u32 = 1080;
+pub const FPS: u64 = 60;
+pub const FRAME_TARGET_TIME: f64 = 1000.0 / FPS as f64;
+
+// =============================================================================
+// Window Events (Discrete)
+// =============================================================================
+
+/// Discrete window events returned by `poll_events()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEvent {
+    Quit,
+    Resize(u32, u32),
+    /// A key the window's [`InputMap`] has no binding for.
+    KeyPress(Key),
+    /// A bound key, with the action its [`InputMap`] binding names.
+    Action {
+        action: Action,
+        key: Key,
+    },
+    LeftMouseDown,
+    RightMouseDown,
+}
+
+/// Keys that trigger discrete events.
+///
+/// These are for one-shot actions (toggle modes, etc).
+/// For continuous input (movement), use [`InputState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Num1,
+    Num2,
+    Num3,
+    Num4,
+    Num5,
+    C,
+    G,
+    M,
+    R,
+    F,
+    T,
+    O,
+    L,
+    P,
+    H,
+    B,
+    U,
+    V,
+    K,
+    Y,
+    I,
+    X,
+    Z,
+    N,
+    J,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F12,
+    Tab,
+    Plus,
+    Minus,
+    Escape,
+}
+
+// =============================================================================
+// Input State (Continuous)
+// =============================================================================
+
+/// Continuous input state for movement and camera control.
+///
+/// Updated each frame by `poll_events()`. Use this for:
+/// - WASD movement (held keys)
+/// - Mouse look (relative movement when captured)
+/// - Roll control (Q/E keys)
+/// - Mouse buttons, cursor position, and scroll wheel
+///
+/// # Example
+///
+/// ```ignore
+/// let input = window.input_state();
+/// if input.forward {
+///     camera.move_forward(speed * delta_time);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InputState {
+    // Movement keys
+    /// W key held - move forward
+    pub forward: bool,
+    /// S key held - move backward
+    pub back: bool,
+    /// A key held - strafe left
+    pub left: bool,
+    /// D key held - strafe right
+    pub right: bool,
+    /// Space key held - move up
+    pub up: bool,
+    /// Left Shift or Left Ctrl held - move down
+    pub down: bool,
+    /// Left Alt held - move faster
+    pub sprint: bool,
+
+    // Roll keys
+    /// Q key held - roll left
+    pub roll_left: bool,
+    /// E key held - roll right
+    pub roll_right: bool,
+
+    /// L key held - mouse movement aims the light instead of the camera
+    pub aim_light: bool,
+
+    // Mouse
+    /// Relative mouse movement this frame (dx, dy).
+    /// Populated when the mouse is captured or while the left or middle
+    /// button or the light-aiming key is held (drag).
+    pub mouse_delta: (i32, i32),
+    /// Scroll wheel movement this frame (positive = away from the user).
+    pub scroll_delta: f32,
+    /// Absolute cursor position in window pixels, tracked even when not captured.
+    pub cursor_position: (i32, i32),
+    /// Left mouse button held.
+    pub left_mouse_down: bool,
+    /// Middle mouse button held.
+    pub middle_mouse_down: bool,
+}
+
+impl InputState {
+    /// Resets per-frame state (mouse delta, scroll).
+    ///
+    /// Backends call this at the start of each frame before processing events.
+    pub fn reset_per_frame(&mut self) {
+        self.mouse_delta = (0, 0);
+        self.scroll_delta = 0.0;
+    }
+}
+
+// =============================================================================
+// Presentation
+// =============================================================================
+
+/// Errors from [`Presenter::present`] and [`FrameRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresentError {
+    /// The buffer length doesn't match the dimensions it was presented with.
+    BufferSizeMismatch {
+        width: u32,
+        height: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// Creating or updating the streaming texture (or surface) failed.
+    Texture(String),
+    /// Copying the frame to the window failed.
+    Canvas(String),
+    /// Encoding or writing an image file failed.
+    Image(String),
+}
+
+impl fmt::Display for PresentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresentError::BufferSizeMismatch {
+                width,
+                height,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "frame buffer is {actual} bytes, expected {expected} for {width}x{height}"
+            ),
+            PresentError::Texture(e) => write!(f, "texture error: {e}"),
+            PresentError::Canvas(e) => write!(f, "canvas error: {e}"),
+            PresentError::Image(e) => write!(f, "image error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PresentError {}
+
+impl From<PresentError> for String {
+    fn from(e: PresentError) -> Self {
+        e.to_string()
+    }
+}
+
+/// A destination for finished frames.
+///
+/// Frames are ARGB8888 pixels (one native-endian `u32` each) as returned
+/// by `Engine::frame_buffer`, `width * height * 4` bytes long.
+pub trait Presenter {
+    /// Show or store one `width` x `height` frame.
+    fn present(&mut self, frame: &[u8], width: u32, height: u32) -> Result<(), PresentError>;
+}
+
+/// A window an app drives from its own loop: events and input in, frames
+/// out through [`Presenter`]. The SDL `Window` and `WinitWindow` both
+/// implement it, so an app can be written once for either backend.
+pub trait WindowBackend: Presenter {
+    /// Every discrete event since the last call, in the order they
+    /// happened.
+    fn poll_events(&mut self) -> Vec<WindowEvent>;
+
+    /// Held keys and mouse movement, updated by `poll_events`.
+    fn input_state(&self) -> &InputState;
+
+    /// Capture the mouse for mouse look, or release it.
+    fn toggle_mouse_capture(&mut self);
+
+    fn is_mouse_captured(&self) -> bool;
+
+    /// Record the window's new size after a [`WindowEvent::Resize`].
+    fn resize(&mut self, width: u32, height: u32);
+
+    /// The pixel of the last presented frame under window point `(x, y)`;
+    /// `None` over letterbox bars.
+    fn window_to_frame(&self, x: i32, y: i32) -> Option<(u32, u32)>;
+
+    fn width(&self) -> u32;
+
+    fn height(&self) -> u32;
+
+    fn set_title(&mut self, title: &str);
+}
+
+/// Check that an ARGB8888 buffer holds exactly `width * height` pixels.
+fn validate_frame(buffer: &[u8], width: u32, height: u32) -> Result<(), PresentError> {
+    let expected = width as usize * height as usize * 4;
+    if buffer.len() != expected {
+        return Err(PresentError::BufferSizeMismatch {
+            width,
+            height,
+            expected,
+            actual: buffer.len(),
+        });
+    }
+    Ok(())
+}
+
+/// How the windows fit a frame into a window of a different size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScaleMode {
+    /// Fill the whole window, distorting the frame when the aspect ratios
+    /// differ.
+    #[default]
+    Stretch,
+    /// The largest size that fits with the frame's aspect ratio kept,
+    /// centered, with black bars over the rest.
+    FitLetterbox,
+    /// As [`ScaleMode::FitLetterbox`], but only whole multiples of the
+    /// frame size, so every frame pixel covers the same number of window
+    /// pixels. A frame larger than the window shrinks to fit instead.
+    IntegerScale,
+}
+
+/// Where a presented frame lands in the window, in window pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ScaleMode {
+    /// Where a `frame`-sized image goes in a `window`-sized one, both as
+    /// `(width, height)`.
+    pub fn destination(self, frame: (u32, u32), window: (u32, u32)) -> PresentRect {
+        let ((frame_width, frame_height), (window_width, window_height)) = (frame, window);
+        let full = PresentRect {
+            x: 0,
+            y: 0,
+            width: window_width,
+            height: window_height,
+        };
+        if frame_width == 0 || frame_height == 0 {
+            return full;
+        }
+
+        let whole = (window_width / frame_width).min(window_height / frame_height);
+        let (width, height) = match self {
+            ScaleMode::Stretch => return full,
+            ScaleMode::IntegerScale if whole > 0 => (frame_width * whole, frame_height * whole),
+            ScaleMode::FitLetterbox | ScaleMode::IntegerScale => {
+                // Compare in integers: whichever side limits the size gets
+                // the window's full extent, the other rounds down
+                let (fw, fh) = (frame_width as u64, frame_height as u64);
+                let (ww, wh) = (window_width as u64, window_height as u64);
+                if ww * fh <= wh * fw {
+                    (window_width, (ww * fh / fw) as u32)
+                } else {
+                    ((wh * fw / fh) as u32, window_height)
+                }
+            }
+        };
+        PresentRect {
+            x: (window_width - width) / 2,
+            y: (window_height - height) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+impl PresentRect {
+    /// The pixel of a `frame`-sized image drawn here that window point
+    /// `(x, y)` falls on, or `None` in the bars around it. Matches the
+    /// nearest-neighbor lookup the windows scale with, so clicks pick the
+    /// pixel they land on.
+    pub fn window_to_frame(&self, frame: (u32, u32), x: i32, y: i32) -> Option<(u32, u32)> {
+        let (dx, dy) = (x as i64 - self.x as i64, y as i64 - self.y as i64);
+        if dx < 0 || dy < 0 || dx >= self.width as i64 || dy >= self.height as i64 {
+            return None;
+        }
+        Some((
+            (dx * frame.0 as i64 / self.width as i64) as u32,
+            (dy * frame.1 as i64 / self.height as i64) as u32,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_frame_accepts_matching_buffer() {
+        assert_eq!(validate_frame(&[0; 4 * 6], 2, 3), Ok(()));
+        assert_eq!(validate_frame(&[], 0, 0), Ok(()));
+    }
+
+    #[test]
+    fn validate_frame_rejects_wrong_size() {
+        // A frame rendered at the old size presented after a resize
+        let stale = vec![0u8; 4 * 4 * 4];
+        assert_eq!(
+            validate_frame(&stale, 8, 4),
+            Err(PresentError::BufferSizeMismatch {
+                width: 8,
+                height: 4,
+                expected: 128,
+                actual: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn letterbox_keeps_the_aspect_ratio() {
+        // 4:3 in 16:9: bars left and right
+        let rect = ScaleMode::FitLetterbox.destination((640, 480), (1920, 1080));
+        assert_eq!(
+            rect,
+            PresentRect {
+                x: 240,
+                y: 0,
+                width: 1440,
+                height: 1080
+            }
+        );
+        // 16:9 in 4:3: bars above and below
+        let rect = ScaleMode::FitLetterbox.destination((1600, 900), (800, 600));
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (0, 75, 800, 450));
+
+        let rect = ScaleMode::Stretch.destination((640, 480), (1920, 1080));
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (0, 0, 1920, 1080)
+        );
+    }
+
+    #[test]
+    fn integer_scale_snaps_to_whole_multiples() {
+        // 4.5x fits the height; 4x is used
+        let rect = ScaleMode::IntegerScale.destination((320, 240), (1920, 1080));
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (320, 60, 1280, 960)
+        );
+        // Too big for one whole multiple, it shrinks like letterboxing
+        assert_eq!(
+            ScaleMode::IntegerScale.destination((1600, 900), (800, 600)),
+            ScaleMode::FitLetterbox.destination((1600, 900), (800, 600))
+        );
+    }
+
+    #[test]
+    fn window_points_map_back_into_the_frame() {
+        let frame = (640, 480);
+        let rect = ScaleMode::FitLetterbox.destination(frame, (1920, 1080));
+        assert_eq!(rect.window_to_frame(frame, 239, 500), None, "in the bar");
+        assert_eq!(rect.window_to_frame(frame, 1680, 500), None, "in the bar");
+        assert_eq!(rect.window_to_frame(frame, 240, 0), Some((0, 0)));
+        assert_eq!(rect.window_to_frame(frame, 1679, 1079), Some((639, 479)));
+        // 2.25 window pixels per frame pixel
+        assert_eq!(
+            rect.window_to_frame(frame, 240 + 720, 540),
+            Some((320, 240))
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Headless presenter that writes frames to PNG files.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{validate_frame, PresentError, Presenter};
+use crate::render::renderer::{export_pixels, ByteOrder};
+
+/// Writes every presented frame to `<dir>/<prefix><index>.png`.
+///
+/// Indices are zero-padded to five digits so the files sort in frame order:
+///
+/// ```ignore
+/// let mut presenter = PngPresenter::new("frames")?;
+/// presenter.present(engine.frame_buffer(), width, height)?; // frames/frame_00000.png
+/// ```
+pub struct PngPresenter {
+    dir: PathBuf,
+    prefix: String,
+    frames_written: u32,
+}
+
+impl PngPresenter {
+    /// Create a presenter writing into `dir`, creating the directory if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, PresentError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| PresentError::Image(e.to_string()))?;
+        Ok(Self {
+            dir,
+            prefix: "frame_".to_string(),
+            frames_written: 0,
+        })
+    }
+
+    /// Use `prefix` instead of `frame_` for file names.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of frames written so far; also the index of the next file.
+    pub fn frames_written(&self) -> u32 {
+        self.frames_written
+    }
+
+    /// Path the frame with `index` is (or will be) written to.
+    pub fn frame_path(&self, index: u32) -> PathBuf {
+        self.dir.join(format!("{}{:05}.png", self.prefix, index))
+    }
+}
+
+impl Presenter for PngPresenter {
+    fn present(&mut self, frame: &[u8], width: u32, height: u32) -> Result<(), PresentError> {
+        validate_frame(frame, width, height)?;
+
+        let path = self.frame_path(self.frames_written);
+        save_png(&path, frame, width, height)?;
+        self.frames_written += 1;
+        Ok(())
+    }
+}
+
+/// Write a frame of ARGB8888 pixels in native byte order to a PNG file.
+pub(crate) fn save_png(
+    path: &Path,
+    frame: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<(), PresentError> {
+    let pixels = frame
+        .chunks_exact(4)
+        .map(|px| u32::from_ne_bytes([px[0], px[1], px[2], px[3]]));
+    let mut rgba = Vec::with_capacity(frame.len());
+    export_pixels(pixels, width, ByteOrder::Rgba8888, None, &mut rgba)
+        .map_err(|e| PresentError::Image(e.to_string()))?;
+
+    image::save_buffer(path, &rgba, width, height, image::ExtendedColorType::Rgba8)
+        .map_err(|e| PresentError::Image(e.to_string()))
+}
+
+/// A free path for a screenshot taken at `time`:
+/// `<dir>/screenshot_YYYYMMDD_HHMMSS.png` in UTC, with `_1`, `_2`, ...
+/// added before the extension if that name is taken.
+pub(crate) fn screenshot_path(dir: &Path, time: SystemTime) -> PathBuf {
+    let stem = format!("screenshot_{}", utc_timestamp(time));
+    let mut path = dir.join(format!("{stem}.png"));
+    let mut suffix = 0;
+    while path.exists() {
+        suffix += 1;
+        path = dir.join(format!("{stem}_{suffix}.png"));
+    }
+    path
+}
+
+/// `time` as `YYYYMMDD_HHMMSS` in UTC.
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, secs) = ((secs / 86_400) as i64, secs % 86_400);
+    // Days since 1970-01-01 to a Gregorian date (Howard Hinnant's
+    // civil_from_days), counting eras of 400 years from 0000-03-01
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}{month:02}{day:02}_{:02}{:02}{:02}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn writes_numbered_frames_with_matching_pixels() {
+        let dir = std::env::temp_dir().join(format!("russsty_png_{}", std::process::id()));
+        let mut presenter = PngPresenter::new(&dir).unwrap();
+
+        let pixels: [u32; 2] = [0xFF112233, 0x80FF0000];
+        let frame: Vec<u8> = pixels.iter().flat_map(|p| p.to_ne_bytes()).collect();
+        presenter.present(&frame, 2, 1).unwrap();
+        presenter.present(&frame, 2, 1).unwrap();
+        assert_eq!(presenter.frames_written(), 2);
+
+        let image = image::open(presenter.frame_path(1)).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (2, 1));
+        assert_eq!(image.get_pixel(0, 0).0, [0x11, 0x22, 0x33, 0xFF]);
+        assert_eq!(image.get_pixel(1, 0).0, [0xFF, 0x00, 0x00, 0x80]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn rejects_wrong_sized_frame() {
+        let dir = std::env::temp_dir().join(format!("russsty_png_bad_{}", std::process::id()));
+        let mut presenter = PngPresenter::new(&dir).unwrap();
+
+        let result = presenter.present(&[0; 12], 2, 2);
+        assert!(matches!(
+            result,
+            Err(PresentError::BufferSizeMismatch { .. })
+        ));
+        assert_eq!(presenter.frames_written(), 0);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn timestamps_are_utc_calendar_dates() {
+        let at = |secs| utc_timestamp(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(0), "19700101_000000");
+        assert_eq!(at(1_700_000_000), "20231114_221320");
+        // Leap day
+        assert_eq!(at(951_782_400 + 3661), "20000229_010101");
+    }
+
+    #[test]
+    fn screenshot_names_skip_taken_ones() {
+        let dir = std::env::temp_dir().join(format!("russsty_shots_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let first = screenshot_path(&dir, time);
+        assert_eq!(first, dir.join("screenshot_20231114_221320.png"));
+        std::fs::write(&first, b"").unwrap();
+        let second = screenshot_path(&dir, time);
+        assert_eq!(second, dir.join("screenshot_20231114_221320_1.png"));
+        std::fs::write(&second, b"").unwrap();
+        assert_eq!(
+            screenshot_path(&dir, time),
+            dir.join("screenshot_20231114_221320_2.png")
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
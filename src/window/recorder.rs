@@ -0,0 +1,257 @@
+//! Records a run of frames to numbered PNG files without stalling rendering.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use super::png::save_png;
+use super::{validate_frame, PresentError};
+
+/// Frames that may wait for the writer thread before new ones are dropped.
+const QUEUE_DEPTH: usize = 8;
+
+/// Frame counts for a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RecordingStats {
+    /// Frames accepted for writing; also the index of the next file.
+    pub captured: u32,
+    /// Frames the writer thread has saved so far.
+    pub written: u32,
+    /// Frames skipped because the writer had fallen `QUEUE_DEPTH` behind.
+    pub dropped: u32,
+}
+
+struct QueuedFrame {
+    path: PathBuf,
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+/// Writes up to `max_frames` frames to `<dir>/000.png`, `001.png`, ...
+///
+/// Encoding happens on a background thread fed through a bounded queue, so
+/// [`FrameRecorder::capture`] costs one frame copy. If the disk can't keep
+/// up, frames are dropped rather than blocking, and counted in
+/// [`RecordingStats::dropped`]; the files that are written stay numbered
+/// without gaps.
+///
+/// ```ignore
+/// let mut recorder = FrameRecorder::new("turntable", 120)?;
+/// while !recorder.is_complete() {
+///     engine.update();
+///     engine.render();
+///     let (width, height) = engine.frame_size();
+///     recorder.capture(engine.frame_buffer(), width, height)?;
+/// }
+/// let stats = recorder.finish()?;
+/// ```
+pub struct FrameRecorder {
+    dir: PathBuf,
+    max_frames: u32,
+    // File names are zero-padded to this many digits
+    digits: usize,
+    sender: Option<SyncSender<QueuedFrame>>,
+    writer: Option<JoinHandle<()>>,
+    captured: u32,
+    dropped: u32,
+    written: Arc<AtomicU32>,
+    // First write error from the writer thread
+    error: Arc<Mutex<Option<PresentError>>>,
+}
+
+impl FrameRecorder {
+    /// Start a recording into `dir`, creating the directory if needed.
+    pub fn new(dir: impl Into<PathBuf>, max_frames: u32) -> Result<Self, PresentError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| PresentError::Image(e.to_string()))?;
+
+        let (sender, receiver) = mpsc::sync_channel::<QueuedFrame>(QUEUE_DEPTH);
+        let written = Arc::new(AtomicU32::new(0));
+        let error = Arc::new(Mutex::new(None));
+        let writer = {
+            let written = Arc::clone(&written);
+            let error = Arc::clone(&error);
+            std::thread::Builder::new()
+                .name("frame-recorder".to_string())
+                .spawn(move || {
+                    for frame in receiver {
+                        match save_png(&frame.path, &frame.pixels, frame.width, frame.height) {
+                            Ok(()) => {
+                                written.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Err(e) => {
+                                error.lock().unwrap().get_or_insert(e);
+                            }
+                        }
+                    }
+                })
+                .map_err(|e| PresentError::Image(e.to_string()))?
+        };
+
+        let last_index = max_frames.saturating_sub(1);
+        Ok(Self {
+            dir,
+            max_frames,
+            digits: last_index.to_string().len().max(3),
+            sender: Some(sender),
+            writer: Some(writer),
+            captured: 0,
+            dropped: 0,
+            written,
+            error,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn max_frames(&self) -> u32 {
+        self.max_frames
+    }
+
+    /// Path the frame with `index` is (or will be) written to.
+    pub fn frame_path(&self, index: u32) -> PathBuf {
+        self.dir
+            .join(format!("{:0width$}.png", index, width = self.digits))
+    }
+
+    /// Whether `max_frames` frames have been captured.
+    pub fn is_complete(&self) -> bool {
+        self.captured >= self.max_frames
+    }
+
+    pub fn stats(&self) -> RecordingStats {
+        RecordingStats {
+            captured: self.captured,
+            written: self.written.load(Ordering::Relaxed),
+            dropped: self.dropped,
+        }
+    }
+
+    /// Queue an ARGB8888 frame for writing. Returns whether it was queued:
+    /// `false` once the recording is complete or when the frame was dropped.
+    pub fn capture(&mut self, frame: &[u8], width: u32, height: u32) -> Result<bool, PresentError> {
+        validate_frame(frame, width, height)?;
+        if self.is_complete() {
+            return Ok(false);
+        }
+        let Some(sender) = &self.sender else {
+            return Ok(false);
+        };
+
+        let queued = QueuedFrame {
+            path: self.frame_path(self.captured),
+            pixels: frame.to_vec(),
+            width,
+            height,
+        };
+        match sender.try_send(queued) {
+            Ok(()) => {
+                self.captured += 1;
+                Ok(true)
+            }
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                Ok(false)
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                self.dropped += 1;
+                // Leave the error in place for `finish` too
+                let error = self.error.lock().unwrap().clone();
+                Err(error.unwrap_or_else(|| {
+                    PresentError::Image("frame writer thread stopped".to_string())
+                }))
+            }
+        }
+    }
+
+    /// Wait for queued frames to be written and stop the writer thread.
+    /// Returns the final counts, or the first error writing a file.
+    pub fn finish(mut self) -> Result<RecordingStats, PresentError> {
+        self.join_writer();
+        match self.error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(self.stats()),
+        }
+    }
+
+    fn join_writer(&mut self) {
+        // Closing the channel ends the writer's loop once it drains
+        self.sender = None;
+        if let Some(writer) = self.writer.take() {
+            writer.join().ok();
+        }
+    }
+}
+
+impl Drop for FrameRecorder {
+    fn drop(&mut self) {
+        self.join_writer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pixel: u32) -> Vec<u8> {
+        [pixel, pixel]
+            .iter()
+            .flat_map(|p| p.to_ne_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn writes_zero_padded_frames_up_to_the_limit() {
+        let dir = std::env::temp_dir().join(format!("russsty_recorder_{}", std::process::id()));
+        let mut recorder = FrameRecorder::new(&dir, 3).unwrap();
+
+        for pixel in [0xFFFF0000, 0xFF00FF00, 0xFF0000FF, 0xFFFFFFFF] {
+            recorder.capture(&frame(pixel), 2, 1).unwrap();
+        }
+        assert!(recorder.is_complete());
+        let last = recorder.frame_path(2);
+        assert!(last.ends_with("002.png"));
+
+        let stats = recorder.finish().unwrap();
+        assert_eq!(
+            stats,
+            RecordingStats {
+                captured: 3,
+                written: 3,
+                dropped: 0
+            }
+        );
+        let image = image::open(&last).unwrap().to_rgba8();
+        assert_eq!(image.get_pixel(1, 0).0, [0x00, 0x00, 0xFF, 0xFF]);
+        assert!(!dir.join("003.png").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn pads_to_the_width_of_the_last_index() {
+        let dir = std::env::temp_dir().join(format!("russsty_recorder_pad_{}", std::process::id()));
+        let recorder = FrameRecorder::new(&dir, 1500).unwrap();
+        assert!(recorder.frame_path(7).ends_with("0007.png"));
+        drop(recorder);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn rejects_wrong_sized_frame() {
+        let dir = std::env::temp_dir().join(format!("russsty_recorder_bad_{}", std::process::id()));
+        let mut recorder = FrameRecorder::new(&dir, 3).unwrap();
+        assert!(matches!(
+            recorder.capture(&[0; 12], 2, 2),
+            Err(PresentError::BufferSizeMismatch { .. })
+        ));
+        assert_eq!(recorder.stats().captured, 0);
+        drop(recorder);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
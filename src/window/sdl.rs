@@ -0,0 +1,536 @@
+//! SDL2 window backend.
+//!
+//! [`Window`] owns the SDL context, translates SDL events into
+//! [`WindowEvent`] and [`InputState`], and presents frames through a
+//! streaming texture. Only compiled with the `sdl2-window` feature.
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+
+use super::{
+    validate_frame, InputMap, InputState, Key, PresentError, PresentRect, Presenter, ScaleMode,
+    WindowBackend, WindowEvent,
+};
+use crate::error::Error;
+
+/// Translates SDL events into [`WindowEvent`] and [`InputState`], apart
+/// from the SDL context so a frame's events can be fed in directly.
+#[derive(Debug, Default)]
+struct SdlInput {
+    input_state: InputState,
+    input_map: InputMap,
+    mouse_captured: bool,
+}
+
+impl SdlInput {
+    /// Reset per-frame state, then translate one frame's `events` in order.
+    fn frame(&mut self, events: impl IntoIterator<Item = Event>) -> Vec<WindowEvent> {
+        self.input_state.reset_per_frame();
+
+        let mut result = Vec::new();
+        for event in events {
+            match event {
+                Event::Quit { .. } => result.push(WindowEvent::Quit),
+
+                Event::Window {
+                    win_event: sdl2::event::WindowEvent::Resized(w, h),
+                    ..
+                } => result.push(WindowEvent::Resize(w as u32, h as u32)),
+
+                // Key down - update continuous state and report discrete keys
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    repeat: false,
+                    ..
+                } => {
+                    self.update_key_state(keycode, true);
+                    if let Some(key) = map_keycode(keycode) {
+                        result.push(self.input_map.event_for(key));
+                    }
+                }
+
+                // Key up - update continuous state only
+                Event::KeyUp {
+                    keycode: Some(keycode),
+                    ..
+                } => {
+                    self.update_key_state(keycode, false);
+                }
+
+                // Mouse motion - cursor always, deltas when captured or dragging
+                Event::MouseMotion {
+                    x, y, xrel, yrel, ..
+                } => {
+                    self.input_state.cursor_position = (x, y);
+                    let dragging = self.input_state.left_mouse_down
+                        || self.input_state.middle_mouse_down
+                        || self.input_state.aim_light;
+                    if self.mouse_captured || dragging {
+                        self.input_state.mouse_delta.0 += xrel;
+                        self.input_state.mouse_delta.1 += yrel;
+                    }
+                }
+
+                // Mouse wheel - accumulate scroll for zoom controls
+                Event::MouseWheel {
+                    precise_y,
+                    direction,
+                    ..
+                } => {
+                    let sign = match direction {
+                        sdl2::mouse::MouseWheelDirection::Flipped => -1.0,
+                        _ => 1.0,
+                    };
+                    self.input_state.scroll_delta += precise_y * sign;
+                }
+
+                // Left/middle buttons - held state, plus discrete left and
+                // right clicks
+                Event::MouseButtonDown {
+                    mouse_btn, x, y, ..
+                } => {
+                    self.input_state.cursor_position = (x, y);
+                    match mouse_btn {
+                        sdl2::mouse::MouseButton::Left => {
+                            self.input_state.left_mouse_down = true;
+                            result.push(WindowEvent::LeftMouseDown);
+                        }
+                        sdl2::mouse::MouseButton::Middle => {
+                            self.input_state.middle_mouse_down = true;
+                        }
+                        sdl2::mouse::MouseButton::Right => {
+                            result.push(WindowEvent::RightMouseDown);
+                        }
+                        _ => {}
+                    }
+                }
+
+                Event::MouseButtonUp { mouse_btn, .. } => match mouse_btn {
+                    sdl2::mouse::MouseButton::Left => self.input_state.left_mouse_down = false,
+                    sdl2::mouse::MouseButton::Middle => self.input_state.middle_mouse_down = false,
+                    _ => {}
+                },
+
+                _ => {}
+            }
+        }
+
+        result
+    }
+
+    /// Updates continuous key state based on key press/release.
+    fn update_key_state(&mut self, keycode: Keycode, pressed: bool) {
+        match keycode {
+            // Movement
+            Keycode::W => self.input_state.forward = pressed,
+            Keycode::S => self.input_state.back = pressed,
+            Keycode::A => self.input_state.left = pressed,
+            Keycode::D => self.input_state.right = pressed,
+            Keycode::Space => self.input_state.up = pressed,
+            Keycode::LShift | Keycode::LCtrl => self.input_state.down = pressed,
+            Keycode::LAlt => self.input_state.sprint = pressed,
+
+            // Roll
+            Keycode::Q => self.input_state.roll_left = pressed,
+            Keycode::E => self.input_state.roll_right = pressed,
+
+            // Hold to aim the light
+            Keycode::L => self.input_state.aim_light = pressed,
+
+            _ => {}
+        }
+    }
+}
+
+/// Maps SDL keycode to discrete key event (if applicable).
+fn map_keycode(keycode: Keycode) -> Option<Key> {
+    match keycode {
+        Keycode::Num1 => Some(Key::Num1),
+        Keycode::Num2 => Some(Key::Num2),
+        Keycode::Num3 => Some(Key::Num3),
+        Keycode::Num4 => Some(Key::Num4),
+        Keycode::Num5 => Some(Key::Num5),
+        Keycode::C => Some(Key::C),
+        Keycode::G => Some(Key::G),
+        Keycode::M => Some(Key::M),
+        Keycode::R => Some(Key::R),
+        Keycode::F => Some(Key::F),
+        Keycode::T => Some(Key::T),
+        Keycode::O => Some(Key::O),
+        Keycode::L => Some(Key::L),
+        Keycode::P => Some(Key::P),
+        Keycode::H => Some(Key::H),
+        Keycode::B => Some(Key::B),
+        Keycode::U => Some(Key::U),
+        Keycode::V => Some(Key::V),
+        Keycode::K => Some(Key::K),
+        Keycode::Y => Some(Key::Y),
+        Keycode::I => Some(Key::I),
+        Keycode::X => Some(Key::X),
+        Keycode::Z => Some(Key::Z),
+        Keycode::N => Some(Key::N),
+        Keycode::J => Some(Key::J),
+        Keycode::F1 => Some(Key::F1),
+        Keycode::F2 => Some(Key::F2),
+        Keycode::F3 => Some(Key::F3),
+        Keycode::F4 => Some(Key::F4),
+        Keycode::F5 => Some(Key::F5),
+        Keycode::F12 => Some(Key::F12),
+        Keycode::Tab => Some(Key::Tab),
+        Keycode::Equals | Keycode::Plus | Keycode::KpPlus => Some(Key::Plus),
+        Keycode::Minus | Keycode::KpMinus => Some(Key::Minus),
+        Keycode::Escape => Some(Key::Escape),
+        _ => None,
+    }
+}
+
+pub struct Window {
+    // SDL2 resources
+    canvas: sdl2::render::Canvas<sdl2::video::Window>,
+    texture_creator: Box<sdl2::render::TextureCreator<sdl2::video::WindowContext>>,
+    texture: sdl2::render::Texture<'static>,
+    // Size the streaming texture was created with; may lag the window size
+    texture_width: u32,
+    texture_height: u32,
+    event_pump: sdl2::EventPump,
+    timer_subsystem: sdl2::TimerSubsystem,
+    sdl_context: sdl2::Sdl,
+
+    // Window state
+    width: u32,
+    height: u32,
+    scale_mode: ScaleMode,
+
+    // Input state
+    input: SdlInput,
+}
+
+impl Window {
+    pub fn new(title: &str, width: u32, height: u32) -> Result<Self, Error> {
+        let sdl_context = sdl2::init().map_err(Error::Window)?;
+        let video_subsystem = sdl_context.video().map_err(Error::Window)?;
+        let timer_subsystem = sdl_context.timer().map_err(Error::Window)?;
+
+        let window = video_subsystem
+            .window(title, width, height)
+            .position_centered()
+            .resizable()
+            .build()
+            .map_err(|e| Error::Window(e.to_string()))?;
+
+        let canvas = window
+            .into_canvas()
+            .build()
+            .map_err(|e| Error::Window(e.to_string()))?;
+        let texture_creator = Box::new(canvas.texture_creator());
+        let event_pump = sdl_context.event_pump().map_err(Error::Window)?;
+
+        // SAFETY: texture_creator is heap-allocated and lives as long as Window.
+        // We ensure texture is dropped before texture_creator by struct field order.
+        let texture_creator_ref: &'static sdl2::render::TextureCreator<sdl2::video::WindowContext> =
+            unsafe { &*(texture_creator.as_ref() as *const _) };
+        let texture = texture_creator_ref
+            .create_texture_streaming(PixelFormatEnum::ARGB8888, width, height)
+            .map_err(|e| Error::Window(e.to_string()))?;
+
+        Ok(Self {
+            sdl_context,
+            canvas,
+            texture_creator,
+            texture,
+            texture_width: width,
+            texture_height: height,
+            event_pump,
+            timer_subsystem,
+            width,
+            height,
+            scale_mode: ScaleMode::default(),
+            input: SdlInput::default(),
+        })
+    }
+
+    // =========================================================================
+    // Event Polling
+    // =========================================================================
+
+    /// Polls for events and updates input state.
+    ///
+    /// Returns every discrete event (quit, resize, key press) since the last
+    /// call, in the order they happened. Key presses come out as
+    /// [`WindowEvent::Action`] where the [`InputMap`] binds them.
+    /// Continuous input (WASD, mouse) is available via `input_state()`.
+    ///
+    /// Call this once per frame at the start of your game loop.
+    pub fn poll_events(&mut self) -> Vec<WindowEvent> {
+        let events: Vec<Event> = self.event_pump.poll_iter().collect();
+        self.input.frame(events)
+    }
+
+    // =========================================================================
+    // Input State Access
+    // =========================================================================
+
+    /// Returns the current continuous input state.
+    ///
+    /// Use this for movement controls (WASD), roll (Q/E), and mouse look.
+    pub fn input_state(&self) -> &InputState {
+        &self.input.input_state
+    }
+
+    /// The key bindings `poll_events` reports actions through.
+    pub fn input_map(&self) -> &InputMap {
+        &self.input.input_map
+    }
+
+    /// Rebind keys, e.g. `window.input_map_mut().bind(Key::Tab, action)`.
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input.input_map
+    }
+
+    // =========================================================================
+    // Mouse Capture
+    // =========================================================================
+
+    /// Captures the mouse for FPS-style camera control.
+    ///
+    /// When captured:
+    /// - Cursor is hidden
+    /// - Mouse is constrained to window
+    /// - `input_state().mouse_delta` reports relative movement
+    ///
+    /// Call `release_mouse()` to restore normal behavior.
+    pub fn capture_mouse(&mut self) {
+        if self.input.mouse_captured {
+            return;
+        }
+
+        // Enable relative mouse mode (hides cursor, reports relative motion)
+        self.sdl_context.mouse().set_relative_mouse_mode(true);
+        self.input.mouse_captured = true;
+
+        // Clear any accumulated delta
+        self.input.input_state.mouse_delta = (0, 0);
+    }
+
+    /// Releases the mouse from capture.
+    ///
+    /// Restores normal cursor behavior.
+    pub fn release_mouse(&mut self) {
+        if !self.input.mouse_captured {
+            return;
+        }
+
+        self.sdl_context.mouse().set_relative_mouse_mode(false);
+        self.input.mouse_captured = false;
+    }
+
+    /// Toggles mouse capture state.
+    ///
+    /// Convenience method for toggling with a single key.
+    pub fn toggle_mouse_capture(&mut self) {
+        if self.input.mouse_captured {
+            self.release_mouse();
+        } else {
+            self.capture_mouse();
+        }
+    }
+
+    /// Returns whether the mouse is currently captured.
+    pub fn is_mouse_captured(&self) -> bool {
+        self.input.mouse_captured
+    }
+
+    // =========================================================================
+    // Rendering
+    // =========================================================================
+
+    /// Record the new window size. The texture follows on the next
+    /// [`Window::present`] with a buffer of the new size.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// How frames of a different size from the window are fitted to it.
+    pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+        self.scale_mode = mode;
+    }
+
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.scale_mode
+    }
+
+    /// Where the last presented frame is drawn in the window.
+    pub fn present_rect(&self) -> PresentRect {
+        self.scale_mode.destination(
+            (self.texture_width, self.texture_height),
+            (self.width, self.height),
+        )
+    }
+
+    /// The pixel of the last presented frame under window point `(x, y)`,
+    /// e.g. `input_state().cursor_position` for `Engine::pick`. `None`
+    /// over the letterbox bars.
+    pub fn window_to_frame(&self, x: i32, y: i32) -> Option<(u32, u32)> {
+        self.present_rect()
+            .window_to_frame((self.texture_width, self.texture_height), x, y)
+    }
+
+    // =========================================================================
+    // Accessors
+    // =========================================================================
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn timer(&self) -> &sdl2::TimerSubsystem {
+        &self.timer_subsystem
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        let _ = self.canvas.window_mut().set_title(title);
+    }
+}
+
+impl WindowBackend for Window {
+    fn poll_events(&mut self) -> Vec<WindowEvent> {
+        Window::poll_events(self)
+    }
+
+    fn input_state(&self) -> &InputState {
+        Window::input_state(self)
+    }
+
+    fn toggle_mouse_capture(&mut self) {
+        Window::toggle_mouse_capture(self)
+    }
+
+    fn is_mouse_captured(&self) -> bool {
+        Window::is_mouse_captured(self)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        Window::resize(self, width, height)
+    }
+
+    fn window_to_frame(&self, x: i32, y: i32) -> Option<(u32, u32)> {
+        Window::window_to_frame(self, x, y)
+    }
+
+    fn width(&self) -> u32 {
+        Window::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        Window::height(self)
+    }
+
+    fn set_title(&mut self, title: &str) {
+        Window::set_title(self, title)
+    }
+}
+
+impl Presenter for Window {
+    /// Upload a `width` x `height` ARGB8888 frame and show it, scaled to
+    /// the window as the [`ScaleMode`] says.
+    ///
+    /// The dimensions are those of the buffer, not the window: during a
+    /// resize the two briefly disagree. The streaming texture is recreated
+    /// here whenever the buffer size changes, and a buffer whose length
+    /// doesn't match its dimensions is rejected rather than uploaded.
+    fn present(&mut self, buffer: &[u8], width: u32, height: u32) -> Result<(), PresentError> {
+        validate_frame(buffer, width, height)?;
+
+        if (width, height) != (self.texture_width, self.texture_height) {
+            // SAFETY: Same as in new() - texture_creator outlives texture
+            let texture_creator_ref: &'static sdl2::render::TextureCreator<
+                sdl2::video::WindowContext,
+            > = unsafe { &*(self.texture_creator.as_ref() as *const _) };
+            self.texture = texture_creator_ref
+                .create_texture_streaming(PixelFormatEnum::ARGB8888, width, height)
+                .map_err(|e| PresentError::Texture(e.to_string()))?;
+            self.texture_width = width;
+            self.texture_height = height;
+        }
+
+        // Rows are packed, 4 bytes a pixel with no padding at odd widths
+        self.texture
+            .update(None, buffer, (width * 4) as usize)
+            .map_err(|e| PresentError::Texture(e.to_string()))?;
+
+        // Clears the letterbox bars too
+        self.canvas.set_draw_color(sdl2::pixels::Color::BLACK);
+        self.canvas.clear();
+        let dest = self.present_rect();
+        self.canvas
+            .copy(
+                &self.texture,
+                None,
+                Some(Rect::new(
+                    dest.x as i32,
+                    dest.y as i32,
+                    dest.width,
+                    dest.height,
+                )),
+            )
+            .map_err(PresentError::Canvas)?;
+        self.canvas.present();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::Action;
+    use sdl2::keyboard::Mod;
+
+    fn key_down(keycode: Keycode) -> Event {
+        Event::KeyDown {
+            timestamp: 0,
+            window_id: 0,
+            keycode: Some(keycode),
+            scancode: None,
+            keymod: Mod::NOMOD,
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn every_event_of_a_frame_arrives_in_order() {
+        let mut input = SdlInput::default();
+        let events = input.frame([
+            Event::Window {
+                timestamp: 0,
+                window_id: 0,
+                win_event: sdl2::event::WindowEvent::Resized(800, 600),
+            },
+            key_down(Keycode::G),
+            key_down(Keycode::W),
+            key_down(Keycode::Tab),
+            Event::Quit { timestamp: 0 },
+        ]);
+
+        assert_eq!(
+            events,
+            [
+                WindowEvent::Resize(800, 600),
+                WindowEvent::Action {
+                    action: Action::ToggleGrid,
+                    key: Key::G
+                },
+                WindowEvent::KeyPress(Key::Tab),
+                WindowEvent::Quit,
+            ]
+        );
+        assert!(input.input_state.forward);
+        assert_eq!(input.frame([]), []);
+    }
+}
@@ -0,0 +1,748 @@
+//! winit + softbuffer window backend.
+//!
+//! [`WinitInput`] translates winit events into the crate's [`WindowEvent`]
+//! and [`InputState`] with the same per-frame semantics as the SDL backend:
+//! every discrete event in order, held keys and buttons as flags, and mouse
+//! deltas only while the mouse is captured or a drag button is held. It has
+//! no platform requirements, so a wasm app can feed it from its own
+//! `ApplicationHandler`.
+//!
+//! On desktop, [`WinitWindow`] wraps it in the same poll-style API as the
+//! SDL `Window` (`poll_events`, `input_state`, mouse
+//! capture, [`Presenter`]), pumping the winit event loop once per frame.
+//! Only compiled with the `winit-window` feature.
+
+use winit::event::{
+    DeviceEvent, ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent as WinitEvent,
+};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use super::{InputMap, InputState, Key, WindowEvent};
+
+/// Scroll distance reported by touchpads in pixels that counts as one wheel notch.
+const PIXELS_PER_LINE: f64 = 20.0;
+
+/// Translates winit events into [`WindowEvent`] and [`InputState`].
+///
+/// Call [`WinitInput::begin_frame`] once per frame before feeding events,
+/// then [`WinitInput::take_events`] after.
+#[derive(Debug, Default)]
+pub struct WinitInput {
+    input_state: InputState,
+    input_map: InputMap,
+    mouse_captured: bool,
+    events: Vec<WindowEvent>,
+    // Last cursor position as reported, for drag deltas while not captured
+    cursor: Option<(f64, f64)>,
+}
+
+impl WinitInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset per-frame state (mouse delta, scroll, pending discrete events).
+    pub fn begin_frame(&mut self) {
+        self.input_state.reset_per_frame();
+        self.events.clear();
+    }
+
+    /// The discrete events fed since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<WindowEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn input_state(&self) -> &InputState {
+        &self.input_state
+    }
+
+    /// The key bindings discrete presses are reported through.
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    pub fn is_mouse_captured(&self) -> bool {
+        self.mouse_captured
+    }
+
+    /// Record whether the backend has captured the mouse. Captured mice
+    /// report deltas from raw device motion rather than cursor movement.
+    pub fn set_mouse_captured(&mut self, captured: bool) {
+        self.mouse_captured = captured;
+        self.input_state.mouse_delta = (0, 0);
+    }
+
+    fn emit(&mut self, event: WindowEvent) {
+        self.events.push(event);
+    }
+
+    /// Feed one window event.
+    pub fn handle_window_event(&mut self, event: &WinitEvent) {
+        match event {
+            WinitEvent::CloseRequested => self.emit(WindowEvent::Quit),
+
+            WinitEvent::Resized(size) => self.emit(WindowEvent::Resize(size.width, size.height)),
+
+            WinitEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        physical_key: PhysicalKey::Code(code),
+                        state,
+                        repeat,
+                        ..
+                    },
+                ..
+            } => self.handle_key(*code, *state == ElementState::Pressed, *repeat),
+
+            WinitEvent::CursorMoved { position, .. } => {
+                let (x, y) = (position.x, position.y);
+                let dragging = self.input_state.left_mouse_down
+                    || self.input_state.middle_mouse_down
+                    || self.input_state.aim_light;
+                if let Some((last_x, last_y)) = self.cursor {
+                    if dragging && !self.mouse_captured {
+                        self.input_state.mouse_delta.0 += (x - last_x).round() as i32;
+                        self.input_state.mouse_delta.1 += (y - last_y).round() as i32;
+                    }
+                }
+                self.cursor = Some((x, y));
+                self.input_state.cursor_position = (x as i32, y as i32);
+            }
+
+            WinitEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y as f64,
+                    MouseScrollDelta::PixelDelta(p) => p.y / PIXELS_PER_LINE,
+                };
+                self.input_state.scroll_delta += lines as f32;
+            }
+
+            WinitEvent::MouseInput { state, button, .. } => {
+                let pressed = *state == ElementState::Pressed;
+                match button {
+                    MouseButton::Left => {
+                        self.input_state.left_mouse_down = pressed;
+                        if pressed {
+                            self.emit(WindowEvent::LeftMouseDown);
+                        }
+                    }
+                    MouseButton::Middle => self.input_state.middle_mouse_down = pressed,
+                    MouseButton::Right if pressed => self.emit(WindowEvent::RightMouseDown),
+                    _ => {}
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Feed one device event. Raw mouse motion drives look while captured.
+    pub fn handle_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta: (dx, dy) } = event {
+            if self.mouse_captured {
+                self.input_state.mouse_delta.0 += dx.round() as i32;
+                self.input_state.mouse_delta.1 += dy.round() as i32;
+            }
+        }
+    }
+
+    /// Update held-key state and emit a discrete press (ignoring repeats),
+    /// as an action where the [`InputMap`] binds the key.
+    pub fn handle_key(&mut self, code: KeyCode, pressed: bool, repeat: bool) {
+        let state = &mut self.input_state;
+        match code {
+            // Movement
+            KeyCode::KeyW => state.forward = pressed,
+            KeyCode::KeyS => state.back = pressed,
+            KeyCode::KeyA => state.left = pressed,
+            KeyCode::KeyD => state.right = pressed,
+            KeyCode::Space => state.up = pressed,
+            KeyCode::ShiftLeft | KeyCode::ControlLeft => state.down = pressed,
+            KeyCode::AltLeft => state.sprint = pressed,
+
+            // Roll
+            KeyCode::KeyQ => state.roll_left = pressed,
+            KeyCode::KeyE => state.roll_right = pressed,
+
+            // Hold to aim the light
+            KeyCode::KeyL => state.aim_light = pressed,
+
+            _ => {}
+        }
+
+        if pressed && !repeat {
+            if let Some(key) = map_key(code) {
+                self.emit(self.input_map.event_for(key));
+            }
+        }
+    }
+}
+
+/// Maps a winit physical key to a discrete key event (if applicable).
+pub fn map_key(code: KeyCode) -> Option<Key> {
+    match code {
+        KeyCode::Digit1 => Some(Key::Num1),
+        KeyCode::Digit2 => Some(Key::Num2),
+        KeyCode::Digit3 => Some(Key::Num3),
+        KeyCode::Digit4 => Some(Key::Num4),
+        KeyCode::Digit5 => Some(Key::Num5),
+        KeyCode::KeyC => Some(Key::C),
+        KeyCode::KeyG => Some(Key::G),
+        KeyCode::KeyM => Some(Key::M),
+        KeyCode::KeyR => Some(Key::R),
+        KeyCode::KeyF => Some(Key::F),
+        KeyCode::KeyT => Some(Key::T),
+        KeyCode::KeyO => Some(Key::O),
+        KeyCode::KeyL => Some(Key::L),
+        KeyCode::KeyP => Some(Key::P),
+        KeyCode::KeyH => Some(Key::H),
+        KeyCode::KeyB => Some(Key::B),
+        KeyCode::KeyU => Some(Key::U),
+        KeyCode::KeyV => Some(Key::V),
+        KeyCode::KeyK => Some(Key::K),
+        KeyCode::KeyY => Some(Key::Y),
+        KeyCode::KeyI => Some(Key::I),
+        KeyCode::KeyX => Some(Key::X),
+        KeyCode::KeyZ => Some(Key::Z),
+        KeyCode::KeyN => Some(Key::N),
+        KeyCode::KeyJ => Some(Key::J),
+        KeyCode::F1 => Some(Key::F1),
+        KeyCode::F2 => Some(Key::F2),
+        KeyCode::F3 => Some(Key::F3),
+        KeyCode::F4 => Some(Key::F4),
+        KeyCode::F5 => Some(Key::F5),
+        KeyCode::F12 => Some(Key::F12),
+        KeyCode::Tab => Some(Key::Tab),
+        KeyCode::Equal | KeyCode::NumpadAdd => Some(Key::Plus),
+        KeyCode::Minus | KeyCode::NumpadSubtract => Some(Key::Minus),
+        KeyCode::Escape => Some(Key::Escape),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use desktop::WinitWindow;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod desktop {
+    use std::num::NonZeroU32;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use winit::application::ApplicationHandler;
+    use winit::dpi::PhysicalSize;
+    use winit::event::{DeviceEvent, DeviceId, WindowEvent as WinitEvent};
+    use winit::event_loop::{ActiveEventLoop, EventLoop};
+    use winit::platform::pump_events::{EventLoopExtPumpEvents, PumpStatus};
+    use winit::window::{CursorGrabMode, WindowId};
+
+    use super::WinitInput;
+    use crate::error::Error;
+    use crate::window::{
+        validate_frame, InputMap, InputState, PresentError, PresentRect, Presenter, ScaleMode,
+        WindowBackend, WindowEvent,
+    };
+
+    type Surface = softbuffer::Surface<Rc<winit::window::Window>, Rc<winit::window::Window>>;
+
+    /// How long `WinitWindow::new` waits for the platform to create the window.
+    const CREATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Receives events from the pumped event loop.
+    struct App {
+        title: String,
+        initial_size: (u32, u32),
+        window: Option<Rc<winit::window::Window>>,
+        surface: Option<Surface>,
+        input: WinitInput,
+        error: Option<String>,
+    }
+
+    impl App {
+        fn create(&mut self, event_loop: &ActiveEventLoop) -> Result<(), String> {
+            let (width, height) = self.initial_size;
+            let attributes = winit::window::Window::default_attributes()
+                .with_title(self.title.as_str())
+                .with_inner_size(PhysicalSize::new(width, height));
+            let window = Rc::new(
+                event_loop
+                    .create_window(attributes)
+                    .map_err(|e| e.to_string())?,
+            );
+            let context = softbuffer::Context::new(window.clone()).map_err(|e| e.to_string())?;
+            let surface = Surface::new(&context, window.clone()).map_err(|e| e.to_string())?;
+            self.window = Some(window);
+            self.surface = Some(surface);
+            Ok(())
+        }
+    }
+
+    impl ApplicationHandler for App {
+        fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+            if self.window.is_none() {
+                if let Err(e) = self.create(event_loop) {
+                    self.error = Some(e);
+                }
+            }
+        }
+
+        fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, event: WinitEvent) {
+            self.input.handle_window_event(&event);
+        }
+
+        fn device_event(&mut self, _: &ActiveEventLoop, _: DeviceId, event: DeviceEvent) {
+            self.input.handle_device_event(&event);
+        }
+    }
+
+    /// A desktop window backed by winit, presenting through softbuffer.
+    ///
+    /// Drop-in for the SDL [`Window`](crate::window::Window) in a poll-style
+    /// main loop.
+    pub struct WinitWindow {
+        // App (and its surface) must drop before the event loop
+        app: App,
+        event_loop: EventLoop<()>,
+        width: u32,
+        height: u32,
+        scale_mode: ScaleMode,
+        // Size of the last presented frame
+        frame_size: (u32, u32),
+        // Set once the event loop exits; later polls keep reporting Quit
+        closed: bool,
+    }
+
+    impl WinitWindow {
+        pub fn new(title: &str, width: u32, height: u32) -> Result<Self, Error> {
+            let mut event_loop = EventLoop::new().map_err(|e| Error::Window(e.to_string()))?;
+            let mut app = App {
+                title: title.to_string(),
+                initial_size: (width, height),
+                window: None,
+                surface: None,
+                input: WinitInput::new(),
+                error: None,
+            };
+
+            // The window can only be created once the loop reports `resumed`
+            let start = std::time::Instant::now();
+            while app.window.is_none() {
+                if let PumpStatus::Exit(code) =
+                    event_loop.pump_app_events(Some(Duration::from_millis(10)), &mut app)
+                {
+                    return Err(Error::Window(format!("event loop exited with code {code}")));
+                }
+                if let Some(e) = app.error.take() {
+                    return Err(Error::Window(e));
+                }
+                if start.elapsed() > CREATE_TIMEOUT {
+                    return Err(Error::Window(
+                        "timed out waiting for the window".to_string(),
+                    ));
+                }
+            }
+
+            Ok(Self {
+                app,
+                event_loop,
+                width,
+                height,
+                scale_mode: ScaleMode::default(),
+                frame_size: (width, height),
+                closed: false,
+            })
+        }
+
+        /// Polls for events and updates input state.
+        ///
+        /// Returns every discrete event (quit, resize, key press) since the
+        /// last call, in the order they happened. Continuous input (WASD,
+        /// mouse) is available via `input_state()`.
+        pub fn poll_events(&mut self) -> Vec<WindowEvent> {
+            self.app.input.begin_frame();
+            if self.closed {
+                return vec![WindowEvent::Quit];
+            }
+            let status = self
+                .event_loop
+                .pump_app_events(Some(Duration::ZERO), &mut self.app);
+            let mut events = self.app.input.take_events();
+            if let PumpStatus::Exit(_) = status {
+                self.closed = true;
+                events.push(WindowEvent::Quit);
+            }
+            events
+        }
+
+        pub fn input_state(&self) -> &InputState {
+            self.app.input.input_state()
+        }
+
+        /// The key bindings `poll_events` reports actions through.
+        pub fn input_map(&self) -> &InputMap {
+            self.app.input.input_map()
+        }
+
+        /// Rebind keys, e.g. `window.input_map_mut().bind(Key::Tab, action)`.
+        pub fn input_map_mut(&mut self) -> &mut InputMap {
+            self.app.input.input_map_mut()
+        }
+
+        /// Captures the mouse for FPS-style camera control.
+        ///
+        /// Hides the cursor and locks it (or confines it where locking is
+        /// unsupported); `input_state().mouse_delta` then reports raw motion.
+        pub fn capture_mouse(&mut self) {
+            if self.app.input.is_mouse_captured() {
+                return;
+            }
+            if let Some(window) = &self.app.window {
+                let _ = window
+                    .set_cursor_grab(CursorGrabMode::Locked)
+                    .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+                window.set_cursor_visible(false);
+            }
+            self.app.input.set_mouse_captured(true);
+        }
+
+        /// Releases the mouse from capture.
+        pub fn release_mouse(&mut self) {
+            if !self.app.input.is_mouse_captured() {
+                return;
+            }
+            if let Some(window) = &self.app.window {
+                let _ = window.set_cursor_grab(CursorGrabMode::None);
+                window.set_cursor_visible(true);
+            }
+            self.app.input.set_mouse_captured(false);
+        }
+
+        pub fn toggle_mouse_capture(&mut self) {
+            if self.app.input.is_mouse_captured() {
+                self.release_mouse();
+            } else {
+                self.capture_mouse();
+            }
+        }
+
+        pub fn is_mouse_captured(&self) -> bool {
+            self.app.input.is_mouse_captured()
+        }
+
+        /// Record the new window size. The surface follows on the next
+        /// [`Presenter::present`].
+        pub fn resize(&mut self, width: u32, height: u32) {
+            self.width = width;
+            self.height = height;
+        }
+
+        /// How frames of a different size from the window are fitted to it.
+        pub fn set_scale_mode(&mut self, mode: ScaleMode) {
+            self.scale_mode = mode;
+        }
+
+        pub fn scale_mode(&self) -> ScaleMode {
+            self.scale_mode
+        }
+
+        /// Where the last presented frame is drawn in the window.
+        pub fn present_rect(&self) -> PresentRect {
+            self.scale_mode
+                .destination(self.frame_size, (self.width, self.height))
+        }
+
+        /// The pixel of the last presented frame under window point
+        /// `(x, y)`, e.g. `input_state().cursor_position` for
+        /// `Engine::pick`. `None` over the letterbox bars.
+        pub fn window_to_frame(&self, x: i32, y: i32) -> Option<(u32, u32)> {
+            self.present_rect().window_to_frame(self.frame_size, x, y)
+        }
+
+        pub fn width(&self) -> u32 {
+            self.width
+        }
+
+        pub fn height(&self) -> u32 {
+            self.height
+        }
+
+        pub fn set_title(&mut self, title: &str) {
+            if let Some(window) = &self.app.window {
+                window.set_title(title);
+            }
+        }
+    }
+
+    impl WindowBackend for WinitWindow {
+        fn poll_events(&mut self) -> Vec<WindowEvent> {
+            WinitWindow::poll_events(self)
+        }
+
+        fn input_state(&self) -> &InputState {
+            WinitWindow::input_state(self)
+        }
+
+        fn toggle_mouse_capture(&mut self) {
+            WinitWindow::toggle_mouse_capture(self)
+        }
+
+        fn is_mouse_captured(&self) -> bool {
+            WinitWindow::is_mouse_captured(self)
+        }
+
+        fn resize(&mut self, width: u32, height: u32) {
+            WinitWindow::resize(self, width, height)
+        }
+
+        fn window_to_frame(&self, x: i32, y: i32) -> Option<(u32, u32)> {
+            WinitWindow::window_to_frame(self, x, y)
+        }
+
+        fn width(&self) -> u32 {
+            WinitWindow::width(self)
+        }
+
+        fn height(&self) -> u32 {
+            WinitWindow::height(self)
+        }
+
+        fn set_title(&mut self, title: &str) {
+            WinitWindow::set_title(self, title)
+        }
+    }
+
+    impl Presenter for WinitWindow {
+        /// Copy a `width` x `height` ARGB8888 frame to the window surface,
+        /// nearest-neighbor scaled as the [`ScaleMode`] says if the window
+        /// has a different size.
+        fn present(&mut self, frame: &[u8], width: u32, height: u32) -> Result<(), PresentError> {
+            validate_frame(frame, width, height)?;
+            self.frame_size = (width, height);
+            let dest = self.present_rect();
+
+            let (Some(surface_width), Some(surface_height)) =
+                (NonZeroU32::new(self.width), NonZeroU32::new(self.height))
+            else {
+                // Minimized; nothing to draw into
+                return Ok(());
+            };
+            let Some(surface) = &mut self.app.surface else {
+                return Ok(());
+            };
+            surface
+                .resize(surface_width, surface_height)
+                .map_err(|e| PresentError::Texture(e.to_string()))?;
+            let mut buffer = surface
+                .buffer_mut()
+                .map_err(|e| PresentError::Texture(e.to_string()))?;
+
+            let window_width = self.width as usize;
+            let (dst_x, dst_y) = (dest.x as usize, dest.y as usize);
+            let (dst_width, dst_height) = (dest.width as usize, dest.height as usize);
+            let (src_width, src_height) = (width as usize, height as usize);
+            // Black letterbox bars, and the whole window for an empty frame
+            buffer.fill(0);
+            if src_width > 0 && src_height > 0 {
+                for y in 0..dst_height {
+                    let src_row = (y * src_height / dst_height) * src_width;
+                    let dst_row = (dst_y + y) * window_width + dst_x;
+                    for x in 0..dst_width {
+                        let i = (src_row + x * src_width / dst_width) * 4;
+                        let argb = u32::from_ne_bytes([
+                            frame[i],
+                            frame[i + 1],
+                            frame[i + 2],
+                            frame[i + 3],
+                        ]);
+                        // softbuffer wants 0RGB: the top byte must be zero
+                        buffer[dst_row + x] = argb & 0x00FF_FFFF;
+                    }
+                }
+            }
+
+            buffer
+                .present()
+                .map_err(|e| PresentError::Canvas(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RenderMode;
+    use crate::window::Action;
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+    use winit::event::{DeviceId, TouchPhase};
+
+    fn mouse_button(button: MouseButton, state: ElementState) -> WinitEvent {
+        WinitEvent::MouseInput {
+            device_id: DeviceId::dummy(),
+            state,
+            button,
+        }
+    }
+
+    fn cursor_moved(x: f64, y: f64) -> WinitEvent {
+        WinitEvent::CursorMoved {
+            device_id: DeviceId::dummy(),
+            position: PhysicalPosition::new(x, y),
+        }
+    }
+
+    #[test]
+    fn keys_set_held_state_and_discrete_events() {
+        let mut input = WinitInput::new();
+        input.begin_frame();
+        input.handle_key(KeyCode::KeyW, true, false);
+        input.handle_key(KeyCode::Digit3, true, false);
+
+        assert!(input.input_state().forward);
+        assert_eq!(
+            input.take_events(),
+            [WindowEvent::Action {
+                action: Action::SetRenderMode(RenderMode::FilledWireframe),
+                key: Key::Num3
+            }]
+        );
+        assert_eq!(input.take_events(), []);
+
+        input.begin_frame();
+        input.handle_key(KeyCode::KeyG, true, true);
+        assert_eq!(input.take_events(), [], "repeats are ignored");
+        input.handle_key(KeyCode::KeyW, false, false);
+        assert!(!input.input_state().forward);
+    }
+
+    #[test]
+    fn window_events_map_to_discrete_events() {
+        let mut input = WinitInput::new();
+        input.handle_window_event(&WinitEvent::Resized(PhysicalSize::new(640, 480)));
+        assert_eq!(input.take_events(), [WindowEvent::Resize(640, 480)]);
+
+        input.handle_window_event(&WinitEvent::CloseRequested);
+        assert_eq!(input.take_events(), [WindowEvent::Quit]);
+
+        input.handle_window_event(&mouse_button(MouseButton::Right, ElementState::Pressed));
+        assert_eq!(input.take_events(), [WindowEvent::RightMouseDown]);
+    }
+
+    #[test]
+    fn every_event_of_a_frame_arrives_in_order() {
+        let mut input = WinitInput::new();
+        input.begin_frame();
+        input.handle_window_event(&WinitEvent::Resized(PhysicalSize::new(800, 600)));
+        input.handle_key(KeyCode::KeyG, true, false);
+        input.handle_key(KeyCode::KeyW, true, false);
+        input.handle_key(KeyCode::Tab, true, false);
+        input.handle_window_event(&WinitEvent::CloseRequested);
+
+        assert_eq!(
+            input.take_events(),
+            [
+                WindowEvent::Resize(800, 600),
+                WindowEvent::Action {
+                    action: Action::ToggleGrid,
+                    key: Key::G
+                },
+                WindowEvent::KeyPress(Key::Tab),
+                WindowEvent::Quit,
+            ]
+        );
+        // Held state still comes through, once for the frame
+        assert!(input.input_state().forward);
+    }
+
+    #[test]
+    fn drag_reports_delta_only_while_button_held() {
+        let mut input = WinitInput::new();
+        input.begin_frame();
+        input.handle_window_event(&cursor_moved(10.0, 10.0));
+        input.handle_window_event(&cursor_moved(15.0, 12.0));
+        assert_eq!(input.input_state().mouse_delta, (0, 0));
+
+        input.handle_window_event(&mouse_button(MouseButton::Left, ElementState::Pressed));
+        assert_eq!(input.take_events(), [WindowEvent::LeftMouseDown]);
+        input.handle_window_event(&cursor_moved(20.0, 8.0));
+        assert_eq!(input.input_state().mouse_delta, (5, -4));
+        assert_eq!(input.input_state().cursor_position, (20, 8));
+
+        input.begin_frame();
+        assert_eq!(input.input_state().mouse_delta, (0, 0));
+    }
+
+    #[test]
+    fn holding_the_aim_key_drags_without_a_button() {
+        let mut input = WinitInput::new();
+        input.begin_frame();
+        input.handle_window_event(&cursor_moved(10.0, 10.0));
+        input.handle_key(KeyCode::KeyL, true, false);
+        assert!(input.input_state().aim_light);
+        // Only held, no action of its own by default
+        assert_eq!(input.take_events(), [WindowEvent::KeyPress(Key::L)]);
+        input.handle_window_event(&cursor_moved(16.0, 7.0));
+        assert_eq!(input.input_state().mouse_delta, (6, -3));
+
+        input.handle_key(KeyCode::KeyL, false, false);
+        input.handle_window_event(&cursor_moved(20.0, 7.0));
+        assert!(!input.input_state().aim_light);
+        assert_eq!(input.input_state().mouse_delta, (6, -3));
+    }
+
+    #[test]
+    fn captured_mouse_uses_raw_motion() {
+        let mut input = WinitInput::new();
+        let motion = DeviceEvent::MouseMotion { delta: (3.0, -2.0) };
+
+        input.handle_device_event(&motion);
+        assert_eq!(input.input_state().mouse_delta, (0, 0));
+
+        input.set_mouse_captured(true);
+        input.handle_device_event(&motion);
+        input.handle_window_event(&cursor_moved(50.0, 50.0));
+        input.handle_window_event(&cursor_moved(90.0, 90.0));
+        assert_eq!(input.input_state().mouse_delta, (3, -2));
+    }
+
+    #[test]
+    fn scroll_accumulates_lines() {
+        let mut input = WinitInput::new();
+        for delta in [
+            MouseScrollDelta::LineDelta(0.0, 1.0),
+            MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, PIXELS_PER_LINE)),
+        ] {
+            input.handle_window_event(&WinitEvent::MouseWheel {
+                device_id: DeviceId::dummy(),
+                delta,
+                phase: TouchPhase::Moved,
+            });
+        }
+        assert_eq!(input.input_state().scroll_delta, 2.0);
+    }
+
+    #[test]
+    fn rebound_keys_report_their_new_action() {
+        let mut input = WinitInput::new();
+        input.input_map_mut().unbind(Key::R);
+        input
+            .input_map_mut()
+            .bind(Key::Tab, Action::CycleRasterizer);
+
+        input.begin_frame();
+        input.handle_key(KeyCode::Tab, true, false);
+        assert_eq!(
+            input.take_events(),
+            [WindowEvent::Action {
+                action: Action::CycleRasterizer,
+                key: Key::Tab
+            }]
+        );
+        input.begin_frame();
+        input.handle_key(KeyCode::KeyR, true, false);
+        assert_eq!(input.take_events(), [WindowEvent::KeyPress(Key::R)]);
+    }
+}
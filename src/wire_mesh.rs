@@ -0,0 +1,179 @@
+//! Wireframe shapes for debug overlays, e.g. collision volumes.
+//!
+//! A [`WireMesh`] is a list of line segments in its own space. Queued with
+//! [`Engine::draw_wire_mesh`] it takes the same path as
+//! [`Engine::draw_line_3d`]: drawn after the meshes and depth-tested
+//! against them, with no lighting or face culling involved.
+//!
+//! [`Engine::draw_wire_mesh`]: crate::engine::Engine::draw_wire_mesh
+//! [`Engine::draw_line_3d`]: crate::engine::Engine::draw_line_3d
+
+use std::f32::consts::{PI, TAU};
+
+use crate::math::vec3::Vec3;
+
+/// Fewest lines around a full circle.
+const MIN_SEGMENTS: u32 = 4;
+
+/// Line segments making up a shape, in the shape's own space.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WireMesh {
+    lines: Vec<[Vec3; 2]>,
+}
+
+impl WireMesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The 12 edges of the box from `min` to `max`.
+    pub fn aabb(min: Vec3, max: Vec3) -> Self {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 != 0 { max.x } else { min.x },
+                if i & 2 != 0 { max.y } else { min.y },
+                if i & 4 != 0 { max.z } else { min.z },
+            )
+        };
+        let mut mesh = Self::new();
+        // Each edge joins two corners a bit apart
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    mesh.push_line(corner(i), corner(i | bit));
+                }
+            }
+        }
+        mesh
+    }
+
+    /// A sphere of `radius` around the origin, drawn as three circles of
+    /// `segments` lines, one in each axis plane. Circles get at least 4
+    /// lines.
+    pub fn sphere(radius: f32, segments: u32) -> Self {
+        let segments = segments.max(MIN_SEGMENTS);
+        let (x, y, z) = (
+            Vec3::RIGHT * radius,
+            Vec3::UP * radius,
+            Vec3::FORWARD * radius,
+        );
+        let mut mesh = Self::new();
+        for (from, toward) in [(x, y), (y, z), (z, x)] {
+            mesh.arc(Vec3::ZERO, from, toward, TAU, segments);
+        }
+        mesh
+    }
+
+    /// A capsule along the y axis: everything within `radius` of the
+    /// segment from `-half_height` to `half_height` on y, so
+    /// `half_height + radius` tall each way.
+    ///
+    /// Drawn as a circle of `segments` lines around each end of the
+    /// segment, four lines joining them, and half circles over both caps in
+    /// the xy and zy planes. Circles get at least 4 lines.
+    pub fn capsule(radius: f32, half_height: f32, segments: u32) -> Self {
+        let segments = segments.max(MIN_SEGMENTS);
+        let half_height = half_height.max(0.0);
+        let (x, z) = (Vec3::RIGHT * radius, Vec3::FORWARD * radius);
+        let mut mesh = Self::new();
+        for end in [-1.0, 1.0] {
+            let center = Vec3::UP * (half_height * end);
+            mesh.arc(center, x, z, TAU, segments);
+            // Over the pole, away from the middle
+            let pole = Vec3::UP * (radius * end);
+            mesh.arc(center, x, pole, PI, segments / 2);
+            mesh.arc(center, z, pole, PI, segments / 2);
+        }
+        let rise = Vec3::UP * half_height;
+        for side in [x, -x, z, -z] {
+            mesh.push_line(side - rise, side + rise);
+        }
+        mesh
+    }
+
+    /// Add a line from `a` to `b`.
+    pub fn push_line(&mut self, a: Vec3, b: Vec3) {
+        self.lines.push([a, b]);
+    }
+
+    /// Move every line by `offset`, e.g. to center a shape on a model's
+    /// bounds.
+    pub fn translate(&mut self, offset: Vec3) {
+        for [a, b] in &mut self.lines {
+            *a = *a + offset;
+            *b = *b + offset;
+        }
+    }
+
+    /// The lines, as their two ends.
+    pub fn lines(&self) -> &[[Vec3; 2]] {
+        &self.lines
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Add `segments` lines along an arc of `angle` radians around
+    /// `center`, starting at `center + from` and turning toward
+    /// `center + toward`.
+    fn arc(&mut self, center: Vec3, from: Vec3, toward: Vec3, angle: f32, segments: u32) {
+        let point = |i: u32| {
+            let t = angle * i as f32 / segments as f32;
+            center + from * t.cos() + toward * t.sin()
+        };
+        for i in 0..segments {
+            self.push_line(point(i), point(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ends(mesh: &WireMesh) -> impl Iterator<Item = Vec3> + '_ {
+        mesh.lines().iter().flatten().copied()
+    }
+
+    #[test]
+    fn boxes_have_twelve_axis_aligned_edges() {
+        let (min, max) = (Vec3::new(-1.0, 0.0, 2.0), Vec3::new(1.0, 3.0, 4.0));
+        let mesh = WireMesh::aabb(min, max);
+        assert_eq!(mesh.line_count(), 12);
+        for [a, b] in mesh.lines() {
+            let d = *b - *a;
+            // Along one axis, from its min to its max
+            let moved = [d.x, d.y, d.z].iter().filter(|&&c| c != 0.0).count();
+            assert_eq!(moved, 1);
+            assert!(d.x >= 0.0 && d.y >= 0.0 && d.z >= 0.0);
+        }
+    }
+
+    #[test]
+    fn sphere_points_lie_on_the_sphere() {
+        let mesh = WireMesh::sphere(2.0, 16);
+        assert_eq!(mesh.line_count(), 48);
+        assert!(ends(&mesh).all(|p| (p.magnitude() - 2.0).abs() < 1e-5));
+        // Too few segments are raised to a square
+        assert_eq!(WireMesh::sphere(1.0, 1).line_count(), 12);
+    }
+
+    #[test]
+    fn capsule_points_lie_on_the_capsule() {
+        let (radius, half_height) = (0.5, 1.5);
+        let mesh = WireMesh::capsule(radius, half_height, 12);
+        // Two rings, four half circles and four sides
+        assert_eq!(mesh.line_count(), 2 * 12 + 4 * 6 + 4);
+        for p in ends(&mesh) {
+            let axis = Vec3::UP * p.y.clamp(-half_height, half_height);
+            assert!(((p - axis).magnitude() - radius).abs() < 1e-5, "{p:?}");
+        }
+        let top = ends(&mesh).map(|p| p.y).fold(f32::MIN, f32::max);
+        assert!((top - (half_height + radius)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,103 @@
+//! Background loading tests — `Engine::load_model_async` and
+//! `Engine::load_texture_async` read files off the calling thread, and
+//! `Engine::poll_loaded` installs them, reporting each load's status.
+
+use std::time::{Duration, Instant};
+
+use russsty::prelude::*;
+use russsty::Error;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// Poll until the load with `handle` is installed or has failed.
+fn wait_for(engine: &mut Engine, handle: LoadHandle) -> &LoadStatus {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !engine.load_status(handle).unwrap().is_finished() {
+        assert!(Instant::now() < deadline, "load never finished");
+        std::thread::sleep(Duration::from_millis(1));
+        engine.poll_loaded();
+    }
+    engine.load_status(handle).unwrap()
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+#[test]
+fn loaded_models_are_installed_when_polled() {
+    let path = common::temp_path("obj");
+    std::fs::write(&path, common::UV_QUAD_OBJ).expect("write temp obj");
+
+    let mut engine = engine();
+    let background = engine.palette().background;
+    let handle = engine.load_model_async("quad", path.to_str().unwrap());
+    assert!(matches!(
+        engine.load_status(handle),
+        Some(LoadStatus::Loading)
+    ));
+    assert_eq!(engine.loads_pending(), 1);
+    // Not there until polled for
+    assert!(engine.model("quad").is_none());
+
+    assert!(matches!(wait_for(&mut engine, handle), LoadStatus::Done));
+    std::fs::remove_file(&path).ok();
+    assert_eq!(engine.loads_pending(), 0);
+    assert_eq!(engine.model("quad").unwrap().meshes().len(), 1);
+    let frame = engine.render_offscreen().to_vec();
+    assert_ne!(frame[(H / 2 * W + W / 2) as usize], background);
+}
+
+#[test]
+fn textures_go_to_their_model() {
+    let obj = common::temp_path("obj");
+    std::fs::write(&obj, common::UV_QUAD_OBJ).expect("write temp obj");
+    let png = common::temp_path("png");
+    image::RgbaImage::from_pixel(2, 2, image::Rgba([0x20, 0xC0, 0x40, 0xFF]))
+        .save(&png)
+        .expect("write temp png");
+
+    let mut engine = engine();
+    let model = engine.load_model_async("quad", obj.to_str().unwrap());
+    wait_for(&mut engine, model);
+    let texture = engine.load_texture_async(Some("quad"), png.to_str().unwrap());
+    assert!(matches!(wait_for(&mut engine, texture), LoadStatus::Done));
+    // Nothing named "gone" to take it
+    let orphan = engine.load_texture_async(Some("gone"), png.to_str().unwrap());
+    assert!(matches!(
+        wait_for(&mut engine, orphan),
+        LoadStatus::Failed(Error::InvalidArgument(_))
+    ));
+    std::fs::remove_file(&obj).ok();
+    std::fs::remove_file(&png).ok();
+
+    assert!(engine.model("quad").unwrap().texture().is_some());
+    engine.set_texture_mode(TextureMode::Replace);
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(frame[(H / 2 * W + W / 2) as usize], 0xFF20C040);
+}
+
+#[test]
+fn missing_files_fail_without_installing_anything() {
+    let mut engine = engine();
+    let path = common::temp_path("obj");
+    let model = engine.load_model_async("missing", path.to_str().unwrap());
+    let texture = engine.load_texture_async(None, path.with_extension("png").to_str().unwrap());
+
+    assert!(matches!(
+        wait_for(&mut engine, model),
+        LoadStatus::Failed(Error::MeshLoad(_))
+    ));
+    assert!(matches!(
+        wait_for(&mut engine, texture),
+        LoadStatus::Failed(Error::Texture(_))
+    ));
+    assert!(engine.model("missing").is_none());
+    assert_eq!(engine.loads_pending(), 0);
+}
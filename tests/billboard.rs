@@ -0,0 +1,113 @@
+//! Billboard tests — sprites face the camera, depth-test against the
+//! scene, and blend by their texture's alpha.
+
+use russsty::colors::RenderPalette;
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const CENTER: usize = (H as usize / 2) * W as usize + W as usize / 2;
+
+const RED: u32 = 0xFFFF0000;
+const BLUE: u32 = 0xFF0000FF;
+
+// A wall facing the default camera, through the origin
+const WALL_OBJ: &str = "\
+v -1.0 -1.0 0.0
+v -1.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+f 1 2 3
+f 1 3 4
+";
+
+fn engine_with_wall() -> Engine {
+    let mut engine = common::engine_with_obj("wall", WALL_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine
+}
+
+fn center_pixel(engine: &mut Engine) -> u32 {
+    engine.render_offscreen()[CENTER]
+}
+
+#[test]
+fn billboards_are_depth_tested_against_the_scene() {
+    let mut engine = engine_with_wall();
+    let wall = center_pixel(&mut engine);
+
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, RED);
+    assert_eq!(center_pixel(&mut engine), RED);
+
+    engine.draw_billboard(Vec3::new(0.0, 0.0, 1.0), 0.5, None, RED);
+    assert_eq!(center_pixel(&mut engine), wall);
+}
+
+#[test]
+fn queue_empties_each_update() {
+    let mut engine = engine_with_wall();
+    let wall = center_pixel(&mut engine);
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, RED);
+    assert_eq!(center_pixel(&mut engine), RED);
+    assert_eq!(center_pixel(&mut engine), wall);
+}
+
+#[test]
+fn texture_top_faces_up_from_any_angle() {
+    let mut engine = engine_with_wall();
+    engine.clear_models();
+    // Top row red, bottom row blue
+    engine.set_billboard_texture(Texture::from_pixels(vec![RED, RED, BLUE, BLUE], 2, 2).unwrap());
+    let camera = Vec3::new(4.0, 0.0, -3.0);
+    engine.set_camera(FpsCamera::looking_at(camera, Vec3::ZERO));
+
+    engine.draw_billboard(Vec3::ZERO, 1.0, None, 0xFFFFFFFF);
+    let frame = engine.render_offscreen();
+    let (x, y) = (W as usize / 2, H as usize / 2);
+    assert_eq!(frame[(y - 3) * W as usize + x], RED);
+    assert_eq!(frame[(y + 3) * W as usize + x], BLUE);
+    // Seen square on: symmetric left to right
+    assert_eq!(frame[y * W as usize + x - 3], frame[y * W as usize + x + 3]);
+}
+
+#[test]
+fn color_tints_the_texture() {
+    let mut engine = engine_with_wall();
+    engine.set_billboard_texture(Texture::from_pixels(vec![0xFFFFFFFF], 1, 1).unwrap());
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, BLUE);
+    assert_eq!(center_pixel(&mut engine) & 0x00FFFFFF, BLUE & 0x00FFFFFF);
+}
+
+#[test]
+fn cutout_and_alpha_follow_texture_alpha() {
+    let mut engine = engine_with_wall();
+    engine.set_palette(RenderPalette {
+        fill: 0xFF000000,
+        ..RenderPalette::default()
+    });
+    engine.set_billboard_texture(Texture::from_pixels(vec![0x40FFFFFF], 1, 1).unwrap());
+
+    engine.set_billboard_blend(BlendMode::Cutout);
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, 0xFFFFFFFF);
+    assert_eq!(center_pixel(&mut engine), 0xFF000000);
+
+    engine.set_billboard_blend(BlendMode::Alpha);
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, 0xFFFFFFFF);
+    assert_eq!(center_pixel(&mut engine), 0xFF404040);
+}
+
+#[test]
+fn alpha_billboards_blend_back_to_front() {
+    let mut engine = engine_with_wall();
+    engine.set_billboard_texture(Texture::from_pixels(vec![0x80FFFFFF], 1, 1).unwrap());
+    // Queued near first; the far one must still end up underneath
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -2.0), 0.5, None, RED);
+    engine.draw_billboard(Vec3::new(0.0, 0.0, -1.0), 0.5, None, BLUE);
+    let color = center_pixel(&mut engine);
+    let (red, blue) = ((color >> 16) & 0xFF, color & 0xFF);
+    assert!(red > blue, "near red should dominate, got {color:08X}");
+}
@@ -0,0 +1,76 @@
+//! Camera path tests — `Engine::update_path` moves whichever camera is
+//! active onto a `CameraPath` and looks it at the keyed target, and stops
+//! driving it once a one-shot path ends.
+
+use russsty::prelude::*;
+
+const EPSILON: f32 = 1e-3;
+
+fn approx_eq(a: Vec3, b: Vec3) -> bool {
+    (a - b).magnitude() < EPSILON
+}
+
+fn three_key_path(looping: bool) -> CameraPath {
+    CameraPath::new(looping)
+        .key(0.0, Vec3::new(0.0, 2.0, -8.0), Vec3::ZERO)
+        .key(1.0, Vec3::new(8.0, 3.0, 0.0), Vec3::ZERO)
+        .key(2.0, Vec3::new(0.0, 2.0, 8.0), Vec3::ZERO)
+}
+
+#[test]
+fn fps_camera_follows_the_path_and_faces_the_target() {
+    let mut engine = Engine::new(32, 32);
+    engine.set_camera_path(three_key_path(false));
+
+    assert!(engine.update_path(1.0));
+    assert!(approx_eq(
+        engine.camera().position(),
+        Vec3::new(8.0, 3.0, 0.0)
+    ));
+    let to_target = (Vec3::ZERO - Vec3::new(8.0, 3.0, 0.0)).normalize();
+    assert!(approx_eq(engine.camera().forward(), to_target));
+}
+
+#[test]
+fn orbit_camera_pivots_on_the_path_target() {
+    let mut engine = Engine::new(32, 32);
+    engine.set_camera(OrbitCamera::new(Vec3::new(5.0, 0.0, 0.0), 3.0));
+    engine.set_camera_path(three_key_path(false));
+
+    assert!(engine.update_path(2.0));
+    assert!(approx_eq(
+        engine.camera().position(),
+        Vec3::new(0.0, 2.0, 8.0)
+    ));
+    let SceneCamera::Orbit(camera) = engine.camera() else {
+        panic!("camera kind changed");
+    };
+    assert!(approx_eq(camera.target(), Vec3::ZERO));
+}
+
+#[test]
+fn finished_path_hands_the_camera_back() {
+    let mut engine = Engine::new(32, 32);
+    assert!(!engine.update_path(0.1), "no path to follow");
+
+    engine.set_camera_path(three_key_path(false));
+    assert!(engine.update_path(5.0));
+    assert!(engine.camera_path().unwrap().is_finished());
+
+    // The player moves the camera; a finished path leaves it alone
+    engine.set_camera_position(Vec3::new(1.0, 1.0, 1.0));
+    assert!(!engine.update_path(0.1));
+    assert_eq!(engine.camera().position(), Vec3::new(1.0, 1.0, 1.0));
+
+    assert!(engine.clear_camera_path().is_some());
+    assert!(engine.camera_path().is_none());
+}
+
+#[test]
+fn looping_path_keeps_driving_the_camera() {
+    let mut engine = Engine::new(32, 32);
+    engine.set_camera_path(three_key_path(true));
+    for _ in 0..100 {
+        assert!(engine.update_path(0.1));
+    }
+}
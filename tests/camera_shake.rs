@@ -0,0 +1,36 @@
+//! Camera shake tests — `Engine::set_camera_shake` turns the view drawn
+//! with on top of the camera, and `Engine::animate` lets it die down.
+
+use russsty::prelude::*;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+#[test]
+fn shake_moves_the_frame_and_dies_down() {
+    // The grid alone shows any turn of the view
+    let mut engine = Engine::new(W, H);
+    engine.set_camera(FpsCamera::looking_at(Vec3::new(0.0, 2.0, -5.0), Vec3::ZERO));
+    let still = engine.render_offscreen().to_vec();
+    let view = engine.view_matrix();
+
+    engine.set_camera_shake(CameraShake::default().with_seed(3));
+    assert_eq!(
+        engine.render_offscreen(),
+        &still[..],
+        "quiet until triggered"
+    );
+
+    engine.camera_shake_mut().unwrap().trigger(1.0);
+    engine.animate(0.05);
+    assert_ne!(engine.view_matrix(), view);
+    assert_ne!(engine.render_offscreen(), &still[..]);
+    // Only the view turns; the camera stays put
+    assert_eq!(engine.camera_position(), Vec3::new(0.0, 2.0, -5.0));
+
+    for _ in 0..200 {
+        engine.animate(0.05);
+    }
+    assert!(!engine.camera_shake().unwrap().is_active());
+    assert_eq!(engine.render_offscreen(), &still[..]);
+}
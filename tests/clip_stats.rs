@@ -0,0 +1,91 @@
+//! Clip pre-pass tests — faces wholly inside or outside the view skip the
+//! clipper, and only straddling faces are clipped.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FILL: u32 = 0xFFFF0000;
+
+/// One mesh of squares in the z = 0 plane facing the default camera at
+/// z = -5, each given as (center x, half-size).
+fn squares_obj(squares: &[(f32, f32)]) -> String {
+    let mut obj = String::new();
+    for (i, &(x, r)) in squares.iter().enumerate() {
+        obj += &format!(
+            "v {l} {b} 0.0\nv {l} {t} 0.0\nv {r_} {t} 0.0\nv {r_} {b} 0.0\n",
+            l = x - r,
+            r_ = x + r,
+            b = -r,
+            t = r,
+        );
+        let base = i * 4;
+        obj += &format!(
+            "f {} {} {}\nf {} {} {}\n",
+            base + 1,
+            base + 2,
+            base + 3,
+            base + 1,
+            base + 3,
+            base + 4
+        );
+    }
+    obj
+}
+
+fn engine_with_squares(squares: &[(f32, f32)]) -> Engine {
+    let mut engine = common::engine_with_obj("squares", &squares_obj(squares), W, H);
+    engine.backface_culling = false;
+    engine
+}
+
+#[test]
+fn faces_are_accepted_rejected_or_clipped() {
+    let mut engine = engine_with_squares(&[
+        // Small and centered: inside every plane
+        (0.0, 0.5),
+        // Far off to the right: outside the right plane. The mesh bounds
+        // still overlap the view, so these faces reach the clipper.
+        (50.0, 0.5),
+        // Reaches past every side of the view
+        (0.0, 20.0),
+    ]);
+    engine.update();
+    let stats = engine.stats();
+
+    assert_eq!(stats.faces_total, 6);
+    assert_eq!(stats.faces_frustum_culled, 0);
+    assert_eq!(stats.faces_trivially_accepted, 2);
+    assert_eq!(stats.faces_trivially_rejected, 2);
+    assert_eq!(stats.faces_clipped, 2);
+    assert_eq!(stats.faces_clipped_away, 2);
+    // Accepted faces stay single triangles; clipped ones may split
+    assert!(stats.triangles_drawn > 4);
+}
+
+#[test]
+fn accepted_faces_render_like_clipped_ones() {
+    // A square inside the view and one reaching past it both fill the
+    // center pixel.
+    for r in [0.5, 20.0] {
+        let mut engine = engine_with_squares(&[(0.0, r)]);
+        engine.set_render_mode(RenderMode::Filled);
+        engine.set_shading_mode(ShadingMode::None);
+        engine.palette_mut().fill = FILL;
+        engine.update();
+        engine.render();
+
+        let stats = engine.stats();
+        if r < 1.0 {
+            assert_eq!(stats.faces_trivially_accepted, 2);
+        } else {
+            assert_eq!(stats.faces_clipped, 2);
+        }
+
+        let i = (((H / 2) * W + W / 2) * 4) as usize;
+        let center = u32::from_ne_bytes(engine.frame_buffer()[i..i + 4].try_into().unwrap());
+        assert_eq!(center, FILL, "half-size {r}");
+    }
+}
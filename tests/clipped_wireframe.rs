@@ -0,0 +1,82 @@
+//! Clipped wireframe tests — a face cut by the screen edges is outlined
+//! along its own edges only, without the fan spokes and clip-plane cuts
+//! the clipper adds to triangulate what's left of it.
+
+use russsty::RenderMode;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const WIRE: u32 = 0xFFFF00FF;
+
+// A quad far bigger than the view of the default camera at z = -5, split
+// along its (-20, -20) to (20, 20) diagonal
+const HUGE_QUAD_OBJ: &str = "\
+v -20.0 -20.0 0.0
+v -20.0 20.0 0.0
+v 20.0 20.0 0.0
+v 20.0 -20.0 0.0
+f 1 2 3
+f 1 3 4
+";
+
+// Hangs off the left of the screen, with its right edge in view
+const HALF_OFF_QUAD_OBJ: &str = "\
+v -20.0 -1.0 0.0
+v -20.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+f 1 2 3
+f 1 3 4
+";
+
+fn render(obj: &str, mode: RenderMode) -> Vec<u32> {
+    let mut engine = common::engine_with_obj("quad", obj, W, H);
+    engine.backface_culling = false;
+    engine.palette_mut().wireframe = WIRE;
+
+    engine.set_render_mode(mode);
+    engine.update();
+    engine.render();
+    engine
+        .frame_buffer()
+        .chunks_exact(4)
+        .map(|p| u32::from_ne_bytes(p.try_into().unwrap()))
+        .collect()
+}
+
+fn wire_pixels(pixels: &[u32], x: u32) -> usize {
+    (0..H)
+        .filter(|y| pixels[(y * W + x) as usize] == WIRE)
+        .count()
+}
+
+#[test]
+fn only_the_true_diagonal_is_outlined() {
+    // Every outer edge is off screen, so all that's left to draw is the
+    // diagonal the two triangles share
+    let pixels = render(HUGE_QUAD_OBJ, RenderMode::Wireframe);
+    for y in 0..H {
+        let row = &pixels[(y * W) as usize..((y + 1) * W) as usize];
+        let drawn = row.iter().filter(|p| **p == WIRE).count();
+        assert!(drawn <= 2, "row {y} has {drawn} wireframe pixels");
+    }
+    let total = pixels.iter().filter(|p| **p == WIRE).count();
+    assert!(
+        (W as usize / 2..=2 * W as usize).contains(&total),
+        "{total} wireframe pixels"
+    );
+}
+
+#[test]
+fn screen_border_is_not_outlined() {
+    let pixels = render(HALF_OFF_QUAD_OBJ, RenderMode::Wireframe);
+    // The quad's top and bottom edges and its diagonal cross the left
+    // border; nothing runs along it
+    assert!(wire_pixels(&pixels, 0) <= 3);
+
+    // Its real right edge is still there
+    let right_edge = (1..W).max_by_key(|&x| wire_pixels(&pixels, x)).unwrap();
+    assert!(wire_pixels(&pixels, right_edge) > 4);
+}
@@ -0,0 +1,89 @@
+//! Fixtures shared by the integration tests. A test file opts in with
+//! `mod common;`.
+
+// Every test file compiles its own copy and uses only part of it
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use russsty::{Engine, Error};
+
+/// A 2x2 square in the z = 0 plane, wound clockwise as seen from the
+/// default camera at z = -5, without texture coordinates.
+pub const QUAD_OBJ: &str = "\
+v -1.0 -1.0 0.0
+v -1.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+f 1 2 3
+f 1 3 4
+";
+
+/// A camera-facing 2x2 square with UVs running 0..1 across it.
+pub const UV_QUAD_OBJ: &str = "\
+v -1 -1 0
+v -1 1 0
+v 1 1 0
+v 1 -1 0
+vt 0 0
+vt 0 1
+vt 1 1
+vt 1 0
+f 1/1 2/2 3/3
+f 1/1 3/3 4/4
+";
+
+/// A name for a temp file or directory that no other test uses. Tests run
+/// in parallel, so each gets its own.
+fn unique_name() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("russsty_test_{}_{n}", std::process::id())
+}
+
+/// A path in the temp directory that no other test uses, ending in
+/// `.{extension}`.
+pub fn temp_path(extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}.{extension}", unique_name()))
+}
+
+/// A fresh, empty directory in the temp directory that no other test uses.
+pub fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(unique_name());
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+/// Load the OBJ source `obj` into `engine` as model `name`, returning what
+/// [`Engine::add_model`] does.
+pub fn try_load_obj(engine: &mut Engine, name: &str, obj: &str) -> Result<usize, Error> {
+    let path = temp_path("obj");
+    std::fs::write(&path, obj).expect("write temp obj");
+    let result = engine.add_model(name, path.to_str().unwrap());
+    std::fs::remove_file(&path).ok();
+    result
+}
+
+/// Load the OBJ source `obj` into `engine` as model `name`.
+///
+/// # Panics
+/// Panics if the OBJ doesn't load.
+pub fn load_obj(engine: &mut Engine, name: &str, obj: &str) -> usize {
+    try_load_obj(engine, name, obj).unwrap_or_else(|e| panic!("load {name}: {e}"))
+}
+
+/// A `width`×`height` engine without the ground grid, holding `obj` as
+/// model `name`.
+pub fn engine_with_obj(name: &str, obj: &str, width: u32, height: u32) -> Engine {
+    let mut engine = Engine::new(width, height);
+    engine.draw_grid = false;
+    load_obj(&mut engine, name, obj);
+    engine
+}
+
+/// The presented frame's pixel at `(x, y)`.
+pub fn pixel(engine: &Engine, (x, y): (u32, u32)) -> u32 {
+    let (width, _) = engine.frame_size();
+    engine.frame_pixels()[(y * width + x) as usize]
+}
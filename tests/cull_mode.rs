@@ -0,0 +1,74 @@
+//! Per-mesh cull mode tests — open surfaces stay visible and lit from
+//! behind when culling is off, while the global flag still overrides.
+
+use russsty::prelude::*;
+use russsty::CullMode;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FILL: u32 = 0xFF808080;
+// FILL under the full light plus the default 0.1 ambient: 0x80 * 1.1,
+// rounded
+const LIT: u32 = 0xFF8D8D8D;
+const BACKGROUND: u32 = 0xFF000000;
+
+fn engine_with_quad(cull_mode: CullMode) -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, W, H);
+    engine.palette_mut().fill = FILL;
+    engine.palette_mut().background = BACKGROUND;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+
+    for mesh in engine.model_mut("quad").unwrap().meshes_mut() {
+        mesh.set_cull_mode(cull_mode);
+    }
+    engine
+}
+
+/// Render with the camera on the `side` of the plane (-1 = front, 1 = back)
+/// and the light shining at the visible side.
+fn render_from(engine: &mut Engine, side: f32) -> u32 {
+    engine.set_camera(FpsCamera::looking_at(
+        Vec3::new(0.0, 0.0, 5.0 * side),
+        Vec3::ZERO,
+    ));
+    engine.set_light_direction(Vec3::new(0.0, 0.0, -side));
+    engine.update();
+    engine.render();
+
+    let i = (((H / 2) * W + W / 2) * 4) as usize;
+    u32::from_ne_bytes(engine.frame_buffer()[i..i + 4].try_into().unwrap())
+}
+
+#[test]
+fn default_culls_back_faces() {
+    let mut engine = engine_with_quad(CullMode::Back);
+    assert_eq!(render_from(&mut engine, -1.0), LIT);
+    assert_eq!(render_from(&mut engine, 1.0), BACKGROUND);
+    assert_eq!(engine.stats().faces_backface_culled, 2);
+}
+
+#[test]
+fn two_sided_mesh_is_lit_from_behind() {
+    let mut engine = engine_with_quad(CullMode::None);
+    assert_eq!(render_from(&mut engine, -1.0), LIT);
+    // Without flipping the normal this would be ambient-only
+    assert_eq!(render_from(&mut engine, 1.0), LIT);
+}
+
+#[test]
+fn front_culling_shows_only_the_back() {
+    let mut engine = engine_with_quad(CullMode::Front);
+    assert_eq!(render_from(&mut engine, -1.0), BACKGROUND);
+    assert_eq!(render_from(&mut engine, 1.0), LIT);
+}
+
+#[test]
+fn global_flag_disables_culling_for_every_mesh() {
+    let mut engine = engine_with_quad(CullMode::Back);
+    engine.backface_culling = false;
+    assert_eq!(render_from(&mut engine, 1.0), LIT);
+    assert_eq!(engine.stats().faces_backface_culled, 0);
+}
@@ -0,0 +1,109 @@
+//! Custom rasterizer tests — a user-supplied `Rasterizer` receives the
+//! engine's triangles and can be swapped in and out at runtime.
+
+use russsty::engine::{FrameBuffer, Triangle};
+use russsty::prelude::*;
+use russsty::texture::Texture;
+use std::cell::Cell;
+use std::rc::Rc;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const MARK: u32 = 0xFF00FF00;
+
+/// Counts the triangles it is given and marks each first vertex's pixel.
+struct CountingRasterizer {
+    calls: Rc<Cell<usize>>,
+}
+
+impl Rasterizer for CountingRasterizer {
+    fn fill_triangle(
+        &self,
+        triangle: &Triangle,
+        buffer: &mut FrameBuffer,
+        _color: u32,
+        _texture: Option<&Texture>,
+    ) {
+        self.calls.set(self.calls.get() + 1);
+        let p = triangle.points[0].position;
+        buffer.set_pixel(p.x as i32, p.y as i32, MARK);
+    }
+
+    fn name(&self) -> &str {
+        "Counting"
+    }
+}
+
+fn engine_with_quad() -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+fn render(engine: &mut Engine) {
+    engine.update();
+    engine.render();
+}
+
+fn marked_pixels(engine: &Engine) -> usize {
+    engine
+        .frame_buffer()
+        .chunks_exact(4)
+        .filter(|b| u32::from_ne_bytes((*b).try_into().unwrap()) == MARK)
+        .count()
+}
+
+#[test]
+fn engine_draws_through_custom_rasterizer() {
+    let mut engine = engine_with_quad();
+    let calls = Rc::new(Cell::new(0));
+    engine.set_custom_rasterizer(Box::new(CountingRasterizer {
+        calls: calls.clone(),
+    }));
+    assert_eq!(engine.rasterizer(), RasterizerType::Custom);
+    assert_eq!(engine.rasterizer_name(), "Counting");
+
+    render(&mut engine);
+    assert_eq!(calls.get(), 2);
+    assert!(marked_pixels(&engine) > 0);
+}
+
+#[test]
+fn builtins_stay_selectable_alongside_custom() {
+    let mut engine = engine_with_quad();
+    let calls = Rc::new(Cell::new(0));
+    engine.set_custom_rasterizer(Box::new(CountingRasterizer {
+        calls: calls.clone(),
+    }));
+
+    engine.set_rasterizer(RasterizerType::EdgeFunction);
+    assert_eq!(engine.rasterizer_name(), "EdgeFunction");
+    render(&mut engine);
+    assert_eq!(calls.get(), 0);
+    assert_eq!(marked_pixels(&engine), 0);
+
+    engine.set_rasterizer(RasterizerType::Custom);
+    render(&mut engine);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn custom_is_ignored_until_registered() {
+    let mut engine = engine_with_quad();
+    engine.set_rasterizer(RasterizerType::EdgeFunction);
+    engine.set_rasterizer(RasterizerType::Custom);
+    assert_eq!(engine.rasterizer(), RasterizerType::EdgeFunction);
+
+    engine.set_custom_rasterizer(Box::new(CountingRasterizer {
+        calls: Rc::new(Cell::new(0)),
+    }));
+    let removed = engine.clear_custom_rasterizer();
+    assert_eq!(
+        removed.map(|r| r.name().to_string()).as_deref(),
+        Some("Counting")
+    );
+    assert_eq!(engine.rasterizer(), RasterizerType::default());
+    assert!(engine.custom_rasterizer().is_none());
+}
@@ -0,0 +1,84 @@
+//! Custom shader tests — a user-supplied `PixelShader` colors filled
+//! triangles in place of the built-in shader selection, in both
+//! rasterizers.
+
+use russsty::colors;
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const CENTER: usize = (H as usize / 2) * W as usize + W as usize / 2;
+
+/// Paints every pixel one color.
+struct Solid(u32);
+
+impl PixelShader for Solid {
+    fn shade(&self, _fragment: &Fragment) -> u32 {
+        self.0
+    }
+}
+
+/// Red from the interpolated `u`, blue from the bound texture.
+struct UvAndTexture;
+
+impl PixelShader for UvAndTexture {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let u = fragment.uv().x;
+        let texel = fragment.sample_texture().unwrap_or(0);
+        colors::pack_color(u, 0.0, 0.0, 1.0) | (texel & 0xFF)
+    }
+}
+
+fn engine_with_quad() -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::UV_QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+#[test]
+fn custom_shader_overrides_both_rasterizers() {
+    let mut engine = engine_with_quad();
+    let builtin = engine.render_offscreen()[CENTER];
+    engine.set_custom_shader(Some(Box::new(Solid(0xFF123456))));
+    assert!(engine.custom_shader().is_some());
+
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        engine.set_rasterizer(rasterizer);
+        assert_eq!(
+            engine.render_offscreen()[CENTER],
+            0xFF123456,
+            "{rasterizer}"
+        );
+    }
+
+    engine.set_custom_shader(None);
+    assert_eq!(engine.render_offscreen()[CENTER], builtin);
+}
+
+#[test]
+fn custom_shader_survives_the_depth_prepass() {
+    let mut engine = engine_with_quad();
+    engine.set_custom_shader(Some(Box::new(Solid(0xFF123456))));
+    engine.set_depth_prepass(true);
+    assert_eq!(engine.render_offscreen()[CENTER], 0xFF123456);
+}
+
+#[test]
+fn fragment_carries_uvs_and_the_mesh_texture() {
+    let mut engine = engine_with_quad();
+    engine
+        .model_mut("quad")
+        .unwrap()
+        .set_texture(Texture::from_pixels(vec![0xFF0000AA], 1, 1).unwrap());
+    engine.set_custom_shader(Some(Box::new(UvAndTexture)));
+
+    let frame = engine.render_offscreen();
+    let row = (H as usize / 2) * W as usize;
+    let red = |x: usize| (frame[row + x] >> 16) & 0xFF;
+    // u grows left to right across the quad
+    assert!(red(24) < red(32) && red(32) < red(40));
+    assert_eq!(frame[CENTER] & 0xFF, 0xAA);
+}
@@ -0,0 +1,153 @@
+//! Debug draw tests — `Engine::set_debug_draw` outlines mesh bounding
+//! boxes and draws vertex normals as depth-tested world-space lines that
+//! follow each mesh's transform.
+
+use russsty::colors::from_rgb_f32;
+use russsty::prelude::*;
+use std::f32::consts::PI;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+const CUBE_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// A camera-facing square whose normals all point along +x.
+const QUAD_OBJ: &str = "\
+v -1 -1 0
+v -1 1 0
+v 1 1 0
+v 1 -1 0
+vn 1 0 0
+f 1//1 2//1 3//1
+f 1//1 3//1 4//1
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("mesh", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine
+}
+
+/// Columns of row `y` holding `color`, give or take rounding in each
+/// channel.
+fn columns_of(frame: &[u32], y: u32, color: u32) -> Vec<u32> {
+    let close = |pixel: u32| {
+        let (a, b) = (pixel.to_be_bytes(), color.to_be_bytes());
+        a.iter().zip(b).all(|(&a, b)| a.abs_diff(b) <= 1)
+    };
+    (0..W)
+        .filter(|&x| close(frame[(y * W + x) as usize]))
+        .collect()
+}
+
+#[test]
+fn default_draws_nothing_extra() {
+    let mut engine = engine_with(CUBE_OBJ);
+    let expected = engine.render_offscreen().to_vec();
+    engine.set_debug_draw(DebugDraw::default());
+    assert_eq!(engine.render_offscreen(), &expected[..]);
+}
+
+#[test]
+fn bounding_box_outlines_the_visible_edges() {
+    let mut engine = engine_with(CUBE_OBJ);
+    engine.set_debug_draw(DebugDraw {
+        aabb: true,
+        ..DebugDraw::default()
+    });
+    let bounds = engine.palette().bounds;
+    let frame = engine.render_offscreen().to_vec();
+
+    // The near face at distance 4 spans NDC ±1 / (4 tan 22.5°): columns
+    // 12 and 51 across the middle row
+    let columns = columns_of(&frame, H / 2 - 4, bounds);
+    assert!(!columns.is_empty());
+    assert!(
+        columns
+            .iter()
+            .all(|&x| x.abs_diff(12) <= 1 || x.abs_diff(51) <= 1),
+        "{columns:?}"
+    );
+    assert!(columns.iter().any(|&x| x < W / 2) && columns.iter().any(|&x| x > W / 2));
+
+    // The far face's edges (columns 19 and 44) are hidden behind the cube,
+    // and show once it is no longer filled in
+    engine.set_render_mode(RenderMode::Wireframe);
+    let frame = engine.render_offscreen().to_vec();
+    let columns = columns_of(&frame, H / 2 - 4, bounds);
+    assert!(
+        columns.contains(&19) && columns.contains(&44),
+        "{columns:?}"
+    );
+}
+
+#[test]
+fn normals_turn_with_the_mesh() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_debug_draw(DebugDraw {
+        normals: true,
+        normal_length: 0.5,
+        ..DebugDraw::default()
+    });
+
+    // Along the top edge from each corner (columns 16 and 47) half a unit
+    // to the right, in the color of +x
+    let right = from_rgb_f32([1.0, 0.5, 0.5]);
+    let frame = engine.render_offscreen().to_vec();
+    let columns = columns_of(&frame, 16, right);
+    assert!(
+        columns.contains(&20) && columns.contains(&52),
+        "{columns:?}"
+    );
+    assert!(
+        columns
+            .iter()
+            .all(|&x| (16..=25).contains(&x) || (47..=56).contains(&x)),
+        "{columns:?}"
+    );
+
+    // Turned half way round, they point left in the color of -x
+    engine
+        .model_mut("mesh")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.0, PI, 0.0));
+    let left = from_rgb_f32([0.0, 0.5, 0.5]);
+    let frame = engine.render_offscreen().to_vec();
+    assert!(columns_of(&frame, 16, right).is_empty());
+    let columns = columns_of(&frame, 16, left);
+    assert!(
+        columns.contains(&12) && columns.contains(&44),
+        "{columns:?}"
+    );
+    assert!(
+        columns
+            .iter()
+            .all(|&x| (7..=17).contains(&x) || (38..=48).contains(&x)),
+        "{columns:?}"
+    );
+}
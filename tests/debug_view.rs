@@ -0,0 +1,142 @@
+//! Debug view tests — `Engine::set_debug_view` paints barycentrics, UVs,
+//! overdraw or normals in false color in place of the shaded surface.
+
+use russsty::colors::unpack_color;
+use russsty::engine::OVERDRAW_STEP;
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// A camera-facing square with UVs running 0..1 left to right (u) and
+/// bottom to top (v), and normals toward the camera.
+const QUAD_OBJ: &str = "\
+v -1 -1 0
+v -1 1 0
+v 1 1 0
+v 1 -1 0
+vt 0 0
+vt 0 1
+vt 1 1
+vt 1 0
+vn 0 0 -1
+f 1/1/1 2/2/1 3/3/1
+f 1/1/1 3/3/1 4/4/1
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("mesh", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine
+}
+
+/// RGB channels of `pixel` in [0, 1].
+fn rgb(pixel: u32) -> [f32; 3] {
+    let (r, g, b) = unpack_color(pixel);
+    [r, g, b]
+}
+
+fn at(frame: &[u32], x: u32, y: u32) -> [f32; 3] {
+    rgb(frame[(y * W + x) as usize])
+}
+
+#[test]
+fn barycentric_weights_sum_to_white() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_debug_view(DebugView::Barycentric);
+    let frame = engine.render_offscreen().to_vec();
+    let background = engine.palette().background;
+
+    let covered: Vec<u32> = frame.into_iter().filter(|&p| p != background).collect();
+    assert!(covered.len() > 100);
+    for pixel in covered {
+        let sum: f32 = rgb(pixel).iter().sum();
+        assert!((sum - 1.0).abs() < 0.02, "{pixel:08X}");
+    }
+}
+
+#[test]
+fn uv_view_ramps_u_right_and_v_up() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_debug_view(DebugView::Uv);
+    let frame = engine.render_offscreen().to_vec();
+
+    let center = at(&frame, W / 2, H / 2);
+    assert!((center[0] - 0.5).abs() < 0.1 && (center[1] - 0.5).abs() < 0.1);
+    assert_eq!(center[2], 0.0);
+    // Red grows to the right, green toward the top of the screen
+    assert!(at(&frame, W / 2 + 8, H / 2)[0] > center[0] + 0.1);
+    assert!(at(&frame, W / 2, H / 2 - 8)[1] > center[1] + 0.1);
+}
+
+#[test]
+fn normals_view_maps_axes_to_channels() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_debug_view(DebugView::Normals);
+    let frame = engine.render_offscreen().to_vec();
+
+    // (0, 0, -1) maps to (0.5, 0.5, 0.0)
+    let [r, g, b] = at(&frame, W / 2, H / 2);
+    assert!((r - 0.5).abs() < 0.01 && (g - 0.5).abs() < 0.01 && b < 0.01);
+
+    // Without vertex normals the face normal stands in
+    let unlit = QUAD_OBJ.replace("/1 ", "/ ").replace("/1\n", "/\n");
+    let mut engine = engine_with(&unlit.replace("vn 0 0 -1\n", ""));
+    engine.set_debug_view(DebugView::Normals);
+    let frame = engine.render_offscreen().to_vec();
+    let [r, g, b] = at(&frame, W / 2, H / 2);
+    assert!((r - 0.5).abs() < 0.01 && (g - 0.5).abs() < 0.01 && b < 0.01);
+}
+
+#[test]
+fn overdraw_brightens_with_each_layer() {
+    // Three overlapping squares, each further right and further back, so
+    // some columns are covered one, two and three layers deep
+    let mut obj = String::new();
+    for (layer, (x, z)) in [(-1.5, 0.0), (-0.5, 1.0), (0.5, 2.0)].iter().enumerate() {
+        for (dx, y) in [(0.0, -0.5), (0.0, 0.5), (1.0, 0.5), (1.0, -0.5)] {
+            obj += &format!("v {} {y} {z}\n", x + dx * 2.0);
+        }
+        let base = layer * 4;
+        obj += &format!("f {} {} {}\n", base + 1, base + 2, base + 3);
+        obj += &format!("f {} {} {}\n", base + 1, base + 3, base + 4);
+    }
+    let mut engine = engine_with(&obj);
+    engine.set_debug_view(DebugView::Overdraw);
+    let frame = engine.render_offscreen().to_vec();
+
+    // Background is black; red counts layers in steps of OVERDRAW_STEP
+    assert_eq!(frame[0], 0xFF000000);
+    let step = (OVERDRAW_STEP >> 16) & 0xFF;
+    let layers = |x: u32| ((frame[(H / 2 * W + x) as usize] >> 16) & 0xFF) / step;
+    let counts: Vec<u32> = (0..W).map(layers).collect();
+    let deepest = *counts.iter().max().unwrap();
+    assert_eq!(deepest, 3, "{counts:?}");
+    assert!(counts.contains(&1) && counts.contains(&2));
+
+    // Depth order doesn't matter: the nearest square hides the others in
+    // the normal view but not here
+    engine.set_debug_view(DebugView::None);
+    let shaded = engine.render_offscreen().to_vec();
+    assert_ne!(shaded, frame);
+}
+
+#[test]
+fn turning_the_view_off_restores_the_frame() {
+    let mut engine = engine_with(QUAD_OBJ);
+    let expected = engine.render_offscreen().to_vec();
+    for view in [
+        DebugView::Barycentric,
+        DebugView::Uv,
+        DebugView::Overdraw,
+        DebugView::Normals,
+    ] {
+        engine.set_debug_view(view);
+        assert_ne!(engine.render_offscreen(), &expected[..], "{view}");
+    }
+    engine.set_debug_view(DebugView::None);
+    assert_eq!(engine.render_offscreen(), &expected[..]);
+}
@@ -0,0 +1,170 @@
+//! Deferred pipeline tests — drawing into G-buffer planes and lighting the
+//! frame in one screen-space pass must look like forward lighting, and
+//! with overlapping meshes it must light fewer pixels.
+
+use russsty::prelude::*;
+
+const W: u32 = 96;
+const H: u32 = 96;
+/// Bands and slices of the test sphere.
+const RINGS: u32 = 16;
+const SEGMENTS: u32 = 24;
+
+fn engine_with_sphere(shading: ShadingMode) -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    let mut model = Model::new("sphere");
+    model.add_mesh(Mesh::uv_sphere("sphere", 1.0, SEGMENTS, RINGS));
+    engine.set_model(model);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(shading);
+    engine.set_light_direction(Vec3::new(0.5, -0.5, 1.0));
+    engine
+}
+
+fn frame(engine: &mut Engine, pipeline: Pipeline) -> Vec<u32> {
+    engine.set_pipeline(pipeline);
+    engine.update();
+    engine.render_offscreen().to_vec()
+}
+
+/// Largest per-channel difference between two frames, and how many pixels
+/// differ at all.
+fn difference(a: &[u32], b: &[u32]) -> (u8, usize) {
+    let mut worst = 0;
+    let mut count = 0;
+    for (&a, &b) in a.iter().zip(b) {
+        let channels = a.to_be_bytes().into_iter().zip(b.to_be_bytes());
+        let diff = channels.map(|(a, b)| a.abs_diff(b)).max().unwrap();
+        worst = worst.max(diff);
+        count += usize::from(diff > 0);
+    }
+    (worst, count)
+}
+
+/// Eight colored lights in a ring around the origin.
+fn ring_of_lights(radius: f32) -> Vec<PointLight> {
+    let colors = [
+        0xFFFF4040, 0xFF40FF40, 0xFF4040FF, 0xFFFFFF40, 0xFFFF40FF, 0xFF40FFFF, 0xFFFFFFFF,
+        0xFFFF8000,
+    ];
+    colors
+        .iter()
+        .enumerate()
+        .map(|(i, &color)| {
+            let angle = std::f32::consts::TAU * i as f32 / colors.len() as f32;
+            let position = Vec3::new(angle.cos() * radius, angle.sin() * radius, -1.5);
+            PointLight {
+                color,
+                intensity: 0.6,
+                ..PointLight::new(position, 4.0)
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn flat_sphere_matches_forward() {
+    let mut engine = engine_with_sphere(ShadingMode::Flat);
+    let forward = frame(&mut engine, Pipeline::Forward);
+    let deferred = frame(&mut engine, Pipeline::Deferred);
+
+    let background = engine.palette().background;
+    assert!(deferred.iter().filter(|&&c| c != background).count() > 1000);
+    // Same face normal, same light; only rounding may differ
+    let (worst, _) = difference(&forward, &deferred);
+    assert!(worst <= 1, "off by {worst}");
+}
+
+#[test]
+fn gouraud_sphere_stays_close_to_forward() {
+    let mut engine = engine_with_sphere(ShadingMode::Gouraud);
+    let forward = frame(&mut engine, Pipeline::Forward);
+    let deferred = frame(&mut engine, Pipeline::Deferred);
+
+    // Deferred lights the interpolated normal per pixel, forward
+    // interpolates the vertex colors; on a smooth sphere they stay close
+    let (worst, _) = difference(&forward, &deferred);
+    assert!(worst <= 12, "off by {worst}");
+}
+
+#[test]
+fn point_lights_match_between_pipelines() {
+    let mut engine = engine_with_sphere(ShadingMode::Gouraud);
+    engine.set_point_lights(ring_of_lights(1.5));
+    let forward = frame(&mut engine, Pipeline::Forward);
+    let deferred = frame(&mut engine, Pipeline::Deferred);
+    let (worst, _) = difference(&forward, &deferred);
+    assert!(worst <= 1, "off by {worst}");
+
+    // The lights show up at all
+    engine.clear_point_lights();
+    let unlit = frame(&mut engine, Pipeline::Deferred);
+    let (_, changed) = difference(&unlit, &deferred);
+    assert!(changed > 500, "{changed} pixels changed");
+}
+
+#[test]
+fn deferred_lights_each_pixel_once() {
+    let mut engine = engine_with_sphere(ShadingMode::Gouraud);
+    let handle = engine.mesh_handle("sphere", "sphere").unwrap();
+    // A row of spheres sinking into the distance, drawn back to front so
+    // forward lighting shades every covered fragment
+    let transforms: Vec<Transform> = (1..6)
+        .map(|i| {
+            let mut transform = Transform::new();
+            transform.set_position_xyz(0.2 * i as f32, 0.0, 0.8 * i as f32);
+            transform
+        })
+        .rev()
+        .collect();
+    engine.add_instances(handle, &transforms);
+    engine.set_point_lights(ring_of_lights(1.5));
+
+    frame(&mut engine, Pipeline::Forward);
+    let forward = engine.stats().pixels_lit;
+    frame(&mut engine, Pipeline::Deferred);
+    let deferred = engine.stats().pixels_lit;
+
+    let background = engine.palette().background;
+    let covered = engine
+        .render_offscreen()
+        .iter()
+        .filter(|&&c| c != background)
+        .count();
+    assert_eq!(deferred, covered);
+    assert!(forward > deferred * 2, "{forward} vs {deferred}");
+}
+
+#[test]
+fn debug_views_draw_forward() {
+    let mut engine = engine_with_sphere(ShadingMode::Gouraud);
+    engine.set_debug_view(DebugView::Normals);
+    let forward = frame(&mut engine, Pipeline::Forward);
+    let deferred = frame(&mut engine, Pipeline::Deferred);
+    assert_eq!(forward, deferred);
+    assert_eq!(engine.stats().pixels_lit, 0);
+}
+
+#[test]
+fn gbuffer_planes_follow_the_pipeline() {
+    let mut engine = engine_with_sphere(ShadingMode::Flat);
+    let mut target = RenderTarget::new(W, H);
+    assert!(!target.has_gbuffer());
+
+    engine.set_pipeline(Pipeline::Deferred);
+    engine.update();
+    engine.render_to(&mut target);
+    assert!(target.has_gbuffer());
+    assert_eq!(target.normals().len(), (W * H) as usize);
+    // The middle of the sphere faces the camera
+    let normal = target.normal_at(W as i32 / 2, H as i32 / 2).unwrap();
+    assert!(normal.z < -0.9, "{normal:?}");
+    assert!(target.inv_w()[(H / 2 * W + W / 2) as usize] > 0.0);
+    assert_eq!(target.inv_w()[0], 0.0);
+
+    engine.set_pipeline(Pipeline::Forward);
+    engine.render_to(&mut target);
+    assert!(!target.has_gbuffer());
+    assert!(target.normals().is_empty());
+}
@@ -0,0 +1,108 @@
+//! Depth mode tests — two large, nearly coplanar quads 90 units out (far
+//! plane at 100) must resolve to the nearer one whichever is drawn first.
+//!
+//! Standard NDC z bunches far depths up against 1.0 and loses that fight
+//! at a 0.001-unit gap; 1/w and reversed NDC keep f32 precision near 0.0
+//! and hold it.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 96;
+const H: u32 = 64;
+
+// A quad at z = `depth` covering the whole view from the origin, tilted
+// slightly so depth varies across the screen.
+fn quad_obj(depth: f32) -> String {
+    format!(
+        "v -80.0 -60.0 {a}\nv -80.0 60.0 {a}\nv 80.0 60.0 {b}\nv 80.0 -60.0 {b}\nf 1 2 3\nf 1 3 4\n",
+        a = depth - 0.5,
+        b = depth + 0.5,
+    )
+}
+
+fn load(engine: &mut Engine, name: &str, depth: f32) {
+    common::load_obj(engine, name, &quad_obj(depth));
+}
+
+/// Fraction of pixels where the far quad shows through the near one, with
+/// the far quad `gap` units behind.
+fn far_fraction(mode: DepthMode, near_first: bool, gap: f32) -> f32 {
+    let mut engine = Engine::new(W, H);
+    engine.backface_culling = false;
+    engine.draw_grid = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_camera(FpsCamera::new(Vec3::ZERO));
+    engine.set_depth_mode(mode);
+    engine.set_picking(true);
+
+    let (first, second) = if near_first {
+        (("near", 90.0), ("far", 90.0 + gap))
+    } else {
+        (("far", 90.0 + gap), ("near", 90.0))
+    };
+    load(&mut engine, first.0, first.1);
+    load(&mut engine, second.0, second.1);
+    let far_index = if near_first { 1 } else { 0 };
+    engine.update();
+    engine.render();
+
+    let mut far = 0;
+    for y in 0..H as i32 {
+        for x in 0..W as i32 {
+            if engine
+                .pick(x, y)
+                .is_some_and(|hit| hit.model_index == far_index)
+            {
+                far += 1;
+            }
+        }
+    }
+    far as f32 / (W * H) as f32
+}
+
+// A handful of pixels along the near quad's diagonal seam show the far
+// quad in every mode; only a real depth fight exceeds this.
+const SEAM_TOLERANCE: f32 = 0.01;
+
+#[test]
+fn all_modes_resolve_a_tenth_of_a_percent_gap() {
+    for mode in [DepthMode::InvW, DepthMode::Ndc, DepthMode::ReversedNdc] {
+        for near_first in [true, false] {
+            let far = far_fraction(mode, near_first, 0.1);
+            assert!(
+                far < SEAM_TOLERANCE,
+                "{mode:?}, near first {near_first}: {far}"
+            );
+        }
+    }
+}
+
+#[test]
+fn reversed_depth_holds_where_ndc_fights() {
+    const GAP: f32 = 0.001;
+
+    for mode in [DepthMode::InvW, DepthMode::ReversedNdc] {
+        for near_first in [true, false] {
+            let far = far_fraction(mode, near_first, GAP);
+            assert!(
+                far < SEAM_TOLERANCE,
+                "{mode:?}, near first {near_first}: {far}"
+            );
+        }
+    }
+
+    // Both quads quantize to the same NDC depth, so the later one wins
+    let far = far_fraction(DepthMode::Ndc, false, GAP);
+    assert!(far > 0.25, "expected NDC depth to fight: {far}");
+}
+
+#[test]
+fn depth_mode_round_trips() {
+    let mut engine = Engine::new(W, H);
+    assert_eq!(engine.depth_mode(), DepthMode::InvW);
+    engine.set_depth_mode(DepthMode::ReversedNdc);
+    assert_eq!(engine.depth_mode(), DepthMode::ReversedNdc);
+}
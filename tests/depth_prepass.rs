@@ -0,0 +1,87 @@
+//! Depth pre-pass tests — with `Engine::set_depth_prepass` the filled pass
+//! writes depth first and then shades each visible pixel exactly once, so
+//! a stack of overlapping quads costs no more shading than the front one,
+//! while the frame comes out the same.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// Four camera-facing quads, farthest first: without a pre-pass every
+/// layer is shaded and then covered by the next.
+fn stacked_quads_obj() -> String {
+    let mut obj = String::new();
+    for (layer, z) in [3.0, 2.0, 1.0, 0.0].into_iter().enumerate() {
+        for (x, y) in [(-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (1.0, -1.0)] {
+            obj += &format!("v {x} {y} {z}\n");
+        }
+        let base = layer * 4;
+        obj += &format!("f {} {} {}\n", base + 1, base + 2, base + 3);
+        obj += &format!("f {} {} {}\n", base + 1, base + 3, base + 4);
+    }
+    obj
+}
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("stack", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine.set_texture_mode(TextureMode::Checker { scale: 2.0 });
+    engine
+}
+
+#[test]
+fn prepass_shades_each_visible_pixel_once() {
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        let mut engine = engine_with(&stacked_quads_obj());
+        engine.set_rasterizer(rasterizer);
+
+        let single_pass = engine.render_offscreen().to_vec();
+        let overdrawn = engine.stats().pixels_shaded;
+
+        engine.set_depth_prepass(true);
+        let prepassed = engine.render_offscreen().to_vec();
+        let shaded = engine.stats().pixels_shaded;
+
+        assert!(prepassed == single_pass, "{rasterizer}: frames differ");
+
+        // The corner is background; everything else drawn is the front quad
+        let background = prepassed[0];
+        let visible = prepassed.iter().filter(|&&p| p != background).count();
+        assert!(visible > 0);
+        assert_eq!(shaded, visible, "{rasterizer}");
+        // Back to front, each farther layer was shaded in full first
+        assert!(
+            overdrawn > 2 * shaded,
+            "{rasterizer}: {overdrawn} shaded without pre-pass, {shaded} with"
+        );
+    }
+}
+
+#[test]
+fn prepass_keeps_picking_on_the_front_surface() {
+    let mut engine = engine_with(&stacked_quads_obj());
+    engine.set_picking(true);
+    engine.render_offscreen();
+    let expected = engine.pick(W as i32 / 2, H as i32 / 2);
+
+    engine.set_depth_prepass(true);
+    engine.render_offscreen();
+    let picked = engine.pick(W as i32 / 2, H as i32 / 2);
+    assert!(picked.is_some());
+    assert_eq!(picked, expected);
+}
+
+#[test]
+fn nothing_is_shaded_without_a_filled_pass() {
+    let mut engine = engine_with(&stacked_quads_obj());
+    engine.render_offscreen();
+    assert!(engine.stats().pixels_shaded > 0);
+
+    engine.set_render_mode(RenderMode::Wireframe);
+    engine.render_offscreen();
+    assert_eq!(engine.stats().pixels_shaded, 0);
+}
@@ -0,0 +1,103 @@
+//! Fill rule tests — both rasterizers follow the top-left rule, so they
+//! cover the same pixels and a quad split into two triangles draws every
+//! pixel inside it exactly once.
+//!
+//! The quads' diagonals run at 45 degrees through whole-pixel corners,
+//! which puts a row of pixel centers exactly on the shared edge: the case
+//! a fill rule exists to settle.
+
+use russsty::bench::{
+    EdgeFunctionRasterizer, OwnedFrameBuffer, Rasterizer, ScanlineRasterizer, ScreenVertex,
+    TriangleBuilder,
+};
+use russsty::prelude::Vec2;
+
+const W: u32 = 48;
+const H: u32 = 48;
+
+fn rasterizers() -> [Box<dyn Rasterizer>; 2] {
+    [
+        Box::new(ScanlineRasterizer::new()),
+        Box::new(EdgeFunctionRasterizer::new()),
+    ]
+}
+
+/// Pixels `rasterizer` draws for the triangle `points`, alone in a fresh
+/// buffer.
+fn coverage(rasterizer: &dyn Rasterizer, points: [Vec2; 3]) -> Vec<bool> {
+    let triangle = TriangleBuilder::new(points.map(|p| ScreenVertex::new(p, 1.0)))
+        .color(0xFFFFFFFF)
+        .build();
+    let mut target = OwnedFrameBuffer::new(W, H);
+    rasterizer.fill_triangle(
+        &triangle,
+        &mut target.as_framebuffer(),
+        triangle.color,
+        None,
+    );
+    target.color().iter().map(|&p| p != 0).collect()
+}
+
+/// Split the quad `[a, b, c, d]` along `a`-`c`, check that the
+/// rasterizers agree on each half and that the halves don't overlap, and
+/// return how many pixels the quad covers.
+fn tile_quad(quad: [Vec2; 4]) -> usize {
+    let [a, b, c, d] = quad;
+    let mut masks = Vec::new();
+    for rasterizer in rasterizers() {
+        let first = coverage(rasterizer.as_ref(), [a, b, c]);
+        let second = coverage(rasterizer.as_ref(), [a, c, d]);
+        let overlap = first
+            .iter()
+            .zip(&second)
+            .filter(|(x, y)| **x && **y)
+            .count();
+        assert_eq!(overlap, 0, "{} draws the diagonal twice", rasterizer.name());
+        masks.push((first, second));
+    }
+    assert!(
+        masks[0] == masks[1],
+        "scanline and edge function coverage differ"
+    );
+
+    let (first, second) = &masks[0];
+    first.iter().zip(second).filter(|(x, y)| **x || **y).count()
+}
+
+#[test]
+fn axis_aligned_quad_is_covered_once() {
+    let quad = [
+        Vec2::new(4.0, 4.0),
+        Vec2::new(36.0, 4.0),
+        Vec2::new(36.0, 36.0),
+        Vec2::new(4.0, 36.0),
+    ];
+    // Every pixel inside, none outside
+    assert_eq!(tile_quad(quad), 32 * 32);
+}
+
+#[test]
+fn either_winding_covers_the_same_pixels() {
+    let quad = [
+        Vec2::new(4.0, 4.0),
+        Vec2::new(4.0, 36.0),
+        Vec2::new(36.0, 36.0),
+        Vec2::new(36.0, 4.0),
+    ];
+    assert_eq!(tile_quad(quad), 32 * 32);
+}
+
+#[test]
+fn slanted_quad_is_covered_once() {
+    // A parallelogram whose sides don't line up with the pixel grid
+    let quad = [
+        Vec2::new(3.0, 2.0),
+        Vec2::new(30.5, 9.25),
+        Vec2::new(43.0, 42.0),
+        Vec2::new(15.5, 34.75),
+    ];
+    // Its area is 27.5 * 32.75 - 7.25 * 12.5 = 810 pixels, which a gapless,
+    // overlap-free tiling matches to within the rounding along its sides
+    let covered = tile_quad(quad);
+    assert!((800..=820).contains(&covered), "covered {covered} pixels");
+}
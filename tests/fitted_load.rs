@@ -0,0 +1,138 @@
+//! Mesh normalization tests — models authored at any scale or offset can
+//! be brought to unit size and framed without hand-tuning.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FILL: u32 = 0xFFFF0000;
+
+/// A closed box from `min` to `max`, optionally starting a named object.
+fn box_obj(object: Option<&str>, min: Vec3, max: Vec3, first_index: usize) -> String {
+    let mut obj = object.map(|name| format!("o {name}\n")).unwrap_or_default();
+    for &z in &[min.z, max.z] {
+        obj += &format!("v {} {} {z}\n", min.x, min.y);
+        obj += &format!("v {} {} {z}\n", min.x, max.y);
+        obj += &format!("v {} {} {z}\n", max.x, max.y);
+        obj += &format!("v {} {} {z}\n", max.x, min.y);
+    }
+    let faces = [
+        [1, 2, 3],
+        [1, 3, 4],
+        [8, 7, 6],
+        [8, 6, 5],
+        [4, 3, 7],
+        [4, 7, 8],
+        [5, 6, 2],
+        [5, 2, 1],
+        [2, 6, 7],
+        [2, 7, 3],
+        [5, 1, 4],
+        [5, 4, 8],
+    ];
+    for [a, b, c] in faces {
+        let i = first_index - 1;
+        obj += &format!("f {} {} {}\n", a + i, b + i, c + i);
+    }
+    obj
+}
+
+// Far from the origin and far too big for the default view
+const MIN: Vec3 = Vec3::new(1000.0, 2000.0, -300.0);
+const MAX: Vec3 = Vec3::new(1500.0, 2250.0, -200.0);
+
+fn assert_close(a: Vec3, b: Vec3) {
+    assert!((a - b).magnitude() < 1e-4, "{a:?} != {b:?}");
+}
+
+#[test]
+fn normalize_to_unit_bakes_scale_and_center() {
+    let mut engine = Engine::new(W, H);
+    common::load_obj(&mut engine, "box", &box_obj(None, MIN, MAX, 1));
+
+    let mesh = &mut engine.model_mut("box").unwrap().meshes_mut()[0];
+    let (min, max) = mesh.bounding_box();
+    assert_close(min, MIN);
+    assert_close(max, MAX);
+
+    mesh.normalize_to_unit(true);
+    let (min, max) = mesh.bounding_box();
+    // 500 x 250 x 100 becomes 1 x 0.5 x 0.2 around the centroid
+    assert_close(max - min, Vec3::new(1.0, 0.5, 0.2));
+    assert_close(min + max, Vec3::ZERO);
+    assert_eq!(*mesh.transform(), Transform::default());
+}
+
+#[test]
+fn normalize_without_center_only_scales() {
+    let mut engine = Engine::new(W, H);
+    common::load_obj(&mut engine, "box", &box_obj(None, MIN, MAX, 1));
+
+    let mesh = &mut engine.model_mut("box").unwrap().meshes_mut()[0];
+    mesh.normalize_to_unit(false);
+    let (min, max) = mesh.bounding_box();
+    assert_close(min, MIN / 500.0);
+    assert_close(max, MAX / 500.0);
+}
+
+#[test]
+fn model_normalization_keeps_meshes_in_proportion() {
+    let big = box_obj(Some("big"), Vec3::ZERO, Vec3::new(100.0, 100.0, 100.0), 1);
+    let small = box_obj(
+        Some("small"),
+        Vec3::new(200.0, 0.0, 0.0),
+        Vec3::new(210.0, 10.0, 10.0),
+        9,
+    );
+    let mut engine = Engine::new(W, H);
+    common::load_obj(&mut engine, "pair", &(big + &small));
+
+    let model = engine.model_mut("pair").unwrap();
+    model.normalize_to_unit(false);
+    let (min, max) = model.bounding_box().unwrap();
+    assert_close(max - min, Vec3::new(1.0, 100.0 / 210.0, 100.0 / 210.0));
+
+    let (small_min, small_max) = model.mesh("small").unwrap().bounding_box();
+    assert_close(small_max - small_min, Vec3::new(10.0, 10.0, 10.0) / 210.0);
+}
+
+#[test]
+fn fitted_model_is_framed_by_the_camera() {
+    let path = common::temp_path("obj");
+    std::fs::write(&path, box_obj(None, MIN, MAX, 1)).expect("write temp obj");
+
+    // Small, medium and large targets all end up in view
+    for target_size in [0.5, 2.0, 20.0] {
+        let mut engine = Engine::new(W, H);
+        engine.draw_grid = false;
+        engine.set_render_mode(RenderMode::Filled);
+        engine.set_shading_mode(ShadingMode::None);
+        engine.palette_mut().fill = FILL;
+        engine
+            .load_mesh_fitted("box", path.to_str().unwrap(), target_size)
+            .unwrap();
+        engine.update();
+        engine.render();
+
+        let pixels: Vec<u32> = engine
+            .frame_buffer()
+            .chunks_exact(4)
+            .map(|b| u32::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        let center = pixels[((H / 2) * W + W / 2) as usize];
+        assert_eq!(center, FILL, "target {target_size}: not centered");
+
+        // Nothing touches the frame edge, so the model is wholly visible
+        let edge = (0..W)
+            .flat_map(|x| [x, (H - 1) * W + x])
+            .chain((0..H).flat_map(|y| [y * W, y * W + W - 1]));
+        assert!(
+            edge.into_iter().all(|i| pixels[i as usize] != FILL),
+            "target {target_size}: model cut off"
+        );
+        assert_eq!(engine.stats().faces_clipped_away, 0);
+    }
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,99 @@
+//! Fog tests — a long ground plane fades into the fog color toward the
+//! horizon, and the background is cleared to that same color.
+
+use russsty::prelude::*;
+
+mod common;
+
+// A ground plane on y = 0 reaching far ahead of the camera.
+const PLANE_OBJ: &str = "\
+v -50.0 0.0 -4.0
+v -50.0 0.0 80.0
+v 50.0 0.0 80.0
+v 50.0 0.0 -4.0
+f 1 2 3
+f 1 3 4
+";
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FILL: u32 = 0xFFFF0000;
+const FOG_COLOR: u32 = 0xFF4060A0;
+
+fn render_plane(fog: Option<Fog>, rasterizer: RasterizerType) -> Engine {
+    let mut engine = common::engine_with_obj("plane", PLANE_OBJ, W, H);
+    engine.backface_culling = false;
+    engine.palette_mut().fill = FILL;
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_rasterizer(rasterizer);
+    engine.set_camera(FpsCamera::new(Vec3::new(0.0, 1.0, -5.0)));
+    if let Some(fog) = fog {
+        engine.set_fog(fog);
+    }
+    engine.update();
+    engine.render();
+    engine
+}
+
+/// Red channel: the plane is pure red, the fog has little red, so this
+/// falls as the fog thickens.
+fn red(color: u32) -> u32 {
+    (color >> 16) & 0xFF
+}
+
+#[test]
+fn plane_fades_into_fog_toward_horizon() {
+    let fog = Fog {
+        color: FOG_COLOR,
+        mode: FogMode::Linear {
+            start: 2.0,
+            end: 40.0,
+        },
+    };
+
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        let engine = render_plane(Some(fog), rasterizer);
+        let x = W / 2;
+
+        // Sky above the horizon is the fog color, not the palette background
+        assert_eq!(common::pixel(&engine, (x, 0)), FOG_COLOR);
+
+        // Walking up the screen from the bottom edge moves away over the
+        // plane; the red channel never increases on the way to the horizon.
+        let column: Vec<u32> = (H / 2 + 1..H)
+            .rev()
+            .map(|y| common::pixel(&engine, (x, y)))
+            .collect();
+        assert!(
+            column.windows(2).all(|p| red(p[1]) <= red(p[0])),
+            "{rasterizer:?}: not monotonic: {column:x?}"
+        );
+        assert!(red(column[0]) > red(*column.last().unwrap()));
+        assert_ne!(
+            column[0], FILL,
+            "{rasterizer:?}: nearest row should already be fogged"
+        );
+    }
+}
+
+#[test]
+fn without_fog_plane_keeps_its_color() {
+    let engine = render_plane(None, RasterizerType::EdgeFunction);
+    assert_eq!(common::pixel(&engine, (W / 2, H - 1)), FILL);
+    assert_eq!(common::pixel(&engine, (W / 2, H / 2 + 2)), FILL);
+    assert_eq!(engine.fog(), None);
+}
+
+#[test]
+fn exponential_fog_never_fully_covers() {
+    let fog = Fog {
+        color: FOG_COLOR,
+        mode: FogMode::Exp { density: 0.05 },
+    };
+    let engine = render_plane(Some(fog), RasterizerType::EdgeFunction);
+    let near = common::pixel(&engine, (W / 2, H - 1));
+    assert_ne!(near, FILL);
+    assert_ne!(near, FOG_COLOR);
+}
@@ -0,0 +1,127 @@
+//! Field of view tests — the frame spans exactly the projection's field of
+//! view along either axis: geometry on the left and right frustum planes
+//! reaches the first and last columns without being clipped early or
+//! drawn past the buffer, whatever the aspect ratio.
+
+use russsty::prelude::*;
+
+mod common;
+
+/// The camera sits at z = -5 looking down +z at the origin.
+const DISTANCE: f32 = 5.0;
+
+const SIZES: [(u32, u32); 4] = [(64, 64), (128, 64), (48, 96), (100, 30)];
+
+/// A camera-facing quad at z = 0, `half_width` across either side of the
+/// center and `half_height` above and below it.
+fn engine_with_quad(width: u32, height: u32, half_width: f32, half_height: f32) -> Engine {
+    let (x, y) = (half_width, half_height);
+    let obj = format!(
+        "v {} {} 0\nv {} {y} 0\nv {x} {y} 0\nv {x} {} 0\nf 1 2 3\nf 1 3 4\n",
+        -x, -y, -x, -y
+    );
+    let mut engine = common::engine_with_obj("quad", &obj, width, height);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine
+}
+
+/// Half the view's width at the quad's depth.
+fn half_width_at_quad(engine: &Engine) -> f32 {
+    DISTANCE * (engine.projection().fov_x() / 2.0).tan()
+}
+
+/// Rows of `engine`'s frame, each as a mask of which pixels are covered.
+fn coverage(engine: &mut Engine) -> Vec<Vec<bool>> {
+    let (width, _) = engine.frame_size();
+    let background = engine.palette().background;
+    engine
+        .render_offscreen()
+        .chunks(width as usize)
+        .map(|row| row.iter().map(|&p| p != background).collect())
+        .collect()
+}
+
+/// Check that the quad, `half_height` tall either side of the center and
+/// at least as wide as the view, fills every column of the rows it covers
+/// and nothing else.
+fn assert_spans_the_frame(engine: &mut Engine, half_height: f32) {
+    let (width, height) = engine.frame_size();
+    let rows = coverage(engine);
+    let middle = &rows[height as usize / 2];
+    assert!(
+        middle.iter().all(|&covered| covered),
+        "{width}x{height}: middle row {middle:?}"
+    );
+
+    // Rows well above and below the quad are empty, so nothing past the
+    // side edges wrapped onto a neighbouring row
+    let half_rows =
+        half_height / (DISTANCE * (engine.projection().fov_y() / 2.0).tan()) * height as f32 / 2.0;
+    let margin = half_rows.ceil() as usize + 2;
+    for (y, row) in rows.iter().enumerate() {
+        if y + margin < height as usize / 2 || y > height as usize / 2 + margin {
+            assert!(!row.contains(&true), "{width}x{height}: row {y} covered");
+        }
+    }
+}
+
+#[test]
+fn geometry_on_the_side_planes_reaches_the_edge_columns() {
+    for (width, height) in SIZES {
+        for axis in [FovAxis::Vertical, FovAxis::Horizontal] {
+            let mut probe = Engine::new(width, height);
+            probe.set_fov(60.0, axis);
+            let half_width = half_width_at_quad(&probe);
+            let half_height = 0.1 * half_width;
+
+            let mut engine = engine_with_quad(width, height, half_width, half_height);
+            engine.set_fov(60.0, axis);
+            assert_spans_the_frame(&mut engine, half_height);
+
+            // Running well past the sides is clipped to the same columns
+            let mut engine = engine_with_quad(width, height, 3.0 * half_width, half_height);
+            engine.set_fov(60.0, axis);
+            assert_spans_the_frame(&mut engine, half_height);
+        }
+    }
+}
+
+#[test]
+fn geometry_inside_the_side_planes_stops_short_of_the_edges() {
+    for (width, height) in SIZES {
+        let mut probe = Engine::new(width, height);
+        probe.set_fov(60.0, FovAxis::Horizontal);
+        // Two pixels in from each side
+        let inset = 1.0 - 4.0 / width as f32;
+        let half_width = inset * half_width_at_quad(&probe);
+
+        let mut engine = engine_with_quad(width, height, half_width, 0.1 * half_width);
+        engine.set_fov(60.0, FovAxis::Horizontal);
+        let rows = coverage(&mut engine);
+        let middle = &rows[height as usize / 2];
+        assert!(
+            !middle[0] && !middle[width as usize - 1],
+            "{width}x{height}"
+        );
+        assert!(middle[2] && middle[width as usize - 3], "{width}x{height}");
+    }
+}
+
+#[test]
+fn horizontal_fov_keeps_the_width_across_resizes() {
+    let mut engine = Engine::new(64, 64);
+    engine.set_fov(90.0, FovAxis::Horizontal);
+    for (width, height) in SIZES {
+        engine.resize(width, height);
+        assert_eq!(engine.fov_axis(), FovAxis::Horizontal);
+        assert!((engine.projection().fov_x().to_degrees() - 90.0).abs() < 1e-3);
+    }
+
+    // Vertical keeps the height instead
+    engine.set_fov(45.0, FovAxis::Vertical);
+    for (width, height) in SIZES {
+        engine.resize(width, height);
+        assert!((engine.fov_degrees() - 45.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,25 @@
+//! Frame statistics — `Engine::stats` counts what the last frame drew.
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+#[test]
+fn frame_stats_count_rendered_quad() {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, W, H);
+    engine.backface_culling = false;
+    engine.set_picking(true);
+    engine.update();
+    engine.render();
+    let stats = engine.stats();
+    assert_eq!(stats.faces_total, 2);
+    assert_eq!(stats.triangles_drawn, 2);
+    assert_eq!(stats.faces_backface_culled, 0);
+
+    // HUD draws on top without disturbing picking or stats
+    engine.draw_debug_hud(true);
+    engine.render();
+    assert_eq!(engine.stats(), stats);
+    assert!(engine.pick(W as i32 / 2 + 3, H as i32 / 2 + 3).is_some());
+}
@@ -0,0 +1,132 @@
+//! Frustum debugging tests — per-placement cull results, culling frozen
+//! to one view while the camera moves, and the frustum and cull results
+//! drawn as lines.
+
+use russsty::prelude::*;
+use russsty::MeshHandle;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FRUSTUM: u32 = 0xFF00FFFF;
+const CULLED: u32 = 0xFFFF4040;
+
+// A small quad facing the default camera
+const TILE_OBJ: &str = "\
+o tile
+v -0.4 -0.4 0.0
+v -0.4 0.4 0.0
+v 0.4 0.4 0.0
+v 0.4 -0.4 0.0
+f 1 2 3
+f 1 3 4
+";
+
+fn engine_with_tile() -> (Engine, MeshHandle) {
+    let mut engine = common::engine_with_obj("tiles", TILE_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    let handle = engine.mesh_handle("tiles", "tile").expect("tile mesh");
+    (engine, handle)
+}
+
+fn at(x: f32, y: f32) -> Transform {
+    let mut transform = Transform::new();
+    transform.set_position(Vec3::new(x, y, 0.0));
+    transform
+}
+
+fn results(engine: &Engine) -> Vec<CullResult> {
+    engine
+        .cull_results()
+        .iter()
+        .map(|cull| cull.result)
+        .collect()
+}
+
+fn count(frame: &[u32], color: u32) -> usize {
+    frame.iter().filter(|&&pixel| pixel == color).count()
+}
+
+#[test]
+fn every_placement_gets_a_cull_result() {
+    let (mut engine, handle) = engine_with_tile();
+    engine.update();
+    assert_eq!(
+        engine.cull_results(),
+        [MeshCull {
+            model_index: 0,
+            mesh_index: 0,
+            instance: None,
+            result: CullResult::Inside,
+        }]
+    );
+
+    // The view is 4 units across at the tiles: one in the middle, one
+    // across its right edge and one far off
+    engine.add_instances(handle, &[at(0.0, 0.0), at(2.0, 0.0), at(50.0, 0.0)]);
+    engine.update();
+    assert_eq!(
+        results(&engine),
+        [
+            CullResult::Inside,
+            CullResult::Intersecting,
+            CullResult::Culled
+        ]
+    );
+    let instances: Vec<_> = engine.cull_results().iter().map(|c| c.instance).collect();
+    assert_eq!(instances, [Some(0), Some(1), Some(2)]);
+}
+
+#[test]
+fn frozen_culling_ignores_the_camera() {
+    let (mut engine, handle) = engine_with_tile();
+    engine.add_instances(handle, &[at(0.0, 0.0), at(50.0, 0.0)]);
+    engine.update();
+    engine.freeze_culling();
+    assert!(engine.culling_frozen());
+
+    // Facing the far tile, which the frozen view still rejects
+    engine.set_camera(FpsCamera::looking_at(
+        Vec3::new(50.0, 0.0, -5.0),
+        Vec3::new(50.0, 0.0, 0.0),
+    ));
+    let background = engine.palette().background;
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(results(&engine), [CullResult::Inside, CullResult::Culled]);
+    assert_eq!(count(&frame, background), frame.len());
+
+    engine.unfreeze_culling();
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(results(&engine), [CullResult::Culled, CullResult::Inside]);
+    assert_ne!(frame[(H / 2 * W + W / 2) as usize], background);
+}
+
+#[test]
+fn the_frozen_frustum_and_culled_bounds_are_drawn() {
+    let (mut engine, handle) = engine_with_tile();
+    // Above the view, which is 4 units high at the tiles
+    engine.add_instances(handle, &[at(0.0, 0.0), at(0.0, 3.0)]);
+    engine.freeze_culling();
+
+    // Backed off to see the frozen view from outside
+    engine.set_camera(FpsCamera::looking_at(
+        Vec3::new(0.0, 0.0, -15.0),
+        Vec3::ZERO,
+    ));
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(count(&frame, FRUSTUM), 0);
+    assert_eq!(count(&frame, CULLED), 0);
+
+    engine.set_debug_draw_frustum(true);
+    let frame = engine.render_offscreen().to_vec();
+    assert!(count(&frame, FRUSTUM) > 0);
+    // The culled tile's outline, around where it would be
+    let culled: Vec<u32> = (0..W * H)
+        .filter(|&i| frame[i as usize] == CULLED)
+        .map(|i| i / W)
+        .collect();
+    assert!(!culled.is_empty());
+    assert!(culled.iter().all(|&y| y < H / 2), "{culled:?}");
+}
@@ -0,0 +1,94 @@
+//! Fixed-timestep loop tests — an animated scene driven through `GameLoop`
+//! with a stand-in clock reaches the same state at the same tick, however
+//! the time is split into frames.
+
+use std::f32::consts::FRAC_PI_2;
+use std::time::Duration;
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+// A wedge, so a spin shows in the image
+const WEDGE_OBJ: &str = "\
+v -1.0 -1.0 0.0
+v 0.0 1.0 0.0
+v 1.0 -0.5 0.0
+f 1 2 3
+";
+
+fn spinning_engine() -> Engine {
+    let mut engine = common::engine_with_obj("wedge", WEDGE_OBJ, W, H);
+    engine.backface_culling = false;
+    engine
+        .model_mut("wedge")
+        .unwrap()
+        .set_animator(Animator::new().spin(Vec3::new(0.0, 0.0, 1.0), FRAC_PI_2));
+    engine
+}
+
+/// Drive a fresh scene through `frame_times`, returning it with the number
+/// of ticks run and how many frames were drawn.
+fn run(frame_times: impl IntoIterator<Item = Duration>) -> (Engine, u64, usize) {
+    let mut engine = spinning_engine();
+    let mut game_loop = GameLoop::new(50.0);
+    let mut frames = 0;
+    for elapsed in frame_times {
+        game_loop.advance(
+            elapsed,
+            &mut engine,
+            |engine, dt| engine.animate(dt),
+            |engine, _| {
+                engine.update();
+                engine.render();
+            },
+        );
+        frames += 1;
+    }
+    (engine, game_loop.ticks(), frames)
+}
+
+#[test]
+fn same_ticks_give_the_same_frame_at_any_frame_rate() {
+    // Two seconds at 100 FPS, at 10 FPS, and at an uneven rate
+    let (mut fast, fast_ticks, _) = run(std::iter::repeat_n(Duration::from_millis(10), 200));
+    let (mut slow, slow_ticks, _) = run(std::iter::repeat_n(Duration::from_millis(100), 20));
+    let uneven = [3, 47, 20, 110, 5, 15].map(Duration::from_millis);
+    let (mut ragged, ragged_ticks, frames) = run(uneven
+        .iter()
+        .copied()
+        .cycle()
+        .take(60)
+        .chain([Duration::ZERO]));
+    assert_eq!(fast_ticks, 100);
+    assert_eq!(slow_ticks, 100);
+    assert_eq!(ragged_ticks, 100);
+    assert_eq!(frames, 61);
+
+    let rotation = fast.model("wedge").unwrap().transform().rotation();
+    assert_eq!(
+        slow.model("wedge").unwrap().transform().rotation(),
+        rotation
+    );
+    assert_eq!(
+        ragged.model("wedge").unwrap().transform().rotation(),
+        rotation
+    );
+
+    let expected = fast.render_offscreen().to_vec();
+    assert_eq!(slow.render_offscreen(), &expected[..]);
+    assert_eq!(ragged.render_offscreen(), &expected[..]);
+}
+
+#[test]
+fn frames_between_ticks_leave_the_simulation_alone() {
+    // Ten frames shorter than a tick each add up to five ticks
+    let (engine, ticks, _) = run(std::iter::repeat_n(Duration::from_millis(10), 10));
+    assert_eq!(ticks, 5);
+    let expected = Vec3::new(0.0, 0.0, 5.0 * 0.02 * FRAC_PI_2);
+    let rotation = engine.model("wedge").unwrap().transform().rotation();
+    assert!((rotation - expected).magnitude() < 1e-5, "{rotation:?}");
+}
@@ -0,0 +1,178 @@
+//! glTF loading tests — a textured quad written to a temp `.gltf` with its
+//! `.bin` buffer and PNG image, loaded through `Engine::add_gltf_model`.
+//! Checks node transforms are flattened and mirrored into the engine's
+//! left-handed space and the base-color texture comes out the right way up.
+
+#![cfg(feature = "gltf")]
+
+use russsty::prelude::*;
+use std::path::PathBuf;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+const RED: u32 = 0xFFFF0000;
+const GREEN: u32 = 0xFF00FF00;
+const BLUE: u32 = 0xFF0000FF;
+const WHITE: u32 = 0xFFFFFFFF;
+
+/// 2x2 quad in the glTF z = 0 plane, front (CCW) side toward +z, with UVs
+/// putting texture row 0 at the top.
+fn quad_buffer() -> Vec<u8> {
+    let positions: [[f32; 3]; 4] = [
+        [-1.0, -1.0, 0.0],
+        [1.0, -1.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [-1.0, 1.0, 0.0],
+    ];
+    let texcoords: [[f32; 2]; 4] = [[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]];
+    let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+
+    let mut bytes = Vec::new();
+    for v in positions.iter().flatten().chain(texcoords.iter().flatten()) {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    for i in indices {
+        bytes.extend_from_slice(&i.to_le_bytes());
+    }
+    bytes
+}
+
+/// A glTF document drawing the quad at `mesh_node`, under `parents` (outermost
+/// first). Each entry is a node's JSON transform properties.
+fn quad_gltf(bin: &str, png: &str, parents: &[&str], mesh_node: &str) -> String {
+    let mut nodes = Vec::new();
+    for (i, transform) in parents.iter().enumerate() {
+        let comma = if transform.is_empty() { "" } else { ", " };
+        nodes.push(format!(
+            r#"{{ {transform}{comma}"children": [{}] }}"#,
+            i + 1
+        ));
+    }
+    let comma = if mesh_node.is_empty() { "" } else { ", " };
+    nodes.push(format!(r#"{{ {mesh_node}{comma}"mesh": 0 }}"#));
+
+    format!(
+        r#"{{
+  "asset": {{ "version": "2.0" }},
+  "scene": 0,
+  "scenes": [{{ "nodes": [0] }}],
+  "nodes": [{nodes}],
+  "meshes": [{{
+    "name": "quad",
+    "primitives": [{{
+      "attributes": {{ "POSITION": 0, "TEXCOORD_0": 1 }},
+      "indices": 2,
+      "material": 0
+    }}]
+  }}],
+  "materials": [{{
+    "pbrMetallicRoughness": {{ "baseColorTexture": {{ "index": 0 }} }}
+  }}],
+  "textures": [{{ "source": 0 }}],
+  "images": [{{ "uri": "{png}" }}],
+  "buffers": [{{ "uri": "{bin}", "byteLength": 92 }}],
+  "bufferViews": [
+    {{ "buffer": 0, "byteOffset": 0, "byteLength": 48 }},
+    {{ "buffer": 0, "byteOffset": 48, "byteLength": 32 }},
+    {{ "buffer": 0, "byteOffset": 80, "byteLength": 12 }}
+  ],
+  "accessors": [
+    {{ "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+       "min": [-1.0, -1.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+    {{ "bufferView": 1, "componentType": 5126, "count": 4, "type": "VEC2" }},
+    {{ "bufferView": 2, "componentType": 5123, "count": 6, "type": "SCALAR" }}
+  ]
+}}"#,
+        nodes = nodes.join(", ")
+    )
+}
+
+/// Write the quad's files to a fresh temp directory and return the `.gltf`
+/// path. The 2x2 texture is red, green over blue, white.
+fn write_quad(parents: &[&str], mesh_node: &str) -> PathBuf {
+    let dir = common::temp_dir();
+
+    std::fs::write(dir.join("quad.bin"), quad_buffer()).expect("write bin");
+    let image = image::RgbaImage::from_fn(2, 2, |x, y| match (x, y) {
+        (0, 0) => image::Rgba([255, 0, 0, 255]),
+        (1, 0) => image::Rgba([0, 255, 0, 255]),
+        (0, 1) => image::Rgba([0, 0, 255, 255]),
+        _ => image::Rgba([255, 255, 255, 255]),
+    });
+    image.save(dir.join("quad.png")).expect("write png");
+
+    let path = dir.join("quad.gltf");
+    std::fs::write(&path, quad_gltf("quad.bin", "quad.png", parents, mesh_node))
+        .expect("write gltf");
+    path
+}
+
+#[test]
+fn textured_quad_renders_right_way_up() {
+    let path = write_quad(&[], "");
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine
+        .add_gltf_model("quad", path.to_str().unwrap())
+        .expect("load quad");
+    std::fs::remove_dir_all(path.parent().unwrap()).ok();
+
+    let model = engine.model("quad").unwrap();
+    assert_eq!(model.mesh_count(), 1);
+    assert_eq!(model.mesh_textures().len(), 1);
+    assert!(model.mesh_texture(0).is_some());
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_texture_mode(TextureMode::Replace);
+    engine.update();
+    engine.render();
+
+    // The quad faces the default camera, so it survives backface culling,
+    // and +x stays on the right
+    assert_eq!(common::pixel(&engine, (W / 2 - 8, H / 2 - 8)), RED);
+    assert_eq!(common::pixel(&engine, (W / 2 + 8, H / 2 - 8)), GREEN);
+    assert_eq!(common::pixel(&engine, (W / 2 - 8, H / 2 + 8)), BLUE);
+    assert_eq!(common::pixel(&engine, (W / 2 + 8, H / 2 + 8)), WHITE);
+}
+
+#[test]
+fn node_transforms_are_flattened_and_mirrored() {
+    // Parent turned 90 degrees about +y (x -> -z in glTF's right-handed
+    // space), child moved along its parent's x
+    let s = std::f32::consts::FRAC_1_SQRT_2;
+    let parent = format!(r#""translation": [0, 1, -2], "rotation": [0, {s}, 0, {s}]"#);
+    let path = write_quad(
+        &[&parent],
+        r#""translation": [1, 0, 0], "scale": [2, 2, 2]"#,
+    );
+    let model = Model::from_gltf("quad", path.to_str().unwrap()).expect("load quad");
+    std::fs::remove_dir_all(path.parent().unwrap()).ok();
+
+    let matrix = model.mesh("quad").unwrap().transform().to_matrix();
+    let expect = |local: Vec3, gltf_world: Vec3| {
+        let got = matrix * local;
+        let want = Vec3::new(gltf_world.x, gltf_world.y, -gltf_world.z);
+        assert!(
+            (got - want).magnitude() < 1e-4,
+            "{local:?}: got {got:?}, want {want:?}"
+        );
+    };
+    // Mesh origin: (0, 1, -2) + R * (1, 0, 0) = (0, 1, -3)
+    expect(Vec3::ZERO, Vec3::new(0.0, 1.0, -3.0));
+    // Vertex (1, 0, 0) with the child's scale of 2: R * (3, 0, 0) = (0, 0, -3)
+    expect(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, -5.0));
+    // Vertex (0, 1, 0) in the engine is (0, 1, 0) in glTF too
+    expect(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 3.0, -3.0));
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let mut engine = Engine::new(W, H);
+    assert!(engine
+        .add_gltf_model("missing", "does/not/exist.gltf")
+        .is_err());
+}
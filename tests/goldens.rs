@@ -0,0 +1,183 @@
+//! Golden-image tests — canonical scenes rendered offscreen with each
+//! built-in rasterizer and compared with the PNGs in `tests/goldens/`, so a
+//! change that alters rasterizer output fails here instead of slipping by.
+//!
+//! After an intended change to the output, regenerate the goldens and
+//! review the new images before committing them:
+//!
+//! ```text
+//! UPDATE_GOLDENS=1 cargo test --test goldens
+//! ```
+//!
+//! A failing comparison writes what was rendered to
+//! `target/golden-failures/` for side-by-side inspection.
+
+use russsty::prelude::*;
+use russsty::texture::Texture;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// Largest per-channel difference still counted as a match, so rounding
+/// tweaks don't need new goldens but any real drift does.
+const CHANNEL_TOLERANCE: u8 = 2;
+
+const RASTERIZERS: [(RasterizerType, &str); 2] = [
+    (RasterizerType::Scanline, "scanline"),
+    (RasterizerType::EdgeFunction, "edge"),
+];
+
+// Wound clockwise toward the default camera at z = -5
+const TRIANGLE_OBJ: &str = "\
+v -1.5 -1.0 0.0
+v 0.0 1.5 0.0
+v 1.5 -1.0 0.0
+f 1 2 3
+";
+
+// Normals fanned outward so Gouraud shading varies across the face
+const GOURAUD_TRIANGLE_OBJ: &str = "\
+v -1.5 -1.0 0.0
+v 0.0 1.5 0.0
+v 1.5 -1.0 0.0
+vn -0.6 0.0 -0.8
+vn 0.0 0.6 -0.8
+vn 0.6 0.0 -0.8
+f 1//1 2//2 3//3
+";
+
+// A floor triangle running from behind the camera to well in front of it
+const FLOOR_TRIANGLE_OBJ: &str = "\
+v -2.0 -1.0 -8.0
+v 0.0 -1.0 2.0
+v 2.0 -1.0 -8.0
+f 1 2 3
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("scene", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+fn goldens_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/goldens")
+}
+
+fn to_rgba(pixels: &[u32]) -> image::RgbaImage {
+    let bytes = pixels
+        .iter()
+        .flat_map(|argb| {
+            let [a, r, g, b] = argb.to_be_bytes();
+            [r, g, b, a]
+        })
+        .collect();
+    image::RgbaImage::from_raw(W, H, bytes).unwrap()
+}
+
+/// Render `engine` with every rasterizer and check each frame against
+/// `<name>_<rasterizer>.png`, or rewrite the goldens with `UPDATE_GOLDENS`.
+fn assert_matches_goldens(name: &str, engine: &mut Engine) {
+    let update = std::env::var_os("UPDATE_GOLDENS").is_some_and(|v| v != "0");
+    let mut failures = Vec::new();
+
+    for (rasterizer, suffix) in RASTERIZERS {
+        engine.set_rasterizer(rasterizer);
+        let actual = to_rgba(engine.render_offscreen());
+        let file = format!("{name}_{suffix}.png");
+        let golden_path = goldens_dir().join(&file);
+
+        if update {
+            std::fs::create_dir_all(goldens_dir()).unwrap();
+            actual.save(&golden_path).expect("write golden");
+            continue;
+        }
+
+        let golden = match image::open(&golden_path) {
+            Ok(image) => image.to_rgba8(),
+            Err(e) => {
+                failures.push(format!(
+                    "{file}: can't read golden ({e}); run with UPDATE_GOLDENS=1"
+                ));
+                continue;
+            }
+        };
+        let mismatched: Vec<(u32, u32)> = actual
+            .enumerate_pixels()
+            .filter(|(x, y, pixel)| {
+                let expected = golden.get_pixel(*x, *y);
+                pixel
+                    .0
+                    .iter()
+                    .zip(expected.0)
+                    .any(|(a, e)| a.abs_diff(e) > CHANNEL_TOLERANCE)
+            })
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        if golden.dimensions() != actual.dimensions() || !mismatched.is_empty() {
+            let failure_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/golden-failures");
+            std::fs::create_dir_all(&failure_dir).unwrap();
+            actual.save(failure_dir.join(&file)).ok();
+            failures.push(format!(
+                "{file}: {} pixels differ, first at {:?}; rendered frame saved to {}",
+                mismatched.len(),
+                mismatched.first(),
+                failure_dir.join(&file).display()
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn flat_triangle() {
+    let mut engine = engine_with(TRIANGLE_OBJ);
+    engine.set_shading_mode(ShadingMode::Flat);
+    // Turned so the light hits it at an angle
+    engine
+        .model_mut("scene")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.0, 0.5, 0.0));
+    assert_matches_goldens("flat_triangle", &mut engine);
+}
+
+#[test]
+fn gouraud_triangle() {
+    let mut engine = engine_with(GOURAUD_TRIANGLE_OBJ);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    assert_matches_goldens("gouraud_triangle", &mut engine);
+}
+
+#[test]
+fn textured_quad() {
+    let mut engine = engine_with(common::UV_QUAD_OBJ);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_texture_mode(TextureMode::Replace);
+    let checker = Texture::from_fn(8, 8, |x, y| {
+        if (x + y) % 2 == 0 {
+            0xFFE0E0E0
+        } else {
+            0xFF203040
+        }
+    })
+    .unwrap();
+    let model = engine.model_mut("scene").unwrap();
+    model.set_texture(checker);
+    // Turned away so perspective-correct interpolation shows
+    model.transform_mut().set_rotation(Vec3::new(0.0, 0.9, 0.0));
+    assert_matches_goldens("textured_quad", &mut engine);
+}
+
+#[test]
+fn near_clipped_triangle() {
+    let mut engine = engine_with(FLOOR_TRIANGLE_OBJ);
+    engine.set_shading_mode(ShadingMode::Flat);
+    assert_matches_goldens("near_clipped_triangle", &mut engine);
+    // The scene is only useful while it really crosses the near plane
+    assert_eq!(engine.stats().faces_clipped, 1);
+}
@@ -0,0 +1,83 @@
+//! Ground grid tests — the world-space grid lies on the y=0 plane, stays
+//! below the horizon, and is occluded by filled geometry.
+
+use russsty::prelude::Vec3;
+use russsty::{Engine, GridMode, RenderMode};
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const GRID: u32 = 0xFF00FF00;
+
+fn engine(with_quad: bool) -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.backface_culling = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.palette_mut().grid = GRID;
+    // Eye level with the top of the quad, one unit above the ground
+    engine.camera_mut().set_position(Vec3::new(0.0, 1.0, -5.0));
+
+    if with_quad {
+        common::load_obj(&mut engine, "quad", common::QUAD_OBJ);
+    }
+    engine
+}
+
+/// Count grid-colored pixels in the rows `rows` across the middle of the screen.
+fn grid_pixels(engine: &Engine, rows: std::ops::Range<u32>) -> usize {
+    rows.flat_map(|y| (W / 2 - 4..W / 2 + 4).map(move |x| (x, y)))
+        .filter(|&(x, y)| common::pixel(engine, (x, y)) == GRID)
+        .count()
+}
+
+#[test]
+fn world_grid_is_default_and_stays_below_horizon() {
+    let mut engine = engine(false);
+    assert!(matches!(engine.grid(), GridMode::WorldPlane { .. }));
+    engine.update();
+    engine.render();
+
+    let above = (0..H / 2 - 1)
+        .flat_map(|y| (0..W).map(move |x| (x, y)))
+        .filter(|&(x, y)| common::pixel(&engine, (x, y)) == GRID)
+        .count();
+    let below = (H / 2 + 1..H)
+        .flat_map(|y| (0..W).map(move |x| (x, y)))
+        .filter(|&(x, y)| common::pixel(&engine, (x, y)) == GRID)
+        .count();
+    assert_eq!(above, 0);
+    assert!(below > 0);
+}
+
+#[test]
+fn world_grid_is_occluded_by_meshes() {
+    // Between the horizon and where the quad meets the ground, the quad
+    // stands in front of the grid
+    let rows = H / 2 + 2..H / 2 + 12;
+
+    let mut open = engine(false);
+    open.update();
+    open.render();
+    assert!(grid_pixels(&open, rows.clone()) > 0);
+
+    let mut occluded = engine(true);
+    occluded.update();
+    occluded.render();
+    assert_eq!(grid_pixels(&occluded, rows), 0);
+}
+
+#[test]
+fn screen_space_grid_ignores_camera() {
+    let mut engine = engine(false);
+    engine.set_grid(GridMode::ScreenSpace);
+    engine.update();
+    engine.render();
+
+    // Lines every 50 pixels through the middle, which at 64 pixels
+    // leaves one each way
+    assert_eq!(common::pixel(&engine, (W / 2, 10)), GRID);
+    assert_eq!(common::pixel(&engine, (10, H / 2)), GRID);
+    assert_ne!(common::pixel(&engine, (10, 10)), GRID);
+    assert_ne!(common::pixel(&engine, (0, 0)), GRID);
+}
@@ -0,0 +1,108 @@
+//! Handedness tests — a scene mirrored in z, with its faces rewound, must
+//! render the same frame right-handed as the original does left-handed.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 96;
+const H: u32 = 64;
+
+// Two lopsided tetrahedra, the second partly behind the first, wound CW
+// from outside so backface culling and depth testing both matter.
+const VERTICES: [[f32; 3]; 8] = [
+    [-1.0, -1.0, -0.5],
+    [0.0, 1.2, 0.0],
+    [1.0, -0.8, -0.3],
+    [0.2, -0.4, 1.0],
+    [0.5, -0.5, 1.5],
+    [1.5, 1.0, 2.0],
+    [2.5, -0.5, 1.2],
+    [1.6, -0.2, 3.0],
+];
+const FACES: [[usize; 3]; 8] = [
+    [1, 2, 3],
+    [1, 4, 2],
+    [2, 4, 3],
+    [1, 3, 4],
+    [5, 6, 7],
+    [5, 8, 6],
+    [6, 8, 7],
+    [5, 7, 8],
+];
+
+/// The scene as OBJ text; `mirrored` negates z and reverses each face.
+fn scene_obj(mirrored: bool) -> String {
+    let mut obj = String::new();
+    for [x, y, z] in VERTICES {
+        let z = if mirrored { -z } else { z };
+        obj += &format!("v {x} {y} {z}\n");
+    }
+    for [a, b, c] in FACES {
+        let (b, c) = if mirrored { (c, b) } else { (b, c) };
+        obj += &format!("f {a} {b} {c}\n");
+    }
+    obj
+}
+
+fn mirror(v: Vec3) -> Vec3 {
+    Vec3::new(v.x, v.y, -v.z)
+}
+
+fn render(handedness: Handedness) -> Vec<u32> {
+    let right = handedness == Handedness::Right;
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine.set_handedness(handedness);
+    common::load_obj(&mut engine, "scene", &scene_obj(right));
+
+    let (eye, target, light) = (
+        Vec3::new(1.5, 2.0, -5.0),
+        Vec3::new(0.6, 0.0, 1.0),
+        Vec3::new(0.3, -0.5, 1.0),
+    );
+    let (eye, target, light) = if right {
+        (mirror(eye), mirror(target), mirror(light))
+    } else {
+        (eye, target, light)
+    };
+    engine.set_camera(FpsCamera::looking_at(eye, target));
+    engine.set_light_direction(light);
+    engine.update();
+    engine.render();
+
+    engine
+        .frame_buffer()
+        .chunks_exact(4)
+        .map(|px| u32::from_ne_bytes([px[0], px[1], px[2], px[3]]))
+        .collect()
+}
+
+#[test]
+fn mirrored_scene_renders_the_same_frame() {
+    let left = render(Handedness::Left);
+    let right = render(Handedness::Right);
+
+    let background = left[0];
+    let covered = left.iter().filter(|&&px| px != background).count();
+    assert!(covered > 200, "scene barely visible: {covered} pixels");
+
+    // Allow for float rounding along a few edges
+    let differing = left.iter().zip(&right).filter(|(a, b)| a != b).count();
+    assert!(
+        differing <= covered / 50,
+        "{differing} of {covered} pixels differ"
+    );
+}
+
+#[test]
+fn set_camera_adopts_engine_handedness() {
+    let mut engine = Engine::new(W, H);
+    engine.set_handedness(Handedness::Right);
+    engine.set_camera(FpsCamera::looking_at(Vec3::ZERO, Vec3::new(0.0, 0.0, -1.0)));
+
+    assert_eq!(engine.handedness(), Handedness::Right);
+    assert!(engine.camera().forward().z < -0.99);
+}
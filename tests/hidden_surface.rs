@@ -0,0 +1,133 @@
+//! Painter's algorithm tests — `HiddenSurface::PainterSort` draws whole
+//! triangles back to front without a depth buffer: the same image as depth
+//! testing wherever triangles can be ordered, and the classic artifacts
+//! where they interpenetrate.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// OBJ text for camera-facing squares given as (half size, z), in
+/// submission order.
+fn squares_obj(squares: &[(f32, f32)]) -> String {
+    let mut obj = String::new();
+    for (layer, &(half, z)) in squares.iter().enumerate() {
+        for (x, y) in [(-half, -half), (-half, half), (half, half), (half, -half)] {
+            obj += &format!("v {x} {y} {z}\n");
+        }
+        let base = layer * 4;
+        obj += &format!("f {} {} {}\n", base + 1, base + 2, base + 3);
+        obj += &format!("f {} {} {}\n", base + 1, base + 3, base + 4);
+    }
+    obj
+}
+
+/// A unit cube: the camera-facing square at z = -1 turned onto each side,
+/// so every face winds the same way seen from outside.
+fn cube_obj() -> String {
+    let mut obj = String::new();
+    for side in 0..6 {
+        for (x, y) in [(-1.0, -1.0), (-1.0, 1.0), (1.0, 1.0), (1.0, -1.0)] {
+            let (x, y, z) = match side {
+                0 => (x, y, -1.0),
+                1 => (-x, y, 1.0),
+                2 => (1.0, y, x),
+                3 => (-1.0, y, -x),
+                4 => (x, 1.0, y),
+                _ => (x, -1.0, -y),
+            };
+            obj += &format!("v {x} {y} {z}\n");
+        }
+        let base = side * 4;
+        obj += &format!("f {} {} {}\n", base + 1, base + 2, base + 3);
+        obj += &format!("f {} {} {}\n", base + 1, base + 3, base + 4);
+    }
+    obj
+}
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("mesh", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine
+}
+
+/// The frame under both modes, depth buffer first.
+fn both_modes(engine: &mut Engine) -> (Vec<u32>, Vec<u32>) {
+    engine.set_hidden_surface(HiddenSurface::DepthBuffer);
+    let depth_tested = engine.render_offscreen().to_vec();
+    engine.set_hidden_surface(HiddenSurface::PainterSort);
+    let sorted = engine.render_offscreen().to_vec();
+    (depth_tested, sorted)
+}
+
+#[test]
+fn sortable_scenes_match_the_depth_buffer() {
+    // A turned cube, with and without back faces
+    let mut engine = engine_with(&cube_obj());
+    engine
+        .model_mut("mesh")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.5, 0.7, 0.0));
+    let background = engine.palette().background;
+    for culling in [true, false] {
+        engine.backface_culling = culling;
+        let (depth_tested, sorted) = both_modes(&mut engine);
+        assert!(depth_tested.iter().filter(|&&p| p != background).count() > 200);
+        assert!(sorted == depth_tested, "culling {culling}: frames differ");
+    }
+
+    // Squares submitted nearest first still come out nearest on top
+    let mut engine = engine_with(&squares_obj(&[(0.5, 0.0), (1.0, 1.0)]));
+    let (depth_tested, sorted) = both_modes(&mut engine);
+    assert!(sorted == depth_tested, "stacked squares differ");
+}
+
+#[test]
+fn interpenetrating_triangles_sort_wrongly() {
+    // A square at z = 0 crossed by a strip sloping from in front on the
+    // left to behind on the right: depth testing cuts each along the
+    // intersection, while painting shows whole triangles
+    let mut obj = squares_obj(&[(1.0, 0.0)]);
+    obj += "v -1 -0.5 -0.8\nv -1 0.5 -0.8\nv 1 0.5 1\nv 1 -0.5 1\n";
+    obj += "f 5 6 7\nf 5 7 8\n";
+    let mut engine = engine_with(&obj);
+    let (depth_tested, sorted) = both_modes(&mut engine);
+    assert!(sorted != depth_tested);
+
+    // Away from the strip both agree
+    let top = (H / 2 - 12 * H / 64) * W + W / 2;
+    assert_eq!(sorted[top as usize], depth_tested[top as usize]);
+}
+
+#[test]
+fn painter_sort_frees_the_depth_buffer() {
+    let mut engine = engine_with(&squares_obj(&[(1.0, 0.0)]));
+    engine.set_hidden_surface(HiddenSurface::PainterSort);
+    let mut target = RenderTarget::new(32, 32);
+    engine.render_to(&mut target);
+    assert!(!target.has_depth());
+    assert!(target.depth().is_empty());
+    assert_ne!(target.pixels()[16 * 32 + 16], engine.palette().background);
+
+    engine.set_hidden_surface(HiddenSurface::DepthBuffer);
+    engine.render_to(&mut target);
+    assert_eq!(target.depth().len(), 32 * 32);
+}
+
+#[test]
+fn wireframe_draws_over_every_mesh() {
+    // A small square hidden behind a large one: its outline only shows
+    // without depth testing
+    let mut engine = engine_with(&squares_obj(&[(0.5, 1.0), (1.0, 0.0)]));
+    engine.set_render_mode(RenderMode::FilledWireframe);
+    let wireframe = engine.palette().wireframe;
+    let (depth_tested, sorted) = both_modes(&mut engine);
+
+    let edges = |frame: &[u32]| frame.iter().filter(|&&p| p == wireframe).count();
+    assert!(edges(&sorted) > edges(&depth_tested));
+}
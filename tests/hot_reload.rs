@@ -0,0 +1,175 @@
+//! Hot reload tests — a model or texture file rewritten on disk is loaded
+//! again in place, keeping the transform, and a broken file keeps the old
+//! data and reports the failure.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use russsty::hot_reload::ReloadEvent;
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+// Quads facing the default camera: one small, one filling most of the view
+const SMALL_QUAD_OBJ: &str = "\
+v -0.2 -0.2 0.0
+v -0.2 0.2 0.0
+v 0.2 0.2 0.0
+v 0.2 -0.2 0.0
+vt 0.0 0.0
+vt 0.0 1.0
+vt 1.0 1.0
+vt 1.0 0.0
+f 1/1 2/2 3/3
+f 1/1 3/3 4/4
+";
+const LARGE_QUAD_OBJ: &str = "\
+v -1.5 -1.5 0.0
+v -1.5 1.5 0.0
+v 1.5 1.5 0.0
+v 1.5 -1.5 0.0
+vt 0.0 0.0
+vt 0.0 1.0
+vt 1.0 1.0
+vt 1.0 0.0
+f 1/1 2/2 3/3
+f 1/1 3/3 4/4
+";
+
+/// Overwrite `path` and push its modification time forward, so the change
+/// shows whatever the filesystem's timestamp resolution.
+fn rewrite(path: &Path, contents: &[u8]) {
+    std::fs::write(path, contents).expect("rewrite file");
+    touch(path);
+}
+
+fn touch(path: &Path) {
+    let later = SystemTime::now() + Duration::from_secs(5);
+    std::fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(later))
+        .expect("touch file");
+}
+
+fn write_png(path: &Path, argb: u32) {
+    let [a, r, g, b] = argb.to_be_bytes();
+    image::RgbaImage::from_pixel(1, 1, image::Rgba([r, g, b, a]))
+        .save(path)
+        .expect("write png");
+}
+
+fn engine_with_quad(dir: &Path) -> Engine {
+    let path = dir.join("quad.obj");
+    std::fs::write(&path, SMALL_QUAD_OBJ).expect("write obj");
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine
+        .add_model("quad", path.to_str().unwrap())
+        .expect("load quad");
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.enable_hot_reload(true);
+    engine
+}
+
+/// Whether pixel (x, y) shows anything but the background
+fn covered(engine: &mut Engine, x: usize, y: usize) -> bool {
+    let background = engine.palette().background;
+    engine.render_offscreen()[y * W as usize + x] != background
+}
+
+#[test]
+fn changed_model_reloads_in_place() {
+    let dir = common::temp_dir();
+    let mut engine = engine_with_quad(&dir);
+    engine
+        .model_mut("quad")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(0.1, 0.0, 0.0));
+    assert!(!covered(&mut engine, 12, 32));
+
+    // Nothing changed yet
+    engine.reload_changed_assets();
+    assert!(engine.take_reload_events().is_empty());
+
+    rewrite(&dir.join("quad.obj"), LARGE_QUAD_OBJ.as_bytes());
+    engine.reload_changed_assets();
+    assert_eq!(
+        engine.take_reload_events(),
+        [ReloadEvent::Model {
+            name: "quad".to_string(),
+            path: dir.join("quad.obj"),
+        }]
+    );
+    assert!(covered(&mut engine, 12, 32));
+    let quad = engine.model("quad").unwrap();
+    assert_eq!(quad.transform().position(), Vec3::new(0.1, 0.0, 0.0));
+    assert!(engine.take_reload_events().is_empty());
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn broken_model_keeps_the_previous_meshes() {
+    let dir = common::temp_dir();
+    let mut engine = engine_with_quad(&dir);
+    rewrite(&dir.join("quad.obj"), b"f 1 2 3\n");
+    engine.reload_changed_assets();
+
+    let events = engine.take_reload_events();
+    assert!(
+        matches!(&events[..], [ReloadEvent::Failed { path, .. }] if *path == dir.join("quad.obj")),
+        "{events:?}"
+    );
+    assert!(covered(&mut engine, 32, 32));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn changed_texture_reloads() {
+    let dir = common::temp_dir();
+    let mut engine = engine_with_quad(&dir);
+    let png = dir.join("quad.png");
+    write_png(&png, 0xFFFF0000);
+    engine
+        .model_mut("quad")
+        .unwrap()
+        .set_texture(Texture::from_file(&png).unwrap());
+    engine.set_texture_mode(TextureMode::Replace);
+    let center = (H as usize / 2) * W as usize + W as usize / 2;
+    assert_eq!(engine.render_offscreen()[center], 0xFFFF0000);
+
+    // The texture's file is first seen on this check
+    engine.reload_changed_assets();
+    write_png(&png, 0xFF0000FF);
+    touch(&png);
+    engine.reload_changed_assets();
+    assert_eq!(
+        engine.take_reload_events(),
+        [ReloadEvent::Texture {
+            model: Some("quad".to_string()),
+            path: png.clone(),
+        }]
+    );
+    assert_eq!(engine.render_offscreen()[center], 0xFF0000FF);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn nothing_reloads_while_disabled() {
+    let dir = common::temp_dir();
+    let mut engine = engine_with_quad(&dir);
+    engine.enable_hot_reload(false);
+    assert!(!engine.hot_reload());
+
+    rewrite(&dir.join("quad.obj"), LARGE_QUAD_OBJ.as_bytes());
+    engine.reload_changed_assets();
+    assert!(engine.take_reload_events().is_empty());
+    assert!(!covered(&mut engine, 12, 32));
+    std::fs::remove_dir_all(&dir).ok();
+}
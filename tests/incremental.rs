@@ -0,0 +1,126 @@
+//! Incremental rendering tests — a frame drawn a slice at a time under a
+//! zero budget ends up identical to one drawn at once, and moving the
+//! camera starts it over.
+
+use russsty::prelude::*;
+use std::fmt::Write as _;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// A `cells`×`cells` grid of quads facing the camera, two triangles each,
+/// with a gentle bump so depth varies.
+fn grid_obj(cells: usize) -> String {
+    let mut obj = String::new();
+    for j in 0..=cells {
+        for i in 0..=cells {
+            let (x, y) = (
+                i as f32 / cells as f32 * 2.0 - 1.0,
+                j as f32 / cells as f32 * 2.0 - 1.0,
+            );
+            let z = -0.3 * (1.0 - x * x) * (1.0 - y * y);
+            writeln!(obj, "v {x} {y} {z}").unwrap();
+        }
+    }
+    let index = |i: usize, j: usize| j * (cells + 1) + i + 1;
+    for j in 0..cells {
+        for i in 0..cells {
+            let [a, b, c, d] = [
+                index(i, j),
+                index(i, j + 1),
+                index(i + 1, j + 1),
+                index(i + 1, j),
+            ];
+            writeln!(obj, "f {a} {b} {c}").unwrap();
+            writeln!(obj, "f {a} {c} {d}").unwrap();
+        }
+    }
+    obj
+}
+
+fn engine_with_grid() -> Engine {
+    let mut engine = common::engine_with_obj("grid", &grid_obj(40), W, H);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine
+}
+
+/// Call `render_incremental` with no time to spare until the frame is
+/// done, checking progress only ever grows. Returns the number of calls.
+fn paint_in(engine: &mut Engine) -> usize {
+    let mut calls = 0;
+    let mut last = 0.0;
+    loop {
+        let progress = engine.render_incremental(0.0);
+        calls += 1;
+        assert!(progress >= last, "progress fell from {last} to {progress}");
+        last = progress;
+        if progress >= 1.0 {
+            return calls;
+        }
+        assert!(calls < 10_000, "never finished");
+    }
+}
+
+#[test]
+fn sliced_frame_matches_a_full_render() {
+    let mut engine = engine_with_grid();
+    let expected = engine.render_offscreen().to_vec();
+
+    engine.restart_incremental();
+    assert!(engine.incremental_progress().is_none());
+    let calls = paint_in(&mut engine);
+    assert!(calls > 10, "drew the frame in only {calls} slices");
+    assert_eq!(engine.frame_pixels(), &expected[..]);
+
+    // A finished frame is presented again as is
+    assert_eq!(engine.render_incremental(0.0), 1.0);
+    assert_eq!(engine.frame_pixels(), &expected[..]);
+}
+
+#[test]
+fn partial_frames_show_some_of_the_mesh() {
+    let mut engine = engine_with_grid();
+    engine.set_render_mode(RenderMode::Filled);
+    let background = engine.palette().background;
+    // The first rows of the grid are thinner than a pixel
+    while engine.render_incremental(0.0) < 0.25 {}
+    assert!(engine.incremental_progress().unwrap() < 1.0);
+
+    let covered = engine
+        .frame_pixels()
+        .iter()
+        .filter(|&&c| c != background)
+        .count();
+    assert!(covered > 0);
+    assert!(
+        covered
+            < engine
+                .render_offscreen()
+                .iter()
+                .filter(|&&c| c != background)
+                .count()
+    );
+}
+
+#[test]
+fn camera_movement_restarts_the_frame() {
+    let mut engine = engine_with_grid();
+    paint_in(&mut engine);
+
+    engine.set_camera_position(Vec3::new(0.3, 0.0, -5.0));
+    assert!(engine.render_incremental(0.0) < 1.0);
+    paint_in(&mut engine);
+    let moved = engine.frame_pixels().to_vec();
+    assert_eq!(engine.render_offscreen(), &moved[..]);
+}
+
+#[test]
+fn full_render_drops_the_incremental_frame() {
+    let mut engine = engine_with_grid();
+    engine.render_incremental(0.0);
+    assert!(engine.incremental_progress().is_some());
+    engine.render();
+    assert!(engine.incremental_progress().is_none());
+}
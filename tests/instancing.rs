@@ -0,0 +1,115 @@
+//! Instancing tests — one mesh drawn at many transforms, culled per
+//! instance and counted as a single mesh.
+
+use russsty::prelude::*;
+use russsty::MeshHandle;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+// A small quad facing the default camera
+const TILE_OBJ: &str = "\
+o tile
+v -0.4 -0.4 0.0
+v -0.4 0.4 0.0
+v 0.4 0.4 0.0
+v 0.4 -0.4 0.0
+f 1 2 3
+f 1 3 4
+";
+
+fn engine_with_tile() -> (Engine, MeshHandle) {
+    let mut engine = common::engine_with_obj("tiles", TILE_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    let handle = engine.mesh_handle("tiles", "tile").expect("tile mesh");
+    (engine, handle)
+}
+
+fn at(x: f32, y: f32) -> Transform {
+    let mut transform = Transform::new();
+    transform.set_position(Vec3::new(x, y, 0.0));
+    transform
+}
+
+/// Whether pixel (x, y) shows anything but the background
+fn covered(engine: &mut Engine, x: usize, y: usize) -> bool {
+    let background = engine.palette().background;
+    engine.render_offscreen()[y * W as usize + x] != background
+}
+
+#[test]
+fn instances_replace_the_single_draw() {
+    let (mut engine, handle) = engine_with_tile();
+    assert!(covered(&mut engine, 32, 32));
+
+    assert!(engine.add_instances(handle, &[at(-1.5, 0.0), at(1.5, 0.0)]));
+    assert!(!covered(&mut engine, 32, 32));
+    assert!(covered(&mut engine, 14, 32));
+    assert!(covered(&mut engine, 50, 32));
+}
+
+#[test]
+fn stats_count_one_mesh_and_every_instance() {
+    let (mut engine, handle) = engine_with_tile();
+    let transforms: Vec<_> = (0..10).map(|i| at(i as f32 * 0.1, 0.0)).collect();
+    engine.add_instances(handle, &transforms);
+    // Far off to the side of the view
+    engine.add_instances(handle, &[at(50.0, 0.0), at(-50.0, 0.0)]);
+    engine.update();
+
+    let stats = engine.stats();
+    assert_eq!(stats.meshes_total, 1);
+    assert_eq!(stats.instances_total, 12);
+    assert_eq!(stats.instances_culled, 2);
+    assert_eq!(stats.faces_total, 24);
+    assert_eq!(stats.faces_frustum_culled, 4);
+    assert_eq!(stats.triangles_drawn, 20);
+}
+
+#[test]
+fn instances_follow_their_transforms_and_the_model() {
+    let (mut engine, handle) = engine_with_tile();
+    engine.add_instances(handle, &[at(-1.5, 0.0)]);
+    assert!(covered(&mut engine, 14, 32));
+
+    engine.instances_mut(handle).unwrap()[0].set_position(Vec3::new(1.5, 0.0, 0.0));
+    assert!(!covered(&mut engine, 14, 32));
+    assert!(covered(&mut engine, 50, 32));
+
+    // The model transform carries every instance with it
+    engine
+        .model_mut("tiles")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(-3.0, 0.0, 0.0));
+    assert!(covered(&mut engine, 14, 32));
+    assert!(!covered(&mut engine, 50, 32));
+}
+
+#[test]
+fn raycast_hits_instances() {
+    let (mut engine, handle) = engine_with_tile();
+    engine.add_instances(handle, &[at(-1.5, 0.0), at(1.5, 0.0)]);
+    let down_z = Vec3::new(0.0, 0.0, 1.0);
+
+    assert!(engine.raycast(Vec3::new(0.0, 0.0, -5.0), down_z).is_none());
+    let hit = engine
+        .raycast(Vec3::new(1.5, 0.0, -5.0), down_z)
+        .expect("hits the right instance");
+    assert!((hit.world_point.x - 1.5).abs() < 1e-4);
+}
+
+#[test]
+fn unknown_handles_are_rejected() {
+    let (mut engine, handle) = engine_with_tile();
+    assert!(engine.mesh_handle("tiles", "missing").is_none());
+    let stale = MeshHandle {
+        model_index: handle.model_index + 1,
+        ..handle
+    };
+    assert!(!engine.add_instances(stale, &[at(0.0, 0.0)]));
+    assert!(engine.instances_mut(stale).is_none());
+}
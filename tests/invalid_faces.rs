@@ -0,0 +1,74 @@
+//! Face validation tests — an OBJ face naming a vertex the file doesn't
+//! define fails to load with the index of the offending face, instead of
+//! loading and panicking later in the render loop.
+
+use russsty::prelude::*;
+use russsty::{Error, LoadError};
+
+mod common;
+
+fn load(obj: &str) -> Result<usize, Error> {
+    let mut engine = Engine::new(32, 32);
+    common::try_load_obj(&mut engine, "scene", obj)
+}
+
+fn assert_invalid_face(obj: &str, expected: usize) {
+    match load(obj) {
+        Err(Error::MeshLoad(LoadError::InvalidFaces { face })) => assert_eq!(face, expected),
+        other => panic!("expected InvalidFaces {{ face: {expected} }}, got {other:?}"),
+    }
+}
+
+#[test]
+fn out_of_range_vertex_names_the_face() {
+    let obj = "\
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 0.0 1.0 0.0
+v 1.0 1.0 0.0
+f 1 2 3
+f 2 4 9
+";
+    assert_invalid_face(obj, 1);
+
+    let message = load(obj).unwrap_err().to_string();
+    assert!(message.contains("face 1"), "{message}");
+}
+
+#[test]
+fn out_of_range_relative_index_is_rejected() {
+    // -4 reaches back past the three vertices defined so far
+    let obj = "\
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 0.0 1.0 0.0
+f -4 -2 -1
+v 1.0 1.0 0.0
+";
+    assert_invalid_face(obj, 0);
+}
+
+#[test]
+fn out_of_range_texcoord_is_rejected() {
+    let obj = "\
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 0.0 1.0 0.0
+vt 0.0 0.0
+f 1/1 2/1 3/2
+";
+    assert_invalid_face(obj, 0);
+}
+
+#[test]
+fn valid_faces_still_load() {
+    let obj = "\
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 0.0 1.0 0.0
+v 1.0 1.0 0.0
+f 1 2 3
+f -3 -1 -2
+";
+    assert!(load(obj).is_ok());
+}
@@ -0,0 +1,44 @@
+//! Lighting regression tests — spheres under a grid of ambient and
+//! diffuse settings must light up as the Lambert term says.
+
+use russsty::diagnostics::{lighting_test_scene, LightingTestScene, LIGHTING_TOLERANCE};
+use russsty::prelude::*;
+
+#[test]
+fn gouraud_spheres_match_lambert() {
+    let report = lighting_test_scene().render();
+    report.assert_within(LIGHTING_TOLERANCE);
+    // Every setting measured, and not all at one level
+    assert_eq!(report.samples().len(), 16);
+    let (lowest, highest) = report
+        .samples()
+        .iter()
+        .fold((1.0f32, 0.0f32), |(lo, hi), s| {
+            (lo.min(s.measured), hi.max(s.measured))
+        });
+    assert!(highest - lowest > 0.5, "{lowest}..{highest}");
+}
+
+#[test]
+fn light_from_behind_leaves_only_ambient() {
+    let scene = LightingTestScene {
+        light_direction: Vec3::new(0.0, 0.0, -1.0),
+        ..lighting_test_scene()
+    };
+    let report = scene.render();
+    report.assert_within(LIGHTING_TOLERANCE);
+    for sample in report.samples() {
+        assert!((sample.expected - sample.ambient).abs() < 1e-6);
+    }
+}
+
+#[test]
+#[should_panic(expected = "lit off by more than")]
+fn unlit_spheres_fail_the_check() {
+    // No lighting at all draws the fill color at full brightness
+    let scene = LightingTestScene {
+        shading: ShadingMode::None,
+        ..lighting_test_scene()
+    };
+    scene.render().assert_within(LIGHTING_TOLERANCE);
+}
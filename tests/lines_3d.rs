@@ -0,0 +1,164 @@
+//! 3D line tests — `Engine::draw_line_3d` and friends queue world-space
+//! segments for one frame, drawn depth-tested over the meshes and cut off
+//! at the near plane.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const LINE: u32 = 0xFFFF00FF;
+
+fn engine_with(obj: Option<&str>) -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine.set_render_mode(RenderMode::Filled);
+    if let Some(obj) = obj {
+        common::load_obj(&mut engine, "quad", obj);
+    }
+    engine
+}
+
+/// Columns of row `y` in the line color.
+fn line_columns(frame: &[u32], y: u32) -> Vec<u32> {
+    (0..W)
+        .filter(|&x| frame[(y * W + x) as usize] == LINE)
+        .collect()
+}
+
+fn line_pixels(frame: &[u32]) -> usize {
+    frame.iter().filter(|&&pixel| pixel == LINE).count()
+}
+
+#[test]
+fn lines_show_for_one_update() {
+    let mut engine = engine_with(None);
+    engine.draw_line_3d(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), LINE);
+    let frame = engine.render_offscreen().to_vec();
+    let columns = line_columns(&frame, H / 2);
+    // ±1 at distance 5 spans columns 16 to 47
+    assert_eq!(columns.first(), Some(&16), "{columns:?}");
+    assert_eq!(columns.last(), Some(&47), "{columns:?}");
+
+    // Drawn and gone with the next update
+    assert_eq!(line_pixels(engine.render_offscreen()), 0);
+
+    engine.draw_lines_3d(&[
+        (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), LINE),
+        (Vec3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0), LINE),
+    ]);
+    let frame = engine.render_offscreen().to_vec();
+    assert!(line_pixels(&frame) > columns.len() * 3 / 2);
+}
+
+#[test]
+fn meshes_in_front_hide_lines() {
+    let mut engine = engine_with(Some(common::QUAD_OBJ));
+    // Across the view behind the square, which covers columns 16 to 47
+    let (a, b) = (Vec3::new(-3.0, 0.0, 1.0), Vec3::new(3.0, 0.0, 1.0));
+    engine.draw_line_3d(a, b, LINE);
+    let frame = engine.render_offscreen().to_vec();
+    let columns = line_columns(&frame, H / 2);
+    assert!(!columns.is_empty());
+    assert!(
+        columns.iter().all(|&x| !(17..=46).contains(&x)),
+        "{columns:?}"
+    );
+
+    // In front of it, the whole line shows
+    engine.draw_line_3d(
+        a - Vec3::new(0.0, 0.0, 2.0),
+        b - Vec3::new(0.0, 0.0, 2.0),
+        LINE,
+    );
+    let frame = engine.render_offscreen().to_vec();
+    assert!((17..=46).all(|x| line_columns(&frame, H / 2).contains(&x)));
+}
+
+#[test]
+fn lines_through_the_camera_are_cut_at_the_near_plane() {
+    let mut engine = engine_with(None);
+    // From behind the camera at z = -5 to straight ahead, below the eye
+    engine.draw_line_3d(
+        Vec3::new(0.0, -1.0, -20.0),
+        Vec3::new(0.0, -1.0, 20.0),
+        LINE,
+    );
+    let frame = engine.render_offscreen().to_vec();
+    assert!(line_pixels(&frame) > 0);
+    // Every drawn pixel is in the middle column, under the horizon
+    for (i, _) in frame.iter().enumerate().filter(|(_, &p)| p == LINE) {
+        let (x, y) = (i as u32 % W, i as u32 / W);
+        assert!(x.abs_diff(W / 2) <= 1 && y > H / 2, "({x}, {y})");
+    }
+
+    // Wholly behind the camera, nothing
+    engine.draw_line_3d(Vec3::new(-1.0, 0.0, -8.0), Vec3::new(1.0, 0.0, -8.0), LINE);
+    assert_eq!(line_pixels(engine.render_offscreen()), 0);
+}
+
+#[test]
+fn arrows_have_a_head_at_the_tip() {
+    let mut engine = engine_with(None);
+    let (from, to) = (Vec3::new(-1.5, 0.0, 0.0), Vec3::new(1.5, 0.0, 0.0));
+    engine.draw_line_3d(from, to, LINE);
+    let shaft = line_pixels(engine.render_offscreen());
+    engine.draw_arrow_3d(from, to, LINE);
+    let frame = engine.render_offscreen().to_vec();
+    assert!(line_pixels(&frame) > shaft);
+
+    // The head spreads above and below the shaft near the tip only
+    let off_shaft: Vec<(u32, u32)> = (0..W * H)
+        .filter(|&i| frame[i as usize] == LINE && (i / W).abs_diff(H / 2) > 1)
+        .map(|i| (i % W, i / W))
+        .collect();
+    assert!(!off_shaft.is_empty());
+    assert!(off_shaft.iter().all(|&(x, _)| x > W / 2), "{off_shaft:?}");
+
+    // A zero-length arrow draws nothing to point with
+    engine.draw_arrow_3d(to, to, LINE);
+    assert!(line_pixels(engine.render_offscreen()) <= 1);
+}
+
+fn at(x: f32, y: f32, z: f32) -> Transform {
+    let mut transform = Transform::new();
+    transform.set_position(Vec3::new(x, y, z));
+    transform
+}
+
+#[test]
+fn wire_meshes_follow_their_transform() {
+    let mut engine = engine_with(None);
+    let capsule = WireMesh::capsule(0.3, 0.4, 16);
+    engine.draw_wire_mesh(&capsule, &at(1.0, 0.0, 0.0), LINE);
+    let frame = engine.render_offscreen().to_vec();
+    let drawn: Vec<u32> = (0..W * H)
+        .filter(|&i| frame[i as usize] == LINE)
+        .map(|i| i % W)
+        .collect();
+    assert!(!drawn.is_empty());
+    assert!(drawn.iter().all(|&x| x > W / 2), "{drawn:?}");
+
+    // One queue per update, like the other lines
+    assert_eq!(line_pixels(engine.render_offscreen()), 0);
+}
+
+#[test]
+fn meshes_in_front_hide_wire_meshes() {
+    let mut engine = engine_with(Some(common::QUAD_OBJ));
+    // Small enough to stay within the square's outline
+    let sphere = WireMesh::sphere(0.4, 16);
+    engine.draw_wire_mesh(&sphere, &at(0.0, 0.0, 1.0), LINE);
+    assert_eq!(line_pixels(engine.render_offscreen()), 0);
+
+    engine.draw_wire_mesh(&sphere, &at(0.0, 0.0, -1.0), LINE);
+    assert!(line_pixels(engine.render_offscreen()) > 0);
+
+    // Straddling the square, only the front half shows
+    let cube = WireMesh::aabb(Vec3::new(-0.4, -0.4, -0.5), Vec3::new(0.4, 0.4, 0.5));
+    engine.draw_wire_mesh(&cube, &Transform::new(), LINE);
+    let front = line_pixels(engine.render_offscreen());
+    engine.draw_wire_mesh(&cube, &at(0.0, 0.0, -1.0), LINE);
+    assert!(front > 0 && front < line_pixels(engine.render_offscreen()));
+}
@@ -0,0 +1,89 @@
+//! Level-of-detail tests — a simplified mesh takes over beyond its
+//! distance, leaving close-up frames exactly as they were.
+
+use russsty::lod::LodGroup;
+use russsty::prelude::*;
+
+const W: u32 = 96;
+const H: u32 = 96;
+
+fn engine_with_sphere() -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    let mut model = Model::new("sphere");
+    model.add_mesh(Mesh::uv_sphere("sphere", 1.0, 128, 64));
+    engine.set_model(model);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine
+}
+
+/// Give the sphere one level with a tenth of its faces, from 10 units out.
+fn add_lod(engine: &mut Engine) {
+    let handle = engine.mesh_handle("sphere", "sphere").unwrap();
+    let mesh = &engine.model("sphere").unwrap().meshes()[0];
+    let group = LodGroup::generate(mesh, &[(10.0, 0.1)]);
+    assert!(engine.set_lod_group(handle, group));
+}
+
+#[test]
+fn simplified_mesh_meets_the_face_target() {
+    let engine = engine_with_sphere();
+    let mesh = &engine.model("sphere").unwrap().meshes()[0];
+    let simplified = mesh.simplify(0.1);
+    assert!(simplified.face_count() <= mesh.face_count() / 10);
+    assert!(simplified.face_count() >= mesh.face_count() / 40);
+    assert_eq!(mesh.simplify(1.0).face_count(), mesh.face_count());
+}
+
+#[test]
+fn close_frames_are_unchanged() {
+    let mut engine = engine_with_sphere();
+    let expected = engine.render_offscreen().to_vec();
+    add_lod(&mut engine);
+    assert_eq!(engine.render_offscreen(), &expected[..]);
+    assert_eq!(engine.stats().faces_lod_reduced, 0);
+}
+
+#[test]
+fn far_camera_draws_the_simplified_level() {
+    let mut engine = engine_with_sphere();
+    engine.set_camera_position(Vec3::new(0.0, 0.0, -20.0));
+    engine.update();
+    let full = engine.stats();
+
+    add_lod(&mut engine);
+    engine.update();
+    let reduced = engine.stats();
+    assert_eq!(reduced.faces_total, full.faces_total);
+    assert!(reduced.faces_lod_reduced >= full.faces_total * 9 / 10);
+    assert!(reduced.triangles_drawn * 5 < full.triangles_drawn);
+
+    // Still a sphere, just a coarser one
+    let background = engine.palette().background;
+    let center = (H as usize / 2) * W as usize + W as usize / 2;
+    assert_ne!(engine.render_offscreen()[center], background);
+}
+
+#[test]
+fn each_instance_picks_its_own_level() {
+    let mut engine = engine_with_sphere();
+    add_lod(&mut engine);
+    let handle = engine.mesh_handle("sphere", "sphere").unwrap();
+    let mut near = Transform::new();
+    near.set_position(Vec3::new(-1.5, 0.0, 0.0));
+    let mut far = Transform::new();
+    far.set_position(Vec3::new(1.5, 0.0, 30.0));
+    engine.add_instances(handle, &[near, far]);
+
+    engine.update();
+    let stats = engine.stats();
+    let faces = engine.model("sphere").unwrap().meshes()[0].face_count();
+    let lod_faces = engine.model("sphere").unwrap().meshes()[0]
+        .lod_group()
+        .unwrap()
+        .levels()[0]
+        .mesh
+        .face_count();
+    assert_eq!(stats.faces_lod_reduced, faces - lod_faces);
+}
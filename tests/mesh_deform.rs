@@ -0,0 +1,89 @@
+//! Mesh deformation tests — `Mesh::map_positions` moves vertices at
+//! runtime, and the mesh then draws and lights as if it had been loaded in
+//! its new shape.
+
+use russsty::prelude::*;
+use russsty::Mesh;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// A camera-facing square, split along its diagonal.
+const QUAD_OBJ: &str = "\
+v -1 -1 0
+v -1 1 0
+v 1 1 0
+v 1 -1 0
+vn 0 0 -1
+f 1//1 2//1 3//1
+f 1//1 3//1 4//1
+";
+
+/// The same square with its right edge pulled toward the camera.
+const TILTED_OBJ: &str = "\
+v -1 -1 0
+v -1 1 0
+v 1 1 -1
+v 1 -1 -1
+vn -0.4472136 0 -0.8944272
+f 1//1 2//1 3//1
+f 1//1 3//1 4//1
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("mesh", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_light_direction(Vec3::new(1.0, 0.0, 1.0));
+    engine
+}
+
+fn quad(engine: &mut Engine) -> &mut Mesh {
+    engine
+        .model_mut("mesh")
+        .unwrap()
+        .mesh_by_index_mut(0)
+        .unwrap()
+}
+
+#[test]
+fn deformed_mesh_renders_like_one_loaded_in_that_shape() {
+    let mut engine = engine_with(QUAD_OBJ);
+    let flat = engine.render_offscreen().to_vec();
+
+    quad(&mut engine).map_positions(|_, p| {
+        if p.x > 0.0 {
+            Vec3::new(p.x, p.y, -1.0)
+        } else {
+            p
+        }
+    });
+    let deformed = engine.render_offscreen().to_vec();
+    assert_ne!(deformed, flat);
+    assert_eq!(quad(&mut engine).bounding_box().0.z, -1.0);
+
+    let expected = engine_with(TILTED_OBJ).render_offscreen().to_vec();
+    assert_eq!(deformed, expected);
+}
+
+#[test]
+fn positions_and_faces_read_back() {
+    let mut engine = engine_with(QUAD_OBJ);
+    let mesh = quad(&mut engine);
+    assert_eq!(mesh.positions().len(), mesh.vertex_count());
+    assert!(mesh.positions().all(|p| p.z == 0.0));
+    let faces: Vec<[u32; 3]> = mesh.face_indices().collect();
+    assert_eq!(faces.len(), mesh.face_count());
+    assert!(faces
+        .iter()
+        .flatten()
+        .all(|&i| (i as usize) < mesh.vertex_count()));
+
+    // Moving every vertex back and forth leaves the mesh as it was
+    let before = mesh.clone();
+    mesh.map_positions(|_, p| p + Vec3::new(0.0, 0.0, 2.0));
+    mesh.map_positions(|_, p| p - Vec3::new(0.0, 0.0, 2.0));
+    assert_eq!(*mesh, before);
+}
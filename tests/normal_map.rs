@@ -0,0 +1,105 @@
+//! Normal mapping tests — under `TextureMode::NormalMapped` a flat quad is
+//! lit per pixel at the normal the map gives, so a tilted map makes the
+//! quad brighter when the light comes from the side it leans toward.
+
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 48;
+const H: u32 = 48;
+
+// Faces the default camera at z = -5, with u along +x and v along +y
+const QUAD_OBJ: &str = "\
+v -1.0 -1.0 0.0
+v -1.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+vt 0.0 0.0
+vt 0.0 1.0
+vt 1.0 1.0
+vt 1.0 0.0
+vn 0.0 0.0 -1.0
+f 1/1/1 2/2/1 3/3/1
+f 1/1/1 3/3/1 4/4/1
+";
+
+/// Tangent-space normal map with every texel the unit normal `n`.
+fn uniform_normal_map(n: Vec3) -> Texture {
+    let n = n.normalize();
+    let channel = |c: f32| ((c * 0.5 + 0.5) * 255.0).round() as u32;
+    let color = 0xFF000000 | (channel(n.x) << 16) | (channel(n.y) << 8) | channel(n.z);
+    Texture::from_fn(4, 4, |_, _| color).unwrap()
+}
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("quad", obj, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine.set_texture_mode(TextureMode::NormalMapped);
+    engine
+}
+
+/// Red channel at the center of the frame lit along `light_direction`.
+fn center_brightness(engine: &mut Engine, light_direction: Vec3) -> u32 {
+    engine.set_light_direction(light_direction);
+    let pixels = engine.render_offscreen();
+    (pixels[(H / 2 * W + W / 2) as usize] >> 16) & 0xFF
+}
+
+// Light coming from the -z side, leaning in from +x or from -x
+const FROM_RIGHT: Vec3 = Vec3::new(-1.0, 0.0, 1.0);
+const FROM_LEFT: Vec3 = Vec3::new(1.0, 0.0, 1.0);
+
+#[test]
+fn tilted_map_follows_the_light() {
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        let mut engine = engine_with(QUAD_OBJ);
+        engine.set_rasterizer(rasterizer);
+
+        engine.set_normal_map(uniform_normal_map(Vec3::new(0.0, 0.0, 1.0)));
+        let flat_right = center_brightness(&mut engine, FROM_RIGHT);
+        let flat_left = center_brightness(&mut engine, FROM_LEFT);
+        assert!(flat_right.abs_diff(flat_left) <= 1, "{rasterizer}");
+
+        // Leaning toward +u, which is +x on this quad
+        engine.set_normal_map(uniform_normal_map(Vec3::new(0.6, 0.0, 0.8)));
+        let right = center_brightness(&mut engine, FROM_RIGHT);
+        let left = center_brightness(&mut engine, FROM_LEFT);
+        assert!(
+            right > flat_right + 20 && left + 20 < flat_left,
+            "{rasterizer}: {left} < {flat_left} < {right}"
+        );
+    }
+}
+
+#[test]
+fn flat_map_matches_gouraud() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_texture_mode(TextureMode::None);
+    let gouraud = center_brightness(&mut engine, FROM_RIGHT);
+
+    engine.set_texture_mode(TextureMode::NormalMapped);
+    engine.set_normal_map(uniform_normal_map(Vec3::new(0.0, 0.0, 1.0)));
+    let mapped = center_brightness(&mut engine, FROM_RIGHT);
+    assert!(gouraud.abs_diff(mapped) <= 1, "{gouraud} vs {mapped}");
+}
+
+#[test]
+fn without_a_normal_map_the_fill_is_lit_as_usual() {
+    let mut engine = engine_with(QUAD_OBJ);
+    let mapped = engine.render_offscreen().to_vec();
+    engine.set_texture_mode(TextureMode::None);
+    assert!(engine.render_offscreen() == mapped.as_slice());
+}
+
+#[test]
+fn unlit_shading_ignores_the_map() {
+    let mut engine = engine_with(QUAD_OBJ);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_normal_map(uniform_normal_map(Vec3::new(0.6, 0.0, 0.8)));
+    let right = center_brightness(&mut engine, FROM_RIGHT);
+    let left = center_brightness(&mut engine, FROM_LEFT);
+    assert_eq!(right, left);
+}
@@ -0,0 +1,128 @@
+//! OBJ object tests — each `o` in a file loads as its own named mesh,
+//! addressable through the model and drawn under the model transform.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 96;
+const H: u32 = 96;
+
+// Three small quads side by side, facing the default camera
+const CART_OBJ: &str = "\
+o wheel
+v -1.6 -0.4 0.0
+v -1.6 0.4 0.0
+v -0.8 0.4 0.0
+v -0.8 -0.4 0.0
+f 1 2 3
+f 1 3 4
+o body
+v -0.4 -0.4 0.0
+v -0.4 0.4 0.0
+v 0.4 0.4 0.0
+v 0.4 -0.4 0.0
+f 5 6 7
+f 5 7 8
+o lamp
+v 0.8 -0.4 0.0
+v 0.8 0.4 0.0
+v 1.6 0.4 0.0
+v 1.6 -0.4 0.0
+f 9 10 11
+f 9 11 12
+";
+
+/// Write the OBJ as `cart.obj` and hand its path to `f`
+fn with_cart_file<T>(f: impl FnOnce(&str) -> T) -> T {
+    // A directory of its own, so the file stem, and with it the model
+    // name, stays "cart"
+    let dir = common::temp_dir();
+    let path = dir.join("cart.obj");
+    std::fs::write(&path, CART_OBJ).expect("write temp obj");
+    let result = f(path.to_str().unwrap());
+    std::fs::remove_dir_all(&dir).ok();
+    result
+}
+
+fn engine_with_cart() -> (Engine, usize) {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    let index = with_cart_file(|path| engine.load_model(path)).expect("load cart");
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_picking(true);
+    (engine, index)
+}
+
+/// Names of the meshes that show up anywhere on screen
+fn visible_meshes(engine: &mut Engine) -> Vec<String> {
+    engine.render_offscreen();
+    let mut names = Vec::new();
+    for y in 0..H as i32 {
+        for x in 0..W as i32 {
+            let Some(hit) = engine.pick(x, y) else {
+                continue;
+            };
+            let name = engine.models()[hit.model_index].meshes()[hit.mesh_index].name();
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    names
+}
+
+#[test]
+fn objects_load_as_named_meshes() {
+    let (engine, index) = engine_with_cart();
+    let model = engine.model("cart").expect("named after the file stem");
+    assert_eq!(engine.model_by_index(index).unwrap().name(), "cart");
+
+    let names: Vec<_> = model.meshes().iter().map(|m| m.name()).collect();
+    assert_eq!(names, ["wheel", "body", "lamp"]);
+    for name in names {
+        assert_eq!(model.mesh(name).unwrap().name(), name);
+    }
+}
+
+#[test]
+fn every_object_renders() {
+    let (mut engine, _) = engine_with_cart();
+    assert_eq!(visible_meshes(&mut engine), ["body", "lamp", "wheel"]);
+}
+
+#[test]
+fn meshes_move_under_the_model_transform() {
+    let (mut engine, _) = engine_with_cart();
+    let model = engine.model_mut("cart").unwrap();
+    // Out of view on its own, but pulled back by the model transform
+    model
+        .mesh_mut("wheel")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(0.0, 20.0, 0.0));
+    assert_eq!(visible_meshes(&mut engine), ["body", "lamp"]);
+
+    engine
+        .model_mut("cart")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(0.0, -20.0, 0.0));
+    assert_eq!(visible_meshes(&mut engine), ["wheel"]);
+}
+
+#[test]
+fn set_model_replaces_by_name() {
+    let (mut engine, index) = engine_with_cart();
+    let mut cart = with_cart_file(|path| Model::from_obj("cart", path)).expect("load cart");
+    cart.transform_mut().set_position(Vec3::new(0.0, 20.0, 0.0));
+
+    assert_eq!(engine.set_model(cart), index);
+    assert_eq!(engine.model_count(), 1);
+    assert!(visible_meshes(&mut engine).is_empty());
+
+    let extra = engine.set_model(Model::new("extra"));
+    assert_eq!(extra, 1);
+    assert_eq!(engine.model_count(), 2);
+}
@@ -0,0 +1,143 @@
+//! Odd and tiny frame size tests — a quad covering the whole view fills
+//! every pixel exactly once at any size, with either rasterizer, and
+//! presenting keeps every row and column in place.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use russsty::prelude::*;
+
+mod common;
+
+// Far wider than the view, so clipping cuts it to the screen edges
+const BIG_QUAD_OBJ: &str = "\
+v -50 -50 0
+v -50 50 0
+v 50 50 0
+v 50 -50 0
+f 1 2 3
+f 1 3 4
+";
+
+/// Counts how often each pixel is shaded, and shading outside the frame.
+/// Each pixel is painted with its own position; see [`position_color`].
+struct Coverage {
+    width: i32,
+    height: i32,
+    hits: Vec<AtomicU32>,
+    outside: AtomicU32,
+}
+
+impl Coverage {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width: width as i32,
+            height: height as i32,
+            hits: (0..width * height).map(|_| AtomicU32::new(0)).collect(),
+            outside: AtomicU32::new(0),
+        }
+    }
+}
+
+struct CountingShader(Arc<Coverage>);
+
+impl PixelShader for CountingShader {
+    fn shade(&self, fragment: &Fragment) -> u32 {
+        let coverage = &self.0;
+        let (x, y) = (fragment.x, fragment.y);
+        if x < 0 || y < 0 || x >= coverage.width || y >= coverage.height {
+            coverage.outside.fetch_add(1, Ordering::Relaxed);
+        } else {
+            coverage.hits[(y * coverage.width + x) as usize].fetch_add(1, Ordering::Relaxed);
+        }
+        position_color(x as u32, y as u32)
+    }
+}
+
+/// A color unique to each pixel of frames up to 1024 pixels on a side.
+fn position_color(x: u32, y: u32) -> u32 {
+    0xFF000000 | (x & 0x3FF) << 10 | (y & 0x3FF)
+}
+
+fn expected_frame(width: u32, height: u32) -> Vec<u32> {
+    (0..width * height)
+        .map(|i| position_color(i % width, i / width))
+        .collect()
+}
+
+fn engine_with_big_quad(width: u32, height: u32) -> Engine {
+    let mut engine = common::engine_with_obj("quad", BIG_QUAD_OBJ, width, height);
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+fn assert_covered_once(width: u32, height: u32) {
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        let mut engine = engine_with_big_quad(width, height);
+        engine.set_rasterizer(rasterizer);
+        let coverage = Arc::new(Coverage::new(width, height));
+        engine.set_custom_shader(Some(Box::new(CountingShader(coverage.clone()))));
+        let frame = engine.render_offscreen().to_vec();
+
+        assert_eq!(frame.len(), (width * height) as usize);
+        assert_eq!(
+            coverage.outside.load(Ordering::Relaxed),
+            0,
+            "{rasterizer:?}"
+        );
+        let wrong: Vec<(u32, u32, u32)> = (0..width * height)
+            .filter_map(|i| {
+                let hits = coverage.hits[i as usize].load(Ordering::Relaxed);
+                (hits != 1).then_some((i % width, i / width, hits))
+            })
+            .take(8)
+            .collect();
+        assert!(
+            wrong.is_empty(),
+            "{rasterizer:?} at {width}x{height}, (x, y, hits): {wrong:?}"
+        );
+        assert!(frame == expected_frame(width, height));
+    }
+}
+
+#[test]
+fn odd_sized_frames_are_covered_exactly_once() {
+    assert_covered_once(799, 601);
+}
+
+#[test]
+fn one_pixel_frames_are_covered_exactly_once() {
+    assert_covered_once(1, 1);
+}
+
+#[test]
+fn three_pixel_frames_are_covered_exactly_once() {
+    assert_covered_once(3, 3);
+}
+
+#[test]
+fn odd_sized_frames_present_row_by_row() {
+    let dir = std::env::temp_dir().join(format!("russsty_odd_sizes_{}", std::process::id()));
+    let mut presenter = PngPresenter::new(&dir).expect("create output dir");
+    for (width, height) in [(799, 601), (3, 3), (1, 1)] {
+        let mut engine = engine_with_big_quad(width, height);
+        let coverage = Arc::new(Coverage::new(width, height));
+        engine.set_custom_shader(Some(Box::new(CountingShader(coverage))));
+        engine.render_offscreen();
+        let path = presenter.frame_path(presenter.frames_written());
+        presenter
+            .present(engine.frame_buffer(), width, height)
+            .expect("write png");
+
+        // Every row lands where it belongs, up to the last column
+        let image = image::open(&path).expect("read png").to_rgba8();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(image.dimensions(), (width, height));
+        let read: Vec<u32> = image
+            .pixels()
+            .map(|p| u32::from_be_bytes([p[3], p[0], p[1], p[2]]))
+            .collect();
+        assert!(read == expected_frame(width, height), "{width}x{height}");
+    }
+    std::fs::remove_dir(&dir).ok();
+}
@@ -0,0 +1,14 @@
+//! Render palette tests — frames are drawn in the engine's palette colors.
+
+mod common;
+
+#[test]
+fn palette_background_is_used_for_clear() {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, 64, 64);
+    engine.update();
+    engine.palette_mut().background = 0xFF123456;
+    engine.render();
+
+    // Top-left pixel is background (the quad sits in the middle)
+    assert_eq!(common::pixel(&engine, (0, 0)), 0xFF123456);
+}
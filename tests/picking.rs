@@ -0,0 +1,73 @@
+//! Picking regression test — the ID buffer written during `render()` maps
+//! screen pixels back to the face that produced them.
+
+use russsty::Engine;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+fn engine_with_quad() -> Engine {
+    engine_with_quad_ssaa(1)
+}
+
+fn engine_with_quad_ssaa(factor: u32) -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, W, H);
+    engine.backface_culling = false;
+    engine.set_supersampling(factor);
+    engine.set_picking(true);
+    engine.update();
+    engine.render();
+    engine
+}
+
+#[test]
+fn pick_hits_face_under_cursor() {
+    let engine = engine_with_quad();
+
+    // Upper-left and lower-right of the quad's screen footprint land in
+    // different faces of the diagonal split.
+    let upper_left = engine
+        .pick(W as i32 / 2 - 3, H as i32 / 2 - 3)
+        .expect("hit");
+    let lower_right = engine
+        .pick(W as i32 / 2 + 3, H as i32 / 2 + 3)
+        .expect("hit");
+
+    assert_eq!(upper_left.model_index, 0);
+    assert_eq!(upper_left.mesh_index, 0);
+    assert_ne!(upper_left.face_index, lower_right.face_index);
+    // Camera sits 5 units in front of the quad
+    assert!((upper_left.depth - 5.0).abs() < 1e-3);
+}
+
+#[test]
+fn pick_background_returns_none() {
+    let engine = engine_with_quad();
+    assert_eq!(engine.pick(0, 0), None);
+    assert_eq!(engine.pick(-1, 10), None);
+}
+
+#[test]
+fn pick_disabled_returns_none() {
+    let mut engine = engine_with_quad();
+    engine.set_picking(false);
+    assert_eq!(engine.pick(W as i32 / 2, H as i32 / 2), None);
+}
+
+#[test]
+fn pick_uses_display_coordinates_when_supersampled() {
+    let plain = engine_with_quad();
+    let ssaa = engine_with_quad_ssaa(2);
+    assert_eq!(ssaa.supersampling(), 2);
+    // Presented frame stays at display size
+    assert_eq!(ssaa.frame_buffer().len(), (W * H * 4) as usize);
+
+    for (x, y) in [(W as i32 / 2 - 3, H as i32 / 2 - 3), (0, 0)] {
+        assert_eq!(
+            plain.pick(x, y).map(|hit| hit.face_index),
+            ssaa.pick(x, y).map(|hit| hit.face_index)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Post-processing tests — the effect sees the final, display-size frame,
+//! HUD included.
+
+use russsty::prelude::*;
+
+const W: u32 = 64;
+const H: u32 = 48;
+
+fn pixels(engine: &Engine) -> Vec<u32> {
+    engine
+        .frame_buffer()
+        .chunks_exact(4)
+        .map(|b| u32::from_ne_bytes(b.try_into().unwrap()))
+        .collect()
+}
+
+fn is_gray(color: u32) -> bool {
+    let r = (color >> 16) & 0xFF;
+    let g = (color >> 8) & 0xFF;
+    let b = color & 0xFF;
+    r == g && g == b
+}
+
+#[test]
+fn effect_sees_display_size_frame() {
+    for factor in [1, 2] {
+        let mut engine = Engine::new(W, H);
+        engine.set_supersampling(factor);
+        engine.set_post_effect(Some(Box::new(|pixels: &mut [u32], w: u32, h: u32| {
+            assert_eq!((w, h), (W, H));
+            assert_eq!(pixels.len(), (W * H) as usize);
+            pixels.fill(0xFFFF0000);
+        })));
+        engine.render();
+
+        assert!(pixels(&engine).iter().all(|&p| p == 0xFFFF0000));
+    }
+}
+
+#[test]
+fn effect_runs_after_hud() {
+    let mut engine = Engine::new(W, H);
+    engine.palette_mut().hud_text = 0xFF00FF00;
+    engine.draw_debug_hud(true);
+    engine.render();
+    assert!(pixels(&engine).iter().any(|&p| !is_gray(p)));
+
+    engine.set_post_effect(Some(Box::new(Grayscale)));
+    engine.render();
+    assert!(pixels(&engine).iter().all(|&p| is_gray(p)));
+}
+
+#[test]
+fn removing_the_effect_restores_the_frame() {
+    let mut engine = Engine::new(W, H);
+    engine.render();
+    let plain = pixels(&engine);
+
+    engine.set_post_effect(Some(Box::new(Vignette::default())));
+    engine.render();
+    assert_ne!(pixels(&engine), plain);
+    assert!(engine.post_effect().is_some());
+
+    engine.set_post_effect(None);
+    engine.render();
+    assert_eq!(pixels(&engine), plain);
+}
@@ -0,0 +1,101 @@
+//! Frame profiling tests — `Engine::set_profiling` times the geometry,
+//! fill, wireframe and present stages of every rendered frame, along with
+//! any scopes the app opens around its own work.
+
+use std::time::Duration;
+
+use russsty::prelude::*;
+use russsty::profile;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+fn engine_with_quad() -> Engine {
+    let mut engine = Engine::new(W, H);
+    common::load_obj(&mut engine, "quad", common::QUAD_OBJ);
+    engine
+}
+
+fn frame(engine: &mut Engine) {
+    engine.update();
+    engine.render();
+}
+
+#[test]
+fn profiling_is_off_by_default() {
+    let mut engine = engine_with_quad();
+    frame(&mut engine);
+    assert!(!engine.profiling());
+    assert!(engine.frame_profile().is_none());
+    assert!(!profile::is_enabled());
+}
+
+#[test]
+fn every_stage_is_timed_each_frame() {
+    let mut engine = engine_with_quad();
+    engine.set_render_mode(RenderMode::FilledWireframe);
+    engine.set_profiling(true);
+    for _ in 0..3 {
+        frame(&mut engine);
+    }
+
+    let frame_profile = engine.frame_profile().unwrap();
+    assert_eq!(frame_profile.frames(), 3);
+    for stage in [
+        profile::WORLD_TRANSFORM,
+        profile::CLIPPING,
+        profile::PROJECTION,
+        profile::FILL,
+        profile::WIREFRAME,
+        profile::PRESENT,
+    ] {
+        let timing = frame_profile
+            .stage(stage)
+            .unwrap_or_else(|| panic!("{stage}"));
+        assert!(
+            timing.min <= timing.avg && timing.avg <= timing.max,
+            "{timing:?}"
+        );
+        assert!(timing.max > Duration::ZERO, "{timing:?}");
+    }
+
+    let summary = frame_profile.summary();
+    assert!(summary.starts_with("geo "), "{summary}");
+    assert!(
+        summary.contains(" | raster ") && summary.contains(" | lines "),
+        "{summary}"
+    );
+
+    // Without wireframes the stage drops out of newer frames
+    engine.set_render_mode(RenderMode::Filled);
+    frame(&mut engine);
+    let frame_profile = engine.frame_profile().unwrap();
+    assert_eq!(
+        frame_profile.stage(profile::WIREFRAME).unwrap().last,
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn app_scopes_land_in_the_engine_frame() {
+    let mut engine = engine_with_quad();
+    engine.set_profiling(true);
+    {
+        let _physics = profile::scope("physics");
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    frame(&mut engine);
+    let physics = engine.frame_profile().unwrap().stage("physics").unwrap();
+    assert!(physics.last >= Duration::from_millis(2), "{physics:?}");
+
+    // Offscreen renders don't count as frames
+    let mut target = RenderTarget::new(32, 32);
+    engine.render_to(&mut target);
+    assert_eq!(engine.frame_profile().unwrap().frames(), 1);
+
+    engine.set_profiling(false);
+    assert!(engine.frame_profile().is_none());
+    assert!(!profile::is_enabled());
+}
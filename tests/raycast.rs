@@ -0,0 +1,90 @@
+//! Ray casting against world-space mesh triangles.
+
+use russsty::math::vec3::Vec3;
+use russsty::Engine;
+
+mod common;
+
+// Unit cube centered on the origin (faces span -0.5..0.5 on each axis).
+const CUBE_OBJ: &str = "\
+v -0.5 -0.5 -0.5
+v -0.5 0.5 -0.5
+v 0.5 0.5 -0.5
+v 0.5 -0.5 -0.5
+v -0.5 -0.5 0.5
+v -0.5 0.5 0.5
+v 0.5 0.5 0.5
+v 0.5 -0.5 0.5
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 5 6 2
+f 5 2 1
+f 4 3 7
+f 4 7 8
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+fn engine_with_cube() -> Engine {
+    let mut engine = Engine::new(64, 64);
+    common::load_obj(&mut engine, "cube", CUBE_OBJ);
+    engine
+}
+
+#[test]
+fn ray_hits_cube_front_face_at_expected_distance() {
+    let engine = engine_with_cube();
+    let origin = Vec3::new(0.1, 0.2, -5.0);
+
+    let hit = engine
+        .raycast(origin, Vec3::new(0.0, 0.0, 1.0))
+        .expect("hit");
+
+    // Front face sits at z = -0.5, so 4.5 units from the origin
+    assert!((hit.distance - 4.5).abs() < 1e-4);
+    assert!((hit.world_point.z + 0.5).abs() < 1e-4);
+    assert!((hit.world_point.x - 0.1).abs() < 1e-4);
+    // Front face is faces 0 and 1
+    assert!(hit.face_index < 2);
+    let sum: f32 = hit.barycentric.iter().sum();
+    assert!((sum - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn ray_that_misses_returns_none() {
+    let engine = engine_with_cube();
+    assert_eq!(
+        engine.raycast(Vec3::new(2.0, 0.0, -5.0), Vec3::new(0.0, 0.0, 1.0)),
+        None
+    );
+    // Pointing away from the cube
+    assert_eq!(
+        engine.raycast(Vec3::new(0.0, 0.0, -5.0), Vec3::new(0.0, 0.0, -1.0)),
+        None
+    );
+}
+
+#[test]
+fn screen_ray_through_center_hits_cube() {
+    let engine = engine_with_cube();
+    // Default camera sits at z = -5 looking down +Z
+    let ray = engine.screen_ray(32, 32);
+    assert!(ray.direction.z > 0.99);
+
+    let hit = engine.raycast(ray.origin, ray.direction).expect("hit");
+    assert!((hit.distance - 4.5).abs() < 0.01);
+}
+
+#[test]
+fn screen_ray_corner_misses_cube() {
+    let engine = engine_with_cube();
+    let ray = engine.screen_ray(0, 0);
+    // Top-left pixel points up and to the left
+    assert!(ray.direction.x < 0.0);
+    assert!(ray.direction.y > 0.0);
+    assert_eq!(engine.raycast(ray.origin, ray.direction), None);
+}
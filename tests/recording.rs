@@ -0,0 +1,47 @@
+//! Frame recording tests — `Engine::start_recording` saves each rendered
+//! frame as a numbered PNG until the frame limit, then stops capturing.
+
+use russsty::prelude::*;
+
+const W: u32 = 32;
+const H: u32 = 24;
+
+#[test]
+fn records_rendered_frames_up_to_the_limit() {
+    let dir = std::env::temp_dir().join(format!("russsty_recording_{}", std::process::id()));
+    let mut engine = Engine::new(W, H);
+    engine.start_recording(&dir, 3).unwrap();
+
+    for _ in 0..5 {
+        engine.update();
+        engine.render();
+    }
+    let recorder = engine.recorder().unwrap();
+    assert!(recorder.is_complete());
+    assert_eq!(recorder.stats().captured, 3);
+
+    let stats = engine.stop_recording().unwrap().unwrap();
+    assert_eq!((stats.written, stats.dropped), (3, 0));
+    assert!(engine.recorder().is_none());
+
+    for name in ["000.png", "001.png", "002.png"] {
+        let image = image::open(dir.join(name)).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (W, H));
+    }
+    assert!(!dir.join("003.png").exists());
+
+    // The saved frame matches the engine's last frame
+    let last = image::open(dir.join("002.png")).unwrap().to_rgba8();
+    let argb = u32::from_ne_bytes(engine.frame_buffer()[0..4].try_into().unwrap());
+    let [a, r, g, b] = argb.to_be_bytes();
+    assert_eq!(last.get_pixel(0, 0).0, [r, g, b, a]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn stopping_without_a_recording_is_a_no_op() {
+    let mut engine = Engine::new(W, H);
+    assert_eq!(engine.stop_recording(), Ok(None));
+    assert_eq!(engine.capture(), Ok(false));
+}
@@ -0,0 +1,107 @@
+//! Per-mesh render mode tests — `Mesh::set_render_mode` picks which passes
+//! draw a mesh, `Engine::set_render_mode` covers the rest, and
+//! `Engine::set_render_mode_override` wins over both.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// Two camera-facing squares side by side, as separate meshes.
+const QUADS_OBJ: &str = "\
+o left
+v -2.5 -1 0
+v -2.5 1 0
+v -0.5 1 0
+v -0.5 -1 0
+f 1 2 3
+f 1 3 4
+o right
+v 0.5 -1 0
+v 0.5 1 0
+v 2.5 1 0
+v 2.5 -1 0
+f 5 6 7
+f 5 7 8
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("quads", obj, W, H);
+    engine.camera_mut().set_position(Vec3::new(0.0, 0.0, -6.0));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine
+}
+
+/// Whether the square's middle pixel, at column `x`, is filled.
+fn filled_at(engine: &mut Engine, x: u32) -> bool {
+    let background = engine.palette().background;
+    let frame = engine.render_offscreen();
+    frame[((H / 2) * W + x) as usize] != background
+}
+
+/// Pixels drawn in the wireframe color.
+fn wire_pixels(engine: &mut Engine) -> usize {
+    let wireframe = engine.palette().wireframe;
+    let frame = engine.render_offscreen();
+    frame.iter().filter(|&&pixel| pixel == wireframe).count()
+}
+
+#[test]
+fn meshes_follow_their_own_mode_or_the_default() {
+    let mut engine = engine_with(QUADS_OBJ);
+    let (left, right) = (W / 4, W * 3 / 4);
+    assert!(filled_at(&mut engine, left) && filled_at(&mut engine, right));
+    assert_eq!(wire_pixels(&mut engine), 0);
+
+    let model = engine.model_mut("quads").unwrap();
+    model
+        .mesh_mut("right")
+        .unwrap()
+        .set_render_mode(RenderMode::Wireframe);
+    assert_eq!(
+        model.mesh("right").unwrap().render_mode(),
+        Some(RenderMode::Wireframe)
+    );
+    assert!(filled_at(&mut engine, left));
+    assert!(!filled_at(&mut engine, right));
+    assert!(wire_pixels(&mut engine) > 0);
+
+    // Back to the engine's mode
+    let mesh = engine
+        .model_mut("quads")
+        .unwrap()
+        .mesh_mut("right")
+        .unwrap();
+    assert_eq!(mesh.clear_render_mode(), Some(RenderMode::Wireframe));
+    let mesh = engine.model("quads").unwrap().mesh("right").unwrap();
+    assert_eq!(engine.effective_render_mode(mesh), RenderMode::Filled);
+    assert!(filled_at(&mut engine, right));
+}
+
+#[test]
+fn the_override_beats_every_mesh() {
+    let mut engine = engine_with(QUADS_OBJ);
+    let (left, right) = (W / 4, W * 3 / 4);
+    engine
+        .model_mut("quads")
+        .unwrap()
+        .mesh_mut("right")
+        .unwrap()
+        .set_render_mode(RenderMode::Wireframe);
+
+    engine.set_render_mode_override(Some(RenderMode::Filled));
+    assert!(filled_at(&mut engine, left) && filled_at(&mut engine, right));
+    assert_eq!(wire_pixels(&mut engine), 0);
+
+    engine.set_render_mode_override(Some(RenderMode::Wireframe));
+    assert!(!filled_at(&mut engine, left) && !filled_at(&mut engine, right));
+    assert!(wire_pixels(&mut engine) > 0);
+
+    // Cleared, each mesh draws its own way again
+    engine.set_render_mode_override(None);
+    assert!(filled_at(&mut engine, left));
+    assert!(!filled_at(&mut engine, right));
+}
@@ -0,0 +1,95 @@
+//! Offscreen render target tests — `Engine::render_to` draws the scene into
+//! a `RenderTarget` of any size, without a window and without touching the
+//! engine's own frame.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+fn engine_with_quad() -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine
+}
+
+/// Width and height in pixels of the box around everything but the
+/// background.
+fn drawn_extent(target: &RenderTarget, background: u32) -> (u32, u32) {
+    let (mut min, mut max) = ((u32::MAX, u32::MAX), (0, 0));
+    for (i, &pixel) in target.pixels().iter().enumerate() {
+        if pixel != background {
+            let (x, y) = (i as u32 % target.width(), i as u32 / target.width());
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+    }
+    assert!(max.0 >= min.0, "nothing drawn");
+    (max.0 - min.0 + 1, max.1 - min.1 + 1)
+}
+
+#[test]
+fn same_frame_renders_into_targets_of_any_size() {
+    let mut engine = engine_with_quad();
+    let background = engine.palette().background;
+
+    // At the engine's own size the target holds exactly its frame
+    let mut same = RenderTarget::new(W, H);
+    engine.render_to(&mut same);
+    assert_eq!(same.pixels(), engine.render_offscreen());
+
+    let mut small = RenderTarget::new(32, 32);
+    let mut wide = RenderTarget::new(256, 128);
+    engine.render_to(&mut small);
+    engine.render_to(&mut wide);
+    assert_eq!(small.pixels().len(), 32 * 32);
+
+    // The quad scales with the target's height and stays square
+    let (small_w, small_h) = drawn_extent(&small, background);
+    let (wide_w, wide_h) = drawn_extent(&wide, background);
+    assert!(small_w.abs_diff(small_h) <= 1, "{small_w}x{small_h}");
+    assert!(wide_w.abs_diff(wide_h) <= 1, "{wide_w}x{wide_h}");
+    assert!(wide_h.abs_diff(small_h * 4) <= 4, "{small_h} vs {wide_h}");
+
+    // Same surface in the middle of both
+    let center =
+        |t: &RenderTarget| t.pixels()[(t.height() / 2 * t.width() + t.width() / 2) as usize];
+    assert_ne!(center(&small), background);
+    assert_eq!(center(&small), center(&wide));
+    assert!(wide.depth_at(128, 64).unwrap() > 0.0);
+}
+
+#[test]
+fn render_to_leaves_the_engine_frame_alone() {
+    let mut engine = engine_with_quad();
+    let expected = engine.render_offscreen().to_vec();
+    let stats = engine.stats();
+
+    let mut target = RenderTarget::new(100, 30);
+    engine.render_to(&mut target);
+    assert_eq!(engine.stats(), stats);
+    assert_eq!(engine.frame_size(), (W, H));
+
+    // Drawing the last update again gives the same frame
+    engine.render();
+    assert_eq!(engine.frame_pixels(), &expected[..]);
+}
+
+#[test]
+fn targets_save_to_png() {
+    let mut engine = engine_with_quad();
+    let mut target = RenderTarget::new(40, 20);
+    engine.render_to(&mut target);
+
+    let path = common::temp_path("png");
+    target.save_png(&path).unwrap();
+    let image = image::open(&path).unwrap().to_rgba8();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(image.dimensions(), (40, 20));
+    let [a, r, g, b] = target.pixels()[10 * 40 + 20].to_be_bytes();
+    assert_eq!(image.get_pixel(20, 10).0, [r, g, b, a]);
+}
@@ -0,0 +1,103 @@
+//! Resolution scale tests — `Engine::set_resolution_scale` renders into a
+//! smaller internal buffer and stretches it back over the full display
+//! size, so fewer pixels are shaded while the frame still fills.
+
+use russsty::engine::MIN_RESOLUTION_SCALE;
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 96;
+const H: u32 = 64;
+
+fn engine_with_quad() -> Engine {
+    let mut engine = Engine::new(W, H);
+    common::load_obj(&mut engine, "quad", common::QUAD_OBJ);
+    engine.set_render_mode(RenderMode::FilledWireframe);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine
+}
+
+#[test]
+fn full_scale_matches_the_default_frame() {
+    let mut engine = engine_with_quad();
+    let expected = engine.render_offscreen().to_vec();
+
+    engine.set_resolution_scale(0.5);
+    assert_ne!(engine.render_offscreen(), &expected[..]);
+    engine.set_resolution_scale(1.0);
+    assert_eq!(engine.render_offscreen(), &expected[..]);
+}
+
+#[test]
+fn half_scale_shades_a_quarter_of_the_pixels() {
+    let mut engine = engine_with_quad();
+    engine.draw_grid = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.render_offscreen();
+    let full = engine.stats().pixels_shaded;
+
+    engine.set_resolution_scale(0.5);
+    let frame = engine.render_offscreen().to_vec();
+    let half = engine.stats().pixels_shaded;
+    let ratio = half as f32 / full as f32;
+    assert!((0.2..0.3).contains(&ratio), "{half} of {full}");
+
+    // Still display-sized, in 2×2 blocks of one color
+    assert_eq!(engine.frame_size(), (W, H));
+    assert_eq!(engine.viewport().width, (W / 2) as f32);
+    assert_eq!(frame.len(), (W * H) as usize);
+    for y in (0..H).step_by(2) {
+        for x in (0..W).step_by(2) {
+            let at = |dx: u32, dy: u32| frame[((y + dy) * W + x + dx) as usize];
+            assert!(
+                at(1, 0) == at(0, 0) && at(0, 1) == at(0, 0) && at(1, 1) == at(0, 0),
+                "block at ({x}, {y})"
+            );
+        }
+    }
+    let background = engine.palette().background;
+    assert_ne!(frame[(H / 2 * W + W / 2) as usize], background);
+}
+
+#[test]
+fn scaling_combines_with_supersampling_and_picking() {
+    let mut engine = engine_with_quad();
+    engine.set_picking(true);
+    let expected = engine.render_offscreen().to_vec();
+
+    // Half scale at 2× supersampling renders at display size, then
+    // averages down to half before stretching back up
+    engine.set_supersampling(2);
+    engine.set_resolution_scale(0.5);
+    assert_eq!(engine.viewport().width, W as f32);
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(frame.len(), expected.len());
+    let center = (H / 2 * W + W / 2) as usize;
+    assert_eq!(frame[center], frame[center + 1]);
+    assert_ne!(frame[center], engine.palette().background);
+
+    // Display coordinates still pick the quad at any scale
+    engine.set_supersampling(1);
+    engine.set_resolution_scale(0.3);
+    engine.render_offscreen();
+    // Above the ground grid's line through the middle
+    assert!(engine.pick(W as i32 / 2, H as i32 / 2 - 6).is_some());
+    assert!(engine.pick(2, 2).is_none());
+}
+
+#[test]
+fn scale_is_clamped() {
+    let mut engine = Engine::new(W, H);
+    engine.set_resolution_scale(0.0);
+    assert_eq!(engine.resolution_scale(), MIN_RESOLUTION_SCALE);
+    engine.set_resolution_scale(4.0);
+    assert_eq!(engine.resolution_scale(), 1.0);
+    engine.set_resolution_scale(f32::NAN);
+    assert_eq!(engine.resolution_scale(), 1.0);
+
+    // Tiny frames keep at least a pixel
+    engine.resize(3, 1);
+    engine.set_resolution_scale(MIN_RESOLUTION_SCALE);
+    assert_eq!(engine.render_offscreen().len(), 3);
+}
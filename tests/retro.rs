@@ -0,0 +1,116 @@
+//! Retro mode tests — `Engine::set_retro_mode` snaps vertices to a coarse
+//! grid, maps textures without perspective correction and cuts colors to
+//! fewer bits, and turning it off gives the usual frame back exactly.
+
+use std::f32::consts::FRAC_PI_3;
+
+use russsty::colors::quantize;
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// Off, so nothing but the retro step differs between frames.
+const PLAIN: RetroSettings = RetroSettings {
+    virtual_resolution: (W, H),
+    affine_textures: false,
+    color_depth: 8,
+};
+
+/// The square, textured with vertical stripes and lit from the side.
+fn engine_with_quad() -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::UV_QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_texture_mode(TextureMode::Modulate);
+    engine.set_light_direction(Vec3::new(0.6, -0.3, 1.0));
+    let stripes = Texture::from_fn(8, 8, |x, _| match x % 2 {
+        0 => 0xFFF0A030,
+        _ => 0xFF3070D0,
+    })
+    .unwrap();
+    engine.set_texture(stripes);
+    engine
+}
+
+fn frame(engine: &mut Engine) -> Vec<u32> {
+    engine.render_offscreen().to_vec()
+}
+
+/// The first and last covered columns of the middle row.
+fn row_span(engine: &mut Engine) -> (u32, u32) {
+    let background = engine.palette().background;
+    let frame = frame(engine);
+    let row = &frame[(H / 2 * W) as usize..((H / 2 + 1) * W) as usize];
+    let covered: Vec<u32> = (0..W).filter(|&x| row[x as usize] != background).collect();
+    (covered[0], *covered.last().unwrap())
+}
+
+#[test]
+fn turning_it_off_gives_the_modern_frame_back() {
+    let mut engine = engine_with_quad();
+    let modern = frame(&mut engine);
+
+    engine.set_retro_mode(Some(RetroSettings::default()));
+    assert!(frame(&mut engine) != modern);
+
+    engine.set_retro_mode(None);
+    assert_eq!(engine.retro_mode(), None);
+    assert!(frame(&mut engine) == modern);
+}
+
+#[test]
+fn vertices_snap_to_the_virtual_grid() {
+    let mut engine = engine_with_quad();
+    // A couple of pixels off the 8-pixel grid
+    engine
+        .model_mut("quad")
+        .unwrap()
+        .transform_mut()
+        .set_position(Vec3::new(0.13, 0.07, 0.0));
+    let (first, _) = row_span(&mut engine);
+    assert_ne!(first % 8, 0);
+
+    engine.set_retro_mode(Some(RetroSettings {
+        virtual_resolution: (8, 8),
+        ..PLAIN
+    }));
+    let (first, last) = row_span(&mut engine);
+    assert_eq!((first % 8, (last + 1) % 8), (0, 0), "{first}..={last}");
+}
+
+#[test]
+fn affine_textures_swim_on_slanted_polygons() {
+    let mut engine = engine_with_quad();
+    // The scanline rasterizer never corrects for perspective
+    engine.set_rasterizer(RasterizerType::EdgeFunction);
+    engine
+        .model_mut("quad")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.0, FRAC_PI_3, 0.0));
+    engine.set_retro_mode(Some(PLAIN));
+    let correct = frame(&mut engine);
+    engine.set_retro_mode(Some(RetroSettings {
+        affine_textures: true,
+        ..PLAIN
+    }));
+    let affine = frame(&mut engine);
+    // The stripes bend toward the far edge
+    let differing = affine.iter().zip(&correct).filter(|(a, b)| a != b).count();
+    assert!(differing > 20, "{differing}");
+}
+
+#[test]
+fn colors_are_cut_to_the_color_depth() {
+    let mut engine = engine_with_quad();
+    assert!(frame(&mut engine).iter().any(|&p| quantize(p, 5) != p));
+
+    engine.set_retro_mode(Some(RetroSettings {
+        color_depth: 5,
+        ..PLAIN
+    }));
+    assert!(frame(&mut engine).iter().all(|&p| quantize(p, 5) == p));
+}
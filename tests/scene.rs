@@ -0,0 +1,164 @@
+//! Scene file tests — a RON or JSON description loaded through
+//! `Engine::load_scene` sets up the same frame as the equivalent code,
+//! warns about unknown fields, and reports every missing asset at once.
+
+#![cfg(feature = "serde")]
+
+use russsty::prelude::*;
+use russsty::scene::{Scene, SceneError};
+use russsty::texture::Texture;
+use std::path::PathBuf;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+const SCENE_RON: &str = r#"
+(
+    models: [
+        (
+            name: "quad",
+            path: "quad.obj",
+            texture: "quad.png",
+            position: (0.5, 0.0, 0.0),
+            rotation: (0.0, 0.3, 0.0),
+        ),
+    ],
+    light: (direction: (0.0, -1.0, 1.0)),
+    camera: (position: (0.0, 1.0, -4.0), target: (0.0, 0.0, 0.0)),
+    settings: (
+        render_mode: Filled,
+        shading_mode: Toon(bands: 3),
+        texture_mode: Modulate,
+        rasterizer: EdgeFunction,
+        draw_grid: false,
+    ),
+)
+"#;
+
+/// A fresh directory holding the quad and its 2x2 texture.
+fn asset_dir() -> PathBuf {
+    let dir = common::temp_dir();
+    std::fs::write(dir.join("quad.obj"), common::UV_QUAD_OBJ).expect("write temp obj");
+    image::RgbaImage::from_fn(2, 2, |x, y| {
+        image::Rgba([255 * x as u8, 255 * y as u8, 128, 255])
+    })
+    .save(dir.join("quad.png"))
+    .expect("write temp png");
+    dir
+}
+
+/// The scene in `SCENE_RON`, set up in code.
+fn engine_built_by_hand(dir: &std::path::Path) -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Toon { bands: 3 });
+    engine.set_texture_mode(TextureMode::Modulate);
+    engine.set_rasterizer(RasterizerType::EdgeFunction);
+    engine.draw_grid = false;
+    engine
+        .add_model("quad", dir.join("quad.obj").to_str().unwrap())
+        .unwrap();
+    let quad = engine.model_mut("quad").unwrap();
+    quad.transform_mut()
+        .set_position(Vec3::new(0.5, 0.0, 0.0))
+        .set_rotation(Vec3::new(0.0, 0.3, 0.0));
+    quad.set_texture(Texture::from_file(dir.join("quad.png")).unwrap());
+    engine.set_light_direction(Vec3::new(0.0, -1.0, 1.0));
+    engine.set_camera(FpsCamera::looking_at(Vec3::new(0.0, 1.0, -4.0), Vec3::ZERO));
+    engine
+}
+
+#[test]
+fn ron_scene_matches_the_same_setup_in_code() {
+    let dir = asset_dir();
+    let scene_path = dir.join("scene.ron");
+    std::fs::write(&scene_path, SCENE_RON).unwrap();
+
+    let mut loaded = Engine::new(W, H);
+    let scene = loaded.load_scene(&scene_path).expect("load scene");
+    assert!(scene.unknown_fields().is_empty());
+    assert_eq!(loaded.model_count(), 1);
+    assert_eq!(loaded.shading_mode(), ShadingMode::Toon { bands: 3 });
+
+    let expected = engine_built_by_hand(&dir).render_offscreen().to_vec();
+    assert_eq!(loaded.render_offscreen(), &expected[..]);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn json_scenes_load_too() {
+    let dir = asset_dir();
+    let scene_path = dir.join("scene.json");
+    std::fs::write(
+        &scene_path,
+        r#"{
+            "models": [{ "name": "quad", "path": "quad.obj", "scale": [2.0, 2.0, 2.0] }],
+            "settings": { "shading_mode": { "Toon": { "bands": 2 } }, "draw_grid": false }
+        }"#,
+    )
+    .unwrap();
+
+    let mut engine = Engine::new(W, H);
+    engine.load_scene(&scene_path).expect("load scene");
+    assert_eq!(engine.shading_mode(), ShadingMode::Toon { bands: 2 });
+    assert!(!engine.draw_grid);
+    let quad = engine.model("quad").expect("quad loaded");
+    assert_eq!(quad.transform().scale(), Vec3::new(2.0, 2.0, 2.0));
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn unknown_fields_are_listed_not_fatal() {
+    let scene = Scene::from_ron(
+        r#"(
+            models: [(name: "quad", path: "quad.obj", textur: "quad.png")],
+            settings: (draw_grid: false, wireframe_colour: 3),
+            weather: "rain",
+        )"#,
+    )
+    .expect("parse scene");
+    assert_eq!(scene.models.len(), 1);
+    assert_eq!(scene.settings.draw_grid, Some(false));
+    assert_eq!(
+        scene.unknown_fields(),
+        ["models.0.textur", "settings.wireframe_colour", "weather"]
+    );
+}
+
+#[test]
+fn every_missing_asset_is_reported_and_nothing_loads() {
+    let dir = asset_dir();
+    let scene = Scene::from_ron(
+        r#"        (
+            models: [
+                (name: "a", path: "quad.obj", texture: "missing.png"),
+                (name: "b", path: "missing.obj"),
+                (name: "c", path: "quad.obj", texture: "quad.png"),
+            ],
+            settings: (draw_grid: false),
+        )"#,
+    )
+    .expect("parse scene");
+
+    let mut engine = Engine::new(W, H);
+    match engine.apply_scene(&scene, &dir) {
+        Err(SceneError::MissingAssets(paths)) => {
+            assert_eq!(paths, [dir.join("missing.png"), dir.join("missing.obj")]);
+        }
+        other => panic!("expected missing assets, got {other:?}"),
+    }
+    assert!(engine.draw_grid);
+    assert_eq!(engine.model_count(), 0);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn demo_scene_file_parses() {
+    let scene = Scene::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/scene.ron"))
+        .expect("parse scene.ron");
+    assert!(scene.unknown_fields().is_empty());
+    assert_eq!(scene.models[0].path, PathBuf::from("assets/f22.obj"));
+    assert_eq!(scene.settings.texture_mode, Some(TextureMode::Replace));
+}
@@ -0,0 +1,48 @@
+//! Screenshot tests — `Engine::save_screenshot` writes the presented frame,
+//! overlays included, to a timestamped PNG without overwriting earlier ones.
+
+use russsty::prelude::*;
+
+#[test]
+fn screenshots_hold_the_presented_frame() {
+    let dir = std::env::temp_dir()
+        .join(format!("russsty_screenshot_{}", std::process::id()))
+        .join("nested");
+    let mut engine = Engine::new(48, 32);
+    engine.draw_debug_hud(true);
+    let frame = engine.render_offscreen().to_vec();
+
+    let path = engine.save_screenshot(&dir).unwrap();
+    let name = path.file_name().unwrap().to_str().unwrap();
+    assert!(
+        name.starts_with("screenshot_") && name.ends_with(".png"),
+        "{name}"
+    );
+    assert_eq!(name.len(), "screenshot_YYYYMMDD_HHMMSS.png".len(), "{name}");
+
+    let image = image::open(&path).unwrap().to_rgba8();
+    assert_eq!(image.dimensions(), (48, 32));
+    for (pixel, &expected) in image.pixels().zip(&frame) {
+        let [a, r, g, b] = expected.to_be_bytes();
+        assert_eq!(pixel.0, [r, g, b, a]);
+    }
+
+    // Taken within the same second, the next one gets its own name
+    let again = engine.save_screenshot(&dir).unwrap();
+    assert_ne!(again, path);
+    assert!(path.exists() && again.exists());
+
+    std::fs::remove_dir_all(dir.parent().unwrap()).ok();
+}
+
+#[test]
+fn unwritable_directories_are_an_error() {
+    let file = std::env::temp_dir().join(format!("russsty_screenshot_file_{}", std::process::id()));
+    std::fs::write(&file, b"").unwrap();
+    let mut engine = Engine::new(8, 8);
+    engine.render_offscreen();
+
+    // A file where the directory should be
+    assert!(engine.save_screenshot(&file).is_err());
+    std::fs::remove_file(&file).ok();
+}
@@ -0,0 +1,168 @@
+//! Selection outline tests — the outline rings the selected mesh's visible
+//! pixels, leaves the mesh itself alone, and skips parts hidden behind
+//! other geometry.
+
+use russsty::prelude::*;
+use russsty::SelectionOutline;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+const OUTLINE: u32 = 0xFFFF00FF;
+
+// A target quad facing the default camera, and a blocker in front of it
+// hiding its right half
+const SCENE_OBJ: &str = "\
+o target
+v -1.0 -1.0 0.0
+v -1.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+f 1 2 3
+f 1 3 4
+o blocker
+v 0.0 -2.0 -1.0
+v 0.0 2.0 -1.0
+v 2.0 2.0 -1.0
+v 2.0 -2.0 -1.0
+f 5 6 7
+f 5 7 8
+";
+
+fn engine_with_scene() -> Engine {
+    let mut engine = common::engine_with_obj("scene", SCENE_OBJ, W, H);
+    engine.backface_culling = false;
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_picking(true);
+    engine
+}
+
+fn target_index(engine: &Engine) -> usize {
+    let model = engine.model("scene").unwrap();
+    model
+        .meshes()
+        .iter()
+        .position(|mesh| mesh.name() == "target")
+        .unwrap()
+}
+
+/// Pixels where the target mesh is what's visible
+fn visible_target(engine: &Engine, target: usize) -> Vec<(i32, i32)> {
+    (0..H as i32)
+        .flat_map(|y| (0..W as i32).map(move |x| (x, y)))
+        .filter(|&(x, y)| {
+            engine
+                .pick(x, y)
+                .is_some_and(|hit| hit.mesh_index == target)
+        })
+        .collect()
+}
+
+#[test]
+fn outline_rings_only_the_visible_part() {
+    let mut engine = engine_with_scene();
+    let target = target_index(&engine);
+    let before = engine.render_offscreen().to_vec();
+    let visible = visible_target(&engine, target);
+    assert!(!visible.is_empty());
+
+    let thickness = 2;
+    engine.set_selection_outline(SelectionOutline {
+        model_index: 0,
+        mesh_index: target,
+        color: OUTLINE,
+        thickness,
+    });
+    let after = engine.render_offscreen().to_vec();
+
+    let changed: Vec<(i32, i32)> = (0..H as i32)
+        .flat_map(|y| (0..W as i32).map(move |x| (x, y)))
+        .filter(|&(x, y)| before[(y * W as i32 + x) as usize] != after[(y * W as i32 + x) as usize])
+        .collect();
+    assert!(!changed.is_empty(), "no outline drawn");
+
+    let reach = (thickness * thickness) as i32;
+    for &(x, y) in &changed {
+        assert_eq!(after[(y * W as i32 + x) as usize], OUTLINE);
+        assert!(
+            !visible.contains(&(x, y)),
+            "outline over the mesh at ({x}, {y})"
+        );
+        // Hidden parts aren't outlined: every outline pixel hugs a
+        // visible one
+        assert!(
+            visible
+                .iter()
+                .any(|&(vx, vy)| (vx - x).pow(2) + (vy - y).pow(2) <= reach),
+            "outline at ({x}, {y}) is away from the visible mesh"
+        );
+    }
+
+    // The visible part's left edge, against the background, is outlined
+    let (left, row) = *visible.iter().min_by_key(|(x, _)| *x).unwrap();
+    assert_eq!(after[(row * W as i32 + left - 1) as usize], OUTLINE);
+}
+
+#[test]
+fn clearing_the_selection_removes_the_outline() {
+    let mut engine = engine_with_scene();
+    let before = engine.render_offscreen().to_vec();
+    engine.set_selection_outline(SelectionOutline {
+        model_index: 0,
+        mesh_index: target_index(&engine),
+        color: OUTLINE,
+        thickness: 1,
+    });
+    assert_ne!(engine.render_offscreen(), &before[..]);
+
+    engine.clear_selection_outline();
+    assert_eq!(engine.render_offscreen(), &before[..]);
+}
+
+#[test]
+fn stale_selection_draws_nothing() {
+    let mut engine = engine_with_scene();
+    let before = engine.render_offscreen().to_vec();
+    engine.set_selection_outline(SelectionOutline {
+        model_index: 3,
+        mesh_index: 0,
+        color: OUTLINE,
+        thickness: 2,
+    });
+    assert_eq!(engine.render_offscreen(), &before[..]);
+}
+
+#[test]
+fn outline_follows_its_model_when_another_is_removed() {
+    let mut engine = engine_with_scene();
+    common::load_obj(&mut engine, "second", SCENE_OBJ);
+    common::load_obj(&mut engine, "third", SCENE_OBJ);
+    let outline = SelectionOutline {
+        model_index: 1,
+        mesh_index: target_index(&engine),
+        color: OUTLINE,
+        thickness: 1,
+    };
+    engine.set_selection_outline(outline);
+
+    // Removing a later model leaves the index alone
+    engine.remove_model("third");
+    assert_eq!(engine.selection_outline(), Some(outline));
+
+    // Removing an earlier one shifts it down with the model
+    engine.remove_model("scene");
+    assert_eq!(
+        engine.selection_outline(),
+        Some(SelectionOutline {
+            model_index: 0,
+            ..outline
+        })
+    );
+
+    // Removing the outlined model clears it
+    engine.remove_model("second");
+    assert_eq!(engine.selection_outline(), None);
+}
@@ -1,16 +1,13 @@
 //! §1.2 regression test — sub-pixel precision + top-left fill rule.
 //!
-//! Locks in the watertightness behavior that the planned §1.2 rewrite is
-//! designed to produce: when two triangles share an edge, every boundary
-//! pixel must be owned by exactly one of them — no double-writes.
+//! Locks in watertightness: when two triangles share an edge, every
+//! boundary pixel must be owned by exactly one of them — no double-writes.
 //!
-//! **TODAY THIS TEST FAILS**, and that is the point. The current
-//! `EdgeFunctionRasterizer` evaluates edges in `f32` with a `>= 0` /
-//! `<= 0` inside test, so pixels whose center lies exactly on a shared
-//! edge pass the test for both triangles and get rasterized twice.
-//! Appendix B in `docs/ROADMAP.md` tracks the fix (fixed-point vertex
-//! coordinates with `i64` edge math + the top-left fill rule bias).
-//! When that lands the test turns green.
+//! A plain `>= 0` / `<= 0` inside test lets pixels whose center lies
+//! exactly on a shared edge pass for both triangles, so they get
+//! rasterized twice. The `EdgeFunctionRasterizer` snaps vertices to
+//! fixed point, evaluates edges in `i64`, and breaks those ties with the
+//! top-left fill rule (Appendix B in `docs/ROADMAP.md`).
 //!
 //! # Why an axis-aligned shared edge at `y = k + 0.5`
 //!
@@ -30,14 +27,15 @@
 //! what we test.
 
 use russsty::bench::{EdgeFunctionRasterizer, FrameBuffer, Rasterizer, ScreenVertex, Triangle};
+use russsty::colors::to_rgb_f32;
 use russsty::engine::TextureMode;
 use russsty::prelude::Vec2;
 use russsty::ShadingMode;
 
 // Horizontal shared edge at y = 30.5, running from x = 10.5 to x = 50.5.
 // Pixel centers on framebuffer row y = 30 have centers at y = 30.5 —
-// exactly on the edge. 41 such pixels (x = 10..=50) should be claimed
-// by both triangles today.
+// exactly on the edge. Without a fill rule, 41 such pixels (x = 10..=50)
+// are claimed by both triangles.
 //
 //                 y = 5.5
 //                    C2
@@ -69,7 +67,7 @@ fn tri(points: [ScreenVertex; 3], color: u32) -> Triangle {
     Triangle::new(
         points,
         color,
-        [color; 3],
+        [to_rgb_f32(color); 3],
         [Vec2::ZERO; 3],
         ShadingMode::None,
         TextureMode::None,
@@ -124,6 +122,6 @@ fn shared_edge_has_no_double_writes() {
     assert_eq!(
         both, 0,
         "Shared-edge pixels are being written by BOTH triangles \
-         ({both} overlap). §1.2 top-left fill rule should reduce this to 0."
+         ({both} overlap). The top-left fill rule should reduce this to 0."
     );
 }
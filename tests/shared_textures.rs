@@ -0,0 +1,133 @@
+//! Shared texture tests — textures added with `Engine::add_texture` are
+//! drawn by every mesh holding their handle from one copy, and removing
+//! one leaves those meshes untextured.
+
+use russsty::prelude::*;
+use russsty::texture::{Texture, TextureHandle};
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const TILES: usize = 10;
+const GREEN: u32 = 0xFF20C040;
+
+// A small quad facing the default camera, with UVs
+const TILE_OBJ: &str = "\
+v -0.3 -0.3 0
+v -0.3 0.3 0
+v 0.3 0.3 0
+v 0.3 -0.3 0
+vt 0 0
+vt 0 1
+vt 1 1
+vt 1 0
+f 1/1 2/2 3/3
+f 1/1 3/3 4/4
+";
+
+/// Ten tiles in two rows of five, each its own model, filling the view.
+fn engine_with_tiles() -> Engine {
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    for i in 0..TILES {
+        let name = format!("tile{i}");
+        common::load_obj(&mut engine, &name, TILE_OBJ);
+        let (x, y) = tile_position(i);
+        engine
+            .model_mut(&name)
+            .unwrap()
+            .transform_mut()
+            .set_position(Vec3::new(x, y, 0.0));
+    }
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_texture_mode(TextureMode::Replace);
+    engine
+}
+
+/// Where tile `i` sits; the view is 4 units across at the tiles.
+fn tile_position(i: usize) -> (f32, f32) {
+    let (column, row) = (i % 5, i / 5);
+    (
+        -1.6 + column as f32 * 0.8,
+        if row == 0 { 0.8 } else { -0.8 },
+    )
+}
+
+fn set_tile_textures(engine: &mut Engine, handle: TextureHandle) {
+    for i in 0..TILES {
+        for mesh in engine.model_mut(&format!("tile{i}")).unwrap().meshes_mut() {
+            mesh.set_texture_handle(handle);
+        }
+    }
+}
+
+/// The color at the middle of each tile.
+fn tile_centers(engine: &mut Engine) -> Vec<u32> {
+    let frame = engine.render_offscreen().to_vec();
+    (0..TILES)
+        .map(|i| {
+            let (x, y) = tile_position(i);
+            let px = ((x + 2.0) / 4.0 * W as f32) as u32;
+            let py = ((2.0 - y) / 4.0 * H as f32) as u32;
+            frame[(py * W + px) as usize]
+        })
+        .collect()
+}
+
+#[test]
+fn ten_meshes_draw_one_4k_texture() {
+    let mut engine = engine_with_tiles();
+    let handle = engine.add_texture(Texture::from_fn(4096, 4096, |_, _| GREEN).unwrap());
+    set_tile_textures(&mut engine, handle);
+
+    assert_eq!(tile_centers(&mut engine), [GREEN; TILES]);
+    // One texture held, which every tile resolves to
+    assert_eq!(engine.shared_texture_count(), 1);
+    let shared = engine.shared_texture(handle).unwrap() as *const Texture;
+    for i in 0..TILES {
+        let mesh = &engine.model(&format!("tile{i}")).unwrap().meshes()[0];
+        let texture = engine
+            .shared_texture(mesh.texture_handle().unwrap())
+            .unwrap();
+        assert!(std::ptr::eq(texture, shared));
+    }
+}
+
+#[test]
+fn handles_win_over_model_and_global_textures() {
+    let mut engine = engine_with_tiles();
+    engine.set_texture(Texture::from_fn(1, 1, |_, _| 0xFFFF0000).unwrap());
+    let handle = engine.add_texture(Texture::from_fn(1, 1, |_, _| GREEN).unwrap());
+    engine
+        .model_mut("tile0")
+        .unwrap()
+        .set_texture(Texture::from_fn(1, 1, |_, _| 0xFF0000FF).unwrap());
+    set_tile_textures(&mut engine, handle);
+    assert_eq!(tile_centers(&mut engine), [GREEN; TILES]);
+
+    // Without its handle, a tile goes back to its model's texture
+    engine.model_mut("tile0").unwrap().meshes_mut()[0].clear_texture_handle();
+    assert_eq!(tile_centers(&mut engine)[..2], [0xFF0000FF, GREEN]);
+}
+
+#[test]
+fn removed_textures_leave_meshes_untextured() {
+    let mut engine = engine_with_tiles();
+    let untextured = tile_centers(&mut engine);
+    assert!(untextured.iter().all(|&color| color != GREEN));
+
+    let handle = engine.add_texture(Texture::from_fn(2, 2, |_, _| GREEN).unwrap());
+    set_tile_textures(&mut engine, handle);
+    assert_eq!(tile_centers(&mut engine), [GREEN; TILES]);
+
+    assert!(engine.remove_texture(handle).is_some());
+    assert!(engine.remove_texture(handle).is_none());
+    assert_eq!(tile_centers(&mut engine), untextured);
+
+    // A texture taking the freed slot isn't picked up by the old handle
+    let other = engine.add_texture(Texture::from_fn(2, 2, |_, _| GREEN).unwrap());
+    assert_ne!(other, handle);
+    assert!(engine.shared_texture(handle).is_none());
+    assert_eq!(tile_centers(&mut engine), untextured);
+}
@@ -0,0 +1,73 @@
+//! Small triangle rejection tests — `Engine::set_min_triangle_area` drops
+//! projected specks that cover no pixel center, leaving the picture as it
+//! was.
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// One mesh of rectangles in the z = 0 plane facing the default camera at
+/// z = -5, where a unit is 16 pixels, each given as (left, bottom, right,
+/// top).
+fn rects_obj(rects: &[(f32, f32, f32, f32)]) -> String {
+    let mut obj = String::new();
+    for (i, &(l, b, r, t)) in rects.iter().enumerate() {
+        obj += &format!("v {l} {b} 0.0\nv {l} {t} 0.0\nv {r} {t} 0.0\nv {r} {b} 0.0\n");
+        let base = i * 4;
+        obj += &format!(
+            "f {} {} {}\nf {} {} {}\n",
+            base + 1,
+            base + 2,
+            base + 3,
+            base + 1,
+            base + 3,
+            base + 4
+        );
+    }
+    obj
+}
+
+fn engine_with_rects(rects: &[(f32, f32, f32, f32)]) -> Engine {
+    let mut engine = common::engine_with_obj("rects", &rects_obj(rects), W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine
+}
+
+#[test]
+fn dropping_specks_leaves_the_picture_alone() {
+    // A row of specks a third of a pixel wide, and one large square
+    let mut rects: Vec<_> = (0..24)
+        .map(|i| {
+            let x = -1.5 + i as f32 * 0.13;
+            (x, 1.5, x + 0.02, 1.52)
+        })
+        .collect();
+    rects.push((-1.0, -1.0, 0.0, 0.0));
+    let mut engine = engine_with_rects(&rects);
+    let all = engine.render_offscreen().to_vec();
+    assert_eq!(engine.stats().triangles_too_small, 0);
+
+    engine.set_min_triangle_area(1.0);
+    let culled = engine.render_offscreen().to_vec();
+    let stats = engine.stats();
+    assert!(stats.triangles_too_small > 24, "{stats:?}");
+    assert_eq!(stats.triangles_drawn + stats.triangles_too_small, 50);
+    assert_eq!(culled, all);
+}
+
+#[test]
+fn long_slivers_across_pixel_centers_stay() {
+    // 48 pixels long and a third of a pixel high, across row 31's centers
+    let mut engine = engine_with_rects(&[(-1.5, 0.02, 1.5, 0.04)]);
+    engine.set_min_triangle_area(100.0);
+    let frame = engine.render_offscreen().to_vec();
+    assert_eq!(engine.stats().triangles_too_small, 0);
+
+    let background = engine.palette().background;
+    let row = &frame[(31 * W) as usize..(32 * W) as usize];
+    assert!(row.iter().filter(|&&pixel| pixel != background).count() > 40);
+}
@@ -0,0 +1,146 @@
+//! Unchanged frame tests — with `Engine::set_skip_unchanged_frames`, a
+//! frame with nothing changed since the last skips geometry and drawing
+//! and presents the last image again, until any change comes along.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use russsty::prelude::*;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+// A 2x2 quad facing the default camera
+const QUAD_OBJ: &str = "\
+o quad
+v -1 -1 0
+v -1 1 0
+v 1 1 0
+v 1 -1 0
+f 1 2 3
+f 1 3 4
+";
+
+/// Paints every pixel white, counting how many it paints.
+struct Counting(Arc<AtomicUsize>);
+
+impl PixelShader for Counting {
+    fn shade(&self, _fragment: &Fragment) -> u32 {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        0xFFFFFFFF
+    }
+}
+
+/// An engine drawing the quad with a counting shader, skipping unchanged
+/// frames, and the count.
+fn engine_with_quad() -> (Engine, Arc<AtomicUsize>) {
+    let mut engine = common::engine_with_obj("quad", QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    let shaded = Arc::new(AtomicUsize::new(0));
+    engine.set_custom_shader(Some(Box::new(Counting(shaded.clone()))));
+    engine.set_skip_unchanged_frames(true);
+    (engine, shaded)
+}
+
+/// Render a frame, returning its pixels, whether it was redrawn and how
+/// many pixels were shaded for it.
+fn frame(engine: &mut Engine, shaded: &AtomicUsize) -> (Vec<u32>, bool, usize) {
+    shaded.store(0, Ordering::Relaxed);
+    let pixels = engine.render_offscreen().to_vec();
+    (
+        pixels,
+        engine.frame_changed(),
+        shaded.load(Ordering::Relaxed),
+    )
+}
+
+#[test]
+fn unchanged_frames_are_presented_without_drawing() {
+    let (mut engine, shaded) = engine_with_quad();
+    let (first, changed, count) = frame(&mut engine, &shaded);
+    assert!(changed);
+    assert!(count > 0);
+
+    for _ in 0..3 {
+        let (pixels, changed, count) = frame(&mut engine, &shaded);
+        assert!(!changed);
+        assert_eq!(count, 0);
+        assert!(pixels == first);
+    }
+
+    // Off, every frame is drawn again
+    engine.set_skip_unchanged_frames(false);
+    let (pixels, changed, count) = frame(&mut engine, &shaded);
+    assert!(changed && count > 0);
+    assert!(pixels == first);
+}
+
+#[test]
+fn any_change_draws_the_next_frame() {
+    let (mut engine, shaded) = engine_with_quad();
+    frame(&mut engine, &shaded);
+
+    type Change = fn(&mut Engine);
+    let changes: [(&str, Change); 6] = [
+        ("camera", |e| {
+            e.camera_mut().set_position(Vec3::new(0.5, 0.0, -5.0))
+        }),
+        ("light", |e| e.set_light_direction(Vec3::new(1.0, 0.0, 1.0))),
+        ("model", |e| {
+            e.model_mut("quad")
+                .unwrap()
+                .transform_mut()
+                .set_position(Vec3::new(0.0, 0.5, 0.0));
+        }),
+        ("grid field", |e| e.draw_grid = true),
+        ("culling field", |e| e.backface_culling = false),
+        ("resize", |e| e.resize(48, 48)),
+    ];
+    for (name, change) in changes {
+        change(&mut engine);
+        let (_, changed, count) = frame(&mut engine, &shaded);
+        assert!(changed && count > 0, "{name}");
+        let (_, changed, count) = frame(&mut engine, &shaded);
+        assert!(!changed && count == 0, "{name}, settled");
+    }
+}
+
+#[test]
+fn queued_lines_draw_their_frame_and_the_one_after() {
+    let (mut engine, shaded) = engine_with_quad();
+    let (plain, _, _) = frame(&mut engine, &shaded);
+
+    engine.draw_line_3d(
+        Vec3::new(-2.0, 1.5, 0.0),
+        Vec3::new(2.0, 1.5, 0.0),
+        0xFFFF00FF,
+    );
+    let (lined, changed, _) = frame(&mut engine, &shaded);
+    assert!(changed);
+    assert!(lined != plain);
+
+    // The line is gone again, which takes a redraw too
+    let (pixels, changed, _) = frame(&mut engine, &shaded);
+    assert!(changed);
+    assert!(pixels == plain);
+    assert!(!frame(&mut engine, &shaded).1);
+}
+
+#[test]
+fn overlays_are_redrawn_over_the_kept_frame() {
+    let (mut engine, shaded) = engine_with_quad();
+    let invert = |pixels: &mut [u32], _: u32, _: u32| {
+        for pixel in pixels {
+            *pixel ^= 0x00FFFFFF;
+        }
+    };
+    engine.set_post_effect(Some(Box::new(invert)));
+    let (first, _, _) = frame(&mut engine, &shaded);
+
+    // Applied afresh to the kept frame each time, not on top of the last
+    let (pixels, changed, _) = frame(&mut engine, &shaded);
+    assert!(!changed);
+    assert!(pixels == first);
+}
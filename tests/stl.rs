@@ -0,0 +1,110 @@
+//! STL loading tests — a binary cube like a typical CAD export (zeroed
+//! facet normals, a header starting with "solid") loads through
+//! `Engine::add_stl_model` with its corners shared per face, and renders
+//! flat-shaded facing the right way.
+
+use russsty::prelude::*;
+use russsty::{Error, LoadError};
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const BACKGROUND: u32 = 0xFF101010;
+
+const CORNERS: [[f32; 3]; 8] = [
+    [-1.0, -1.0, -1.0],
+    [-1.0, 1.0, -1.0],
+    [1.0, 1.0, -1.0],
+    [1.0, -1.0, -1.0],
+    [-1.0, -1.0, 1.0],
+    [-1.0, 1.0, 1.0],
+    [1.0, 1.0, 1.0],
+    [1.0, -1.0, 1.0],
+];
+
+// Outward-facing triangles, two per side
+const TRIANGLES: [[usize; 3]; 12] = [
+    [0, 1, 2],
+    [0, 2, 3],
+    [7, 6, 5],
+    [7, 5, 4],
+    [3, 2, 6],
+    [3, 6, 7],
+    [4, 5, 1],
+    [4, 1, 0],
+    [1, 5, 6],
+    [1, 6, 2],
+    [4, 0, 3],
+    [4, 3, 7],
+];
+
+fn binary_cube_stl() -> Vec<u8> {
+    let mut bytes = b"solid cube exported by a CAD package".to_vec();
+    bytes.resize(80, b' ');
+    bytes.extend_from_slice(&(TRIANGLES.len() as u32).to_le_bytes());
+    for triangle in TRIANGLES {
+        // Zeroed facet normal, as many exporters write
+        bytes.extend_from_slice(&[0; 12]);
+        for corner in triangle {
+            for c in CORNERS[corner] {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+    }
+    bytes
+}
+
+fn engine_with_cube() -> Engine {
+    let path = common::temp_path("stl");
+    std::fs::write(&path, binary_cube_stl()).expect("write temp stl");
+
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine.palette_mut().background = BACKGROUND;
+    engine
+        .add_stl_model("cube", path.to_str().unwrap())
+        .expect("load cube");
+    std::fs::remove_file(&path).ok();
+    engine
+}
+
+#[test]
+fn binary_cube_shares_corners_within_each_side() {
+    let engine = engine_with_cube();
+    let mesh = &engine.model("cube").unwrap().meshes()[0];
+    assert_eq!(mesh.face_count(), 12);
+    // Sides meet at 90 degrees: each of the 8 corners is split three ways,
+    // and each side's two triangles share their diagonal
+    assert_eq!(mesh.vertex_count(), 24);
+}
+
+#[test]
+fn binary_cube_renders_flat_shaded() {
+    let mut engine = engine_with_cube();
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Flat);
+    engine
+        .model_mut("cube")
+        .unwrap()
+        .transform_mut()
+        .set_rotation(Vec3::new(0.4, 0.6, 0.0));
+    engine.update();
+    engine.render();
+
+    // Backface culling is on, so the near sides are the ones drawn
+    let stats = engine.stats();
+    assert_eq!(stats.faces_backface_culled, 6);
+    assert_ne!(common::pixel(&engine, (W / 2, H / 2)), BACKGROUND);
+    assert_eq!(common::pixel(&engine, (1, 1)), BACKGROUND);
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let mut engine = Engine::new(W, H);
+    assert!(matches!(
+        engine.add_stl_model("missing", "does/not/exist.stl"),
+        Err(Error::MeshLoad(LoadError::Io(_)))
+    ));
+}
@@ -0,0 +1,151 @@
+//! Subdivision tests — `Mesh::subdivide_loop` splits every face in four per
+//! round and rounds a closed mesh off into a smoothly shaded surface.
+
+use russsty::prelude::*;
+use russsty::Mesh;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// A cube from -1 to 1 sharing its eight corners between faces.
+const CUBE_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+f 1 2 3
+f 1 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+/// The same cube with a normal per side, which splits every corner into
+/// three vertices at load.
+const FACETED_CUBE_OBJ: &str = "\
+v -1 -1 -1
+v -1 1 -1
+v 1 1 -1
+v 1 -1 -1
+v -1 -1 1
+v -1 1 1
+v 1 1 1
+v 1 -1 1
+vn 0 0 -1
+vn 0 0 1
+vn 1 0 0
+vn -1 0 0
+vn 0 1 0
+vn 0 -1 0
+f 1//1 2//1 3//1
+f 1//1 3//1 4//1
+f 8//2 7//2 6//2
+f 8//2 6//2 5//2
+f 4//3 3//3 7//3
+f 4//3 7//3 8//3
+f 5//4 6//4 2//4
+f 5//4 2//4 1//4
+f 2//5 6//5 7//5
+f 2//5 7//5 3//5
+f 5//6 1//6 4//6
+f 5//6 4//6 8//6
+";
+
+fn engine_with(obj: &str) -> Engine {
+    let mut engine = common::engine_with_obj("cube", obj, W, H);
+    engine.set_camera(FpsCamera::looking_at(Vec3::new(2.5, 2.0, -3.0), Vec3::ZERO));
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    engine
+}
+
+fn cube(engine: &mut Engine) -> &mut Mesh {
+    engine
+        .model_mut("cube")
+        .unwrap()
+        .mesh_by_index_mut(0)
+        .unwrap()
+}
+
+/// The nearest and farthest vertex from the origin.
+fn radius_range(mesh: &Mesh) -> (f32, f32) {
+    mesh.positions()
+        .map(|p| p.magnitude())
+        .fold((f32::MAX, 0.0), |(lo, hi), r| (lo.min(r), hi.max(r)))
+}
+
+/// The largest brightness step between neighbouring pixels on the cube.
+fn largest_step(frame: &[u32]) -> u32 {
+    let brightness = |pixel: u32| (pixel >> 16 & 0xFF) + (pixel >> 8 & 0xFF) + (pixel & 0xFF);
+    let background = frame[0];
+    let mut largest = 0;
+    for y in 0..H {
+        for x in 1..W {
+            let (a, b) = (frame[(y * W + x - 1) as usize], frame[(y * W + x) as usize]);
+            if a != background && b != background {
+                largest = largest.max(brightness(a).abs_diff(brightness(b)));
+            }
+        }
+    }
+    largest
+}
+
+#[test]
+fn each_round_quadruples_the_faces() {
+    let mut engine = engine_with(CUBE_OBJ);
+    let mesh = cube(&mut engine);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (8, 12));
+
+    // A closed mesh gains one vertex per edge: 8 + 18, then 26 + 72
+    assert_eq!(mesh.subdivide_loop(1), 0);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (26, 48));
+    assert_eq!(mesh.subdivide_loop(1), 0);
+    assert_eq!((mesh.vertex_count(), mesh.face_count()), (98, 192));
+    assert!(mesh
+        .face_indices()
+        .flatten()
+        .all(|i| (i as usize) < mesh.vertex_count()));
+
+    assert_eq!(mesh.subdivide_loop(0), 0);
+    assert_eq!(mesh.face_count(), 192);
+}
+
+#[test]
+fn subdivided_cube_rounds_off() {
+    let mut engine = engine_with(CUBE_OBJ);
+    cube(&mut engine).subdivide_loop(2);
+    // The corners pull in from sqrt(3), leaving something close to a ball
+    let (near, far) = radius_range(cube(&mut engine));
+    assert!(far < 1.1 && far / near < 1.2, "{near} to {far}");
+
+    // Split at its seams, the cube still subdivides into the same shape
+    let mut faceted = engine_with(FACETED_CUBE_OBJ);
+    let mesh = cube(&mut faceted);
+    assert_eq!(mesh.vertex_count(), 24);
+    assert_eq!(mesh.subdivide_loop(2), 0);
+    assert_eq!(mesh.face_count(), 192);
+    assert_eq!(radius_range(mesh), (near, far));
+}
+
+#[test]
+fn subdivided_cube_shades_smoothly() {
+    let mut engine = engine_with(FACETED_CUBE_OBJ);
+    let hard = largest_step(engine.render_offscreen());
+    cube(&mut engine).subdivide_loop(2);
+    let smooth = largest_step(engine.render_offscreen());
+    // No more hard edges where the sides meet
+    assert!(smooth * 4 < hard, "{smooth} vs {hard}");
+}
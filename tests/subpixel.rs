@@ -0,0 +1,94 @@
+//! Sub-pixel snapping tests — both rasterizers round vertices to a
+//! 1/16-pixel grid before deciding coverage, so vertex jitter smaller than
+//! half a grid step can't change which pixels a triangle covers.
+
+use russsty::bench::{
+    EdgeFunctionRasterizer, OwnedFrameBuffer, Rasterizer, ScanlineRasterizer, ScreenVertex,
+    TriangleBuilder,
+};
+use russsty::prelude::Vec2;
+
+const W: u32 = 48;
+const H: u32 = 48;
+
+/// On the 1/16 grid, with edges passing close to many pixel centers
+fn triangle() -> [Vec2; 3] {
+    [
+        Vec2::new(3.5, 2.0625),
+        Vec2::new(44.25, 17.5),
+        Vec2::new(12.8125, 45.5),
+    ]
+}
+
+/// Offsets below half a grid step (1/32 pixel), so every vertex still
+/// snaps to the same grid point
+fn jitter() -> [Vec2; 4] {
+    [
+        Vec2::new(0.03, -0.03),
+        Vec2::new(-0.02, 0.01),
+        Vec2::new(0.001, 0.0299),
+        Vec2::new(-0.0301, -0.015),
+    ]
+}
+
+fn coverage(rasterizer: &dyn Rasterizer, points: [Vec2; 3]) -> Vec<bool> {
+    let triangle = TriangleBuilder::new(points.map(|p| ScreenVertex::new(p, 1.0)))
+        .color(0xFFFFFFFF)
+        .build();
+    let mut target = OwnedFrameBuffer::new(W, H);
+    rasterizer.fill_triangle(
+        &triangle,
+        &mut target.as_framebuffer(),
+        triangle.color,
+        None,
+    );
+    target.color().iter().map(|&p| p != 0).collect()
+}
+
+#[test]
+fn jitter_within_the_snap_grid_keeps_coverage() {
+    let rasterizers: [Box<dyn Rasterizer>; 2] = [
+        Box::new(ScanlineRasterizer::new()),
+        Box::new(EdgeFunctionRasterizer::new()),
+    ];
+    for rasterizer in rasterizers {
+        let expected = coverage(rasterizer.as_ref(), triangle());
+        assert!(expected.iter().any(|p| *p));
+
+        let jitter = jitter();
+        for i in 0..jitter.len() {
+            // Jitter each vertex differently
+            let jittered: [Vec2; 3] =
+                std::array::from_fn(|v| triangle()[v] + jitter[(i + v) % jitter.len()]);
+            assert!(
+                coverage(rasterizer.as_ref(), jittered) == expected,
+                "{} coverage changed with jitter {jittered:?}",
+                rasterizer.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn real_motion_still_changes_coverage() {
+    // The snap is coarse enough to hide jitter, not real motion
+    let rasterizer = EdgeFunctionRasterizer::new();
+    let before = coverage(&rasterizer, triangle());
+    let moved = triangle().map(|p| p + Vec2::new(0.5, 0.0));
+    assert!(coverage(&rasterizer, moved) != before);
+}
+
+#[test]
+fn rasterizers_agree_off_the_grid() {
+    // With exact integer coverage the two agree for any input, not just
+    // positions that happen to be representable
+    let points = [
+        Vec2::new(2.603, 3.377),
+        Vec2::new(41.421, 17.32),
+        Vec2::new(9.87, 44.05),
+    ];
+    assert!(
+        coverage(&ScanlineRasterizer::new(), points)
+            == coverage(&EdgeFunctionRasterizer::new(), points)
+    );
+}
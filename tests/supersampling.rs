@@ -0,0 +1,14 @@
+//! Supersampling settings — the factor stays within what the engine can
+//! allocate.
+
+use russsty::engine::MAX_SUPERSAMPLING;
+use russsty::Engine;
+
+#[test]
+fn supersampling_factor_is_clamped() {
+    let mut engine = Engine::new(8, 8);
+    engine.set_supersampling(0);
+    assert_eq!(engine.supersampling(), 1);
+    engine.set_supersampling(100);
+    assert_eq!(engine.supersampling(), MAX_SUPERSAMPLING);
+}
@@ -0,0 +1,60 @@
+//! Texture address mode tests — a decal quad whose UVs run exactly 0..1
+//! shows a sliver of the opposite edge under bilinear filtering when the
+//! texture repeats, and none with `AddressMode::ClampToEdge`.
+
+use russsty::prelude::*;
+use russsty::texture::{AddressMode, Texture, TextureFilter};
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+
+/// 4x4: a red left column and a blue right column around green
+fn decal() -> Texture {
+    Texture::from_fn(4, 4, |x, _| match x {
+        0 => 0xFFFF0000,
+        3 => 0xFF0000FF,
+        _ => 0xFF00FF00,
+    })
+    .unwrap()
+}
+
+fn engine_with(address: AddressMode) -> Engine {
+    let mut engine = common::engine_with_obj("decal", common::UV_QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_texture_mode(TextureMode::Replace);
+
+    let mut texture = decal();
+    texture.set_filter(TextureFilter::Bilinear);
+    texture.set_address_mode(address, address);
+    engine.set_texture(texture);
+    engine
+}
+
+/// The leftmost and rightmost covered pixels of the middle row.
+fn row_edges(engine: &mut Engine) -> (u32, u32) {
+    let background = engine.palette().background;
+    let frame = engine.render_offscreen();
+    let row = &frame[(H / 2 * W) as usize..((H / 2 + 1) * W) as usize];
+    let mut covered = row.iter().copied().filter(|&p| p != background);
+    let first = covered.next().expect("decal drawn");
+    (first, covered.next_back().unwrap_or(first))
+}
+
+#[test]
+fn clamped_decal_edges_show_no_wraparound() {
+    let (left, right) = row_edges(&mut engine_with(AddressMode::ClampToEdge));
+    // No blue from the right column on the left edge, nor red on the right
+    assert_eq!(left & 0xFF, 0, "{left:08X}");
+    assert_eq!((right >> 16) & 0xFF, 0, "{right:08X}");
+    assert!((left >> 16) & 0xFF > 0x80 && right & 0xFF > 0x80);
+}
+
+#[test]
+fn repeating_decal_edges_bleed() {
+    let (left, right) = row_edges(&mut engine_with(AddressMode::Repeat));
+    assert!(left & 0xFF > 0x40, "{left:08X}");
+    assert!((right >> 16) & 0xFF > 0x40, "{right:08X}");
+}
@@ -0,0 +1,165 @@
+//! Texture atlas tests — meshes mapped onto regions of one shared texture
+//! sample only their own region, however far their UVs run past its edges.
+
+use russsty::prelude::*;
+use russsty::texture::{Texture, TextureRegion};
+
+mod common;
+
+const W: u32 = 96;
+const H: u32 = 96;
+
+const ATLAS_MTL: &str = "\
+newmtl crate
+newmtl metal
+";
+
+// A cube with two materials. Every face's UVs run 0..2, so each repeats
+// twice across its region of the atlas.
+const CUBE_OBJ: &str = "\
+mtllib atlas.mtl
+o cube
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+vt 0.0 0.0
+vt 0.0 2.0
+vt 2.0 2.0
+vt 2.0 0.0
+usemtl crate
+f 1/1 2/2 3/3 4/4
+f 2/1 6/2 7/3 3/4
+usemtl metal
+f 8/1 7/2 6/3 5/4
+f 5/1 6/2 2/3 1/4
+f 4/1 3/2 7/3 8/4
+f 5/1 1/2 4/3 8/4
+";
+
+/// 16x8: a red checker on the left half, a blue one on the right
+fn atlas() -> Texture {
+    Texture::from_fn(16, 8, |x, y| {
+        let dark = (x + y) % 2 == 0;
+        match (x < 8, dark) {
+            (true, false) => 0xFFFF0000,
+            (true, true) => 0xFF800000,
+            (false, false) => 0xFF0000FF,
+            (false, true) => 0xFF000080,
+        }
+    })
+    .unwrap()
+}
+
+fn engine_with_cube() -> Engine {
+    // The OBJ finds its MTL next to it
+    let dir = common::temp_dir();
+    std::fs::write(dir.join("atlas.mtl"), ATLAS_MTL).expect("write temp mtl");
+    let path = dir.join("cube.obj");
+    std::fs::write(&path, CUBE_OBJ).expect("write temp obj");
+
+    let mut engine = Engine::new(W, H);
+    engine.draw_grid = false;
+    engine.backface_culling = false;
+    engine
+        .add_model("cube", path.to_str().unwrap())
+        .expect("load cube");
+    std::fs::remove_dir_all(&dir).ok();
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_texture_mode(TextureMode::Replace);
+    engine.set_picking(true);
+    let model = engine.model_mut("cube").unwrap();
+    model.set_texture(atlas());
+    // Turned so faces of both materials show
+    model.transform_mut().set_rotation(Vec3::new(0.5, 0.6, 0.0));
+    engine
+}
+
+/// Count, per material, the pixels that sampled the wrong half of the
+/// atlas; also returns how many pixels each material covered.
+fn bleeding(engine: &mut Engine) -> [(usize, usize); 2] {
+    let pixels = engine.render_offscreen().to_vec();
+    let mut counts = [(0, 0); 2];
+    for y in 0..H as i32 {
+        for x in 0..W as i32 {
+            let Some(hit) = engine.pick(x, y) else {
+                continue;
+            };
+            let mesh = &engine.models()[hit.model_index].meshes()[hit.mesh_index];
+            let (slot, wrong_channel) = match mesh.material() {
+                Some("crate") => (0, 0x0000FF),
+                Some("metal") => (1, 0xFF0000),
+                other => panic!("unexpected material {other:?}"),
+            };
+            let pixel = pixels[(y as u32 * W + x as u32) as usize];
+            counts[slot].0 += 1;
+            if pixel & wrong_channel != 0 {
+                counts[slot].1 += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn obj_materials_name_their_meshes() {
+    let engine = engine_with_cube();
+    let model = engine.model("cube").unwrap();
+    let materials: Vec<_> = model.meshes().iter().map(|m| m.material()).collect();
+    assert_eq!(materials, [Some("crate"), Some("metal")]);
+}
+
+#[test]
+fn materials_sample_only_their_atlas_region() {
+    for rasterizer in [RasterizerType::Scanline, RasterizerType::EdgeFunction] {
+        let mut engine = engine_with_cube();
+        engine.set_rasterizer(rasterizer);
+        let atlas = atlas();
+        let model = engine.model_mut("cube").unwrap();
+        assert_eq!(
+            model.set_material_region("crate", atlas.region(0, 0, 8, 8)),
+            1
+        );
+        assert_eq!(
+            model.set_material_region("metal", atlas.region(8, 0, 8, 8)),
+            1
+        );
+
+        let [(crate_px, crate_bleed), (metal_px, metal_bleed)] = bleeding(&mut engine);
+        assert!(crate_px > 100 && metal_px > 100, "both materials in view");
+        assert_eq!(crate_bleed, 0, "{rasterizer:?}: crate faces sampled metal");
+        assert_eq!(metal_bleed, 0, "{rasterizer:?}: metal faces sampled crate");
+    }
+}
+
+#[test]
+fn without_regions_uvs_span_the_whole_atlas() {
+    // The cube's UVs cross both halves, so the region is what keeps them apart
+    let mut engine = engine_with_cube();
+    let [(_, crate_bleed), (_, metal_bleed)] = bleeding(&mut engine);
+    assert!(crate_bleed > 0 && metal_bleed > 0);
+
+    // Clearing a region goes back to the whole texture
+    let mut engine = engine_with_cube();
+    let model = engine.model_mut("cube").unwrap();
+    model.set_material_region("crate", TextureRegion::new(0.0, 0.0, 0.5, 1.0));
+    for mesh in model.meshes_mut() {
+        mesh.clear_texture_region();
+    }
+    let [(_, crate_bleed), _] = bleeding(&mut engine);
+    assert!(crate_bleed > 0);
+}
+
+#[test]
+fn unknown_material_sets_no_regions() {
+    let mut engine = engine_with_cube();
+    let model = engine.model_mut("cube").unwrap();
+    assert_eq!(model.set_material_region("wood", TextureRegion::FULL), 0);
+    assert!(model.meshes().iter().all(|m| m.texture_region().is_none()));
+}
@@ -0,0 +1,43 @@
+//! Texture streaming tests — pixels written into the bound texture through
+//! `Engine::texture_mut` show up in the next frame.
+
+use russsty::prelude::*;
+use russsty::texture::Texture;
+
+mod common;
+
+const W: u32 = 32;
+const H: u32 = 32;
+
+fn engine_with_quad() -> Engine {
+    let mut engine = common::engine_with_obj("quad", common::UV_QUAD_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(ShadingMode::None);
+    engine.set_texture_mode(TextureMode::Replace);
+    engine
+}
+
+#[test]
+fn streamed_pixels_show_in_the_next_frame() {
+    let mut engine = engine_with_quad();
+    assert!(engine.texture_mut().is_none());
+    engine.set_texture(Texture::new(2, 2).unwrap());
+    let center = (H / 2 * W + W / 2) as usize;
+    assert_eq!(engine.render_offscreen()[center], 0xFF000000);
+
+    for color in [0xFFFF0000, 0xFF00FF00, 0xFF0000FF] {
+        let texture = engine.texture_mut().unwrap();
+        texture.update_from_argb(&[color; 4]).unwrap();
+        assert_eq!(engine.render_offscreen()[center], color);
+    }
+
+    // Only the top-left texel: the upper-left quarter of the quad
+    engine
+        .texture_mut()
+        .unwrap()
+        .update_region(0, 0, 1, 1, &[0xFFFFFFFF])
+        .unwrap();
+    let frame = engine.render_offscreen();
+    assert_eq!(frame[((H / 2 - 4) * W + W / 2 - 4) as usize], 0xFFFFFFFF);
+    assert_eq!(frame[((H / 2 + 4) * W + W / 2 + 4) as usize], 0xFF0000FF);
+}
@@ -0,0 +1,109 @@
+//! Toon shading tests — diffuse light snaps to a few flat bands, and the
+//! silhouette outline inks the edges of meshes against the background.
+
+use russsty::prelude::*;
+use russsty::SilhouetteOutline;
+use std::collections::HashSet;
+
+mod common;
+
+const W: u32 = 64;
+const H: u32 = 64;
+const INK: u32 = 0xFF102030;
+
+// A shallow trough of four quads with smoothly turning normals, so
+// light falls across it at a continuous range of angles
+const TROUGH_OBJ: &str = "\
+v -1.0 -1.0 0.6
+v -1.0 1.0 0.6
+v -0.5 -1.0 0.2
+v -0.5 1.0 0.2
+v 0.0 -1.0 0.0
+v 0.0 1.0 0.0
+v 0.5 -1.0 0.2
+v 0.5 1.0 0.2
+v 1.0 -1.0 0.6
+v 1.0 1.0 0.6
+vn -1.5 0.0 -1.0
+vn -0.75 0.0 -1.0
+vn 0.0 0.0 -1.0
+vn 0.75 0.0 -1.0
+vn 1.5 0.0 -1.0
+f 1//1 2//1 4//2
+f 1//1 4//2 3//2
+f 3//2 4//2 6//3
+f 3//2 6//3 5//3
+f 5//3 6//3 8//4
+f 5//3 8//4 7//4
+f 7//4 8//4 10//5
+f 7//4 10//5 9//5
+";
+
+fn engine_with_trough() -> Engine {
+    let mut engine = common::engine_with_obj("trough", TROUGH_OBJ, W, H);
+    engine.set_render_mode(RenderMode::Filled);
+    engine
+}
+
+/// The distinct colors across the middle row of the mesh
+fn row_colors(engine: &mut Engine) -> HashSet<u32> {
+    let background = engine.palette().background;
+    let frame = engine.render_offscreen();
+    let row = (H as usize / 2) * W as usize;
+    frame[row..row + W as usize]
+        .iter()
+        .copied()
+        .filter(|&c| c != background && c != INK)
+        .collect()
+}
+
+#[test]
+fn toon_shading_steps_between_bands() {
+    let mut engine = engine_with_trough();
+    engine.set_shading_mode(ShadingMode::Gouraud);
+    let smooth = row_colors(&mut engine);
+
+    for bands in [2, 3] {
+        engine.set_shading_mode(ShadingMode::Toon { bands });
+        let stepped = row_colors(&mut engine);
+        assert!(!stepped.is_empty());
+        assert!(
+            stepped.len() <= bands as usize,
+            "{bands} bands gave {} colors",
+            stepped.len()
+        );
+        assert!(stepped.len() < smooth.len(), "{stepped:x?} vs {smooth:x?}");
+    }
+}
+
+#[test]
+fn silhouette_outline_inks_mesh_edges_only() {
+    let mut engine = engine_with_trough();
+    engine.set_shading_mode(ShadingMode::Toon { bands: 3 });
+    let plain = engine.render_offscreen().to_vec();
+    assert!(!plain.contains(&INK));
+
+    engine.set_silhouette_outline(Some(SilhouetteOutline {
+        color: INK,
+        thickness: 1,
+    }));
+    assert!(engine.silhouette_outline().is_some());
+    let inked = engine.render_offscreen().to_vec();
+    let background = engine.palette().background;
+
+    let ink = inked.iter().filter(|&&c| c == INK).count();
+    assert!(ink > 0);
+    // Ink only lands on the background just outside the mesh; the middle
+    // of the trough and the far corners are untouched
+    let center = (H as usize / 2) * W as usize + W as usize / 2;
+    assert_eq!(inked[center], plain[center]);
+    assert_eq!(inked[0], background);
+    for (i, (&before, &after)) in plain.iter().zip(&inked).enumerate() {
+        if after == INK {
+            assert_eq!(before, background, "ink over the mesh at pixel {i}");
+        }
+    }
+
+    engine.set_silhouette_outline(None);
+    assert_eq!(engine.render_offscreen(), &plain[..]);
+}
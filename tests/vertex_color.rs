@@ -0,0 +1,110 @@
+//! Vertex color tests — baked `v x y z r g b` colors show through every
+//! shading mode under `TextureMode::VertexColor`, and meshes without them
+//! fall back to the palette fill.
+
+use russsty::prelude::*;
+
+mod common;
+
+// A 2x2 quad in the z = 0 plane facing the default camera at z = -5, red
+// on the left edge and blue on the right.
+const COLORED_QUAD_OBJ: &str = "\
+v -1.0 -1.0 0.0 1.0 0.0 0.0
+v -1.0 1.0 0.0 1.0 0.0 0.0
+v 1.0 1.0 0.0 0.0 0.0 1.0
+v 1.0 -1.0 0.0 0.0 0.0 1.0
+f 1 2 3
+f 1 3 4
+";
+
+const PLAIN_QUAD_OBJ: &str = "\
+v -1.0 -1.0 0.0
+v -1.0 1.0 0.0
+v 1.0 1.0 0.0
+v 1.0 -1.0 0.0
+f 1 2 3
+f 1 3 4
+";
+
+// A dark red-to-black ramp far wider than the view, so both the left and
+// right clip planes cut it, lit evenly head-on.
+const CLIPPED_RAMP_OBJ: &str = "\
+v -12.0 -3.0 0.0 0.25 0.0 0.0
+v -12.0 3.0 0.0 0.25 0.0 0.0
+v 12.0 0.0 0.0 0.0 0.0 0.0
+vn 0.0 0.0 -1.0
+f 1//1 2//1 3//1
+";
+
+const W: u32 = 64;
+const H: u32 = 64;
+const FILL: u32 = 0xFF406080;
+
+// Screen points over the left and right thirds of the quad
+const LEFT: (u32, u32) = (W / 2 - 8, H / 2);
+const RIGHT: (u32, u32) = (W / 2 + 8, H / 2);
+
+fn engine_with_quad(obj: &str, shading: ShadingMode) -> Engine {
+    let mut engine = common::engine_with_obj("quad", obj, W, H);
+    engine.palette_mut().fill = FILL;
+
+    engine.set_render_mode(RenderMode::Filled);
+    engine.set_shading_mode(shading);
+    engine.set_texture_mode(TextureMode::VertexColor);
+    engine.update();
+    engine.render();
+    engine
+}
+
+fn rgb(color: u32) -> (u32, u32, u32) {
+    ((color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF)
+}
+
+#[test]
+fn baked_colors_show_in_every_shading_mode() {
+    for shading in [ShadingMode::None, ShadingMode::Flat, ShadingMode::Gouraud] {
+        let engine = engine_with_quad(COLORED_QUAD_OBJ, shading);
+        assert!(engine.model("quad").unwrap().meshes()[0].has_vertex_colors());
+
+        let (lr, lg, lb) = rgb(common::pixel(&engine, LEFT));
+        let (rr, rg, rb) = rgb(common::pixel(&engine, RIGHT));
+        assert!(lr > lb && lg == 0, "{shading:?} left: {lr} {lg} {lb}");
+        assert!(rb > rr && rg == 0, "{shading:?} right: {rr} {rg} {rb}");
+    }
+}
+
+#[test]
+fn unlit_vertex_colors_blend_across_the_face() {
+    let engine = engine_with_quad(COLORED_QUAD_OBJ, ShadingMode::None);
+    let (r, _, b) = rgb(common::pixel(&engine, (W / 2, H / 2)));
+    // Halfway between red and blue
+    assert!(r.abs_diff(b) <= 16, "center: {r} {b}");
+    assert!(r > 96, "center: {r} {b}");
+}
+
+#[test]
+fn meshes_without_colors_use_the_palette_fill() {
+    let engine = engine_with_quad(PLAIN_QUAD_OBJ, ShadingMode::None);
+    assert!(!engine.model("quad").unwrap().meshes()[0].has_vertex_colors());
+    assert_eq!(common::pixel(&engine, LEFT), FILL);
+    assert_eq!(common::pixel(&engine, RIGHT), FILL);
+}
+
+#[test]
+fn clipped_gradients_stay_smooth() {
+    let mut engine = engine_with_quad(CLIPPED_RAMP_OBJ, ShadingMode::Gouraud);
+    engine.backface_culling = false;
+    engine.update();
+    engine.render();
+    assert!(engine.stats().faces_clipped > 0);
+
+    // Colors are carried in floats through clipping and packed once per
+    // pixel, so the ramp falls steadily, one 8-bit step at most per pixel
+    let reds: Vec<u32> = (0..W)
+        .map(|x| rgb(common::pixel(&engine, (x, H / 2))).0)
+        .collect();
+    for pair in reds.windows(2) {
+        assert!(pair[1] <= pair[0] && pair[0] - pair[1] <= 1, "{reds:?}");
+    }
+    assert!(reds[0] - reds[W as usize - 1] >= 8, "{reds:?}");
+}
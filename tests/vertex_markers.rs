@@ -0,0 +1,90 @@
+//! Vertex marker tests — markers are depth-tested against filled faces and
+//! centered on the projected vertex.
+
+use russsty::{Engine, RenderMode};
+
+mod common;
+
+// A 2x2x2 cube centered on the origin. The front face (z = -1) is split
+// along the (-1, 1) to (1, -1) diagonal, away from the back vertex sampled
+// below.
+const CUBE_OBJ: &str = "\
+v -1.0 -1.0 -1.0
+v -1.0 1.0 -1.0
+v 1.0 1.0 -1.0
+v 1.0 -1.0 -1.0
+v -1.0 -1.0 1.0
+v -1.0 1.0 1.0
+v 1.0 1.0 1.0
+v 1.0 -1.0 1.0
+f 1 2 4
+f 2 3 4
+f 8 7 6
+f 8 6 5
+f 4 3 7
+f 4 7 8
+f 5 6 2
+f 5 2 1
+f 2 6 7
+f 2 7 3
+f 5 1 4
+f 5 4 8
+";
+
+const W: u32 = 64;
+const H: u32 = 64;
+const VERTEX: u32 = 0xFFFF00FF;
+
+// Where vertices project with the default camera at z = -5 and a 45° FOV
+const FRONT_VERTEX: (u32, u32) = (51, 12); // (1, 1, -1)
+const BACK_VERTEX: (u32, u32) = (44, 19); // (1, 1, 1)
+
+fn render_cube(mode: RenderMode, marker_size: u32) -> Engine {
+    let mut engine = common::engine_with_obj("cube", CUBE_OBJ, W, H);
+    engine.backface_culling = false;
+    engine.palette_mut().vertex = VERTEX;
+
+    engine.set_render_mode(mode);
+    engine.set_vertex_marker_size(marker_size);
+    engine.update();
+    engine.render();
+    engine
+}
+
+#[test]
+fn back_vertices_are_hidden_by_front_faces() {
+    let filled = render_cube(RenderMode::Filled, 4);
+    let marked = render_cube(RenderMode::FilledWireframeVertices, 4);
+
+    assert_ne!(common::pixel(&marked, BACK_VERTEX), VERTEX);
+    assert_eq!(
+        common::pixel(&marked, BACK_VERTEX),
+        common::pixel(&filled, BACK_VERTEX)
+    );
+}
+
+#[test]
+fn front_vertices_draw_over_their_faces() {
+    let filled = render_cube(RenderMode::Filled, 4);
+    let marked = render_cube(RenderMode::FilledWireframeVertices, 4);
+
+    // Just inside the front face, below-left of the corner vertex
+    let (x, y) = FRONT_VERTEX;
+    assert_ne!(common::pixel(&filled, (x - 1, y + 1)), VERTEX);
+    assert_eq!(common::pixel(&marked, (x - 1, y + 1)), VERTEX);
+}
+
+#[test]
+fn marker_size_is_configurable_and_centered() {
+    // Sample the row above the cube so only the marker can cover it
+    let (x, y) = (FRONT_VERTEX.0, FRONT_VERTEX.1 - 2);
+    let small = render_cube(RenderMode::FilledWireframeVertices, 4);
+    let large = render_cube(RenderMode::FilledWireframeVertices, 8);
+    assert_eq!(large.vertex_marker_size(), 8);
+
+    // A 4px marker spans x-2..=x+1; an 8px one reaches x-4 on the left
+    assert_eq!(common::pixel(&small, (x - 2, y)), VERTEX);
+    assert_ne!(common::pixel(&small, (x - 3, y)), VERTEX);
+    assert_eq!(common::pixel(&large, (x - 4, y)), VERTEX);
+    assert_eq!(common::pixel(&large, (x + 3, y)), VERTEX);
+}